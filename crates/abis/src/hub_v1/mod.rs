@@ -0,0 +1,8 @@
+use alloy_sol_types::sol;
+
+sol!(
+    #[allow(clippy::too_many_arguments)]
+    #[sol(rpc)]
+    HubV1,
+    "src/hub_v1/hub_v1.json"
+);
@@ -1,21 +1,149 @@
+//! Typed bindings for the Circles protocol contracts.
+//!
+//! Every contract family sits behind a cargo feature, all enabled by default:
+//!
+//! | feature          | contracts                                                        |
+//! |------------------|------------------------------------------------------------------|
+//! | `v1`             | `HubV1`, `TokenV1`                                               |
+//! | `migration`      | `Migration`                                                      |
+//! | `hub`            | `HubV2`                                                          |
+//! | `groups`         | `BaseGroup`, `BaseGroupFactory`, `StandardTreasury`, `StandardVault` |
+//! | `erc20-wrappers` | `DemurrageCircles`, `InflationaryCircles`, `LiftERC20`           |
+//! | `invitations`    | `InvitationEscrow`, `InvitationFarm`, `ReferralsModule`          |
+//! | `name-registry`  | `NameRegistry`                                                   |
+//! | `subscriptions`  | `SubscriptionModule`                                             |
+
+#[cfg(feature = "groups")]
 mod base_group;
+#[cfg(feature = "groups")]
 mod base_group_factory;
+#[cfg(feature = "erc20-wrappers")]
 mod demurrage_circles;
+#[cfg(feature = "v1")]
+mod hub_v1;
+#[cfg(feature = "hub")]
 mod hub_v2;
+#[cfg(feature = "erc20-wrappers")]
 mod inflationary_circles;
+#[cfg(feature = "invitations")]
 mod invitation_escrow;
+#[cfg(feature = "invitations")]
 mod invitation_farm;
+#[cfg(feature = "erc20-wrappers")]
 mod lift_erc20;
+#[cfg(feature = "migration")]
+mod migration;
+#[cfg(feature = "name-registry")]
 mod name_registry;
+#[cfg(feature = "invitations")]
 mod referrals_module;
+#[cfg(feature = "groups")]
+mod standard_treasury;
+#[cfg(feature = "groups")]
+mod standard_vault;
+#[cfg(feature = "subscriptions")]
+mod subscription_module;
+#[cfg(feature = "v1")]
+mod token_v1;
 
+#[cfg(feature = "groups")]
 pub use base_group::BaseGroup;
+#[cfg(feature = "groups")]
 pub use base_group_factory::BaseGroupFactory;
+#[cfg(feature = "erc20-wrappers")]
 pub use demurrage_circles::DemurrageCircles;
+#[cfg(feature = "v1")]
+pub use hub_v1::HubV1;
+#[cfg(feature = "hub")]
 pub use hub_v2::HubV2;
+#[cfg(feature = "erc20-wrappers")]
 pub use inflationary_circles::InflationaryCircles;
+#[cfg(feature = "invitations")]
 pub use invitation_escrow::InvitationEscrow;
+#[cfg(feature = "invitations")]
 pub use invitation_farm::InvitationFarm;
+#[cfg(feature = "erc20-wrappers")]
 pub use lift_erc20::LiftERC20;
+#[cfg(feature = "migration")]
+pub use migration::Migration;
+#[cfg(feature = "name-registry")]
 pub use name_registry::NameRegistry;
+#[cfg(feature = "invitations")]
 pub use referrals_module::ReferralsModule;
+#[cfg(feature = "groups")]
+pub use standard_treasury::StandardTreasury;
+#[cfg(feature = "groups")]
+pub use standard_vault::StandardVault;
+#[cfg(feature = "subscriptions")]
+pub use subscription_module::SubscriptionModule;
+#[cfg(feature = "v1")]
+pub use token_v1::TokenV1;
+
+/// Raw JSON ABI of every enabled contract, keyed by contract name.
+///
+/// The typed bindings above cover calls and events; these are for runtime decoding of
+/// arbitrary calldata, e.g. with `alloy-dyn-abi`.
+pub const JSON_ABIS: &[(&str, &str)] = &[
+    #[cfg(feature = "groups")]
+    ("BaseGroup", include_str!("base_group/base_group.json")),
+    #[cfg(feature = "groups")]
+    (
+        "BaseGroupFactory",
+        include_str!("base_group_factory/base_group_factory.json"),
+    ),
+    #[cfg(feature = "erc20-wrappers")]
+    (
+        "DemurrageCircles",
+        include_str!("demurrage_circles/demurrage_circles.json"),
+    ),
+    #[cfg(feature = "v1")]
+    ("HubV1", include_str!("hub_v1/hub_v1.json")),
+    #[cfg(feature = "hub")]
+    ("HubV2", include_str!("hub_v2/hub_v2.json")),
+    #[cfg(feature = "erc20-wrappers")]
+    (
+        "InflationaryCircles",
+        include_str!("inflationary_circles/inflationary_circles.json"),
+    ),
+    #[cfg(feature = "invitations")]
+    (
+        "InvitationEscrow",
+        include_str!("invitation_escrow/invitation_escrow.json"),
+    ),
+    #[cfg(feature = "invitations")]
+    (
+        "InvitationFarm",
+        include_str!("invitation_farm/invitation_farm.json"),
+    ),
+    #[cfg(feature = "erc20-wrappers")]
+    ("LiftERC20", include_str!("lift_erc20/lift_erc20.json")),
+    #[cfg(feature = "migration")]
+    ("Migration", include_str!("migration/migration.json")),
+    #[cfg(feature = "name-registry")]
+    (
+        "NameRegistry",
+        include_str!("name_registry/name_registry.json"),
+    ),
+    #[cfg(feature = "invitations")]
+    (
+        "ReferralsModule",
+        include_str!("referrals_module/referrals_module.json"),
+    ),
+    #[cfg(feature = "groups")]
+    (
+        "StandardTreasury",
+        include_str!("standard_treasury/standard_treasury.json"),
+    ),
+    #[cfg(feature = "groups")]
+    (
+        "StandardVault",
+        include_str!("standard_vault/standard_vault.json"),
+    ),
+    #[cfg(feature = "subscriptions")]
+    (
+        "SubscriptionModule",
+        include_str!("subscription_module/subscription_module.json"),
+    ),
+    #[cfg(feature = "v1")]
+    ("TokenV1", include_str!("token_v1/token_v1.json")),
+];
@@ -0,0 +1,8 @@
+use alloy_sol_types::sol;
+
+sol!(
+    #[allow(clippy::too_many_arguments)]
+    #[sol(rpc)]
+    Migration,
+    "src/migration/migration.json"
+);
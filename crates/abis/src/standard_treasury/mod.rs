@@ -0,0 +1,8 @@
+use alloy_sol_types::sol;
+
+sol!(
+    #[allow(clippy::too_many_arguments)]
+    #[sol(rpc)]
+    StandardTreasury,
+    "src/standard_treasury/standard_treasury.json"
+);
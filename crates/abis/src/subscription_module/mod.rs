@@ -0,0 +1,7 @@
+use alloy_sol_types::sol;
+
+sol!(
+    #[sol(rpc)]
+    SubscriptionModule,
+    "src/subscription_module/subscription_module.json"
+);
@@ -0,0 +1,8 @@
+use alloy_sol_types::sol;
+
+sol!(
+    #[allow(clippy::too_many_arguments)]
+    #[sol(rpc)]
+    TokenV1,
+    "src/token_v1/token_v1.json"
+);
@@ -0,0 +1,19 @@
+use circles_rpc::CirclesRpcError;
+use circles_sdk::SdkError;
+
+/// Errors from the local store or from ingesting events.
+#[derive(Debug, thiserror::Error)]
+pub enum IndexerError {
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+    #[error(transparent)]
+    Rpc(#[from] CirclesRpcError),
+    /// Event decoding or subscription failures reported by the SDK.
+    #[error(transparent)]
+    Sdk(#[from] SdkError),
+    /// The database was created by a newer version of this crate.
+    #[error("database schema version {found} is newer than the supported version {supported}")]
+    SchemaVersion { found: i64, supported: i64 },
+}
+
+pub type Result<T> = std::result::Result<T, IndexerError>;
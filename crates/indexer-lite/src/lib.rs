@@ -0,0 +1,24 @@
+//! Embedded SQLite index of Circles state for apps that must work offline.
+//!
+//! [`Store`] keeps avatars, trust edges, transfers, and per-token balances in a local
+//! SQLite file, folded from [`CirclesEvent`](circles_types::CirclesEvent)s by the SDK's
+//! [`Projector`](circles_sdk::Projector). Every batch is written in one transaction together
+//! with the highest block it covered, so a restarted app resumes from
+//! [`Store::last_block`] instead of re-ingesting history.
+//!
+//! [`Indexer`] drives ingestion: [`Indexer::catch_up`] backfills over HTTP with
+//! `circles_events` up to the RPC indexer's head, and with the `ws` feature
+//! `Indexer::follow` keeps the store current from a live subscription.
+//!
+//! Writes are idempotent (transfers are keyed by their log position, trust keeps the
+//! newest change), so replaying a range that was partially applied is harmless.
+
+mod error;
+mod schema;
+mod store;
+mod sync;
+
+pub use error::IndexerError;
+pub use schema::SCHEMA_VERSION;
+pub use store::{AvatarRow, BalanceRow, Store, TransferRow, TrustRow};
+pub use sync::Indexer;
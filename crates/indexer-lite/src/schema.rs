@@ -0,0 +1,82 @@
+//! Table layout and migrations, tracked with SQLite's `user_version` pragma.
+//!
+//! Addresses are stored as lowercase `0x` hex and amounts as decimal strings, since SQLite
+//! integers stop at 64 bits.
+
+use crate::error::{IndexerError, Result};
+use rusqlite::Connection;
+
+/// Schema version written to `PRAGMA user_version`.
+pub const SCHEMA_VERSION: i64 = 1;
+
+const SCHEMA_V1: &str = "
+CREATE TABLE avatars (
+    address      TEXT PRIMARY KEY,
+    avatar_type  TEXT NOT NULL,
+    inviter      TEXT,
+    name         TEXT,
+    block_number INTEGER NOT NULL,
+    timestamp    INTEGER,
+    stopped      INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE trust (
+    truster      TEXT NOT NULL,
+    trustee      TEXT NOT NULL,
+    expiry_time  INTEGER NOT NULL,
+    block_number INTEGER NOT NULL,
+    timestamp    INTEGER,
+    PRIMARY KEY (truster, trustee)
+);
+CREATE INDEX trust_by_trustee ON trust (trustee);
+
+CREATE TABLE transfers (
+    block_number      INTEGER NOT NULL,
+    transaction_index INTEGER NOT NULL,
+    log_index         INTEGER NOT NULL,
+    batch_index       INTEGER NOT NULL,
+    transaction_hash  TEXT,
+    timestamp         INTEGER,
+    token             TEXT NOT NULL,
+    sender            TEXT NOT NULL,
+    receiver          TEXT NOT NULL,
+    amount            TEXT NOT NULL,
+    wrapped           INTEGER NOT NULL,
+    PRIMARY KEY (block_number, transaction_index, log_index, batch_index)
+);
+CREATE INDEX transfers_by_sender ON transfers (sender, block_number);
+CREATE INDEX transfers_by_receiver ON transfers (receiver, block_number);
+
+CREATE TABLE balances (
+    account    TEXT NOT NULL,
+    token      TEXT NOT NULL,
+    amount     TEXT NOT NULL,
+    wrapped    INTEGER NOT NULL,
+    updated_at INTEGER,
+    PRIMARY KEY (account, token)
+);
+
+CREATE TABLE sync_state (
+    id         INTEGER PRIMARY KEY CHECK (id = 0),
+    last_block INTEGER NOT NULL
+);
+";
+
+/// Bring `conn` up to [`SCHEMA_VERSION`].
+pub(crate) fn migrate(conn: &mut Connection) -> Result<()> {
+    let found: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if found > SCHEMA_VERSION {
+        return Err(IndexerError::SchemaVersion {
+            found,
+            supported: SCHEMA_VERSION,
+        });
+    }
+    if found == SCHEMA_VERSION {
+        return Ok(());
+    }
+    let tx = conn.transaction()?;
+    tx.execute_batch(SCHEMA_V1)?;
+    tx.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+    tx.commit()?;
+    Ok(())
+}
@@ -0,0 +1,566 @@
+//! The SQLite store and its typed queries.
+
+use crate::error::Result;
+use crate::schema;
+use alloy_primitives::{Address, B256, TxHash, U256};
+use circles_sdk::{AvatarRegistration, Projection, Projector, TokenTransfer, TrustChange};
+use circles_types::{AvatarType, CirclesEvent};
+use circles_utils::converter::project_balance;
+use rusqlite::types::Type;
+use rusqlite::{Connection, OptionalExtension, Row, Transaction, params};
+use std::path::Path;
+use std::str::FromStr;
+
+/// A registered avatar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AvatarRow {
+    pub address: Address,
+    pub avatar_type: AvatarType,
+    pub inviter: Option<Address>,
+    pub name: Option<String>,
+    pub block_number: u64,
+    pub timestamp: Option<u64>,
+    /// The human stopped minting personal tokens.
+    pub stopped: bool,
+}
+
+/// The latest trust change between two avatars.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustRow {
+    pub truster: Address,
+    pub trustee: Address,
+    pub expiry_time: u64,
+    pub block_number: u64,
+}
+
+/// One token movement; batch transfers produce one row per token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferRow {
+    pub block_number: u64,
+    pub transaction_index: u32,
+    pub log_index: u32,
+    /// Position within a `TransferBatch`, `0` for single transfers.
+    pub batch_index: u32,
+    pub transaction_hash: Option<TxHash>,
+    pub timestamp: Option<u64>,
+    /// Avatar address for hub tokens, wrapper address for ERC20 transfers.
+    pub token: Address,
+    pub from: Address,
+    pub to: Address,
+    pub amount: U256,
+    pub wrapped: bool,
+}
+
+/// Balance of one token held by an account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceRow {
+    pub token: Address,
+    /// Hub balances are demurraged to the queried time; wrapper balances are raw sums.
+    pub amount: U256,
+    pub wrapped: bool,
+}
+
+/// Local index backed by one SQLite database.
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    /// Open (or create) the database at `path` and migrate it to the current schema.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        // WAL lets readers on other connections proceed while a batch is being written.
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |_| Ok(()))?;
+        Self::from_connection(conn)
+    }
+
+    /// Throwaway database, mostly for tests.
+    pub fn open_in_memory() -> Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(mut conn: Connection) -> Result<Self> {
+        schema::migrate(&mut conn)?;
+        Ok(Self { conn })
+    }
+
+    /// Highest block whose events have been applied; ingestion resumes from here.
+    pub fn last_block(&self) -> Result<Option<u64>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT last_block FROM sync_state WHERE id = 0",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    /// Fold `events` into the tables in a single transaction.
+    ///
+    /// Nothing is written when an event is malformed. Events may repeat ones applied
+    /// earlier; those are skipped rather than counted twice.
+    pub fn apply(&mut self, events: &[CirclesEvent]) -> Result<()> {
+        let mut projector = Projector::new(Changes::default());
+        projector.apply_all(events)?;
+        let changes = projector.into_inner();
+
+        let tx = self.conn.transaction()?;
+        changes.write(&tx)?;
+        if let Some(block) = events.iter().map(|event| event.base.block_number).max() {
+            advance(&tx, block)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Record that every event up to `block` has been applied, e.g. after an empty range.
+    pub(crate) fn mark_synced(&mut self, block: u64) -> Result<()> {
+        advance(&self.conn, block)
+    }
+
+    pub fn avatar(&self, address: Address) -> Result<Option<AvatarRow>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT address, avatar_type, inviter, name, block_number, timestamp, stopped
+                 FROM avatars WHERE address = ?1",
+                params![hex(&address)],
+                avatar_row,
+            )
+            .optional()?)
+    }
+
+    /// Avatars `truster` trusts as of the unix time `at`.
+    pub fn trusts_of(&self, truster: Address, at: u64) -> Result<Vec<TrustRow>> {
+        self.trust_rows("truster", truster, at)
+    }
+
+    /// Avatars that trust `trustee` as of the unix time `at`.
+    pub fn trusted_by(&self, trustee: Address, at: u64) -> Result<Vec<TrustRow>> {
+        self.trust_rows("trustee", trustee, at)
+    }
+
+    fn trust_rows(&self, column: &str, avatar: Address, at: u64) -> Result<Vec<TrustRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT truster, trustee, expiry_time, block_number FROM trust
+             WHERE {column} = ?1 AND expiry_time > ?2 ORDER BY block_number"
+        ))?;
+        let rows = stmt.query_map(params![hex(&avatar), clamp(at)], |row| {
+            Ok(TrustRow {
+                truster: text(row, 0)?,
+                trustee: text(row, 1)?,
+                expiry_time: row.get(2)?,
+                block_number: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Non-zero balances of `account`, with demurrage applied up to the unix time `at`.
+    pub fn balances_of(&self, account: Address, at: u64) -> Result<Vec<BalanceRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT token, amount, wrapped, updated_at FROM balances
+             WHERE account = ?1 AND amount != '0' ORDER BY token",
+        )?;
+        let rows = stmt.query_map(params![hex(&account)], |row| {
+            let amount: U256 = text(row, 1)?;
+            let wrapped: bool = row.get(2)?;
+            let updated_at: Option<u64> = row.get(3)?;
+            Ok(BalanceRow {
+                token: text(row, 0)?,
+                amount: match updated_at {
+                    Some(from) if !wrapped => project_balance(amount, from, at),
+                    _ => amount,
+                },
+                wrapped,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Up to `limit` transfers sent or received by `account`, newest first.
+    pub fn transfers_of(&self, account: Address, limit: usize) -> Result<Vec<TransferRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT block_number, transaction_index, log_index, batch_index, transaction_hash,
+                    timestamp, token, sender, receiver, amount, wrapped
+             FROM transfers WHERE sender = ?1 OR receiver = ?1
+             ORDER BY block_number DESC, transaction_index DESC, log_index DESC, batch_index DESC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![hex(&account), clamp(limit as u64)], |row| {
+            Ok(TransferRow {
+                block_number: row.get(0)?,
+                transaction_index: row.get(1)?,
+                log_index: row.get(2)?,
+                batch_index: row.get(3)?,
+                transaction_hash: optional_text(row, 4)?,
+                timestamp: row.get(5)?,
+                token: text(row, 6)?,
+                from: text(row, 7)?,
+                to: text(row, 8)?,
+                amount: text(row, 9)?,
+                wrapped: row.get(10)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+}
+
+/// Where an event sits in the chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Position {
+    block_number: u64,
+    transaction_index: u32,
+    log_index: u32,
+    timestamp: Option<u64>,
+    transaction_hash: Option<TxHash>,
+}
+
+impl Position {
+    fn of(event: &CirclesEvent) -> Self {
+        Self {
+            block_number: event.base.block_number,
+            transaction_index: event.base.transaction_index,
+            log_index: event.base.log_index,
+            timestamp: event.base.timestamp,
+            transaction_hash: event.base.transaction_hash,
+        }
+    }
+}
+
+/// Changes collected from one batch of events before they are written.
+#[derive(Default)]
+struct Changes {
+    avatars: Vec<(Position, AvatarRegistration)>,
+    stopped: Vec<Address>,
+    trust: Vec<(Position, TrustChange)>,
+    transfers: Vec<(Position, u32, TokenTransfer)>,
+}
+
+impl Projection for Changes {
+    fn on_transfer(&mut self, event: &CirclesEvent, transfer: &TokenTransfer) {
+        let position = Position::of(event);
+        let batch_index = match self.transfers.last() {
+            Some((last, index, _)) if *last == position => index + 1,
+            _ => 0,
+        };
+        self.transfers
+            .push((position, batch_index, transfer.clone()));
+    }
+
+    fn on_trust(&mut self, event: &CirclesEvent, change: &TrustChange) {
+        self.trust.push((Position::of(event), change.clone()));
+    }
+
+    fn on_avatar_registered(&mut self, event: &CirclesEvent, registration: &AvatarRegistration) {
+        self.avatars
+            .push((Position::of(event), registration.clone()));
+    }
+
+    fn on_stopped(&mut self, _event: &CirclesEvent, avatar: Address) {
+        self.stopped.push(avatar);
+    }
+}
+
+impl Changes {
+    fn write(&self, tx: &Transaction<'_>) -> Result<()> {
+        for (at, registration) in &self.avatars {
+            tx.execute(
+                "INSERT OR IGNORE INTO avatars
+                 (address, avatar_type, inviter, name, block_number, timestamp)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    hex(&registration.avatar),
+                    avatar_type_name(&registration.avatar_type),
+                    registration.inviter.as_ref().map(hex),
+                    registration.name,
+                    at.block_number,
+                    at.timestamp,
+                ],
+            )?;
+        }
+        for avatar in &self.stopped {
+            tx.execute(
+                "UPDATE avatars SET stopped = 1 WHERE address = ?1",
+                params![hex(avatar)],
+            )?;
+        }
+        for (at, change) in &self.trust {
+            // Replays of older ranges must not overwrite a newer expiry.
+            tx.execute(
+                "INSERT INTO trust (truster, trustee, expiry_time, block_number, timestamp)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT (truster, trustee) DO UPDATE SET
+                     expiry_time = excluded.expiry_time,
+                     block_number = excluded.block_number,
+                     timestamp = excluded.timestamp
+                 WHERE excluded.block_number >= trust.block_number",
+                params![
+                    hex(&change.truster),
+                    hex(&change.trustee),
+                    clamp(change.expiry_time),
+                    at.block_number,
+                    at.timestamp,
+                ],
+            )?;
+        }
+        for (at, batch_index, transfer) in &self.transfers {
+            let token = token_address(transfer.token_id);
+            let inserted = tx.execute(
+                "INSERT OR IGNORE INTO transfers
+                 (block_number, transaction_index, log_index, batch_index, transaction_hash,
+                  timestamp, token, sender, receiver, amount, wrapped)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    at.block_number,
+                    at.transaction_index,
+                    at.log_index,
+                    batch_index,
+                    at.transaction_hash.as_ref().map(ToString::to_string),
+                    at.timestamp,
+                    hex(&token),
+                    hex(&transfer.from),
+                    hex(&transfer.to),
+                    transfer.amount.to_string(),
+                    transfer.wrapped,
+                ],
+            )?;
+            if inserted == 0 {
+                continue;
+            }
+            if !transfer.from.is_zero() {
+                adjust_balance(tx, transfer.from, token, transfer, at.timestamp, false)?;
+            }
+            if !transfer.to.is_zero() {
+                adjust_balance(tx, transfer.to, token, transfer, at.timestamp, true)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Move the resume cursor forward; it never goes back.
+fn advance(conn: &Connection, block: u64) -> Result<()> {
+    conn.execute(
+        "INSERT INTO sync_state (id, last_block) VALUES (0, ?1)
+         ON CONFLICT (id) DO UPDATE SET last_block = max(last_block, excluded.last_block)",
+        params![block],
+    )?;
+    Ok(())
+}
+
+/// Add or subtract a transfer, first demurraging the stored hub balance to the transfer's day
+/// the way the hub does on every balance update.
+fn adjust_balance(
+    tx: &Transaction<'_>,
+    account: Address,
+    token: Address,
+    transfer: &TokenTransfer,
+    at: Option<u64>,
+    credit: bool,
+) -> Result<()> {
+    let stored: Option<(U256, Option<u64>)> = tx
+        .query_row(
+            "SELECT amount, updated_at FROM balances WHERE account = ?1 AND token = ?2",
+            params![hex(&account), hex(&token)],
+            |row| Ok((text(row, 0)?, row.get(1)?)),
+        )
+        .optional()?;
+    let (current, updated_at) = match stored {
+        Some((amount, Some(from))) => match at {
+            Some(to) if !transfer.wrapped => (project_balance(amount, from, to), Some(to)),
+            _ => (amount, Some(from)),
+        },
+        Some((amount, None)) => (amount, at),
+        None => (U256::ZERO, at),
+    };
+    let amount = if credit {
+        current.saturating_add(transfer.amount)
+    } else {
+        current.saturating_sub(transfer.amount)
+    };
+    tx.execute(
+        "INSERT INTO balances (account, token, amount, wrapped, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT (account, token) DO UPDATE SET
+             amount = excluded.amount,
+             updated_at = excluded.updated_at",
+        params![
+            hex(&account),
+            hex(&token),
+            amount.to_string(),
+            transfer.wrapped,
+            updated_at,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Hub token ids and left-padded wrapper addresses both keep the address in the low 20 bytes.
+fn token_address(token_id: U256) -> Address {
+    Address::from_word(B256::from(token_id))
+}
+
+fn hex(address: &Address) -> String {
+    format!("{address:#x}")
+}
+
+/// SQLite integers are signed 64-bit; unlimited trust expiries saturate.
+fn clamp(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+fn avatar_type_name(avatar_type: &AvatarType) -> &'static str {
+    match avatar_type {
+        AvatarType::CrcV2RegisterHuman => "CrcV2_RegisterHuman",
+        AvatarType::CrcV2RegisterGroup => "CrcV2_RegisterGroup",
+        AvatarType::CrcV2RegisterOrganization => "CrcV2_RegisterOrganization",
+        AvatarType::CrcV1Signup => "CrcV1_Signup",
+        AvatarType::CrcV1OrganizationSignup => "CrcV1_OrganizationSignup",
+    }
+}
+
+fn avatar_row(row: &Row<'_>) -> rusqlite::Result<AvatarRow> {
+    let avatar_type: String = row.get(1)?;
+    Ok(AvatarRow {
+        address: text(row, 0)?,
+        avatar_type: serde_json::from_value(avatar_type.clone().into())
+            .map_err(|_| unparsable(1, avatar_type))?,
+        inviter: optional_text(row, 2)?,
+        name: row.get(3)?,
+        block_number: row.get(4)?,
+        timestamp: row.get(5)?,
+        stopped: row.get(6)?,
+    })
+}
+
+fn text<T: FromStr>(row: &Row<'_>, idx: usize) -> rusqlite::Result<T> {
+    let raw: String = row.get(idx)?;
+    raw.parse().map_err(|_| unparsable(idx, raw))
+}
+
+fn optional_text<T: FromStr>(row: &Row<'_>, idx: usize) -> rusqlite::Result<Option<T>> {
+    let raw: Option<String> = row.get(idx)?;
+    raw.map(|raw| raw.parse().map_err(|_| unparsable(idx, raw)))
+        .transpose()
+}
+
+fn unparsable(idx: usize, raw: String) -> rusqlite::Error {
+    rusqlite::Error::FromSqlConversionFailure(
+        idx,
+        Type::Text,
+        format!("unparsable value `{raw}`").into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+    use circles_rpc_mock::fixtures::circles_event;
+    use circles_types::CirclesEventType;
+    use serde_json::{Value, json};
+
+    const ALICE: Address = address!("1000000000000000000000000000000000000001");
+    const BOB: Address = address!("2000000000000000000000000000000000000002");
+    /// 2024-10-15T00:00:00Z, well past the Circles epoch.
+    const DAY: u64 = 1_728_950_400;
+
+    fn event(
+        block: u64,
+        log_index: u32,
+        event_type: CirclesEventType,
+        data: Value,
+    ) -> CirclesEvent {
+        let mut event = circles_event(event_type, data);
+        event.base.block_number = block;
+        event.base.log_index = log_index;
+        event.base.timestamp = Some(DAY);
+        event
+    }
+
+    fn history() -> Vec<CirclesEvent> {
+        vec![
+            event(
+                1,
+                0,
+                CirclesEventType::CrcV2RegisterHuman,
+                json!({ "avatar": ALICE.to_string() }),
+            ),
+            event(
+                1,
+                1,
+                CirclesEventType::CrcV2TransferSingle,
+                json!({
+                    "operator": ALICE.to_string(),
+                    "from": Address::ZERO.to_string(),
+                    "to": ALICE.to_string(),
+                    "id": U256::from_be_slice(ALICE.as_slice()).to_string(),
+                    "value": "100",
+                }),
+            ),
+            event(
+                2,
+                0,
+                CirclesEventType::CrcV2Trust,
+                json!({
+                    "truster": BOB.to_string(),
+                    "trustee": ALICE.to_string(),
+                    "expiryTime": U256::MAX.to_string(),
+                }),
+            ),
+            event(
+                2,
+                1,
+                CirclesEventType::CrcV2TransferSingle,
+                json!({
+                    "operator": ALICE.to_string(),
+                    "from": ALICE.to_string(),
+                    "to": BOB.to_string(),
+                    "id": U256::from_be_slice(ALICE.as_slice()).to_string(),
+                    "value": "40",
+                }),
+            ),
+        ]
+    }
+
+    #[test]
+    fn folds_events_into_typed_rows() {
+        let mut store = Store::open_in_memory().unwrap();
+        assert_eq!(store.last_block().unwrap(), None);
+        store.apply(&history()).unwrap();
+
+        assert_eq!(store.last_block().unwrap(), Some(2));
+        let alice = store.avatar(ALICE).unwrap().unwrap();
+        assert_eq!(alice.avatar_type, AvatarType::CrcV2RegisterHuman);
+        assert_eq!(store.trusted_by(ALICE, DAY).unwrap()[0].truster, BOB);
+        assert!(store.trusts_of(ALICE, DAY).unwrap().is_empty());
+
+        let balances = store.balances_of(BOB, DAY).unwrap();
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[0].token, ALICE);
+        assert_eq!(balances[0].amount, U256::from(40u64));
+        assert_eq!(
+            store.balances_of(ALICE, DAY).unwrap()[0].amount,
+            U256::from(60u64)
+        );
+
+        let transfers = store.transfers_of(ALICE, 10).unwrap();
+        assert_eq!(transfers.len(), 2);
+        assert_eq!(transfers[0].to, BOB);
+    }
+
+    #[test]
+    fn replayed_events_are_not_counted_twice() {
+        let mut store = Store::open_in_memory().unwrap();
+        store.apply(&history()).unwrap();
+        store.apply(&history()[1..]).unwrap();
+
+        assert_eq!(store.transfers_of(BOB, 10).unwrap().len(), 1);
+        assert_eq!(
+            store.balances_of(BOB, DAY).unwrap()[0].amount,
+            U256::from(40u64)
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Ingestion from the Circles RPC into a [`Store`].
+
+use crate::error::Result;
+use crate::store::Store;
+use circles_rpc::CirclesRpc;
+use futures::StreamExt;
+
+/// Events written per SQLite transaction during a backfill.
+const DEFAULT_BATCH_SIZE: usize = 500;
+
+/// Keeps a [`Store`] in sync with a Circles RPC endpoint.
+///
+/// Ingestion resumes at [`Store::last_block`] (inclusive, since a block may have been cut
+/// short by a crash), falling back to the start block for an empty store.
+pub struct Indexer {
+    rpc: CirclesRpc,
+    store: Store,
+    start_block: u64,
+    batch_size: usize,
+}
+
+impl Indexer {
+    pub fn new(rpc: CirclesRpc, store: Store) -> Self {
+        Self {
+            rpc,
+            store,
+            start_block: 0,
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// First block to ingest into an empty store, e.g. the hub deployment block.
+    pub fn with_start_block(mut self, start_block: u64) -> Self {
+        self.start_block = start_block;
+        self
+    }
+
+    /// Events per write transaction; larger batches backfill faster but hold the write
+    /// lock longer.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    pub fn into_store(self) -> Store {
+        self.store
+    }
+
+    /// Backfill up to the block the RPC's indexer has reached and return the store's
+    /// cursor afterwards.
+    pub async fn catch_up(&mut self) -> Result<Option<u64>> {
+        match self.rpc.health().indexed_block_number().await? {
+            Some(head) => self.catch_up_to(head).await,
+            None => self.store.last_block(),
+        }
+    }
+
+    /// Backfill every event up to and including `to_block`.
+    pub async fn catch_up_to(&mut self, to_block: u64) -> Result<Option<u64>> {
+        let from_block = self.resume_block()?;
+        if from_block > to_block {
+            return self.store.last_block();
+        }
+        let mut events = self
+            .rpc
+            .events()
+            .iter_events(None, from_block, to_block, None);
+        let mut batch = Vec::with_capacity(self.batch_size);
+        while let Some(event) = events.next().await {
+            batch.push(event?);
+            if batch.len() >= self.batch_size {
+                self.store.apply(&batch)?;
+                batch.clear();
+            }
+        }
+        self.store.apply(&batch)?;
+        self.store.mark_synced(to_block)?;
+        self.store.last_block()
+    }
+
+    /// Catch up over HTTP, then apply live events from `ws_url` until the subscription
+    /// ends.
+    ///
+    /// Returns the error that ended the subscription; call again to reconnect, which
+    /// backfills whatever was missed in between.
+    #[cfg(feature = "ws")]
+    pub async fn follow(&mut self, ws_url: &str) -> Result<()> {
+        // Subscribe before the backfill so no event falls between the two.
+        let mut sub =
+            circles_sdk::ws::subscribe_with_retries(ws_url, serde_json::json!({}), None).await?;
+        self.catch_up().await?;
+        while let Some(event) = sub.next().await {
+            self.store.apply(&[event?])?;
+        }
+        Err(circles_rpc::CirclesRpcError::SubscriptionClosed.into())
+    }
+
+    fn resume_block(&self) -> Result<u64> {
+        Ok(self.store.last_block()?.unwrap_or(self.start_block))
+    }
+}
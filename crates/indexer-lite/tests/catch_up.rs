@@ -0,0 +1,60 @@
+use alloy_primitives::{Address, address};
+use circles_indexer_lite::{Indexer, Store};
+use circles_rpc::CirclesRpc;
+use circles_rpc_mock::{MockRpcServer, fixtures};
+use serde_json::json;
+
+const ALICE: Address = address!("1000000000000000000000000000000000000001");
+const BOB: Address = address!("2000000000000000000000000000000000000002");
+
+fn trust(block: u64) -> serde_json::Value {
+    fixtures::event(
+        "CrcV2_Trust",
+        json!({
+            "truster": BOB.to_string(),
+            "trustee": ALICE.to_string(),
+            "expiryTime": "1800000000",
+            "blockNumber": block,
+            "timestamp": 1_700_000_000u64,
+            "transactionIndex": 0,
+            "logIndex": 0,
+        }),
+    )
+}
+
+#[tokio::test]
+async fn catch_up_resumes_from_the_stored_cursor() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::temp_dir().join(format!("circles-indexer-lite-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let server = MockRpcServer::builder()
+        .respond(
+            "circles_query",
+            fixtures::query_result(&["blockNumber"], vec![vec![120u64.into()]]),
+        )
+        .respond("circles_events", json!([trust(110)]))
+        .start()
+        .await?;
+    let rpc = CirclesRpc::try_from_http(&server.url())?;
+
+    let mut indexer = Indexer::new(rpc.clone(), Store::open(&path)?).with_start_block(100);
+    assert_eq!(indexer.catch_up().await?, Some(120));
+    let first = server.params_for("circles_events");
+    assert_eq!(first[0][1], 100);
+    drop(indexer);
+
+    // A restarted app reopens the file and only asks for blocks from the cursor on.
+    let mut indexer = Indexer::new(rpc, Store::open(&path)?).with_start_block(100);
+    assert_eq!(indexer.store().last_block()?, Some(120));
+    indexer.catch_up().await?;
+    let events = server.params_for("circles_events");
+    assert_eq!(events[first.len()][1], 120);
+
+    let trusted = indexer.store().trusted_by(ALICE, 1_700_000_000)?;
+    assert_eq!(trusted.len(), 1);
+    assert_eq!(trusted[0].truster, BOB);
+
+    drop(indexer);
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
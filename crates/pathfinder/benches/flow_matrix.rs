@@ -0,0 +1,174 @@
+//! Flow-matrix benchmarks over the paths in `benches/fixtures/graph_snapshot.json`.
+//!
+//! Run with `cargo bench -p circles-pathfinder --bench flow_matrix`; compare against a saved
+//! run with `-- --save-baseline main` / `-- --baseline main`. The snapshot's trust and balance
+//! sections are not used yet; they are there so a local pathfinder can be benchmarked on the
+//! same graph the matrix benchmarks use.
+
+use alloy_primitives::Address;
+use alloy_primitives::aliases::U192;
+use circles_pathfinder::{
+    FlowMatrixBuilder, PathData, create_flow_matrix, pack_coordinates, transform_to_flow_vertices,
+    unpack_coordinates,
+};
+use circles_types::TransferStep;
+use criterion::{
+    BatchSize, BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main,
+};
+use serde::Deserialize;
+
+#[allow(dead_code)]
+#[derive(Deserialize)]
+struct Snapshot {
+    avatars: Vec<Address>,
+    trust: Vec<TrustEdge>,
+    balances: Vec<Balance>,
+    paths: Vec<SnapshotPath>,
+}
+
+#[allow(dead_code)]
+#[derive(Deserialize)]
+struct TrustEdge {
+    truster: Address,
+    trustee: Address,
+}
+
+#[allow(dead_code)]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Balance {
+    holder: Address,
+    token_owner: Address,
+    amount: U192,
+}
+
+#[derive(Deserialize)]
+struct SnapshotPath {
+    name: String,
+    from: Address,
+    to: Address,
+    value: U192,
+    transfers: Vec<TransferStep>,
+}
+
+fn snapshot() -> Snapshot {
+    serde_json::from_str(include_str!("fixtures/graph_snapshot.json")).expect("snapshot")
+}
+
+fn bench_create_flow_matrix(c: &mut Criterion) {
+    let snapshot = snapshot();
+    let mut group = c.benchmark_group("create_flow_matrix");
+    for path in &snapshot.paths {
+        group.throughput(Throughput::Elements(path.transfers.len() as u64));
+        group.bench_with_input(BenchmarkId::new("fresh", &path.name), path, |b, path| {
+            b.iter(|| {
+                create_flow_matrix(
+                    black_box(path.from),
+                    black_box(path.to),
+                    path.value,
+                    black_box(&path.transfers),
+                )
+                .expect("balanced path")
+            })
+        });
+        let mut builder = FlowMatrixBuilder::new();
+        group.bench_with_input(
+            BenchmarkId::new("reused_builder", &path.name),
+            path,
+            |b, path| {
+                b.iter(|| {
+                    builder
+                        .build(
+                            black_box(path.from),
+                            black_box(path.to),
+                            path.value,
+                            black_box(&path.transfers),
+                        )
+                        .expect("balanced path")
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_path_data(c: &mut Criterion) {
+    let snapshot = snapshot();
+    let mut group = c.benchmark_group("path_data");
+    for path in &snapshot.paths {
+        group.throughput(Throughput::Elements(path.transfers.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("from_transfers", &path.name),
+            path,
+            |b, path| {
+                b.iter(|| {
+                    PathData::from_transfers(
+                        black_box(&path.transfers),
+                        path.from,
+                        path.to,
+                        path.value,
+                    )
+                    .expect("balanced path")
+                })
+            },
+        );
+        let data = PathData::from_transfers(&path.transfers, path.from, path.to, path.value)
+            .expect("balanced path");
+        group.bench_with_input(
+            BenchmarkId::new("to_contract_params", &path.name),
+            &data,
+            |b, data| b.iter(|| black_box(data).to_contract_params()),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("into_contract_params", &path.name),
+            &data,
+            |b, data| {
+                b.iter_batched(
+                    || data.clone(),
+                    |data| data.into_contract_params(),
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_packing(c: &mut Criterion) {
+    let snapshot = snapshot();
+    let mut group = c.benchmark_group("packing");
+    for path in &snapshot.paths {
+        let matrix = create_flow_matrix(path.from, path.to, path.value, &path.transfers)
+            .expect("balanced path");
+        let coords = unpack_coordinates(&matrix.packed_coordinates);
+        group.throughput(Throughput::Elements(coords.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("pack_coordinates", &path.name),
+            &coords,
+            |b, coords| b.iter(|| pack_coordinates(black_box(coords))),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("unpack_coordinates", &path.name),
+            &matrix.packed_coordinates,
+            |b, packed| b.iter(|| unpack_coordinates(black_box(packed))),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("transform_to_flow_vertices", &path.name),
+            path,
+            |b, path| {
+                b.iter(|| {
+                    transform_to_flow_vertices(black_box(&path.transfers), path.from, path.to)
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_create_flow_matrix,
+    bench_path_data,
+    bench_packing
+);
+criterion_main!(benches);
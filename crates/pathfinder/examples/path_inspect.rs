@@ -0,0 +1,43 @@
+//! Print a flow matrix as tables or Graphviz DOT.
+//!
+//! ```bash
+//! cargo run -p circles-pathfinder --example path_inspect -- matrix.json
+//! cargo run -p circles-pathfinder --example path_inspect -- --dot matrix.json | dot -Tsvg > matrix.svg
+//! ```
+//!
+//! The input is flow-matrix JSON as produced by `serde_json::to_string(&matrix)` or the
+//! TypeScript SDK; pass `-` (or nothing) to read it from stdin.
+use circles_pathfinder::FlowMatrix;
+use std::io::Read;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut dot = false;
+    let mut input = None;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--dot" => dot = true,
+            "-h" | "--help" => {
+                eprintln!("usage: path_inspect [--dot] [matrix.json | -]");
+                return Ok(());
+            }
+            _ => input = Some(arg),
+        }
+    }
+
+    let json = match input.as_deref() {
+        None | Some("-") => {
+            let mut json = String::new();
+            std::io::stdin().read_to_string(&mut json)?;
+            json
+        }
+        Some(path) => std::fs::read_to_string(path)?,
+    };
+    let matrix: FlowMatrix = serde_json::from_str(&json)?;
+
+    if dot {
+        print!("{}", matrix.to_dot());
+    } else {
+        print!("{}", matrix.to_table());
+    }
+    Ok(())
+}
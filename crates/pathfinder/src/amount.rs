@@ -0,0 +1,40 @@
+//! Conversions between RPC amounts (`U256`) and on-chain flow amounts (`uint192`).
+
+use crate::PathfinderError;
+use alloy_primitives::{U256, aliases::U192};
+
+/// Convert a `U256` amount to the hub's `uint192`, failing if it does not fit.
+pub fn u256_to_u192(value: U256) -> Result<U192, PathfinderError> {
+    let limbs = value.into_limbs();
+    if limbs[3] != 0 {
+        return Err(PathfinderError::AmountOverflow { value });
+    }
+    Ok(U192::from_limbs([limbs[0], limbs[1], limbs[2]]))
+}
+
+/// Convert a `U256` amount to `uint192`, clamping to `U192::MAX`.
+///
+/// Only for callers that treat the amount as an upper bound; a clamped transfer amount no
+/// longer matches what was requested. Prefer [`u256_to_u192`].
+pub fn u256_to_u192_saturating(value: U256) -> U192 {
+    u256_to_u192(value).unwrap_or(U192::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_values_above_u192_max() {
+        let max = U256::from(U192::MAX);
+        assert_eq!(u256_to_u192(max).unwrap(), U192::MAX);
+
+        let over = max + U256::from(1u64);
+        assert!(matches!(
+            u256_to_u192(over),
+            Err(PathfinderError::AmountOverflow { value }) if value == over
+        ));
+        assert_eq!(u256_to_u192_saturating(over), U192::MAX);
+        assert_eq!(u256_to_u192_saturating(U256::from(7u64)), U192::from(7u64));
+    }
+}
@@ -0,0 +1,671 @@
+//! Path cache keyed by `(from, to, amount, block)`.
+//!
+//! Wallets often quote the same transfer repeatedly while a user edits an amount or
+//! re-opens a send sheet. [`PathCache`] keeps recent [`PathfindingResult`]s so those
+//! quotes skip the `circlesV2_findPath` round trip.
+//!
+//! Entries for a fixed block never go stale. Entries cached without a block ("latest")
+//! are dropped when they exceed the cache's `max_age` or when
+//! [`PathCache::invalidate_for_event`] sees a trust, transfer, mint, or wrap event that
+//! touches any vertex of the cached path.
+//!
+//! Results fetched through the cache carry the indexer block they were computed at, so
+//! callers can also age them in blocks with [`PathFreshness::is_stale`].
+//!
+//! [`MemoryPathStore`] is always available; enable `path-cache-sled` for
+//! `SledPathStore`, which survives restarts. Both hold a bounded number of entries,
+//! and [`PathCache::prune_expired`] deletes "latest" entries past `max_age`.
+
+use crate::PathfinderError;
+use alloy_primitives::{Address, U256};
+use circles_types::{CirclesEvent, CirclesEventType, FindPathParams, PathfindingResult};
+use circles_utils::time::unix_now;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Identifies a cached path.
+///
+/// `request` is the canonical JSON of the full [`FindPathParams`], so requests that
+/// differ only in token filters or simulations never share an entry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PathCacheKey {
+    pub from: Address,
+    pub to: Address,
+    pub amount: U256,
+    /// Block the path was computed at; `None` for the indexer's latest state.
+    pub block: Option<u64>,
+    pub request: String,
+}
+
+impl PathCacheKey {
+    pub fn new(params: &FindPathParams, block: Option<u64>) -> Self {
+        Self {
+            from: params.from,
+            to: params.to,
+            amount: params.target_flow,
+            block,
+            request: serde_json::to_string(params).expect("path params serialize"),
+        }
+    }
+}
+
+/// A cached pathfinding result with the vertices used for invalidation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedPath {
+    pub key: PathCacheKey,
+    pub result: PathfindingResult,
+    /// Every address the path touches (endpoints, hop endpoints, token owners).
+    pub vertices: Vec<Address>,
+    /// Unix seconds at insertion.
+    pub cached_at: u64,
+}
+
+impl CachedPath {
+    pub fn new(key: PathCacheKey, result: PathfindingResult) -> Self {
+        let mut vertices: HashSet<Address> = HashSet::from([key.from, key.to]);
+        for step in &result.transfers {
+            vertices.insert(step.from);
+            vertices.insert(step.to);
+            if let Ok(owner) = step.token_owner.parse() {
+                vertices.insert(owner);
+            }
+        }
+        Self {
+            key,
+            result,
+            vertices: vertices.into_iter().collect(),
+            cached_at: unix_now(),
+        }
+    }
+
+    fn touches(&self, addresses: &HashSet<Address>) -> bool {
+        self.vertices
+            .iter()
+            .any(|vertex| addresses.contains(vertex))
+    }
+}
+
+/// Block-based staleness checks for a [`PathfindingResult`].
+pub trait PathFreshness {
+    /// How many blocks `current_block` is past the block the path was computed at;
+    /// `None` when the result carries no block.
+    fn blocks_behind(&self, current_block: u64) -> Option<u64>;
+
+    /// Whether the path is more than `max_blocks` behind `current_block`. Results without
+    /// a block are always stale, since their age is unknown.
+    fn is_stale(&self, current_block: u64, max_blocks: u64) -> bool {
+        self.blocks_behind(current_block)
+            .is_none_or(|behind| behind > max_blocks)
+    }
+}
+
+impl PathFreshness for PathfindingResult {
+    fn blocks_behind(&self, current_block: u64) -> Option<u64> {
+        self.block_number
+            .map(|block| current_block.saturating_sub(block))
+    }
+}
+
+impl PathFreshness for CachedPath {
+    fn blocks_behind(&self, current_block: u64) -> Option<u64> {
+        self.result.blocks_behind(current_block)
+    }
+}
+
+/// Storage backend for [`PathCache`].
+pub trait PathStore: Send + Sync {
+    fn get(&self, key: &PathCacheKey) -> Result<Option<CachedPath>, PathfinderError>;
+    fn insert(&self, entry: CachedPath) -> Result<(), PathfinderError>;
+    /// Remove every entry matching `predicate`; returns how many were removed.
+    fn remove_where(
+        &self,
+        predicate: &dyn Fn(&CachedPath) -> bool,
+    ) -> Result<usize, PathfinderError>;
+    fn clear(&self) -> Result<(), PathfinderError>;
+}
+
+#[derive(Debug, Default)]
+struct MemoryInner {
+    entries: HashMap<PathCacheKey, CachedPath>,
+    order: VecDeque<PathCacheKey>,
+}
+
+/// In-process store holding at most `capacity` entries, evicting the least recently used.
+#[derive(Debug)]
+pub struct MemoryPathStore {
+    capacity: usize,
+    inner: Mutex<MemoryInner>,
+}
+
+impl MemoryPathStore {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            inner: Mutex::new(MemoryInner::default()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock().expect("lock").entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for MemoryPathStore {
+    fn default() -> Self {
+        Self::new(1024)
+    }
+}
+
+impl PathStore for MemoryPathStore {
+    fn get(&self, key: &PathCacheKey) -> Result<Option<CachedPath>, PathfinderError> {
+        let mut inner = self.inner.lock().expect("lock");
+        let Some(entry) = inner.entries.get(key).cloned() else {
+            return Ok(None);
+        };
+        // Move the key to the back so hits outlive entries nobody reads.
+        inner.order.retain(|k| k != key);
+        inner.order.push_back(key.clone());
+        Ok(Some(entry))
+    }
+
+    fn insert(&self, entry: CachedPath) -> Result<(), PathfinderError> {
+        let mut inner = self.inner.lock().expect("lock");
+        let key = entry.key.clone();
+        if inner.entries.insert(key.clone(), entry).is_some() {
+            inner.order.retain(|k| k != &key);
+        }
+        inner.order.push_back(key);
+        while inner.entries.len() > self.capacity {
+            let Some(oldest) = inner.order.pop_front() else {
+                break;
+            };
+            inner.entries.remove(&oldest);
+        }
+        Ok(())
+    }
+
+    fn remove_where(
+        &self,
+        predicate: &dyn Fn(&CachedPath) -> bool,
+    ) -> Result<usize, PathfinderError> {
+        let mut inner = self.inner.lock().expect("lock");
+        let before = inner.entries.len();
+        inner.entries.retain(|_, entry| !predicate(entry));
+        let MemoryInner { entries, order } = &mut *inner;
+        order.retain(|key| entries.contains_key(key));
+        Ok(before - entries.len())
+    }
+
+    fn clear(&self) -> Result<(), PathfinderError> {
+        let mut inner = self.inner.lock().expect("lock");
+        inner.entries.clear();
+        inner.order.clear();
+        Ok(())
+    }
+}
+
+/// Disk-backed store on a [`sled`] tree; entries are JSON-encoded.
+///
+/// Holds at most `capacity` entries (default 10 000); inserting past it evicts the
+/// entries cached longest ago.
+#[cfg(feature = "path-cache-sled")]
+#[derive(Debug, Clone)]
+pub struct SledPathStore {
+    tree: sled::Tree,
+    capacity: usize,
+}
+
+#[cfg(feature = "path-cache-sled")]
+impl SledPathStore {
+    /// Open (or create) a database at `path` and use its default tree.
+    pub fn open(path: impl AsRef<std::path::Path>) -> Result<Self, PathfinderError> {
+        let db = sled::open(path).map_err(cache_error)?;
+        Ok(Self::from_tree((*db).clone()))
+    }
+
+    /// Use an existing tree, e.g. one of several in an application database.
+    pub fn from_tree(tree: sled::Tree) -> Self {
+        Self {
+            tree,
+            capacity: 10_000,
+        }
+    }
+
+    /// Keep at most `capacity` entries.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// Remove the oldest entries until at most `capacity` remain. Entries that no longer
+    /// decode count as oldest.
+    fn evict_oldest(&self) -> Result<(), PathfinderError> {
+        let excess = self.tree.len().saturating_sub(self.capacity);
+        if excess == 0 {
+            return Ok(());
+        }
+        let mut ages = Vec::new();
+        for item in self.tree.iter() {
+            let (raw_key, value) = item.map_err(cache_error)?;
+            let cached_at = serde_json::from_slice::<CachedPath>(&value)
+                .map(|entry| entry.cached_at)
+                .unwrap_or(0);
+            ages.push((cached_at, raw_key));
+        }
+        ages.sort();
+        for (_, raw_key) in ages.into_iter().take(excess) {
+            self.tree.remove(raw_key).map_err(cache_error)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "path-cache-sled")]
+impl PathStore for SledPathStore {
+    fn get(&self, key: &PathCacheKey) -> Result<Option<CachedPath>, PathfinderError> {
+        let raw_key = serde_json::to_vec(key).map_err(cache_error)?;
+        self.tree
+            .get(raw_key)
+            .map_err(cache_error)?
+            .map(|value| serde_json::from_slice(&value).map_err(cache_error))
+            .transpose()
+    }
+
+    fn insert(&self, entry: CachedPath) -> Result<(), PathfinderError> {
+        let raw_key = serde_json::to_vec(&entry.key).map_err(cache_error)?;
+        let value = serde_json::to_vec(&entry).map_err(cache_error)?;
+        self.tree.insert(raw_key, value).map_err(cache_error)?;
+        self.evict_oldest()
+    }
+
+    fn remove_where(
+        &self,
+        predicate: &dyn Fn(&CachedPath) -> bool,
+    ) -> Result<usize, PathfinderError> {
+        let mut removed = 0;
+        for item in self.tree.iter() {
+            let (raw_key, value) = item.map_err(cache_error)?;
+            // Entries that no longer decode (e.g. written by an older version) are dropped too.
+            let matches = serde_json::from_slice::<CachedPath>(&value)
+                .map(|entry| predicate(&entry))
+                .unwrap_or(true);
+            if matches {
+                self.tree.remove(raw_key).map_err(cache_error)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    fn clear(&self) -> Result<(), PathfinderError> {
+        self.tree.clear().map_err(cache_error)
+    }
+}
+
+#[cfg(feature = "path-cache-sled")]
+fn cache_error(err: impl std::fmt::Display) -> PathfinderError {
+    PathfinderError::Cache(err.to_string())
+}
+
+/// Caches pathfinding results in a [`PathStore`].
+#[derive(Debug)]
+pub struct PathCache<S = MemoryPathStore> {
+    store: S,
+    max_age: Option<Duration>,
+}
+
+impl PathCache<MemoryPathStore> {
+    /// In-memory cache of up to `capacity` paths.
+    pub fn in_memory(capacity: usize) -> Self {
+        Self::new(MemoryPathStore::new(capacity))
+    }
+}
+
+impl<S: PathStore> PathCache<S> {
+    pub fn new(store: S) -> Self {
+        Self {
+            store,
+            max_age: None,
+        }
+    }
+
+    /// Treat "latest" entries older than `max_age` as misses. Block-pinned entries
+    /// are unaffected.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Cached result for `params` at `block`, if present and fresh.
+    pub fn get(
+        &self,
+        params: &FindPathParams,
+        block: Option<u64>,
+    ) -> Result<Option<PathfindingResult>, PathfinderError> {
+        let key = PathCacheKey::new(params, block);
+        let Some(entry) = self.store.get(&key)? else {
+            return Ok(None);
+        };
+        if self.is_expired(&entry, unix_now()) {
+            self.store.remove_where(&|cached| cached.key == key)?;
+            return Ok(None);
+        }
+        Ok(Some(entry.result))
+    }
+
+    /// Delete every "latest" entry older than `max_age`; returns how many were removed.
+    /// Without a `max_age` nothing expires.
+    pub fn prune_expired(&self) -> Result<usize, PathfinderError> {
+        if self.max_age.is_none() {
+            return Ok(0);
+        }
+        let now = unix_now();
+        self.store
+            .remove_where(&|entry| self.is_expired(entry, now))
+    }
+
+    fn is_expired(&self, entry: &CachedPath, now: u64) -> bool {
+        entry.key.block.is_none()
+            && self
+                .max_age
+                .is_some_and(|max_age| now.saturating_sub(entry.cached_at) > max_age.as_secs())
+    }
+
+    pub fn insert(
+        &self,
+        params: &FindPathParams,
+        block: Option<u64>,
+        result: PathfindingResult,
+    ) -> Result<(), PathfinderError> {
+        self.store
+            .insert(CachedPath::new(PathCacheKey::new(params, block), result))
+    }
+
+    /// Return the cached path or query `circlesV2_findPath` and cache the result.
+    ///
+    /// `block` only labels the entry: the indexer always answers for its current state,
+    /// so pass the block it reported (e.g. from `eth_blockNumber`) or `None`. Fresh
+    /// results are stamped with the indexer's height (see
+    /// [`crate::find_path_result_via_rpc`]).
+    #[cfg(feature = "rpc")]
+    pub async fn find_path_via_rpc(
+        &self,
+        rpc: &circles_rpc::CirclesRpc,
+        params: FindPathParams,
+        block: Option<u64>,
+    ) -> Result<PathfindingResult, PathfinderError> {
+        if let Some(result) = self.get(&params, block)? {
+            return Ok(result);
+        }
+        let result = crate::find_path_result_via_rpc(rpc, params.clone()).await?;
+        self.insert(&params, block, result.clone())?;
+        Ok(result)
+    }
+
+    /// Drop "latest" entries whose path touches one of `addresses`.
+    ///
+    /// Entries pinned to a block describe that block's state and are kept.
+    pub fn invalidate_addresses(&self, addresses: &[Address]) -> Result<usize, PathfinderError> {
+        let addresses: HashSet<Address> = addresses.iter().copied().collect();
+        self.store
+            .remove_where(&|entry| entry.key.block.is_none() && entry.touches(&addresses))
+    }
+
+    /// Drop "latest" entries affected by `event`.
+    ///
+    /// Trust changes, transfers, mints, wrapper deposits/withdrawals, and group
+    /// redemptions invalidate cached paths through any address named in the event.
+    /// Other events are ignored. Returns how many entries were removed.
+    pub fn invalidate_for_event(&self, event: &CirclesEvent) -> Result<usize, PathfinderError> {
+        if !affects_paths(&event.event_type) {
+            return Ok(0);
+        }
+        let addresses: Vec<Address> = event
+            .data
+            .values()
+            .filter_map(|value| value.as_str()?.parse().ok())
+            .collect();
+        if addresses.is_empty() {
+            return Ok(0);
+        }
+        self.invalidate_addresses(&addresses)
+    }
+
+    pub fn clear(&self) -> Result<(), PathfinderError> {
+        self.store.clear()
+    }
+}
+
+fn affects_paths(event_type: &CirclesEventType) -> bool {
+    use CirclesEventType::*;
+    matches!(
+        event_type,
+        CrcV2Trust
+            | CrcV2TransferSingle
+            | CrcV2TransferBatch
+            | CrcV2Transfer
+            | CrcV2StreamCompleted
+            | CrcV2PersonalMint
+            | CrcV2GroupMint
+            | CrcV2DepositDemurraged
+            | CrcV2DepositInflationary
+            | CrcV2WithdrawDemurraged
+            | CrcV2WithdrawInflationary
+            | CrcV2GroupRedeemCollateralBurn
+            | CrcV2GroupRedeemCollateralReturn
+            | CrcV2Stopped
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use circles_rpc_mock::fixtures::circles_event as event;
+    use circles_types::PathfindingTransferStep;
+    use serde_json::json;
+
+    fn addr(byte: u8) -> Address {
+        Address::repeat_byte(byte)
+    }
+
+    fn params(from: Address, to: Address, amount: u64) -> FindPathParams {
+        FindPathParams {
+            from,
+            to,
+            target_flow: U256::from(amount),
+            use_wrapped_balances: Some(true),
+            from_tokens: None,
+            to_tokens: None,
+            exclude_from_tokens: None,
+            exclude_to_tokens: None,
+            simulated_balances: None,
+            simulated_trusts: None,
+            max_transfers: None,
+        }
+    }
+
+    fn two_hop(from: Address, via: Address, to: Address, amount: u64) -> PathfindingResult {
+        let step = |a: Address, b: Address| PathfindingTransferStep {
+            from: a,
+            to: b,
+            token_owner: a.to_string(),
+            value: U256::from(amount),
+        };
+        PathfindingResult {
+            max_flow: U256::from(amount),
+            transfers: vec![step(from, via), step(via, to)],
+            block_number: None,
+        }
+    }
+
+    #[test]
+    fn keys_separate_amounts_blocks_and_options() {
+        let cache = PathCache::in_memory(8);
+        let base = params(addr(1), addr(2), 100);
+        cache
+            .insert(&base, None, two_hop(addr(1), addr(3), addr(2), 100))
+            .unwrap();
+
+        assert!(cache.get(&base, None).unwrap().is_some());
+        assert!(cache.get(&base, Some(10)).unwrap().is_none());
+        assert!(
+            cache
+                .get(&params(addr(1), addr(2), 101), None)
+                .unwrap()
+                .is_none()
+        );
+        let mut unwrapped = base.clone();
+        unwrapped.use_wrapped_balances = Some(false);
+        assert!(cache.get(&unwrapped, None).unwrap().is_none());
+    }
+
+    #[test]
+    fn memory_store_evicts_least_recently_used() {
+        let cache = PathCache::in_memory(2);
+        let insert = |amount| {
+            let p = params(addr(1), addr(2), amount);
+            cache
+                .insert(&p, None, two_hop(addr(1), addr(3), addr(2), amount))
+                .unwrap();
+        };
+        let cached = |amount| {
+            cache
+                .get(&params(addr(1), addr(2), amount), None)
+                .unwrap()
+                .is_some()
+        };
+        insert(1);
+        insert(2);
+        // Reading 1 makes 2 the least recently used.
+        assert!(cached(1));
+        insert(3);
+        assert_eq!(cache.store().len(), 2);
+        assert!(cached(1));
+        assert!(!cached(2));
+        assert!(cached(3));
+    }
+
+    #[test]
+    fn trust_events_invalidate_paths_through_intermediates() {
+        let cache = PathCache::in_memory(8);
+        let via_three = params(addr(1), addr(2), 100);
+        let via_four = params(addr(5), addr(6), 100);
+        cache
+            .insert(&via_three, None, two_hop(addr(1), addr(3), addr(2), 100))
+            .unwrap();
+        cache
+            .insert(&via_four, None, two_hop(addr(5), addr(4), addr(6), 100))
+            .unwrap();
+        cache
+            .insert(&via_three, Some(7), two_hop(addr(1), addr(3), addr(2), 100))
+            .unwrap();
+
+        let ignored = event(
+            CirclesEventType::CrcV2URI,
+            json!({ "value": addr(3).to_string() }),
+        );
+        assert_eq!(cache.invalidate_for_event(&ignored).unwrap(), 0);
+
+        let trust = event(
+            CirclesEventType::CrcV2Trust,
+            json!({
+                "truster": addr(3).to_string(),
+                "trustee": addr(9).to_string(),
+                "expiryTime": "0",
+            }),
+        );
+        assert_eq!(cache.invalidate_for_event(&trust).unwrap(), 1);
+        assert!(cache.get(&via_three, None).unwrap().is_none());
+        assert!(cache.get(&via_four, None).unwrap().is_some());
+        // Block-pinned entries describe history and stay valid.
+        assert!(cache.get(&via_three, Some(7)).unwrap().is_some());
+    }
+
+    #[test]
+    fn max_age_only_applies_to_latest_entries() {
+        let cache = PathCache::in_memory(8).with_max_age(Duration::from_secs(60));
+        let p = params(addr(1), addr(2), 100);
+        let mut stale = CachedPath::new(
+            PathCacheKey::new(&p, None),
+            two_hop(addr(1), addr(3), addr(2), 100),
+        );
+        stale.cached_at -= 120;
+        cache.store().insert(stale.clone()).unwrap();
+        assert!(cache.get(&p, None).unwrap().is_none());
+        // The expired entry is deleted, not just skipped.
+        assert!(cache.store().is_empty());
+
+        let latest = stale.clone();
+        stale.key = PathCacheKey::new(&p, Some(5));
+        cache.store().insert(stale).unwrap();
+        assert!(cache.get(&p, Some(5)).unwrap().is_some());
+
+        cache.store().insert(latest).unwrap();
+        assert_eq!(cache.prune_expired().unwrap(), 1);
+        assert_eq!(cache.store().len(), 1);
+    }
+
+    #[cfg(feature = "path-cache-sled")]
+    #[test]
+    fn sled_store_evicts_the_oldest_entries_past_capacity() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let store = SledPathStore::from_tree((*db).clone()).with_capacity(2);
+        for (age, amount) in [(30, 1), (10, 2), (20, 3)] {
+            let p = params(addr(1), addr(2), amount);
+            let mut entry = CachedPath::new(
+                PathCacheKey::new(&p, None),
+                two_hop(addr(1), addr(3), addr(2), amount),
+            );
+            entry.cached_at -= age;
+            store.insert(entry).unwrap();
+        }
+        assert_eq!(store.len(), 2);
+        let cached = |amount| {
+            store
+                .get(&PathCacheKey::new(&params(addr(1), addr(2), amount), None))
+                .unwrap()
+                .is_some()
+        };
+        assert!(!cached(1));
+        assert!(cached(2));
+        assert!(cached(3));
+    }
+
+    #[test]
+    fn freshness_counts_blocks_since_the_path_was_computed() {
+        let mut result = two_hop(addr(1), addr(3), addr(2), 100);
+        assert_eq!(result.blocks_behind(120), None);
+        assert!(result.is_stale(120, 1_000));
+
+        result.block_number = Some(100);
+        assert_eq!(result.blocks_behind(120), Some(20));
+        assert!(!result.is_stale(120, 20));
+        assert!(result.is_stale(121, 20));
+        // A lagging current block never makes a path look older.
+        assert_eq!(result.blocks_behind(90), Some(0));
+
+        let cached = CachedPath::new(
+            PathCacheKey::new(&params(addr(1), addr(2), 100), None),
+            result,
+        );
+        assert!(cached.is_stale(200, 50));
+    }
+}
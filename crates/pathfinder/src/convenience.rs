@@ -1,13 +1,15 @@
-use crate::PathfinderError;
-use crate::rpc::u256_to_u192;
-use crate::{FlowEdge, PathData, Stream};
-use crate::{FlowMatrix, find_path_with_params_via_rpc};
+use crate::FlowMatrix;
+use crate::{FlowEdge, Stream};
 use alloy_primitives::Address;
-use alloy_primitives::aliases::{U192, U256};
 use alloy_sol_types::SolValue;
-use circles_rpc::CirclesRpc;
-use circles_types::FindPathParams;
-use circles_types::TransferStep;
+#[cfg(feature = "rpc")]
+use {
+    crate::u256_to_u192,
+    crate::{PathData, PathfinderError, find_path_with_params_via_rpc},
+    alloy_primitives::aliases::{U192, U256},
+    circles_rpc::CirclesRpc,
+    circles_types::{FindPathParams, PathfinderConfig, TransferStep},
+};
 
 /// High-level function that combines pathfinding and matrix creation
 ///
@@ -45,11 +47,12 @@ use circles_types::TransferStep;
 /// let path_data = prepare_flow_for_contract("https://rpc.example.com", params).await?;
 ///
 /// // Ready to use with smart contract calls
-/// let (vertices, edges, streams, coords) = path_data.to_contract_params();
+/// let (vertices, edges, streams, coords) = path_data.into_contract_params();
 /// // contract.some_function(vertices, edges, streams, coords);
 /// # Ok(())
 /// # }
 /// ```
+#[cfg(feature = "rpc")]
 pub async fn prepare_flow_for_contract_via_rpc(
     rpc: &CirclesRpc,
     params: FindPathParams,
@@ -61,11 +64,22 @@ pub async fn prepare_flow_for_contract_via_rpc(
 }
 
 /// High-level helper that performs pathfinding and flow-matrix preparation from an RPC URL.
+#[cfg(feature = "rpc")]
 pub async fn prepare_flow_for_contract(
     rpc_url: &str,
     params: FindPathParams,
 ) -> Result<PathData, PathfinderError> {
-    let rpc = CirclesRpc::try_from_http(rpc_url)?;
+    prepare_flow_for_contract_with_config(rpc_url, params, PathfinderConfig::default()).await
+}
+
+/// [`prepare_flow_for_contract`] with a pathfinder timeout and `max_transfers` default.
+#[cfg(feature = "rpc")]
+pub async fn prepare_flow_for_contract_with_config(
+    rpc_url: &str,
+    params: FindPathParams,
+    config: PathfinderConfig,
+) -> Result<PathData, PathfinderError> {
+    let rpc = CirclesRpc::try_from_http(rpc_url)?.with_pathfinder_config(config);
     prepare_flow_for_contract_via_rpc(&rpc, params).await
 }
 
@@ -73,6 +87,7 @@ pub async fn prepare_flow_for_contract(
 ///
 /// This is a convenience wrapper around `prepare_flow_for_contract` for users
 /// who prefer to pass individual parameters instead of a struct.
+#[cfg(feature = "rpc")]
 pub async fn prepare_flow_for_contract_simple(
     rpc_url: &str,
     from: alloy_primitives::Address,
@@ -106,6 +121,7 @@ pub async fn prepare_flow_for_contract_simple(
 /// # Returns
 /// A tuple of (available_flow, transfers) where available_flow is the actual
 /// amount that can be transferred.
+#[cfg(feature = "rpc")]
 pub async fn get_available_flow_via_rpc(
     rpc: &CirclesRpc,
     params: FindPathParams,
@@ -122,6 +138,7 @@ pub async fn get_available_flow_via_rpc(
 }
 
 /// Get the maximum available flow between two addresses from an RPC URL.
+#[cfg(feature = "rpc")]
 pub async fn get_available_flow(
     rpc_url: &str,
     params: FindPathParams,
@@ -135,7 +152,7 @@ pub fn encode_redeem_trusted_data(
     flow: Vec<FlowEdge>,
     streams: Vec<Stream>,
     packed_coordinates: Vec<u8>,
-    source_coordinate: U256,
+    source_coordinate: u16,
 ) -> Vec<u8> {
     (
         flow_vertices,
@@ -157,7 +174,7 @@ pub fn encode_redeem_flow_matrix(matrix: FlowMatrix) -> Vec<u8> {
     )
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "rpc"))]
 mod tests {
     use super::*;
     use alloy_primitives::Address;
@@ -4,35 +4,22 @@
 //! This module handles the conversion of transfer paths into flow matrices
 //! suitable for smart contract execution, including vertex transformation,
 //! edge creation, and coordinate packing.
+use crate::packing::pack_coordinates;
 use crate::{FlowEdge, FlowMatrix, PathfinderError, Stream};
-use alloy_primitives::aliases::{U192, U256};
+use alloy_primitives::aliases::U192;
 use alloy_primitives::{Address, Bytes};
 use circles_types::TransferStep;
-use std::collections::HashSet;
 
-use crate::packing::{pack_coordinates, transform_to_flow_vertices};
-
-fn detect_terminal_edges(transfers: &[TransferStep], receiver: Address) -> HashSet<usize> {
-    let mut terminal_edges = HashSet::new();
-    let mut edges_to_receiver = Vec::new();
-    let mut self_loop_index = None;
-
-    for (index, transfer) in transfers.iter().enumerate() {
-        if transfer.from_address == receiver && transfer.to_address == receiver {
-            self_loop_index = Some(index);
-        } else if transfer.to_address == receiver {
-            edges_to_receiver.push(index);
-        }
-    }
-
-    if let Some(index) = self_loop_index {
-        terminal_edges.insert(index);
-    } else {
-        terminal_edges.extend(edges_to_receiver);
-    }
+/// Most vertices a flow matrix can address.
+///
+/// The hub's packed coordinates and `Stream.sourceCoordinate` are `uint16` vertex indices,
+/// so vertex `65_535` is the last one reachable. The block gas limit caps real paths far
+/// below this, since the hub transfers tokens once per edge while settling; bound path size
+/// with the pathfinder's `max_transfers` rather than relying on this limit.
+pub const MAX_FLOW_VERTICES: usize = u16::MAX as usize + 1;
 
-    terminal_edges
-}
+/// Most edges a flow matrix can address; `Stream.flowEdgeIds` are `uint16` edge indices.
+pub const MAX_FLOW_EDGES: usize = u16::MAX as usize + 1;
 
 /// Create a flow matrix from a sequence of transfer steps.
 ///
@@ -77,6 +64,8 @@ fn detect_terminal_edges(transfers: &[TransferStep], receiver: Address) -> HashS
 /// # Errors
 ///
 /// - [`PathfinderError::Imbalanced`] - When terminal flow doesn't match expected value
+/// - [`PathfinderError::TooManyEdges`] / [`PathfinderError::TooManyVertices`] - When the
+///   path cannot be indexed with the hub's `uint16` coordinates
 ///
 /// # See Also
 ///
@@ -88,80 +77,258 @@ pub fn create_flow_matrix(
     value: U192,
     transfers: &[TransferStep],
 ) -> Result<FlowMatrix, PathfinderError> {
-    if transfers.is_empty() {
-        // If the TS version never calls this with an empty path,
-        // treat it as a logic error / invalid input:
-        return Err(PathfinderError::Imbalanced {
-            expected: value,
-            terminal_sum: U192::from(0u64),
-        });
+    FlowMatrixBuilder::new().build(sender, receiver, value, transfers)
+}
+
+/// How [`create_flow_matrix_with_options`] treats paths that are not strictly well formed.
+///
+/// The default is what [`create_flow_matrix`] does: reject paths without an edge into the
+/// receiver, accept zero-value edges, keep duplicate edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatrixOptions {
+    /// Fail when no edge reaches the receiver. When `false`, the last edge is marked
+    /// terminal instead, as the TypeScript SDK does; the terminal sum check still applies.
+    pub strict_terminals: bool,
+    /// Accept edges that move no value. When `false`, the first such edge fails the build
+    /// with [`PathfinderError::ZeroValueEdge`].
+    pub allow_zero_value_edges: bool,
+    /// Merge steps with the same `(from, to, token_owner)` before building, see
+    /// [`crate::coalesce_transfer_steps`]. Fewer edges mean less calldata.
+    pub coalesce_edges: bool,
+}
+
+impl Default for MatrixOptions {
+    fn default() -> Self {
+        Self {
+            strict_terminals: true,
+            allow_zero_value_edges: true,
+            coalesce_edges: false,
+        }
     }
+}
 
-    let (flow_vertices, idx) = transform_to_flow_vertices(transfers, sender, receiver);
-    let terminal_edge_indices = detect_terminal_edges(transfers, receiver);
-
-    // Build edges
-    let flow_edges: Vec<FlowEdge> = transfers
-        .iter()
-        .enumerate()
-        .map(|(index, t)| FlowEdge {
-            streamSinkId: if terminal_edge_indices.contains(&index) {
-                1
-            } else {
-                0
-            },
-            amount: t.value,
-        })
-        .collect();
+/// [`create_flow_matrix`] with explicit [`MatrixOptions`].
+///
+/// ```rust
+/// use circles_pathfinder::{MatrixOptions, create_flow_matrix_with_options};
+/// # use alloy_primitives::{Address, aliases::U192};
+/// # use circles_types::TransferStep;
+/// # let (alice, bob, carol) = (Address::repeat_byte(1), Address::repeat_byte(2), Address::repeat_byte(3));
+/// // The only edge ends at carol, not at the receiver bob.
+/// let transfers = vec![TransferStep {
+///     from_address: alice, to_address: carol, token_owner: alice, value: U192::from(5u64),
+/// }];
+///
+/// let strict = MatrixOptions::default();
+/// assert!(create_flow_matrix_with_options(alice, bob, U192::from(5u64), &transfers, strict).is_err());
+///
+/// let lenient = MatrixOptions { strict_terminals: false, ..strict };
+/// let matrix = create_flow_matrix_with_options(alice, bob, U192::from(5u64), &transfers, lenient)?;
+/// assert_eq!(matrix.streams[0].flowEdgeIds, vec![0]);
+/// # Ok::<_, circles_pathfinder::PathfinderError>(())
+/// ```
+///
+/// # Errors
+///
+/// Same as [`create_flow_matrix`], plus [`PathfinderError::ZeroValueEdge`] when
+/// `allow_zero_value_edges` is off.
+pub fn create_flow_matrix_with_options(
+    sender: Address,
+    receiver: Address,
+    value: U192,
+    transfers: &[TransferStep],
+    options: MatrixOptions,
+) -> Result<FlowMatrix, PathfinderError> {
+    FlowMatrixBuilder::new().build_with_options(sender, receiver, value, transfers, options)
+}
+
+/// Reusable scratch space for building many flow matrices.
+///
+/// [`create_flow_matrix`] allocates its vertex index and coordinate buffers on every call.
+/// A builder keeps them between calls, so a service preparing matrices in a loop (e.g. a
+/// redemption bot) only allocates the vectors that end up in each returned [`FlowMatrix`],
+/// each sized exactly once. Output is identical to [`create_flow_matrix`].
+///
+/// ```rust
+/// use circles_pathfinder::FlowMatrixBuilder;
+/// # use alloy_primitives::{Address, aliases::U192};
+/// # use circles_types::TransferStep;
+/// # let (alice, bob) = (Address::repeat_byte(1), Address::repeat_byte(2));
+/// # let jobs = vec![(U192::from(5u64), vec![TransferStep {
+/// #     from_address: alice, to_address: bob, token_owner: alice, value: U192::from(5u64),
+/// # }])];
+///
+/// let mut builder = FlowMatrixBuilder::new();
+/// for (value, transfers) in &jobs {
+///     let matrix = builder.build(alice, bob, *value, transfers)?;
+///     # assert_eq!(matrix.flow_vertices, vec![alice, bob]);
+/// }
+/// # Ok::<_, circles_pathfinder::PathfinderError>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct FlowMatrixBuilder {
+    vertices: Vec<Address>,
+    coords: Vec<u16>,
+    terminal: Vec<u16>,
+}
+
+impl FlowMatrixBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-    if terminal_edge_indices.is_empty() {
-        return Err(PathfinderError::RpcResponse(format!(
-            "No terminal edges detected. Flow must have at least one edge delivering to receiver {receiver:#x}"
-        )));
+    /// Builder whose buffers already fit paths of up to `transfers` steps.
+    pub fn with_capacity(transfers: usize) -> Self {
+        Self {
+            vertices: Vec::with_capacity(transfers * 3 + 2),
+            coords: Vec::with_capacity(transfers * 3),
+            terminal: Vec::with_capacity(transfers),
+        }
     }
 
-    // Check terminal balance
-    let terminal_sum: U192 = flow_edges
-        .iter()
-        .filter(|e| e.streamSinkId == 1)
-        .map(|e| e.amount)
-        .sum();
-    if terminal_sum != value {
-        return Err(PathfinderError::Imbalanced {
-            terminal_sum,
-            expected: value,
-        });
+    /// Same as [`create_flow_matrix`], reusing this builder's buffers.
+    pub fn build(
+        &mut self,
+        sender: Address,
+        receiver: Address,
+        value: U192,
+        transfers: &[TransferStep],
+    ) -> Result<FlowMatrix, PathfinderError> {
+        self.build_with_options(sender, receiver, value, transfers, MatrixOptions::default())
     }
 
-    // Build streams
-    let mut term_edge_ids: Vec<u16> = terminal_edge_indices
-        .iter()
-        .map(|index| *index as u16)
-        .collect();
-    term_edge_ids.sort_unstable();
-
-    let streams = vec![Stream {
-        sourceCoordinate: *idx.get(&sender).unwrap() as u16,
-        flowEdgeIds: term_edge_ids,
-        data: Bytes::new(),
-    }];
-
-    // Pack coordinates
-    let mut coords: Vec<u16> = Vec::with_capacity(transfers.len() * 3);
-    for t in transfers {
-        coords.push(*idx.get(&t.token_owner).unwrap() as u16);
-        coords.push(*idx.get(&t.from_address).unwrap() as u16);
-        coords.push(*idx.get(&t.to_address).unwrap() as u16);
+    /// Same as [`create_flow_matrix_with_options`], reusing this builder's buffers.
+    pub fn build_with_options(
+        &mut self,
+        sender: Address,
+        receiver: Address,
+        value: U192,
+        transfers: &[TransferStep],
+        options: MatrixOptions,
+    ) -> Result<FlowMatrix, PathfinderError> {
+        if options.coalesce_edges {
+            let merged = crate::coalesce_transfer_steps(transfers);
+            let options = MatrixOptions {
+                coalesce_edges: false,
+                ..options
+            };
+            return self.build_with_options(sender, receiver, value, &merged, options);
+        }
+        if transfers.is_empty() {
+            // If the TS version never calls this with an empty path,
+            // treat it as a logic error / invalid input:
+            return Err(PathfinderError::Imbalanced {
+                expected: value,
+                terminal_sum: U192::from(0u64),
+            });
+        }
+        if transfers.len() > MAX_FLOW_EDGES {
+            return Err(PathfinderError::TooManyEdges {
+                count: transfers.len(),
+                max: MAX_FLOW_EDGES,
+            });
+        }
+        if !options.allow_zero_value_edges
+            && let Some(edge) = transfers.iter().position(|t| t.value.is_zero())
+        {
+            return Err(PathfinderError::ZeroValueEdge { edge });
+        }
+
+        // Vertices sorted byte-wise, as in `transform_to_flow_vertices`; indices are found
+        // by binary search instead of through a hash map.
+        self.vertices.clear();
+        self.vertices.extend([sender, receiver]);
+        for t in transfers {
+            self.vertices
+                .extend([t.from_address, t.to_address, t.token_owner]);
+        }
+        self.vertices.sort_unstable();
+        self.vertices.dedup();
+        if self.vertices.len() > MAX_FLOW_VERTICES {
+            return Err(PathfinderError::TooManyVertices {
+                count: self.vertices.len(),
+                max: MAX_FLOW_VERTICES,
+            });
+        }
+        let vertices = &self.vertices;
+        // Both bounds are checked above, so the `as u16` casts below cannot truncate.
+        let index = |address: &Address| {
+            vertices
+                .binary_search(address)
+                .expect("every transfer address is a vertex") as u16
+        };
+
+        // A receiver self-loop is the only terminal edge; otherwise every edge into the
+        // receiver is.
+        self.terminal.clear();
+        match transfers
+            .iter()
+            .position(|t| t.from_address == receiver && t.to_address == receiver)
+        {
+            Some(self_loop) => self.terminal.push(self_loop as u16),
+            None => self.terminal.extend(
+                transfers
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, t)| t.to_address == receiver)
+                    .map(|(index, _)| index as u16),
+            ),
+        }
+        if self.terminal.is_empty() {
+            if options.strict_terminals {
+                return Err(PathfinderError::RpcResponse(format!(
+                    "No terminal edges detected. Flow must have at least one edge delivering to receiver {receiver:#x}; none of the {} edges end there (last edge ends at {:#x})",
+                    transfers.len(),
+                    transfers[transfers.len() - 1].to_address,
+                )));
+            }
+            self.terminal.push((transfers.len() - 1) as u16);
+        }
+
+        let terminal_sum: U192 = self
+            .terminal
+            .iter()
+            .map(|&edge| transfers[edge as usize].value)
+            .sum();
+        if terminal_sum != value {
+            return Err(PathfinderError::Imbalanced {
+                terminal_sum,
+                expected: value,
+            });
+        }
+
+        let mut flow_edges = Vec::with_capacity(transfers.len());
+        let mut terminal = self.terminal.iter().peekable();
+        for (edge, t) in transfers.iter().enumerate() {
+            let is_terminal = terminal.next_if(|&&id| id as usize == edge).is_some();
+            flow_edges.push(FlowEdge {
+                streamSinkId: u16::from(is_terminal),
+                amount: t.value,
+            });
+        }
+
+        self.coords.clear();
+        for t in transfers {
+            self.coords.extend([
+                index(&t.token_owner),
+                index(&t.from_address),
+                index(&t.to_address),
+            ]);
+        }
+
+        let source_coordinate = index(&sender);
+        Ok(FlowMatrix {
+            flow_vertices: self.vertices.clone(),
+            flow_edges,
+            streams: vec![Stream {
+                sourceCoordinate: source_coordinate,
+                flowEdgeIds: self.terminal.clone(),
+                data: Bytes::new(),
+            }],
+            packed_coordinates: pack_coordinates(&self.coords),
+            source_coordinate,
+        })
     }
-    let packed_coordinates = pack_coordinates(&coords);
-
-    Ok(FlowMatrix {
-        flow_vertices,
-        flow_edges,
-        streams,
-        packed_coordinates,
-        source_coordinate: U256::from(*idx.get(&sender).unwrap()),
-    })
 }
 
 /// Clone flow-matrix streams and optionally attach transaction data to the first stream.
@@ -180,3 +347,85 @@ pub fn prepare_flow_matrix_streams(
     }
     streams
 }
+
+impl FlowMatrix {
+    /// Split into `operateFlowMatrix` arguments (vertices, edges, streams, packed
+    /// coordinates) without copying any of them.
+    pub fn into_contract_params(self) -> (Vec<Address>, Vec<FlowEdge>, Vec<Stream>, Bytes) {
+        (
+            self.flow_vertices,
+            self.flow_edges,
+            self.streams,
+            Bytes::from(self.packed_coordinates),
+        )
+    }
+}
+
+impl From<FlowMatrix> for circles_types::FlowMatrix {
+    fn from(matrix: FlowMatrix) -> Self {
+        Self {
+            flow_vertices: matrix.flow_vertices,
+            flow_edges: matrix
+                .flow_edges
+                .into_iter()
+                .map(|edge| circles_types::FlowEdge {
+                    stream_sink_id: edge.streamSinkId,
+                    amount: edge.amount,
+                })
+                .collect(),
+            streams: matrix
+                .streams
+                .into_iter()
+                .map(|stream| circles_types::Stream {
+                    source_coordinate: stream.sourceCoordinate,
+                    flow_edge_ids: stream.flowEdgeIds,
+                    data: stream.data.into(),
+                })
+                .collect(),
+            packed_coordinates: matrix.packed_coordinates,
+            source_coordinate: matrix.source_coordinate,
+        }
+    }
+}
+
+impl From<circles_types::FlowMatrix> for FlowMatrix {
+    fn from(matrix: circles_types::FlowMatrix) -> Self {
+        Self {
+            flow_vertices: matrix.flow_vertices,
+            flow_edges: matrix
+                .flow_edges
+                .into_iter()
+                .map(|edge| FlowEdge {
+                    streamSinkId: edge.stream_sink_id,
+                    amount: edge.amount,
+                })
+                .collect(),
+            streams: matrix
+                .streams
+                .into_iter()
+                .map(|stream| Stream {
+                    sourceCoordinate: stream.source_coordinate,
+                    flowEdgeIds: stream.flow_edge_ids,
+                    data: stream.data.into(),
+                })
+                .collect(),
+            packed_coordinates: matrix.packed_coordinates,
+            source_coordinate: matrix.source_coordinate,
+        }
+    }
+}
+
+impl From<FlowMatrix> for circles_types::PathfindingFlowMatrix {
+    fn from(matrix: FlowMatrix) -> Self {
+        circles_types::FlowMatrix::from(matrix).into()
+    }
+}
+
+impl TryFrom<circles_types::PathfindingFlowMatrix> for FlowMatrix {
+    type Error = alloy_primitives::hex::FromHexError;
+
+    /// Fails when a vertex is not an address or the coordinates are not valid hex.
+    fn try_from(matrix: circles_types::PathfindingFlowMatrix) -> Result<Self, Self::Error> {
+        circles_types::FlowMatrix::try_from(matrix).map(Into::into)
+    }
+}
@@ -34,7 +34,7 @@
 //!
 //! // Create PathData and convert to contract types
 //! let path_data = PathData::from_transfers(&transfers, params.from, params.to, target_flow)?;
-//! let (vertices, edges, streams, coords) = path_data.to_contract_params();
+//! let (vertices, edges, streams, coords) = path_data.into_contract_params();
 //!
 //! // Ready for contract calls!
 //! // contract.some_function(vertices, edges, streams, coords).send().await?;
@@ -44,15 +44,20 @@
 
 use crate::{FlowEdge, FlowMatrix, PathfinderError, Stream, create_flow_matrix};
 use alloy_primitives::aliases::U192;
-use alloy_primitives::{Address, Bytes, U256};
+use alloy_primitives::{Address, Bytes};
 use circles_types::TransferStep;
+use serde::{Deserialize, Serialize};
 
 /// Simplified pathfinding result data structure
 ///
 /// This struct contains the raw pathfinding results in a format that can be
 /// easily converted to contract-compatible types. It eliminates the need for
 /// manual field-by-field conversion between different type layers.
-#[derive(Debug, Clone)]
+///
+/// Serializes to the same camelCase JSON as [`FlowMatrix`] (see its docs for the
+/// schema), so paths can be cached or handed between services.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PathData {
     /// Sorted list of all addresses involved in the flow
     pub flow_vertices: Vec<Address>,
@@ -61,9 +66,10 @@ pub struct PathData {
     /// Streams as (source_coordinate, flow_edge_ids, data) tuples
     pub streams: Vec<Stream>, // Vec<(u16, Vec<u16>, Vec<u8>)>,
     /// Packed coordinates as raw bytes
+    #[serde(with = "crate::packing::hex_bytes")]
     pub packed_coordinates: Vec<u8>,
     /// Source coordinate index
-    pub source_coordinate: U256,
+    pub source_coordinate: u16,
 }
 
 impl PathData {
@@ -106,20 +112,7 @@ impl PathData {
         // Create flow matrix
         let matrix = create_flow_matrix(from, to, flow_amount, transfers)?;
 
-        Ok(Self::from_flow_matrix(matrix))
-    }
-
-    /// Create PathData from a FlowMatrix
-    ///
-    /// Internal constructor for converting from the core FlowMatrix type.
-    fn from_flow_matrix(matrix: FlowMatrix) -> Self {
-        Self {
-            flow_vertices: matrix.flow_vertices,
-            flow_edges: matrix.flow_edges,
-            streams: matrix.streams,
-            packed_coordinates: matrix.packed_coordinates,
-            source_coordinate: matrix.source_coordinate,
-        }
+        Ok(matrix.into())
     }
 
     /// Convert to standard Circles Hub FlowEdge types
@@ -131,14 +124,13 @@ impl PathData {
     /// ```rust,no_run
     /// # use circles_pathfinder::hub::PathData;
     /// # use alloy_primitives::aliases::U192;
-    /// # use alloy_primitives::U256;
     /// # use circles_pathfinder::FlowEdge;
     /// # let path_data = PathData {
     /// #     flow_vertices: vec![],
     /// #     flow_edges: vec![FlowEdge { streamSinkId: 1, amount: U192::from(1000u64) }],
     /// #     streams: vec![],
     /// #     packed_coordinates: vec![],
-    /// #     source_coordinate: U256::from(0),
+    /// #     source_coordinate: 0,
     /// # };
     /// let edges = path_data.to_flow_edges();
     /// assert_eq!(edges[0].streamSinkId, 1);
@@ -163,13 +155,12 @@ impl PathData {
     /// ```rust,no_run
     /// # use circles_pathfinder::hub::PathData;
     /// # use circles_pathfinder::Stream;
-    /// # use alloy_primitives::U256;
     /// # let path_data = PathData {
     /// #     flow_vertices: vec![],
     /// #     flow_edges: vec![],
     /// #     streams: vec![Stream { sourceCoordinate:0, flowEdgeIds: vec![1, 2], data: vec![0x01, 0x02].into(),}],
     /// #     packed_coordinates: vec![],
-    /// #     source_coordinate: U256::from(0),
+    /// #     source_coordinate: 0,
     /// # };
     /// let streams = path_data.to_streams();
     /// assert_eq!(streams[0].sourceCoordinate, 0);
@@ -193,18 +184,19 @@ impl PathData {
     ///
     /// # Returns
     /// A tuple of (flow_vertices, flow_edges, streams, packed_coordinates)
-    /// ready to use in contract function calls.
+    /// ready to use in contract function calls. Every field is cloned; use
+    /// [`into_contract_params`](Self::into_contract_params) when the `PathData` is not
+    /// needed afterwards.
     ///
     /// # Example
     /// ```rust,no_run
     /// # use circles_pathfinder::hub::PathData;
-    /// # use alloy_primitives::U256;
     /// # let path_data = PathData {
     /// #     flow_vertices: vec![],
     /// #     flow_edges: vec![],
     /// #     streams: vec![],
     /// #     packed_coordinates: vec![0x01, 0x02],
-    /// #     source_coordinate: U256::from(0),
+    /// #     source_coordinate: 0,
     /// # };
     /// let (vertices, edges, streams, coords) = path_data.to_contract_params();
     ///
@@ -227,6 +219,34 @@ impl PathData {
     pub fn to_packed_coordinates(&self) -> Bytes {
         Bytes::from(self.packed_coordinates.clone())
     }
+
+    /// Consuming [`to_contract_params`](Self::to_contract_params): moves the vectors out
+    /// instead of cloning them.
+    pub fn into_contract_params(self) -> (Vec<Address>, Vec<FlowEdge>, Vec<Stream>, Bytes) {
+        (
+            self.flow_vertices,
+            self.flow_edges,
+            self.streams,
+            Bytes::from(self.packed_coordinates),
+        )
+    }
+
+    /// Consuming [`to_packed_coordinates`](Self::to_packed_coordinates).
+    pub fn into_packed_coordinates(self) -> Bytes {
+        Bytes::from(self.packed_coordinates)
+    }
+}
+
+impl From<FlowMatrix> for PathData {
+    fn from(matrix: FlowMatrix) -> Self {
+        Self {
+            flow_vertices: matrix.flow_vertices,
+            flow_edges: matrix.flow_edges,
+            streams: matrix.streams,
+            packed_coordinates: matrix.packed_coordinates,
+            source_coordinate: matrix.source_coordinate,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -295,7 +315,7 @@ mod tests {
             flow_edges: vec![],
             streams: vec![],
             packed_coordinates: vec![0x01, 0x02, 0x03],
-            source_coordinate: U256::from(0),
+            source_coordinate: 0,
         };
 
         let coords = path_data.to_packed_coordinates();
@@ -0,0 +1,186 @@
+//! Human-readable renderings of a [`FlowMatrix`] for debugging.
+//!
+//! Both renderings work from the packed coordinates, so they show what the hub would see,
+//! and tolerate malformed matrices (e.g. ones deserialized from elsewhere): coordinates
+//! that are missing or point past the vertex list show up as `?` in tables and as an
+//! `unknown` node in DOT output.
+use crate::FlowMatrix;
+use crate::packing::unpack_coordinates;
+use alloy_primitives::{Address, U256};
+use std::fmt::Write;
+
+/// One edge as `(token_owner, from, to)` vertex indices.
+type EdgeCoords = [Option<u16>; 3];
+
+impl FlowMatrix {
+    /// Render the matrix as a Graphviz `digraph`.
+    ///
+    /// Vertices are labelled with their index and a shortened address; the source is drawn
+    /// as a box and receivers of terminal edges with a double border. Edges are labelled
+    /// `#<edge id> <amount>` plus the token owner's vertex, and terminal edges are bold.
+    ///
+    /// ```rust
+    /// # use circles_pathfinder::create_flow_matrix;
+    /// # use alloy_primitives::{Address, aliases::U192};
+    /// # use circles_types::TransferStep;
+    /// # let (alice, bob) = (Address::repeat_byte(1), Address::repeat_byte(2));
+    /// # let transfers = vec![TransferStep {
+    /// #     from_address: alice, to_address: bob, token_owner: alice, value: U192::from(5u64),
+    /// # }];
+    /// let matrix = create_flow_matrix(alice, bob, U192::from(5u64), &transfers)?;
+    /// // Pipe into `dot -Tsvg` to draw it.
+    /// assert!(matrix.to_dot().contains("v0 -> v1"));
+    /// # Ok::<_, circles_pathfinder::PathfinderError>(())
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let edges = self.edge_coords();
+        let mut sinks = vec![false; self.flow_vertices.len()];
+        for (edge, [_, _, to]) in self.flow_edges.iter().zip(&edges) {
+            if edge.streamSinkId != 0
+                && let Some(sink) = to.and_then(|to| sinks.get_mut(to as usize))
+            {
+                *sink = true;
+            }
+        }
+
+        let mut out = String::from("digraph flow_matrix {\n  rankdir=LR;\n");
+        for (index, address) in self.flow_vertices.iter().enumerate() {
+            let mut attrs = format!("label=\"{index}\\n{}\"", short(address));
+            if index == self.source_coordinate as usize {
+                attrs.push_str(", shape=box");
+            }
+            if sinks[index] {
+                attrs.push_str(", peripheries=2");
+            }
+            let _ = writeln!(out, "  v{index} [{attrs}];");
+        }
+        for (id, (edge, [token, from, to])) in self.flow_edges.iter().zip(&edges).enumerate() {
+            let mut attrs = format!(
+                "label=\"#{id} {}\\ntoken v{}\"",
+                edge.amount,
+                vertex(*token)
+            );
+            if edge.streamSinkId != 0 {
+                attrs.push_str(", style=bold, color=blue");
+            }
+            let _ = writeln!(out, "  {} -> {} [{attrs}];", node(*from), node(*to));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Render the matrix as plain-text tables of vertices, edges and streams.
+    ///
+    /// Vertices carry their net flow (received minus sent over all edges), which is zero for
+    /// every intermediate of a balanced path; streams carry the sum of their terminal edges,
+    /// which is what [`crate::PathfinderError::Imbalanced`] compares against.
+    pub fn to_table(&self) -> String {
+        let edges = self.edge_coords();
+        let mut received = vec![U256::ZERO; self.flow_vertices.len()];
+        let mut sent = vec![U256::ZERO; self.flow_vertices.len()];
+        for (edge, [_, from, to]) in self.flow_edges.iter().zip(&edges) {
+            let amount = U256::from(edge.amount);
+            if let Some(total) = from.and_then(|from| sent.get_mut(from as usize)) {
+                *total += amount;
+            }
+            if let Some(total) = to.and_then(|to| received.get_mut(to as usize)) {
+                *total += amount;
+            }
+        }
+
+        let mut out = String::new();
+        let _ = writeln!(out, "vertices ({})", self.flow_vertices.len());
+        let _ = writeln!(out, "{:>5}  {:<42}  {:>6}  net", "#", "address", "role");
+        for (index, address) in self.flow_vertices.iter().enumerate() {
+            let role = if index == self.source_coordinate as usize {
+                "source"
+            } else {
+                ""
+            };
+            let net = match received[index].cmp(&sent[index]) {
+                std::cmp::Ordering::Equal => "0".to_string(),
+                std::cmp::Ordering::Greater => format!("+{}", received[index] - sent[index]),
+                std::cmp::Ordering::Less => format!("-{}", sent[index] - received[index]),
+            };
+            let _ = writeln!(out, "{index:>5}  {address:#x}  {role:>6}  {net}");
+        }
+
+        let _ = writeln!(out, "\nedges ({})", self.flow_edges.len());
+        let _ = writeln!(
+            out,
+            "{:>5}  {:>5}  {:>5}  {:>5}  {:>4}  amount",
+            "#", "from", "to", "token", "sink"
+        );
+        for (id, (edge, [token, from, to])) in self.flow_edges.iter().zip(&edges).enumerate() {
+            let sink = if edge.streamSinkId == 0 {
+                "-".to_string()
+            } else {
+                edge.streamSinkId.to_string()
+            };
+            let _ = writeln!(
+                out,
+                "{id:>5}  {:>5}  {:>5}  {:>5}  {sink:>4}  {}",
+                vertex(*from),
+                vertex(*to),
+                vertex(*token),
+                edge.amount
+            );
+        }
+
+        let _ = writeln!(out, "\nstreams ({})", self.streams.len());
+        let _ = writeln!(
+            out,
+            "{:>5}  {:>6}  {:<20}  terminal sum  data",
+            "#", "source", "edges"
+        );
+        for (id, stream) in self.streams.iter().enumerate() {
+            let terminal_sum: U256 = stream
+                .flowEdgeIds
+                .iter()
+                .filter_map(|&edge| self.flow_edges.get(edge as usize))
+                .map(|edge| U256::from(edge.amount))
+                .sum();
+            let _ = writeln!(
+                out,
+                "{id:>5}  {:>6}  {:<20}  {terminal_sum:<12}  {}",
+                stream.sourceCoordinate,
+                format!("{:?}", stream.flowEdgeIds),
+                stream.data
+            );
+        }
+        out
+    }
+
+    fn edge_coords(&self) -> Vec<EdgeCoords> {
+        let coords = unpack_coordinates(&self.packed_coordinates);
+        let in_range = |coord: Option<&u16>| {
+            coord
+                .copied()
+                .filter(|&c| (c as usize) < self.flow_vertices.len())
+        };
+        (0..self.flow_edges.len())
+            .map(|edge| {
+                let base = edge * 3;
+                [
+                    in_range(coords.get(base)),
+                    in_range(coords.get(base + 1)),
+                    in_range(coords.get(base + 2)),
+                ]
+            })
+            .collect()
+    }
+}
+
+fn vertex(index: Option<u16>) -> String {
+    index.map_or_else(|| "?".to_string(), |index| index.to_string())
+}
+
+/// DOT node id; edges with a missing endpoint all point at a shared `unknown` node.
+fn node(index: Option<u16>) -> String {
+    index.map_or_else(|| "unknown".to_string(), |index| format!("v{index}"))
+}
+
+fn short(address: &Address) -> String {
+    let hex = format!("{address:#x}");
+    format!("{}…{}", &hex[..6], &hex[hex.len() - 4..])
+}
@@ -33,7 +33,7 @@
 //! let path_data = prepare_flow_for_contract("https://rpc.circles.com", params).await?;
 //!
 //! // Ready for smart contract calls
-//! let (vertices, edges, streams, coords) = path_data.to_contract_params();
+//! let (vertices, edges, streams, coords) = path_data.into_contract_params();
 //! // contract.some_function(vertices, edges, streams, coords)
 //! # Ok(())
 //! # }
@@ -73,6 +73,7 @@
 //! - `flow` - Flow matrix calculation
 //! - `packing` - Coordinate packing utilities
 //! - `convenience` - High-level convenience functions
+//! - `simulation` - Simulated balance sets for previewing paths in hypothetical states
 //!
 //! ## Features
 //!
@@ -81,64 +82,121 @@
 //! - **Type safety** with `alloy-primitives`
 //! - **Efficient packing** for on-chain storage
 //! - **Comprehensive testing** with real-world scenarios
+//!
+//! ## Cargo features
+//!
+//! - `rpc` (default) - RPC-backed pathfinding and token lookups via `circles-rpc`.
+//!   Disable it (`default-features = false`) for offline/WASM builds that only need
+//!   flow-matrix construction and packing.
+//! - `ws` - WebSocket support in the underlying RPC client.
+//! - `path-cache-sled` - disk-backed `cache::SledPathStore` for the path cache.
+//! - `parallel` - convert ([`transfer_steps_from_path`]) and net ([`compute_netted_flow`])
+//!   paths of [`PARALLEL_THRESHOLD`] or more steps on the rayon pool. Results, including
+//!   which error is reported, match the sequential code.
+//! - `proptest` - `strategies` with `proptest` generators for balanced transfer paths,
+//!   for fuzzing flow-matrix construction in downstream integrations.
 
+mod amount;
+pub mod cache;
 mod convenience;
 mod flow;
 pub mod hub;
+mod inspect;
 mod packing;
+#[cfg(feature = "rpc")]
 mod rpc;
+pub mod simulation;
+#[cfg(feature = "proptest")]
+pub mod strategies;
 
 use alloy_primitives::{U256, aliases::U192};
 
 // Core public API - the main functions users need
-pub use flow::{create_flow_matrix, prepare_flow_matrix_streams};
+pub use amount::{u256_to_u192, u256_to_u192_saturating};
+pub use flow::{
+    FlowMatrixBuilder, MAX_FLOW_EDGES, MAX_FLOW_VERTICES, MatrixOptions, create_flow_matrix,
+    create_flow_matrix_with_options, prepare_flow_matrix_streams,
+};
 pub mod path;
 
 // RPC functionality
-pub use circles_types::FindPathParams;
-pub use rpc::{find_path, find_path_via_rpc, find_path_with_params, find_path_with_params_via_rpc};
+pub use circles_types::{ErrorKind, FindPathParams, PathfinderConfig};
+#[cfg(feature = "rpc")]
+pub use rpc::{
+    find_path, find_path_result_via_rpc, find_path_via_rpc, find_path_with_config,
+    find_path_with_params, find_path_with_params_via_rpc,
+};
+pub use simulation::BalanceSimulation;
+#[cfg(feature = "rpc")]
+pub use simulation::find_path_simulated;
 
 // Hub contract integration types and functions
 use alloy_primitives::Address;
 use alloy_sol_types::sol;
+pub use cache::PathFreshness;
 pub use hub::PathData;
+use serde::{Deserialize, Serialize};
 
 // High-level convenience functions
+pub use convenience::{encode_redeem_flow_matrix, encode_redeem_trusted_data};
+#[cfg(feature = "rpc")]
 pub use convenience::{
-    encode_redeem_flow_matrix, encode_redeem_trusted_data, get_available_flow,
-    get_available_flow_via_rpc, prepare_flow_for_contract, prepare_flow_for_contract_simple,
-    prepare_flow_for_contract_via_rpc,
+    get_available_flow, get_available_flow_via_rpc, prepare_flow_for_contract,
+    prepare_flow_for_contract_simple, prepare_flow_for_contract_via_rpc,
+    prepare_flow_for_contract_with_config,
 };
 
 pub use path::{
-    assert_no_netted_flow_mismatch, compute_netted_flow, expected_unwrapped_totals,
-    expected_unwrapped_totals_at, get_wrapped_tokens_from_path, replace_wrapped_tokens,
-    replace_wrapped_tokens_with_avatars, shrink_path_values, token_info_map_from_path,
-    token_info_map_from_path_via_rpc, token_info_map_from_path_with_url, wrapped_totals_from_path,
+    PARALLEL_THRESHOLD, assert_no_netted_flow_mismatch, coalesce_transfer_steps,
+    compute_netted_flow, expected_unwrapped_totals, expected_unwrapped_totals_at,
+    get_wrapped_tokens_from_path, replace_wrapped_tokens, replace_wrapped_tokens_with_avatars,
+    shrink_path_values, transfer_steps_from_path, wrapped_totals_from_path,
+};
+#[cfg(feature = "rpc")]
+pub use path::{
+    token_info_map_from_path, token_info_map_from_path_via_rpc, token_info_map_from_path_with_url,
 };
 
 // Utility functions for advanced users
-pub use packing::{pack_coordinates, transform_to_flow_vertices};
+pub use packing::{pack_coordinates, transform_to_flow_vertices, unpack_coordinates};
 
-#[derive(Clone, Debug)]
+/// ABI-ready flow matrix produced by [`create_flow_matrix`].
+///
+/// Serializes to camelCase JSON matching the TypeScript SDK's flow matrix:
+///
+/// ```json
+/// {
+///   "flowVertices": ["0x…", "0x…"],
+///   "flowEdges": [{ "streamSinkId": 1, "amount": "0x3e8" }],
+///   "streams": [{ "sourceCoordinate": 0, "flowEdgeIds": [0], "data": "0x" }],
+///   "packedCoordinates": "0x000000000001",
+///   "sourceCoordinate": 0
+/// }
+/// ```
+///
+/// Amounts are `0x` hex strings on output; decimal strings are accepted on input.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct FlowMatrix {
     pub flow_vertices: Vec<Address>,
     pub flow_edges: Vec<FlowEdge>,
     pub streams: Vec<Stream>,
+    #[serde(with = "packing::hex_bytes")]
     pub packed_coordinates: Vec<u8>,
-    pub source_coordinate: U256,
+    /// Index of the sender in `flow_vertices`; same as the first stream's `sourceCoordinate`.
+    pub source_coordinate: u16,
 }
 
 sol!(
     /// Standard Circles Hub FlowEdge struct matching the contract ABI
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
     struct FlowEdge {
         uint16 streamSinkId;
         uint192 amount;
     }
 
     /// Standard Circles Hub Stream struct matching the contract ABI
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
     struct Stream {
         uint16 sourceCoordinate;
         uint16[] flowEdgeIds;
@@ -173,10 +231,79 @@ pub enum PathfinderError {
     },
 
     /// RPC transport/client error (HTTP/WS or deserialization).
+    #[cfg(feature = "rpc")]
     #[error("rpc transport error: {0}")]
     Transport(#[from] circles_rpc::CirclesRpcError),
 
+    /// Amount does not fit the hub's `uint192` flow amounts.
+    #[error("amount {value} exceeds uint192")]
+    AmountOverflow {
+        /// The amount that failed to convert
+        value: U256,
+    },
+
     /// JSON-RPC payload error returned by the server or an invalid response body.
     #[error("rpc response error: {0}")]
     RpcResponse(String),
+
+    /// The path touches more addresses than `uint16` coordinates can index.
+    #[error(
+        "flow matrix has {count} vertices, more than the {max} addressable by uint16 coordinates"
+    )]
+    TooManyVertices {
+        /// Distinct addresses in the path, including sender and receiver
+        count: usize,
+        /// [`MAX_FLOW_VERTICES`]
+        max: usize,
+    },
+
+    /// The path has more edges than `uint16` flow edge ids can index.
+    #[error("flow matrix has {count} edges, more than the {max} addressable by uint16 edge ids")]
+    TooManyEdges {
+        /// Transfer steps in the path
+        count: usize,
+        /// [`MAX_FLOW_EDGES`]
+        max: usize,
+    },
+
+    /// A flow edge moves no value and [`MatrixOptions::allow_zero_value_edges`] is off.
+    #[error("flow edge {edge} has zero value")]
+    ZeroValueEdge {
+        /// Index of the first zero-value edge in the path
+        edge: usize,
+    },
+
+    /// Path cache storage failed (I/O or encoding in a persistent store).
+    #[error("path cache error: {0}")]
+    Cache(String),
+}
+
+impl PathfinderError {
+    /// Coarse classification for retry and UX policies; RPC failures keep the kind of the
+    /// underlying [`circles_rpc::CirclesRpcError`].
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            #[cfg(feature = "rpc")]
+            Self::Transport(err) => err.kind(),
+            // A path that would overflow the hub's amounts or coordinates has to be split
+            // into smaller transfers by the caller.
+            Self::AmountOverflow { .. }
+            | Self::TooManyVertices { .. }
+            | Self::TooManyEdges { .. } => ErrorKind::InvalidInput,
+            Self::Imbalanced { .. } | Self::RpcResponse(_) | Self::ZeroValueEdge { .. } => {
+                ErrorKind::Protocol
+            }
+            Self::Cache(_) => ErrorKind::Other,
+        }
+    }
+
+    /// Whether repeating the operation later may succeed.
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+
+    /// Whether the caller's input caused the failure.
+    pub fn is_user_error(&self) -> bool {
+        self.kind().is_user_error()
+    }
 }
@@ -35,6 +35,24 @@ pub fn pack_coordinates(coords: &[u16]) -> Vec<u8> {
     out
 }
 
+/// Unpack big-endian `u16` coordinates produced by [`pack_coordinates`].
+///
+/// A trailing odd byte cannot form a coordinate and is ignored.
+///
+/// # Examples
+/// ```rust
+/// use circles_pathfinder::{pack_coordinates, unpack_coordinates};
+///
+/// let coords = vec![0x1234, 0x5678];
+/// assert_eq!(unpack_coordinates(&pack_coordinates(&coords)), coords);
+/// ```
+pub fn unpack_coordinates(packed: &[u8]) -> Vec<u16> {
+    packed
+        .chunks_exact(2)
+        .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+        .collect()
+}
+
 /// Transform transfer steps into sorted vertices and coordinate mapping.
 ///
 /// Creates a deterministically sorted list of all unique addresses involved
@@ -70,3 +88,17 @@ pub fn transform_to_flow_vertices(
         .collect::<std::collections::HashMap<_, _>>();
     (sorted, idx)
 }
+
+/// Serde adapter encoding raw byte vectors as `0x` hex strings.
+pub(crate) mod hex_bytes {
+    use alloy_primitives::Bytes;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        Bytes::copy_from_slice(bytes).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        Bytes::deserialize(deserializer).map(Into::into)
+    }
+}
@@ -1,15 +1,52 @@
-use crate::PathfinderError;
-use alloy_primitives::{Address, I256, U256};
+use crate::{PathfinderError, u256_to_u192};
+use alloy_primitives::{Address, I256, U256, U512};
+#[cfg(feature = "rpc")]
 use circles_rpc::CirclesRpc;
-use circles_types::{PathfindingResult, TokenInfo};
+use circles_types::{PathfindingResult, PathfindingTransferStep, TokenInfo, TransferStep};
 use circles_utils::converter::atto_static_circles_to_atto_circles;
 use std::collections::HashMap;
 use std::str::FromStr;
 
+/// Paths at least this long are converted and netted on the rayon pool when the `parallel`
+/// feature is enabled; shorter ones are cheaper to process on the calling thread.
+pub const PARALLEL_THRESHOLD: usize = 256;
+
+/// Convert RPC path steps into the [`TransferStep`]s used for flow matrices.
+///
+/// Fails on the first step (in path order) whose token owner is not an address or whose
+/// value does not fit `uint192`. With the `parallel` feature, long paths are converted on
+/// the rayon pool with the same result and error.
+pub fn transfer_steps_from_path(
+    steps: &[PathfindingTransferStep],
+) -> Result<Vec<TransferStep>, PathfinderError> {
+    #[cfg(feature = "parallel")]
+    if steps.len() >= PARALLEL_THRESHOLD {
+        use rayon::prelude::*;
+        let converted: Vec<_> = steps.par_iter().map(convert_step).collect();
+        return converted.into_iter().collect();
+    }
+    steps.iter().map(convert_step).collect()
+}
+
+fn convert_step(step: &PathfindingTransferStep) -> Result<TransferStep, PathfinderError> {
+    let token_owner: Address = step
+        .token_owner
+        .parse()
+        .map_err(|e| PathfinderError::RpcResponse(format!("invalid tokenOwner: {e}")))?;
+    let value = u256_to_u192(step.value)?;
+    Ok(TransferStep {
+        from_address: step.from,
+        to_address: step.to,
+        token_owner,
+        value,
+    })
+}
+
 /// Build a map of token info for all token owners the current avatar sends from in the path.
 ///
 /// Normalizes wrapper token types so non-inflationary wrappers are coerced to
 /// `CrcV2_ERC20WrapperDeployed_Demurraged` for downstream logic.
+#[cfg(feature = "rpc")]
 pub async fn token_info_map_from_path_via_rpc(
     current_avatar: Address,
     rpc: &CirclesRpc,
@@ -43,6 +80,7 @@ pub async fn token_info_map_from_path_via_rpc(
 }
 
 /// Compatibility wrapper that preserves the existing Rust client-based entrypoint.
+#[cfg(feature = "rpc")]
 pub async fn token_info_map_from_path(
     current_avatar: Address,
     rpc: &CirclesRpc,
@@ -52,6 +90,7 @@ pub async fn token_info_map_from_path(
 }
 
 /// Convenience wrapper for callers that only have an RPC URL.
+#[cfg(feature = "rpc")]
 pub async fn token_info_map_from_path_with_url(
     current_avatar: Address,
     rpc_url: &str,
@@ -159,6 +198,7 @@ pub fn replace_wrapped_tokens_with_avatars(
     PathfindingResult {
         max_flow: path.max_flow,
         transfers,
+        block_number: path.block_number,
     }
 }
 
@@ -193,6 +233,7 @@ pub fn replace_wrapped_tokens(
     PathfindingResult {
         max_flow: path.max_flow,
         transfers,
+        block_number: path.block_number,
     }
 }
 
@@ -200,18 +241,75 @@ pub fn replace_wrapped_tokens(
 ///
 /// Useful for netting checks: shrink a path to match a reduced payment amount
 /// while preserving proportions.
+///
+/// Flooring every edge on its own loses a different amount of dust on each side of a
+/// vertex. Instead, vertices are visited in flow order: each source sends
+/// `floor(outflow * retain_bps / 1e12)`, every other vertex forwards exactly what it
+/// received, and each amount is split over the vertex's outgoing edges in proportion to
+/// their original values, handing the rounding remainder out by largest remainder (ties go
+/// to the earlier edge). A balanced path therefore stays balanced and delivers
+/// `floor(max_flow * retain_bps / 1e12)` to `sink`. Self-loops are scaled on their own,
+/// and cycles (which the pathfinder does not return) are not guaranteed to conserve flow.
+/// Edges that shrink to zero are dropped.
 pub fn shrink_path_values(
     path: &PathfindingResult,
     sink: Address,
     retain_bps: U256,
 ) -> PathfindingResult {
     let denom = U256::from(1_000_000_000_000u64);
+    let mut inflow: HashMap<Address, U256> = HashMap::new();
+    let mut outflow: HashMap<Address, U256> = HashMap::new();
+    let mut out_edges: HashMap<Address, Vec<usize>> = HashMap::new();
+    let mut scaled = vec![U256::ZERO; path.transfers.len()];
+    for (index, edge) in path.transfers.iter().enumerate() {
+        if edge.from == edge.to {
+            scaled[index] = mul_div(edge.value, retain_bps, denom).0;
+            continue;
+        }
+        *inflow.entry(edge.to).or_default() += edge.value;
+        *outflow.entry(edge.from).or_default() += edge.value;
+        out_edges.entry(edge.from).or_default().push(index);
+    }
+
+    let mut scaled_in: HashMap<Address, U256> = HashMap::new();
+    for vertex in flow_order(path) {
+        let Some(edges) = out_edges.get(&vertex) else {
+            continue;
+        };
+        let out = outflow[&vertex];
+        let budget = match inflow.get(&vertex) {
+            Some(received) => {
+                let forwarded = scaled_in.get(&vertex).copied().unwrap_or_default();
+                mul_div(out, forwarded, *received).0
+            }
+            None => mul_div(out, retain_bps, denom).0,
+        };
+
+        // Largest-remainder split of `budget` over the outgoing edges.
+        let mut remainders = Vec::with_capacity(edges.len());
+        let mut allocated = U256::ZERO;
+        for &index in edges {
+            let (share, remainder) = mul_div(budget, path.transfers[index].value, out);
+            scaled[index] = share;
+            allocated += share;
+            remainders.push((remainder, index));
+        }
+        remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        let leftover = budget.saturating_sub(allocated).saturating_to::<usize>();
+        for &(_, index) in remainders.iter().take(leftover) {
+            scaled[index] += U256::from(1u64);
+        }
+        for &index in edges {
+            *scaled_in.entry(path.transfers[index].to).or_default() += scaled[index];
+        }
+    }
+
     let mut incoming_to_sink: HashMap<Address, U256> = HashMap::new();
     let transfers = path
         .transfers
         .iter()
-        .filter_map(|edge| {
-            let scaled = edge.value.saturating_mul(retain_bps) / denom;
+        .zip(scaled)
+        .filter_map(|(edge, scaled)| {
             if scaled.is_zero() {
                 return None;
             }
@@ -227,36 +325,143 @@ pub fn shrink_path_values(
     PathfindingResult {
         max_flow,
         transfers,
+        block_number: path.block_number,
+    }
+}
+
+/// `a * b / d` and its remainder without intermediate overflow.
+fn mul_div(a: U256, b: U256, d: U256) -> (U256, U256) {
+    if d.is_zero() {
+        return (U256::ZERO, U256::ZERO);
     }
+    let product = U512::from(a) * U512::from(b);
+    let d = U512::from(d);
+    (
+        (product / d).saturating_to::<U256>(),
+        (product % d).to::<U256>(),
+    )
+}
+
+/// Vertices in topological order of the path's edges (ignoring self-loops), seeded in order
+/// of first appearance. Vertices on or behind a cycle follow in order of appearance.
+fn flow_order(path: &PathfindingResult) -> Vec<Address> {
+    let mut vertices = Vec::new();
+    let mut pending: HashMap<Address, usize> = HashMap::new();
+    let mut successors: HashMap<Address, Vec<Address>> = HashMap::new();
+    for edge in &path.transfers {
+        for vertex in [edge.from, edge.to] {
+            pending.entry(vertex).or_insert_with(|| {
+                vertices.push(vertex);
+                0
+            });
+        }
+        if edge.from != edge.to {
+            *pending.get_mut(&edge.to).expect("inserted above") += 1;
+            successors.entry(edge.from).or_default().push(edge.to);
+        }
+    }
+
+    let mut order = Vec::with_capacity(vertices.len());
+    let mut ready: std::collections::VecDeque<Address> = vertices
+        .iter()
+        .copied()
+        .filter(|vertex| pending[vertex] == 0)
+        .collect();
+    while let Some(vertex) = ready.pop_front() {
+        order.push(vertex);
+        for next in successors.get(&vertex).into_iter().flatten() {
+            let count = pending.get_mut(next).expect("every successor is a vertex");
+            *count -= 1;
+            if *count == 0 {
+                ready.push_back(*next);
+            }
+        }
+    }
+    if order.len() < vertices.len() {
+        order.extend(vertices.into_iter().filter(|vertex| pending[vertex] > 0));
+    }
+    order
+}
+
+/// Merge steps that share `(from, to, token_owner)`, summing their values.
+///
+/// Each merged step sits where its first occurrence was, and the remaining steps keep their
+/// order. The hub settles edges in order, so this pulls later amounts forward; that is safe
+/// for topologically ordered paths such as the pathfinder's, where every inflow to an
+/// address precedes its outflows. A step whose sum would overflow `uint192` is kept
+/// separate.
+pub fn coalesce_transfer_steps(transfers: &[TransferStep]) -> Vec<TransferStep> {
+    let mut merged: Vec<TransferStep> = Vec::with_capacity(transfers.len());
+    let mut slots: HashMap<(Address, Address, Address), usize> = HashMap::new();
+    for t in transfers {
+        let key = (t.from_address, t.to_address, t.token_owner);
+        if let Some(&slot) = slots.get(&key)
+            && let Some(sum) = merged[slot].value.checked_add(t.value)
+        {
+            merged[slot].value = sum;
+            continue;
+        }
+        slots.insert(key, merged.len());
+        merged.push(t.clone());
+    }
+    merged
 }
 
 /// Compute netted flow per address (sink positive, source negative).
+///
+/// With the `parallel` feature, paths of at least [`PARALLEL_THRESHOLD`] steps are summed on
+/// the rayon pool; the totals are the same either way.
 pub fn compute_netted_flow(path: &PathfindingResult) -> HashMap<Address, I256> {
+    #[cfg(feature = "parallel")]
+    if path.transfers.len() >= PARALLEL_THRESHOLD {
+        use rayon::prelude::*;
+        return path
+            .transfers
+            .par_iter()
+            .fold(HashMap::new, |mut net, t| {
+                add_step_to_net(&mut net, t);
+                net
+            })
+            .reduce(HashMap::new, |mut net, partial| {
+                for (addr, balance) in partial {
+                    *net.entry(addr).or_insert(I256::ZERO) += balance;
+                }
+                net
+            });
+    }
     let mut net = HashMap::new();
     for t in &path.transfers {
-        let amount: I256 = I256::from_raw(t.value);
-        net.entry(t.from)
-            .and_modify(|v| *v -= amount)
-            .or_insert(-amount);
-        net.entry(t.to)
-            .and_modify(|v| *v += amount)
-            .or_insert(amount);
+        add_step_to_net(&mut net, t);
     }
     net
 }
 
+fn add_step_to_net(net: &mut HashMap<Address, I256>, t: &PathfindingTransferStep) {
+    let amount: I256 = I256::from_raw(t.value);
+    net.entry(t.from)
+        .and_modify(|v| *v -= amount)
+        .or_insert(-amount);
+    net.entry(t.to)
+        .and_modify(|v| *v += amount)
+        .or_insert(amount);
+}
+
 /// Assert that source/sink/intermediate balances match expected netting rules.
 ///
 /// - Source must be net negative, sink net positive, intermediates balanced.
 /// - If source == sink, all vertices must net to zero.
+///
+/// When several vertices are off, the error names the one with the lowest address.
 pub fn assert_no_netted_flow_mismatch(
     path: &PathfindingResult,
     override_source: Option<Address>,
     override_sink: Option<Address>,
 ) -> Result<(), PathfinderError> {
-    let net = compute_netted_flow(path);
     let (source, sink) = get_source_and_sink(path, override_source, override_sink)?;
     let endpoints_coincide = source == sink;
+    // Check vertices in address order so the reported vertex does not depend on hashing.
+    let mut net: Vec<_> = compute_netted_flow(path).into_iter().collect();
+    net.sort_unstable_by_key(|(addr, _)| *addr);
 
     for (addr, balance) in net {
         if endpoints_coincide {
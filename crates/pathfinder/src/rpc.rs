@@ -1,32 +1,9 @@
 use crate::PathfinderError;
+use crate::path::transfer_steps_from_path;
 use alloy_primitives::Address;
 use alloy_primitives::aliases::{U192, U256};
 use circles_rpc::CirclesRpc;
-use circles_types::{FindPathParams, PathfindingResult, PathfindingTransferStep, TransferStep};
-
-pub(crate) fn u256_to_u192(value: U256) -> Result<U192, PathfinderError> {
-    let limbs = value.into_limbs();
-    if limbs[3] != 0 {
-        return Err(PathfinderError::RpcResponse(
-            "transfer value exceeds U192".into(),
-        ));
-    }
-    Ok(U192::from_limbs([limbs[0], limbs[1], limbs[2]]))
-}
-
-fn convert_step(step: &PathfindingTransferStep) -> Result<TransferStep, PathfinderError> {
-    let token_owner: Address = step
-        .token_owner
-        .parse()
-        .map_err(|e| PathfinderError::RpcResponse(format!("invalid tokenOwner: {e}")))?;
-    let value = u256_to_u192(step.value)?;
-    Ok(TransferStep {
-        from_address: step.from,
-        to_address: step.to,
-        token_owner,
-        value,
-    })
-}
+use circles_types::{FindPathParams, PathfinderConfig, PathfindingResult, TransferStep};
 
 /// Find an optimal path between two addresses in the Circles network.
 ///
@@ -114,11 +91,24 @@ pub async fn find_path_with_params_via_rpc(
     params: FindPathParams,
 ) -> Result<Vec<TransferStep>, PathfinderError> {
     let result: PathfindingResult = rpc.pathfinder().find_path(params).await?;
-    result
-        .transfers
-        .iter()
-        .map(convert_step)
-        .collect::<Result<Vec<_>, _>>()
+    transfer_steps_from_path(&result.transfers)
+}
+
+/// Query `circlesV2_findPath` and return the raw result stamped with a block height.
+///
+/// Backends that report the block they answered from are trusted as-is. Otherwise the
+/// indexer's height is read right after the query, which may be a block or two newer
+/// than the state the path was computed from. A failed height lookup leaves the path
+/// unstamped (`block_number: None`) rather than failing it.
+pub async fn find_path_result_via_rpc(
+    rpc: &CirclesRpc,
+    params: FindPathParams,
+) -> Result<PathfindingResult, PathfinderError> {
+    let mut result = rpc.pathfinder().find_path(params).await?;
+    if result.block_number.is_none() {
+        result.block_number = rpc.health().indexed_block_number().await.ok().flatten();
+    }
+    Ok(result)
 }
 
 /// Find a path using structured parameters.
@@ -126,6 +116,18 @@ pub async fn find_path_with_params(
     rpc_url: &str,
     params: FindPathParams,
 ) -> Result<Vec<TransferStep>, PathfinderError> {
-    let rpc = CirclesRpc::try_from_http(rpc_url)?;
+    find_path_with_config(rpc_url, params, PathfinderConfig::default()).await
+}
+
+/// Find a path with a request timeout and `max_transfers` default.
+///
+/// A timeout surfaces as [`PathfinderError::Transport`] wrapping
+/// `CirclesRpcError::Timeout`.
+pub async fn find_path_with_config(
+    rpc_url: &str,
+    params: FindPathParams,
+    config: PathfinderConfig,
+) -> Result<Vec<TransferStep>, PathfinderError> {
+    let rpc = CirclesRpc::try_from_http(rpc_url)?.with_pathfinder_config(config);
     find_path_with_params_via_rpc(&rpc, params).await
 }
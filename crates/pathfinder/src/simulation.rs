@@ -0,0 +1,156 @@
+//! Simulated balance sets for previewing paths against hypothetical states.
+//!
+//! The pathfinder treats each `SimulatedBalance` as the holder's entire balance of that
+//! token, replacing what it has indexed. [`BalanceSimulation`] therefore starts from the
+//! holder's current balances and applies deltas on top, so a preview of "after I mint"
+//! keeps every other token the holder already has.
+use alloy_primitives::{Address, U256};
+use circles_types::{Balance, SimulatedBalance, TokenBalanceResponse};
+
+/// Builder for the `SimulatedBalances` of a [`circles_types::FindPathParams`].
+///
+/// ```rust
+/// use circles_pathfinder::simulation::BalanceSimulation;
+/// use alloy_primitives::{Address, U256};
+///
+/// let alice = Address::repeat_byte(1);
+/// let bob = Address::repeat_byte(2);
+/// let simulated = BalanceSimulation::new()
+///     .after_mint(alice, U256::from(24u64))
+///     .after_receiving(alice, bob, U256::from(5u64))
+///     .into_simulated_balances();
+/// assert_eq!(simulated.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BalanceSimulation {
+    balances: Vec<SimulatedBalance>,
+}
+
+impl BalanceSimulation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from `holder`'s current balances, as returned by `circlesV2_getTokenBalances`
+    /// with `as_time_circles = false`.
+    ///
+    /// Inflationary wrappers are taken in static units (`is_static`), everything else in
+    /// atto-circles. Rows that only carry a time-circles float are skipped.
+    pub fn from_balances(holder: Address, balances: &[TokenBalanceResponse]) -> Self {
+        let mut simulation = Self::new();
+        for row in balances {
+            let is_static = row.is_wrapped && row.is_inflationary;
+            let raw = match row.balance {
+                Balance::Raw(value) => Some(value),
+                Balance::TimeCircles(_) => None,
+            };
+            let amount = if is_static {
+                row.static_atto_circles.or(raw)
+            } else {
+                row.atto_circles.or(raw)
+            };
+            if let Some(amount) = amount {
+                simulation.balances.push(SimulatedBalance {
+                    holder,
+                    token: row.token_address,
+                    amount,
+                    is_wrapped: row.is_wrapped,
+                    is_static,
+                });
+            }
+        }
+        simulation
+    }
+
+    /// Fetch `holder`'s current v2 balances and start from them.
+    #[cfg(feature = "rpc")]
+    pub async fn fetch(
+        rpc: &circles_rpc::CirclesRpc,
+        holder: Address,
+    ) -> Result<Self, crate::PathfinderError> {
+        let balances = rpc.token().get_token_balances(holder, false, true).await?;
+        Ok(Self::from_balances(holder, &balances))
+    }
+
+    /// Set `holder`'s balance of `token` outright, replacing any earlier entry.
+    pub fn set(mut self, holder: Address, token: Address, amount: U256) -> Self {
+        self.entry(holder, token).amount = amount;
+        self
+    }
+
+    /// Add `amount` of `token` to `holder`'s balance.
+    pub fn credit(mut self, holder: Address, token: Address, amount: U256) -> Self {
+        let entry = self.entry(holder, token);
+        entry.amount = entry.amount.saturating_add(amount);
+        self
+    }
+
+    /// Remove `amount` of `token` from `holder`'s balance, stopping at zero.
+    pub fn debit(mut self, holder: Address, token: Address, amount: U256) -> Self {
+        let entry = self.entry(holder, token);
+        entry.amount = entry.amount.saturating_sub(amount);
+        self
+    }
+
+    /// `avatar` after minting `amount` of its own personal token.
+    pub fn after_mint(self, avatar: Address, amount: U256) -> Self {
+        self.credit(avatar, avatar, amount)
+    }
+
+    /// `holder` after receiving `amount` of `token_owner`'s personal token.
+    pub fn after_receiving(self, holder: Address, token_owner: Address, amount: U256) -> Self {
+        self.credit(holder, token_owner, amount)
+    }
+
+    /// `holder` after sending `amount` of `token_owner`'s personal token away.
+    pub fn after_sending(self, holder: Address, token_owner: Address, amount: U256) -> Self {
+        self.debit(holder, token_owner, amount)
+    }
+
+    /// The simulated balances, one per `(holder, token)`.
+    pub fn into_simulated_balances(self) -> Vec<SimulatedBalance> {
+        self.balances
+    }
+
+    /// Entry for `(holder, token)`; new entries are unwrapped ERC-1155 balances of zero.
+    fn entry(&mut self, holder: Address, token: Address) -> &mut SimulatedBalance {
+        let index = match self
+            .balances
+            .iter()
+            .position(|b| b.holder == holder && b.token == token)
+        {
+            Some(index) => index,
+            None => {
+                self.balances.push(SimulatedBalance {
+                    holder,
+                    token,
+                    amount: U256::ZERO,
+                    is_wrapped: false,
+                    is_static: false,
+                });
+                self.balances.len() - 1
+            }
+        };
+        &mut self.balances[index]
+    }
+}
+
+impl From<BalanceSimulation> for Vec<SimulatedBalance> {
+    fn from(simulation: BalanceSimulation) -> Self {
+        simulation.into_simulated_balances()
+    }
+}
+
+/// Run `circlesV2_findPath` with `params` against the simulated balances.
+///
+/// The simulation replaces any `simulated_balances` already set on `params`. The result's
+/// `max_flow` tells a wallet how much would be payable in that state.
+#[cfg(feature = "rpc")]
+pub async fn find_path_simulated(
+    rpc: &circles_rpc::CirclesRpc,
+    mut params: circles_types::FindPathParams,
+    simulation: BalanceSimulation,
+) -> Result<circles_types::PathfindingResult, crate::PathfinderError> {
+    params.simulated_balances = Some(simulation.into_simulated_balances());
+    Ok(rpc.pathfinder().find_path(params).await?)
+}
@@ -0,0 +1,129 @@
+//! `proptest` strategies for transfer paths (enable the `proptest` feature).
+//!
+//! Generated paths are balanced by construction: every route starts at the sender, ends at
+//! the receiver, and carries one amount across all of its hops, so intermediates net to zero
+//! and the receiver gets exactly [`GeneratedPath::value`]. Feed them to
+//! [`create_flow_matrix`](crate::create_flow_matrix) or your own encoding to check invariants.
+//!
+//! ```rust
+//! use circles_pathfinder::{create_flow_matrix, strategies::arb_transfer_path};
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(path in arb_transfer_path())| {
+//!     create_flow_matrix(path.sender, path.receiver, path.value, &path.transfers).unwrap();
+//! });
+//! ```
+
+use alloy_primitives::aliases::U192;
+use alloy_primitives::{Address, U256};
+use circles_types::{PathfindingResult, PathfindingTransferStep, TransferStep};
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+/// Largest per-route amount (1M CRC), small enough that route sums never overflow `U192`.
+const MAX_ROUTE_AMOUNT: u128 = 1_000_000_000_000_000_000_000_000;
+
+/// A balanced transfer path together with the endpoints and amount it delivers.
+#[derive(Debug, Clone)]
+pub struct GeneratedPath {
+    /// Source of every route.
+    pub sender: Address,
+    /// Sink of every route.
+    pub receiver: Address,
+    /// Sum of the amounts reaching `receiver`.
+    pub value: U192,
+    /// Hops of all routes, route by route.
+    pub transfers: Vec<TransferStep>,
+}
+
+impl GeneratedPath {
+    /// The same path in the `circlesV2_findPath` response shape.
+    pub fn to_pathfinding_result(&self) -> PathfindingResult {
+        PathfindingResult {
+            max_flow: U256::from(self.value),
+            transfers: self
+                .transfers
+                .iter()
+                .map(|step| PathfindingTransferStep {
+                    from: step.from_address,
+                    to: step.to_address,
+                    token_owner: step.token_owner.to_string(),
+                    value: U256::from(step.value),
+                })
+                .collect(),
+            block_number: None,
+        }
+    }
+}
+
+/// Any 20-byte address.
+pub fn arb_address() -> impl Strategy<Value = Address> {
+    any::<[u8; 20]>().prop_map(Address::from)
+}
+
+/// Non-zero amount up to 1M CRC (in atto-circles).
+pub fn arb_amount() -> impl Strategy<Value = U192> {
+    (1..=MAX_ROUTE_AMOUNT).prop_map(U192::from)
+}
+
+/// Arbitrary coordinate lists for pack/unpack round trips.
+pub fn arb_coordinates() -> impl Strategy<Value = Vec<u16>> {
+    prop::collection::vec(any::<u16>(), 0..64)
+}
+
+/// Balanced paths of up to 4 parallel routes with up to 3 intermediates each.
+pub fn arb_transfer_path() -> impl Strategy<Value = GeneratedPath> {
+    transfer_path(4, 3)
+}
+
+/// Balanced paths of `1..=max_routes` routes, each with `0..=max_intermediates`
+/// intermediate avatars between sender and receiver.
+///
+/// Each hop moves a token whose owner is one of the vertices the route has already
+/// visited, mirroring how personal tokens travel along trust chains.
+pub fn transfer_path(
+    max_routes: usize,
+    max_intermediates: usize,
+) -> impl Strategy<Value = GeneratedPath> {
+    assert!(max_routes > 0, "at least one route is required");
+    let route = (
+        prop::collection::vec(arb_address(), 0..=max_intermediates),
+        prop::collection::vec(any::<Index>(), max_intermediates + 1),
+        arb_amount(),
+    );
+    (
+        arb_address(),
+        arb_address(),
+        prop::collection::vec(route, 1..=max_routes),
+    )
+        .prop_filter(
+            "sender and receiver must differ",
+            |(sender, receiver, _)| sender != receiver,
+        )
+        .prop_map(|(sender, receiver, routes)| {
+            let mut value = U192::ZERO;
+            let mut transfers = Vec::new();
+            for (mut intermediates, owners, amount) in routes {
+                intermediates.retain(|a| *a != sender && *a != receiver);
+                let mut visited = vec![sender];
+                let vertices = intermediates.into_iter().chain(std::iter::once(receiver));
+                for (hop, to) in vertices.enumerate() {
+                    let from = *visited.last().expect("route starts at sender");
+                    transfers.push(TransferStep {
+                        from_address: from,
+                        to_address: to,
+                        token_owner: *owners[hop].get(&visited),
+                        value: amount,
+                    });
+                    visited.push(to);
+                }
+                value += amount;
+            }
+            GeneratedPath {
+                sender,
+                receiver,
+                value,
+                transfers,
+            }
+        })
+}
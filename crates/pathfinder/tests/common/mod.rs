@@ -41,9 +41,31 @@ pub fn wei_from_str(wei_str: &str) -> U192 {
     U192::from_str_radix(wei_str, 10).expect("Invalid wei value")
 }
 
+/// Start a mock RPC answering `circlesV2_findPath` with a two-hop path of one CRC:
+/// sender -> intermediate_a5 (sender's token) -> receiver (a5's token).
+pub async fn mock_rpc() -> circles_rpc_mock::MockRpcServer {
+    use alloy_primitives::U256;
+
+    let one = U256::from(wei_from_str(ONE_ETH_WEI));
+    let (sender, a5, receiver) = (
+        addresses::sender(),
+        addresses::intermediate_a5(),
+        addresses::receiver(),
+    );
+    circles_rpc_mock::MockRpcServer::builder()
+        .respond(
+            "circlesV2_findPath",
+            circles_rpc_mock::fixtures::path_result(&[
+                (sender, a5, sender, one),
+                (a5, receiver, a5, one),
+            ]),
+        )
+        .start()
+        .await
+        .expect("mock rpc server")
+}
+
 /// Common test constants
-#[allow(dead_code)]
-pub const CIRCLES_RPC: &str = "https://rpc.aboutcircles.com/";
 pub const ONE_ETH_WEI: &str = "1000000000000000000";
 #[allow(dead_code)]
 pub const ONE_TENTH_ETH_WEI: &str = "100000000000000000";
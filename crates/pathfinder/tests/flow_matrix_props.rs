@@ -0,0 +1,118 @@
+//! Property-based invariants for flow matrix construction over generated paths.
+
+use alloy_primitives::{I256, U256, aliases::U192};
+use circles_pathfinder::strategies::{arb_coordinates, arb_transfer_path};
+use circles_pathfinder::{
+    PathfinderError, assert_no_netted_flow_mismatch, compute_netted_flow, create_flow_matrix,
+    pack_coordinates, shrink_path_values, unpack_coordinates,
+};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn pack_unpack_round_trips(coords in arb_coordinates()) {
+        let packed = pack_coordinates(&coords);
+        prop_assert_eq!(packed.len(), coords.len() * 2);
+        prop_assert_eq!(unpack_coordinates(&packed), coords);
+    }
+
+    #[test]
+    fn generated_paths_net_out(path in arb_transfer_path()) {
+        let result = path.to_pathfinding_result();
+        let net = compute_netted_flow(&result);
+        let value = I256::from_raw(U256::from(path.value));
+
+        prop_assert_eq!(net[&path.sender], -value);
+        prop_assert_eq!(net[&path.receiver], value);
+        for (addr, balance) in &net {
+            if *addr != path.sender && *addr != path.receiver {
+                prop_assert_eq!(*balance, I256::ZERO);
+            }
+        }
+        prop_assert!(assert_no_netted_flow_mismatch(&result, None, None).is_ok());
+    }
+
+    #[test]
+    fn shrunk_paths_net_to_scaled_value(
+        path in arb_transfer_path(),
+        retain_bps in 0u64..=1_000_000_000_000,
+    ) {
+        let result = path.to_pathfinding_result();
+        let retain = U256::from(retain_bps);
+        let shrunk = shrink_path_values(&result, path.receiver, retain);
+        let expected = U256::from(path.value) * retain / U256::from(1_000_000_000_000u64);
+
+        prop_assert_eq!(shrunk.max_flow, expected);
+        let net = compute_netted_flow(&shrunk);
+        for (addr, balance) in &net {
+            if *addr == path.receiver {
+                prop_assert_eq!(*balance, I256::from_raw(expected));
+            } else if *addr == path.sender {
+                prop_assert_eq!(*balance, -I256::from_raw(expected));
+            } else {
+                prop_assert_eq!(*balance, I256::ZERO);
+            }
+        }
+        for step in &shrunk.transfers {
+            prop_assert!(!step.value.is_zero());
+        }
+        prop_assert!(
+            shrunk.transfers.len() <= result.transfers.len()
+        );
+    }
+
+    #[test]
+    fn terminal_edges_sum_to_value(path in arb_transfer_path()) {
+        let matrix = create_flow_matrix(path.sender, path.receiver, path.value, &path.transfers)
+            .expect("balanced path builds");
+
+        let terminal_sum: U192 = matrix
+            .flow_edges
+            .iter()
+            .filter(|edge| edge.streamSinkId == 1)
+            .map(|edge| edge.amount)
+            .sum();
+        prop_assert_eq!(terminal_sum, path.value);
+
+        prop_assert_eq!(matrix.streams.len(), 1);
+        for id in &matrix.streams[0].flowEdgeIds {
+            prop_assert_eq!(matrix.flow_edges[*id as usize].streamSinkId, 1);
+            prop_assert_eq!(path.transfers[*id as usize].to_address, path.receiver);
+        }
+    }
+
+    #[test]
+    fn coordinates_stay_in_bounds(path in arb_transfer_path()) {
+        let matrix = create_flow_matrix(path.sender, path.receiver, path.value, &path.transfers)
+            .expect("balanced path builds");
+        let vertices = &matrix.flow_vertices;
+
+        prop_assert!(vertices.windows(2).all(|w| w[0] < w[1]), "vertices sorted and unique");
+        let source = matrix.source_coordinate as usize;
+        prop_assert!(source < vertices.len());
+        prop_assert_eq!(vertices[source], path.sender);
+        prop_assert_eq!(matrix.streams[0].sourceCoordinate as usize, source);
+
+        let coords = unpack_coordinates(&matrix.packed_coordinates);
+        prop_assert_eq!(coords.len(), path.transfers.len() * 3);
+        for (triple, step) in coords.chunks_exact(3).zip(&path.transfers) {
+            prop_assert!(triple.iter().all(|c| (*c as usize) < vertices.len()));
+            prop_assert_eq!(vertices[triple[0] as usize], step.token_owner);
+            prop_assert_eq!(vertices[triple[1] as usize], step.from_address);
+            prop_assert_eq!(vertices[triple[2] as usize], step.to_address);
+        }
+    }
+
+    #[test]
+    fn mismatched_value_is_rejected(path in arb_transfer_path()) {
+        let err = create_flow_matrix(
+            path.sender,
+            path.receiver,
+            path.value + U192::from(1u64),
+            &path.transfers,
+        )
+        .unwrap_err();
+        let is_imbalanced = matches!(err, PathfinderError::Imbalanced { .. });
+        prop_assert!(is_imbalanced);
+    }
+}
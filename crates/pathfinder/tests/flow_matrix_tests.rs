@@ -1,6 +1,8 @@
-use alloy_primitives::{Bytes, U256, aliases::U192};
+use alloy_primitives::{Bytes, aliases::U192};
 use circles_pathfinder::{
-    PathfinderError, Stream, create_flow_matrix, prepare_flow_matrix_streams,
+    FlowMatrixBuilder, MAX_FLOW_EDGES, MAX_FLOW_VERTICES, MatrixOptions, PathfinderError, Stream,
+    coalesce_transfer_steps, create_flow_matrix, create_flow_matrix_with_options,
+    prepare_flow_matrix_streams, transform_to_flow_vertices,
 };
 
 mod common;
@@ -243,7 +245,8 @@ fn test_create_flow_matrix_source_coordinate() {
         .position(|&addr| addr == sender)
         .expect("Sender should be in vertices");
 
-    assert_eq!(matrix.source_coordinate, U256::from(sender_index));
+    assert_eq!(matrix.source_coordinate as usize, sender_index);
+    assert_eq!(matrix.streams[0].sourceCoordinate, matrix.source_coordinate);
 }
 
 #[test]
@@ -257,7 +260,7 @@ fn test_prepare_flow_matrix_streams_without_tx_data() {
             data: Bytes::from(vec![0x01, 0x02]),
         }],
         packed_coordinates: vec![],
-        source_coordinate: U256::ZERO,
+        source_coordinate: 0,
     };
 
     let streams = prepare_flow_matrix_streams(&matrix, None);
@@ -283,7 +286,7 @@ fn test_prepare_flow_matrix_streams_with_tx_data() {
             },
         ],
         packed_coordinates: vec![],
-        source_coordinate: U256::ZERO,
+        source_coordinate: 0,
     };
 
     let streams = prepare_flow_matrix_streams(&matrix, Some(Bytes::from(vec![0xaa, 0xbb])));
@@ -291,3 +294,295 @@ fn test_prepare_flow_matrix_streams_with_tx_data() {
     assert_eq!(streams[0].data, Bytes::from(vec![0xaa, 0xbb]));
     assert_eq!(streams[1].data, Bytes::from(vec![0x02]));
 }
+
+#[test]
+fn test_flow_matrix_converts_between_representations() {
+    let sender = common::addresses::sender();
+    let receiver = common::addresses::receiver();
+    let value = common::wei_from_str(common::ONE_ETH_WEI);
+    let transfers = vec![common::sample_transfer_step(
+        sender, receiver, sender, value,
+    )];
+    let matrix = create_flow_matrix(sender, receiver, value, &transfers).unwrap();
+
+    let plain = circles_types::FlowMatrix::from(matrix.clone());
+    assert_eq!(plain.source_coordinate, matrix.source_coordinate);
+    assert_eq!(plain.flow_edges[0].stream_sink_id, 1);
+
+    let shaped = circles_types::PathfindingFlowMatrix::from(matrix.clone());
+    assert_eq!(shaped.source_coordinate, matrix.source_coordinate);
+    let back = circles_pathfinder::FlowMatrix::try_from(shaped).expect("round trip");
+    assert_eq!(back.flow_vertices, matrix.flow_vertices);
+    assert_eq!(back.flow_edges, matrix.flow_edges);
+    assert_eq!(back.streams, matrix.streams);
+    assert_eq!(back.packed_coordinates, matrix.packed_coordinates);
+
+    let from_plain = circles_pathfinder::FlowMatrix::from(plain);
+    assert_eq!(from_plain.packed_coordinates, matrix.packed_coordinates);
+}
+
+#[test]
+fn test_flow_matrix_json_schema_is_stable() {
+    let sender = common::addresses::sender();
+    let receiver = common::addresses::receiver();
+    let value = U192::from(1000u64);
+    let transfers = vec![common::sample_transfer_step(
+        sender, receiver, sender, value,
+    )];
+    let matrix = create_flow_matrix(sender, receiver, value, &transfers).unwrap();
+
+    let json = serde_json::to_value(&matrix).unwrap();
+    assert_eq!(json["flowEdges"][0]["streamSinkId"], 1);
+    assert_eq!(json["flowEdges"][0]["amount"], "0x3e8");
+    assert_eq!(json["streams"][0]["flowEdgeIds"], serde_json::json!([0]));
+    assert_eq!(json["streams"][0]["data"], "0x");
+    assert_eq!(json["sourceCoordinate"], matrix.source_coordinate);
+    assert!(
+        json["packedCoordinates"]
+            .as_str()
+            .unwrap()
+            .starts_with("0x")
+    );
+
+    let decoded: circles_pathfinder::FlowMatrix = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded, matrix);
+
+    let path_data = circles_pathfinder::PathData::from(matrix.clone());
+    let encoded = serde_json::to_string(&path_data).unwrap();
+    let decoded: circles_pathfinder::PathData = serde_json::from_str(&encoded).unwrap();
+    assert_eq!(decoded, path_data);
+    assert_eq!(
+        serde_json::to_value(&path_data).unwrap(),
+        serde_json::to_value(&matrix).unwrap()
+    );
+}
+
+#[test]
+fn test_flow_edge_accepts_decimal_amounts() {
+    let edge: circles_pathfinder::FlowEdge =
+        serde_json::from_str(r#"{"streamSinkId":1,"amount":"1000"}"#).unwrap();
+    assert_eq!(edge.amount, U192::from(1000u64));
+}
+
+#[test]
+fn test_flow_matrix_builder_reuse_matches_fresh_builds() {
+    let sender = common::addresses::sender();
+    let receiver = common::addresses::receiver();
+    let intermediate = common::addresses::intermediate_a5();
+    let token_owner = common::addresses::token_owner_7b();
+    let value = common::wei_from_str(common::ONE_ETH_WEI);
+
+    let long = vec![
+        common::sample_transfer_step(sender, intermediate, sender, value),
+        common::sample_transfer_step(intermediate, receiver, token_owner, value),
+    ];
+    let short = vec![common::sample_transfer_step(
+        sender, receiver, sender, value,
+    )];
+
+    let mut builder = FlowMatrixBuilder::with_capacity(1);
+    let matrix = builder.build(sender, receiver, value, &long).unwrap();
+    let (vertices, _) = transform_to_flow_vertices(&long, sender, receiver);
+    assert_eq!(matrix.flow_vertices, vertices);
+    assert_eq!(
+        matrix,
+        create_flow_matrix(sender, receiver, value, &long).unwrap()
+    );
+
+    // A failed build must not leak state into the next one.
+    assert!(
+        builder
+            .build(sender, receiver, U192::from(1u64), &long)
+            .is_err()
+    );
+    assert_eq!(
+        builder.build(sender, receiver, value, &short).unwrap(),
+        create_flow_matrix(sender, receiver, value, &short).unwrap()
+    );
+
+    let (vertices, edges, streams, coords) = matrix.clone().into_contract_params();
+    assert_eq!(vertices, matrix.flow_vertices);
+    assert_eq!(edges, matrix.flow_edges);
+    assert_eq!(streams, matrix.streams);
+    assert_eq!(coords.to_vec(), matrix.packed_coordinates);
+}
+
+#[test]
+fn test_matrix_options_lenient_terminals_and_zero_value_edges() {
+    let sender = common::addresses::sender();
+    let receiver = common::addresses::receiver();
+    let intermediate = common::addresses::intermediate_a5();
+    let value = common::wei_from_str(common::ONE_ETH_WEI);
+
+    let transfers = vec![
+        common::sample_transfer_step(sender, intermediate, sender, U192::ZERO),
+        common::sample_transfer_step(sender, intermediate, sender, value),
+    ];
+
+    match create_flow_matrix_with_options(
+        sender,
+        receiver,
+        value,
+        &transfers,
+        MatrixOptions::default(),
+    )
+    .unwrap_err()
+    {
+        PathfinderError::RpcResponse(message) => {
+            assert!(message.contains("No terminal edges detected"));
+            assert!(message.contains(&format!("{intermediate:#x}")));
+        }
+        other => panic!("Expected RpcResponse error, got: {other:?}"),
+    }
+
+    let lenient = MatrixOptions {
+        strict_terminals: false,
+        ..MatrixOptions::default()
+    };
+    let matrix =
+        create_flow_matrix_with_options(sender, receiver, value, &transfers, lenient).unwrap();
+    assert_eq!(matrix.streams[0].flowEdgeIds, vec![1]);
+    assert_eq!(matrix.flow_edges[0].streamSinkId, 0);
+    assert_eq!(matrix.flow_edges[1].streamSinkId, 1);
+
+    let no_zero = MatrixOptions {
+        allow_zero_value_edges: false,
+        ..lenient
+    };
+    assert!(matches!(
+        create_flow_matrix_with_options(sender, receiver, value, &transfers, no_zero),
+        Err(PathfinderError::ZeroValueEdge { edge: 0 })
+    ));
+}
+
+#[test]
+fn test_create_flow_matrix_rejects_paths_beyond_uint16_indices() {
+    let sender = common::addresses::sender();
+    let receiver = common::addresses::receiver();
+    let value = U192::from(1u64);
+
+    // Each hop introduces two new addresses (next hop and token owner).
+    let hop = |i: usize| alloy_primitives::Address::with_last_byte(1).create(i as u64);
+    let owner = |i: usize| alloy_primitives::Address::with_last_byte(2).create(i as u64);
+    let hops = MAX_FLOW_VERTICES / 2;
+    let mut transfers: Vec<_> = (0..hops)
+        .map(|i| {
+            let from = if i == 0 { sender } else { hop(i - 1) };
+            common::sample_transfer_step(from, hop(i), owner(i), value)
+        })
+        .collect();
+    transfers.push(common::sample_transfer_step(
+        hop(hops - 1),
+        receiver,
+        owner(hops),
+        value,
+    ));
+
+    match create_flow_matrix(sender, receiver, value, &transfers).unwrap_err() {
+        PathfinderError::TooManyVertices { count, max } => {
+            assert_eq!(max, MAX_FLOW_VERTICES);
+            assert!(count > max);
+        }
+        other => panic!("Expected TooManyVertices, got: {other:?}"),
+    }
+
+    let edges =
+        vec![common::sample_transfer_step(sender, receiver, sender, value); MAX_FLOW_EDGES + 1];
+    assert!(matches!(
+        create_flow_matrix(sender, receiver, value, &edges),
+        Err(PathfinderError::TooManyEdges { count, max: MAX_FLOW_EDGES }) if count == MAX_FLOW_EDGES + 1
+    ));
+}
+
+#[test]
+fn test_coalesce_duplicate_edges_before_building() {
+    let sender = common::addresses::sender();
+    let receiver = common::addresses::receiver();
+    let intermediate = common::addresses::intermediate_a5();
+    let two = U192::from(2u64);
+    let three = U192::from(3u64);
+
+    let transfers = [
+        common::sample_transfer_step(sender, intermediate, sender, two),
+        common::sample_transfer_step(intermediate, receiver, intermediate, two),
+        common::sample_transfer_step(sender, intermediate, sender, three),
+        common::sample_transfer_step(intermediate, receiver, intermediate, three),
+        common::sample_transfer_step(intermediate, receiver, sender, U192::MAX),
+        common::sample_transfer_step(intermediate, receiver, sender, U192::from(1u64)),
+    ];
+
+    let key = |steps: &[circles_types::TransferStep]| {
+        steps
+            .iter()
+            .map(|t| (t.from_address, t.to_address, t.token_owner, t.value))
+            .collect::<Vec<_>>()
+    };
+    let merged = coalesce_transfer_steps(&transfers[..4]);
+    assert_eq!(
+        key(&merged),
+        vec![
+            (sender, intermediate, sender, two + three),
+            (intermediate, receiver, intermediate, two + three),
+        ]
+    );
+    // Sums that would overflow uint192 stay separate.
+    assert_eq!(
+        key(&coalesce_transfer_steps(&transfers[4..])),
+        key(&transfers[4..])
+    );
+
+    let options = MatrixOptions {
+        coalesce_edges: true,
+        ..MatrixOptions::default()
+    };
+    let matrix =
+        create_flow_matrix_with_options(sender, receiver, two + three, &transfers[..4], options)
+            .unwrap();
+    assert_eq!(matrix.flow_edges.len(), 2);
+    assert_eq!(
+        matrix,
+        create_flow_matrix(sender, receiver, two + three, &merged).unwrap()
+    );
+}
+
+#[test]
+fn test_flow_matrix_table_and_dot_rendering() {
+    let sender = common::addresses::sender();
+    let receiver = common::addresses::receiver();
+    let intermediate = common::addresses::intermediate_a5();
+    let value = U192::from(1000u64);
+
+    let transfers = vec![
+        common::sample_transfer_step(sender, intermediate, sender, value),
+        common::sample_transfer_step(intermediate, receiver, intermediate, value),
+    ];
+    let mut matrix = create_flow_matrix(sender, receiver, value, &transfers).unwrap();
+    let index = |address| {
+        matrix
+            .flow_vertices
+            .iter()
+            .position(|v| *v == address)
+            .unwrap()
+    };
+    let (s, i, r) = (index(sender), index(intermediate), index(receiver));
+
+    let table = matrix.to_table();
+    assert!(table.contains(&format!("{sender:#x}  source  -1000")));
+    assert!(table.contains(&format!("{intermediate:#x}          0")));
+    assert!(table.contains(&format!("{receiver:#x}          +1000")));
+    assert!(table.contains(&format!("    1  {i:>5}  {r:>5}  {i:>5}     1  1000")));
+    assert!(table.contains("[1]"));
+
+    let dot = matrix.to_dot();
+    assert!(dot.starts_with("digraph flow_matrix {"));
+    assert!(dot.contains(&format!("v{s} [label=\"{s}\\n0x")));
+    assert!(dot.contains(&format!("v{s} -> v{i} [label=\"#0 1000\\ntoken v{s}\"];")));
+    assert!(dot.contains(&format!(
+        "v{i} -> v{r} [label=\"#1 1000\\ntoken v{i}\", style=bold, color=blue];"
+    )));
+
+    // Truncated coordinates must not panic.
+    matrix.packed_coordinates.truncate(8);
+    assert!(matrix.to_table().contains("    1      ?      ?"));
+    assert!(matrix.to_dot().contains("unknown -> unknown"));
+}
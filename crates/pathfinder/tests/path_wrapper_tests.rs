@@ -18,6 +18,7 @@ async fn normalizes_demurraged_wrapper_type() {
             token_owner: format!("{wrapper:#x}"),
             value: alloy_primitives::U256::from(1u64),
         }],
+        block_number: None,
     };
 
     // Build a fake token info map as the helper would, then apply normalization.
@@ -109,6 +110,7 @@ fn replaces_wrapped_tokens_with_avatar_addresses() {
                 value: alloy_primitives::U256::from(1u64),
             },
         ],
+        block_number: None,
     };
 
     let mut info_map = std::collections::HashMap::new();
@@ -154,6 +156,7 @@ fn get_wrapped_tokens_alias_matches_existing_helper() {
             token_owner: format!("{wrapper:#x}"),
             value: alloy_primitives::U256::from(42u64),
         }],
+        block_number: None,
     };
 
     let mut info_map = std::collections::HashMap::new();
@@ -186,6 +189,7 @@ fn replace_wrapped_tokens_preserves_unmapped_owner_string() {
             token_owner: original_owner.clone(),
             value: alloy_primitives::U256::from(1u64),
         }],
+        block_number: None,
     };
 
     let rewritten =
@@ -248,6 +252,7 @@ fn compute_netted_flow_matches_ts_balances() {
                 value: alloy_primitives::U256::from(5u64),
             },
         ],
+        block_number: None,
     };
 
     let net = circles_pathfinder::compute_netted_flow(&path);
@@ -269,6 +274,7 @@ fn assert_no_netted_flow_mismatch_accepts_closed_loop_with_overrides() {
             token_owner: format!("{avatar:#x}"),
             value: alloy_primitives::U256::from(7u64),
         }],
+        block_number: None,
     };
 
     let result =
@@ -299,6 +305,7 @@ fn assert_no_netted_flow_mismatch_uses_ts_source_order_on_malformed_paths() {
                 value: alloy_primitives::U256::from(3u64),
             },
         ],
+        block_number: None,
     };
 
     let err = circles_pathfinder::assert_no_netted_flow_mismatch(&path, None, None).unwrap_err();
@@ -333,6 +340,7 @@ fn shrink_path_values_scales_and_drops_subunit_edges() {
                 value: alloy_primitives::U256::from(1u64),
             },
         ],
+        block_number: None,
     };
 
     let shrunk = circles_pathfinder::shrink_path_values(
@@ -350,6 +358,45 @@ fn shrink_path_values_scales_and_drops_subunit_edges() {
     );
 }
 
+#[test]
+fn shrink_path_values_keeps_intermediates_balanced() {
+    let source = address!("0x8000000000000000000000000000000000000008");
+    let intermediate = address!("0x9000000000000000000000000000000000000009");
+    let sink = address!("0xa00000000000000000000000000000000000000a");
+    let other_owner = address!("0xb00000000000000000000000000000000000000b");
+    let step = |from, to, owner: alloy_primitives::Address, value: u64| PathfindingTransferStep {
+        from,
+        to,
+        token_owner: format!("{owner:#x}"),
+        value: alloy_primitives::U256::from(value),
+    };
+
+    // Halving each 1-unit edge on its own floors both to zero while the 2-unit edge keeps 1,
+    // leaving the intermediate forwarding a unit it never received.
+    let path = PathfindingResult {
+        max_flow: alloy_primitives::U256::from(2u64),
+        transfers: vec![
+            step(source, intermediate, source, 1),
+            step(source, intermediate, other_owner, 1),
+            step(intermediate, sink, intermediate, 2),
+        ],
+        block_number: None,
+    };
+
+    let shrunk = circles_pathfinder::shrink_path_values(
+        &path,
+        sink,
+        alloy_primitives::U256::from(500_000_000_000u64),
+    );
+
+    assert_eq!(shrunk.max_flow, alloy_primitives::U256::from(1u64));
+    assert_eq!(shrunk.transfers.len(), 2);
+    assert_eq!(shrunk.transfers[0].token_owner, format!("{source:#x}"));
+    assert!(circles_pathfinder::assert_no_netted_flow_mismatch(&shrunk, None, None).is_ok());
+    let net = circles_pathfinder::compute_netted_flow(&shrunk);
+    assert_eq!(net[&intermediate], alloy_primitives::I256::ZERO);
+}
+
 #[tokio::test]
 async fn token_info_map_from_path_via_rpc_returns_transport_error_for_invalid_target() {
     let current = address!("0xb00000000000000000000000000000000000000b");
@@ -364,6 +411,7 @@ async fn token_info_map_from_path_via_rpc_returns_transport_error_for_invalid_ta
             token_owner: format!("{wrapper:#x}"),
             value: alloy_primitives::U256::from(1u64),
         }],
+        block_number: None,
     };
 
     let err = circles_pathfinder::token_info_map_from_path_via_rpc(current, &rpc, &path)
@@ -389,6 +437,7 @@ async fn token_info_map_from_path_with_url_returns_transport_error_for_invalid_t
             token_owner: format!("{wrapper:#x}"),
             value: alloy_primitives::U256::from(1u64),
         }],
+        block_number: None,
     };
 
     let err = circles_pathfinder::token_info_map_from_path_with_url(
@@ -404,3 +453,64 @@ async fn token_info_map_from_path_with_url_returns_transport_error_for_invalid_t
         other => panic!("expected transport error, got {other:?}"),
     }
 }
+
+fn long_chain(len: usize) -> Vec<PathfindingTransferStep> {
+    (0..len)
+        .map(|i| {
+            let from = alloy_primitives::Address::with_last_byte(0).create(i as u64);
+            let to = alloy_primitives::Address::with_last_byte(0).create(i as u64 + 1);
+            PathfindingTransferStep {
+                from,
+                to,
+                token_owner: format!("{from:#x}"),
+                value: alloy_primitives::U256::from(9u64),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn transfer_steps_from_path_reports_first_failure_in_path_order() {
+    let mut steps = long_chain(circles_pathfinder::PARALLEL_THRESHOLD + 44);
+    steps[200].value = alloy_primitives::U256::MAX;
+    steps[100].token_owner = "not-an-address".to_string();
+
+    let err = circles_pathfinder::transfer_steps_from_path(&steps).unwrap_err();
+    match err {
+        circles_pathfinder::PathfinderError::RpcResponse(message) => {
+            assert!(message.contains("invalid tokenOwner"), "{message}");
+        }
+        other => panic!("expected RpcResponse, got {other:?}"),
+    }
+
+    steps[100].token_owner = format!("{:#x}", steps[100].from);
+    let converted = circles_pathfinder::transfer_steps_from_path(&steps[..200]).unwrap();
+    assert_eq!(converted.len(), 200);
+    assert_eq!(converted[100].token_owner, steps[100].from);
+}
+
+#[test]
+fn compute_netted_flow_on_long_chain_only_moves_the_endpoints() {
+    let steps = long_chain(circles_pathfinder::PARALLEL_THRESHOLD * 2);
+    let source = steps[0].from;
+    let sink = steps[steps.len() - 1].to;
+    let path = PathfindingResult {
+        max_flow: alloy_primitives::U256::from(9u64),
+        transfers: steps,
+        block_number: None,
+    };
+
+    let net = circles_pathfinder::compute_netted_flow(&path);
+    let nine = alloy_primitives::I256::from_raw(alloy_primitives::U256::from(9u64));
+
+    assert_eq!(net.len(), path.transfers.len() + 1);
+    assert_eq!(net.get(&source), Some(&(-nine)));
+    assert_eq!(net.get(&sink), Some(&nine));
+    assert_eq!(
+        net.values()
+            .filter(|v| **v == alloy_primitives::I256::ZERO)
+            .count(),
+        path.transfers.len() - 1
+    );
+    assert!(circles_pathfinder::assert_no_netted_flow_mismatch(&path, None, None).is_ok());
+}
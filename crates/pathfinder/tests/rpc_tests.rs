@@ -1,6 +1,8 @@
 use alloy_primitives::aliases::U192;
 use circles_pathfinder::{
-    FindPathParams, PathfinderError, find_path, find_path_via_rpc, find_path_with_params_via_rpc,
+    BalanceSimulation, ErrorKind, FindPathParams, PathFreshness, PathfinderError, find_path,
+    find_path_result_via_rpc, find_path_simulated, find_path_via_rpc,
+    find_path_with_params_via_rpc,
 };
 use circles_rpc::CirclesRpc;
 
@@ -8,6 +10,7 @@ mod common;
 
 #[tokio::test]
 async fn test_find_path() {
+    let server = common::mock_rpc().await;
     let sender = common::addresses::sender();
     let receiver = common::addresses::receiver();
 
@@ -15,7 +18,7 @@ async fn test_find_path() {
     let value = common::wei_from_str(common::ONE_ETH_WEI);
 
     let result = find_path(
-        common::CIRCLES_RPC,
+        &server.url(),
         sender,
         receiver,
         value,
@@ -44,7 +47,12 @@ async fn test_find_path_with_invalid_rpc() {
 
     // Check that it's the right kind of error
     match result.unwrap_err() {
-        PathfinderError::Transport(_) => {} // Expected
+        err @ PathfinderError::Transport(_) => {
+            // An unreachable endpoint is a network failure, worth retrying later.
+            assert_eq!(err.kind(), ErrorKind::Network);
+            assert!(err.is_retryable());
+            assert!(!err.is_user_error());
+        }
         other => panic!("Expected RPC error, got: {other:?}"),
     }
 }
@@ -101,11 +109,12 @@ async fn test_find_path_with_params_via_rpc_with_invalid_rpc() {
 
 #[tokio::test]
 async fn test_find_path_with_zero_value() {
+    let server = common::mock_rpc().await;
     let sender = common::addresses::sender();
     let receiver = common::addresses::receiver();
     let value = U192::ZERO;
 
-    let result = find_path(common::CIRCLES_RPC, sender, receiver, value, true).await;
+    let result = find_path(&server.url(), sender, receiver, value, true).await;
 
     // This test checks edge case behavior - the result depends on your RPC implementation
     // You might expect this to succeed with empty transfers or fail with a specific error
@@ -129,11 +138,12 @@ async fn test_find_path_with_zero_value() {
 
 #[tokio::test]
 async fn test_find_path_same_sender_receiver() {
+    let server = common::mock_rpc().await;
     let address = common::addresses::sender();
     let value = common::wei_from_str(common::ONE_ETH_WEI);
 
     let result = find_path(
-        common::CIRCLES_RPC,
+        &server.url(),
         address,
         address, // Same address for sender and receiver
         value,
@@ -155,3 +165,197 @@ async fn test_find_path_same_sender_receiver() {
         }
     }
 }
+
+#[tokio::test]
+async fn test_find_path_simulated_sends_current_balances_plus_deltas() {
+    use alloy_primitives::U256;
+
+    let sender = common::addresses::sender();
+    let receiver = common::addresses::receiver();
+    let a5 = common::addresses::intermediate_a5();
+    let wrapper = common::addresses::token_owner_7b();
+    let server = circles_rpc_mock::MockRpcServer::builder()
+        .respond(
+            "circlesV2_getTokenBalances",
+            serde_json::json!([
+                {
+                    "tokenAddress": sender,
+                    "tokenId": sender,
+                    "tokenOwner": sender,
+                    "attoCircles": "0x64",
+                    "isErc1155": true
+                },
+                {
+                    "tokenAddress": wrapper,
+                    "tokenId": wrapper,
+                    "tokenOwner": a5,
+                    "attoCircles": "0x10",
+                    "staticAttoCircles": "0x20",
+                    "isErc20": true,
+                    "isWrapped": true,
+                    "isInflationary": true
+                }
+            ]),
+        )
+        .respond(
+            "circlesV2_findPath",
+            circles_rpc_mock::fixtures::path_result(&[(
+                sender,
+                receiver,
+                sender,
+                U256::from(150u64),
+            )]),
+        )
+        .start()
+        .await
+        .expect("mock rpc server");
+    let rpc = CirclesRpc::try_from_http(&server.url()).unwrap();
+
+    let simulation = BalanceSimulation::fetch(&rpc, sender)
+        .await
+        .unwrap()
+        .after_mint(sender, U256::from(50u64))
+        .after_receiving(sender, a5, U256::from(7u64));
+    let params = FindPathParams {
+        from: sender,
+        to: receiver,
+        target_flow: U256::from(1_000u64),
+        use_wrapped_balances: Some(true),
+        from_tokens: None,
+        to_tokens: None,
+        exclude_from_tokens: None,
+        exclude_to_tokens: None,
+        simulated_balances: None,
+        simulated_trusts: None,
+        max_transfers: None,
+    };
+    let result = find_path_simulated(&rpc, params, simulation).await.unwrap();
+    assert_eq!(result.max_flow, U256::from(150u64));
+
+    let sent = server.params_for("circlesV2_findPath");
+    assert_eq!(
+        sent[0][0]["SimulatedBalances"],
+        serde_json::json!([
+            {
+                "holder": format!("{sender:#x}"),
+                "token": format!("{sender:#x}"),
+                "amount": "0x96",
+                "isWrapped": false,
+                "isStatic": false
+            },
+            {
+                "holder": format!("{sender:#x}"),
+                "token": format!("{wrapper:#x}"),
+                "amount": "0x20",
+                "isWrapped": true,
+                "isStatic": true
+            },
+            {
+                "holder": format!("{sender:#x}"),
+                "token": format!("{a5:#x}"),
+                "amount": "0x7",
+                "isWrapped": false,
+                "isStatic": false
+            }
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_find_path_result_is_stamped_with_indexed_block() {
+    use alloy_primitives::U256;
+
+    let sender = common::addresses::sender();
+    let receiver = common::addresses::receiver();
+    let path =
+        circles_rpc_mock::fixtures::path_result(&[(sender, receiver, sender, U256::from(5u64))]);
+    let mut reported = path.clone();
+    reported["blockNumber"] = serde_json::json!(41_000_000u64);
+    let server = circles_rpc_mock::MockRpcServer::builder()
+        .respond("circlesV2_findPath", path)
+        .respond(
+            "circles_query",
+            circles_rpc_mock::fixtures::query_result(
+                &["blockNumber"],
+                vec![vec![serde_json::json!(40_999_990u64)]],
+            ),
+        )
+        .respond("circlesV2_findPath", reported)
+        .start()
+        .await
+        .expect("mock rpc server");
+    let rpc = CirclesRpc::try_from_http(&server.url()).unwrap();
+    let params = FindPathParams {
+        from: sender,
+        to: receiver,
+        target_flow: U256::from(5u64),
+        use_wrapped_balances: Some(true),
+        from_tokens: None,
+        to_tokens: None,
+        exclude_from_tokens: None,
+        exclude_to_tokens: None,
+        simulated_balances: None,
+        simulated_trusts: None,
+        max_transfers: None,
+    };
+
+    let fetched = find_path_result_via_rpc(&rpc, params.clone())
+        .await
+        .unwrap();
+    assert_eq!(fetched.block_number, Some(40_999_990));
+    assert!(!fetched.is_stale(41_000_000, 10));
+    assert!(fetched.is_stale(41_000_001, 10));
+
+    // A block reported by the backend wins; no extra query is made.
+    let reported = find_path_result_via_rpc(&rpc, params).await.unwrap();
+    assert_eq!(reported.block_number, Some(41_000_000));
+    assert_eq!(server.params_for("circles_query").len(), 1);
+}
+
+#[tokio::test]
+async fn test_find_path_result_survives_a_failed_height_lookup() {
+    use alloy_primitives::U256;
+
+    let sender = common::addresses::sender();
+    let receiver = common::addresses::receiver();
+    let server = circles_rpc_mock::MockRpcServer::builder()
+        .respond(
+            "circlesV2_findPath",
+            circles_rpc_mock::fixtures::path_result(&[(
+                sender,
+                receiver,
+                sender,
+                U256::from(5u64),
+            )]),
+        )
+        .respond_error("circles_query", -32000, "indexer unavailable")
+        .start()
+        .await
+        .expect("mock rpc server");
+    let rpc = CirclesRpc::try_from_http(&server.url()).unwrap();
+    let params = FindPathParams {
+        from: sender,
+        to: receiver,
+        target_flow: U256::from(5u64),
+        use_wrapped_balances: Some(true),
+        from_tokens: None,
+        to_tokens: None,
+        exclude_from_tokens: None,
+        exclude_to_tokens: None,
+        simulated_balances: None,
+        simulated_trusts: None,
+        max_transfers: None,
+    };
+
+    let result = find_path_result_via_rpc(&rpc, params.clone())
+        .await
+        .expect("path kept");
+    assert_eq!(result.block_number, None);
+    assert_eq!(result.transfers.len(), 1);
+
+    let cached = circles_pathfinder::cache::PathCache::in_memory(8)
+        .find_path_via_rpc(&rpc, params, None)
+        .await
+        .expect("cached path kept");
+    assert_eq!(cached.block_number, None);
+}
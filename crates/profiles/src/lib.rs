@@ -1,8 +1,12 @@
 //! Client for the Circles profile service (pin + fetch profile metadata).
 //! Mirrors the minimal behavior of the TypeScript `@profiles` package.
 
-pub use circles_types::{GroupProfile, Profile};
-use reqwest::{Client, StatusCode, Url};
+pub use circles_types::{
+    AuthError, AuthProvider, BearerToken, ErrorKind, GroupProfile, Profile, Timeouts,
+};
+use reqwest::{Client, RequestBuilder, StatusCode, Url};
+use std::fmt;
+use std::sync::Arc;
 use thiserror::Error;
 
 /// Errors that can occur when interacting with the profile service.
@@ -24,16 +28,68 @@ pub enum ProfilesError {
     /// The service returned a non-success status during profile creation.
     #[error("profile creation failed (status {status}): {body}")]
     CreateFailed { status: StatusCode, body: String },
+    /// The configured [`AuthProvider`] could not produce credentials.
+    #[error(transparent)]
+    Auth(#[from] AuthError),
     /// The service responded with an unexpected payload.
     #[error("unexpected response format (status {status}): {body}")]
     DecodeFailed { status: StatusCode, body: String },
 }
 
+impl ProfilesError {
+    /// Coarse classification for retry and UX policies.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::InvalidUrl { .. } | Self::CannotBeABase { .. } | Self::Auth(_) => {
+                ErrorKind::Configuration
+            }
+            Self::Http(err) => reqwest_error_kind(err),
+            Self::CreateFailed { status, .. } => ErrorKind::from_http_status(status.as_u16()),
+            Self::DecodeFailed { .. } => ErrorKind::Protocol,
+        }
+    }
+
+    /// Whether repeating the request later may succeed.
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+
+    /// Whether the caller's input caused the failure.
+    pub fn is_user_error(&self) -> bool {
+        self.kind().is_user_error()
+    }
+}
+
+/// Classify an HTTP client error; shared with the SDK's other HTTP service clients.
+pub fn reqwest_error_kind(err: &reqwest::Error) -> ErrorKind {
+    if err.is_timeout() {
+        ErrorKind::Timeout
+    } else if let Some(status) = err.status() {
+        ErrorKind::from_http_status(status.as_u16())
+    } else if err.is_decode() {
+        ErrorKind::Protocol
+    } else if err.is_builder() {
+        ErrorKind::Configuration
+    } else {
+        ErrorKind::Network
+    }
+}
+
 /// Thin wrapper over the Circles profile service.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Profiles {
     base_url: Url,
     client: Client,
+    auth: Option<Arc<dyn AuthProvider>>,
+}
+
+impl fmt::Debug for Profiles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Profiles")
+            .field("base_url", &self.base_url.as_str())
+            .field("authenticated", &self.auth.is_some())
+            .finish()
+    }
 }
 
 impl Profiles {
@@ -42,19 +98,53 @@ impl Profiles {
         Self::with_client(profile_service_url, Client::new())
     }
 
+    /// Build a client whose requests honor the connect and request limits in `timeouts`.
+    pub fn with_timeouts(
+        profile_service_url: impl AsRef<str>,
+        timeouts: Timeouts,
+    ) -> Result<Self, ProfilesError> {
+        let mut builder = Client::builder();
+        if let Some(connect) = timeouts.connect {
+            builder = builder.connect_timeout(connect);
+        }
+        if let Some(request) = timeouts.request {
+            builder = builder.timeout(request);
+        }
+        Self::with_client(profile_service_url, builder.build()?)
+    }
+
     /// Build a client using a provided Reqwest client (useful for custom middleware or mocks).
     pub fn with_client(
         profile_service_url: impl AsRef<str>,
         client: Client,
     ) -> Result<Self, ProfilesError> {
         let base_url = normalize_base_url(profile_service_url.as_ref())?;
-        Ok(Self { base_url, client })
+        Ok(Self {
+            base_url,
+            client,
+            auth: None,
+        })
+    }
+
+    /// Attach headers from `auth` to every request.
+    pub fn with_auth(mut self, auth: Arc<dyn AuthProvider>) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Send `Authorization: Bearer <token>` with every request.
+    pub fn with_bearer_token(self, token: impl Into<String>) -> Self {
+        self.with_auth(Arc::new(BearerToken::new(token)))
     }
 
     /// Create and pin a profile, returning its CID.
     pub async fn create(&self, profile: &Profile) -> Result<String, ProfilesError> {
         let url = endpoint(&self.base_url, "pin")?;
-        let response = self.client.post(url).json(profile).send().await?;
+        let response = self
+            .authorize(self.client.post(url).json(profile))
+            .await?
+            .send()
+            .await?;
         let status = response.status();
         let body = response.text().await?;
 
@@ -73,7 +163,7 @@ impl Profiles {
         let mut url = endpoint(&self.base_url, "get")?;
         url.query_pairs_mut().append_pair("cid", cid);
 
-        let response = self.client.get(url).send().await?;
+        let response = self.authorize(self.client.get(url)).await?.send().await?;
         let status = response.status();
         let body = response.text().await?;
 
@@ -101,6 +191,19 @@ impl Profiles {
             }
         }
     }
+
+    async fn authorize(&self, request: RequestBuilder) -> Result<RequestBuilder, ProfilesError> {
+        let Some(auth) = &self.auth else {
+            return Ok(request);
+        };
+        Ok(auth
+            .headers()
+            .await?
+            .into_iter()
+            .fold(request, |request, (name, value)| {
+                request.header(name, value)
+            }))
+    }
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -0,0 +1,24 @@
+use circles_profiles::{ErrorKind, Profiles, ProfilesError, Timeouts};
+use std::net::TcpListener;
+use std::time::Duration;
+
+#[tokio::test]
+async fn request_timeout_bounds_unresponsive_service() {
+    // The kernel completes the handshake, but nothing ever reads or answers.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/profiles/", listener.local_addr().unwrap());
+    let timeouts = Timeouts {
+        request: Some(Duration::from_millis(50)),
+        ..Timeouts::none()
+    };
+    let profiles = Profiles::with_timeouts(url, timeouts).expect("client");
+
+    let err = profiles.get("QmMissing").await.unwrap_err();
+    assert!(
+        matches!(&err, ProfilesError::Http(inner) if inner.is_timeout()),
+        "{err:?}"
+    );
+    assert_eq!(err.kind(), ErrorKind::Timeout);
+    assert!(err.is_retryable());
+    drop(listener);
+}
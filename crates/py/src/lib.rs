@@ -0,0 +1,356 @@
+//! Python bindings for Circles pathfinding and indexer queries.
+//!
+//! Exposes `find_path`, `prepare_flow_for_contract`, and a cursor-following `paged_query`
+//! so Python users get the same flow matrices the Rust SDK submits on-chain. Results are
+//! plain dicts/lists with decimal amount strings and `0x` hex bytes.
+//!
+//! Build with the `python` feature (e.g. `maturin develop --features python`) to emit
+//! the `circles_py` extension module; without it the crate is a plain Rust library whose
+//! blocking functions return `serde_json::Value`, which is how the conversions are tested.
+
+use alloy_primitives::aliases::U192;
+use alloy_primitives::{Address, U256};
+use circles_pathfinder::{PathData, PathfinderError};
+use circles_rpc::{CirclesRpc, CirclesRpcError};
+use circles_types::{FindPathParams, PagedQueryParams, SortOrder, TransferStep};
+use serde_json::{Map, Value, json};
+use std::future::Future;
+
+/// Errors raised to Python as `ValueError` (bad input) or `RuntimeError` (everything else).
+#[derive(Debug, thiserror::Error)]
+pub enum BindingError {
+    /// Unparsable address, amount, or sort order.
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+    /// Pathfinding or flow matrix construction failed.
+    #[error(transparent)]
+    Pathfinder(#[from] PathfinderError),
+    /// Indexer query failed.
+    #[error(transparent)]
+    Rpc(#[from] CirclesRpcError),
+    /// The blocking runtime could not be started.
+    #[error("runtime error: {0}")]
+    Runtime(String),
+}
+
+fn parse_address(value: &str, field: &str) -> Result<Address, BindingError> {
+    value
+        .parse()
+        .map_err(|e| BindingError::InvalidInput(format!("{field}: {e}")))
+}
+
+fn parse_amount(value: &str, field: &str) -> Result<U192, BindingError> {
+    value
+        .parse()
+        .map_err(|e| BindingError::InvalidInput(format!("{field}: {e}")))
+}
+
+fn parse_sort_order(value: &str) -> Result<SortOrder, BindingError> {
+    match value.to_ascii_uppercase().as_str() {
+        "ASC" => Ok(SortOrder::ASC),
+        "DESC" => Ok(SortOrder::DESC),
+        other => Err(BindingError::InvalidInput(format!(
+            "sort_order: expected ASC or DESC, got {other}"
+        ))),
+    }
+}
+
+fn block_on<F: Future>(future: F) -> Result<F::Output, BindingError> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| BindingError::Runtime(e.to_string()))?;
+    Ok(runtime.block_on(future))
+}
+
+fn find_path_params(
+    from: &str,
+    to: &str,
+    target_flow: &str,
+    use_wrapped_balances: bool,
+) -> Result<FindPathParams, BindingError> {
+    Ok(FindPathParams {
+        from: parse_address(from, "from_address")?,
+        to: parse_address(to, "to_address")?,
+        target_flow: U256::from(parse_amount(target_flow, "target_flow")?),
+        use_wrapped_balances: Some(use_wrapped_balances),
+        from_tokens: None,
+        to_tokens: None,
+        exclude_from_tokens: None,
+        exclude_to_tokens: None,
+        simulated_balances: None,
+        simulated_trusts: None,
+        max_transfers: None,
+    })
+}
+
+fn transfers_to_json(transfers: &[TransferStep]) -> Value {
+    Value::Array(
+        transfers
+            .iter()
+            .map(|step| {
+                json!({
+                    "from_address": step.from_address,
+                    "to_address": step.to_address,
+                    "token_owner": step.token_owner,
+                    "value": step.value.to_string(),
+                })
+            })
+            .collect(),
+    )
+}
+
+fn path_data_to_json(data: &PathData) -> Value {
+    json!({
+        "flow_vertices": data.flow_vertices,
+        "flow_edges": data
+            .flow_edges
+            .iter()
+            .map(|edge| json!({
+                "stream_sink_id": edge.streamSinkId,
+                "amount": edge.amount.to_string(),
+            }))
+            .collect::<Vec<_>>(),
+        "streams": data
+            .streams
+            .iter()
+            .map(|stream| json!({
+                "source_coordinate": stream.sourceCoordinate,
+                "flow_edge_ids": stream.flowEdgeIds,
+                "data": stream.data,
+            }))
+            .collect::<Vec<_>>(),
+        "packed_coordinates": alloy_primitives::Bytes::from(data.packed_coordinates.clone()),
+        "source_coordinate": data.source_coordinate.to_string(),
+    })
+}
+
+/// Find a path and return the transfer steps as a JSON array.
+pub fn find_path(
+    rpc_url: &str,
+    from: &str,
+    to: &str,
+    target_flow: &str,
+    use_wrapped_balances: bool,
+) -> Result<Value, BindingError> {
+    let params = find_path_params(from, to, target_flow, use_wrapped_balances)?;
+    let transfers = block_on(circles_pathfinder::find_path_with_params(rpc_url, params))??;
+    Ok(transfers_to_json(&transfers))
+}
+
+/// Find a path and return the ABI-ready `PathData` as a JSON object.
+pub fn prepare_flow_for_contract(
+    rpc_url: &str,
+    from: &str,
+    to: &str,
+    target_flow: &str,
+    use_wrapped_balances: bool,
+) -> Result<Value, BindingError> {
+    let params = find_path_params(from, to, target_flow, use_wrapped_balances)?;
+    let data = block_on(circles_pathfinder::prepare_flow_for_contract(
+        rpc_url, params,
+    ))??;
+    Ok(path_data_to_json(&data))
+}
+
+/// Fetch rows from a `circles_query` table, following cursors until the table is
+/// exhausted or `max_rows` rows have been collected.
+pub fn paged_query(
+    rpc_url: &str,
+    namespace: &str,
+    table: &str,
+    columns: Vec<String>,
+    limit: u32,
+    max_rows: Option<usize>,
+    sort_order: &str,
+) -> Result<Vec<Map<String, Value>>, BindingError> {
+    let params = PagedQueryParams::new(
+        namespace.to_string(),
+        table.to_string(),
+        parse_sort_order(sort_order)?,
+        columns,
+        limit,
+    );
+    let rpc = CirclesRpc::try_from_http(rpc_url)?;
+    block_on(async move {
+        let mut query = rpc.paged_query::<Map<String, Value>>(params);
+        let mut rows = Vec::new();
+        while let Some(page) = query.next_page().await? {
+            rows.extend(page.items);
+            if max_rows.is_some_and(|max| rows.len() >= max) || !page.has_more {
+                break;
+            }
+        }
+        if let Some(max) = max_rows {
+            rows.truncate(max);
+        }
+        Ok::<_, BindingError>(rows)
+    })?
+}
+
+#[cfg(feature = "python")]
+mod python {
+    use super::BindingError;
+    use pyo3::exceptions::{PyRuntimeError, PyValueError};
+    use pyo3::prelude::*;
+    use serde_json::Value;
+
+    impl From<BindingError> for PyErr {
+        fn from(err: BindingError) -> Self {
+            match err {
+                BindingError::InvalidInput(_) => PyValueError::new_err(err.to_string()),
+                _ => PyRuntimeError::new_err(err.to_string()),
+            }
+        }
+    }
+
+    fn to_python<'py>(py: Python<'py>, value: &Value) -> PyResult<Bound<'py, PyAny>> {
+        py.import("json")?
+            .call_method1("loads", (value.to_string(),))
+    }
+
+    /// Find a path from `from_address` to `to_address`; returns a list of transfer dicts.
+    #[pyfunction]
+    #[pyo3(signature = (rpc_url, from_address, to_address, target_flow, use_wrapped_balances = true))]
+    fn find_path<'py>(
+        py: Python<'py>,
+        rpc_url: &str,
+        from_address: &str,
+        to_address: &str,
+        target_flow: &str,
+        use_wrapped_balances: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let value = py.detach(|| {
+            super::find_path(
+                rpc_url,
+                from_address,
+                to_address,
+                target_flow,
+                use_wrapped_balances,
+            )
+        })?;
+        to_python(py, &value)
+    }
+
+    /// Find a path and build the flow matrix; returns a `PathData` dict.
+    #[pyfunction]
+    #[pyo3(signature = (rpc_url, from_address, to_address, target_flow, use_wrapped_balances = true))]
+    fn prepare_flow_for_contract<'py>(
+        py: Python<'py>,
+        rpc_url: &str,
+        from_address: &str,
+        to_address: &str,
+        target_flow: &str,
+        use_wrapped_balances: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let value = py.detach(|| {
+            super::prepare_flow_for_contract(
+                rpc_url,
+                from_address,
+                to_address,
+                target_flow,
+                use_wrapped_balances,
+            )
+        })?;
+        to_python(py, &value)
+    }
+
+    /// Fetch rows from a `circles_query` table; returns a list of row dicts.
+    #[pyfunction]
+    #[pyo3(signature = (rpc_url, namespace, table, columns, limit = 100, max_rows = None, sort_order = "DESC"))]
+    #[allow(clippy::too_many_arguments)]
+    fn paged_query<'py>(
+        py: Python<'py>,
+        rpc_url: &str,
+        namespace: &str,
+        table: &str,
+        columns: Vec<String>,
+        limit: u32,
+        max_rows: Option<usize>,
+        sort_order: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let rows = py.detach(|| {
+            super::paged_query(
+                rpc_url, namespace, table, columns, limit, max_rows, sort_order,
+            )
+        })?;
+        to_python(
+            py,
+            &Value::Array(rows.into_iter().map(Value::Object).collect()),
+        )
+    }
+
+    #[pymodule]
+    fn circles_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(find_path, m)?)?;
+        m.add_function(wrap_pyfunction!(prepare_flow_for_contract, m)?)?;
+        m.add_function(wrap_pyfunction!(paged_query, m)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SENDER: &str = "0x1000000000000000000000000000000000000001";
+    const RECEIVER: &str = "0x2000000000000000000000000000000000000002";
+
+    fn single_hop() -> Vec<TransferStep> {
+        vec![TransferStep {
+            from_address: SENDER.parse().unwrap(),
+            to_address: RECEIVER.parse().unwrap(),
+            token_owner: SENDER.parse().unwrap(),
+            value: U192::from(1000u64),
+        }]
+    }
+
+    #[test]
+    fn transfers_serialize_with_decimal_values() {
+        let json = transfers_to_json(&single_hop());
+        assert_eq!(json[0]["value"], "1000");
+        assert_eq!(json[0]["from_address"], SENDER);
+    }
+
+    #[test]
+    fn path_data_serializes_amounts_and_bytes() {
+        let data = PathData::from_transfers(
+            &single_hop(),
+            SENDER.parse().unwrap(),
+            RECEIVER.parse().unwrap(),
+            U192::from(1000u64),
+        )
+        .expect("path data");
+        let json = path_data_to_json(&data);
+
+        assert_eq!(json["flow_edges"][0]["amount"], "1000");
+        assert_eq!(json["flow_vertices"].as_array().unwrap().len(), 2);
+        assert!(
+            json["packed_coordinates"]
+                .as_str()
+                .unwrap()
+                .starts_with("0x")
+        );
+        assert_eq!(
+            json["source_coordinate"],
+            data.source_coordinate.to_string()
+        );
+    }
+
+    #[test]
+    fn invalid_inputs_are_rejected_before_any_request() {
+        assert!(matches!(
+            find_path_params("nope", RECEIVER, "1", true),
+            Err(BindingError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            find_path_params(SENDER, RECEIVER, "1.5", true),
+            Err(BindingError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            parse_sort_order("sideways"),
+            Err(BindingError::InvalidInput(_))
+        ));
+        assert!(matches!(parse_sort_order("asc"), Ok(SortOrder::ASC)));
+    }
+}
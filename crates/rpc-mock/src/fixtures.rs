@@ -0,0 +1,98 @@
+//! Builders for the response payloads of the Circles RPC methods tests usually script.
+
+use alloy_primitives::{Address, U256};
+use circles_types::{
+    AvatarType, CirclesBaseEvent, CirclesEvent, CirclesEventType, CirclesQueryResponse,
+    PathfindingResult, PathfindingTransferStep,
+};
+use serde_json::{Value, json};
+
+/// `circles_query` result with the given columns and rows.
+pub fn query_result(columns: &[&str], rows: Vec<Vec<Value>>) -> Value {
+    serde_json::to_value(CirclesQueryResponse {
+        columns: columns.iter().map(|c| c.to_string()).collect(),
+        rows,
+    })
+    .expect("query response serializes")
+}
+
+/// `circlesV2_findPath` result from `(from, to, token_owner, value)` hops.
+///
+/// `maxFlow` is the sum of the hops that end at the last hop's recipient.
+pub fn path_result(hops: &[(Address, Address, Address, U256)]) -> Value {
+    let sink = hops.last().map(|(_, to, _, _)| *to);
+    let max_flow = hops
+        .iter()
+        .filter(|(_, to, _, _)| Some(*to) == sink)
+        .fold(U256::ZERO, |acc, (_, _, _, value)| acc + value);
+    serde_json::to_value(PathfindingResult {
+        max_flow,
+        transfers: hops
+            .iter()
+            .map(|(from, to, token_owner, value)| PathfindingTransferStep {
+                from: *from,
+                to: *to,
+                token_owner: token_owner.to_string(),
+                value: *value,
+            })
+            .collect(),
+        block_number: None,
+    })
+    .expect("path result serializes")
+}
+
+/// Minimal `circles_getAvatarInfo` result for a registered v2 avatar.
+pub fn avatar_info(avatar: Address, avatar_type: AvatarType) -> Value {
+    let is_human = avatar_type == AvatarType::CrcV2RegisterHuman;
+    json!({
+        "version": 2,
+        "type": avatar_type,
+        "avatar": avatar,
+        "tokenId": U256::from_be_slice(avatar.as_slice()),
+        "hasV1": false,
+        "isHuman": is_human,
+    })
+}
+
+/// Raw `circles_events` entry (`{"event", "values"}`) as the indexer returns it.
+pub fn event(event: &str, values: Value) -> Value {
+    json!({ "event": event, "values": values })
+}
+
+/// Decoded event at block 1, log 0 of transaction 0, timestamped 1000.
+///
+/// Tests that care about ordering or timing adjust `base` afterwards.
+pub fn circles_event(event_type: CirclesEventType, data: Value) -> CirclesEvent {
+    CirclesEvent {
+        base: CirclesBaseEvent {
+            block_number: 1,
+            timestamp: Some(1_000),
+            transaction_index: 0,
+            log_index: 0,
+            transaction_hash: None,
+        },
+        event_type,
+        data: serde_json::from_value(data).expect("event data is a JSON object"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn path_result_sums_flow_into_sink() {
+        let a = address!("1000000000000000000000000000000000000001");
+        let b = address!("2000000000000000000000000000000000000002");
+        let c = address!("3000000000000000000000000000000000000003");
+        let value = path_result(&[
+            (a, b, a, U256::from(5u64)),
+            (a, c, a, U256::from(3u64)),
+            (b, c, b, U256::from(5u64)),
+        ]);
+        let parsed: PathfindingResult = serde_json::from_value(value).expect("decode");
+        assert_eq!(parsed.max_flow, U256::from(8u64));
+        assert_eq!(parsed.transfers.len(), 3);
+    }
+}
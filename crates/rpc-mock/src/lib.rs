@@ -0,0 +1,346 @@
+//! Deterministic JSON-RPC mock server for Circles integration tests.
+//!
+//! [`MockRpcServer`] binds a local port and answers JSON-RPC POSTs from a fixed set of
+//! fixtures, so tests exercising `circles_query`, `circlesV2_findPath`, or `circles_events`
+//! run offline and always see the same data. Every request is recorded for assertions.
+//!
+//! The server speaks just enough HTTP/1.1 for `reqwest`/Alloy HTTP transports
+//! (`Content-Length` bodies, keep-alive) and handles batch requests.
+
+pub mod fixtures;
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::io;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// A request received by the mock server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedRequest {
+    /// JSON-RPC method name.
+    pub method: String,
+    /// Raw params (`null` when omitted).
+    pub params: Value,
+    /// HTTP headers of the carrying request, names lowercased.
+    pub headers: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+enum Reply {
+    Result(Value),
+    Error { code: i64, message: String },
+}
+
+#[derive(Debug, Clone)]
+struct Fixture {
+    method: String,
+    params: Option<Value>,
+    reply: Reply,
+    served: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct FixtureRecord {
+    method: String,
+    #[serde(default)]
+    params: Option<Value>,
+    #[serde(default)]
+    result: Value,
+    #[serde(default)]
+    error: Option<FixtureError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FixtureError {
+    code: i64,
+    message: String,
+}
+
+/// Collects fixtures before starting a [`MockRpcServer`].
+#[derive(Debug, Clone, Default)]
+pub struct MockRpcBuilder {
+    fixtures: Vec<Fixture>,
+}
+
+impl MockRpcBuilder {
+    fn push(mut self, method: impl Into<String>, params: Option<Value>, reply: Reply) -> Self {
+        self.fixtures.push(Fixture {
+            method: method.into(),
+            params,
+            reply,
+            served: 0,
+        });
+        self
+    }
+
+    /// Answer calls to `method` with `result`, whatever the params.
+    pub fn respond(self, method: impl Into<String>, result: Value) -> Self {
+        self.push(method, None, Reply::Result(result))
+    }
+
+    /// Answer calls to `method` whose params equal `params` exactly.
+    ///
+    /// Param-specific fixtures take precedence over [`respond`](Self::respond).
+    pub fn respond_to(self, method: impl Into<String>, params: Value, result: Value) -> Self {
+        self.push(method, Some(params), Reply::Result(result))
+    }
+
+    /// Answer calls to `method` with a JSON-RPC error.
+    pub fn respond_error(
+        self,
+        method: impl Into<String>,
+        code: i64,
+        message: impl Into<String>,
+    ) -> Self {
+        self.push(
+            method,
+            None,
+            Reply::Error {
+                code,
+                message: message.into(),
+            },
+        )
+    }
+
+    /// Load fixtures from a JSON array of `{"method", "params"?, "result" | "error"}` records.
+    pub fn fixtures_json(mut self, json: &str) -> Result<Self, serde_json::Error> {
+        let records: Vec<FixtureRecord> = serde_json::from_str(json)?;
+        for record in records {
+            let reply = match record.error {
+                Some(err) => Reply::Error {
+                    code: err.code,
+                    message: err.message,
+                },
+                None => Reply::Result(record.result),
+            };
+            self = self.push(record.method, record.params, reply);
+        }
+        Ok(self)
+    }
+
+    /// Load fixtures from a file in the [`fixtures_json`](Self::fixtures_json) format.
+    pub fn fixtures_file(self, path: impl AsRef<Path>) -> io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        self.fixtures_json(&json)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Bind `127.0.0.1` on a random port and start serving.
+    pub async fn start(self) -> io::Result<MockRpcServer> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(State {
+            fixtures: Mutex::new(self.fixtures),
+            requests: Mutex::new(Vec::new()),
+        });
+        let accept_state = state.clone();
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = accept_state.clone();
+                tokio::spawn(async move {
+                    let _ = serve_connection(stream, state).await;
+                });
+            }
+        });
+        Ok(MockRpcServer { addr, state, task })
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    fixtures: Mutex<Vec<Fixture>>,
+    requests: Mutex<Vec<RecordedRequest>>,
+}
+
+impl State {
+    fn reply_for(&self, method: &str, params: &Value) -> Reply {
+        let mut fixtures = self.fixtures.lock().expect("lock");
+        let exact: Vec<usize> = fixtures
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| f.method == method && f.params.as_ref() == Some(params))
+            .map(|(i, _)| i)
+            .collect();
+        let candidates = if exact.is_empty() {
+            fixtures
+                .iter()
+                .enumerate()
+                .filter(|(_, f)| f.method == method && f.params.is_none())
+                .map(|(i, _)| i)
+                .collect()
+        } else {
+            exact
+        };
+        // Serve fixtures in order; once all were used, keep repeating the last one.
+        let Some(index) = candidates
+            .iter()
+            .copied()
+            .find(|&i| fixtures[i].served == 0)
+            .or_else(|| candidates.last().copied())
+        else {
+            return Reply::Error {
+                code: -32601,
+                message: format!("no fixture for method {method}"),
+            };
+        };
+        fixtures[index].served += 1;
+        fixtures[index].reply.clone()
+    }
+
+    fn handle(&self, request: &Value, headers: &[(String, String)]) -> Value {
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+        let Some(method) = request.get("method").and_then(Value::as_str) else {
+            return json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": -32600, "message": "invalid request" },
+            });
+        };
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+        self.requests.lock().expect("lock").push(RecordedRequest {
+            method: method.to_string(),
+            params: params.clone(),
+            headers: headers.to_vec(),
+        });
+        match self.reply_for(method, &params) {
+            Reply::Result(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Reply::Error { code, message } => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": message },
+            }),
+        }
+    }
+
+    fn handle_body(&self, body: &[u8], headers: &[(String, String)]) -> Value {
+        match serde_json::from_slice::<Value>(body) {
+            Ok(Value::Array(batch)) => Value::Array(
+                batch
+                    .iter()
+                    .map(|request| self.handle(request, headers))
+                    .collect(),
+            ),
+            Ok(request) => self.handle(&request, headers),
+            Err(e) => json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": { "code": -32700, "message": e.to_string() },
+            }),
+        }
+    }
+}
+
+async fn serve_connection(stream: TcpStream, state: Arc<State>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(());
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut content_length = 0usize;
+        let mut headers = Vec::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).await? == 0 || line.trim().is_empty() {
+                break;
+            }
+            let Some((name, value)) = line.split_once(':') else {
+                continue;
+            };
+            let (name, value) = (name.trim().to_ascii_lowercase(), value.trim().to_string());
+            if name == "content-length" {
+                content_length = value.parse().map_err(|_| {
+                    io::Error::new(io::ErrorKind::InvalidData, "bad content-length")
+                })?;
+            }
+            headers.push((name, value));
+        }
+
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body).await?;
+        let response = state.handle_body(&body, &headers).to_string();
+
+        let stream = reader.get_mut();
+        stream
+            .write_all(
+                format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n",
+                    response.len()
+                )
+                .as_bytes(),
+            )
+            .await?;
+        stream.write_all(response.as_bytes()).await?;
+        stream.flush().await?;
+    }
+}
+
+/// Running mock server; stops when dropped.
+#[derive(Debug)]
+pub struct MockRpcServer {
+    addr: SocketAddr,
+    state: Arc<State>,
+    task: JoinHandle<()>,
+}
+
+impl MockRpcServer {
+    /// Start collecting fixtures.
+    pub fn builder() -> MockRpcBuilder {
+        MockRpcBuilder::default()
+    }
+
+    /// Socket address the server is bound to.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// HTTP URL to hand to `CirclesRpc::try_from_http` or the pathfinder helpers.
+    pub fn url(&self) -> String {
+        format!("http://{}/", self.addr)
+    }
+
+    /// Every request received so far, in arrival order.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.requests.lock().expect("lock").clone()
+    }
+
+    /// Params of every request to `method`, in arrival order.
+    pub fn params_for(&self, method: &str) -> Vec<Value> {
+        self.requests()
+            .into_iter()
+            .filter(|r| r.method == method)
+            .map(|r| r.params)
+            .collect()
+    }
+
+    /// Value of header `name` (case-insensitive) on every request to `method`.
+    pub fn header_for(&self, method: &str, name: &str) -> Vec<Option<String>> {
+        let name = name.to_ascii_lowercase();
+        self.requests()
+            .into_iter()
+            .filter(|r| r.method == method)
+            .map(|r| {
+                r.headers
+                    .into_iter()
+                    .find(|(header, _)| *header == name)
+                    .map(|(_, value)| value)
+            })
+            .collect()
+    }
+}
+
+impl Drop for MockRpcServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
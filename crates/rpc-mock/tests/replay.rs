@@ -0,0 +1,122 @@
+use alloy_primitives::{U256, address};
+use circles_rpc::CirclesRpc;
+use circles_rpc_mock::{MockRpcServer, fixtures};
+use circles_types::{FindPathParams, PagedQueryParams, SortOrder};
+use serde_json::{Map, Value, json};
+
+fn row(block: u64, avatar: &str) -> Vec<Value> {
+    vec![json!(block), json!(0), json!(0), json!(avatar)]
+}
+
+#[tokio::test]
+async fn paged_query_replays_pages_in_order() {
+    let columns = ["blockNumber", "transactionIndex", "logIndex", "avatar"];
+    let server = MockRpcServer::builder()
+        .respond(
+            "circles_query",
+            fixtures::query_result(&columns, vec![row(3, "0xa"), row(2, "0xb")]),
+        )
+        .respond(
+            "circles_query",
+            fixtures::query_result(&columns, vec![row(1, "0xc")]),
+        )
+        .start()
+        .await
+        .expect("server");
+
+    let rpc = CirclesRpc::try_from_http(&server.url()).expect("rpc");
+    let params = PagedQueryParams::new(
+        "V_CrcV2".to_string(),
+        "Avatars".to_string(),
+        SortOrder::DESC,
+        columns.iter().map(|c| c.to_string()).collect(),
+        2,
+    );
+    let mut query = rpc.paged_query::<Map<String, Value>>(params);
+
+    let first = query.next_page().await.expect("page").expect("rows");
+    assert_eq!(first.items.len(), 2);
+    assert!(first.has_more);
+    let second = query.next_page().await.expect("page").expect("rows");
+    assert_eq!(second.items[0]["avatar"], "0xc");
+    assert!(!second.has_more);
+
+    let sent = server.params_for("circles_query");
+    assert_eq!(sent.len(), 2);
+    assert!(
+        sent[1][0]["Filter"]
+            .as_array()
+            .is_some_and(|f| !f.is_empty()),
+        "second page should carry a cursor filter"
+    );
+}
+
+#[tokio::test]
+async fn find_path_uses_param_specific_fixture() {
+    let a = address!("1000000000000000000000000000000000000001");
+    let b = address!("2000000000000000000000000000000000000002");
+    let params = FindPathParams {
+        from: a,
+        to: b,
+        target_flow: U256::from(7u64),
+        use_wrapped_balances: Some(true),
+        from_tokens: None,
+        to_tokens: None,
+        exclude_from_tokens: None,
+        exclude_to_tokens: None,
+        simulated_balances: None,
+        simulated_trusts: None,
+        max_transfers: None,
+    };
+    let server = MockRpcServer::builder()
+        .respond("circlesV2_findPath", fixtures::path_result(&[]))
+        .respond_to(
+            "circlesV2_findPath",
+            json!([params.clone()]),
+            fixtures::path_result(&[(a, b, a, U256::from(7u64))]),
+        )
+        .start()
+        .await
+        .expect("server");
+
+    let rpc = CirclesRpc::try_from_http(&server.url()).expect("rpc");
+    let result = rpc.pathfinder().find_path(params).await.expect("path");
+    assert_eq!(result.max_flow, U256::from(7u64));
+    assert_eq!(result.transfers.len(), 1);
+}
+
+#[tokio::test]
+async fn unknown_methods_and_scripted_errors_fail() {
+    let server = MockRpcServer::builder()
+        .respond_error("circles_health", -32000, "indexer down")
+        .start()
+        .await
+        .expect("server");
+    let rpc = CirclesRpc::try_from_http(&server.url()).expect("rpc");
+
+    let health = rpc.health().health().await;
+    assert!(health.unwrap_err().to_string().contains("indexer down"));
+
+    let tables = rpc.tables().tables().await;
+    assert!(tables.is_err());
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn fixtures_load_from_json() {
+    let server = MockRpcServer::builder()
+        .fixtures_json(
+            r#"[
+                {"method": "circles_health", "result": {"status": "ok"}},
+                {"method": "circles_tables", "error": {"code": -32000, "message": "boom"}}
+            ]"#,
+        )
+        .expect("fixtures")
+        .start()
+        .await
+        .expect("server");
+    let rpc = CirclesRpc::try_from_http(&server.url()).expect("rpc");
+
+    assert!(rpc.tables().tables().await.is_err());
+    assert_eq!(server.params_for("circles_tables").len(), 1);
+}
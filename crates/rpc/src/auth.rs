@@ -0,0 +1,105 @@
+//! Authenticated HTTP transport for deployments that require API keys or signed requests.
+//!
+//! [`AuthHttp`] posts JSON-RPC packets like Alloy's reqwest transport, but asks an
+//! [`AuthProvider`] for headers before every request.
+
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_provider::transport::{
+    TransportError, TransportErrorKind, TransportFut, TransportResult,
+};
+use circles_types::AuthProvider;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::fmt;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::Service;
+
+/// HTTP JSON-RPC transport that injects [`AuthProvider`] headers per request.
+#[derive(Clone)]
+pub struct AuthHttp {
+    client: reqwest::Client,
+    url: reqwest::Url,
+    auth: Arc<dyn AuthProvider>,
+}
+
+impl AuthHttp {
+    pub fn new(url: reqwest::Url, auth: Arc<dyn AuthProvider>) -> Self {
+        Self::with_client(reqwest::Client::new(), url, auth)
+    }
+
+    /// Use a pre-configured reqwest client (timeouts, proxies, TLS).
+    pub fn with_client(
+        client: reqwest::Client,
+        url: reqwest::Url,
+        auth: Arc<dyn AuthProvider>,
+    ) -> Self {
+        Self { client, url, auth }
+    }
+
+    /// Endpoint URL.
+    pub fn url(&self) -> &reqwest::Url {
+        &self.url
+    }
+
+    async fn send(self, request: RequestPacket) -> TransportResult<ResponsePacket> {
+        let headers = self
+            .auth
+            .headers()
+            .await
+            .map_err(TransportErrorKind::custom)?;
+        let response = self
+            .client
+            .post(self.url)
+            .headers(header_map(headers)?)
+            .json(&request)
+            .send()
+            .await
+            .map_err(TransportErrorKind::custom)?;
+        let status = response.status();
+        let body = response.bytes().await.map_err(TransportErrorKind::custom)?;
+        if !status.is_success() {
+            return Err(TransportErrorKind::http_error(
+                status.as_u16(),
+                String::from_utf8_lossy(&body).into_owned(),
+            ));
+        }
+        serde_json::from_slice(&body)
+            .map_err(|err| TransportError::deser_err(err, String::from_utf8_lossy(&body)))
+    }
+}
+
+impl fmt::Debug for AuthHttp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthHttp")
+            .field("url", &self.url.as_str())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Service<RequestPacket> for AuthHttp {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        Box::pin(self.clone().send(request))
+    }
+}
+
+fn header_map(headers: Vec<(String, String)>) -> TransportResult<HeaderMap> {
+    let mut map = HeaderMap::with_capacity(headers.len());
+    for (name, value) in headers {
+        let name = HeaderName::try_from(name.as_str())
+            .map_err(|e| TransportErrorKind::custom_str(&format!("invalid header {name}: {e}")))?;
+        let mut value = HeaderValue::try_from(value).map_err(|e| {
+            TransportErrorKind::custom_str(&format!("invalid value for header {name}: {e}"))
+        })?;
+        value.set_sensitive(true);
+        map.append(name, value);
+    }
+    Ok(map)
+}
@@ -1,31 +1,114 @@
+use crate::auth::AuthHttp;
 use crate::error::{CirclesRpcError, Result};
+use crate::failover::{FailoverPolicy, FailoverPool};
+#[cfg(feature = "fixtures")]
+use crate::fixtures::{FixtureMode, FixtureStore};
+use crate::metrics;
+use crate::rate_limit::{RateLimit, RateLimiter};
 use alloy_json_rpc::{RpcRecv, RpcSend};
 #[cfg(feature = "ws")]
 use alloy_provider::GetSubscription;
+use alloy_provider::transport::utils::guess_local_url;
+use alloy_provider::transport::{IntoBoxTransport, TransportErrorKind};
 use alloy_provider::{Identity, Provider, ProviderBuilder, RootProvider};
 #[cfg(feature = "ws")]
 use alloy_transport_ws::WsConnect;
+use circles_types::{AuthProvider, BearerToken, StaticHeaders, Timeouts};
 use serde::de::DeserializeOwned;
 use std::borrow::Cow;
+use std::sync::Arc;
+use std::time::Instant;
 
-/// Thin wrapper around an Alloy provider. This will be expanded with WebSocket support
-/// and reconnection logic as we port the TypeScript client behavior.
+/// Thin wrapper around an Alloy provider, optionally spread over several HTTP endpoints
+/// with health-aware failover.
 #[derive(Clone, Debug)]
 pub struct RpcClient {
     provider: RootProvider,
+    failover: Option<Arc<FailoverPool>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    timeouts: Timeouts,
+    #[cfg(feature = "fixtures")]
+    fixtures: Option<Arc<FixtureStore>>,
 }
 
 impl RpcClient {
     /// Create a client from an existing provider.
     pub fn new(provider: RootProvider) -> Self {
-        Self { provider }
+        Self {
+            provider,
+            failover: None,
+            rate_limiter: None,
+            timeouts: Timeouts::none(),
+            #[cfg(feature = "fixtures")]
+            fixtures: None,
+        }
     }
 
     /// Build a client from an HTTP URL using the vanilla provider (no fillers).
     pub fn http(url: reqwest::Url) -> Self {
         let provider: RootProvider =
             ProviderBuilder::<Identity, Identity>::default().connect_http(url);
-        Self { provider }
+        Self::new(provider)
+    }
+
+    /// Build an HTTP client on a pre-configured reqwest client (proxies, TLS roots or
+    /// pinning, timeouts, default headers). Combine a client with an [`AuthProvider`]
+    /// through [`AuthHttp::with_client`] and [`from_transport`](Self::from_transport).
+    pub fn http_with_client(url: reqwest::Url, client: reqwest::Client) -> Self {
+        // Alloy's HTTP transport pins its own reqwest version, so go through ours.
+        let is_local = guess_local_url(url.as_str());
+        let transport = AuthHttp::with_client(client, url, Arc::new(StaticHeaders::new()));
+        Self::from_transport(transport, is_local)
+    }
+
+    /// Build an HTTP client honoring every limit in `timeouts`, including the connect
+    /// timeout (which needs its own reqwest client).
+    pub fn http_with_timeouts(url: reqwest::Url, timeouts: Timeouts) -> Result<Self> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(connect) = timeouts.connect {
+            builder = builder.connect_timeout(connect);
+        }
+        let client = builder
+            .build()
+            .map_err(|err| CirclesRpcError::Transport(TransportErrorKind::custom(err)))?;
+        Ok(Self::http_with_client(url, client).with_timeouts(timeouts))
+    }
+
+    /// Build a client on any Alloy transport, e.g. a Unix-socket bridge or an in-memory
+    /// tower service that answers requests in tests without a network.
+    ///
+    /// `is_local` tunes Alloy's polling interval for local nodes.
+    pub fn from_transport(transport: impl IntoBoxTransport, is_local: bool) -> Self {
+        Self::new(RootProvider::new(alloy_rpc_client::RpcClient::new(
+            transport, is_local,
+        )))
+    }
+
+    /// Build an HTTP client that asks `auth` for headers before every request.
+    pub fn http_with_auth(url: reqwest::Url, auth: Arc<dyn AuthProvider>) -> Self {
+        let is_local = guess_local_url(url.as_str());
+        Self::from_transport(AuthHttp::new(url, auth), is_local)
+    }
+
+    /// Build an HTTP client sending `Authorization: Bearer <token>`.
+    pub fn http_with_bearer(url: reqwest::Url, token: impl Into<String>) -> Self {
+        Self::http_with_auth(url, Arc::new(BearerToken::new(token)))
+    }
+
+    /// Build a client over several HTTP endpoints with automatic failover.
+    ///
+    /// Requests go to the last endpoint that answered; transport failures put the
+    /// endpoint into cooldown per `policy` and retry the request on the next one.
+    pub fn http_with_failover(urls: Vec<reqwest::Url>, policy: FailoverPolicy) -> Result<Self> {
+        let pool = FailoverPool::new(urls, policy)?;
+        Ok(Self {
+            provider: pool.primary_provider().clone(),
+            failover: Some(Arc::new(pool)),
+            rate_limiter: None,
+            timeouts: Timeouts::none(),
+            #[cfg(feature = "fixtures")]
+            fixtures: None,
+        })
     }
 
     /// Build a client from a WebSocket URL (requires the `ws` feature).
@@ -34,26 +117,129 @@ impl RpcClient {
         let provider: RootProvider = ProviderBuilder::<Identity, Identity>::default()
             .connect_ws(WsConnect::new(url.to_string()))
             .await?;
-        Ok(Self { provider })
+        Ok(Self::new(provider))
+    }
+
+    /// Throttle requests to `limit`; clones of the returned client share the same bucket.
+    pub fn with_rate_limit(mut self, limit: RateLimit) -> Self {
+        self.rate_limiter = Some(Arc::new(RateLimiter::new(limit)));
+        self
+    }
+
+    /// Bound every request by `timeouts.request` and subscriptions by `timeouts.ws_idle`.
+    ///
+    /// The connect timeout belongs to the transport and is only applied by
+    /// [`http_with_timeouts`](Self::http_with_timeouts); pathfinder requests follow the
+    /// pathfinder config instead (see `CirclesRpc::with_timeouts`).
+    pub fn with_timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Limits installed with [`with_timeouts`](Self::with_timeouts); unbounded by default.
+    pub fn timeouts(&self) -> &Timeouts {
+        &self.timeouts
+    }
+
+    /// Rate limiter installed with [`with_rate_limit`](Self::with_rate_limit), if any.
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_deref()
+    }
+
+    /// Record responses to, or replay them from, `store` (requires the `fixtures` feature).
+    #[cfg(feature = "fixtures")]
+    pub fn with_fixtures(mut self, store: FixtureStore) -> Self {
+        self.fixtures = Some(Arc::new(store));
+        self
+    }
+
+    /// Fixture store attached with [`with_fixtures`](Self::with_fixtures), if any.
+    #[cfg(feature = "fixtures")]
+    pub fn fixtures(&self) -> Option<&FixtureStore> {
+        self.fixtures.as_deref()
     }
 
     /// Perform a JSON-RPC call using typed params and response.
+    ///
+    /// Fails with [`CirclesRpcError::RequestTimeout`] when the configured request timeout
+    /// elapses first.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.rpc", skip_all, fields(rpc_method = %method)))]
     pub async fn call<Req, Resp>(&self, method: &str, params: Req) -> Result<Resp>
     where
         Req: RpcSend,
         Resp: RpcRecv + DeserializeOwned,
     {
-        let method: Cow<'static, str> = Cow::Owned(method.to_string());
-        self.provider
-            .raw_request(method, params)
+        let Some(after) = self.timeouts.request else {
+            return self.call_unbounded(method, params).await;
+        };
+        tokio::time::timeout(after, self.call_unbounded(method, params))
             .await
-            .map_err(CirclesRpcError::from)
+            .map_err(|_| CirclesRpcError::RequestTimeout {
+                method: method.to_string(),
+                after,
+            })?
+    }
+
+    /// [`call`](Self::call) without the request timeout, for methods with their own limit.
+    pub(crate) async fn call_unbounded<Req, Resp>(&self, method: &str, params: Req) -> Result<Resp>
+    where
+        Req: RpcSend,
+        Resp: RpcRecv + DeserializeOwned,
+    {
+        #[cfg(feature = "fixtures")]
+        if let Some(store) = &self.fixtures {
+            return match store.mode() {
+                FixtureMode::Replay => store.load(method, &params),
+                FixtureMode::Record => {
+                    let result: serde_json::Value = self.send(method, params.clone()).await?;
+                    store.save(method, &params, &result)?;
+                    Ok(serde_json::from_value(result)?)
+                }
+            };
+        }
+        self.send(method, params).await
+    }
+
+    async fn send<Req, Resp>(&self, method: &str, params: Req) -> Result<Resp>
+    where
+        Req: RpcSend,
+        Resp: RpcRecv,
+    {
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire().await;
+        }
+        let started = Instant::now();
+        let method: Cow<'static, str> = Cow::Owned(method.to_string());
+        let result = match &self.failover {
+            Some(pool) => pool
+                .run(|provider| {
+                    let method = method.clone();
+                    let params = params.clone();
+                    async move { provider.raw_request(method, params).await }
+                })
+                .await
+                .map_err(CirclesRpcError::from),
+            None => self
+                .provider
+                .raw_request(method.clone(), params)
+                .await
+                .map_err(CirclesRpcError::from),
+        };
+        metrics::record_request(&method, result.is_ok(), started.elapsed());
+        result
     }
 
     /// Access the inner provider. This is useful for lower-level calls or subscriptions.
+    ///
+    /// With failover enabled this is the first configured endpoint.
     pub fn provider(&self) -> &RootProvider {
         &self.provider
     }
+
+    /// Failover pool, when the client was built with several endpoints.
+    pub fn failover(&self) -> Option<&FailoverPool> {
+        self.failover.as_deref()
+    }
 }
 
 #[cfg(feature = "ws")]
@@ -0,0 +1,162 @@
+//! Column lists inferred from a row type's serde field names.
+//!
+//! `#[derive(Deserialize)]` passes the (renamed) field names of a struct to
+//! `Deserializer::deserialize_struct`. [`row_columns`] runs the derived impl against a probe
+//! deserializer that only captures that list, so the columns requested from `circles_query`
+//! always match what the row type will read back.
+//!
+//! That list also holds every `#[serde(alias)]` name, sorted in with the real ones, and
+//! aliases are not columns. The probe therefore also counts the struct's fields through
+//! its derived `visit_seq` and only trusts the list when the two agree.
+
+use serde::de::{self, DeserializeOwned, Visitor};
+use std::cell::Cell;
+use std::fmt;
+
+/// Serialized field names of `T`, or `None` when `T` accepts or needs any column: maps,
+/// `serde_json::Value`, `#[serde(flatten)]` structs, and structs with `#[serde(alias)]`
+/// fields, whose real columns cannot be told apart from their aliases.
+///
+/// Fields marked `#[serde(skip)]` are left out.
+pub fn row_columns<T: DeserializeOwned>() -> Option<Vec<String>> {
+    let probed = Cell::new(None);
+    let _ = T::deserialize(FieldProbe { probed: &probed });
+    match probed.get() {
+        Some((fields, Some(declared))) if declared == fields.len() => {
+            Some(fields.iter().map(|field| field.to_string()).collect())
+        }
+        _ => None,
+    }
+}
+
+/// Field names from `deserialize_struct` and the declared field count, if it was found.
+type Probed = Option<(&'static [&'static str], Option<usize>)>;
+
+struct FieldProbe<'a> {
+    probed: &'a Cell<Probed>,
+}
+
+/// Probe error; keeps what `invalid_length` expected, e.g. `struct Row with 3 elements`.
+#[derive(Debug)]
+struct ProbeError(Option<String>);
+
+impl fmt::Display for ProbeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("column probe")
+    }
+}
+
+impl std::error::Error for ProbeError {}
+
+impl de::Error for ProbeError {
+    fn custom<T: fmt::Display>(_msg: T) -> Self {
+        Self(None)
+    }
+
+    fn invalid_length(_len: usize, expected: &dyn de::Expected) -> Self {
+        Self(Some(expected.to_string()))
+    }
+}
+
+/// A sequence without elements: the derived `visit_seq` fails at the first field without
+/// a default and names the struct's field count.
+struct NoElements;
+
+impl<'de> de::SeqAccess<'de> for NoElements {
+    type Error = ProbeError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        _seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        Ok(None)
+    }
+}
+
+/// Field count from serde_derive's `struct Row with N elements` expectation.
+fn declared_fields(expected: &str) -> Option<usize> {
+    let (_, count) = expected.rsplit_once(" with ")?;
+    count.split(' ').next()?.parse().ok()
+}
+
+impl<'de> de::Deserializer<'de> for FieldProbe<'_> {
+    type Error = ProbeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let declared = match visitor.visit_seq(NoElements) {
+            Err(ProbeError(Some(expected))) => declared_fields(&expected),
+            _ => None,
+        };
+        self.probed.set(Some((fields, declared)));
+        Err(de::Error::custom("fields captured"))
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::{Map, Value};
+
+    #[allow(dead_code)]
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct TransferRow {
+        block_number: u64,
+        #[serde(rename = "from")]
+        sender: String,
+        #[serde(skip)]
+        cached: Option<u64>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Deserialize)]
+    struct Wrapped(TransferRow);
+
+    #[allow(dead_code)]
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct AliasedRow {
+        #[serde(default)]
+        account: Option<String>,
+        #[serde(alias = "token_address")]
+        token_address: String,
+    }
+
+    #[test]
+    fn struct_fields_follow_serde_renames() {
+        let expected = Some(vec!["blockNumber".to_string(), "from".to_string()]);
+        assert_eq!(row_columns::<TransferRow>(), expected);
+        assert_eq!(row_columns::<Wrapped>(), expected);
+    }
+
+    #[test]
+    fn untyped_and_aliased_rows_accept_any_column() {
+        assert_eq!(row_columns::<Value>(), None);
+        assert_eq!(row_columns::<Map<String, Value>>(), None);
+        assert_eq!(row_columns::<AliasedRow>(), None);
+        assert_eq!(row_columns::<circles_types::TokenHolder>(), None);
+    }
+}
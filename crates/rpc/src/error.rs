@@ -1,4 +1,5 @@
-use alloy_provider::transport::TransportError;
+use alloy_provider::transport::{RpcError, TransportError, TransportErrorKind};
+use circles_types::ErrorKind;
 use thiserror::Error;
 
 /// Result alias for the Circles RPC crate.
@@ -19,4 +20,124 @@ pub enum CirclesRpcError {
     /// WebSocket subscription closed unexpectedly.
     #[error("subscription closed")]
     SubscriptionClosed,
+    /// A buffered subscription with `OverflowPolicy::Error` ran out of space.
+    #[error("subscription buffer overflowed (capacity {capacity})")]
+    SubscriptionOverflow { capacity: usize },
+    /// A request exceeded its configured time limit.
+    #[error("{method} timed out after {after:?}")]
+    Timeout {
+        method: &'static str,
+        after: std::time::Duration,
+    },
+    /// A request exceeded the client's [`Timeouts::request`](circles_types::Timeouts) limit.
+    #[error("{method} request timed out after {after:?}")]
+    RequestTimeout {
+        method: String,
+        after: std::time::Duration,
+    },
+    /// A `circles_events` range still hit the truncation threshold at the smallest chunk,
+    /// so some of its events may be missing.
+    #[error(
+        "circles_events returned {count} events for blocks {from_block}..={to_block}, the result is likely truncated"
+    )]
+    TruncatedEvents {
+        from_block: u64,
+        to_block: u64,
+        count: usize,
+    },
+    /// A fixture file could not be read or written (e.g. no recording for a replayed call).
+    #[error("fixture {}: {message}", path.display())]
+    Fixture {
+        path: std::path::PathBuf,
+        message: String,
+    },
+}
+
+impl CirclesRpcError {
+    /// Coarse classification for retry and UX policies.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Transport(err) => transport_kind(err),
+            Self::Serde(_) | Self::InvalidResponse { .. } | Self::TruncatedEvents { .. } => {
+                ErrorKind::Protocol
+            }
+            // Subscriptions end when the socket drops; resubscribing is the recovery.
+            Self::SubscriptionClosed => ErrorKind::Network,
+            Self::SubscriptionOverflow { .. } => ErrorKind::Other,
+            Self::Timeout { .. } | Self::RequestTimeout { .. } => ErrorKind::Timeout,
+            Self::Fixture { .. } => ErrorKind::Configuration,
+        }
+    }
+
+    /// Whether repeating the request later may succeed.
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+
+    /// Whether the caller's input caused the failure.
+    pub fn is_user_error(&self) -> bool {
+        self.kind().is_user_error()
+    }
+}
+
+fn transport_kind(err: &TransportError) -> ErrorKind {
+    match err {
+        RpcError::Transport(TransportErrorKind::HttpError(http)) => {
+            ErrorKind::from_http_status(http.status)
+        }
+        RpcError::Transport(TransportErrorKind::PubsubUnavailable) => ErrorKind::Configuration,
+        RpcError::Transport(_) | RpcError::NullResp => ErrorKind::Network,
+        RpcError::ErrorResp(payload) if payload.is_retry_err() => ErrorKind::RateLimited,
+        RpcError::ErrorResp(payload) => match payload.code {
+            // Invalid request / invalid params.
+            -32600 | -32602 => ErrorKind::InvalidInput,
+            // Method not found: the endpoint does not serve the Circles namespace.
+            -32601 => ErrorKind::Configuration,
+            // `eth_call` / `eth_estimateGas` revert.
+            3 => ErrorKind::Rejected,
+            _ => ErrorKind::Protocol,
+        },
+        RpcError::UnsupportedFeature(_) | RpcError::LocalUsageError(_) => ErrorKind::Configuration,
+        RpcError::DeserError { .. } => ErrorKind::Protocol,
+        _ => ErrorKind::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::ErrorPayload;
+
+    #[test]
+    fn transport_errors_are_classified_by_status_and_code() {
+        let rate_limited =
+            CirclesRpcError::from(TransportErrorKind::http_error(429, String::new()));
+        assert_eq!(rate_limited.kind(), ErrorKind::RateLimited);
+        assert!(rate_limited.is_retryable());
+
+        let gone = CirclesRpcError::from(TransportErrorKind::backend_gone());
+        assert_eq!(gone.kind(), ErrorKind::Network);
+
+        let invalid = CirclesRpcError::from(TransportError::ErrorResp(ErrorPayload {
+            code: -32602,
+            message: "invalid params".into(),
+            data: None,
+        }));
+        assert_eq!(invalid.kind(), ErrorKind::InvalidInput);
+        assert!(invalid.is_user_error());
+        assert!(!invalid.is_retryable());
+
+        let timeout = CirclesRpcError::RequestTimeout {
+            method: "circles_query".into(),
+            after: std::time::Duration::from_secs(1),
+        };
+        assert!(timeout.is_retryable());
+        assert_eq!(
+            CirclesRpcError::InvalidResponse {
+                message: "empty".into()
+            }
+            .kind(),
+            ErrorKind::Protocol
+        );
+    }
 }
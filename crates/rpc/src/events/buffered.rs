@@ -0,0 +1,291 @@
+//! Bounded buffering between a subscription and a slow consumer.
+
+use crate::error::{CirclesRpcError, Result};
+use crate::metrics;
+use futures::{Stream, StreamExt};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+/// What a [`BufferedSubscription`] does when its buffer is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Evict the oldest buffered item to make room for the new one.
+    DropOldest,
+    /// Stop reading from the upstream stream until the consumer catches up.
+    ///
+    /// Memory stays bounded here; the transport's own subscription buffer may still lag
+    /// and drop items upstream during long stalls.
+    Pause,
+    /// Deliver [`CirclesRpcError::SubscriptionOverflow`] after the buffered items and end
+    /// the subscription.
+    Error,
+}
+
+/// Counters describing how far a consumer is behind.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BufferStats {
+    pub capacity: usize,
+    /// Items waiting to be consumed right now.
+    pub buffered: usize,
+    /// Largest `buffered` value observed.
+    pub high_water_mark: usize,
+    /// Items read from upstream.
+    pub received: u64,
+    /// Items handed to the consumer.
+    pub delivered: u64,
+    /// Items lost to overflow (evicted, or rejected under [`OverflowPolicy::Error`]).
+    pub dropped: u64,
+}
+
+impl BufferStats {
+    /// Fraction of the buffer in use, from `0.0` to `1.0`.
+    pub fn fill_ratio(&self) -> f64 {
+        if self.capacity == 0 {
+            return 0.0;
+        }
+        self.buffered as f64 / self.capacity as f64
+    }
+}
+
+struct State<T> {
+    queue: VecDeque<Result<T>>,
+    stats: BufferStats,
+    closed: bool,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    /// Wakes the consumer when items arrive or the stream ends.
+    items: Notify,
+    /// Wakes a paused pump when the consumer frees space.
+    space: Notify,
+}
+
+/// A subscription whose items are read eagerly into a bounded buffer.
+///
+/// A background task drains the upstream stream (typically a
+/// [`CirclesSubscription`](super::subscription::CirclesSubscription)) so the socket keeps
+/// flowing, while the buffer never grows past `capacity`. Dropping this value stops the
+/// task and drops the upstream stream, which unsubscribes.
+pub struct BufferedSubscription<T> {
+    shared: Arc<Shared<T>>,
+    pump: JoinHandle<()>,
+}
+
+impl<T: Send + 'static> BufferedSubscription<T> {
+    /// Start buffering `stream`. Must be called inside a Tokio runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new<S>(stream: S, capacity: usize, policy: OverflowPolicy) -> Self
+    where
+        S: Stream<Item = Result<T>> + Send + Unpin + 'static,
+    {
+        assert!(capacity > 0, "buffer capacity must be non-zero");
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                queue: VecDeque::with_capacity(capacity),
+                stats: BufferStats {
+                    capacity,
+                    ..BufferStats::default()
+                },
+                closed: false,
+            }),
+            items: Notify::new(),
+            space: Notify::new(),
+        });
+        let pump = tokio::spawn(pump(stream, shared.clone(), policy));
+        Self { shared, pump }
+    }
+}
+
+impl<T> BufferedSubscription<T> {
+    /// Next item, waiting if the buffer is empty. `None` once the stream ended and the
+    /// buffer is drained.
+    pub async fn recv(&self) -> Option<Result<T>> {
+        let mut batch = self.drain(1).await;
+        batch.pop()
+    }
+
+    /// Wait for at least one item, then take up to `max` buffered items without waiting
+    /// further. Empty once the stream ended and the buffer is drained.
+    pub async fn drain(&self, max: usize) -> Vec<Result<T>> {
+        loop {
+            let notified = self.shared.items.notified();
+            let batch = self.try_drain(max);
+            if !batch.is_empty() || self.is_closed() {
+                return batch;
+            }
+            notified.await;
+        }
+    }
+
+    /// Take up to `max` buffered items without waiting.
+    pub fn try_drain(&self, max: usize) -> Vec<Result<T>> {
+        let mut state = self.shared.state.lock().expect("buffer lock poisoned");
+        let take = max.min(state.queue.len());
+        let batch: Vec<_> = state.queue.drain(..take).collect();
+        state.stats.delivered += batch.len() as u64;
+        state.stats.buffered = state.queue.len();
+        drop(state);
+        if !batch.is_empty() {
+            self.shared.space.notify_one();
+        }
+        batch
+    }
+
+    /// Current lag and overflow counters.
+    pub fn stats(&self) -> BufferStats {
+        self.shared
+            .state
+            .lock()
+            .expect("buffer lock poisoned")
+            .stats
+    }
+
+    /// Whether upstream has ended (buffered items may remain).
+    pub fn is_closed(&self) -> bool {
+        self.shared
+            .state
+            .lock()
+            .expect("buffer lock poisoned")
+            .closed
+    }
+}
+
+impl<T> Drop for BufferedSubscription<T> {
+    fn drop(&mut self) {
+        self.pump.abort();
+    }
+}
+
+enum Step<T> {
+    Stored,
+    Wait(Result<T>),
+    Stop,
+}
+
+async fn pump<S, T>(mut stream: S, shared: Arc<Shared<T>>, policy: OverflowPolicy)
+where
+    S: Stream<Item = Result<T>> + Unpin,
+{
+    while let Some(item) = stream.next().await {
+        let mut pending = item;
+        loop {
+            let space = shared.space.notified();
+            let step = store(&shared, pending, policy);
+            shared.items.notify_one();
+            match step {
+                Step::Stored => break,
+                Step::Stop => return,
+                Step::Wait(item) => {
+                    pending = item;
+                    space.await;
+                }
+            }
+        }
+    }
+    shared.state.lock().expect("buffer lock poisoned").closed = true;
+    shared.items.notify_one();
+}
+
+fn store<T>(shared: &Shared<T>, item: Result<T>, policy: OverflowPolicy) -> Step<T> {
+    let mut state = shared.state.lock().expect("buffer lock poisoned");
+    let capacity = state.stats.capacity;
+    if state.queue.len() < capacity {
+        push(&mut state, item);
+        return Step::Stored;
+    }
+    match policy {
+        OverflowPolicy::DropOldest => {
+            state.queue.pop_front();
+            state.stats.dropped += 1;
+            metrics::record_events_dropped(1);
+            push(&mut state, item);
+            Step::Stored
+        }
+        OverflowPolicy::Error => {
+            // The overflowing item is lost; the error takes its place behind the backlog.
+            state.stats.received += 1;
+            state.stats.dropped += 1;
+            metrics::record_events_dropped(1);
+            state
+                .queue
+                .push_back(Err(CirclesRpcError::SubscriptionOverflow { capacity }));
+            state.stats.buffered = state.queue.len();
+            state.closed = true;
+            Step::Stop
+        }
+        OverflowPolicy::Pause => Step::Wait(item),
+    }
+}
+
+fn push<T>(state: &mut State<T>, item: Result<T>) {
+    state.queue.push_back(item);
+    state.stats.received += 1;
+    state.stats.buffered = state.queue.len();
+    state.stats.high_water_mark = state.stats.high_water_mark.max(state.stats.buffered);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream;
+
+    fn numbers(n: u32) -> impl Stream<Item = Result<u32>> + Send + Unpin + 'static {
+        stream::iter((0..n).map(Ok))
+    }
+
+    async fn wait_closed<T>(sub: &BufferedSubscription<T>) {
+        while !sub.is_closed() {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    fn values(batch: Vec<Result<u32>>) -> Vec<u32> {
+        batch.into_iter().map(|item| item.unwrap()).collect()
+    }
+
+    #[tokio::test]
+    async fn drop_oldest_keeps_latest_items() {
+        let sub = BufferedSubscription::new(numbers(10), 3, OverflowPolicy::DropOldest);
+        wait_closed(&sub).await;
+        assert_eq!(values(sub.drain(10).await), vec![7, 8, 9]);
+        let stats = sub.stats();
+        assert_eq!((stats.received, stats.dropped, stats.delivered), (10, 7, 3));
+        assert_eq!(stats.high_water_mark, 3);
+        assert!(sub.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn pause_delivers_everything_in_order() {
+        let sub = BufferedSubscription::new(numbers(7), 2, OverflowPolicy::Pause);
+        let mut seen = Vec::new();
+        loop {
+            let batch = sub.drain(2).await;
+            if batch.is_empty() {
+                break;
+            }
+            assert!(sub.stats().high_water_mark <= 2);
+            seen.extend(values(batch));
+        }
+        assert_eq!(seen, (0..7).collect::<Vec<_>>());
+        assert_eq!(sub.stats().dropped, 0);
+    }
+
+    #[tokio::test]
+    async fn error_policy_ends_with_overflow() {
+        let sub = BufferedSubscription::new(numbers(5), 2, OverflowPolicy::Error);
+        wait_closed(&sub).await;
+        let batch = sub.drain(10).await;
+        assert_eq!(batch.len(), 3);
+        assert!(matches!(
+            batch[2],
+            Err(CirclesRpcError::SubscriptionOverflow { capacity: 2 })
+        ));
+        assert!(sub.recv().await.is_none());
+    }
+}
@@ -1,7 +1,10 @@
 use crate::error::{CirclesRpcError, Result};
+use circles_types::{CirclesEvent, EventCursor};
 use futures::{Stream, StreamExt};
 use std::pin::Pin;
+use std::time::Duration;
 
+pub mod buffered;
 pub mod parser;
 pub mod subscription;
 
@@ -21,12 +24,68 @@ impl<T> EventStream<T> {
         }
     }
 
+    /// End the stream with [`CirclesRpcError::Timeout`] once no item arrives for `idle`.
+    ///
+    /// `None` leaves the stream untouched.
+    pub fn with_idle_timeout(self, idle: Option<Duration>) -> Self
+    where
+        T: Send + 'static,
+    {
+        let Some(idle) = idle else {
+            return self;
+        };
+        Self::new(futures::stream::unfold(
+            Some(self.inner),
+            move |state| async move {
+                let mut inner = state?;
+                match tokio::time::timeout(idle, inner.next()).await {
+                    Ok(Some(item)) => Some((item, Some(inner))),
+                    Ok(None) => None,
+                    Err(_) => Some((
+                        Err(CirclesRpcError::Timeout {
+                            method: "eth_subscribe",
+                            after: idle,
+                        }),
+                        None,
+                    )),
+                }
+            },
+        ))
+    }
+
     /// Access the inner stream if needed.
     pub fn into_inner(self) -> Pin<Box<dyn Stream<Item = Result<T>> + Send>> {
         self.inner
     }
 }
 
+impl EventStream<CirclesEvent> {
+    /// Pair every event with the [`EventCursor`] to persist once it is handled.
+    pub fn with_cursors(self) -> EventStream<(EventCursor, CirclesEvent)> {
+        self.resume_from(EventCursor::default())
+    }
+
+    /// Like [`with_cursors`](Self::with_cursors), dropping events before `cursor`.
+    ///
+    /// Events at or before the last yielded position are dropped as well, so a stream that
+    /// chains a backfill with an overlapping live subscription yields each event once.
+    /// Errors pass through without moving the cursor.
+    pub fn resume_from(self, cursor: EventCursor) -> EventStream<(EventCursor, CirclesEvent)> {
+        let mut cursor = cursor;
+        EventStream::new(self.inner.filter_map(move |item| {
+            let item = match item {
+                Ok(event) if cursor.is_pending(&event) => {
+                    cursor = event.cursor();
+                    Some(Ok((cursor, event)))
+                }
+                Ok(_) => None,
+                Err(err) => Some(Err(err)),
+            };
+            futures::future::ready(item)
+        }))
+    }
+}
+
 impl<T> Stream for EventStream<T> {
     type Item = Result<T>;
 
@@ -2,6 +2,7 @@ use crate::error::{CirclesRpcError, Result};
 use crate::events::EventStream;
 use alloy_primitives::B256;
 use alloy_provider::RootProvider;
+use circles_types::{CirclesEvent, EventCursor};
 use futures::Stream;
 
 /// Wrapper around an [`EventStream`] that will best-effort `eth_unsubscribe` on drop.
@@ -35,6 +36,14 @@ impl<T> Drop for CirclesSubscription<T> {
     }
 }
 
+impl CirclesSubscription<CirclesEvent> {
+    /// Pair live events with cursors via [`EventStream::resume_from`]. The subscription
+    /// lives inside the returned stream and is unsubscribed when it is dropped.
+    pub fn resume_from(self, cursor: EventCursor) -> EventStream<(EventCursor, CirclesEvent)> {
+        EventStream::new(self).resume_from(cursor)
+    }
+}
+
 impl<T> Stream for CirclesSubscription<T>
 where
     EventStream<T>: Stream<Item = Result<T>>,
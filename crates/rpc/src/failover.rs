@@ -0,0 +1,250 @@
+//! Multi-endpoint failover for [`RpcClient`].
+//!
+//! A [`FailoverPool`] keeps one HTTP provider per endpoint, remembers which one answered
+//! last, and puts endpoints that fail at the transport level into a cooldown that grows
+//! with consecutive failures. JSON-RPC error responses are request errors, not endpoint
+//! errors, so they are returned to the caller without failing over.
+
+use crate::client::RpcClient;
+use crate::error::{CirclesRpcError, Result};
+use crate::methods::HealthMethods;
+use alloy_json_rpc::RpcError;
+use alloy_provider::transport::TransportError;
+use alloy_provider::{Identity, ProviderBuilder, RootProvider};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Cooldown policy for endpoints that fail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FailoverPolicy {
+    /// Cooldown after the first consecutive failure.
+    pub cooldown: Duration,
+    /// Upper bound for the doubling cooldown.
+    pub max_cooldown: Duration,
+}
+
+impl Default for FailoverPolicy {
+    fn default() -> Self {
+        Self {
+            cooldown: Duration::from_secs(5),
+            max_cooldown: Duration::from_secs(300),
+        }
+    }
+}
+
+impl FailoverPolicy {
+    fn cooldown_for(&self, consecutive_failures: u32) -> Duration {
+        let shift = consecutive_failures.saturating_sub(1).min(16);
+        self.cooldown
+            .saturating_mul(1u32 << shift)
+            .min(self.max_cooldown)
+    }
+}
+
+#[derive(Debug, Default)]
+struct EndpointState {
+    consecutive_failures: u32,
+    banned_until: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    url: reqwest::Url,
+    provider: RootProvider,
+    state: Mutex<EndpointState>,
+}
+
+/// Set of interchangeable HTTP endpoints with health tracking.
+#[derive(Debug)]
+pub struct FailoverPool {
+    endpoints: Vec<Endpoint>,
+    active: AtomicUsize,
+    policy: FailoverPolicy,
+}
+
+impl FailoverPool {
+    /// Build a pool from endpoint URLs, tried in the given order.
+    pub fn new(urls: Vec<reqwest::Url>, policy: FailoverPolicy) -> Result<Self> {
+        if urls.is_empty() {
+            return Err(CirclesRpcError::InvalidResponse {
+                message: "failover pool needs at least one endpoint".to_string(),
+            });
+        }
+        let endpoints = urls
+            .into_iter()
+            .map(|url| Endpoint {
+                provider: ProviderBuilder::<Identity, Identity>::default()
+                    .connect_http(url.clone()),
+                url,
+                state: Mutex::new(EndpointState::default()),
+            })
+            .collect();
+        Ok(Self {
+            endpoints,
+            active: AtomicUsize::new(0),
+            policy,
+        })
+    }
+
+    /// Endpoint URLs in configured order.
+    pub fn urls(&self) -> Vec<reqwest::Url> {
+        self.endpoints.iter().map(|e| e.url.clone()).collect()
+    }
+
+    /// URL of the endpoint that will be tried first.
+    pub fn active_url(&self) -> reqwest::Url {
+        self.endpoints[self.active.load(Ordering::Relaxed)]
+            .url
+            .clone()
+    }
+
+    /// Whether the endpoint at `url` is currently cooling down.
+    pub fn is_banned(&self, url: &reqwest::Url) -> bool {
+        let now = Instant::now();
+        self.endpoints
+            .iter()
+            .filter(|e| &e.url == url)
+            .any(|e| e.banned_until(now).is_some())
+    }
+
+    pub(crate) fn primary_provider(&self) -> &RootProvider {
+        &self.endpoints[0].provider
+    }
+
+    /// Probe every endpoint with `circles_health` and make the first healthy one active.
+    ///
+    /// Any successful health response counts as healthy; failing endpoints are put
+    /// into cooldown. Returns the selected URL.
+    pub async fn select_healthy(&self) -> Result<reqwest::Url> {
+        let mut last_err = None;
+        for (index, endpoint) in self.endpoints.iter().enumerate() {
+            let health = HealthMethods::new(RpcClient::new(endpoint.provider.clone()));
+            match health.health().await {
+                Ok(_) => {
+                    self.mark_ok(index);
+                    return Ok(endpoint.url.clone());
+                }
+                Err(err) => {
+                    self.mark_failed(index);
+                    last_err = Some(err);
+                }
+            }
+        }
+        Err(last_err.expect("pool has at least one endpoint"))
+    }
+
+    /// Indices to try for the next request: the active endpoint, then the others,
+    /// with cooling-down endpoints last (soonest-available first).
+    fn candidates(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let active = self.active.load(Ordering::Relaxed);
+        let count = self.endpoints.len();
+        let mut ready = Vec::new();
+        let mut banned = Vec::new();
+        for index in (0..count).map(|offset| (active + offset) % count) {
+            match self.endpoints[index].banned_until(now) {
+                Some(until) => banned.push((until, index)),
+                None => ready.push(index),
+            }
+        }
+        banned.sort();
+        ready.extend(banned.into_iter().map(|(_, index)| index));
+        ready
+    }
+
+    fn mark_ok(&self, index: usize) {
+        let mut state = self.endpoints[index].state.lock().expect("lock");
+        state.consecutive_failures = 0;
+        state.banned_until = None;
+        self.active.store(index, Ordering::Relaxed);
+    }
+
+    fn mark_failed(&self, index: usize) {
+        let mut state = self.endpoints[index].state.lock().expect("lock");
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        state.banned_until =
+            Some(Instant::now() + self.policy.cooldown_for(state.consecutive_failures));
+    }
+
+    /// Run `op` against endpoints until one answers without a transport error.
+    pub(crate) async fn run<T, F, Fut>(&self, mut op: F) -> std::result::Result<T, TransportError>
+    where
+        F: FnMut(RootProvider) -> Fut,
+        Fut: Future<Output = std::result::Result<T, TransportError>>,
+    {
+        let mut last_err = None;
+        for index in self.candidates() {
+            if last_err.is_some() {
+                crate::metrics::record_failover();
+            }
+            match op(self.endpoints[index].provider.clone()).await {
+                Ok(value) => {
+                    self.mark_ok(index);
+                    return Ok(value);
+                }
+                Err(err @ RpcError::Transport(_)) => {
+                    self.mark_failed(index);
+                    last_err = Some(err);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Err(last_err.expect("pool has at least one endpoint"))
+    }
+}
+
+impl Endpoint {
+    fn banned_until(&self, now: Instant) -> Option<Instant> {
+        let state = self.state.lock().expect("lock");
+        state.banned_until.filter(|until| *until > now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(urls: &[&str]) -> FailoverPool {
+        FailoverPool::new(
+            urls.iter().map(|u| u.parse().expect("url")).collect(),
+            FailoverPolicy::default(),
+        )
+        .expect("pool")
+    }
+
+    #[test]
+    fn cooldown_doubles_up_to_max() {
+        let policy = FailoverPolicy {
+            cooldown: Duration::from_secs(2),
+            max_cooldown: Duration::from_secs(10),
+        };
+        assert_eq!(policy.cooldown_for(1), Duration::from_secs(2));
+        assert_eq!(policy.cooldown_for(2), Duration::from_secs(4));
+        assert_eq!(policy.cooldown_for(3), Duration::from_secs(8));
+        assert_eq!(policy.cooldown_for(4), Duration::from_secs(10));
+        assert_eq!(policy.cooldown_for(u32::MAX), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn failed_endpoints_move_to_the_back() {
+        let pool = pool(&["http://a.example", "http://b.example", "http://c.example"]);
+        assert_eq!(pool.candidates(), vec![0, 1, 2]);
+
+        pool.mark_failed(0);
+        assert!(pool.is_banned(&"http://a.example".parse().expect("url")));
+        assert_eq!(pool.candidates(), vec![1, 2, 0]);
+
+        pool.mark_ok(2);
+        assert_eq!(pool.active_url().as_str(), "http://c.example/");
+        assert_eq!(pool.candidates(), vec![2, 1, 0]);
+
+        pool.mark_ok(0);
+        assert_eq!(pool.candidates(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn empty_pool_is_rejected() {
+        assert!(FailoverPool::new(Vec::new(), FailoverPolicy::default()).is_err());
+    }
+}
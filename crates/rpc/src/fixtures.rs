@@ -0,0 +1,123 @@
+//! Record/replay of JSON-RPC responses as files (requires the `fixtures` feature).
+//!
+//! Attach a [`FixtureStore`] with [`RpcClient::with_fixtures`](crate::RpcClient::with_fixtures):
+//! in [`FixtureMode::Record`] every call still goes to the endpoint and its `result` is written
+//! to `<dir>/<method>-<hash>.json`; in [`FixtureMode::Replay`] calls are answered from those
+//! files and never touch the network. The hash covers the serialized params, so the same
+//! call always maps to the same file and a recorded scenario replays deterministically.
+
+use crate::error::{CirclesRpcError, Result};
+use alloy_primitives::{hex, keccak256};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Environment variable read by [`FixtureStore::from_env`] (`record` or `replay`).
+pub const FIXTURES_ENV: &str = "CIRCLES_RPC_FIXTURES";
+
+/// Whether a [`FixtureStore`] captures live responses or serves recorded ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixtureMode {
+    Record,
+    Replay,
+}
+
+/// Directory of recorded responses, one JSON file per distinct `(method, params)` pair.
+///
+/// Recording the same call twice overwrites the earlier file, so scenarios that expect a
+/// call to return different results over time should be recorded as separate stores.
+#[derive(Debug, Clone)]
+pub struct FixtureStore {
+    dir: PathBuf,
+    mode: FixtureMode,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Fixture {
+    method: String,
+    params: Value,
+    result: Value,
+}
+
+impl FixtureStore {
+    pub fn new(dir: impl Into<PathBuf>, mode: FixtureMode) -> Self {
+        Self {
+            dir: dir.into(),
+            mode,
+        }
+    }
+
+    /// Record into `dir`.
+    pub fn record(dir: impl Into<PathBuf>) -> Self {
+        Self::new(dir, FixtureMode::Record)
+    }
+
+    /// Replay from `dir`.
+    pub fn replay(dir: impl Into<PathBuf>) -> Self {
+        Self::new(dir, FixtureMode::Replay)
+    }
+
+    /// Store over `dir` in the mode named by [`FIXTURES_ENV`], or `None` when the variable is
+    /// unset or not `record`/`replay`, so tests can opt into either without code changes.
+    pub fn from_env(dir: impl Into<PathBuf>) -> Option<Self> {
+        let mode = match std::env::var(FIXTURES_ENV)
+            .ok()?
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "record" => FixtureMode::Record,
+            "replay" => FixtureMode::Replay,
+            _ => return None,
+        };
+        Some(Self::new(dir, mode))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn mode(&self) -> FixtureMode {
+        self.mode
+    }
+
+    /// File a call with `method` and `params` is recorded to.
+    pub fn path_for<P: Serialize>(&self, method: &str, params: &P) -> Result<PathBuf> {
+        let params = serde_json::to_vec(params)?;
+        let hash = keccak256(&params);
+        let file = format!("{method}-{}.json", hex::encode(&hash[..8]));
+        Ok(self.dir.join(file))
+    }
+
+    /// Recorded result for this call, decoded as `Resp`.
+    pub fn load<P: Serialize, Resp: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: &P,
+    ) -> Result<Resp> {
+        let path = self.path_for(method, params)?;
+        let contents = std::fs::read(&path).map_err(|err| fixture_error(&path, err))?;
+        let fixture: Fixture = serde_json::from_slice(&contents)?;
+        Ok(serde_json::from_value(fixture.result)?)
+    }
+
+    /// Write `result` as the recording for this call, creating the directory if needed.
+    pub fn save<P: Serialize>(&self, method: &str, params: &P, result: &Value) -> Result<()> {
+        let path = self.path_for(method, params)?;
+        let fixture = Fixture {
+            method: method.to_string(),
+            params: serde_json::to_value(params)?,
+            result: result.clone(),
+        };
+        std::fs::create_dir_all(&self.dir).map_err(|err| fixture_error(&self.dir, err))?;
+        let contents = serde_json::to_vec_pretty(&fixture)?;
+        std::fs::write(&path, contents).map_err(|err| fixture_error(&path, err))
+    }
+}
+
+fn fixture_error(path: &Path, err: std::io::Error) -> CirclesRpcError {
+    CirclesRpcError::Fixture {
+        path: path.to_path_buf(),
+        message: err.to_string(),
+    }
+}
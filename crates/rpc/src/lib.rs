@@ -3,24 +3,51 @@
 //! - HTTP via `alloy-provider`; WebSocket subscriptions behind the `ws` feature.
 //! - Method namespaces under [`methods`] map directly to Circles RPC methods
 //!   (balance, token, trust, avatar, query, events, invitation, pathfinder, group, tables, health, network, search).
-//! - `paged_query`/`paged_stream` helpers for `circles_query` with cursor handling.
+//! - Optional multi-endpoint failover ([`FailoverPool`]) with `circles_health` probing and cooldowns.
+//! - Optional client-side token-bucket rate limiting ([`RateLimit`]) shared by every namespace.
+//! - [`Timeouts`] bound connects, individual requests, pathfinder queries, and quiet
+//!   subscriptions; clients built without them wait indefinitely.
+//! - [`CirclesRpcError::kind`] classifies failures as an [`ErrorKind`] (retryable, user error, ...).
+//! - `paged_query`/`paged_stream` helpers for `circles_query` with cursor handling; empty
+//!   `columns` are inferred from the row type's serde fields ([`row_columns`]).
+//! - `count`/`sum`/`count_by`/`sum_by` aggregates on [`QueryMethods`], using the backend when it
+//!   supports the aggregate and streaming pages with progress callbacks otherwise.
+//! - Optional `fixtures` feature: `RpcClient::with_fixtures` records responses to JSON files
+//!   and replays them offline for reproducible tests.
 //! - WS parsing tolerates heartbeats (`[]`) and batched frames; unknown event types surface as `CrcUnknownEvent`.
 
+pub mod auth;
 pub mod client;
+pub mod columns;
 pub mod error;
 pub mod events;
+pub mod failover;
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
 pub mod methods;
+pub mod metrics;
 pub mod paged_query;
+pub mod rate_limit;
 pub mod rpc;
 pub mod utils;
 
+pub use auth::AuthHttp;
+pub use circles_types::{AuthError, AuthProvider, BearerToken, ErrorKind, StaticHeaders, Timeouts};
 pub use client::RpcClient;
+pub use columns::row_columns;
 pub use error::{CirclesRpcError, Result};
 pub use events::EventStream;
+pub use events::buffered::{BufferStats, BufferedSubscription, OverflowPolicy};
+pub use failover::{FailoverPolicy, FailoverPool};
+#[cfg(feature = "fixtures")]
+pub use fixtures::{FixtureMode, FixtureStore};
 pub use methods::{
-    AvatarMethods, BalanceMethods, EventsMethods, GroupMethods, HealthMethods, InvitationMethods,
-    NetworkMethods, PathfinderMethods, QueryMethods, SdkMethods, SearchMethods, TablesMethods,
-    TokenInfoMethods, TokenMethods, TransactionMethods, TrustMethods,
+    AggregateProgress, AggregateQuery, AvatarMethods, BalanceMethods, EventRangeOptions,
+    EventsMethods, GroupMethods, HealthMethods, InvitationMethods, InviteStats, InviteeState,
+    InviteeStatus, MemberBalanceOptions, MemberBalancePage, MemberBalanceSort, NetworkMethods,
+    PathfinderMethods, QueryMethods, SdkMethods, SearchMethods, SearchOptions, SearchPage,
+    SearchRanking, TablesMethods, TokenInfoMethods, TokenMethods, TransactionMethods, TrustMethods,
 };
 pub use paged_query::{Page, PagedQuery};
+pub use rate_limit::{RateLimit, RateLimiter};
 pub use rpc::CirclesRpc;
@@ -0,0 +1,257 @@
+//! Aggregates over `circles_query` tables: count, sum, and grouped variants.
+//!
+//! `count` and `sum` first ask the backend for the aggregate in one `circles_query`
+//! call (`COUNT(*)` / `SUM(column)` as the selected column). Backends that reject the
+//! expression, or return something other than a single numeric cell, are handled by
+//! streaming every matching row and aggregating client-side. Grouped aggregates are
+//! always computed client-side.
+
+use crate::error::{CirclesRpcError, Result};
+use crate::methods::QueryMethods;
+use alloy_primitives::U256;
+use circles_types::{CursorColumn, Filter, PagedQueryParams, QueryParams, SortOrder};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Table and filters an aggregate runs over.
+#[derive(Debug, Clone)]
+pub struct AggregateQuery {
+    pub namespace: String,
+    pub table: String,
+    pub filter: Vec<Filter>,
+    /// Rows per page when aggregating client-side.
+    pub page_size: u32,
+    /// Cursor columns for tables without block/transaction/log columns.
+    pub cursor_columns: Option<Vec<CursorColumn>>,
+    /// Try a single backend aggregate before streaming (default `true`).
+    pub use_backend: bool,
+}
+
+impl AggregateQuery {
+    pub fn new(namespace: impl Into<String>, table: impl Into<String>) -> Self {
+        Self {
+            namespace: namespace.into(),
+            table: table.into(),
+            filter: Vec::new(),
+            page_size: 1000,
+            cursor_columns: None,
+            use_backend: true,
+        }
+    }
+
+    pub fn with_filter(mut self, filter: Vec<Filter>) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn with_page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    pub fn with_cursor_columns(mut self, cursor_columns: Vec<CursorColumn>) -> Self {
+        self.cursor_columns = Some(cursor_columns);
+        self
+    }
+
+    /// Skip the backend attempt and always stream.
+    pub fn client_side_only(mut self) -> Self {
+        self.use_backend = false;
+        self
+    }
+
+    fn paged_params(&self, mut columns: Vec<String>) -> PagedQueryParams {
+        let mut params = PagedQueryParams::new(
+            self.namespace.clone(),
+            self.table.clone(),
+            SortOrder::ASC,
+            Vec::new(),
+            self.page_size,
+        );
+        if let Some(cursor_columns) = &self.cursor_columns {
+            params = params.with_cursor_columns(cursor_columns.clone());
+        }
+        if !self.filter.is_empty() {
+            params = params.with_filter(self.filter.clone());
+        }
+        // Cursor columns must be selected so the pager can advance.
+        for cursor in params.resolved_cursor_columns() {
+            if !columns.contains(&cursor.name) {
+                columns.push(cursor.name);
+            }
+        }
+        params.columns = columns;
+        params
+    }
+}
+
+/// Progress of a client-side aggregate, reported after every page.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AggregateProgress {
+    pub pages: u64,
+    pub rows: u64,
+}
+
+/// Callback receiving [`AggregateProgress`] updates.
+pub type ProgressFn<'a> = &'a mut (dyn FnMut(AggregateProgress) + Send);
+
+impl QueryMethods {
+    /// Number of rows matching `query`.
+    pub async fn count(
+        &self,
+        query: &AggregateQuery,
+        progress: Option<ProgressFn<'_>>,
+    ) -> Result<u64> {
+        if query.use_backend
+            && let Some(value) = self.backend_aggregate(query, "COUNT(*)").await
+            && let Ok(count) = u64::try_from(value)
+        {
+            return Ok(count);
+        }
+        let mut count = 0u64;
+        self.for_each_row(query, Vec::new(), progress, |_| {
+            count += 1;
+            Ok(())
+        })
+        .await?;
+        Ok(count)
+    }
+
+    /// Sum of `column` over rows matching `query`. Values may be JSON numbers or
+    /// decimal/`0x` strings; nulls are skipped.
+    pub async fn sum(
+        &self,
+        query: &AggregateQuery,
+        column: &str,
+        progress: Option<ProgressFn<'_>>,
+    ) -> Result<U256> {
+        if query.use_backend
+            && let Some(value) = self
+                .backend_aggregate(query, &format!("SUM({column})"))
+                .await
+        {
+            return Ok(value);
+        }
+        let mut total = U256::ZERO;
+        self.for_each_row(query, vec![column.to_string()], progress, |row| {
+            if let Some(value) = numeric_cell(row, column)? {
+                total = total.saturating_add(value);
+            }
+            Ok(())
+        })
+        .await?;
+        Ok(total)
+    }
+
+    /// Row counts per distinct value of `group_by`.
+    pub async fn count_by(
+        &self,
+        query: &AggregateQuery,
+        group_by: &str,
+        progress: Option<ProgressFn<'_>>,
+    ) -> Result<BTreeMap<String, u64>> {
+        let mut groups = BTreeMap::new();
+        self.for_each_row(query, vec![group_by.to_string()], progress, |row| {
+            *groups.entry(group_key(row, group_by)).or_insert(0) += 1;
+            Ok(())
+        })
+        .await?;
+        Ok(groups)
+    }
+
+    /// Sums of `column` per distinct value of `group_by`.
+    pub async fn sum_by(
+        &self,
+        query: &AggregateQuery,
+        group_by: &str,
+        column: &str,
+        progress: Option<ProgressFn<'_>>,
+    ) -> Result<BTreeMap<String, U256>> {
+        let mut groups: BTreeMap<String, U256> = BTreeMap::new();
+        let columns = vec![group_by.to_string(), column.to_string()];
+        self.for_each_row(query, columns, progress, |row| {
+            let entry = groups.entry(group_key(row, group_by)).or_default();
+            if let Some(value) = numeric_cell(row, column)? {
+                *entry = entry.saturating_add(value);
+            }
+            Ok(())
+        })
+        .await?;
+        Ok(groups)
+    }
+
+    /// One-shot aggregate; `None` when the backend rejects or misreports it.
+    async fn backend_aggregate(&self, query: &AggregateQuery, expression: &str) -> Option<U256> {
+        let params = QueryParams {
+            namespace: query.namespace.clone(),
+            table: query.table.clone(),
+            columns: vec![expression.to_string()],
+            filter: query.filter.clone(),
+            order: Vec::new(),
+            limit: None,
+        };
+        let rows = self
+            .circles_query::<Map<String, Value>>(params)
+            .await
+            .ok()?;
+        match rows.as_slice() {
+            [row] if row.len() == 1 => row.values().next().and_then(parse_numeric),
+            _ => None,
+        }
+    }
+
+    async fn for_each_row(
+        &self,
+        query: &AggregateQuery,
+        columns: Vec<String>,
+        mut progress: Option<ProgressFn<'_>>,
+        mut visit: impl FnMut(&Map<String, Value>) -> Result<()> + Send,
+    ) -> Result<()> {
+        let mut pager = self.paged::<Map<String, Value>>(query.paged_params(columns));
+        let mut state = AggregateProgress::default();
+        while let Some(page) = pager.next_page().await? {
+            for row in &page.items {
+                visit(row)?;
+            }
+            state.pages += 1;
+            state.rows += page.items.len() as u64;
+            if let Some(report) = progress.as_mut() {
+                report(state);
+            }
+            if !page.has_more {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_numeric(value: &Value) -> Option<U256> {
+    match value {
+        Value::Number(n) => n.as_u64().map(U256::from),
+        Value::String(s) => U256::from_str(s.trim()).ok(),
+        _ => None,
+    }
+}
+
+fn numeric_cell(row: &Map<String, Value>, column: &str) -> Result<Option<U256>> {
+    match row.get(column) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => {
+            parse_numeric(value)
+                .map(Some)
+                .ok_or_else(|| CirclesRpcError::InvalidResponse {
+                    message: format!("column {column} is not numeric: {value}"),
+                })
+        }
+    }
+}
+
+fn group_key(row: &Map<String, Value>, column: &str) -> String {
+    match row.get(column) {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    }
+}
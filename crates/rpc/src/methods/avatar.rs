@@ -21,6 +21,11 @@ impl AvatarMethods {
         self.client.call("circles_getAvatarInfo", (address,)).await
     }
 
+    /// circles_getAvatarInfo, with `None` for addresses that never registered.
+    pub async fn find_avatar_info(&self, address: Address) -> Result<Option<AvatarInfo>> {
+        self.client.call("circles_getAvatarInfo", (address,)).await
+    }
+
     /// circles_getAvatarInfoBatch
     pub async fn get_avatar_info_batch(&self, addresses: Vec<Address>) -> Result<Vec<AvatarInfo>> {
         self.client
@@ -60,10 +65,12 @@ impl AvatarMethods {
     }
 
     /// circles_getProfileByAddressBatch
+    ///
+    /// One entry per address, `None` where the avatar has no profile.
     pub async fn get_profile_by_address_batch(
         &self,
         addresses: Vec<Address>,
-    ) -> Result<Vec<Profile>> {
+    ) -> Result<Vec<Option<Profile>>> {
         self.client
             .call("circles_getProfileByAddressBatch", (addresses,))
             .await
@@ -1,6 +1,8 @@
 use crate::client::RpcClient;
-use crate::error::Result;
-use circles_types::{Address, Balance};
+use crate::error::{CirclesRpcError, Result};
+use circles_types::{Address, Balance, TotalBalance, U256};
+use circles_utils::display::parse_crc;
+use serde_json::Value;
 
 /// Methods for aggregate balance queries (`circles_getTotalBalance` / `circlesV2_getTotalBalance`).
 #[derive(Clone, Debug)]
@@ -15,6 +17,7 @@ impl BalanceMethods {
     }
 
     /// circles_getTotalBalance / circlesV2_getTotalBalance
+    #[deprecated(note = "use `get_total_balances`, which queries both hubs and returns atto units")]
     pub async fn get_total_balance(
         &self,
         address: Address,
@@ -28,4 +31,104 @@ impl BalanceMethods {
         };
         self.client.call(method, (address, as_time_circles)).await
     }
+
+    /// circles_getTotalBalance + circlesV2_getTotalBalance, queried concurrently.
+    ///
+    /// Both methods answer in decimal circles (e.g. `"12.5"`), which is converted to atto
+    /// units, matching the TS SDK's `circlesToAttoCircles` step.
+    pub async fn get_total_balances(
+        &self,
+        avatar: Address,
+        as_time_circles: bool,
+    ) -> Result<TotalBalance> {
+        let (v1, v2) = futures::try_join!(
+            self.total("circles_getTotalBalance", avatar, as_time_circles),
+            self.total("circlesV2_getTotalBalance", avatar, as_time_circles),
+        )?;
+        Ok(TotalBalance {
+            v1,
+            v2,
+            as_time_circles,
+        })
+    }
+
+    async fn total(
+        &self,
+        method: &'static str,
+        avatar: Address,
+        as_time_circles: bool,
+    ) -> Result<U256> {
+        let value: Value = self.client.call(method, (avatar, as_time_circles)).await?;
+        parse_total(&value).ok_or_else(|| CirclesRpcError::InvalidResponse {
+            message: format!("{method} returned {value}, expected a circles amount"),
+        })
+    }
+}
+
+/// Decimal circles (string or number) to atto units; `0x` strings are already atto.
+///
+/// Numbers are read from their decimal text rather than re-formatted as `f64`, so an
+/// amount like `12.345` becomes exactly 12.345e18 atto.
+fn parse_total(value: &Value) -> Option<U256> {
+    match value {
+        Value::String(s) if s.starts_with("0x") => s.parse().ok(),
+        Value::String(s) => parse_crc(s).ok(),
+        Value::Number(n) => parse_crc(&plain_decimal(&n.to_string())?).ok(),
+        Value::Null => Some(U256::ZERO),
+        _ => None,
+    }
+}
+
+/// Rewrite `1.25e3`-style number text as plain decimal (`1250`); other text is returned
+/// unchanged.
+fn plain_decimal(text: &str) -> Option<String> {
+    let Some((mantissa, exponent)) = text.split_once(['e', 'E']) else {
+        return Some(text.to_string());
+    };
+    let exponent: i64 = exponent.parse().ok()?;
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = format!("{whole}{fraction}");
+    let point = whole.len() as i64 + exponent;
+    Some(if point <= 0 {
+        format!("0.{}{digits}", "0".repeat(point.unsigned_abs() as usize))
+    } else if point as usize >= digits.len() {
+        format!("{digits}{}", "0".repeat(point as usize - digits.len()))
+    } else {
+        let (whole, fraction) = digits.split_at(point as usize);
+        format!("{whole}.{fraction}")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_total;
+    use circles_types::U256;
+    use serde_json::json;
+
+    #[test]
+    fn totals_are_decimal_circles() {
+        let one = U256::from(1_000_000_000_000_000_000u64);
+        assert_eq!(
+            parse_total(&json!("12.5")),
+            Some(one * U256::from(25u64) / U256::from(2u64))
+        );
+        assert_eq!(parse_total(&json!("3")), Some(one * U256::from(3u64)));
+        assert_eq!(parse_total(&json!(3)), Some(one * U256::from(3u64)));
+        assert_eq!(parse_total(&json!(0.5)), Some(one / U256::from(2u64)));
+        assert_eq!(
+            parse_total(&json!(12.345)),
+            Some(U256::from(12_345_000_000_000_000_000u128))
+        );
+        assert_eq!(
+            parse_total(&json!(1e20)),
+            Some(one * U256::from(10u64).pow(U256::from(20u64)))
+        );
+        assert_eq!(
+            parse_total(&json!(2.5e-7)),
+            Some(U256::from(250_000_000_000u64))
+        );
+        assert_eq!(parse_total(&json!("0x10")), Some(U256::from(16u64)));
+        assert_eq!(parse_total(&json!(null)), Some(U256::ZERO));
+        assert_eq!(parse_total(&json!("n/a")), None);
+    }
 }
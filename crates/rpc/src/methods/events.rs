@@ -3,8 +3,125 @@ use crate::error::Result;
 use crate::events::EventStream;
 use crate::events::subscription::CirclesSubscription;
 use alloy_json_rpc::RpcSend;
-use circles_types::{CirclesEvent, RpcSubscriptionEvent};
+use circles_types::{Address, CirclesEvent, EventCursor, Filter, RpcSubscriptionEvent};
 use futures::StreamExt;
+use std::collections::VecDeque;
+
+/// Block-range chunking for [`EventsMethods::iter_events_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventRangeOptions {
+    /// Blocks requested by the first `circles_events` call.
+    pub initial_chunk: u64,
+    /// Smallest chunk; errors and truncated responses at this size are yielded instead of
+    /// retried.
+    pub min_chunk: u64,
+    /// Upper bound when growing after sparse ranges.
+    pub max_chunk: u64,
+    /// A response with at least this many events is assumed to be cut off by the server
+    /// and is re-fetched with a smaller chunk.
+    pub truncation_threshold: usize,
+}
+
+impl Default for EventRangeOptions {
+    fn default() -> Self {
+        Self {
+            initial_chunk: 10_000,
+            min_chunk: 1,
+            max_chunk: 1_000_000,
+            truncation_threshold: 1_000,
+        }
+    }
+}
+
+/// What to do with a fetched chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChunkOutcome {
+    Accept,
+    Retry,
+    /// Truncated even at `min_chunk`; the range cannot be fetched completely.
+    Truncated,
+}
+
+/// Walks `[next, end]` in chunks, halving on errors/truncation and doubling on sparse ranges.
+#[derive(Debug, Clone)]
+struct ChunkPlanner {
+    next: Option<u64>,
+    end: u64,
+    chunk: u64,
+    options: EventRangeOptions,
+}
+
+impl ChunkPlanner {
+    fn new(from_block: u64, to_block: u64, options: EventRangeOptions) -> Self {
+        let min_chunk = options.min_chunk.max(1);
+        let options = EventRangeOptions {
+            min_chunk,
+            max_chunk: options.max_chunk.max(min_chunk),
+            ..options
+        };
+        Self {
+            next: (from_block <= to_block).then_some(from_block),
+            end: to_block,
+            chunk: options
+                .initial_chunk
+                .clamp(options.min_chunk, options.max_chunk),
+            options,
+        }
+    }
+
+    /// Inclusive block range of the next request, if any remain.
+    fn range(&self) -> Option<(u64, u64)> {
+        let from = self.next?;
+        Some((from, from.saturating_add(self.chunk - 1).min(self.end)))
+    }
+
+    fn shrink(&mut self) -> bool {
+        let Some((from, to)) = self.range() else {
+            return false;
+        };
+        let span = to - from + 1;
+        if span <= self.options.min_chunk {
+            return false;
+        }
+        self.chunk = (span / 2).max(self.options.min_chunk);
+        true
+    }
+
+    fn on_events(&mut self, count: usize) -> ChunkOutcome {
+        if count >= self.options.truncation_threshold {
+            if self.shrink() {
+                return ChunkOutcome::Retry;
+            }
+            self.next = None;
+            return ChunkOutcome::Truncated;
+        }
+        let Some((_, to)) = self.range() else {
+            return ChunkOutcome::Accept;
+        };
+        self.next = to.checked_add(1).filter(|next| *next <= self.end);
+        if count < self.options.truncation_threshold / 4 {
+            self.chunk = self.chunk.saturating_mul(2).min(self.options.max_chunk);
+        }
+        ChunkOutcome::Accept
+    }
+
+    /// Whether a failed request should be retried with a smaller chunk.
+    fn on_error(&mut self) -> bool {
+        if self.shrink() {
+            return true;
+        }
+        self.next = None;
+        false
+    }
+}
+
+struct RangeState {
+    methods: EventsMethods,
+    address: Option<Address>,
+    filter: Option<Vec<Filter>>,
+    planner: ChunkPlanner,
+    buffered: VecDeque<CirclesEvent>,
+}
 
 /// Methods for fetching Circles events over HTTP or websocket.
 ///
@@ -13,15 +130,26 @@ use futures::StreamExt;
 #[derive(Clone, Debug)]
 pub struct EventsMethods {
     client: RpcClient,
+    archive: RpcClient,
 }
 
 impl EventsMethods {
     /// Create a new accessor for event-related RPCs.
     pub fn new(client: RpcClient) -> Self {
-        Self { client }
+        Self {
+            archive: client.clone(),
+            client,
+        }
+    }
+
+    /// Fetch the chunks of [`Self::iter_events_with`] from `archive` instead.
+    pub fn with_archive(mut self, archive: RpcClient) -> Self {
+        self.archive = archive;
+        self
     }
 
     /// HTTP: `circles_events(address, fromBlock, toBlock?, filter?)`
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.rpc", skip_all, fields(rpc_method = "circles_events", avatar = ?address, block = from_block)))]
     pub async fn circles_events(
         &self,
         address: Option<circles_types::Address>,
@@ -31,6 +159,7 @@ impl EventsMethods {
     ) -> Result<Vec<CirclesEvent>> {
         let params = (address, from_block, to_block, filter);
         let raw: Vec<RpcSubscriptionEvent> = self.client.call("circles_events", params).await?;
+        crate::metrics::record_events("http", raw.len());
         raw.into_iter()
             .map(|e| {
                 crate::events::parser::parse(e).map_err(|err| {
@@ -42,6 +171,103 @@ impl EventsMethods {
             .collect()
     }
 
+    /// Stream every event in `[from_block, to_block]` in chain order, using default chunking.
+    pub fn iter_events(
+        &self,
+        address: Option<Address>,
+        from_block: u64,
+        to_block: u64,
+        filter: Option<Vec<Filter>>,
+    ) -> EventStream<CirclesEvent> {
+        self.iter_events_with(
+            address,
+            from_block,
+            to_block,
+            filter,
+            EventRangeOptions::default(),
+        )
+    }
+
+    /// Stream events from `cursor` through `to_block`, each with the cursor to persist after it.
+    ///
+    /// Pass the last persisted cursor to continue after a restart without repeating events.
+    pub fn resume_from(
+        &self,
+        cursor: EventCursor,
+        address: Option<Address>,
+        to_block: u64,
+        filter: Option<Vec<Filter>>,
+    ) -> EventStream<(EventCursor, CirclesEvent)> {
+        self.iter_events(address, cursor.block_number(), to_block, filter)
+            .resume_from(cursor)
+    }
+
+    /// Stream every event in `[from_block, to_block]` via chunked `circles_events` calls.
+    ///
+    /// Chunks are fetched lazily, one request at a time. A failed or apparently truncated
+    /// chunk is retried at half its size; sparse chunks double the next one. An error that
+    /// persists at `min_chunk` is yielded and ends the stream, as is
+    /// [`CirclesRpcError::TruncatedEvents`](crate::CirclesRpcError::TruncatedEvents) when a
+    /// `min_chunk` range still reaches `truncation_threshold`. Events are ordered by block,
+    /// transaction index and log index. Chunks are requested from the archive client set
+    /// with [`Self::with_archive`], if any.
+    pub fn iter_events_with(
+        &self,
+        address: Option<Address>,
+        from_block: u64,
+        to_block: u64,
+        filter: Option<Vec<Filter>>,
+        options: EventRangeOptions,
+    ) -> EventStream<CirclesEvent> {
+        let state = RangeState {
+            methods: Self::new(self.archive.clone()),
+            address,
+            filter,
+            planner: ChunkPlanner::new(from_block, to_block, options),
+            buffered: VecDeque::new(),
+        };
+        EventStream::new(futures::stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(event) = state.buffered.pop_front() {
+                    return Some((Ok(event), state));
+                }
+                let (from, to) = state.planner.range()?;
+                let result = state
+                    .methods
+                    .circles_events(state.address, from, Some(to), state.filter.clone())
+                    .await;
+                match result {
+                    Ok(mut events) => match state.planner.on_events(events.len()) {
+                        ChunkOutcome::Accept => {
+                            events.sort_by_key(|e| {
+                                (
+                                    e.base.block_number,
+                                    e.base.transaction_index,
+                                    e.base.log_index,
+                                )
+                            });
+                            state.buffered.extend(events);
+                        }
+                        ChunkOutcome::Retry => {}
+                        ChunkOutcome::Truncated => {
+                            let err = crate::error::CirclesRpcError::TruncatedEvents {
+                                from_block: from,
+                                to_block: to,
+                                count: events.len(),
+                            };
+                            return Some((Err(err), state));
+                        }
+                    },
+                    Err(err) => {
+                        if !state.planner.on_error() {
+                            return Some((Err(err), state));
+                        }
+                    }
+                }
+            }
+        }))
+    }
+
     /// Subscribe via `eth_subscribe("circles", filter)` and yield raw `RpcSubscriptionEvent`s.
     #[cfg(feature = "ws")]
     pub async fn subscribe_circles_events<F>(
@@ -58,6 +284,8 @@ impl EventsMethods {
             .client
             .subscribe::<_, serde_json::Value>(("circles", filter))?;
         let (raw_stream, id) = EventStream::from_subscription(sub).await?;
+        // Heartbeats count as activity, so the idle limit applies before they are dropped.
+        let raw_stream = raw_stream.with_idle_timeout(self.client.timeouts().ws_idle);
         let mapped = raw_stream.into_inner().flat_map(|item| match item {
             Ok(val) => {
                 // Normalize frames: empty arrays are heartbeats, arrays batch events.
@@ -85,6 +313,11 @@ impl EventsMethods {
             }
             Err(e) => futures::stream::once(async { Err(e) }).boxed(),
         });
+        let mapped = mapped.inspect(|item| {
+            if item.is_ok() {
+                crate::metrics::record_events("ws", 1);
+            }
+        });
         Ok(CirclesSubscription::new(
             EventStream::new(mapped),
             id,
@@ -106,6 +339,8 @@ impl EventsMethods {
             .client
             .subscribe::<_, serde_json::Value>(("circles", filter))?;
         let (raw_stream, id) = EventStream::from_subscription(sub).await?;
+        // Heartbeats count as activity, so the idle limit applies before they are dropped.
+        let raw_stream = raw_stream.with_idle_timeout(self.client.timeouts().ws_idle);
         let mapped = raw_stream.into_inner().flat_map(|item| match item {
             Ok(val) => {
                 if let Some(arr) = val.as_array() {
@@ -144,6 +379,11 @@ impl EventsMethods {
             }
             Err(e) => futures::stream::once(async { Err(e) }).boxed(),
         });
+        let mapped = mapped.inspect(|item| {
+            if item.is_ok() {
+                crate::metrics::record_events("ws", 1);
+            }
+        });
         Ok(CirclesSubscription::new(
             EventStream::new(mapped),
             id,
@@ -151,3 +391,47 @@ impl EventsMethods {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(initial_chunk: u64, min_chunk: u64, max_chunk: u64) -> EventRangeOptions {
+        EventRangeOptions {
+            initial_chunk,
+            min_chunk,
+            max_chunk,
+            truncation_threshold: 100,
+        }
+    }
+
+    #[test]
+    fn planner_shrinks_on_truncation_and_grows_when_sparse() {
+        let mut planner = ChunkPlanner::new(0, 999, options(400, 50, 800));
+        assert_eq!(planner.range(), Some((0, 399)));
+        assert_eq!(planner.on_events(100), ChunkOutcome::Retry);
+        assert_eq!(planner.range(), Some((0, 199)));
+        assert_eq!(planner.on_events(60), ChunkOutcome::Accept);
+        assert_eq!(planner.range(), Some((200, 399)));
+        assert_eq!(planner.on_events(0), ChunkOutcome::Accept);
+        assert_eq!(planner.range(), Some((400, 799)));
+        assert_eq!(planner.on_events(0), ChunkOutcome::Accept);
+        assert_eq!(planner.range(), Some((800, 999)));
+        assert_eq!(planner.on_events(0), ChunkOutcome::Accept);
+        assert_eq!(planner.range(), None);
+    }
+
+    #[test]
+    fn planner_gives_up_at_min_chunk() {
+        let mut planner = ChunkPlanner::new(10, 13, options(4, 2, 8));
+        assert!(planner.on_error());
+        assert_eq!(planner.range(), Some((10, 11)));
+        assert!(!planner.on_error());
+        assert_eq!(planner.range(), None);
+        // Truncated at the minimum size: report it and stop rather than loop forever.
+        let mut planner = ChunkPlanner::new(0, 5, options(1, 1, 1));
+        assert_eq!(planner.on_events(500), ChunkOutcome::Truncated);
+        assert_eq!(planner.range(), None);
+        assert_eq!(ChunkPlanner::new(5, 4, options(1, 1, 1)).range(), None);
+    }
+}
@@ -3,17 +3,66 @@ use crate::error::{CirclesRpcError, Result};
 use crate::methods::QueryMethods;
 use crate::paged_query::{PagedFetch, PagedQuery};
 use circles_types::{
-    Address, Conjunction, CursorColumn, Filter, FilterPredicate, GroupMembershipRow,
-    GroupQueryParams, GroupRow, GroupTokenHolderRow, OrderBy, PagedQueryParams, PagedResponse,
-    SortOrder,
+    Address, Conjunction, CursorColumn, Filter, FilterPredicate, GroupMemberBalance,
+    GroupMembershipRow, GroupQueryParams, GroupRow, GroupTokenHolderRow, OrderBy, PagedQueryParams,
+    PagedResponse, PagedResult, SortOrder, U256,
 };
 use serde::Serialize;
+use std::collections::HashMap;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 const DEFAULT_FIND_GROUPS_LIMIT: u32 = 50;
 const DEFAULT_GROUP_MEMBERS_LIMIT: u32 = 100;
 const DEFAULT_GROUP_MEMBERSHIPS_LIMIT: u32 = 50;
+const GROUP_HOLDERS_PAGE_SIZE: u32 = 1000;
+
+/// Ordering of [`GroupMethods::get_member_balances`] results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MemberBalanceSort {
+    /// Largest holders first (ties by member address).
+    #[default]
+    BalanceDesc,
+    /// Smallest holders first, e.g. to find members who never minted.
+    BalanceAsc,
+    /// By member address.
+    Member,
+}
+
+/// Paging and ordering for [`GroupMethods::get_member_balances`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemberBalanceOptions {
+    pub limit: u32,
+    pub offset: u32,
+    pub sort: MemberBalanceSort,
+}
+
+impl Default for MemberBalanceOptions {
+    fn default() -> Self {
+        Self {
+            limit: 50,
+            offset: 0,
+            sort: MemberBalanceSort::BalanceDesc,
+        }
+    }
+}
+
+/// One page of group member balances.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberBalancePage {
+    pub results: Vec<GroupMemberBalance>,
+    pub offset: u32,
+    /// Members across all pages.
+    pub total: u32,
+}
+
+impl MemberBalancePage {
+    /// Offset to request the following page with, if there is one.
+    pub fn next_offset(&self) -> Option<u32> {
+        let next = self.offset + self.results.len() as u32;
+        (next < self.total).then_some(next)
+    }
+}
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -40,12 +89,23 @@ impl From<GroupQueryParams> for NativeGroupQueryParams {
 #[derive(Clone, Debug)]
 pub struct GroupMethods {
     client: RpcClient,
+    archive: RpcClient,
 }
 
 impl GroupMethods {
     /// Create a new accessor for group-related RPCs.
     pub fn new(client: RpcClient) -> Self {
-        Self { client }
+        Self {
+            archive: client.clone(),
+            client,
+        }
+    }
+
+    /// Fetch the pages of the paged `Groups`/`GroupMemberships` queries from `archive`
+    /// instead.
+    pub fn with_archive(mut self, archive: RpcClient) -> Self {
+        self.archive = archive;
+        self
     }
 
     /// Native paged group discovery via `circles_findGroups`.
@@ -59,7 +119,9 @@ impl GroupMethods {
             && (params_ref.group_address_in.is_some()
                 || params_ref.group_type_in.is_some()
                 || params_ref.mint_handler_equals.is_some()
-                || params_ref.treasury_equals.is_some())
+                || params_ref.treasury_equals.is_some()
+                || params_ref.mint_policy_equals.is_some()
+                || params_ref.has_member.is_some())
         {
             return Err(CirclesRpcError::InvalidResponse {
                 message: "circles_findGroups only supports name_starts_with, symbol_starts_with, and owner_in filters".into(),
@@ -127,8 +189,22 @@ impl GroupMethods {
             + Unpin
             + 'static,
     {
-        let client = self.client.clone();
-        let fetch: PagedFetch<TRow> = Arc::new(move |params: PagedQueryParams| {
+        PagedQuery::new(self.paged_fetch(), params)
+    }
+
+    fn paged_fetch<TRow>(&self) -> PagedFetch<TRow>
+    where
+        TRow: serde::de::DeserializeOwned
+            + serde::Serialize
+            + Clone
+            + Send
+            + Sync
+            + std::fmt::Debug
+            + Unpin
+            + 'static,
+    {
+        let client = self.archive.clone();
+        Arc::new(move |params: PagedQueryParams| {
             let client = client.clone();
             Box::pin(async move { QueryMethods::new(client).paged_query::<TRow>(params).await })
                 as Pin<
@@ -137,8 +213,7 @@ impl GroupMethods {
                             + Send,
                     >,
                 >
-        });
-        PagedQuery::new(fetch, params)
+        })
     }
 
     /// circles_getGroupMemberships
@@ -281,14 +356,66 @@ impl GroupMethods {
         })
     }
 
+    /// Every current member of `group` with its holdings of the group token, as a sorted page.
+    ///
+    /// Joins `circles_getGroupMembers` with `GroupTokenHoldersBalance`; members holding
+    /// nothing appear with zero balances and holders that are not members are left out.
+    /// Both lists are loaded in full on each call, then sorted and sliced.
+    pub async fn get_member_balances(
+        &self,
+        group: Address,
+        options: &MemberBalanceOptions,
+    ) -> Result<MemberBalancePage> {
+        let mut members = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = self
+                .get_group_members_page(group, Some(DEFAULT_GROUP_MEMBERS_LIMIT), cursor.as_deref())
+                .await?;
+            members.extend(page.results);
+            cursor = page.next_cursor.filter(|_| page.has_more);
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        let mut holders = Vec::new();
+        let mut query = self.get_group_holders(group, GROUP_HOLDERS_PAGE_SIZE);
+        while let Some(page) = query.next_page().await? {
+            holders.extend(page.items);
+            if !page.has_more {
+                break;
+            }
+        }
+
+        let mut balances = join_member_balances(group, members, holders);
+        sort_member_balances(&mut balances, options.sort);
+        let total = balances.len() as u32;
+        let results = balances
+            .into_iter()
+            .skip(options.offset as usize)
+            .take(options.limit as usize)
+            .collect();
+        Ok(MemberBalancePage {
+            results,
+            offset: options.offset,
+            total,
+        })
+    }
+
     /// Paged `Groups` query with optional filters matching the TS helper.
+    ///
+    /// The `Groups` table has no member column, so `GroupQueryParams::has_member` is
+    /// resolved from the avatar's memberships when the first page is fetched and added as a
+    /// `group` filter. An avatar without memberships yields no pages.
     pub fn get_groups_paged(
         &self,
         limit: u32,
         params: Option<GroupQueryParams>,
         sort_order: SortOrder,
     ) -> PagedQuery<GroupRow> {
-        self.paged_query(PagedQueryParams {
+        let member = params.as_ref().and_then(|params| params.has_member);
+        let params = PagedQueryParams {
             namespace: "V_CrcV2".into(),
             table: "Groups".into(),
             sort_order,
@@ -317,16 +444,84 @@ impl GroupMethods {
             cursor_columns: None,
             order_columns: None,
             limit,
+        };
+        match member {
+            Some(member) => PagedQuery::new(self.member_groups_fetch(member), params),
+            None => self.paged_query(params),
+        }
+    }
+
+    /// Fetcher for `Groups` pages narrowed to the groups `member` belongs to; the
+    /// memberships are loaded once and reused for later pages.
+    fn member_groups_fetch(&self, member: Address) -> PagedFetch<GroupRow> {
+        let fetch = self.paged_fetch::<GroupRow>();
+        let methods = self.clone();
+        let resolved: Arc<Mutex<Option<Vec<Address>>>> = Arc::default();
+        Arc::new(move |mut params: PagedQueryParams| {
+            let fetch = fetch.clone();
+            let methods = methods.clone();
+            let resolved = resolved.clone();
+            Box::pin(async move {
+                let cached = resolved.lock().expect("membership cache poisoned").clone();
+                let groups = match cached {
+                    Some(groups) => groups,
+                    None => {
+                        let mut groups: Vec<Address> = Vec::new();
+                        for row in methods.get_memberships(member).await? {
+                            if !groups.contains(&row.group) {
+                                groups.push(row.group);
+                            }
+                        }
+                        *resolved.lock().expect("membership cache poisoned") = Some(groups.clone());
+                        groups
+                    }
+                };
+                if groups.is_empty() {
+                    return Ok(PagedResult {
+                        limit: params.limit,
+                        size: 0,
+                        first_cursor: None,
+                        last_cursor: None,
+                        sort_order: params.sort_order,
+                        has_more: false,
+                        results: Vec::new(),
+                    });
+                }
+                let mut filters = params.filter.take().unwrap_or_default();
+                filters.push(any_group(groups));
+                params.filter = Some(if filters.len() > 1 {
+                    vec![Conjunction::and(filters).into()]
+                } else {
+                    filters
+                });
+                fetch(params).await
+            })
         })
     }
 
+    /// Paged `Groups` query; the same as [`Self::get_groups_paged`], kept for callers
+    /// that await it.
+    pub async fn find_groups_paged(
+        &self,
+        limit: u32,
+        params: Option<GroupQueryParams>,
+        sort_order: SortOrder,
+    ) -> Result<PagedQuery<GroupRow>> {
+        Ok(self.get_groups_paged(limit, params, sort_order))
+    }
+
     /// Fetch groups across pages until `limit` rows are collected.
+    ///
+    /// Supports every `GroupQueryParams` filter, so "groups I belong to" is
+    /// `find_groups(limit, Some(GroupQueryParams { has_member: Some(me), ..Default::default() }))`.
     pub async fn find_groups(
         &self,
         limit: u32,
         params: Option<GroupQueryParams>,
     ) -> Result<Vec<GroupRow>> {
-        let mut query = self.get_groups_paged(limit, params, SortOrder::DESC);
+        let mut query = self
+            .find_groups_paged(limit, params, SortOrder::DESC)
+            .await?;
         let mut rows = Vec::new();
 
         while let Some(page) = query.next_page().await? {
@@ -341,6 +536,65 @@ impl GroupMethods {
     }
 }
 
+/// One entry per distinct member, carrying its holder row when there is one.
+fn join_member_balances(
+    group: Address,
+    members: Vec<GroupMembershipRow>,
+    holders: Vec<GroupTokenHolderRow>,
+) -> Vec<GroupMemberBalance> {
+    let holders: HashMap<Address, GroupTokenHolderRow> =
+        holders.into_iter().map(|row| (row.holder, row)).collect();
+    let mut expiry: HashMap<Address, u64> = HashMap::new();
+    for row in members {
+        let entry = expiry.entry(row.member).or_default();
+        *entry = (*entry).max(row.expiry_time);
+    }
+    expiry
+        .into_iter()
+        .map(|(member, expiry_time)| {
+            let holding = holders.get(&member);
+            GroupMemberBalance {
+                group,
+                member,
+                expiry_time,
+                total_balance: holding.map_or(U256::ZERO, |h| h.total_balance),
+                demurraged_total_balance: holding
+                    .map_or(U256::ZERO, |h| h.demurraged_total_balance),
+                fraction_ownership: holding.map_or(0.0, |h| h.fraction_ownership),
+            }
+        })
+        .collect()
+}
+
+fn sort_member_balances(balances: &mut [GroupMemberBalance], sort: MemberBalanceSort) {
+    match sort {
+        MemberBalanceSort::BalanceDesc => balances.sort_by(|a, b| {
+            b.total_balance
+                .cmp(&a.total_balance)
+                .then(a.member.cmp(&b.member))
+        }),
+        MemberBalanceSort::BalanceAsc => balances.sort_by(|a, b| {
+            a.total_balance
+                .cmp(&b.total_balance)
+                .then(a.member.cmp(&b.member))
+        }),
+        MemberBalanceSort::Member => balances.sort_by_key(|b| b.member),
+    }
+}
+
+/// `group` equal to one of `groups` (non-empty).
+fn any_group(groups: Vec<Address>) -> Filter {
+    let predicates: Vec<Filter> = groups
+        .into_iter()
+        .map(|addr| FilterPredicate::equals("group".into(), format!("{addr:#x}")).into())
+        .collect();
+    if predicates.len() == 1 {
+        predicates.into_iter().next().expect("one predicate")
+    } else {
+        Conjunction::or(predicates).into()
+    }
+}
+
 fn build_group_filters(params: Option<GroupQueryParams>) -> Option<Vec<Filter>> {
     let params = params?;
 
@@ -357,15 +611,7 @@ fn build_group_filters(params: Option<GroupQueryParams>) -> Option<Vec<Filter>>
     if let Some(group_addresses) = params.group_address_in
         && !group_addresses.is_empty()
     {
-        let predicates: Vec<Filter> = group_addresses
-            .into_iter()
-            .map(|addr| FilterPredicate::equals("group".into(), format!("{addr:#x}")).into())
-            .collect();
-        filters.push(if predicates.len() == 1 {
-            predicates.into_iter().next().expect("one predicate")
-        } else {
-            Conjunction::or(predicates).into()
-        });
+        filters.push(any_group(group_addresses));
     }
 
     if let Some(group_types) = params.group_type_in
@@ -406,6 +652,11 @@ fn build_group_filters(params: Option<GroupQueryParams>) -> Option<Vec<Filter>>
         filters.push(FilterPredicate::equals("treasury".into(), format!("{treasury:#x}")).into());
     }
 
+    if let Some(mint_policy) = params.mint_policy_equals {
+        filters
+            .push(FilterPredicate::equals("mintPolicy".into(), format!("{mint_policy:#x}")).into());
+    }
+
     if filters.len() > 1 {
         Some(vec![Conjunction::and(filters).into()])
     } else if filters.is_empty() {
@@ -449,6 +700,8 @@ mod tests {
                 owner_in: Some(vec![Address::repeat_byte(0x44)]),
                 mint_handler_equals: Some(Address::repeat_byte(0x55)),
                 treasury_equals: None,
+                mint_policy_equals: None,
+                has_member: None,
             }),
             SortOrder::ASC,
         );
@@ -463,6 +716,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn member_balances_include_non_holders_and_sort() {
+        let group = Address::repeat_byte(0x01);
+        let member = |byte: u8, expiry_time: u64| GroupMembershipRow {
+            block_number: 1,
+            timestamp: 1,
+            transaction_index: 0,
+            log_index: 0,
+            transaction_hash: Default::default(),
+            group,
+            member: Address::repeat_byte(byte),
+            expiry_time,
+        };
+        let holder = |byte: u8, balance: u64| GroupTokenHolderRow {
+            group,
+            holder: Address::repeat_byte(byte),
+            total_balance: U256::from(balance),
+            demurraged_total_balance: U256::from(balance),
+            fraction_ownership: 0.5,
+        };
+        let mut balances = join_member_balances(
+            group,
+            vec![
+                member(0xa, 10),
+                member(0xb, 20),
+                member(0xa, 30),
+                member(0xc, 5),
+            ],
+            vec![holder(0xb, 7), holder(0xc, 9), holder(0xd, 100)],
+        );
+        assert_eq!(balances.len(), 3);
+
+        sort_member_balances(&mut balances, MemberBalanceSort::BalanceDesc);
+        let order: Vec<_> = balances
+            .iter()
+            .map(|b| (b.member, b.total_balance))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (Address::repeat_byte(0xc), U256::from(9)),
+                (Address::repeat_byte(0xb), U256::from(7)),
+                (Address::repeat_byte(0xa), U256::ZERO),
+            ]
+        );
+        assert_eq!(balances[2].expiry_time, 30);
+
+        sort_member_balances(&mut balances, MemberBalanceSort::BalanceAsc);
+        assert_eq!(balances[0].member, Address::repeat_byte(0xa));
+    }
+
     #[test]
     fn group_holders_query_matches_ts_cursor_and_order_shape() {
         let query = methods().get_group_holders(Address::repeat_byte(0x66), 10);
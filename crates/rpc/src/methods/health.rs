@@ -1,6 +1,9 @@
 use crate::client::RpcClient;
 use crate::error::Result;
+use crate::methods::QueryMethods;
+use circles_types::{OrderBy, QueryParams};
 use serde::Deserialize;
+use serde_json::Value;
 
 /// Health payload returned by `circles_health`.
 #[derive(Clone, Debug, Deserialize)]
@@ -25,4 +28,24 @@ impl HealthMethods {
     pub async fn health(&self) -> Result<HealthResponse> {
         self.client.call("circles_health", ()).await
     }
+
+    /// Highest block the indexer has processed (latest `System.Block` row), or `None`
+    /// before the first block is indexed.
+    pub async fn indexed_block_number(&self) -> Result<Option<u64>> {
+        let rows: Vec<Value> = QueryMethods::new(self.client.clone())
+            .circles_query(QueryParams {
+                namespace: "System".to_string(),
+                table: "Block".to_string(),
+                columns: vec!["blockNumber".to_string()],
+                filter: Vec::new(),
+                order: vec![OrderBy::desc("blockNumber".to_string())],
+                limit: Some(1),
+            })
+            .await?;
+        Ok(rows.first().and_then(|row| match &row["blockNumber"] {
+            Value::Number(n) => n.as_u64(),
+            Value::String(s) => s.parse().ok(),
+            _ => None,
+        }))
+    }
 }
@@ -2,7 +2,7 @@ use crate::client::RpcClient;
 use crate::error::Result;
 use circles_types::{
     Address, AllInvitationsResponse, AtScaleInvitation, Balance, EscrowInvitation,
-    InvitationOriginResponse, InvitationsFromResponse, TrustInvitation,
+    InvitationOriginResponse, InvitationsFromResponse, InvitedAccountInfo, TrustInvitation,
 };
 use futures::pin_mut;
 use futures::stream::{self, StreamExt};
@@ -27,6 +27,50 @@ pub struct InvitationRow {
     pub invitation_balance: Balance,
 }
 
+/// Where an invitee stands in an inviter's onboarding funnel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InviteeStatus {
+    /// The invitee registered using the invitation.
+    Redeemed,
+    /// Still open.
+    Pending,
+    /// Reported by the backend as expired or revoked; it can no longer be redeemed.
+    Expired,
+}
+
+/// One invitee in [`InviteStats`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InviteeState {
+    pub invitee: Address,
+    pub status: InviteeStatus,
+    pub block_number: u64,
+    pub timestamp: u64,
+}
+
+/// Invitation funnel of one inviter, from [`InvitationMethods::get_invite_stats`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InviteStats {
+    pub inviter: Address,
+    /// Distinct invitees, whatever their status.
+    pub sent: usize,
+    pub redeemed: usize,
+    pub pending: usize,
+    pub expired: usize,
+    /// Per-invitee status, most recent first.
+    pub invitees: Vec<InviteeState>,
+}
+
+impl InviteStats {
+    /// Share of sent invitations that were redeemed (0 when none were sent).
+    pub fn redemption_rate(&self) -> f64 {
+        if self.sent == 0 {
+            0.0
+        } else {
+            self.redeemed as f64 / self.sent as f64
+        }
+    }
+}
+
 impl InvitationMethods {
     pub fn new(client: RpcClient) -> Self {
         Self { client }
@@ -142,6 +186,18 @@ impl InvitationMethods {
         Ok(rows)
     }
 
+    /// Sent/redeemed/pending/expired counts and per-invitee status for `inviter`.
+    ///
+    /// The backend splits invitees into accepted and open ones (`circles_getInvitationsFrom`,
+    /// both fetched concurrently); only the tally across the two lists happens client-side.
+    pub async fn get_invite_stats(&self, inviter: Address) -> Result<InviteStats> {
+        let (accepted, open) = futures::try_join!(
+            self.get_invitations_from(inviter, true),
+            self.get_invitations_from(inviter, false),
+        )?;
+        Ok(invite_stats(inviter, accepted.results, open.results))
+    }
+
     /// `circles_getInvitationsFrom` — accepted or pending invitees for an inviter.
     pub async fn get_invitations_from(
         &self,
@@ -153,3 +209,109 @@ impl InvitationMethods {
             .await
     }
 }
+
+fn invite_stats(
+    inviter: Address,
+    accepted: Vec<InvitedAccountInfo>,
+    open: Vec<InvitedAccountInfo>,
+) -> InviteStats {
+    let mut invitees: Vec<InviteeState> = Vec::with_capacity(accepted.len() + open.len());
+    let accepted = accepted
+        .into_iter()
+        .map(|account| (account, InviteeStatus::Redeemed));
+    let open = open.into_iter().map(|account| {
+        let status = match account.status.to_ascii_lowercase().as_str() {
+            "expired" | "revoked" => InviteeStatus::Expired,
+            _ => InviteeStatus::Pending,
+        };
+        (account, status)
+    });
+    // Accepted rows come first, so an invitee listed twice keeps the redeemed entry.
+    for (account, status) in accepted.chain(open) {
+        if invitees
+            .iter()
+            .any(|state| state.invitee == account.address)
+        {
+            continue;
+        }
+        invitees.push(InviteeState {
+            invitee: account.address,
+            status,
+            block_number: account.block_number,
+            timestamp: account.timestamp,
+        });
+    }
+    invitees.sort_by(|a, b| {
+        b.block_number
+            .cmp(&a.block_number)
+            .then(b.timestamp.cmp(&a.timestamp))
+    });
+
+    let count = |status| invitees.iter().filter(|s| s.status == status).count();
+    InviteStats {
+        inviter,
+        sent: invitees.len(),
+        redeemed: count(InviteeStatus::Redeemed),
+        pending: count(InviteeStatus::Pending),
+        expired: count(InviteeStatus::Expired),
+        invitees,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(byte: u8, status: &str, block_number: u64) -> InvitedAccountInfo {
+        InvitedAccountInfo {
+            address: Address::repeat_byte(byte),
+            status: status.to_string(),
+            block_number,
+            timestamp: block_number * 5,
+            avatar_info: None,
+        }
+    }
+
+    #[test]
+    fn invite_stats_tally_the_funnel() {
+        let inviter = Address::repeat_byte(0xaa);
+        let stats = invite_stats(
+            inviter,
+            vec![account(1, "accepted", 10), account(2, "accepted", 30)],
+            vec![
+                account(3, "pending", 20),
+                account(4, "Expired", 40),
+                // Re-invited after redeeming: still counted once, as redeemed.
+                account(1, "pending", 50),
+            ],
+        );
+
+        assert_eq!(stats.inviter, inviter);
+        assert_eq!(
+            (stats.sent, stats.redeemed, stats.pending, stats.expired),
+            (4, 2, 1, 1)
+        );
+        assert_eq!(stats.redemption_rate(), 0.5);
+        let order: Vec<_> = stats
+            .invitees
+            .iter()
+            .map(|state| (state.invitee, state.status))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (Address::repeat_byte(4), InviteeStatus::Expired),
+                (Address::repeat_byte(2), InviteeStatus::Redeemed),
+                (Address::repeat_byte(3), InviteeStatus::Pending),
+                (Address::repeat_byte(1), InviteeStatus::Redeemed),
+            ]
+        );
+    }
+
+    #[test]
+    fn no_invitations_means_zero_rate() {
+        let stats = invite_stats(Address::ZERO, Vec::new(), Vec::new());
+        assert_eq!(stats.sent, 0);
+        assert_eq!(stats.redemption_rate(), 0.0);
+    }
+}
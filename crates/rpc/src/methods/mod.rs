@@ -1,6 +1,7 @@
 //! RPC method namespaces. Each module mirrors the TS SDK methods with
 //! thin wrappers around `RpcClient`.
 
+pub mod aggregate;
 pub mod avatar;
 pub mod balance;
 pub mod events;
@@ -18,17 +19,18 @@ pub mod token_info;
 pub mod transaction;
 pub mod trust;
 
+pub use aggregate::{AggregateProgress, AggregateQuery};
 pub use avatar::AvatarMethods;
 pub use balance::BalanceMethods;
-pub use events::EventsMethods;
-pub use group::GroupMethods;
+pub use events::{EventRangeOptions, EventsMethods};
+pub use group::{GroupMethods, MemberBalanceOptions, MemberBalancePage, MemberBalanceSort};
 pub use health::HealthMethods;
-pub use invitation::InvitationMethods;
+pub use invitation::{InvitationMethods, InviteStats, InviteeState, InviteeStatus};
 pub use network::NetworkMethods;
 pub use pathfinder::PathfinderMethods;
 pub use query::QueryMethods;
 pub use sdk::SdkMethods;
-pub use search::SearchMethods;
+pub use search::{SearchMethods, SearchOptions, SearchPage, SearchRanking};
 pub use tables::TablesMethods;
 pub use token::TokenMethods;
 pub use token_info::TokenInfoMethods;
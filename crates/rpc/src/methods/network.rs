@@ -1,6 +1,6 @@
 use crate::client::RpcClient;
 use crate::error::Result;
-use circles_types::NetworkSnapshot;
+use circles_types::{NetworkSnapshot, SnapshotDiff};
 
 /// Methods for fetching network snapshots (`circles_getNetworkSnapshot`).
 #[derive(Clone, Debug)]
@@ -18,4 +18,24 @@ impl NetworkMethods {
     pub async fn snapshot(&self) -> Result<NetworkSnapshot> {
         self.client.call("circles_getNetworkSnapshot", ()).await
     }
+
+    /// circles_getNetworkSnapshot (at a specific block)
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.rpc", skip_all, fields(rpc_method = "circles_getNetworkSnapshot", block = block)))]
+    pub async fn get_network_snapshot_at(&self, block: u64) -> Result<NetworkSnapshot> {
+        self.client
+            .call("circles_getNetworkSnapshot", (block,))
+            .await
+    }
+
+    /// Fetch the latest snapshot and diff it against `previous`.
+    ///
+    /// Returns the new snapshot alongside the diff so callers can keep it as the next baseline.
+    pub async fn diff_since(
+        &self,
+        previous: &NetworkSnapshot,
+    ) -> Result<(NetworkSnapshot, SnapshotDiff)> {
+        let latest = self.snapshot().await?;
+        let diff = SnapshotDiff::between(previous, &latest);
+        Ok((latest, diff))
+    }
 }
@@ -1,6 +1,8 @@
 use crate::client::RpcClient;
-use crate::error::Result;
-use circles_types::{FindPathParams, PathfindingResult, SimulatedBalance, SimulatedTrust};
+use crate::error::{CirclesRpcError, Result};
+use circles_types::{
+    FindPathParams, PathfinderConfig, PathfindingResult, SimulatedBalance, SimulatedTrust,
+};
 
 /// Methods for invoking the pathfinder (max-flow) RPC.
 ///
@@ -9,17 +11,38 @@ use circles_types::{FindPathParams, PathfindingResult, SimulatedBalance, Simulat
 #[derive(Clone, Debug)]
 pub struct PathfinderMethods {
     client: RpcClient,
+    config: PathfinderConfig,
 }
 
 impl PathfinderMethods {
     /// Create a new accessor for pathfinder RPCs.
     pub fn new(client: RpcClient) -> Self {
-        Self { client }
+        Self::with_config(client, PathfinderConfig::default())
+    }
+
+    /// Accessor applying `config`'s timeout and `max_transfers` default to every request.
+    pub fn with_config(client: RpcClient, config: PathfinderConfig) -> Self {
+        Self { client, config }
     }
 
     /// circlesV2_findPath — accepts full FindPathParams
-    pub async fn find_path(&self, params: FindPathParams) -> Result<PathfindingResult> {
-        self.client.call("circlesV2_findPath", (params,)).await
+    ///
+    /// Fails with [`CirclesRpcError::Timeout`] when the configured timeout elapses.
+    pub async fn find_path(&self, mut params: FindPathParams) -> Result<PathfindingResult> {
+        self.config.apply_defaults(&mut params);
+        // The pathfinder timeout replaces the client's per-request limit.
+        let call = self.client.call_unbounded("circlesV2_findPath", (params,));
+        match self.config.timeout {
+            Some(after) => {
+                tokio::time::timeout(after, call)
+                    .await
+                    .map_err(|_| CirclesRpcError::Timeout {
+                        method: "circlesV2_findPath",
+                        after,
+                    })?
+            }
+            None => call.await,
+        }
     }
 
     /// Compatibility variant that lets callers overlay simulated balance/trust inputs.
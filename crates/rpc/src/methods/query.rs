@@ -1,9 +1,13 @@
 use crate::client::RpcClient;
+use crate::columns::row_columns;
 use crate::error::{CirclesRpcError, Result};
+use crate::paged_query::{PagedFetch, PagedQuery};
 use circles_types::{
     CirclesQueryResponse, Cursor, CursorColumn, OrderBy, PagedQueryParams, PagedResult, QueryParams,
 };
 use serde_json::Value;
+use std::pin::Pin;
+use std::sync::Arc;
 
 /// Methods for issuing `circles_query` requests and decoding the tabular response.
 ///
@@ -12,12 +16,22 @@ use serde_json::Value;
 #[derive(Clone, Debug)]
 pub struct QueryMethods {
     client: RpcClient,
+    archive: RpcClient,
 }
 
 impl QueryMethods {
     /// Create a new accessor for `circles_query` RPCs.
     pub fn new(client: RpcClient) -> Self {
-        Self { client }
+        Self {
+            archive: client.clone(),
+            client,
+        }
+    }
+
+    /// Fetch the pages of [`Self::paged`] from `archive` instead.
+    pub fn with_archive(mut self, archive: RpcClient) -> Self {
+        self.archive = archive;
+        self
     }
 
     /// Direct `circles_query` invocation returning decoded rows.
@@ -32,6 +46,10 @@ impl QueryMethods {
     /// Convenience wrapper for paged queries using the `circles_query` method.
     /// Note: The underlying backend expects `QueryParams`; we translate from the
     /// higher-level `PagedQueryParams` struct.
+    ///
+    /// Empty `columns` are filled from `TRow`'s serde field names plus the cursor columns
+    /// (see [`row_columns`]); untyped rows such as `serde_json::Value` and rows with serde
+    /// aliases still select everything.
     pub async fn paged_query<TRow>(&self, params: PagedQueryParams) -> Result<PagedResult<TRow>>
     where
         TRow: serde::de::DeserializeOwned
@@ -73,6 +91,12 @@ impl QueryMethods {
             params.resolved_cursor_columns()
         };
 
+        let columns = if columns.is_empty() {
+            inferred_columns::<TRow>(&cursor_columns)
+        } else {
+            columns
+        };
+
         let query_params = QueryParams {
             namespace,
             table,
@@ -103,6 +127,35 @@ impl QueryMethods {
         })
     }
 
+    /// Build a [`PagedQuery`] that fetches pages through the archive client.
+    pub fn paged<TRow>(&self, params: PagedQueryParams) -> PagedQuery<TRow>
+    where
+        TRow: serde::de::DeserializeOwned
+            + serde::Serialize
+            + Clone
+            + Send
+            + Sync
+            + std::fmt::Debug
+            + Unpin
+            + 'static,
+    {
+        let client = self.archive.clone();
+        let fetch: PagedFetch<TRow> = Arc::new(move |params: PagedQueryParams| {
+            let client = client.clone();
+            Box::pin(async move {
+                // Use the higher-level query helper to decode rows and cursors.
+                QueryMethods::new(client).paged_query::<TRow>(params).await
+            })
+                as Pin<
+                    Box<
+                        dyn std::future::Future<Output = Result<circles_types::PagedResult<TRow>>>
+                            + Send,
+                    >,
+                >
+        });
+        PagedQuery::new(fetch, params)
+    }
+
     pub fn decode_rows<TRow>(
         &self,
         columns: Vec<String>,
@@ -203,3 +256,18 @@ impl QueryMethods {
         Self::as_u64(val).map(|v| v as u32)
     }
 }
+
+/// Columns for a typed row with cursor columns appended, or none (all columns) for untyped rows.
+fn inferred_columns<TRow: serde::de::DeserializeOwned>(
+    cursor_columns: &[CursorColumn],
+) -> Vec<String> {
+    let Some(mut columns) = row_columns::<TRow>() else {
+        return Vec::new();
+    };
+    for cursor in cursor_columns {
+        if !columns.contains(&cursor.name) {
+            columns.push(cursor.name.clone());
+        }
+    }
+    columns
+}
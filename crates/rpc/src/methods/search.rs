@@ -1,6 +1,11 @@
 use crate::client::RpcClient;
 use crate::error::Result;
-use circles_types::Profile;
+use crate::methods::QueryMethods;
+use circles_types::{
+    Address, AvatarType, Conjunction, Filter, FilterPredicate, OrderBy, Profile, QueryParams,
+    SearchResultProfile,
+};
+use serde::Deserialize;
 
 /// Methods for full-text profile search (`circles_searchProfiles`).
 #[derive(Clone, Debug)]
@@ -8,6 +13,63 @@ pub struct SearchMethods {
     client: RpcClient,
 }
 
+/// Client-side ordering applied to a page of search hits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchRanking {
+    /// Keep the backend's order.
+    #[default]
+    Backend,
+    /// Exact name matches first, then prefix, word-prefix, substring, and
+    /// in-order-characters matches.
+    Fuzzy,
+    /// Alphabetical by name (case-insensitive).
+    Name,
+}
+
+/// Paging, filtering, and ranking for [`SearchMethods`] queries.
+#[derive(Debug, Clone)]
+pub struct SearchOptions {
+    pub limit: u32,
+    pub offset: u32,
+    /// Only return avatars of these types.
+    pub avatar_types: Option<Vec<AvatarType>>,
+    pub ranking: SearchRanking,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            limit: 20,
+            offset: 0,
+            avatar_types: None,
+            ranking: SearchRanking::Backend,
+        }
+    }
+}
+
+/// One page of search hits.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchPage {
+    pub results: Vec<SearchResultProfile>,
+    pub offset: u32,
+    pub has_more: bool,
+}
+
+impl SearchPage {
+    /// Offset to request the following page with, if there is one.
+    pub fn next_offset(&self) -> Option<u32> {
+        self.has_more
+            .then(|| self.offset.saturating_add(self.results.len() as u32))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AvatarRow {
+    avatar: Address,
+    #[serde(rename = "type")]
+    avatar_type: Option<AvatarType>,
+}
+
 impl SearchMethods {
     /// Create a new accessor for search RPCs.
     pub fn new(client: RpcClient) -> Self {
@@ -20,4 +82,216 @@ impl SearchMethods {
             .call("circles_searchProfiles", (query, limit))
             .await
     }
+
+    /// Paged `circles_searchProfiles` returning avatar type and preview image per hit.
+    ///
+    /// Ranking is applied within the returned page only.
+    pub async fn search_profiles_paged(
+        &self,
+        query: &str,
+        options: &SearchOptions,
+    ) -> Result<SearchPage> {
+        // One extra row tells whether another page exists.
+        let mut results: Vec<SearchResultProfile> = self
+            .client
+            .call(
+                "circles_searchProfiles",
+                (
+                    query.to_lowercase(),
+                    options.limit.saturating_add(1),
+                    options.offset,
+                    options.avatar_types.clone(),
+                ),
+            )
+            .await?;
+        let has_more = results.len() > options.limit as usize;
+        results.truncate(options.limit as usize);
+        rank(&mut results, query, options.ranking);
+        Ok(SearchPage {
+            results,
+            offset: options.offset,
+            has_more,
+        })
+    }
+
+    /// Avatars whose address starts with `prefix` (with or without `0x`), with their
+    /// profiles where one exists. A prefix that is not hex matches nothing.
+    pub async fn search_by_address_prefix(
+        &self,
+        prefix: &str,
+        options: &SearchOptions,
+    ) -> Result<SearchPage> {
+        let hex = prefix.strip_prefix("0x").unwrap_or(prefix).to_lowercase();
+        if hex.len() > 40 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Ok(SearchPage {
+                results: Vec::new(),
+                offset: options.offset,
+                has_more: false,
+            });
+        }
+
+        let mut filter: Vec<Filter> =
+            vec![FilterPredicate::like("avatar".into(), format!("0x{hex}%")).into()];
+        if let Some(types) = options.avatar_types.as_ref().filter(|t| !t.is_empty()) {
+            let predicates: Vec<Filter> = types
+                .iter()
+                .map(|ty| FilterPredicate::equals("type".into(), serde_json::json!(ty)).into())
+                .collect();
+            filter.push(Conjunction::or(predicates).into());
+        }
+        let params = QueryParams {
+            namespace: "V_Crc".into(),
+            table: "Avatars".into(),
+            columns: vec!["avatar".into(), "type".into()],
+            filter,
+            order: vec![OrderBy::asc("avatar".into())],
+            limit: Some(
+                options
+                    .offset
+                    .saturating_add(options.limit)
+                    .saturating_add(1),
+            ),
+        };
+        let rows: Vec<AvatarRow> = QueryMethods::new(self.client.clone())
+            .circles_query(params)
+            .await?;
+        let has_more = rows.len() > options.offset.saturating_add(options.limit) as usize;
+        let rows: Vec<AvatarRow> = rows
+            .into_iter()
+            .skip(options.offset as usize)
+            .take(options.limit as usize)
+            .collect();
+        if rows.is_empty() {
+            return Ok(SearchPage {
+                results: Vec::new(),
+                offset: options.offset,
+                has_more,
+            });
+        }
+
+        let addresses: Vec<Address> = rows.iter().map(|row| row.avatar).collect();
+        let profiles: Vec<Option<Profile>> = self
+            .client
+            .call("circles_getProfileByAddressBatch", (addresses,))
+            .await?;
+        let mut results: Vec<SearchResultProfile> = rows
+            .into_iter()
+            .zip(profiles.into_iter().chain(std::iter::repeat(None)))
+            .map(|(row, profile)| SearchResultProfile {
+                address: row.avatar,
+                avatar_type: row.avatar_type,
+                name: profile.as_ref().map(|p| p.name.clone()).unwrap_or_default(),
+                description: profile.as_ref().and_then(|p| p.description.clone()),
+                preview_image_url: profile.as_ref().and_then(|p| p.preview_image_url.clone()),
+                image_url: profile.and_then(|p| p.image_url),
+            })
+            .collect();
+        rank(&mut results, "", options.ranking);
+        Ok(SearchPage {
+            results,
+            offset: options.offset,
+            has_more,
+        })
+    }
+}
+
+fn rank(results: &mut [SearchResultProfile], query: &str, ranking: SearchRanking) {
+    match ranking {
+        SearchRanking::Backend => {}
+        SearchRanking::Name => results.sort_by_cached_key(|hit| hit.name.to_lowercase()),
+        SearchRanking::Fuzzy => {
+            let query = query.trim().to_lowercase();
+            results.sort_by_cached_key(|hit| fuzzy_score(&query, &hit.name.to_lowercase()));
+        }
+    }
+}
+
+/// Lower is better; non-matching names sort last.
+fn fuzzy_score(query: &str, name: &str) -> u8 {
+    if query.is_empty() {
+        return 0;
+    }
+    if name == query {
+        0
+    } else if name.starts_with(query) {
+        1
+    } else if name.split_whitespace().any(|word| word.starts_with(query)) {
+        2
+    } else if name.contains(query) {
+        3
+    } else if is_subsequence(query, name) {
+        4
+    } else {
+        5
+    }
+}
+
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut chars = haystack.chars();
+    needle.chars().all(|c| chars.any(|h| h == c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(name: &str) -> SearchResultProfile {
+        SearchResultProfile {
+            address: Address::ZERO,
+            avatar_type: None,
+            name: name.to_string(),
+            description: None,
+            preview_image_url: None,
+            image_url: None,
+        }
+    }
+
+    #[test]
+    fn fuzzy_ranking_prefers_closer_matches() {
+        let mut results = vec![
+            hit("Xavier"),
+            hit("Berlin Alice"),
+            hit("Malice"),
+            hit("Alicia"),
+            hit("alice"),
+            hit("a-l-i-c-e"),
+        ];
+        rank(&mut results, "Alice", SearchRanking::Fuzzy);
+        let names: Vec<_> = results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "alice",
+                "Berlin Alice",
+                "Malice",
+                "a-l-i-c-e",
+                "Xavier",
+                "Alicia"
+            ]
+        );
+    }
+
+    #[test]
+    fn next_offset_follows_page() {
+        let page = SearchPage {
+            results: vec![hit("a"), hit("b")],
+            offset: 10,
+            has_more: true,
+        };
+        assert_eq!(page.next_offset(), Some(12));
+        assert_eq!(
+            SearchPage {
+                has_more: false,
+                ..page
+            }
+            .next_offset(),
+            None
+        );
+        let last = SearchPage {
+            results: vec![hit("a")],
+            offset: u32::MAX,
+            has_more: true,
+        };
+        assert_eq!(last.next_offset(), Some(u32::MAX));
+    }
 }
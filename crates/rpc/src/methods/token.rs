@@ -1,7 +1,13 @@
 use crate::client::RpcClient;
-use crate::error::Result;
+use crate::error::{CirclesRpcError, Result};
+use crate::methods::QueryMethods;
+use crate::paged_query::PagedQuery;
 use alloy_primitives::U256;
-use circles_types::{Address, PagedResponse, TokenBalanceResponse, TokenHolder, TokenHolderRow};
+use circles_types::{
+    Address, CursorColumn, FilterPredicate, OrderBy, PagedQueryParams, PagedResponse, SortOrder,
+    TokenBalanceResponse, TokenHolder, TokenHolderRow,
+};
+use futures::{Stream, TryStreamExt};
 use std::str::FromStr;
 
 const DEFAULT_TOKEN_HOLDER_LIMIT: u32 = 100;
@@ -13,6 +19,7 @@ const DEFAULT_TOKEN_HOLDER_LIMIT: u32 = 100;
 #[derive(Clone, Debug)]
 pub struct TokenMethods {
     client: RpcClient,
+    archive: RpcClient,
 }
 
 /// Normalized token holder with numeric balance.
@@ -51,7 +58,16 @@ impl From<TokenHolderRow> for TokenHolderNormalized {
 impl TokenMethods {
     /// Create a new accessor for token-related RPCs.
     pub fn new(client: RpcClient) -> Self {
-        Self { client }
+        Self {
+            archive: client.clone(),
+            client,
+        }
+    }
+
+    /// Fetch the pages of [`Self::get_token_holders_by_balance`] from `archive` instead.
+    pub fn with_archive(mut self, archive: RpcClient) -> Self {
+        self.archive = archive;
+        self
     }
 
     /// circles_getTokenBalances (v1/v2, selected via `use_v2`)
@@ -100,29 +116,81 @@ impl TokenMethods {
 
     /// Raw token holders (string balances) if needed.
     pub async fn get_token_holders_raw(&self, token: Address) -> Result<Vec<TokenHolder>> {
-        let mut cursor: Option<String> = None;
-        let mut holders = Vec::new();
+        self.get_token_holders_stream(token).try_collect().await
+    }
 
-        loop {
-            let page = self
-                .get_token_holders_page(token, Some(DEFAULT_TOKEN_HOLDER_LIMIT), cursor.as_deref())
-                .await?;
+    /// Stream every holder of `token`, one `circles_getTokenHolders` page at a time.
+    ///
+    /// Rows arrive in the backend's order; pages are only requested as the stream is polled,
+    /// so large groups can be processed without holding every holder in memory.
+    pub fn get_token_holders_stream(
+        &self,
+        token: Address,
+    ) -> impl Stream<Item = Result<TokenHolder>> + Send + 'static {
+        let methods = self.clone();
+        // `None` once the last page has been yielded; `Some(cursor)` otherwise.
+        futures::stream::try_unfold(Some(None::<String>), move |cursor| {
+            let methods = methods.clone();
+            async move {
+                let Some(cursor) = cursor else {
+                    return Ok(None);
+                };
+                let page = methods
+                    .get_token_holders_page(
+                        token,
+                        Some(DEFAULT_TOKEN_HOLDER_LIMIT),
+                        cursor.as_deref(),
+                    )
+                    .await?;
+                let next = page.next_cursor.filter(|_| page.has_more).map(Some);
+                let rows = page.results.into_iter().map(|row| Ok(holder_from_row(row)));
+                Ok::<_, CirclesRpcError>(Some((futures::stream::iter(rows), next)))
+            }
+        })
+        .try_flatten()
+    }
 
-            holders.extend(page.results.into_iter().map(|holder| TokenHolder {
-                account: holder.account,
-                token_address: holder.token_address,
-                demurraged_total_balance: holder.balance,
-            }));
+    /// Holders of `token` ordered by demurraged balance, via `circles_query` on
+    /// `V_CrcV2.BalancesByAccountAndToken`.
+    ///
+    /// `circles_getTokenHolders` has no sort parameter, so this is the way to get the
+    /// largest (or smallest) holders first. Ties are broken by account, which keeps the
+    /// cursor stable across pages; use `into_stream()` to walk every holder.
+    pub fn get_token_holders_by_balance(
+        &self,
+        token: Address,
+        sort_order: SortOrder,
+        limit: u32,
+    ) -> PagedQuery<TokenHolder> {
+        let balance = CursorColumn::new("demurragedTotalBalance".into(), sort_order.clone());
+        QueryMethods::new(self.archive.clone()).paged(PagedQueryParams {
+            namespace: "V_CrcV2".into(),
+            table: "BalancesByAccountAndToken".into(),
+            sort_order: sort_order.clone(),
+            columns: TOKEN_HOLDER_COLUMNS
+                .iter()
+                .map(|column| column.to_string())
+                .collect(),
+            filter: Some(vec![
+                FilterPredicate::equals("tokenAddress".into(), format!("{token:#x}")).into(),
+            ]),
+            cursor_columns: Some(vec![balance, CursorColumn::asc("account".into())]),
+            order_columns: Some(vec![
+                OrderBy::new("demurragedTotalBalance".into(), sort_order),
+                OrderBy::asc("account".into()),
+            ]),
+            limit,
+        })
+    }
+}
 
-            if !page.has_more {
-                break;
-            }
-            cursor = page.next_cursor;
-            if cursor.is_none() {
-                break;
-            }
-        }
+/// `V_CrcV2.BalancesByAccountAndToken` columns read into a [`TokenHolder`].
+const TOKEN_HOLDER_COLUMNS: [&str; 3] = ["account", "tokenAddress", "demurragedTotalBalance"];
 
-        Ok(holders)
+fn holder_from_row(row: TokenHolderRow) -> TokenHolder {
+    TokenHolder {
+        account: row.account,
+        token_address: row.token_address,
+        demurraged_total_balance: row.balance,
     }
 }
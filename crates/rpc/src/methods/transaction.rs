@@ -70,12 +70,22 @@ fn enrich_transaction_row(row: RawTransactionHistoryRow) -> Result<TransactionHi
 #[derive(Clone, Debug)]
 pub struct TransactionMethods {
     client: RpcClient,
+    archive: RpcClient,
 }
 
 impl TransactionMethods {
     /// Create a new accessor for transaction-history RPCs.
     pub fn new(client: RpcClient) -> Self {
-        Self { client }
+        Self {
+            archive: client.clone(),
+            client,
+        }
+    }
+
+    /// Fetch the pages of [`Self::get_transaction_history`] from `archive` instead.
+    pub fn with_archive(mut self, archive: RpcClient) -> Self {
+        self.archive = archive;
+        self
     }
 
     /// Native paged transaction history via `circles_getTransactionHistory`.
@@ -141,7 +151,7 @@ impl TransactionMethods {
             limit,
         };
 
-        let client = self.client.clone();
+        let client = self.archive.clone();
         let fetch: PagedFetch<TransactionHistoryRow> = Arc::new(move |params: PagedQueryParams| {
             let client = client.clone();
             Box::pin(async move {
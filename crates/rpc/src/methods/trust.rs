@@ -1,6 +1,7 @@
 use crate::client::RpcClient;
 use crate::error::Result;
 use circles_types::{Address, AggregatedTrustRelation, TrustRelation, TrustRelationType};
+use circles_utils::time::unix_now;
 
 /// Methods for trust relation queries.
 ///
@@ -33,6 +34,21 @@ impl TrustMethods {
             .await
     }
 
+    /// One relation per counterpart of `avatar`, merged client-side from
+    /// `circles_getTrustRelations` like the TS SDK: rows in both directions become
+    /// `mutuallyTrusts`, and relations already expired are dropped.
+    pub async fn compute_aggregated_trust_relations(
+        &self,
+        avatar: Address,
+    ) -> Result<Vec<AggregatedTrustRelation>> {
+        let relations = self.get_trust_relations(avatar).await?;
+        Ok(AggregatedTrustRelation::aggregate(
+            avatar,
+            &relations,
+            unix_now(),
+        ))
+    }
+
     /// circles_getCommonTrust
     pub async fn get_common_trust(
         &self,
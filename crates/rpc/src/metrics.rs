@@ -0,0 +1,65 @@
+//! Metric names and recorders for the `metrics` feature.
+//!
+//! Values are emitted through the [`metrics`](https://docs.rs/metrics) facade, so a
+//! service decides where they go by installing a recorder (for example
+//! `metrics-exporter-prometheus`). Without the feature the recorders compile to no-ops.
+
+use std::time::Duration;
+
+/// Counter of JSON-RPC calls, labelled by `method` and `outcome` (`ok`/`error`).
+pub const RPC_REQUESTS_TOTAL: &str = "circles_rpc_requests_total";
+/// Histogram of JSON-RPC call latency in seconds, labelled by `method`.
+pub const RPC_REQUEST_DURATION_SECONDS: &str = "circles_rpc_request_duration_seconds";
+/// Counter of requests retried on another endpoint after a transport error.
+pub const RPC_FAILOVERS_TOTAL: &str = "circles_rpc_failovers_total";
+/// Counter of `circles_query` pages fetched by `PagedQuery`, labelled by `table`.
+pub const PAGES_FETCHED_TOTAL: &str = "circles_rpc_pages_fetched_total";
+/// Counter of rows returned by `PagedQuery`, labelled by `table`.
+pub const ROWS_FETCHED_TOTAL: &str = "circles_rpc_rows_fetched_total";
+/// Counter of parsed Circles events, labelled by `transport` (`http`/`ws`).
+pub const EVENTS_RECEIVED_TOTAL: &str = "circles_rpc_events_received_total";
+/// Counter of events lost to a full `BufferedSubscription`.
+pub const EVENTS_DROPPED_TOTAL: &str = "circles_rpc_events_dropped_total";
+
+pub(crate) fn record_request(method: &str, ok: bool, elapsed: Duration) {
+    #[cfg(feature = "metrics")]
+    {
+        let outcome = if ok { "ok" } else { "error" };
+        ::metrics::counter!(RPC_REQUESTS_TOTAL, "method" => method.to_string(), "outcome" => outcome)
+            .increment(1);
+        ::metrics::histogram!(RPC_REQUEST_DURATION_SECONDS, "method" => method.to_string())
+            .record(elapsed.as_secs_f64());
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = (method, ok, elapsed);
+}
+
+pub(crate) fn record_failover() {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!(RPC_FAILOVERS_TOTAL).increment(1);
+}
+
+pub(crate) fn record_page(table: &str, rows: usize) {
+    #[cfg(feature = "metrics")]
+    {
+        ::metrics::counter!(PAGES_FETCHED_TOTAL, "table" => table.to_string()).increment(1);
+        ::metrics::counter!(ROWS_FETCHED_TOTAL, "table" => table.to_string())
+            .increment(rows as u64);
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = (table, rows);
+}
+
+pub(crate) fn record_events(transport: &'static str, count: usize) {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!(EVENTS_RECEIVED_TOTAL, "transport" => transport).increment(count as u64);
+    #[cfg(not(feature = "metrics"))]
+    let _ = (transport, count);
+}
+
+pub(crate) fn record_events_dropped(count: usize) {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!(EVENTS_DROPPED_TOTAL).increment(count as u64);
+    #[cfg(not(feature = "metrics"))]
+    let _ = count;
+}
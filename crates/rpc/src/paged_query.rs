@@ -54,6 +54,7 @@ where
     }
 
     /// Fetch the next page. Consumers can track `current_cursor` to drive cursor-based filters.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.rpc", skip_all, fields(rpc_method = "circles_query", table = %self.params.table)))]
     pub async fn next_page(&mut self) -> Result<Option<Page<TRow>>> {
         let mut params = self.params.clone();
 
@@ -62,7 +63,9 @@ where
             params.filter = combine_filters(params.filter.take(), cursor_filter);
         }
 
+        let table = params.table.clone();
         let result = (self.fetch)(params).await?;
+        crate::metrics::record_page(&table, result.results.len());
 
         if result.results.is_empty() {
             return Ok(None);
@@ -80,20 +83,18 @@ where
     }
 
     /// Convert this paginator into a stream of rows.
+    ///
+    /// The page reporting `has_more == false` is still yielded; the stream ends after it.
     pub fn into_stream(self) -> impl Stream<Item = Result<TRow>> {
-        futures::stream::unfold(self, |mut state| async move {
+        futures::stream::unfold(Some(self), |state| async move {
+            let mut state = state?;
             match state.next_page().await {
                 Ok(Some(page)) => {
-                    let has_more = page.has_more;
-                    let items = page.items;
-                    if has_more {
-                        Some((Ok(items), state))
-                    } else {
-                        None
-                    }
+                    let next = page.has_more.then_some(state);
+                    Some((Ok(page.items), next))
                 }
                 Ok(None) => None,
-                Err(e) => Some((Err(e), state)),
+                Err(e) => Some((Err(e), Some(state))),
             }
         })
         .flat_map(|res| match res {
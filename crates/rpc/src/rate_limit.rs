@@ -0,0 +1,149 @@
+//! Client-side token-bucket rate limiting for [`RpcClient`](crate::RpcClient).
+//!
+//! The bucket holds up to `burst` tokens and refills at `requests_per_second`. Every request
+//! takes one token; when the bucket is empty the request reserves the next token anyway and
+//! sleeps until it is due, so concurrent callers are served in arrival order instead of
+//! polling. Clones of a client share one bucket, so every method namespace of a
+//! [`CirclesRpc`](crate::CirclesRpc) counts against the same budget.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Sustained request rate and burst size.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimit {
+    /// Tokens added per second; must be positive.
+    pub requests_per_second: f64,
+    /// Bucket capacity, i.e. how many requests may go out back-to-back after idling.
+    pub burst: u32,
+}
+
+impl RateLimit {
+    /// # Panics
+    ///
+    /// Panics unless `requests_per_second` is positive.
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        assert_positive(requests_per_second);
+        Self {
+            requests_per_second,
+            burst,
+        }
+    }
+
+    /// `requests_per_second` with a burst of the same size.
+    pub fn per_second(requests_per_second: u32) -> Self {
+        Self::new(f64::from(requests_per_second), requests_per_second)
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Available tokens; negative while requests are queued for future tokens.
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token bucket shared by all clones of a client.
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    bucket: Mutex<Bucket>,
+}
+
+impl RateLimiter {
+    /// Limiter that starts with a full bucket.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `limit.requests_per_second` is positive: a zero rate would never
+    /// refill the bucket.
+    pub fn new(limit: RateLimit) -> Self {
+        assert_positive(limit.requests_per_second);
+        let limit = RateLimit {
+            burst: limit.burst.max(1),
+            ..limit
+        };
+        Self {
+            bucket: Mutex::new(Bucket {
+                tokens: f64::from(limit.burst),
+                updated: Instant::now(),
+            }),
+            limit,
+        }
+    }
+
+    pub fn limit(&self) -> RateLimit {
+        self.limit
+    }
+
+    /// Wait until a request may be sent.
+    pub async fn acquire(&self) {
+        let wait = self.reserve(Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Take a token at `now` and return how long the caller has to wait for it.
+    fn reserve(&self, now: Instant) -> Duration {
+        let mut bucket = self.bucket.lock().expect("lock");
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.limit.requests_per_second)
+            .min(f64::from(self.limit.burst));
+        bucket.updated = bucket.updated.max(now);
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.limit.requests_per_second)
+        }
+    }
+}
+
+fn assert_positive(requests_per_second: f64) {
+    assert!(
+        requests_per_second > 0.0,
+        "rate limit must allow a positive number of requests per second, got {requests_per_second}"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn burst_is_free_then_requests_are_spaced() {
+        let limiter = RateLimiter::new(RateLimit::new(10.0, 3));
+        let start = limiter.bucket.lock().expect("lock").updated;
+
+        for _ in 0..3 {
+            assert_eq!(limiter.reserve(start), Duration::ZERO);
+        }
+        assert_eq!(limiter.reserve(start), Duration::from_millis(100));
+        assert_eq!(limiter.reserve(start), Duration::from_millis(200));
+
+        // Two tokens refilled after 200ms, both already reserved by the queued requests.
+        let later = start + Duration::from_millis(200);
+        assert_eq!(limiter.reserve(later), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn idle_time_refills_at_most_the_burst() {
+        let limiter = RateLimiter::new(RateLimit::per_second(2));
+        let start = limiter.bucket.lock().expect("lock").updated;
+        let later = start + Duration::from_secs(60);
+
+        assert_eq!(limiter.reserve(later), Duration::ZERO);
+        assert_eq!(limiter.reserve(later), Duration::ZERO);
+        assert_eq!(limiter.reserve(later), Duration::from_millis(500));
+    }
+
+    #[test]
+    #[should_panic(expected = "positive number of requests per second")]
+    fn zero_rate_is_rejected() {
+        RateLimiter::new(RateLimit {
+            requests_per_second: 0.0,
+            burst: 1,
+        });
+    }
+}
@@ -1,43 +1,96 @@
 use crate::client::RpcClient;
 use crate::error::{CirclesRpcError, Result};
+use crate::failover::FailoverPolicy;
 use crate::methods::{
     AvatarMethods, BalanceMethods, EventsMethods, GroupMethods, HealthMethods, InvitationMethods,
     NetworkMethods, PathfinderMethods, QueryMethods, SdkMethods, SearchMethods, TablesMethods,
     TokenInfoMethods, TokenMethods, TransactionMethods, TrustMethods,
 };
-use crate::paged_query::{PagedFetch, PagedQuery};
-use circles_types::PagedQueryParams;
+use crate::paged_query::PagedQuery;
+use crate::rate_limit::RateLimit;
+use circles_types::{AuthProvider, PagedQueryParams, PathfinderConfig, Timeouts};
 use futures::Stream;
-use std::pin::Pin;
 use std::sync::Arc;
 
 /// High-level facade that mirrors the TypeScript SDK entry point.
 ///
 /// This type exposes grouped method accessors and convenience helpers for pagination
 /// and streaming. It can be built from an HTTP URL or any pre-built [`RpcClient`].
+///
+/// With [`Self::with_archive`], event backfills ([`EventsMethods::iter_events`]), every
+/// [`PagedQuery`] (table exports through [`Self::paged_query`] or [`QueryMethods::paged`]
+/// and method-level pagers such as [`TransactionMethods::get_transaction_history`]) and
+/// table introspection ([`Self::tables`]) go to a separate archive endpoint while every
+/// other call stays on [`Self::client`].
+#[derive(Clone)]
 pub struct CirclesRpc {
     pub client: RpcClient,
+    archive: Option<RpcClient>,
+    pathfinder_config: PathfinderConfig,
 }
 
 impl CirclesRpc {
     /// Construct from a pre-built client (useful for dependency injection in tests).
     pub fn new(client: RpcClient) -> Self {
-        Self { client }
+        Self {
+            client,
+            archive: None,
+            pathfinder_config: PathfinderConfig::default(),
+        }
     }
 
     /// Build from an HTTP endpoint URL.
     pub fn from_http_url(url: reqwest::Url) -> Self {
-        Self {
-            client: RpcClient::http(url),
-        }
+        Self::new(RpcClient::http(url))
     }
 
     /// Build from a WebSocket endpoint URL (requires the `ws` feature).
     #[cfg(feature = "ws")]
     pub async fn from_ws_url(url: reqwest::Url) -> Result<Self> {
-        Ok(Self {
-            client: RpcClient::ws(url).await?,
-        })
+        Ok(Self::new(RpcClient::ws(url).await?))
+    }
+
+    /// Apply a timeout and `max_transfers` default to every [`Self::pathfinder`] request.
+    pub fn with_pathfinder_config(mut self, config: PathfinderConfig) -> Self {
+        self.pathfinder_config = config;
+        self
+    }
+
+    /// Send heavy historical queries (event backfills, paged queries, table introspection)
+    /// to `archive`.
+    pub fn with_archive(mut self, archive: RpcClient) -> Self {
+        self.archive = Some(archive);
+        self
+    }
+
+    /// Client used for heavy historical queries: the archive when one is set, otherwise
+    /// [`Self::client`].
+    pub fn archive_client(&self) -> &RpcClient {
+        self.archive.as_ref().unwrap_or(&self.client)
+    }
+
+    /// Throttle every request made through this facade (all namespaces share one bucket;
+    /// an archive endpoint gets a bucket of its own).
+    pub fn with_rate_limit(mut self, limit: RateLimit) -> Self {
+        self.client = self.client.with_rate_limit(limit);
+        self.archive = self.archive.map(|archive| archive.with_rate_limit(limit));
+        self
+    }
+
+    /// Apply `timeouts` to every request: `request` and `ws_idle` through the client,
+    /// `pathfinder` through the pathfinder config unless it already has a timeout. The
+    /// connect limit needs a fresh transport, see [`Self::try_from_http_with_timeouts`].
+    pub fn with_timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.client = self.client.with_timeouts(timeouts);
+        self.archive = self.archive.map(|archive| archive.with_timeouts(timeouts));
+        if self.pathfinder_config.timeout.is_none() {
+            self.pathfinder_config.timeout = timeouts.pathfinder;
+        }
+        self
+    }
+
+    pub fn pathfinder_config(&self) -> &PathfinderConfig {
+        &self.pathfinder_config
     }
 
     /// Convenience helper to parse `&str` URLs into [`CirclesRpc`].
@@ -50,6 +103,65 @@ impl CirclesRpc {
         Ok(Self::from_http_url(parsed))
     }
 
+    /// Parse an HTTP URL and authenticate every request through `auth`.
+    pub fn try_from_http_with_auth(url: &str, auth: Arc<dyn AuthProvider>) -> Result<Self> {
+        let parsed = url
+            .parse::<reqwest::Url>()
+            .map_err(|e| CirclesRpcError::InvalidResponse {
+                message: e.to_string(),
+            })?;
+        Ok(Self::new(RpcClient::http_with_auth(parsed, auth)))
+    }
+
+    /// Parse an HTTP URL and send requests through a caller-configured reqwest client.
+    pub fn try_from_http_with_client(url: &str, client: reqwest::Client) -> Result<Self> {
+        let parsed = url
+            .parse::<reqwest::Url>()
+            .map_err(|e| CirclesRpcError::InvalidResponse {
+                message: e.to_string(),
+            })?;
+        Ok(Self::new(RpcClient::http_with_client(parsed, client)))
+    }
+
+    /// Parse an HTTP URL and honor every limit in `timeouts`, e.g. `CirclesConfig::timeouts`.
+    pub fn try_from_http_with_timeouts(url: &str, timeouts: Timeouts) -> Result<Self> {
+        let parsed = url
+            .parse::<reqwest::Url>()
+            .map_err(|e| CirclesRpcError::InvalidResponse {
+                message: e.to_string(),
+            })?;
+        Ok(Self::new(RpcClient::http_with_timeouts(parsed, timeouts)?).with_timeouts(timeouts))
+    }
+
+    /// Build from several HTTP endpoints with failover (default cooldown policy).
+    pub fn try_from_http_urls(urls: &[&str]) -> Result<Self> {
+        Self::try_from_http_urls_with_policy(urls, FailoverPolicy::default())
+    }
+
+    /// Build from several HTTP endpoints with a custom failover cooldown policy.
+    pub fn try_from_http_urls_with_policy(urls: &[&str], policy: FailoverPolicy) -> Result<Self> {
+        let parsed = urls
+            .iter()
+            .map(|url| {
+                url.parse::<reqwest::Url>()
+                    .map_err(|e| CirclesRpcError::InvalidResponse {
+                        message: e.to_string(),
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::new(RpcClient::http_with_failover(parsed, policy)?))
+    }
+
+    /// Probe all failover endpoints via `circles_health` and switch to the first healthy one.
+    ///
+    /// Single-endpoint clients just run the health check against their endpoint.
+    pub async fn select_healthy_endpoint(&self) -> Result<()> {
+        match self.client.failover() {
+            Some(pool) => pool.select_healthy().await.map(|_| ()),
+            None => self.health().health().await.map(|_| ()),
+        }
+    }
+
     /// Convenience helper to parse `&str` WS URLs into [`CirclesRpc`] (requires `ws` feature).
     #[cfg(feature = "ws")]
     pub async fn try_from_ws(url: &str) -> Result<Self> {
@@ -68,7 +180,7 @@ impl CirclesRpc {
     }
     /// RPC methods for token holder/balance queries.
     pub fn token(&self) -> TokenMethods {
-        TokenMethods::new(self.client.clone())
+        TokenMethods::new(self.client.clone()).with_archive(self.archive_client().clone())
     }
     /// RPC methods returning token metadata.
     pub fn token_info(&self) -> TokenInfoMethods {
@@ -82,13 +194,15 @@ impl CirclesRpc {
     pub fn avatar(&self) -> AvatarMethods {
         AvatarMethods::new(self.client.clone())
     }
-    /// Low-level `circles_query` table accessors and pagination helpers.
+    /// Low-level `circles_query` table accessors and pagination helpers; pagers use the
+    /// archive endpoint.
     pub fn query(&self) -> QueryMethods {
-        QueryMethods::new(self.client.clone())
+        QueryMethods::new(self.client.clone()).with_archive(self.archive_client().clone())
     }
-    /// RPC methods for fetching events over HTTP or websocket.
+    /// RPC methods for fetching events over HTTP or websocket; range iteration uses the
+    /// archive endpoint.
     pub fn events(&self) -> EventsMethods {
-        EventsMethods::new(self.client.clone())
+        EventsMethods::new(self.client.clone()).with_archive(self.archive_client().clone())
     }
     /// RPC methods for invitations with internal batching.
     pub fn invitation(&self) -> InvitationMethods {
@@ -96,19 +210,20 @@ impl CirclesRpc {
     }
     /// RPC methods for path-finding in the trust graph.
     pub fn pathfinder(&self) -> PathfinderMethods {
-        PathfinderMethods::new(self.client.clone())
+        PathfinderMethods::with_config(self.client.clone(), self.pathfinder_config)
     }
-    /// RPC methods for transaction history queries.
+    /// RPC methods for transaction history queries; pagers use the archive endpoint.
     pub fn transaction(&self) -> TransactionMethods {
-        TransactionMethods::new(self.client.clone())
+        TransactionMethods::new(self.client.clone()).with_archive(self.archive_client().clone())
     }
-    /// RPC methods for group lookups.
+    /// RPC methods for group lookups; pagers use the archive endpoint.
     pub fn group(&self) -> GroupMethods {
-        GroupMethods::new(self.client.clone())
+        GroupMethods::new(self.client.clone()).with_archive(self.archive_client().clone())
     }
-    /// RPC methods for querying database tables/introspection.
+    /// RPC methods for querying database tables/introspection, served by the archive
+    /// endpoint.
     pub fn tables(&self) -> TablesMethods {
-        TablesMethods::new(self.client.clone())
+        TablesMethods::new(self.archive_client().clone())
     }
     /// RPC methods for indexer health checks.
     pub fn health(&self) -> HealthMethods {
@@ -127,7 +242,7 @@ impl CirclesRpc {
         SdkMethods::new(self.client.clone())
     }
 
-    /// Build a `PagedQuery` helper around `circles_query`.
+    /// Build a `PagedQuery` helper around `circles_query`, served by the archive endpoint.
     pub fn paged_query<TRow>(&self, params: PagedQueryParams) -> PagedQuery<TRow>
     where
         TRow: serde::de::DeserializeOwned
@@ -139,21 +254,7 @@ impl CirclesRpc {
             + Unpin
             + 'static,
     {
-        let client = self.client.clone();
-        let fetch: PagedFetch<TRow> = Arc::new(move |params: PagedQueryParams| {
-            let client = client.clone();
-            Box::pin(async move {
-                // Use the higher-level query helper to decode rows and cursors.
-                QueryMethods::new(client).paged_query::<TRow>(params).await
-            })
-                as Pin<
-                    Box<
-                        dyn std::future::Future<Output = Result<circles_types::PagedResult<TRow>>>
-                            + Send,
-                    >,
-                >
-        });
-        PagedQuery::new(fetch, params)
+        self.query().paged(params)
     }
 
     /// Convenience: directly get a stream of rows for a paged query.
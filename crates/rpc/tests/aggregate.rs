@@ -0,0 +1,124 @@
+use alloy_primitives::U256;
+use circles_rpc::{AggregateProgress, AggregateQuery, CirclesRpc};
+use circles_rpc_mock::{MockRpcServer, fixtures};
+use serde_json::{Value, json};
+
+const COLUMNS: [&str; 5] = [
+    "token",
+    "amount",
+    "blockNumber",
+    "transactionIndex",
+    "logIndex",
+];
+
+fn row(token: &str, amount: Value, block: u64) -> Vec<Value> {
+    vec![json!(token), amount, json!(block), json!(0), json!(0)]
+}
+
+async fn two_page_server() -> MockRpcServer {
+    MockRpcServer::builder()
+        .respond(
+            "circles_query",
+            fixtures::query_result(
+                &COLUMNS,
+                vec![row("0xa", json!("100"), 1), row("0xb", json!(5), 2)],
+            ),
+        )
+        .respond(
+            "circles_query",
+            fixtures::query_result(&COLUMNS, vec![row("0xa", json!("0x10"), 3)]),
+        )
+        .start()
+        .await
+        .expect("server")
+}
+
+#[tokio::test]
+async fn client_side_aggregates_stream_all_pages() {
+    let server = two_page_server().await;
+    let rpc = CirclesRpc::try_from_http(&server.url()).expect("rpc");
+    let query = AggregateQuery::new("V_CrcV2", "Transfers")
+        .with_page_size(2)
+        .client_side_only();
+
+    let mut updates = Vec::new();
+    let mut record = |progress: AggregateProgress| updates.push(progress);
+    let total = rpc
+        .query()
+        .sum(&query, "amount", Some(&mut record))
+        .await
+        .expect("sum");
+    assert_eq!(total, U256::from(121u64));
+    assert_eq!(
+        updates.last(),
+        Some(&AggregateProgress { pages: 2, rows: 3 })
+    );
+
+    let first_page = &server.params_for("circles_query")[0][0];
+    assert_eq!(first_page["Columns"][0], "amount");
+    assert_eq!(first_page["Limit"], 2);
+}
+
+#[tokio::test]
+async fn grouped_aggregates_key_by_column_value() {
+    let server = two_page_server().await;
+    let rpc = CirclesRpc::try_from_http(&server.url()).expect("rpc");
+    let query = AggregateQuery::new("V_CrcV2", "Transfers").with_page_size(2);
+
+    let sums = rpc
+        .query()
+        .sum_by(&query, "token", "amount", None)
+        .await
+        .expect("sum_by");
+    assert_eq!(sums["0xa"], U256::from(116u64));
+    assert_eq!(sums["0xb"], U256::from(5u64));
+}
+
+#[tokio::test]
+async fn count_uses_backend_aggregate_when_supported() {
+    let server = MockRpcServer::builder()
+        .respond(
+            "circles_query",
+            fixtures::query_result(&["count"], vec![vec![json!(42)]]),
+        )
+        .start()
+        .await
+        .expect("server");
+    let rpc = CirclesRpc::try_from_http(&server.url()).expect("rpc");
+
+    let count = rpc
+        .query()
+        .count(&AggregateQuery::new("V_CrcV2", "Avatars"), None)
+        .await
+        .expect("count");
+    assert_eq!(count, 42);
+    let requests = server.params_for("circles_query");
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0][0]["Columns"], json!(["COUNT(*)"]));
+}
+
+#[tokio::test]
+async fn count_falls_back_to_streaming_when_backend_rejects() {
+    let columns = ["blockNumber", "transactionIndex", "logIndex"];
+    let page = fixtures::query_result(
+        &columns,
+        vec![
+            vec![json!(1), json!(0), json!(0)],
+            vec![json!(2), json!(0), json!(0)],
+        ],
+    );
+    let server = MockRpcServer::builder()
+        .respond_error("circles_query", -32602, "unknown column COUNT(*)")
+        .respond("circles_query", page)
+        .start()
+        .await
+        .expect("server");
+    let rpc = CirclesRpc::try_from_http(&server.url()).expect("rpc");
+
+    let count = rpc
+        .query()
+        .count(&AggregateQuery::new("V_CrcV2", "Avatars"), None)
+        .await
+        .expect("count");
+    assert_eq!(count, 2);
+}
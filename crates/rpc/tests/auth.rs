@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use circles_rpc::{AuthError, AuthProvider, CirclesRpc, RpcClient, StaticHeaders};
+use circles_rpc_mock::MockRpcServer;
+use serde_json::json;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+async fn health_server() -> MockRpcServer {
+    MockRpcServer::builder()
+        .respond("circles_health", json!({ "status": "ok" }))
+        .start()
+        .await
+        .expect("server")
+}
+
+#[tokio::test]
+async fn bearer_token_is_sent_with_every_request() {
+    let server = health_server().await;
+    let client = RpcClient::http_with_bearer(server.url().parse().unwrap(), "secret");
+    let rpc = CirclesRpc::new(client);
+
+    rpc.health().health().await.expect("health");
+    rpc.health().health().await.expect("health");
+
+    assert_eq!(
+        server.header_for("circles_health", "Authorization"),
+        vec![Some("Bearer secret".to_string()); 2]
+    );
+}
+
+/// Hands out a fresh token per request, like a signer with nonces would.
+struct CountingAuth(AtomicUsize);
+
+#[async_trait]
+impl AuthProvider for CountingAuth {
+    async fn headers(&self) -> Result<Vec<(String, String)>, AuthError> {
+        let n = self.0.fetch_add(1, Ordering::SeqCst);
+        Ok(vec![("x-request-token".to_string(), format!("token-{n}"))])
+    }
+}
+
+#[tokio::test]
+async fn custom_provider_is_asked_per_request() {
+    let server = health_server().await;
+    let rpc = CirclesRpc::try_from_http_with_auth(
+        &server.url(),
+        Arc::new(CountingAuth(AtomicUsize::new(0))),
+    )
+    .expect("rpc");
+
+    rpc.health().health().await.expect("health");
+    rpc.health().health().await.expect("health");
+
+    assert_eq!(
+        server.header_for("circles_health", "x-request-token"),
+        vec![Some("token-0".to_string()), Some("token-1".to_string())]
+    );
+}
+
+struct FailingAuth;
+
+#[async_trait]
+impl AuthProvider for FailingAuth {
+    async fn headers(&self) -> Result<Vec<(String, String)>, AuthError> {
+        Err(AuthError("token expired".to_string()))
+    }
+}
+
+#[tokio::test]
+async fn auth_failures_surface_without_sending() {
+    let server = health_server().await;
+    let rpc =
+        CirclesRpc::try_from_http_with_auth(&server.url(), Arc::new(FailingAuth)).expect("rpc");
+
+    let err = rpc.health().health().await.expect_err("auth error");
+    assert!(err.to_string().contains("token expired"), "{err}");
+    assert!(server.requests().is_empty());
+
+    let keyed = CirclesRpc::try_from_http_with_auth(
+        &server.url(),
+        Arc::new(StaticHeaders::new().with("x-api-key", "k")),
+    )
+    .expect("rpc");
+    keyed.health().health().await.expect("health");
+    assert_eq!(
+        server.header_for("circles_health", "x-api-key"),
+        vec![Some("k".to_string())]
+    );
+}
@@ -0,0 +1,294 @@
+use circles_rpc::{CirclesRpc, CirclesRpcError, EventRangeOptions};
+use circles_rpc_mock::{MockRpcServer, fixtures};
+use circles_types::{PagedQueryParams, SortOrder};
+use futures::StreamExt;
+use serde_json::json;
+
+fn trust(block: u64, log_index: u64) -> serde_json::Value {
+    fixtures::event(
+        "CrcV2_Trust",
+        json!({
+            "truster": "0x1000000000000000000000000000000000000001",
+            "trustee": "0x2000000000000000000000000000000000000002",
+            "expiryTime": "1800000000",
+            "blockNumber": block,
+            "transactionIndex": 0,
+            "logIndex": log_index,
+        }),
+    )
+}
+
+#[tokio::test]
+async fn iter_events_splits_failing_range_and_keeps_order() {
+    let server = MockRpcServer::builder()
+        .fixtures_json(
+            &json!([{
+                "method": "circles_events",
+                "params": [null, 0, 99, null],
+                "error": { "code": -32000, "message": "query timeout" }
+            }])
+            .to_string(),
+        )
+        .unwrap()
+        .respond_to(
+            "circles_events",
+            json!([null, 0, 49, null]),
+            json!([trust(40, 1), trust(10, 0), trust(40, 0)]),
+        )
+        .respond_to(
+            "circles_events",
+            json!([null, 50, 99, null]),
+            json!([trust(75, 0)]),
+        )
+        .start()
+        .await
+        .expect("server");
+    let rpc = CirclesRpc::try_from_http(&server.url()).expect("rpc");
+
+    let options = EventRangeOptions {
+        initial_chunk: 100,
+        min_chunk: 10,
+        ..EventRangeOptions::default()
+    };
+    let events: Vec<_> = rpc
+        .events()
+        .iter_events_with(None, 0, 99, None, options)
+        .collect()
+        .await;
+    let order: Vec<_> = events
+        .into_iter()
+        .map(|e| {
+            let e = e.expect("event");
+            (e.base.block_number, e.base.log_index)
+        })
+        .collect();
+    assert_eq!(order, vec![(10, 0), (40, 0), (40, 1), (75, 0)]);
+
+    let ranges: Vec<_> = server
+        .params_for("circles_events")
+        .into_iter()
+        .map(|p| (p[1].as_u64().unwrap(), p[2].as_u64().unwrap()))
+        .collect();
+    assert_eq!(ranges, vec![(0, 99), (0, 49), (50, 99)]);
+}
+
+#[tokio::test]
+async fn iter_events_yields_error_at_min_chunk() {
+    let server = MockRpcServer::builder()
+        .respond_error("circles_events", -32000, "backend down")
+        .start()
+        .await
+        .expect("server");
+    let rpc = CirclesRpc::try_from_http(&server.url()).expect("rpc");
+
+    let options = EventRangeOptions {
+        initial_chunk: 4,
+        min_chunk: 2,
+        ..EventRangeOptions::default()
+    };
+    let events: Vec<_> = rpc
+        .events()
+        .iter_events_with(None, 0, 3, None, options)
+        .collect()
+        .await;
+    assert_eq!(events.len(), 1);
+    assert!(events[0].is_err());
+    assert_eq!(server.params_for("circles_events").len(), 2);
+}
+
+#[tokio::test]
+async fn iter_events_reports_truncation_at_min_chunk() {
+    let server = MockRpcServer::builder()
+        .respond("circles_events", json!([trust(0, 0), trust(0, 1)]))
+        .start()
+        .await
+        .expect("server");
+    let rpc = CirclesRpc::try_from_http(&server.url()).expect("rpc");
+
+    let options = EventRangeOptions {
+        initial_chunk: 2,
+        min_chunk: 1,
+        truncation_threshold: 2,
+        ..EventRangeOptions::default()
+    };
+    let events: Vec<_> = rpc
+        .events()
+        .iter_events_with(None, 0, 3, None, options)
+        .collect()
+        .await;
+    assert_eq!(events.len(), 1);
+    assert!(matches!(
+        events[0],
+        Err(CirclesRpcError::TruncatedEvents {
+            from_block: 0,
+            to_block: 0,
+            count: 2
+        })
+    ));
+}
+
+#[tokio::test]
+async fn backfills_and_exports_go_to_the_archive() {
+    let primary = MockRpcServer::builder()
+        .respond("circles_events", json!([trust(200, 0)]))
+        .start()
+        .await
+        .expect("primary");
+    let archive = MockRpcServer::builder()
+        .respond("circles_events", json!([trust(5, 0)]))
+        .respond(
+            "circles_query",
+            fixtures::query_result(&["avatar"], vec![vec![json!("0x01")]]),
+        )
+        .start()
+        .await
+        .expect("archive");
+    let rpc = CirclesRpc::try_from_http(&primary.url())
+        .expect("rpc")
+        .with_archive(
+            CirclesRpc::try_from_http(&archive.url())
+                .expect("archive rpc")
+                .client,
+        );
+
+    let backfill: Vec<_> = rpc.events().iter_events(None, 0, 9, None).collect().await;
+    assert_eq!(backfill.len(), 1);
+    let recent = rpc
+        .events()
+        .circles_events(None, 200, None, None)
+        .await
+        .expect("recent events");
+    assert_eq!(recent.len(), 1);
+
+    let params = PagedQueryParams {
+        namespace: "V_Crc".to_string(),
+        table: "Avatars".to_string(),
+        sort_order: SortOrder::DESC,
+        columns: vec!["avatar".to_string()],
+        filter: None,
+        cursor_columns: None,
+        order_columns: None,
+        limit: 10,
+    };
+    let page = rpc
+        .paged_query::<serde_json::Value>(params)
+        .next_page()
+        .await
+        .expect("page");
+    assert!(page.is_some());
+
+    assert_eq!(archive.params_for("circles_events").len(), 1);
+    assert_eq!(archive.params_for("circles_query").len(), 1);
+    assert_eq!(primary.params_for("circles_events").len(), 1);
+    assert!(primary.params_for("circles_query").is_empty());
+}
+
+#[tokio::test]
+async fn method_pagers_and_tables_go_to_the_archive() {
+    let primary = MockRpcServer::builder()
+        .respond(
+            "circles_query",
+            fixtures::query_result(&["avatar"], vec![vec![json!("0x01")]]),
+        )
+        .start()
+        .await
+        .expect("primary");
+    let archive = MockRpcServer::builder()
+        .respond("circles_query", fixtures::query_result(&["avatar"], vec![]))
+        .respond("circles_tables", json!([]))
+        .start()
+        .await
+        .expect("archive");
+    let rpc = CirclesRpc::try_from_http(&primary.url())
+        .expect("rpc")
+        .with_archive(
+            CirclesRpc::try_from_http(&archive.url())
+                .expect("archive rpc")
+                .client,
+        );
+    let avatar = "0x1000000000000000000000000000000000000001"
+        .parse()
+        .expect("address");
+
+    rpc.transaction()
+        .get_transaction_history(avatar, 10, SortOrder::DESC)
+        .next_page()
+        .await
+        .expect("history page");
+    rpc.group()
+        .get_group_memberships(avatar, 10, SortOrder::DESC)
+        .next_page()
+        .await
+        .expect("memberships page");
+    rpc.query()
+        .paged::<serde_json::Value>(PagedQueryParams {
+            namespace: "V_Crc".to_string(),
+            table: "Avatars".to_string(),
+            sort_order: SortOrder::DESC,
+            columns: vec!["avatar".to_string()],
+            filter: None,
+            cursor_columns: None,
+            order_columns: None,
+            limit: 10,
+        })
+        .next_page()
+        .await
+        .expect("query page");
+    rpc.tables().tables().await.expect("tables");
+
+    // A single `circles_query` is not an export and stays on the primary.
+    let rows: Vec<serde_json::Value> = rpc
+        .query()
+        .circles_query(circles_types::QueryParams {
+            namespace: "V_Crc".to_string(),
+            table: "Avatars".to_string(),
+            columns: vec!["avatar".to_string()],
+            filter: vec![],
+            order: vec![],
+            limit: Some(1),
+        })
+        .await
+        .expect("query");
+    assert_eq!(rows.len(), 1);
+
+    assert_eq!(archive.params_for("circles_query").len(), 3);
+    assert_eq!(archive.params_for("circles_tables").len(), 1);
+    assert_eq!(primary.params_for("circles_query").len(), 1);
+    assert!(primary.params_for("circles_tables").is_empty());
+}
+
+#[tokio::test]
+async fn resume_from_skips_events_before_the_persisted_cursor() {
+    let server = MockRpcServer::builder()
+        .respond(
+            "circles_events",
+            json!([trust(40, 0), trust(40, 1), trust(40, 2), trust(41, 0)]),
+        )
+        .start()
+        .await
+        .expect("server");
+    let rpc = CirclesRpc::try_from_http(&server.url()).expect("rpc");
+
+    let first: Vec<_> = rpc
+        .events()
+        .iter_events(None, 40, 41, None)
+        .with_cursors()
+        .take(2)
+        .collect()
+        .await;
+    let (persisted, _) = first[1].as_ref().expect("event");
+    let persisted: circles_types::EventCursor = persisted.to_string().parse().expect("cursor");
+
+    let resumed: Vec<_> = rpc
+        .events()
+        .resume_from(persisted, None, 41, None)
+        .map(|item| {
+            let (_, e) = item.expect("event");
+            (e.base.block_number, e.base.log_index)
+        })
+        .collect()
+        .await;
+    assert_eq!(resumed, vec![(40, 2), (41, 0)]);
+    let from_block = server.params_for("circles_events").last().unwrap()[1].clone();
+    assert_eq!(from_block, json!(40));
+}
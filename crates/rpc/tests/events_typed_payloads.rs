@@ -0,0 +1,68 @@
+use alloy_primitives::{Address, U256};
+use circles_rpc::events::parser::parse;
+use circles_types::{
+    CirclesEvent, CirclesEventPayload, CirclesEventType, EventPayloadError,
+    GroupRedeemCollateralBurnEvent, RpcSubscriptionEvent, TransferBatchEvent, TrustEvent,
+};
+use std::collections::HashSet;
+
+fn fixture_events() -> Vec<CirclesEvent> {
+    let raw: Vec<RpcSubscriptionEvent> =
+        serde_json::from_str(include_str!("fixtures/circles_events_all_types.json"))
+            .expect("parse fixture");
+    raw.into_iter().map(|e| parse(e).unwrap()).collect()
+}
+
+#[test]
+fn every_event_type_decodes_into_its_payload() {
+    let events = fixture_events();
+    let mut seen = HashSet::new();
+    for event in &events {
+        let payload = event
+            .payload()
+            .unwrap_or_else(|err| panic!("{:?}: {err}", event.event_type));
+        assert_eq!(payload.event_type(), event.event_type);
+        seen.insert(format!("{:?}", event.event_type));
+    }
+    // 29 known event types plus the unknown fallback.
+    assert_eq!(seen.len(), 30);
+    assert!(matches!(
+        events.last().unwrap().payload().unwrap(),
+        CirclesEventPayload::Unknown(values) if values["foo"] == "bar"
+    ));
+}
+
+#[test]
+fn typed_fields_decode_numbers_and_aliases() {
+    let events = fixture_events();
+    let find = |event_type: CirclesEventType| {
+        events
+            .iter()
+            .find(|e| e.event_type == event_type)
+            .unwrap()
+            .clone()
+    };
+
+    let trust = TrustEvent::try_from(find(CirclesEventType::CrcV2Trust)).unwrap();
+    assert_eq!(trust.truster, Address::repeat_byte(1));
+    assert_eq!(trust.expiry_time, U256::from(u128::MAX >> 32));
+
+    let batch = TransferBatchEvent::try_from(find(CirclesEventType::CrcV2TransferBatch)).unwrap();
+    assert_eq!(batch.ids, vec![U256::from(1), U256::from(2)]);
+    assert_eq!(batch.values, vec![U256::from(5), U256::from(7)]);
+
+    let burn = GroupRedeemCollateralBurnEvent::try_from(find(
+        CirclesEventType::CrcV2GroupRedeemCollateralBurn,
+    ))
+    .unwrap();
+    assert_eq!(burn.ids, vec![U256::from(1)]);
+
+    let err = TrustEvent::try_from(find(CirclesEventType::CrcV2Stopped)).unwrap_err();
+    assert_eq!(
+        err,
+        EventPayloadError::WrongType {
+            expected: CirclesEventType::CrcV2Trust,
+            actual: CirclesEventType::CrcV2Stopped,
+        }
+    );
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "fixtures")]
+
+use alloy_primitives::{Address, U256, address};
+use circles_rpc::{CirclesRpc, CirclesRpcError, FixtureStore, RpcClient};
+use circles_rpc_mock::{MockRpcServer, fixtures};
+use circles_types::FindPathParams;
+
+const ALICE: Address = address!("1000000000000000000000000000000000000001");
+const BOB: Address = address!("2000000000000000000000000000000000000002");
+
+fn params(target_flow: u64) -> FindPathParams {
+    FindPathParams {
+        from: ALICE,
+        to: BOB,
+        target_flow: U256::from(target_flow),
+        use_wrapped_balances: Some(true),
+        from_tokens: None,
+        to_tokens: None,
+        exclude_from_tokens: None,
+        exclude_to_tokens: None,
+        simulated_balances: None,
+        simulated_trusts: None,
+        max_transfers: None,
+    }
+}
+
+fn rpc(url: &str, store: FixtureStore) -> CirclesRpc {
+    CirclesRpc::new(RpcClient::http(url.parse().unwrap()).with_fixtures(store))
+}
+
+#[tokio::test]
+async fn recorded_responses_replay_without_the_network() {
+    let dir = std::env::temp_dir().join(format!("circles-rpc-fixtures-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let live = MockRpcServer::builder()
+        .respond(
+            "circlesV2_findPath",
+            fixtures::path_result(&[(ALICE, BOB, ALICE, U256::from(10u64))]),
+        )
+        .start()
+        .await
+        .unwrap();
+    let recorded = rpc(&live.url(), FixtureStore::record(&dir))
+        .pathfinder()
+        .find_path(params(10))
+        .await
+        .unwrap();
+    assert_eq!(live.requests().len(), 1);
+
+    let offline = MockRpcServer::builder().start().await.unwrap();
+    let replay = rpc(&offline.url(), FixtureStore::replay(&dir));
+    let replayed = replay.pathfinder().find_path(params(10)).await.unwrap();
+    assert_eq!(replayed.max_flow, recorded.max_flow);
+    assert_eq!(replayed.transfers.len(), recorded.transfers.len());
+
+    let missing = replay.pathfinder().find_path(params(11)).await;
+    assert!(matches!(missing, Err(CirclesRpcError::Fixture { .. })));
+    assert!(offline.requests().is_empty());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,144 @@
+use alloy_primitives::{Address, address};
+use circles_rpc::CirclesRpc;
+use circles_rpc_mock::{MockRpcServer, fixtures};
+use circles_types::GroupQueryParams;
+use serde_json::json;
+
+const ME: Address = address!("1000000000000000000000000000000000000001");
+const GROUP_A: Address = address!("a00000000000000000000000000000000000000a");
+const GROUP_B: Address = address!("b00000000000000000000000000000000000000b");
+const POLICY: Address = address!("5000000000000000000000000000000000000005");
+
+fn membership(group: Address) -> serde_json::Value {
+    json!({
+        "blockNumber": 1,
+        "timestamp": 1,
+        "transactionIndex": 0,
+        "logIndex": 0,
+        "transactionHash": format!("0x{}", "00".repeat(32)),
+        "group": group,
+        "member": ME,
+        "expiryTime": 0,
+    })
+}
+
+#[tokio::test]
+async fn find_groups_resolves_membership_into_group_filter() {
+    let server = MockRpcServer::builder()
+        .respond(
+            "circles_getGroupMemberships",
+            json!({
+                "results": [membership(GROUP_A), membership(GROUP_B)],
+                "hasMore": false,
+                "nextCursor": null,
+            }),
+        )
+        .respond(
+            "circles_query",
+            fixtures::query_result(
+                &["group", "type", "owner", "mintPolicy", "symbol"],
+                vec![vec![
+                    json!(GROUP_B),
+                    json!("Standard"),
+                    json!(ME),
+                    json!(POLICY),
+                    json!("BCRC"),
+                ]],
+            ),
+        )
+        .start()
+        .await
+        .expect("server");
+    let rpc = CirclesRpc::try_from_http(&server.url()).expect("rpc");
+
+    let groups = rpc
+        .group()
+        .find_groups(
+            10,
+            Some(GroupQueryParams {
+                has_member: Some(ME),
+                mint_policy_equals: Some(POLICY),
+                symbol_starts_with: Some("B".into()),
+                ..Default::default()
+            }),
+        )
+        .await
+        .expect("groups");
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].group, GROUP_B);
+    assert_eq!(groups[0].mint_policy, Some(POLICY));
+
+    let filter = server.params_for("circles_query")[0][0]["Filter"].to_string();
+    assert!(filter.contains(&format!("{GROUP_A:#x}")));
+    assert!(filter.contains(&format!("{GROUP_B:#x}")));
+    assert!(filter.contains("mintPolicy"));
+    assert!(filter.contains("B%"));
+}
+
+#[tokio::test]
+async fn find_groups_without_memberships_skips_the_query() {
+    let server = MockRpcServer::builder()
+        .respond(
+            "circles_getGroupMemberships",
+            json!({ "results": [], "hasMore": false, "nextCursor": null }),
+        )
+        .start()
+        .await
+        .expect("server");
+    let rpc = CirclesRpc::try_from_http(&server.url()).expect("rpc");
+
+    let groups = rpc
+        .group()
+        .find_groups(
+            10,
+            Some(GroupQueryParams {
+                has_member: Some(ME),
+                ..Default::default()
+            }),
+        )
+        .await
+        .expect("groups");
+    assert!(groups.is_empty());
+    assert!(server.params_for("circles_query").is_empty());
+}
+
+#[tokio::test]
+async fn groups_paged_applies_the_member_filter() {
+    let server = MockRpcServer::builder()
+        .respond(
+            "circles_getGroupMemberships",
+            json!({
+                "results": [membership(GROUP_A)],
+                "hasMore": false,
+                "nextCursor": null,
+            }),
+        )
+        .respond(
+            "circles_query",
+            fixtures::query_result(
+                &["group", "type", "owner"],
+                vec![vec![json!(GROUP_A), json!("Standard"), json!(ME)]],
+            ),
+        )
+        .start()
+        .await
+        .expect("server");
+    let rpc = CirclesRpc::try_from_http(&server.url()).expect("rpc");
+
+    let mut query = rpc.group().get_groups_paged(
+        10,
+        Some(GroupQueryParams {
+            has_member: Some(ME),
+            ..Default::default()
+        }),
+        circles_types::SortOrder::DESC,
+    );
+    let page = query.next_page().await.expect("page").expect("rows");
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].group, GROUP_A);
+
+    let filter = server.params_for("circles_query")[0][0]["Filter"].to_string();
+    assert!(filter.contains(&format!("{GROUP_A:#x}")));
+    assert!(!filter.contains(&format!("{GROUP_B:#x}")));
+    assert_eq!(server.params_for("circles_getGroupMemberships").len(), 1);
+}
@@ -0,0 +1,37 @@
+use circles_rpc::CirclesRpc;
+use circles_rpc_mock::MockRpcServer;
+use serde_json::json;
+
+fn snapshot(block_number: u64) -> serde_json::Value {
+    json!({
+        "trust_relations": [],
+        "balances": [],
+        "block_number": block_number,
+        "timestamp": 1_700_000_000u64,
+    })
+}
+
+#[tokio::test]
+async fn snapshot_at_sends_the_block() {
+    let server = MockRpcServer::builder()
+        .respond("circles_getNetworkSnapshot", snapshot(200))
+        .respond_to("circles_getNetworkSnapshot", json!([123]), snapshot(123))
+        .start()
+        .await
+        .expect("server");
+    let rpc = CirclesRpc::try_from_http(&server.url()).expect("rpc");
+
+    let pinned = rpc
+        .network()
+        .get_network_snapshot_at(123)
+        .await
+        .expect("snapshot");
+    assert_eq!(pinned.block_number, 123);
+    let latest = rpc.network().snapshot().await.expect("snapshot");
+    assert_eq!(latest.block_number, 200);
+
+    let sent = server.params_for("circles_getNetworkSnapshot");
+    assert_eq!(sent.len(), 2);
+    assert_eq!(sent[0], json!([123]));
+    assert_ne!(sent[1], json!([123]));
+}
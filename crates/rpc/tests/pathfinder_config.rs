@@ -0,0 +1,81 @@
+use alloy_primitives::{Address, U256, address};
+use circles_rpc::{CirclesRpc, CirclesRpcError};
+use circles_rpc_mock::{MockRpcServer, fixtures};
+use circles_types::{FindPathParams, PathfinderConfig};
+use std::time::Duration;
+
+const ALICE: Address = address!("1000000000000000000000000000000000000001");
+const BOB: Address = address!("2000000000000000000000000000000000000002");
+
+fn params(max_transfers: Option<u32>) -> FindPathParams {
+    FindPathParams {
+        from: ALICE,
+        to: BOB,
+        target_flow: U256::from(10u64),
+        use_wrapped_balances: Some(true),
+        from_tokens: None,
+        to_tokens: None,
+        exclude_from_tokens: None,
+        exclude_to_tokens: None,
+        simulated_balances: None,
+        simulated_trusts: None,
+        max_transfers,
+    }
+}
+
+#[tokio::test]
+async fn default_max_transfers_fills_unset_requests_only() {
+    let server = MockRpcServer::builder()
+        .respond(
+            "circlesV2_findPath",
+            fixtures::path_result(&[(ALICE, BOB, ALICE, U256::from(10u64))]),
+        )
+        .respond(
+            "circlesV2_findPath",
+            fixtures::path_result(&[(ALICE, BOB, ALICE, U256::from(10u64))]),
+        )
+        .start()
+        .await
+        .unwrap();
+    let rpc = CirclesRpc::try_from_http(&server.url())
+        .unwrap()
+        .with_pathfinder_config(PathfinderConfig {
+            timeout: Some(Duration::from_secs(5)),
+            default_max_transfers: Some(6),
+        });
+
+    rpc.pathfinder().find_path(params(None)).await.unwrap();
+    rpc.pathfinder().find_path(params(Some(2))).await.unwrap();
+
+    let sent = server.params_for("circlesV2_findPath");
+    assert_eq!(sent[0][0]["MaxTransfers"], 6);
+    assert_eq!(sent[1][0]["MaxTransfers"], 2);
+}
+
+#[tokio::test]
+async fn slow_pathfinder_times_out() {
+    // Accept connections but never answer.
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let mut open = Vec::new();
+        while let Ok((socket, _)) = listener.accept().await {
+            open.push(socket);
+        }
+    });
+
+    let rpc = CirclesRpc::try_from_http(&format!("http://{addr}"))
+        .unwrap()
+        .with_pathfinder_config(PathfinderConfig {
+            timeout: Some(Duration::from_millis(50)),
+            default_max_transfers: None,
+        });
+    let err = rpc.pathfinder().find_path(params(None)).await.unwrap_err();
+    assert!(matches!(
+        err,
+        CirclesRpcError::Timeout {
+            method: "circlesV2_findPath",
+            ..
+        }
+    ));
+}
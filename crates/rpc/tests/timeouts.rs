@@ -0,0 +1,101 @@
+use alloy_primitives::{Address, U256};
+use circles_rpc::{CirclesRpc, CirclesRpcError, EventStream, Timeouts};
+use circles_types::{FindPathParams, PathfinderConfig};
+use futures::StreamExt;
+use std::time::Duration;
+
+/// Accept connections but never answer.
+async fn silent_server() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let mut open = Vec::new();
+        while let Ok((socket, _)) = listener.accept().await {
+            open.push(socket);
+        }
+    });
+    format!("http://{addr}")
+}
+
+#[tokio::test]
+async fn requests_and_pathfinder_use_their_own_limits() {
+    let url = silent_server().await;
+    let timeouts = Timeouts {
+        request: Some(Duration::from_millis(30)),
+        pathfinder: Some(Duration::from_millis(60)),
+        ..Timeouts::none()
+    };
+    let rpc = CirclesRpc::try_from_http_with_timeouts(&url, timeouts).unwrap();
+    assert_eq!(rpc.pathfinder_config().timeout, timeouts.pathfinder);
+
+    let err = rpc.health().health().await.unwrap_err();
+    assert!(
+        matches!(&err, CirclesRpcError::RequestTimeout { method, after }
+            if method == "circles_health" && *after == Duration::from_millis(30)),
+        "{err:?}"
+    );
+
+    let params = FindPathParams {
+        from: Address::repeat_byte(1),
+        to: Address::repeat_byte(2),
+        target_flow: U256::from(10u64),
+        use_wrapped_balances: None,
+        from_tokens: None,
+        to_tokens: None,
+        exclude_from_tokens: None,
+        exclude_to_tokens: None,
+        simulated_balances: None,
+        simulated_trusts: None,
+        max_transfers: None,
+    };
+    let err = rpc.pathfinder().find_path(params).await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            CirclesRpcError::Timeout {
+                method: "circlesV2_findPath",
+                after,
+            } if after == Duration::from_millis(60)
+        ),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn explicit_pathfinder_timeout_survives_shared_timeouts() {
+    let explicit = PathfinderConfig {
+        timeout: Some(Duration::from_secs(5)),
+        ..PathfinderConfig::default()
+    };
+    let rpc = CirclesRpc::try_from_http("http://127.0.0.1:1")
+        .unwrap()
+        .with_pathfinder_config(explicit)
+        .with_timeouts(Timeouts::default());
+    assert_eq!(rpc.pathfinder_config().timeout, explicit.timeout);
+
+    let rpc = CirclesRpc::try_from_http("http://127.0.0.1:1")
+        .unwrap()
+        .with_timeouts(Timeouts::default());
+    assert_eq!(
+        rpc.pathfinder_config().timeout,
+        Timeouts::default().pathfinder
+    );
+}
+
+#[tokio::test]
+async fn quiet_subscription_stream_ends_with_timeout() {
+    let quiet =
+        EventStream::<u64>::new(futures::stream::iter([Ok(1)]).chain(futures::stream::pending()))
+            .with_idle_timeout(Some(Duration::from_millis(20)));
+    let items: Vec<_> = quiet.collect().await;
+
+    assert_eq!(items.len(), 2);
+    assert_eq!(*items[0].as_ref().unwrap(), 1);
+    assert!(matches!(
+        items[1],
+        Err(CirclesRpcError::Timeout {
+            method: "eth_subscribe",
+            ..
+        })
+    ));
+}
@@ -0,0 +1,111 @@
+use alloy_primitives::{Address, address};
+use circles_rpc::CirclesRpc;
+use circles_rpc_mock::{MockRpcServer, fixtures};
+use circles_types::SortOrder;
+use futures::TryStreamExt;
+use serde_json::json;
+
+const TOKEN: Address = address!("7000000000000000000000000000000000000007");
+
+fn holder_row(account: &str, balance: &str) -> serde_json::Value {
+    json!({
+        "account": account,
+        "balance": balance,
+        "tokenAddress": format!("{TOKEN:#x}"),
+        "version": 2,
+    })
+}
+
+#[tokio::test]
+async fn holders_stream_follows_native_cursor() {
+    let server = MockRpcServer::builder()
+        .respond(
+            "circles_getTokenHolders",
+            json!({
+                "results": [
+                    holder_row("0x1000000000000000000000000000000000000001", "30"),
+                    holder_row("0x2000000000000000000000000000000000000002", "20"),
+                ],
+                "hasMore": true,
+                "nextCursor": "c1",
+            }),
+        )
+        .respond(
+            "circles_getTokenHolders",
+            json!({
+                "results": [holder_row("0x3000000000000000000000000000000000000003", "10")],
+                "hasMore": false,
+                "nextCursor": null,
+            }),
+        )
+        .start()
+        .await
+        .expect("server");
+    let rpc = CirclesRpc::try_from_http(&server.url()).expect("rpc");
+
+    let holders: Vec<_> = rpc
+        .token()
+        .get_token_holders_stream(TOKEN)
+        .try_collect()
+        .await
+        .expect("holders");
+    let balances: Vec<_> = holders
+        .iter()
+        .map(|h| h.demurraged_total_balance.as_str())
+        .collect();
+    assert_eq!(balances, ["30", "20", "10"]);
+
+    let calls = server.params_for("circles_getTokenHolders");
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0][2], json!(null));
+    assert_eq!(calls[1][2], "c1");
+}
+
+#[tokio::test]
+async fn holders_by_balance_stream_includes_last_partial_page() {
+    let columns = ["account", "tokenAddress", "demurragedTotalBalance"];
+    let row = |account: &str, balance: &str| {
+        vec![json!(account), json!(format!("{TOKEN:#x}")), json!(balance)]
+    };
+    let server = MockRpcServer::builder()
+        .respond(
+            "circles_query",
+            fixtures::query_result(
+                &columns,
+                vec![
+                    row("0x1000000000000000000000000000000000000001", "30"),
+                    row("0x2000000000000000000000000000000000000002", "20"),
+                ],
+            ),
+        )
+        .respond(
+            "circles_query",
+            fixtures::query_result(
+                &columns,
+                vec![row("0x3000000000000000000000000000000000000003", "10")],
+            ),
+        )
+        .start()
+        .await
+        .expect("server");
+    let rpc = CirclesRpc::try_from_http(&server.url()).expect("rpc");
+
+    let holders: Vec<_> = rpc
+        .token()
+        .get_token_holders_by_balance(TOKEN, SortOrder::DESC, 2)
+        .into_stream()
+        .try_collect()
+        .await
+        .expect("holders");
+    assert_eq!(holders.len(), 3);
+    assert_eq!(holders[2].demurraged_total_balance, "10");
+
+    let calls = server.params_for("circles_query");
+    let first = &calls[0][0];
+    assert_eq!(first["Table"], "BalancesByAccountAndToken");
+    assert_eq!(first["Columns"], json!(columns));
+    assert_eq!(first["Order"][0]["Column"], "demurragedTotalBalance");
+    assert_eq!(first["Order"][0]["SortOrder"], "DESC");
+    // The second page continues after the last (balance, account) pair.
+    assert!(calls[1][0]["Filter"].to_string().contains("\"20\""));
+}
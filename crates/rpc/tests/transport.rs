@@ -0,0 +1,63 @@
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_provider::transport::{TransportError, TransportFut};
+use circles_rpc::{CirclesRpc, RpcClient};
+use circles_rpc_mock::MockRpcServer;
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use tower::service_fn;
+
+/// Answers every single request with `{"status": "in-memory"}` and records the method.
+fn in_memory_transport(
+    seen: Arc<Mutex<Vec<String>>>,
+) -> impl Fn(RequestPacket) -> TransportFut<'static> + Clone + Send + Sync + 'static {
+    move |packet: RequestPacket| -> TransportFut<'static> {
+        let seen = seen.clone();
+        Box::pin(async move {
+            let RequestPacket::Single(request) = packet else {
+                return Err(TransportError::local_usage_str("batch not supported"));
+            };
+            seen.lock().unwrap().push(request.method().to_string());
+            let body = json!({
+                "jsonrpc": "2.0",
+                "id": request.id(),
+                "result": { "status": "in-memory" },
+            });
+            Ok(serde_json::from_str::<ResponsePacket>(&body.to_string()).unwrap())
+        })
+    }
+}
+
+#[tokio::test]
+async fn custom_transport_serves_requests_without_network() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let client = RpcClient::from_transport(service_fn(in_memory_transport(seen.clone())), true);
+    let rpc = CirclesRpc::new(client);
+
+    let health = rpc.health().health().await.expect("health");
+    assert_eq!(health.status, "in-memory");
+    assert_eq!(*seen.lock().unwrap(), vec!["circles_health".to_string()]);
+}
+
+#[tokio::test]
+async fn configured_reqwest_client_is_used_for_requests() {
+    let server = MockRpcServer::builder()
+        .respond("circles_health", json!({ "status": "ok" }))
+        .start()
+        .await
+        .expect("server");
+    let mut headers = HeaderMap::new();
+    headers.insert("x-client", HeaderValue::from_static("configured"));
+    let client = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .expect("reqwest client");
+
+    let rpc = CirclesRpc::try_from_http_with_client(&server.url(), client).expect("rpc");
+    rpc.health().health().await.expect("health");
+
+    assert_eq!(
+        server.header_for("circles_health", "x-client"),
+        vec![Some("configured".to_string())]
+    );
+}
@@ -35,6 +35,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     simulated_trusts: None,
                     max_transfers: None,
                     tx_data: None,
+                    validate_path: None,
                 }),
             )
             .await?;
@@ -0,0 +1,30 @@
+use circles_sdk::{Resolution, Sdk, config};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let name = std::env::args()
+        .nth(1)
+        .ok_or("usage: resolve_name <name | symbol | 0x-address | ens-name>")?;
+
+    #[allow(unused_mut)]
+    let mut sdk = Sdk::new(config::gnosis_mainnet(), None)?;
+    #[cfg(feature = "ens")]
+    if let Ok(url) = std::env::var("ENS_RPC_URL") {
+        sdk = sdk.with_ens_rpc(&url)?;
+    }
+
+    match sdk.resolve(&name).await? {
+        Resolution::NotFound => println!("no avatar named {name:?}"),
+        Resolution::Unique(found) => println!("{:#x} ({:?})", found.address, found.source),
+        Resolution::Ambiguous(candidates) => {
+            println!("{name:?} is ambiguous:");
+            for candidate in candidates {
+                println!(
+                    "  {:#x}  {:?}  {}",
+                    candidate.address, candidate.source, candidate.label
+                );
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,421 @@
+//! Per-token bookkeeping for an avatar over a block range.
+//!
+//! Balances are tracked in static (inflation-adjusted) units, which do not decay, and
+//! converted to demurraged atto-circles at the range boundaries. Movements are valued at
+//! their transfer time, so whatever the flows do not explain between opening and closing is
+//! the demurrage charged over the range. Only v2 transfers are covered.
+//!
+//! The same replay reconstructs a single balance at a past block ([`HistoricalBalance`]).
+
+use crate::core::avatar_token_id;
+use crate::{Sdk, SdkError};
+use alloy_primitives::{Address, U256};
+use alloy_provider::Provider;
+use circles_types::{SortOrder, TransactionHistoryRow};
+use circles_utils::converter::atto_static_circles_to_atto_circles;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+/// History rows fetched per page while replaying an avatar's transfers.
+const HISTORY_PAGE_SIZE: u32 = 1000;
+
+/// Movements of one token for [`AccountingReport`], all in demurraged atto-circles.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenAccount {
+    pub token_address: Address,
+    /// Balance just before the first block of the range.
+    pub opening: U256,
+    /// Received from the zero address (personal or group mints).
+    pub mints: U256,
+    pub transfers_in: U256,
+    /// Sent away, including burns.
+    pub transfers_out: U256,
+    /// Decay over the range: `opening + mints + in - out - closing`.
+    pub demurrage: U256,
+    /// Balance at the end of the last block of the range.
+    pub closing: U256,
+    /// Transfers inside the range.
+    pub transfer_count: usize,
+}
+
+/// Accounting report for an avatar between two blocks (both inclusive).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountingReport {
+    pub avatar: Address,
+    pub from_block: u64,
+    pub to_block: u64,
+    /// Timestamp the opening balances are valued at.
+    pub from_timestamp: u64,
+    /// Timestamp the closing balances are valued at.
+    pub to_timestamp: u64,
+    /// One entry per token the avatar held or moved, ordered by token address.
+    pub tokens: Vec<TokenAccount>,
+}
+
+#[derive(Default)]
+struct Ledger {
+    account: TokenAccount,
+    opening_static: U256,
+    closing_static: U256,
+}
+
+impl AccountingReport {
+    /// Replay `history` (v2 transfers of `avatar` in any order) into a report.
+    ///
+    /// Rows after `to_block` are ignored. `from_timestamp` and `to_timestamp` are the
+    /// timestamps of `from_block` and `to_block`.
+    pub fn from_history(
+        avatar: Address,
+        (from_block, from_timestamp): (u64, u64),
+        (to_block, to_timestamp): (u64, u64),
+        history: impl IntoIterator<Item = TransactionHistoryRow>,
+    ) -> Self {
+        let mut ledgers: BTreeMap<Address, Ledger> = BTreeMap::new();
+        for row in history {
+            let incoming = row.to == avatar;
+            if row.block_number > to_block || incoming == (row.from == avatar) {
+                continue;
+            }
+            let amount = row.atto_circles.unwrap_or_default();
+            let static_amount = row.static_atto_circles.unwrap_or_default();
+            let ledger = ledgers.entry(row.token_address).or_default();
+
+            if row.block_number < from_block {
+                ledger.opening_static = apply(ledger.opening_static, static_amount, incoming);
+            } else {
+                let account = &mut ledger.account;
+                account.transfer_count += 1;
+                match (incoming, row.from.is_zero()) {
+                    (true, true) => account.mints += amount,
+                    (true, false) => account.transfers_in += amount,
+                    (false, _) => account.transfers_out += amount,
+                }
+            }
+            ledger.closing_static = apply(ledger.closing_static, static_amount, incoming);
+        }
+
+        let tokens = ledgers
+            .into_iter()
+            .map(|(token_address, ledger)| {
+                let mut account = ledger.account;
+                account.token_address = token_address;
+                account.opening = atto_static_circles_to_atto_circles(
+                    ledger.opening_static,
+                    Some(from_timestamp),
+                );
+                account.closing =
+                    atto_static_circles_to_atto_circles(ledger.closing_static, Some(to_timestamp));
+                account.demurrage = (account.opening + account.mints + account.transfers_in)
+                    .saturating_sub(account.transfers_out)
+                    .saturating_sub(account.closing);
+                account
+            })
+            .collect();
+
+        Self {
+            avatar,
+            from_block,
+            to_block,
+            from_timestamp,
+            to_timestamp,
+            tokens,
+        }
+    }
+
+    /// One CSV row per token, amounts as decimal atto-circles.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "token,opening,mints,transfers_in,transfers_out,demurrage,closing,transfer_count\n",
+        );
+        for t in &self.tokens {
+            let _ = writeln!(
+                csv,
+                "{:#x},{},{},{},{},{},{},{}",
+                t.token_address,
+                t.opening,
+                t.mints,
+                t.transfers_in,
+                t.transfers_out,
+                t.demurrage,
+                t.closing,
+                t.transfer_count
+            );
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// An avatar's balance of one token at a past block, rebuilt from its transfers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoricalBalance {
+    pub avatar: Address,
+    pub token: Address,
+    pub block: u64,
+    pub timestamp: u64,
+    /// Demurraged atto-circles at `timestamp`.
+    pub balance: U256,
+    /// Hub `balanceOf` at `block`, when the chain RPC can still serve that state.
+    pub spot: Option<U256>,
+}
+
+impl HistoricalBalance {
+    /// Replay `history` (v2 transfers of `avatar`) up to `block` and value the result at
+    /// `timestamp`, applying the demurrage factor of each transfer's day.
+    pub fn from_history(
+        avatar: Address,
+        token: Address,
+        (block, timestamp): (u64, u64),
+        history: impl IntoIterator<Item = TransactionHistoryRow>,
+    ) -> Self {
+        let balance = AccountingReport::from_history(
+            avatar,
+            (block, timestamp),
+            (block, timestamp),
+            history.into_iter().filter(|row| row.token_address == token),
+        )
+        .tokens
+        .first()
+        .map_or(U256::ZERO, |account| account.closing);
+        Self {
+            avatar,
+            token,
+            block,
+            timestamp,
+            balance,
+            spot: None,
+        }
+    }
+
+    /// Whether the replayed balance agrees with the spot query within `tolerance`
+    /// atto-circles; `None` without a spot value.
+    ///
+    /// Each transfer's static amount is rounded down, so allow a few atto per transfer.
+    pub fn matches_spot(&self, tolerance: U256) -> Option<bool> {
+        self.spot
+            .map(|spot| spot.abs_diff(self.balance) <= tolerance)
+    }
+}
+
+/// Add or remove a static amount; the indexer never lets a balance go negative, so
+/// saturation only absorbs rounding.
+fn apply(balance: U256, amount: U256, incoming: bool) -> U256 {
+    if incoming {
+        balance.saturating_add(amount)
+    } else {
+        balance.saturating_sub(amount)
+    }
+}
+
+pub(crate) async fn accounting_report(
+    sdk: &Sdk,
+    avatar: Address,
+    from_block: u64,
+    to_block: u64,
+) -> Result<AccountingReport, SdkError> {
+    if from_block > to_block {
+        return Err(SdkError::OperationFailed(format!(
+            "invalid block range {from_block}..={to_block}"
+        )));
+    }
+    let from_timestamp = block_timestamp(sdk, from_block).await?;
+    let to_timestamp = block_timestamp(sdk, to_block).await?;
+
+    let rows = history_through(sdk, avatar, to_block).await?;
+
+    Ok(AccountingReport::from_history(
+        avatar,
+        (from_block, from_timestamp),
+        (to_block, to_timestamp),
+        rows,
+    ))
+}
+
+pub(crate) async fn balance_at_block(
+    sdk: &Sdk,
+    avatar: Address,
+    token: Address,
+    block: u64,
+) -> Result<HistoricalBalance, SdkError> {
+    let timestamp = block_timestamp(sdk, block).await?;
+    let rows = history_through(sdk, avatar, block).await?;
+    let mut balance = HistoricalBalance::from_history(avatar, token, (block, timestamp), rows);
+    // Non-archive nodes reject old state; the replay stands on its own then.
+    balance.spot = sdk
+        .core
+        .hub_v2()
+        .balanceOf(avatar, avatar_token_id(token))
+        .block(block.into())
+        .call()
+        .await
+        .ok();
+    Ok(balance)
+}
+
+/// The avatar's v2 history in ascending order, stopping at the first page past `to_block`.
+async fn history_through(
+    sdk: &Sdk,
+    avatar: Address,
+    to_block: u64,
+) -> Result<Vec<TransactionHistoryRow>, SdkError> {
+    let mut query =
+        sdk.rpc
+            .transaction()
+            .get_transaction_history(avatar, HISTORY_PAGE_SIZE, SortOrder::ASC);
+    let mut rows = Vec::new();
+    while let Some(page) = query.next_page().await? {
+        let past_range = page
+            .items
+            .last()
+            .is_some_and(|row| row.block_number > to_block);
+        rows.extend(page.items);
+        if past_range || !page.has_more {
+            break;
+        }
+    }
+    Ok(rows)
+}
+
+async fn block_timestamp(sdk: &Sdk, block: u64) -> Result<u64, SdkError> {
+    sdk.core
+        .provider()
+        .get_block_by_number(block.into())
+        .await
+        .map_err(|e| SdkError::Contract(e.to_string()))?
+        .map(|block| block.header.timestamp)
+        .ok_or_else(|| SdkError::OperationFailed(format!("block {block} not found")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::TxHash;
+    use circles_utils::converter::atto_circles_to_atto_static_circles;
+
+    const AVATAR: Address = Address::repeat_byte(0x0a);
+    const TOKEN: Address = Address::repeat_byte(0x0a);
+    const OTHER: Address = Address::repeat_byte(0x0b);
+    const DAY: u64 = 24 * 60 * 60;
+    const START: u64 = 1_750_000_000;
+
+    fn transfer(from: Address, to: Address, block: u64, amount: u64) -> TransactionHistoryRow {
+        let timestamp = START + block * DAY;
+        let atto = U256::from(amount) * U256::from(10u64).pow(U256::from(18));
+        TransactionHistoryRow {
+            block_number: block,
+            timestamp,
+            transaction_index: 0,
+            log_index: 0,
+            transaction_hash: TxHash::ZERO,
+            version: 2,
+            operator: None,
+            from,
+            to,
+            id: None,
+            token_address: TOKEN,
+            value: atto.to_string(),
+            circles: None,
+            atto_circles: Some(atto),
+            static_circles: None,
+            static_atto_circles: Some(atto_circles_to_atto_static_circles(atto, Some(timestamp))),
+            crc: None,
+            atto_crc: None,
+        }
+    }
+
+    fn report_rows() -> Vec<TransactionHistoryRow> {
+        vec![
+            transfer(Address::ZERO, AVATAR, 1, 100),
+            transfer(Address::ZERO, AVATAR, 20, 24),
+            transfer(OTHER, AVATAR, 25, 10),
+            transfer(AVATAR, OTHER, 30, 50),
+            transfer(AVATAR, AVATAR, 31, 5),
+            transfer(OTHER, AVATAR, 50, 999),
+        ]
+    }
+
+    fn report() -> AccountingReport {
+        AccountingReport::from_history(
+            AVATAR,
+            (10, START + 10 * DAY),
+            (40, START + 40 * DAY),
+            report_rows(),
+        )
+    }
+
+    #[test]
+    fn report_splits_flows_and_attributes_the_rest_to_demurrage() {
+        let report = report();
+        assert_eq!(report.tokens.len(), 1);
+        let token = &report.tokens[0];
+        let crc = |n: u64| U256::from(n) * U256::from(10u64).pow(U256::from(18));
+
+        // 100 minted on day 1 has decayed by day 10.
+        assert!(token.opening < crc(100) && token.opening > crc(99));
+        assert_eq!(token.mints, crc(24));
+        assert_eq!(token.transfers_in, crc(10));
+        assert_eq!(token.transfers_out, crc(50));
+        assert_eq!(token.transfer_count, 3);
+        assert!(token.demurrage > U256::ZERO);
+        assert_eq!(
+            token.opening + token.mints + token.transfers_in,
+            token.transfers_out + token.closing + token.demurrage
+        );
+    }
+
+    #[test]
+    fn historical_balance_applies_demurrage_since_each_transfer() {
+        let crc = |n: u64| U256::from(n) * U256::from(10u64).pow(U256::from(18));
+        let at = |block: u64| (block, START + block * DAY);
+        let rows = || report_rows().into_iter();
+
+        let before_mint = HistoricalBalance::from_history(AVATAR, TOKEN, at(0), rows());
+        assert_eq!(before_mint.balance, U256::ZERO);
+
+        // Same day as the mint: no decay yet.
+        let minted = HistoricalBalance::from_history(AVATAR, TOKEN, at(1), rows());
+        assert!(crc(100).abs_diff(minted.balance) <= U256::from(1));
+
+        let later = HistoricalBalance::from_history(AVATAR, TOKEN, at(40), rows());
+        let report = report();
+        assert_eq!(later.balance, report.tokens[0].closing);
+        assert_eq!(later.matches_spot(U256::from(10)), None);
+
+        let other_token = HistoricalBalance::from_history(AVATAR, OTHER, at(40), rows());
+        assert_eq!(other_token.balance, U256::ZERO);
+
+        let checked = HistoricalBalance {
+            spot: Some(later.balance + U256::from(3)),
+            ..later
+        };
+        assert_eq!(checked.matches_spot(U256::from(10)), Some(true));
+        assert_eq!(checked.matches_spot(U256::from(2)), Some(false));
+    }
+
+    #[test]
+    fn report_exports_csv_and_json() {
+        let report = report();
+        let csv = report.to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("token,opening,mints,transfers_in,transfers_out,demurrage,closing,transfer_count")
+        );
+        let row = lines.next().expect("token row");
+        assert!(row.starts_with(&format!("{TOKEN:#x},")));
+        assert!(row.ends_with(",3"));
+        assert!(lines.next().is_none());
+
+        let json: serde_json::Value =
+            serde_json::from_str(&report.to_json().expect("json")).expect("parse");
+        assert_eq!(json["fromBlock"], 10);
+        assert_eq!(json["tokens"][0]["transferCount"], 3);
+    }
+}
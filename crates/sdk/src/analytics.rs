@@ -0,0 +1,147 @@
+//! Reputation-style metrics over a local [`TrustGraph`].
+//!
+//! Everything here is pure computation: build or sync a [`TrustGraph`] first, then call
+//! [`analyze`] as often as needed without touching the network.
+
+use crate::TrustGraph;
+use alloy_primitives::Address;
+use std::collections::BTreeMap;
+
+/// Tuning for the PageRank pass in [`analyze`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageRankOptions {
+    /// Probability of following a trust edge rather than jumping to a random avatar.
+    pub damping: f64,
+    pub max_iterations: usize,
+    /// Stop once the summed absolute score change of an iteration drops below this.
+    pub tolerance: f64,
+}
+
+impl Default for PageRankOptions {
+    fn default() -> Self {
+        Self {
+            damping: 0.85,
+            max_iterations: 100,
+            tolerance: 1e-9,
+        }
+    }
+}
+
+/// Per-avatar trust metrics.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrustMetrics {
+    /// Avatars trusting this one.
+    pub in_degree: usize,
+    /// Avatars this one trusts.
+    pub out_degree: usize,
+    /// Counterparts trusting and trusted by this one.
+    pub mutual: usize,
+    /// PageRank score; scores over the whole graph sum to 1.
+    pub score: f64,
+}
+
+/// Degree, mutual-trust, and PageRank metrics for every avatar in `graph`.
+///
+/// Trust flows from truster to trustee, so being trusted by well-trusted avatars raises the
+/// score. Avatars that trust nobody spread their score evenly over the graph.
+pub fn analyze(graph: &TrustGraph, options: &PageRankOptions) -> BTreeMap<Address, TrustMetrics> {
+    let scores = pagerank(graph, options);
+    graph
+        .avatars()
+        .into_iter()
+        .map(|avatar| {
+            let metrics = TrustMetrics {
+                in_degree: graph.trusted_by(avatar).count(),
+                out_degree: graph.trusts(avatar).count(),
+                mutual: mutual_count(graph, avatar),
+                score: scores.get(&avatar).copied().unwrap_or_default(),
+            };
+            (avatar, metrics)
+        })
+        .collect()
+}
+
+/// Number of avatars with trust in both directions with `avatar`.
+pub fn mutual_count(graph: &TrustGraph, avatar: Address) -> usize {
+    graph
+        .trusts(avatar)
+        .filter(|other| graph.is_trusting(*other, avatar))
+        .count()
+}
+
+/// PageRank scores keyed by avatar.
+pub fn pagerank(graph: &TrustGraph, options: &PageRankOptions) -> BTreeMap<Address, f64> {
+    let avatars: Vec<Address> = graph.avatars().into_iter().collect();
+    let n = avatars.len();
+    if n == 0 {
+        return BTreeMap::new();
+    }
+    let index: BTreeMap<Address, usize> = avatars
+        .iter()
+        .enumerate()
+        .map(|(i, avatar)| (*avatar, i))
+        .collect();
+    let outgoing: Vec<Vec<usize>> = avatars
+        .iter()
+        .map(|avatar| graph.trusts(*avatar).map(|t| index[&t]).collect())
+        .collect();
+
+    let uniform = 1.0 / n as f64;
+    let mut scores = vec![uniform; n];
+    for _ in 0..options.max_iterations {
+        let dangling: f64 = outgoing
+            .iter()
+            .zip(&scores)
+            .filter(|(out, _)| out.is_empty())
+            .map(|(_, score)| score)
+            .sum();
+        let base = (1.0 - options.damping) * uniform + options.damping * dangling * uniform;
+        let mut next = vec![base; n];
+        for (from, out) in outgoing.iter().enumerate() {
+            if out.is_empty() {
+                continue;
+            }
+            let share = options.damping * scores[from] / out.len() as f64;
+            for to in out {
+                next[*to] += share;
+            }
+        }
+        let delta: f64 = next.iter().zip(&scores).map(|(a, b)| (a - b).abs()).sum();
+        scores = next;
+        if delta < options.tolerance {
+            break;
+        }
+    }
+    avatars.into_iter().zip(scores).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_widely_trusted_avatar_highest() {
+        let hub = Address::repeat_byte(1);
+        let a = Address::repeat_byte(2);
+        let b = Address::repeat_byte(3);
+        let c = Address::repeat_byte(4);
+        let mut graph = TrustGraph::new();
+        for truster in [a, b, c] {
+            graph.insert(truster, hub, u64::MAX);
+        }
+        graph.insert(hub, a, u64::MAX);
+
+        let metrics = analyze(&graph, &PageRankOptions::default());
+        assert_eq!(metrics.len(), 4);
+        assert_eq!(metrics[&hub].in_degree, 3);
+        assert_eq!(metrics[&hub].out_degree, 1);
+        assert_eq!(metrics[&hub].mutual, 1);
+        assert_eq!(metrics[&b].mutual, 0);
+
+        let total: f64 = metrics.values().map(|m| m.score).sum();
+        assert!((total - 1.0).abs() < 1e-6);
+        assert!(metrics[&hub].score > metrics[&a].score);
+        assert!(metrics[&a].score > metrics[&b].score);
+        assert!((metrics[&b].score - metrics[&c].score).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,177 @@
+//! Heuristics about an avatar, meant to inform a decision to trust it.
+
+use crate::SdkError;
+use alloy_primitives::Address;
+use circles_rpc::CirclesRpc;
+use circles_types::{AggregatedTrustRelation, AvatarType, TrustRelationType};
+use circles_utils::time::unix_now;
+use std::collections::HashSet;
+
+/// Longest inviter chain followed before giving up.
+const MAX_INVITE_CHAIN: usize = 32;
+/// Transaction history rows sampled for [`TokenVelocity`].
+const VELOCITY_SAMPLE: u32 = 100;
+/// Window [`TokenVelocity`] counts over: 30 days.
+pub const VELOCITY_WINDOW_SECS: u64 = 30 * 24 * 60 * 60;
+
+/// Transfer activity within the last [`VELOCITY_WINDOW_SECS`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TokenVelocity {
+    pub transfers_in: usize,
+    pub transfers_out: usize,
+    /// Distinct avatars sent to or received from.
+    pub counterparties: usize,
+    pub circles_in: f64,
+    pub circles_out: f64,
+    /// The sampled history page was full and still inside the window, so the counts are a
+    /// lower bound.
+    pub truncated: bool,
+}
+
+/// Signals gathered by [`Sdk::assess_avatar`](crate::Sdk::assess_avatar).
+///
+/// None of these prove or disprove that an avatar is a real person; they are inputs for a
+/// UI or policy that has to decide whether to trust it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AvatarAssessment {
+    pub avatar: Address,
+    pub avatar_type: AvatarType,
+    /// Registration time (unix seconds), from the invitation record or the avatar info.
+    pub registered_at: Option<u64>,
+    /// Seconds since `registered_at`.
+    pub account_age_secs: Option<u64>,
+    /// Inviters from the direct one up to the root of the chain.
+    pub invite_chain: Vec<Address>,
+    /// The chain hit a cycle or the 32-inviter limit before reaching its root.
+    pub invite_chain_truncated: bool,
+    pub trusted_by_count: usize,
+    pub trusts_count: usize,
+    /// Avatars connected to this one by trust in either direction that the SDK's sender
+    /// trusts. `None` without a sender address.
+    pub mutual_connections: Option<Vec<Address>>,
+    pub velocity: TokenVelocity,
+}
+
+impl AvatarAssessment {
+    /// Number of invitation hops between this avatar and an uninvited root.
+    pub fn invite_depth(&self) -> usize {
+        self.invite_chain.len()
+    }
+}
+
+pub(crate) async fn assess_avatar(
+    rpc: &CirclesRpc,
+    me: Option<Address>,
+    avatar: Address,
+) -> Result<AvatarAssessment, SdkError> {
+    let now = unix_now();
+    let info = rpc.avatar().get_avatar_info(avatar).await?;
+    let origin = rpc.invitation().get_invitation_origin(avatar).await?;
+    let registered_at = origin
+        .as_ref()
+        .map(|origin| origin.timestamp)
+        .or(info.timestamp);
+
+    let (invite_chain, invite_chain_truncated) =
+        invite_chain(rpc, avatar, origin.and_then(|origin| origin.inviter)).await?;
+
+    let relations = rpc
+        .trust()
+        .compute_aggregated_trust_relations(avatar)
+        .await?;
+    let mutual_connections = match me {
+        Some(me) => {
+            let mine = rpc.trust().compute_aggregated_trust_relations(me).await?;
+            Some(mutual_connections(me, avatar, &mine, &relations))
+        }
+        None => None,
+    };
+
+    let history = rpc
+        .transaction()
+        .get_transaction_history_page(avatar, Some(VELOCITY_SAMPLE), None, None, None)
+        .await?;
+    let since = now.saturating_sub(VELOCITY_WINDOW_SECS);
+    let mut velocity = TokenVelocity::default();
+    let mut counterparties = HashSet::new();
+    let mut oldest = u64::MAX;
+    for row in &history.results {
+        oldest = oldest.min(row.timestamp);
+        if row.timestamp < since {
+            continue;
+        }
+        let circles = row.circles.unwrap_or_default();
+        if row.to == avatar {
+            velocity.transfers_in += 1;
+            velocity.circles_in += circles;
+            counterparties.insert(row.from);
+        } else if row.from == avatar {
+            velocity.transfers_out += 1;
+            velocity.circles_out += circles;
+            counterparties.insert(row.to);
+        }
+    }
+    velocity.counterparties = counterparties.len();
+    velocity.truncated = history.has_more && oldest >= since;
+
+    Ok(AvatarAssessment {
+        avatar,
+        avatar_type: info.avatar_type,
+        registered_at,
+        account_age_secs: registered_at.map(|at| now.saturating_sub(at)),
+        invite_chain,
+        invite_chain_truncated,
+        trusted_by_count: count_relations(&relations, TrustRelationType::TrustedBy),
+        trusts_count: count_relations(&relations, TrustRelationType::Trusts),
+        mutual_connections,
+        velocity,
+    })
+}
+
+async fn invite_chain(
+    rpc: &CirclesRpc,
+    avatar: Address,
+    mut next: Option<Address>,
+) -> Result<(Vec<Address>, bool), SdkError> {
+    let mut chain = Vec::new();
+    let mut seen = HashSet::from([avatar]);
+    while let Some(inviter) = next {
+        if chain.len() >= MAX_INVITE_CHAIN || !seen.insert(inviter) {
+            return Ok((chain, true));
+        }
+        chain.push(inviter);
+        next = rpc.invitation().get_invited_by(inviter).await?;
+    }
+    Ok((chain, false))
+}
+
+/// Relations of `kind`, counting mutual trust for both directions.
+fn count_relations(relations: &[AggregatedTrustRelation], kind: TrustRelationType) -> usize {
+    relations
+        .iter()
+        .filter(|rel| rel.relation == kind || rel.relation == TrustRelationType::MutuallyTrusts)
+        .count()
+}
+
+fn mutual_connections(
+    me: Address,
+    avatar: Address,
+    mine: &[AggregatedTrustRelation],
+    theirs: &[AggregatedTrustRelation],
+) -> Vec<Address> {
+    let trusted_by_me: HashSet<Address> = mine
+        .iter()
+        .filter(|rel| {
+            matches!(
+                rel.relation,
+                TrustRelationType::Trusts | TrustRelationType::MutuallyTrusts
+            )
+        })
+        .map(|rel| rel.object_avatar)
+        .collect();
+    theirs
+        .iter()
+        .map(|rel| rel.object_avatar)
+        .filter(|other| *other != me && *other != avatar && trusted_by_me.contains(other))
+        .collect()
+}
@@ -1,10 +1,13 @@
-use crate::avatar::common::CommonAvatar;
-use crate::cid_v0_to_digest::cid_v0_to_digest;
+use crate::avatar::common::{CommonAvatar, TrustDiff};
+use crate::cid::cid_to_digest;
+use crate::services::trust_scheduler::{TrustScheduler, trust_expiry_after_days};
+use crate::trust_list::{TrustImportPlan, TrustImportPolicy, TrustList};
 use crate::{
     ContractRunner, Core, PreparedTransaction, Profile, SdkError, SubmittedTx, call_to_tx,
 };
 use alloy_primitives::{Address, Bytes, U256, aliases::U96};
-use circles_abis::BaseGroup;
+use alloy_sol_types::sol;
+use circles_abis::{BaseGroup, StandardTreasury};
 use circles_profiles::Profiles;
 #[cfg(feature = "ws")]
 use circles_rpc::events::subscription::CirclesSubscription;
@@ -13,10 +16,248 @@ use circles_rpc::{CirclesRpc, PagedQuery};
 use circles_types::CirclesEvent;
 use circles_types::{
     AdvancedTransferOptions, AggregatedTrustRelation, AvatarInfo, Balance, PathfindingResult,
-    SortOrder, TokenBalanceResponse, TransactionHistoryRow, TrustRelation,
+    SortOrder, TokenBalanceResponse, TotalBalance, TransactionHistoryRow, TrustRelation,
+    TrustRelationType,
 };
+use circles_utils::time::unix_now;
+use std::collections::HashSet;
 use std::sync::Arc;
 
+sol! {
+    #[sol(rpc)]
+    interface IMembershipCondition {
+        function passesMembershipCondition(address avatar) external view returns (bool);
+    }
+
+    #[sol(rpc)]
+    interface IMintPolicy {
+        function beforeMintPolicy(
+            address minter,
+            address group,
+            uint256[] collateral,
+            uint256[] amounts,
+            bytes data
+        ) external returns (bool);
+    }
+}
+
+/// Result of asking one membership condition contract about a prospective member.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConditionOutcome {
+    Passed,
+    Failed,
+    /// The condition contract reverted or could not be called; the join would revert too.
+    Errored(String),
+}
+
+/// One membership condition evaluated by [`BaseGroupAvatar::can_join`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConditionCheck {
+    pub condition: Address,
+    pub outcome: ConditionOutcome,
+}
+
+/// Whether an avatar would pass the group's membership conditions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JoinEligibility {
+    pub avatar: Address,
+    /// The group already trusts the avatar.
+    pub already_member: bool,
+    /// Every active condition, in the order the group checks them on-chain.
+    pub checks: Vec<ConditionCheck>,
+}
+
+impl JoinEligibility {
+    /// Whether `trustBatchWithConditions` would accept the avatar.
+    pub fn can_join(&self) -> bool {
+        self.blocking().next().is_none()
+    }
+
+    /// Conditions that do not pass. The first is the one a `MembershipCheckFailed`
+    /// revert would name.
+    pub fn blocking(&self) -> impl Iterator<Item = &ConditionCheck> {
+        self.checks
+            .iter()
+            .filter(|check| check.outcome != ConditionOutcome::Passed)
+    }
+}
+
+/// Which mint policy contract a group delegates to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MintPolicyKind {
+    /// The deployment's base group mint policy, which accepts every mint the hub allows.
+    Base,
+    /// Any other contract; its rules can only be checked by simulating it.
+    Custom,
+}
+
+/// Why a prospective group mint would revert.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MintPolicyViolation {
+    #[error("no collateral given")]
+    NoCollateral,
+    #[error("zero amount of collateral {collateral}")]
+    ZeroAmount { collateral: Address },
+    #[error("group does not trust collateral {collateral}")]
+    UntrustedCollateral { collateral: Address },
+    #[error("minter holds {available} of collateral {collateral}, needs {required}")]
+    InsufficientCollateral {
+        collateral: Address,
+        required: U256,
+        available: U256,
+    },
+    #[error("mint policy {policy} rejected the mint")]
+    RejectedByPolicy { policy: Address },
+}
+
+/// A group's mint policy and the rules a mint is checked against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupMintPolicy {
+    /// Policy contract registered for the group on the v2 hub (`mintPolicies`).
+    pub policy: Address,
+    pub kind: MintPolicyKind,
+    /// Policy the group contract itself was deployed with (`BASE_MINT_POLICY`); the hub's
+    /// `policy` is the one a mint is checked against.
+    pub group_policy: Address,
+    /// Contract that turns collateral sent by pathfinding into group mints.
+    pub mint_handler: Address,
+    /// Treasury that receives and holds the collateral of every mint.
+    pub treasury: Address,
+    /// Service address allowed to manage membership on the owner's behalf.
+    pub service: Address,
+    /// Address that collects the group's fees.
+    pub fee_collection: Address,
+    /// Condition contracts new members must satisfy before the group trusts them.
+    pub membership_conditions: Vec<Address>,
+    /// Most membership conditions the group can hold at once (`MAX_CONDITIONS`).
+    pub max_conditions: U256,
+    /// Avatars whose tokens the group accepts as collateral, i.e. the ones it trusts.
+    pub accepted_collateral: HashSet<Address>,
+}
+
+impl GroupMintPolicy {
+    /// Check `collateral` (avatar, amount) pairs against the rules known off-chain.
+    ///
+    /// A [`MintPolicyKind::Custom`] policy may still reject a mint that passes here; see
+    /// [`BaseGroupAvatar::validate_mint`].
+    pub fn check(&self, collateral: &[(Address, U256)]) -> Result<(), MintPolicyViolation> {
+        if collateral.is_empty() {
+            return Err(MintPolicyViolation::NoCollateral);
+        }
+        for &(avatar, amount) in collateral {
+            if amount.is_zero() {
+                return Err(MintPolicyViolation::ZeroAmount { collateral: avatar });
+            }
+            if !self.accepted_collateral.contains(&avatar) {
+                return Err(MintPolicyViolation::UntrustedCollateral { collateral: avatar });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of simulating `beforeMintPolicy`. A policy that reverts rejects the mint just as
+/// one returning false does; any other failure says nothing about the policy and is returned.
+fn policy_verdict(result: Result<bool, alloy_contract::Error>) -> Result<bool, SdkError> {
+    match result {
+        Ok(accepted) => Ok(accepted),
+        Err(alloy_contract::Error::TransportError(err)) if err.as_error_resp().is_some() => {
+            Ok(false)
+        }
+        Err(err) => Err(SdkError::Contract(err.to_string())),
+    }
+}
+
+/// One collateral position held for a group by its treasury.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreasuryCollateral {
+    /// Avatar whose personal/group token is locked as collateral.
+    pub avatar: Address,
+    /// Locked amount in atto-circles.
+    pub amount: U256,
+}
+
+/// Snapshot of a group's standard treasury backing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupTreasury {
+    /// Treasury contract configured on the group (`BASE_TREASURY`).
+    pub treasury: Address,
+    /// Vault created by the treasury for this group, if one exists yet.
+    pub vault: Option<Address>,
+    /// Per-avatar collateral, largest position first.
+    pub collateral: Vec<TreasuryCollateral>,
+    /// Sum of all collateral positions.
+    pub total_collateral: U256,
+}
+
+/// Collateral a redeemer would receive for burning group tokens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedemptionQuote {
+    /// Group token amount the quote was requested for.
+    pub requested: U256,
+    /// Amount that can actually be redeemed from eligible collateral.
+    pub redeemable: U256,
+    /// Collateral picked to cover `redeemable`, largest position first.
+    pub collateral: Vec<TreasuryCollateral>,
+}
+
+impl RedemptionQuote {
+    /// Whether the full requested amount is covered.
+    pub fn is_complete(&self) -> bool {
+        self.redeemable == self.requested
+    }
+}
+
+fn collateral_from_balances(balances: Vec<TokenBalanceResponse>) -> Vec<TreasuryCollateral> {
+    let mut collateral: Vec<TreasuryCollateral> = balances
+        .into_iter()
+        .filter(|balance| balance.is_erc1155)
+        .filter_map(|balance| {
+            let amount = balance.atto_circles.or(match balance.balance {
+                Balance::Raw(raw) => Some(raw),
+                Balance::TimeCircles(_) => None,
+            })?;
+            (!amount.is_zero()).then_some(TreasuryCollateral {
+                avatar: balance.token_owner,
+                amount,
+            })
+        })
+        .collect();
+    collateral.sort_by(|a, b| b.amount.cmp(&a.amount).then(a.avatar.cmp(&b.avatar)));
+    collateral
+}
+
+/// Greedily allocate collateral (largest first) to cover `amount`.
+///
+/// When `eligible` is set, only collateral minted by those avatars is considered.
+fn quote_redemption(
+    collateral: &[TreasuryCollateral],
+    amount: U256,
+    eligible: Option<&HashSet<Address>>,
+) -> RedemptionQuote {
+    let mut remaining = amount;
+    let mut picked = Vec::new();
+    for position in collateral {
+        if remaining.is_zero() {
+            break;
+        }
+        if eligible.is_some_and(|set| !set.contains(&position.avatar)) {
+            continue;
+        }
+        let take = position.amount.min(remaining);
+        remaining -= take;
+        picked.push(TreasuryCollateral {
+            avatar: position.avatar,
+            amount: take,
+        });
+    }
+    RedemptionQuote {
+        requested: amount,
+        redeemable: amount - remaining,
+        collateral: picked,
+    }
+}
+
 /// Top-level avatar enum variant: base group.
 pub struct BaseGroupAvatar {
     /// Avatar address on-chain.
@@ -42,6 +283,8 @@ impl BaseGroupAvatar {
     }
 
     /// Get aggregate balance (v1/v2 selectable).
+    #[deprecated(note = "use `total_balances`, which covers both hubs in atto units")]
+    #[allow(deprecated)]
     pub async fn total_balance(
         &self,
         as_time_circles: bool,
@@ -50,6 +293,11 @@ impl BaseGroupAvatar {
         self.common.total_balance(as_time_circles, use_v2).await
     }
 
+    /// Get the aggregate balance on both hubs, in atto units.
+    pub async fn total_balances(&self, as_time_circles: bool) -> Result<TotalBalance, SdkError> {
+        self.common.total_balances(as_time_circles).await
+    }
+
     /// Get the total supply of this group's token.
     pub async fn total_supply(&self) -> Result<U256, SdkError> {
         let token_id = self
@@ -154,6 +402,220 @@ impl BaseGroupAvatar {
             .map_err(|e| SdkError::Contract(e.to_string()))
     }
 
+    /// Evaluate the group's membership conditions for `avatar` without sending anything.
+    ///
+    /// Each condition contract is called directly, so the report lists every blocking
+    /// condition rather than only the first one `trustBatchWithConditions` would revert on.
+    pub async fn can_join(&self, avatar: Address) -> Result<JoinEligibility, SdkError> {
+        let mut checks = Vec::new();
+        for condition in self.membership_conditions().await? {
+            let outcome = match IMembershipCondition::new(condition, self.core.provider())
+                .passesMembershipCondition(avatar)
+                .call()
+                .await
+            {
+                Ok(true) => ConditionOutcome::Passed,
+                Ok(false) => ConditionOutcome::Failed,
+                Err(err) => ConditionOutcome::Errored(err.to_string()),
+            };
+            checks.push(ConditionCheck { condition, outcome });
+        }
+        Ok(JoinEligibility {
+            avatar,
+            already_member: self.is_trusting(avatar).await?,
+            checks,
+        })
+    }
+
+    /// Read the group's mint policy with its parameters: the contracts a mint passes
+    /// through, membership conditions and their limit, and accepted collateral.
+    pub async fn mint_policy(&self) -> Result<GroupMintPolicy, SdkError> {
+        let group = self.core.base_group(self.address);
+        let policy = self
+            .core
+            .hub_v2()
+            .mintPolicies(self.address)
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))?;
+        let max_conditions = group
+            .MAX_CONDITIONS()
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))?;
+        let group_policy = group
+            .BASE_MINT_POLICY()
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))?;
+        let accepted_collateral = self
+            .trusts()
+            .await?
+            .into_iter()
+            .map(|rel| rel.object_avatar)
+            .collect();
+        Ok(GroupMintPolicy {
+            policy,
+            kind: if policy == self.core.config.base_group_mint_policy {
+                MintPolicyKind::Base
+            } else {
+                MintPolicyKind::Custom
+            },
+            group_policy,
+            mint_handler: self.mint_handler().await?,
+            treasury: self.treasury_address().await?,
+            service: self.service().await?,
+            fee_collection: self.fee_collection().await?,
+            membership_conditions: self.membership_conditions().await?,
+            max_conditions,
+            accepted_collateral,
+        })
+    }
+
+    /// Check a prospective `groupMint` by `minter` before sending it.
+    ///
+    /// Runs [`GroupMintPolicy::check`], compares each amount with the minter's ERC1155
+    /// balance, and simulates a custom policy's `beforeMintPolicy` as the hub would call
+    /// it. Failures come back as [`SdkError::MintPolicy`] instead of a revert.
+    pub async fn validate_mint(
+        &self,
+        minter: Address,
+        collateral: &[(Address, U256)],
+        data: Bytes,
+    ) -> Result<GroupMintPolicy, SdkError> {
+        let policy = self.mint_policy().await?;
+        policy.check(collateral)?;
+
+        let balances = self
+            .common
+            .rpc
+            .token()
+            .get_token_balances(minter, false, true)
+            .await?;
+        let held = collateral_from_balances(balances);
+        for &(avatar, required) in collateral {
+            let available = held
+                .iter()
+                .find(|position| position.avatar == avatar)
+                .map_or(U256::ZERO, |position| position.amount);
+            if available < required {
+                return Err(MintPolicyViolation::InsufficientCollateral {
+                    collateral: avatar,
+                    required,
+                    available,
+                }
+                .into());
+            }
+        }
+
+        if policy.kind == MintPolicyKind::Custom {
+            let (ids, amounts): (Vec<U256>, Vec<U256>) = collateral
+                .iter()
+                .map(|(avatar, amount)| (U256::from_be_slice(avatar.as_slice()), *amount))
+                .unzip();
+            let accepted = policy_verdict(
+                IMintPolicy::new(policy.policy, self.core.provider())
+                    .beforeMintPolicy(minter, self.address, ids, amounts, data)
+                    .from(self.core.config.v2_hub_address)
+                    .call()
+                    .await,
+            )?;
+            if !accepted {
+                return Err(MintPolicyViolation::RejectedByPolicy {
+                    policy: policy.policy,
+                }
+                .into());
+            }
+        }
+        Ok(policy)
+    }
+
+    /// Address of the treasury configured on this group.
+    pub async fn treasury_address(&self) -> Result<Address, SdkError> {
+        self.core
+            .base_group(self.address)
+            .BASE_TREASURY()
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))
+    }
+
+    /// Vault the standard treasury created for this group, if any.
+    pub async fn treasury_vault(&self) -> Result<Option<Address>, SdkError> {
+        let treasury = self.treasury_address().await?;
+        self.vault_for(treasury).await
+    }
+
+    /// Per-avatar collateral backing this group, largest position first.
+    pub async fn treasury_collateral(&self) -> Result<Vec<TreasuryCollateral>, SdkError> {
+        Ok(self.treasury().await?.collateral)
+    }
+
+    /// Inspect the group's treasury: vault address and collateral composition.
+    ///
+    /// Collateral is read from the vault when one exists, otherwise from the treasury itself.
+    pub async fn treasury(&self) -> Result<GroupTreasury, SdkError> {
+        let treasury = self.treasury_address().await?;
+        let vault = self.vault_for(treasury).await?;
+        let balances = self
+            .common
+            .rpc
+            .token()
+            .get_token_balances(vault.unwrap_or(treasury), false, true)
+            .await?;
+        let collateral = collateral_from_balances(balances);
+        let total_collateral = collateral.iter().fold(U256::ZERO, |acc, position| {
+            acc.saturating_add(position.amount)
+        });
+        Ok(GroupTreasury {
+            treasury,
+            vault,
+            collateral,
+            total_collateral,
+        })
+    }
+
+    /// Quote which collateral redeeming `amount` group tokens would release.
+    ///
+    /// With a `redeemer`, only collateral from avatars the redeemer trusts is eligible,
+    /// mirroring the transfer builder's redeem path.
+    pub async fn redemption_quote(
+        &self,
+        amount: U256,
+        redeemer: Option<Address>,
+    ) -> Result<RedemptionQuote, SdkError> {
+        let collateral = self.treasury_collateral().await?;
+        let eligible = match redeemer {
+            Some(redeemer) => Some(
+                self.common
+                    .rpc
+                    .trust()
+                    .get_aggregated_trust_relations(redeemer)
+                    .await?
+                    .into_iter()
+                    .filter(|rel| {
+                        matches!(
+                            rel.relation,
+                            TrustRelationType::Trusts | TrustRelationType::MutuallyTrusts
+                        )
+                    })
+                    .map(|rel| rel.object_avatar)
+                    .collect::<HashSet<_>>(),
+            ),
+            None => None,
+        };
+        Ok(quote_redemption(&collateral, amount, eligible.as_ref()))
+    }
+
+    async fn vault_for(&self, treasury: Address) -> Result<Option<Address>, SdkError> {
+        let vault = StandardTreasury::new(treasury, self.core.provider())
+            .vaults(self.address)
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))?;
+        Ok((vault != Address::ZERO).then_some(vault))
+    }
+
     /// Fetch profile (cached by CID in memory).
     pub async fn profile(&self) -> Result<Option<Profile>, SdkError> {
         self.common.profile(self.info.cid_v0.as_deref()).await
@@ -176,7 +638,7 @@ impl BaseGroupAvatar {
 
     /// Update the on-chain profile CID pointer through the BaseGroup contract (requires runner).
     pub async fn update_profile_metadata(&self, cid: &str) -> Result<Vec<SubmittedTx>, SdkError> {
-        let digest = cid_v0_to_digest(cid)?;
+        let digest = cid_to_digest(cid)?;
         let call = circles_abis::BaseGroup::updateMetadataDigestCall {
             _metadataDigest: digest,
         };
@@ -216,6 +678,74 @@ impl BaseGroupAvatar {
         self.trust_add(avatars, 0).await
     }
 
+    /// Trust one or more avatars for `days` days from now (requires runner).
+    pub async fn trust_add_with_duration(
+        &self,
+        avatars: &[Address],
+        days: u64,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        let expiry = trust_expiry_after_days(unix_now(), days)?;
+        self.trust_add(avatars, expiry).await
+    }
+
+    /// Scheduler that renews the group's expiring member trust through the group contract.
+    pub fn trust_scheduler(&self) -> TrustScheduler {
+        TrustScheduler::new(
+            self.common.rpc.clone(),
+            self.runner.clone(),
+            self.address,
+            self.address,
+        )
+    }
+
+    /// Diff current outgoing trust against `target`.
+    pub async fn trust_diff(&self, target: &[Address]) -> Result<TrustDiff, SdkError> {
+        self.common.trust_diff(target).await
+    }
+
+    /// Plan trust/untrust calls that make outgoing trust equal `target` (no submit).
+    pub async fn plan_trust_sync(
+        &self,
+        target: &[Address],
+        expiry: u128,
+    ) -> Result<Vec<PreparedTransaction>, SdkError> {
+        self.common
+            .plan_trust_sync(self.address, target, expiry)
+            .await
+    }
+
+    /// Sync outgoing trust to `target` with a minimal batch of `trust` calls (requires runner).
+    pub async fn trust_sync(
+        &self,
+        target: &[Address],
+        expiry: u128,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        self.common.trust_sync(self.address, target, expiry).await
+    }
+
+    /// Export live outgoing trust as a canonical list for JSON/CSV files.
+    pub async fn trust_export(&self) -> Result<TrustList, SdkError> {
+        self.common.trust_export().await
+    }
+
+    /// Diff outgoing trust against an imported `list` (no submit).
+    pub async fn plan_trust_import(
+        &self,
+        list: &TrustList,
+        policy: TrustImportPolicy,
+    ) -> Result<TrustImportPlan, SdkError> {
+        self.common.plan_trust_import(list, policy).await
+    }
+
+    /// Apply an imported trust `list` under `policy` in one batch (requires runner).
+    pub async fn trust_import(
+        &self,
+        list: &TrustList,
+        policy: TrustImportPolicy,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        self.common.trust_import(self.address, list, policy).await
+    }
+
     /// Trust a batch of members with membership condition checks (requires runner).
     pub async fn trust_add_batch_with_conditions(
         &self,
@@ -279,6 +809,16 @@ impl BaseGroupAvatar {
         self.common.subscribe_events_ws(ws_url, filter).await
     }
 
+    /// Subscribe through a shared [`crate::ws::WsHub`] connection.
+    #[cfg(feature = "ws")]
+    pub async fn subscribe_events_shared(
+        &self,
+        hub: &crate::ws::WsHub,
+        filter: Option<serde_json::Value>,
+    ) -> Result<crate::ws::WsHubSubscription, SdkError> {
+        self.common.subscribe_events_shared(hub, filter).await
+    }
+
     #[cfg(feature = "ws")]
     pub async fn subscribe_events_ws_with_retries(
         &self,
@@ -311,6 +851,26 @@ impl BaseGroupAvatar {
         self.common.transfer(to, amount, options).await
     }
 
+    /// Plan a transfer as a resumable [`TransferPlan`](crate::TransferPlan).
+    pub async fn plan_resumable_transfer(
+        &self,
+        to: Address,
+        amount: U256,
+        options: Option<AdvancedTransferOptions>,
+    ) -> Result<crate::TransferPlan, SdkError> {
+        self.common
+            .plan_resumable_transfer(to, amount, options)
+            .await
+    }
+
+    /// Send the unfinished steps of a [`TransferPlan`](crate::TransferPlan) (requires runner).
+    pub async fn resume_transfer(
+        &self,
+        plan: &mut crate::TransferPlan,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        self.common.resume_transfer(plan).await
+    }
+
     /// Plan a direct transfer without pathfinding.
     pub async fn plan_direct_transfer(
         &self,
@@ -529,7 +1089,7 @@ mod tests {
     use alloy_sol_types::SolCall;
     use async_trait::async_trait;
     use circles_profiles::Profiles;
-    use circles_types::{AvatarType, CirclesConfig};
+    use circles_types::{AvatarType, CirclesConfig, Timeouts};
     use std::sync::Mutex;
 
     const TEST_CID: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
@@ -564,8 +1124,11 @@ mod tests {
 
     fn dummy_config() -> CirclesConfig {
         CirclesConfig {
+            chain_id: 100,
             circles_rpc_url: "https://rpc.example.com".into(),
             chain_rpc_url: None,
+            replica_rpc_url: None,
+            archive_rpc_url: None,
             pathfinder_url: Some("https://pathfinder.example.com".into()),
             profile_service_url: Some("https://profiles.example.com".into()),
             referrals_service_url: None,
@@ -581,6 +1144,7 @@ mod tests {
             invitation_farm_address: Address::repeat_byte(0x0a),
             referrals_module_address: Address::repeat_byte(0x0b),
             invitation_module_address: Address::repeat_byte(0x0c),
+            timeouts: Timeouts::default(),
         }
     }
 
@@ -623,6 +1187,131 @@ mod tests {
         (avatar, runner)
     }
 
+    fn position(byte: u8, amount: u64) -> TreasuryCollateral {
+        TreasuryCollateral {
+            avatar: Address::repeat_byte(byte),
+            amount: U256::from(amount),
+        }
+    }
+
+    #[test]
+    fn redemption_quote_takes_largest_positions_first() {
+        let collateral = vec![position(0x01, 50), position(0x02, 30), position(0x03, 20)];
+
+        let quote = quote_redemption(&collateral, U256::from(70u64), None);
+        assert!(quote.is_complete());
+        assert_eq!(
+            quote.collateral,
+            vec![position(0x01, 50), position(0x02, 20)]
+        );
+
+        let short = quote_redemption(&collateral, U256::from(500u64), None);
+        assert!(!short.is_complete());
+        assert_eq!(short.redeemable, U256::from(100u64));
+        assert_eq!(short.collateral.len(), 3);
+    }
+
+    #[test]
+    fn redemption_quote_respects_eligible_collateral() {
+        let collateral = vec![position(0x01, 50), position(0x02, 30), position(0x03, 20)];
+        let eligible: HashSet<Address> = [Address::repeat_byte(0x02), Address::repeat_byte(0x03)]
+            .into_iter()
+            .collect();
+
+        let quote = quote_redemption(&collateral, U256::from(40u64), Some(&eligible));
+        assert!(quote.is_complete());
+        assert_eq!(
+            quote.collateral,
+            vec![position(0x02, 30), position(0x03, 10)]
+        );
+    }
+
+    #[test]
+    fn join_eligibility_names_blocking_conditions_in_order() {
+        let check = |byte: u8, outcome: ConditionOutcome| ConditionCheck {
+            condition: Address::repeat_byte(byte),
+            outcome,
+        };
+        let mut eligibility = JoinEligibility {
+            avatar: Address::repeat_byte(0x01),
+            already_member: false,
+            checks: vec![check(0x0a, ConditionOutcome::Passed)],
+        };
+        assert!(eligibility.can_join());
+
+        eligibility.checks.extend([
+            check(0x0b, ConditionOutcome::Errored("execution reverted".into())),
+            check(0x0c, ConditionOutcome::Failed),
+        ]);
+        assert!(!eligibility.can_join());
+        let blocking: Vec<_> = eligibility
+            .blocking()
+            .map(|check| check.condition)
+            .collect();
+        assert_eq!(
+            blocking,
+            vec![Address::repeat_byte(0x0b), Address::repeat_byte(0x0c)]
+        );
+    }
+
+    #[test]
+    fn mint_policy_check_reports_typed_violations() {
+        let member = Address::repeat_byte(0x01);
+        let stranger = Address::repeat_byte(0x02);
+        let policy = GroupMintPolicy {
+            policy: Address::repeat_byte(0x04),
+            kind: MintPolicyKind::Base,
+            group_policy: Address::repeat_byte(0x04),
+            mint_handler: Address::repeat_byte(0x05),
+            treasury: Address::repeat_byte(0x06),
+            service: Address::repeat_byte(0x07),
+            fee_collection: Address::repeat_byte(0x08),
+            membership_conditions: Vec::new(),
+            max_conditions: U256::from(10u64),
+            accepted_collateral: [member].into_iter().collect(),
+        };
+
+        assert_eq!(policy.check(&[(member, U256::from(5u64))]), Ok(()));
+        assert_eq!(policy.check(&[]), Err(MintPolicyViolation::NoCollateral));
+        assert_eq!(
+            policy.check(&[(member, U256::ZERO)]),
+            Err(MintPolicyViolation::ZeroAmount { collateral: member })
+        );
+        let untrusted = policy
+            .check(&[(member, U256::from(1u64)), (stranger, U256::from(1u64))])
+            .unwrap_err();
+        assert_eq!(
+            untrusted,
+            MintPolicyViolation::UntrustedCollateral {
+                collateral: stranger
+            }
+        );
+        assert_eq!(
+            SdkError::from(untrusted).kind(),
+            circles_types::ErrorKind::InvalidInput
+        );
+    }
+
+    #[test]
+    fn policy_reverts_reject_but_transport_failures_propagate() {
+        use alloy_provider::transport::{RpcError, TransportErrorKind};
+
+        assert!(policy_verdict(Ok(true)).unwrap());
+        let reverted = RpcError::ErrorResp(
+            serde_json::from_value(serde_json::json!({
+                "code": 3,
+                "message": "execution reverted"
+            }))
+            .unwrap(),
+        );
+        assert!(!policy_verdict(Err(alloy_contract::Error::TransportError(reverted))).unwrap());
+        let unreachable = TransportErrorKind::custom_str("connection refused");
+        assert!(matches!(
+            policy_verdict(Err(alloy_contract::Error::TransportError(unreachable))),
+            Err(SdkError::Contract(_))
+        ));
+    }
+
     #[tokio::test]
     async fn base_group_write_helpers_encode_expected_calls() {
         let (avatar, runner) = test_avatar();
@@ -660,7 +1349,7 @@ mod tests {
         assert_eq!(
             &sent[0][0].data[..4],
             &BaseGroup::updateMetadataDigestCall {
-                _metadataDigest: cid_v0_to_digest(TEST_CID).expect("cid"),
+                _metadataDigest: cid_to_digest(TEST_CID).expect("cid"),
             }
             .abi_encode()[..4]
         );
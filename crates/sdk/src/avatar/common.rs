@@ -1,7 +1,10 @@
+use crate::trust_list::{TrustImportPlan, TrustImportPolicy, TrustList};
 #[cfg(feature = "ws")]
 use crate::ws;
-use crate::{ContractRunner, Core, PreparedTransaction, Profile, SdkError, call_to_tx};
-use alloy_primitives::{Address, Bytes, U256};
+use crate::{
+    ContractRunner, Core, PreparedTransaction, Profile, SdkError, TransferPlan, call_to_tx,
+};
+use alloy_primitives::{Address, Bytes, U256, aliases::U96};
 use alloy_sol_types::sol;
 use circles_abis::{DemurrageCircles, HubV2, InflationaryCircles};
 use circles_profiles::Profiles;
@@ -11,12 +14,14 @@ use circles_rpc::{CirclesRpc, PagedQuery};
 use circles_transfers::TransferBuilder;
 use circles_types::{
     AdvancedTransferOptions, AggregatedTrustRelation, Balance, PathfindingResult, SortOrder,
-    TokenBalanceResponse, TransactionHistoryRow, TrustRelation, TrustRelationType,
+    TokenBalanceResponse, TotalBalance, TransactionHistoryRow, TrustRelation, TrustRelationType,
 };
 #[cfg(feature = "ws")]
 use circles_types::{CirclesEvent, Filter};
+use circles_utils::time::unix_now;
 #[cfg(feature = "ws")]
 use serde_json::json;
+use std::collections::HashSet;
 use std::sync::Arc;
 
 sol! {
@@ -111,6 +116,64 @@ fn build_unwrap_inflation_erc20_tx(wrapper: Address, amount: U256) -> PreparedTr
     call_to_tx(wrapper, call, None)
 }
 
+/// Trust changes needed to move an avatar's outgoing trust list to a target list.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustDiff {
+    /// Avatars in the target list that are not trusted yet.
+    pub add: Vec<Address>,
+    /// Currently trusted avatars missing from the target list.
+    pub remove: Vec<Address>,
+}
+
+impl TrustDiff {
+    /// Compare the current outgoing trust list against the desired one.
+    ///
+    /// Output order follows the input lists; duplicates are ignored.
+    pub fn compute(current: &[Address], target: &[Address]) -> Self {
+        let current_set: HashSet<Address> = current.iter().copied().collect();
+        let target_set: HashSet<Address> = target.iter().copied().collect();
+        let mut seen = HashSet::new();
+        let add = target
+            .iter()
+            .copied()
+            .filter(|addr| !current_set.contains(addr) && seen.insert(*addr))
+            .collect();
+        let remove = current
+            .iter()
+            .copied()
+            .filter(|addr| !target_set.contains(addr) && seen.insert(*addr))
+            .collect();
+        Self { add, remove }
+    }
+
+    /// Whether the current list already matches the target.
+    pub fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.remove.is_empty()
+    }
+}
+
+/// Build `trust(address,uint96)` calls for a diff against `trust_contract`.
+///
+/// The hub and base groups share the same `trust` signature, so the hub binding
+/// encodes calls for either.
+fn build_trust_sync_txs(
+    trust_contract: Address,
+    diff: &TrustDiff,
+    expiry: u128,
+) -> Vec<PreparedTransaction> {
+    let adds = diff.add.iter().map(|addr| (*addr, expiry));
+    let removes = diff.remove.iter().map(|addr| (*addr, 0));
+    adds.chain(removes)
+        .map(|(addr, expiry)| {
+            let call = HubV2::trustCall {
+                _trustReceiver: addr,
+                _expiry: U96::from(expiry),
+            };
+            call_to_tx(trust_contract, call, None)
+        })
+        .collect()
+}
+
 /// Shared avatar context and read helpers.
 ///
 /// Most methods are read-only; ones that submit transactions require a runner
@@ -154,6 +217,8 @@ impl CommonAvatar {
     }
 
     /// Get aggregate balance (v1/v2 selectable).
+    #[deprecated(note = "use `total_balances`, which covers both hubs in atto units")]
+    #[allow(deprecated)]
     pub async fn total_balance(
         &self,
         as_time_circles: bool,
@@ -166,6 +231,15 @@ impl CommonAvatar {
             .await?)
     }
 
+    /// Get the aggregate balance on both hubs, in atto units.
+    pub async fn total_balances(&self, as_time_circles: bool) -> Result<TotalBalance, SdkError> {
+        Ok(self
+            .rpc
+            .balance()
+            .get_total_balances(self.address, as_time_circles)
+            .await?)
+    }
+
     /// Get trust relations.
     pub async fn trust_relations(&self) -> Result<Vec<TrustRelation>, SdkError> {
         Ok(self.rpc.trust().get_trust_relations(self.address).await?)
@@ -253,6 +327,7 @@ impl CommonAvatar {
     }
 
     /// Submit transactions via runner (helper).
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.sdk", skip_all, fields(method = "send", avatar = %self.address, txs = txs.len())))]
     pub async fn send(
         &self,
         txs: Vec<PreparedTransaction>,
@@ -261,17 +336,124 @@ impl CommonAvatar {
         Ok(runner.send_transactions(txs).await?)
     }
 
+    /// Diff this avatar's outgoing trust list against `target`.
+    pub async fn trust_diff(&self, target: &[Address]) -> Result<TrustDiff, SdkError> {
+        let current: Vec<Address> = self
+            .trusts()
+            .await?
+            .into_iter()
+            .filter(|rel| {
+                matches!(
+                    rel.relation,
+                    TrustRelationType::Trusts | TrustRelationType::MutuallyTrusts
+                )
+            })
+            .map(|rel| rel.object_avatar)
+            .filter(|addr| *addr != self.address)
+            .collect();
+        let target: Vec<Address> = target
+            .iter()
+            .copied()
+            .filter(|addr| *addr != self.address)
+            .collect();
+        Ok(TrustDiff::compute(&current, &target))
+    }
+
+    /// Plan the minimal set of `trust` calls on `trust_contract` that makes the
+    /// outgoing trust list equal `target`.
+    ///
+    /// New entries are trusted until `expiry`; avatars missing from `target` get
+    /// expiry 0. Entries already trusted are left untouched.
+    pub async fn plan_trust_sync(
+        &self,
+        trust_contract: Address,
+        target: &[Address],
+        expiry: u128,
+    ) -> Result<Vec<PreparedTransaction>, SdkError> {
+        let diff = self.trust_diff(target).await?;
+        Ok(build_trust_sync_txs(trust_contract, &diff, expiry))
+    }
+
+    /// Sync the outgoing trust list to `target` in a single runner batch.
+    ///
+    /// Returns an empty list without touching the runner when nothing changes.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.sdk", skip_all, fields(method = "trust_sync", avatar = %self.address)))]
+    pub async fn trust_sync(
+        &self,
+        trust_contract: Address,
+        target: &[Address],
+        expiry: u128,
+    ) -> Result<Vec<crate::SubmittedTx>, SdkError> {
+        let txs = self.plan_trust_sync(trust_contract, target, expiry).await?;
+        if txs.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.send(txs).await
+    }
+
+    /// Live outgoing trust as a canonical [`TrustList`].
+    pub async fn trust_export(&self) -> Result<TrustList, SdkError> {
+        let relations = self.trust_relations().await?;
+        Ok(TrustList::from_relations(
+            self.address,
+            &relations,
+            unix_now(),
+        ))
+    }
+
+    /// Diff current outgoing trust against `list` under `policy`.
+    ///
+    /// Fails when the list was exported for another avatar.
+    pub async fn plan_trust_import(
+        &self,
+        list: &TrustList,
+        policy: TrustImportPolicy,
+    ) -> Result<TrustImportPlan, SdkError> {
+        if list.avatar != self.address {
+            return Err(SdkError::OperationFailed(format!(
+                "trust list belongs to {}, not {}",
+                list.avatar, self.address
+            )));
+        }
+        let current = self.trust_export().await?;
+        Ok(TrustImportPlan::compute(&current, list, policy, unix_now()))
+    }
+
+    /// Apply `list` through `trust_contract` in a single runner batch.
+    ///
+    /// Returns an empty list without touching the runner when nothing changes.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.sdk", skip_all, fields(method = "trust_import", avatar = %self.address)))]
+    pub async fn trust_import(
+        &self,
+        trust_contract: Address,
+        list: &TrustList,
+        policy: TrustImportPolicy,
+    ) -> Result<Vec<crate::SubmittedTx>, SdkError> {
+        let plan = self.plan_trust_import(list, policy).await?;
+        if plan.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.send(plan.txs(trust_contract)).await
+    }
+
+    /// Transfer builder sharing this avatar's config and pathfinder limits.
+    pub(crate) fn transfer_builder(&self) -> Result<TransferBuilder, SdkError> {
+        Ok(TransferBuilder::new(self.core.config.clone())?
+            .with_pathfinder_config(*self.rpc.pathfinder_config()))
+    }
+
     /// Plan a transfer using the transfer builder (no submit). Returns ordered prepared txs.
     ///
     /// Wrapper handling matches the TS SDK: unwrap inflationary/demurraged as
     /// needed and include re-wrap when static balances are present.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.sdk", skip_all, fields(method = "plan_transfer", avatar = %self.address)))]
     pub async fn plan_transfer(
         &self,
         to: Address,
         amount: U256,
         options: Option<AdvancedTransferOptions>,
     ) -> Result<Vec<PreparedTransaction>, SdkError> {
-        let builder = TransferBuilder::new(self.core.config.clone())?;
+        let builder = self.transfer_builder()?;
         let txs = builder
             .construct_advanced_transfer(self.address, to, amount, options)
             .await?;
@@ -286,6 +468,7 @@ impl CommonAvatar {
     }
 
     /// Plan and execute a transfer using the runner (if present).
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.sdk", skip_all, fields(method = "transfer", avatar = %self.address)))]
     pub async fn transfer(
         &self,
         to: Address,
@@ -296,11 +479,32 @@ impl CommonAvatar {
         self.send(txs).await
     }
 
+    /// Plan a transfer as a resumable [`TransferPlan`] (no submit).
+    pub async fn plan_resumable_transfer(
+        &self,
+        to: Address,
+        amount: U256,
+        options: Option<AdvancedTransferOptions>,
+    ) -> Result<TransferPlan, SdkError> {
+        let txs = self.plan_transfer(to, amount, options).await?;
+        Ok(TransferPlan::new(self.address, txs))
+    }
+
+    /// Send the unfinished steps of `plan` using the runner, skipping steps already applied.
+    pub async fn resume_transfer(
+        &self,
+        plan: &mut TransferPlan,
+    ) -> Result<Vec<crate::SubmittedTx>, SdkError> {
+        let runner = self.runner.clone().ok_or(SdkError::MissingRunner)?;
+        plan.resume(&self.core, runner.as_ref()).await
+    }
+
     /// Plan a direct transfer without pathfinding.
     ///
     /// Mirrors the TS direct-transfer helper:
     /// - human/group tokens use `HubV2.safeTransferFrom`
     /// - wrapped ERC20 tokens use `transfer(address,uint256)`
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.sdk", skip_all, fields(method = "plan_direct_transfer", avatar = %self.address)))]
     pub async fn plan_direct_transfer(
         &self,
         to: Address,
@@ -318,23 +522,14 @@ impl CommonAvatar {
         let token_info = self.rpc.token_info().get_token_info(token).await?;
 
         let tx = match classify_direct_transfer_kind(&token_info.token_type) {
-            Some(DirectTransferKind::Erc1155) => {
-                let token_id = self
-                    .core
-                    .hub_v2()
-                    .toTokenId(token)
-                    .call()
-                    .await
-                    .map_err(|e| SdkError::Contract(e.to_string()))?;
-                build_direct_erc1155_transfer_tx(
-                    self.core.config.v2_hub_address,
-                    self.address,
-                    to,
-                    token_id,
-                    amount,
-                    tx_data.unwrap_or_default(),
-                )
-            }
+            Some(DirectTransferKind::Erc1155) => build_direct_erc1155_transfer_tx(
+                self.core.config.v2_hub_address,
+                self.address,
+                to,
+                address_to_erc1155_token_id(token),
+                amount,
+                tx_data.unwrap_or_default(),
+            ),
             Some(DirectTransferKind::Erc20) => build_direct_erc20_transfer_tx(token, to, amount),
             None => {
                 return Err(SdkError::OperationFailed(format!(
@@ -348,6 +543,7 @@ impl CommonAvatar {
     }
 
     /// Execute a direct transfer using the runner (if present).
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.sdk", skip_all, fields(method = "direct_transfer", avatar = %self.address)))]
     pub async fn direct_transfer(
         &self,
         to: Address,
@@ -516,13 +712,14 @@ impl CommonAvatar {
 
     /// Plan a replenish flow for `token_id`, optionally delivering the final
     /// balance to `receiver` instead of keeping it on this avatar.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.sdk", skip_all, fields(method = "plan_replenish", avatar = %self.address)))]
     pub async fn plan_replenish(
         &self,
         token_id: Address,
         amount: U256,
         receiver: Option<Address>,
     ) -> Result<Vec<PreparedTransaction>, SdkError> {
-        let builder = TransferBuilder::new(self.core.config.clone())?;
+        let builder = self.transfer_builder()?;
         let txs = builder
             .construct_replenish(self.address, token_id, amount, receiver)
             .await?;
@@ -537,6 +734,7 @@ impl CommonAvatar {
     }
 
     /// Plan and execute a replenish flow using the runner (if present).
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.sdk", skip_all, fields(method = "replenish", avatar = %self.address)))]
     pub async fn replenish(
         &self,
         token_id: Address,
@@ -548,12 +746,13 @@ impl CommonAvatar {
     }
 
     /// Plan a TS-style automatic group-token redeem flow for `group`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.sdk", skip_all, fields(method = "plan_group_token_redeem", avatar = %self.address)))]
     pub async fn plan_group_token_redeem(
         &self,
         group: Address,
         amount: U256,
     ) -> Result<Vec<PreparedTransaction>, SdkError> {
-        let builder = TransferBuilder::new(self.core.config.clone())?;
+        let builder = self.transfer_builder()?;
         let txs = builder
             .construct_group_token_redeem(self.address, group, amount)
             .await?;
@@ -568,6 +767,7 @@ impl CommonAvatar {
     }
 
     /// Execute a group-token redeem flow using the runner (if present).
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.sdk", skip_all, fields(method = "group_token_redeem", avatar = %self.address)))]
     pub async fn group_token_redeem(
         &self,
         group: Address,
@@ -578,6 +778,7 @@ impl CommonAvatar {
     }
 
     /// Find a path between this avatar and `to` with a target flow (defaults use_wrapped_balances=true).
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.sdk", skip_all, fields(method = "find_path", avatar = %self.address)))]
     pub async fn find_path(
         &self,
         to: Address,
@@ -594,6 +795,7 @@ impl CommonAvatar {
             simulated_trusts: None,
             max_transfers: None,
             tx_data: None,
+            validate_path: None,
         });
         let params = opts.to_find_path_params(self.address, to, target_flow);
         Ok(self.rpc.pathfinder().find_path(params).await?)
@@ -629,6 +831,17 @@ impl CommonAvatar {
         ws::subscribe_with_retries(ws_url, filter, max_attempts).await
     }
 
+    /// Subscribe through a shared [`ws::WsHub`] connection (defaults to this avatar's address filter).
+    #[cfg(feature = "ws")]
+    pub async fn subscribe_events_shared(
+        &self,
+        hub: &ws::WsHub,
+        filter: Option<serde_json::Value>,
+    ) -> Result<ws::WsHubSubscription, SdkError> {
+        let filt = filter.unwrap_or_else(|| json!({ "address": format!("{:#x}", self.address) }));
+        hub.subscribe(filt).await
+    }
+
     /// Subscribe with retries and optionally fetch HTTP events for a catch-up range.
     /// Returns (catch_up_events, live_subscription).
     #[cfg(feature = "ws")]
@@ -659,6 +872,52 @@ mod tests {
     use alloy_primitives::address;
     use alloy_sol_types::SolCall;
 
+    #[test]
+    fn trust_diff_computes_adds_and_removes() {
+        let a = Address::repeat_byte(0x01);
+        let b = Address::repeat_byte(0x02);
+        let c = Address::repeat_byte(0x03);
+        let d = Address::repeat_byte(0x04);
+
+        let diff = TrustDiff::compute(&[a, b, c], &[b, d, d, c]);
+        assert_eq!(diff.add, vec![d]);
+        assert_eq!(diff.remove, vec![a]);
+        assert!(TrustDiff::compute(&[a, b], &[b, a]).is_empty());
+    }
+
+    #[test]
+    fn trust_sync_txs_trust_adds_and_expire_removes() {
+        let contract = address!("1000000000000000000000000000000000000000");
+        let diff = TrustDiff {
+            add: vec![Address::repeat_byte(0x01)],
+            remove: vec![Address::repeat_byte(0x02)],
+        };
+
+        let txs = build_trust_sync_txs(contract, &diff, 99);
+        assert_eq!(txs.len(), 2);
+        assert!(txs.iter().all(|tx| tx.to == contract));
+        assert_eq!(
+            txs[0].data,
+            Bytes::from(
+                HubV2::trustCall {
+                    _trustReceiver: Address::repeat_byte(0x01),
+                    _expiry: U96::from(99u64),
+                }
+                .abi_encode()
+            )
+        );
+        assert_eq!(
+            txs[1].data,
+            Bytes::from(
+                HubV2::trustCall {
+                    _trustReceiver: Address::repeat_byte(0x02),
+                    _expiry: U96::ZERO,
+                }
+                .abi_encode()
+            )
+        );
+    }
+
     #[test]
     fn direct_transfer_classifies_ts_token_types() {
         assert_eq!(
@@ -1,10 +1,13 @@
-use crate::avatar::common::CommonAvatar;
-use crate::cid_v0_to_digest::cid_v0_to_digest;
+use crate::avatar::common::{CommonAvatar, TrustDiff};
+use crate::cid::cid_to_digest;
 use crate::runner::{PreparedTransaction as RunnerTx, SubmittedTx as RunnerSubmitted};
 use crate::services::referrals::{
     ReferralPreviewList, ReferralPublicListOptions, Referrals, generate_private_key,
     private_key_to_address,
 };
+use crate::services::trust_scheduler::trust_expiry_after_days;
+use crate::snapshot::{AvatarSnapshot, SNAPSHOT_VERSION};
+use crate::trust_list::{TrustImportPlan, TrustImportPolicy, TrustList};
 use crate::{
     ContractRunner, Core, PreparedTransaction, Profile, SdkError, SubmittedTx, call_to_tx,
 };
@@ -15,16 +18,17 @@ use circles_profiles::Profiles;
 #[cfg(feature = "ws")]
 use circles_rpc::events::subscription::CirclesSubscription;
 use circles_rpc::{CirclesRpc, PagedQuery};
-use circles_transfers::TransferBuilder;
 #[cfg(feature = "ws")]
 use circles_types::CirclesEvent;
 use circles_types::{
     AdvancedTransferOptions, AggregatedTrustRelation, AllInvitationsResponse, AtScaleInvitation,
     AvatarInfo, Balance, EscrowInvitation, GroupMembershipRow, GroupQueryParams, GroupRow,
     InvitationOriginResponse, InvitationsFromResponse, InvitedAccountInfo, PathfindingResult,
-    PathfindingTransferStep, SimulatedTrust, SortOrder, TokenBalanceResponse,
+    PathfindingTransferStep, SimulatedTrust, SortOrder, TokenBalanceResponse, TotalBalance,
     TransactionHistoryRow, TrustInvitation, TrustRelation,
 };
+use circles_utils::converter::atto_circles_to_atto_crc;
+use circles_utils::time::unix_now;
 use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 use std::sync::Arc;
@@ -268,7 +272,7 @@ impl HumanAvatar {
     ) -> Result<Vec<PreparedTransaction>, SdkError> {
         let invitation_module = self.common.core.config.invitation_module_address;
         let mut transactions = self.ensure_inviter_setup().await?;
-        let transfer_builder = TransferBuilder::new(self.common.core.config.clone())?;
+        let transfer_builder = self.common.transfer_builder()?;
         let proxy_inviters = self.proxy_inviters().await?;
 
         if let Some(proxy_inviter) = proxy_inviters.first() {
@@ -290,6 +294,7 @@ impl HumanAvatar {
                         }]),
                         max_transfers: None,
                         tx_data: Some(tx_data.clone()),
+                        validate_path: None,
                     }),
                     true,
                 )
@@ -313,6 +318,7 @@ impl HumanAvatar {
                     simulated_trusts: None,
                     max_transfers: None,
                     tx_data: None,
+                    validate_path: None,
                 }),
                 true,
             )
@@ -353,6 +359,8 @@ impl HumanAvatar {
     }
 
     /// Get aggregate balance (v1/v2 selectable).
+    #[deprecated(note = "use `total_balances`, which covers both hubs in atto units")]
+    #[allow(deprecated)]
     pub async fn total_balance(
         &self,
         as_time_circles: bool,
@@ -361,6 +369,11 @@ impl HumanAvatar {
         self.common.total_balance(as_time_circles, use_v2).await
     }
 
+    /// Get the aggregate balance on both hubs, in atto units.
+    pub async fn total_balances(&self, as_time_circles: bool) -> Result<TotalBalance, SdkError> {
+        self.common.total_balances(as_time_circles).await
+    }
+
     /// Get trust relations.
     pub async fn trust_relations(&self) -> Result<Vec<TrustRelation>, SdkError> {
         self.common.trust_relations().await
@@ -403,6 +416,23 @@ impl HumanAvatar {
         self.common.profile(self.info.cid_v0.as_deref()).await
     }
 
+    /// Capture info, v2 balances (atto-circles), trust relations, and profile for offline use.
+    ///
+    /// Restore with [`Sdk::load_snapshot`](crate::Sdk::load_snapshot).
+    pub async fn snapshot(&self) -> Result<AvatarSnapshot, SdkError> {
+        let balances = self.balances(false, true).await?;
+        let trust_relations = self.trust_relations().await?;
+        let profile = self.profile().await?;
+        Ok(AvatarSnapshot {
+            version: SNAPSHOT_VERSION,
+            taken_at: unix_now(),
+            info: self.info.clone(),
+            balances,
+            trust_relations,
+            profile,
+        })
+    }
+
     /// Get transaction history for this avatar using cursor-based pagination.
     pub fn transaction_history(
         &self,
@@ -420,7 +450,7 @@ impl HumanAvatar {
 
     /// Update the on-chain profile CID pointer through NameRegistry (requires runner).
     pub async fn update_profile_metadata(&self, cid: &str) -> Result<Vec<SubmittedTx>, SdkError> {
-        let digest = cid_v0_to_digest(cid)?;
+        let digest = cid_to_digest(cid)?;
         let call = circles_abis::NameRegistry::updateMetadataDigestCall {
             _metadataDigest: digest,
         };
@@ -460,6 +490,68 @@ impl HumanAvatar {
         self.trust_add(avatars, 0).await
     }
 
+    /// Trust one or more avatars for `days` days from now (requires runner).
+    pub async fn trust_add_with_duration(
+        &self,
+        avatars: &[Address],
+        days: u64,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        let expiry = trust_expiry_after_days(unix_now(), days)?;
+        self.trust_add(avatars, expiry).await
+    }
+
+    /// Diff current outgoing trust against `target`.
+    pub async fn trust_diff(&self, target: &[Address]) -> Result<TrustDiff, SdkError> {
+        self.common.trust_diff(target).await
+    }
+
+    /// Plan trust/untrust calls that make outgoing trust equal `target` (no submit).
+    pub async fn plan_trust_sync(
+        &self,
+        target: &[Address],
+        expiry: u128,
+    ) -> Result<Vec<PreparedTransaction>, SdkError> {
+        self.common
+            .plan_trust_sync(self.core.config.v2_hub_address, target, expiry)
+            .await
+    }
+
+    /// Sync outgoing trust to `target` with a minimal batch of `trust` calls (requires runner).
+    pub async fn trust_sync(
+        &self,
+        target: &[Address],
+        expiry: u128,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        self.common
+            .trust_sync(self.core.config.v2_hub_address, target, expiry)
+            .await
+    }
+
+    /// Export live outgoing trust as a canonical list for JSON/CSV files.
+    pub async fn trust_export(&self) -> Result<TrustList, SdkError> {
+        self.common.trust_export().await
+    }
+
+    /// Diff outgoing trust against an imported `list` (no submit).
+    pub async fn plan_trust_import(
+        &self,
+        list: &TrustList,
+        policy: TrustImportPolicy,
+    ) -> Result<TrustImportPlan, SdkError> {
+        self.common.plan_trust_import(list, policy).await
+    }
+
+    /// Apply an imported trust `list` under `policy` in one batch (requires runner).
+    pub async fn trust_import(
+        &self,
+        list: &TrustList,
+        policy: TrustImportPolicy,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        self.common
+            .trust_import(self.core.config.v2_hub_address, list, policy)
+            .await
+    }
+
     #[cfg(feature = "ws")]
     pub async fn subscribe_events_ws(
         &self,
@@ -469,6 +561,16 @@ impl HumanAvatar {
         self.common.subscribe_events_ws(ws_url, filter).await
     }
 
+    /// Subscribe through a shared [`crate::ws::WsHub`] connection.
+    #[cfg(feature = "ws")]
+    pub async fn subscribe_events_shared(
+        &self,
+        hub: &crate::ws::WsHub,
+        filter: Option<serde_json::Value>,
+    ) -> Result<crate::ws::WsHubSubscription, SdkError> {
+        self.common.subscribe_events_shared(hub, filter).await
+    }
+
     #[cfg(feature = "ws")]
     pub async fn subscribe_events_ws_with_retries(
         &self,
@@ -501,13 +603,26 @@ impl HumanAvatar {
             .await
     }
 
-    /// Plan a transfer without submitting.
+    /// Whether this avatar still lives on Hub v1 (has a v1 token and never moved to v2).
+    pub fn is_v1_only(&self) -> bool {
+        self.info.version < 2 && self.info.has_v1
+    }
+
+    /// Plan a transfer of `amount` demurraged atto-Circles without submitting.
+    ///
+    /// Avatars that are [v1-only](Self::is_v1_only) are routed to
+    /// [`plan_v1_transfer`](Self::plan_v1_transfer) with `amount` converted to v1
+    /// atto-CRC at the current time; `options` do not apply there.
     pub async fn plan_transfer(
         &self,
         to: Address,
         amount: U256,
         options: Option<AdvancedTransferOptions>,
     ) -> Result<Vec<PreparedTransaction>, SdkError> {
+        if self.is_v1_only() {
+            let v1_amount = atto_circles_to_atto_crc(amount, unix_now());
+            return self.plan_v1_transfer(to, v1_amount).await;
+        }
         self.common.plan_transfer(to, amount, options).await
     }
 
@@ -518,7 +633,44 @@ impl HumanAvatar {
         amount: U256,
         options: Option<AdvancedTransferOptions>,
     ) -> Result<Vec<SubmittedTx>, SdkError> {
-        self.common.transfer(to, amount, options).await
+        let txs = self.plan_transfer(to, amount, options).await?;
+        self.common.send(txs).await
+    }
+
+    /// Plan a Hub v1 `transferThrough` of `amount` v1 atto-CRC of this avatar's own v1
+    /// token to `to`.
+    ///
+    /// `to` must trust this avatar on v1; the v1 send limit is checked before planning.
+    pub async fn plan_v1_transfer(
+        &self,
+        to: Address,
+        amount: U256,
+    ) -> Result<Vec<PreparedTransaction>, SdkError> {
+        let txs = self
+            .common
+            .transfer_builder()?
+            .construct_v1_transfer(self.address, to, amount)
+            .await?;
+        Ok(transfer_txs_to_prepared(txs))
+    }
+
+    /// Plan a transfer as a resumable [`TransferPlan`](crate::TransferPlan).
+    pub async fn plan_resumable_transfer(
+        &self,
+        to: Address,
+        amount: U256,
+        options: Option<AdvancedTransferOptions>,
+    ) -> Result<crate::TransferPlan, SdkError> {
+        let txs = self.plan_transfer(to, amount, options).await?;
+        Ok(crate::TransferPlan::new(self.address, txs))
+    }
+
+    /// Send the unfinished steps of a [`TransferPlan`](crate::TransferPlan) (requires runner).
+    pub async fn resume_transfer(
+        &self,
+        plan: &mut crate::TransferPlan,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        self.common.resume_transfer(plan).await
     }
 
     /// Plan a direct transfer without pathfinding.
@@ -708,6 +860,7 @@ impl HumanAvatar {
             simulated_trusts: None,
             max_transfers: None,
             tx_data: None,
+            validate_path: None,
         });
         if opts.use_wrapped_balances.is_none() {
             opts.use_wrapped_balances = Some(true);
@@ -771,6 +924,7 @@ impl HumanAvatar {
                 simulated_trusts: None,
                 max_transfers: None,
                 tx_data: None,
+                validate_path: None,
             }),
         )
         .await
@@ -826,6 +980,7 @@ impl HumanAvatar {
                     simulated_trusts: None,
                     max_transfers: None,
                     tx_data: None,
+                    validate_path: None,
                 }),
             )
             .await?
@@ -1272,6 +1427,7 @@ impl HumanAvatar {
                     }]),
                     max_transfers: None,
                     tx_data: None,
+                    validate_path: None,
                 }),
             )
             .await?;
@@ -1355,6 +1511,7 @@ impl HumanAvatar {
                     }]),
                     max_transfers: None,
                     tx_data: None,
+                    validate_path: None,
                 }),
             )
             .await?;
@@ -1396,6 +1553,7 @@ impl HumanAvatar {
                     simulated_trusts: None,
                     max_transfers: None,
                     tx_data: None,
+                    validate_path: None,
                 }),
             )
             .await?;
@@ -1603,7 +1761,7 @@ mod tests {
     use alloy_primitives::{Bytes, TxHash, address};
     use async_trait::async_trait;
     use circles_profiles::Profiles;
-    use circles_types::{AvatarType, CirclesConfig};
+    use circles_types::{AvatarType, CirclesConfig, Timeouts};
     use std::sync::Mutex;
 
     const TEST_CID: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
@@ -1638,8 +1796,11 @@ mod tests {
 
     fn dummy_config() -> CirclesConfig {
         CirclesConfig {
+            chain_id: 100,
             circles_rpc_url: "https://rpc.example.com".into(),
             chain_rpc_url: None,
+            replica_rpc_url: None,
+            archive_rpc_url: None,
             pathfinder_url: Some("https://pathfinder.example.com".into()),
             profile_service_url: Some("https://profiles.example.com".into()),
             referrals_service_url: None,
@@ -1655,6 +1816,7 @@ mod tests {
             invitation_farm_address: Address::repeat_byte(0x0a),
             referrals_module_address: Address::repeat_byte(0x0b),
             invitation_module_address: Address::repeat_byte(0x0c),
+            timeouts: Timeouts::default(),
         }
     }
 
@@ -1710,6 +1872,62 @@ mod tests {
         (avatar, config)
     }
 
+    #[test]
+    fn avatar_enum_accessors_and_conversions() {
+        let (human, _) = test_avatar_without_runner();
+        let avatar = crate::Avatar::from(human);
+        assert_eq!(avatar.address(), Address::repeat_byte(0xaa));
+        assert!(avatar.info().is_human);
+        assert!(avatar.as_human().is_some());
+        assert!(avatar.as_organisation().is_none());
+        assert!(avatar.as_group().is_none());
+
+        let avatar = match crate::BaseGroupAvatar::try_from(avatar) {
+            Ok(_) => panic!("a human is not a group"),
+            Err(original) => original,
+        };
+        let Ok(human) = HumanAvatar::try_from(avatar) else {
+            panic!("human variant");
+        };
+        assert_eq!(human.address, Address::repeat_byte(0xaa));
+    }
+
+    #[tokio::test]
+    async fn avatar_ops_dispatches_through_the_enum() {
+        use crate::avatar::AvatarOps;
+
+        async fn trust_one(
+            avatar: &impl AvatarOps,
+            other: Address,
+        ) -> Result<Vec<SubmittedTx>, SdkError> {
+            avatar.trust_add(&[other], 1).await
+        }
+
+        let (human, runner, config) = test_avatar();
+        let avatar = crate::Avatar::Human(human);
+        assert_eq!(AvatarOps::address(&avatar), Address::repeat_byte(0xaa));
+        assert_eq!(avatar.as_ops().info().avatar, Address::repeat_byte(0xaa));
+
+        trust_one(&avatar, Address::repeat_byte(0xbb))
+            .await
+            .expect("trust through trait");
+        let sent = runner.sent.lock().expect("lock");
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0][0].to, config.v2_hub_address);
+    }
+
+    #[test]
+    fn v1_only_requires_v1_token_and_no_v2_registration() {
+        let (mut avatar, _) = test_avatar_without_runner();
+        assert!(!avatar.is_v1_only());
+
+        avatar.info.has_v1 = true;
+        assert!(!avatar.is_v1_only(), "migrated avatars keep using v2");
+
+        avatar.info.version = 1;
+        assert!(avatar.is_v1_only());
+    }
+
     #[test]
     fn referral_payload_encodes() {
         let signers = vec![address!("1000000000000000000000000000000000000001")];
@@ -2093,7 +2311,7 @@ mod tests {
         assert_eq!(
             &sent[0][0].data[..4],
             &circles_abis::NameRegistry::updateMetadataDigestCall {
-                _metadataDigest: cid_v0_to_digest(TEST_CID).expect("cid"),
+                _metadataDigest: cid_to_digest(TEST_CID).expect("cid"),
             }
             .abi_encode()[..4]
         );
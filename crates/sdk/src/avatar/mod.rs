@@ -1,8 +1,14 @@
 pub mod base_group;
 pub mod common;
 pub mod human;
+pub mod ops;
 pub mod organisation;
 
-pub use base_group::BaseGroupAvatar;
+pub use base_group::{
+    BaseGroupAvatar, ConditionCheck, ConditionOutcome, GroupMintPolicy, GroupTreasury,
+    JoinEligibility, MintPolicyKind, MintPolicyViolation, RedemptionQuote, TreasuryCollateral,
+};
+pub use common::TrustDiff;
 pub use human::HumanAvatar;
-pub use organisation::OrganisationAvatar;
+pub use ops::AvatarOps;
+pub use organisation::{EscrowedInvitation, OrganisationAvatar};
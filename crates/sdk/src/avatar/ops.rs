@@ -0,0 +1,323 @@
+//! Operations every avatar kind supports, for code that should not care which kind it has.
+
+use crate::avatar::{BaseGroupAvatar, HumanAvatar, OrganisationAvatar};
+use crate::{Avatar, PreparedTransaction, Profile, SdkError, SubmittedTx};
+use alloy_primitives::{Address, U256};
+use async_trait::async_trait;
+use circles_rpc::PagedQuery;
+#[cfg(feature = "ws")]
+use circles_rpc::events::subscription::CirclesSubscription;
+#[cfg(feature = "ws")]
+use circles_types::CirclesEvent;
+use circles_types::{
+    AdvancedTransferOptions, AggregatedTrustRelation, AvatarInfo, Balance, PathfindingResult,
+    SortOrder, TokenBalanceResponse, TotalBalance, TransactionHistoryRow, TrustRelation,
+};
+
+/// Balances, trust, transfers, profile, and subscriptions shared by [`HumanAvatar`],
+/// [`OrganisationAvatar`], [`BaseGroupAvatar`], and the [`Avatar`] enum.
+///
+/// Each method forwards to the inherent method of the same name; kind-specific operations
+/// (minting, invitations, group administration) stay on the concrete types.
+#[async_trait]
+pub trait AvatarOps: Send + Sync {
+    fn address(&self) -> Address;
+
+    fn info(&self) -> &AvatarInfo;
+
+    async fn balances(
+        &self,
+        as_time_circles: bool,
+        use_v2: bool,
+    ) -> Result<Vec<TokenBalanceResponse>, SdkError>;
+
+    #[deprecated(note = "use `total_balances`, which covers both hubs in atto units")]
+    async fn total_balance(&self, as_time_circles: bool, use_v2: bool)
+    -> Result<Balance, SdkError>;
+
+    async fn total_balances(&self, as_time_circles: bool) -> Result<TotalBalance, SdkError>;
+
+    async fn trust_relations(&self) -> Result<Vec<TrustRelation>, SdkError>;
+
+    async fn aggregated_trust_relations(&self) -> Result<Vec<AggregatedTrustRelation>, SdkError>;
+
+    async fn trusts(&self) -> Result<Vec<AggregatedTrustRelation>, SdkError>;
+
+    async fn trusted_by(&self) -> Result<Vec<AggregatedTrustRelation>, SdkError>;
+
+    async fn mutual_trusts(&self) -> Result<Vec<AggregatedTrustRelation>, SdkError>;
+
+    async fn is_trusting(&self, other_avatar: Address) -> Result<bool, SdkError>;
+
+    async fn is_trusted_by(&self, other_avatar: Address) -> Result<bool, SdkError>;
+
+    async fn trust_add(
+        &self,
+        avatars: &[Address],
+        expiry: u128,
+    ) -> Result<Vec<SubmittedTx>, SdkError>;
+
+    async fn trust_remove(&self, avatars: &[Address]) -> Result<Vec<SubmittedTx>, SdkError>;
+
+    async fn trust_add_with_duration(
+        &self,
+        avatars: &[Address],
+        days: u64,
+    ) -> Result<Vec<SubmittedTx>, SdkError>;
+
+    async fn profile(&self) -> Result<Option<Profile>, SdkError>;
+
+    async fn update_profile(&self, profile: &Profile) -> Result<Vec<SubmittedTx>, SdkError>;
+
+    fn transaction_history(
+        &self,
+        limit: u32,
+        sort_order: SortOrder,
+    ) -> PagedQuery<TransactionHistoryRow>;
+
+    async fn find_path(
+        &self,
+        to: Address,
+        target_flow: U256,
+        options: Option<AdvancedTransferOptions>,
+    ) -> Result<PathfindingResult, SdkError>;
+
+    async fn max_flow_to(
+        &self,
+        to: Address,
+        options: Option<AdvancedTransferOptions>,
+    ) -> Result<PathfindingResult, SdkError>;
+
+    async fn plan_transfer(
+        &self,
+        to: Address,
+        amount: U256,
+        options: Option<AdvancedTransferOptions>,
+    ) -> Result<Vec<PreparedTransaction>, SdkError>;
+
+    async fn transfer(
+        &self,
+        to: Address,
+        amount: U256,
+        options: Option<AdvancedTransferOptions>,
+    ) -> Result<Vec<SubmittedTx>, SdkError>;
+
+    #[cfg(feature = "ws")]
+    async fn subscribe_events_ws(
+        &self,
+        ws_url: &str,
+        filter: Option<serde_json::Value>,
+    ) -> Result<CirclesSubscription<CirclesEvent>, SdkError>;
+}
+
+/// Implements [`AvatarOps`] for `$ty` with `$avatar` bound to the receiver: `$via` is the
+/// value the async methods are called on, `$address` and `$info` read the identity.
+macro_rules! avatar_ops {
+    ($ty:ty, |$avatar:ident| $via:expr, $address:expr, $info:expr) => {
+        #[async_trait]
+        impl AvatarOps for $ty {
+            fn address(&self) -> Address {
+                let $avatar = self;
+                $address
+            }
+
+            fn info(&self) -> &AvatarInfo {
+                let $avatar = self;
+                $info
+            }
+
+            async fn balances(
+                &self,
+                as_time_circles: bool,
+                use_v2: bool,
+            ) -> Result<Vec<TokenBalanceResponse>, SdkError> {
+                let $avatar = self;
+                $via.balances(as_time_circles, use_v2).await
+            }
+
+            #[allow(deprecated)]
+            async fn total_balance(
+                &self,
+                as_time_circles: bool,
+                use_v2: bool,
+            ) -> Result<Balance, SdkError> {
+                let $avatar = self;
+                $via.total_balance(as_time_circles, use_v2).await
+            }
+
+            async fn total_balances(
+                &self,
+                as_time_circles: bool,
+            ) -> Result<TotalBalance, SdkError> {
+                let $avatar = self;
+                $via.total_balances(as_time_circles).await
+            }
+
+            async fn trust_relations(&self) -> Result<Vec<TrustRelation>, SdkError> {
+                let $avatar = self;
+                $via.trust_relations().await
+            }
+
+            async fn aggregated_trust_relations(
+                &self,
+            ) -> Result<Vec<AggregatedTrustRelation>, SdkError> {
+                let $avatar = self;
+                $via.aggregated_trust_relations().await
+            }
+
+            async fn trusts(&self) -> Result<Vec<AggregatedTrustRelation>, SdkError> {
+                let $avatar = self;
+                $via.trusts().await
+            }
+
+            async fn trusted_by(&self) -> Result<Vec<AggregatedTrustRelation>, SdkError> {
+                let $avatar = self;
+                $via.trusted_by().await
+            }
+
+            async fn mutual_trusts(&self) -> Result<Vec<AggregatedTrustRelation>, SdkError> {
+                let $avatar = self;
+                $via.mutual_trusts().await
+            }
+
+            async fn is_trusting(&self, other_avatar: Address) -> Result<bool, SdkError> {
+                let $avatar = self;
+                $via.is_trusting(other_avatar).await
+            }
+
+            async fn is_trusted_by(&self, other_avatar: Address) -> Result<bool, SdkError> {
+                let $avatar = self;
+                $via.is_trusted_by(other_avatar).await
+            }
+
+            async fn trust_add(
+                &self,
+                avatars: &[Address],
+                expiry: u128,
+            ) -> Result<Vec<SubmittedTx>, SdkError> {
+                let $avatar = self;
+                $via.trust_add(avatars, expiry).await
+            }
+
+            async fn trust_remove(
+                &self,
+                avatars: &[Address],
+            ) -> Result<Vec<SubmittedTx>, SdkError> {
+                let $avatar = self;
+                $via.trust_remove(avatars).await
+            }
+
+            async fn trust_add_with_duration(
+                &self,
+                avatars: &[Address],
+                days: u64,
+            ) -> Result<Vec<SubmittedTx>, SdkError> {
+                let $avatar = self;
+                $via.trust_add_with_duration(avatars, days).await
+            }
+
+            async fn profile(&self) -> Result<Option<Profile>, SdkError> {
+                let $avatar = self;
+                $via.profile().await
+            }
+
+            async fn update_profile(
+                &self,
+                profile: &Profile,
+            ) -> Result<Vec<SubmittedTx>, SdkError> {
+                let $avatar = self;
+                $via.update_profile(profile).await
+            }
+
+            fn transaction_history(
+                &self,
+                limit: u32,
+                sort_order: SortOrder,
+            ) -> PagedQuery<TransactionHistoryRow> {
+                let $avatar = self;
+                $via.transaction_history(limit, sort_order)
+            }
+
+            async fn find_path(
+                &self,
+                to: Address,
+                target_flow: U256,
+                options: Option<AdvancedTransferOptions>,
+            ) -> Result<PathfindingResult, SdkError> {
+                let $avatar = self;
+                $via.find_path(to, target_flow, options).await
+            }
+
+            async fn max_flow_to(
+                &self,
+                to: Address,
+                options: Option<AdvancedTransferOptions>,
+            ) -> Result<PathfindingResult, SdkError> {
+                let $avatar = self;
+                $via.max_flow_to(to, options).await
+            }
+
+            async fn plan_transfer(
+                &self,
+                to: Address,
+                amount: U256,
+                options: Option<AdvancedTransferOptions>,
+            ) -> Result<Vec<PreparedTransaction>, SdkError> {
+                let $avatar = self;
+                $via.plan_transfer(to, amount, options).await
+            }
+
+            async fn transfer(
+                &self,
+                to: Address,
+                amount: U256,
+                options: Option<AdvancedTransferOptions>,
+            ) -> Result<Vec<SubmittedTx>, SdkError> {
+                let $avatar = self;
+                $via.transfer(to, amount, options).await
+            }
+
+            #[cfg(feature = "ws")]
+            async fn subscribe_events_ws(
+                &self,
+                ws_url: &str,
+                filter: Option<serde_json::Value>,
+            ) -> Result<CirclesSubscription<CirclesEvent>, SdkError> {
+                let $avatar = self;
+                $via.subscribe_events_ws(ws_url, filter).await
+            }
+        }
+    };
+}
+
+// The concrete types call their inherent methods, which take precedence over the trait's.
+avatar_ops!(HumanAvatar, |avatar| avatar, avatar.address, &avatar.info);
+avatar_ops!(
+    OrganisationAvatar,
+    |avatar| avatar,
+    avatar.address,
+    &avatar.info
+);
+avatar_ops!(
+    BaseGroupAvatar,
+    |avatar| avatar,
+    avatar.address,
+    &avatar.info
+);
+avatar_ops!(
+    Avatar,
+    |avatar| avatar.as_ops(),
+    avatar.address(),
+    avatar.info()
+);
+
+impl Avatar {
+    /// This avatar as a trait object over its concrete kind.
+    pub fn as_ops(&self) -> &dyn AvatarOps {
+        match self {
+            Avatar::Human(avatar) => avatar,
+            Avatar::Organisation(avatar) => avatar,
+            Avatar::Group(avatar) => avatar,
+        }
+    }
+}
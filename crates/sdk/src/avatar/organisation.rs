@@ -1,10 +1,13 @@
-use crate::avatar::common::CommonAvatar;
-use crate::cid_v0_to_digest::cid_v0_to_digest;
+use crate::avatar::common::{CommonAvatar, TrustDiff};
+use crate::cid::cid_to_digest;
+use crate::services::trust_scheduler::trust_expiry_after_days;
+use crate::trust_list::{TrustImportPlan, TrustImportPolicy, TrustList};
 use crate::{
     ContractRunner, Core, PreparedTransaction, Profile, SdkError, SubmittedTx, call_to_tx,
 };
 use alloy_primitives::{Address, Bytes, U256, aliases::U96};
-use circles_abis::HubV2;
+use alloy_sol_types::SolValue;
+use circles_abis::{HubV2, InvitationEscrow};
 use circles_profiles::Profiles;
 #[cfg(feature = "ws")]
 use circles_rpc::events::subscription::CirclesSubscription;
@@ -12,11 +15,30 @@ use circles_rpc::{CirclesRpc, PagedQuery};
 #[cfg(feature = "ws")]
 use circles_types::CirclesEvent;
 use circles_types::{
-    AdvancedTransferOptions, AggregatedTrustRelation, AvatarInfo, Balance, PathfindingResult,
-    SortOrder, TokenBalanceResponse, TransactionHistoryRow, TrustRelation,
+    AdvancedTransferOptions, AggregatedTrustRelation, AvatarInfo, Balance, EscrowedAmountAndDays,
+    PathfindingResult, SortOrder, TokenBalanceResponse, TotalBalance, TransactionHistoryRow,
+    TrustRelation,
 };
+use circles_utils::time::unix_now;
 use std::sync::Arc;
 
+/// One invitation currently escrowed by an organisation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscrowedInvitation {
+    /// Invitee the escrow is reserved for.
+    pub invitee: Address,
+    /// Escrowed CRC amount and days elapsed since the deposit.
+    pub escrow: EscrowedAmountAndDays,
+}
+
+fn build_revoke_invitation_tx(escrow: Address, invitee: Address) -> PreparedTransaction {
+    call_to_tx(
+        escrow,
+        InvitationEscrow::revokeInvitationCall { invitee },
+        None,
+    )
+}
+
 /// Top-level avatar enum variant: organisation.
 pub struct OrganisationAvatar {
     /// Avatar address on-chain.
@@ -42,6 +64,8 @@ impl OrganisationAvatar {
     }
 
     /// Get aggregate balance (v1/v2 selectable).
+    #[deprecated(note = "use `total_balances`, which covers both hubs in atto units")]
+    #[allow(deprecated)]
     pub async fn total_balance(
         &self,
         as_time_circles: bool,
@@ -50,6 +74,11 @@ impl OrganisationAvatar {
         self.common.total_balance(as_time_circles, use_v2).await
     }
 
+    /// Get the aggregate balance on both hubs, in atto units.
+    pub async fn total_balances(&self, as_time_circles: bool) -> Result<TotalBalance, SdkError> {
+        self.common.total_balances(as_time_circles).await
+    }
+
     /// Get trust relations.
     pub async fn trust_relations(&self) -> Result<Vec<TrustRelation>, SdkError> {
         self.common.trust_relations().await
@@ -109,7 +138,7 @@ impl OrganisationAvatar {
 
     /// Update the on-chain profile CID pointer through NameRegistry (requires runner).
     pub async fn update_profile_metadata(&self, cid: &str) -> Result<Vec<SubmittedTx>, SdkError> {
-        let digest = cid_v0_to_digest(cid)?;
+        let digest = cid_to_digest(cid)?;
         let call = circles_abis::NameRegistry::updateMetadataDigestCall {
             _metadataDigest: digest,
         };
@@ -149,6 +178,68 @@ impl OrganisationAvatar {
         self.trust_add(avatars, 0).await
     }
 
+    /// Trust one or more avatars for `days` days from now (requires runner).
+    pub async fn trust_add_with_duration(
+        &self,
+        avatars: &[Address],
+        days: u64,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        let expiry = trust_expiry_after_days(unix_now(), days)?;
+        self.trust_add(avatars, expiry).await
+    }
+
+    /// Diff current outgoing trust against `target`.
+    pub async fn trust_diff(&self, target: &[Address]) -> Result<TrustDiff, SdkError> {
+        self.common.trust_diff(target).await
+    }
+
+    /// Plan trust/untrust calls that make outgoing trust equal `target` (no submit).
+    pub async fn plan_trust_sync(
+        &self,
+        target: &[Address],
+        expiry: u128,
+    ) -> Result<Vec<PreparedTransaction>, SdkError> {
+        self.common
+            .plan_trust_sync(self.core.config.v2_hub_address, target, expiry)
+            .await
+    }
+
+    /// Sync outgoing trust to `target` with a minimal batch of `trust` calls (requires runner).
+    pub async fn trust_sync(
+        &self,
+        target: &[Address],
+        expiry: u128,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        self.common
+            .trust_sync(self.core.config.v2_hub_address, target, expiry)
+            .await
+    }
+
+    /// Export live outgoing trust as a canonical list for JSON/CSV files.
+    pub async fn trust_export(&self) -> Result<TrustList, SdkError> {
+        self.common.trust_export().await
+    }
+
+    /// Diff outgoing trust against an imported `list` (no submit).
+    pub async fn plan_trust_import(
+        &self,
+        list: &TrustList,
+        policy: TrustImportPolicy,
+    ) -> Result<TrustImportPlan, SdkError> {
+        self.common.plan_trust_import(list, policy).await
+    }
+
+    /// Apply an imported trust `list` under `policy` in one batch (requires runner).
+    pub async fn trust_import(
+        &self,
+        list: &TrustList,
+        policy: TrustImportPolicy,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        self.common
+            .trust_import(self.core.config.v2_hub_address, list, policy)
+            .await
+    }
+
     #[cfg(feature = "ws")]
     pub async fn subscribe_events_ws(
         &self,
@@ -158,6 +249,16 @@ impl OrganisationAvatar {
         self.common.subscribe_events_ws(ws_url, filter).await
     }
 
+    /// Subscribe through a shared [`crate::ws::WsHub`] connection.
+    #[cfg(feature = "ws")]
+    pub async fn subscribe_events_shared(
+        &self,
+        hub: &crate::ws::WsHub,
+        filter: Option<serde_json::Value>,
+    ) -> Result<crate::ws::WsHubSubscription, SdkError> {
+        self.common.subscribe_events_shared(hub, filter).await
+    }
+
     /// Plan a transfer without submitting.
     pub async fn plan_transfer(
         &self,
@@ -178,6 +279,26 @@ impl OrganisationAvatar {
         self.common.transfer(to, amount, options).await
     }
 
+    /// Plan a transfer as a resumable [`TransferPlan`](crate::TransferPlan).
+    pub async fn plan_resumable_transfer(
+        &self,
+        to: Address,
+        amount: U256,
+        options: Option<AdvancedTransferOptions>,
+    ) -> Result<crate::TransferPlan, SdkError> {
+        self.common
+            .plan_resumable_transfer(to, amount, options)
+            .await
+    }
+
+    /// Send the unfinished steps of a [`TransferPlan`](crate::TransferPlan) (requires runner).
+    pub async fn resume_transfer(
+        &self,
+        plan: &mut crate::TransferPlan,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        self.common.resume_transfer(plan).await
+    }
+
     /// Plan a direct transfer without pathfinding.
     pub async fn plan_direct_transfer(
         &self,
@@ -365,6 +486,7 @@ impl OrganisationAvatar {
             simulated_trusts: None,
             max_transfers: None,
             tx_data: None,
+            validate_path: None,
         });
         if opts.use_wrapped_balances.is_none() {
             opts.use_wrapped_balances = Some(true);
@@ -428,6 +550,7 @@ impl OrganisationAvatar {
                 simulated_trusts: None,
                 max_transfers: None,
                 tx_data: None,
+                validate_path: None,
             }),
         )
         .await
@@ -483,6 +606,7 @@ impl OrganisationAvatar {
                     simulated_trusts: None,
                     max_transfers: None,
                     tx_data: None,
+                    validate_path: None,
                 }),
             )
             .await?
@@ -571,6 +695,149 @@ impl OrganisationAvatar {
             .map_err(|e| SdkError::Contract(e.to_string()))
     }
 
+    /// Plan an invitation-escrow deposit of `amount` `token` CRC reserved for `invitee`.
+    ///
+    /// The deposit is a Hub ERC1155 transfer into the escrow with the invitee
+    /// ABI-encoded as transfer data; the escrow contract validates the token.
+    pub async fn plan_fund_escrow_invitation(
+        &self,
+        invitee: Address,
+        token: Address,
+        amount: U256,
+    ) -> Result<Vec<PreparedTransaction>, SdkError> {
+        self.common
+            .plan_transfer_erc1155(
+                token,
+                self.core.config.invitation_escrow_address,
+                amount,
+                Some(Bytes::from(invitee.abi_encode())),
+            )
+            .await
+    }
+
+    /// Deposit CRC into the invitation escrow for `invitee` (requires runner).
+    pub async fn fund_escrow_invitation(
+        &self,
+        invitee: Address,
+        token: Address,
+        amount: U256,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        let txs = self
+            .plan_fund_escrow_invitation(invitee, token, amount)
+            .await?;
+        self.common.send(txs).await
+    }
+
+    /// Plan escrow deposits for many invitees at once (one Hub transfer each).
+    pub async fn plan_fund_escrow_invitations(
+        &self,
+        invitees: &[Address],
+        token: Address,
+        amount_each: U256,
+    ) -> Result<Vec<PreparedTransaction>, SdkError> {
+        if invitees.is_empty() {
+            return Err(SdkError::OperationFailed(
+                "at least one invitee address must be provided".to_string(),
+            ));
+        }
+        let mut txs = Vec::with_capacity(invitees.len());
+        for invitee in invitees {
+            txs.extend(
+                self.plan_fund_escrow_invitation(*invitee, token, amount_each)
+                    .await?,
+            );
+        }
+        Ok(txs)
+    }
+
+    /// Deposit escrowed invitations for many invitees in one runner batch (requires runner).
+    pub async fn fund_escrow_invitations(
+        &self,
+        invitees: &[Address],
+        token: Address,
+        amount_each: U256,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        let txs = self
+            .plan_fund_escrow_invitations(invitees, token, amount_each)
+            .await?;
+        self.common.send(txs).await
+    }
+
+    /// Invitees that currently hold an escrowed invitation from this organisation.
+    pub async fn escrowed_invitees(&self) -> Result<Vec<Address>, SdkError> {
+        self.core
+            .invitation_escrow()
+            .getInvitees(self.address)
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))
+    }
+
+    /// Escrowed amount and elapsed days for a single invitee.
+    pub async fn escrowed_amount_and_days(
+        &self,
+        invitee: Address,
+    ) -> Result<EscrowedAmountAndDays, SdkError> {
+        let result = self
+            .core
+            .invitation_escrow()
+            .getEscrowedAmountAndDays(self.address, invitee)
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))?;
+        Ok(EscrowedAmountAndDays {
+            escrowed_amount: result.escrowedAmount,
+            days_: U256::from(result.days_),
+        })
+    }
+
+    /// All escrowed invitations of this organisation with their current escrow state.
+    pub async fn escrowed_invitations(&self) -> Result<Vec<EscrowedInvitation>, SdkError> {
+        let invitees = self.escrowed_invitees().await?;
+        let mut invitations = Vec::with_capacity(invitees.len());
+        for invitee in invitees {
+            invitations.push(EscrowedInvitation {
+                invitee,
+                escrow: self.escrowed_amount_and_days(invitee).await?,
+            });
+        }
+        Ok(invitations)
+    }
+
+    /// Plan withdrawing the unused escrow reserved for `invitee`.
+    pub fn plan_revoke_escrow_invitation(&self, invitee: Address) -> Vec<PreparedTransaction> {
+        vec![build_revoke_invitation_tx(
+            self.core.config.invitation_escrow_address,
+            invitee,
+        )]
+    }
+
+    /// Withdraw the unused escrow reserved for `invitee` (requires runner).
+    pub async fn revoke_escrow_invitation(
+        &self,
+        invitee: Address,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        self.common
+            .send(self.plan_revoke_escrow_invitation(invitee))
+            .await
+    }
+
+    /// Plan withdrawing every unused escrow of this organisation.
+    pub fn plan_revoke_all_escrow_invitations(&self) -> Vec<PreparedTransaction> {
+        vec![call_to_tx(
+            self.core.config.invitation_escrow_address,
+            InvitationEscrow::revokeAllInvitationsCall {},
+            None,
+        )]
+    }
+
+    /// Withdraw every unused escrow of this organisation (requires runner).
+    pub async fn revoke_all_escrow_invitations(&self) -> Result<Vec<SubmittedTx>, SdkError> {
+        self.common
+            .send(self.plan_revoke_all_escrow_invitations())
+            .await
+    }
+
     /// Build a typed organisation avatar wrapper from already-fetched components.
     pub fn new(
         address: Address,
@@ -598,7 +865,7 @@ mod tests {
     use alloy_sol_types::SolCall;
     use async_trait::async_trait;
     use circles_profiles::Profiles;
-    use circles_types::{AvatarType, CirclesConfig};
+    use circles_types::{AvatarType, CirclesConfig, Timeouts};
     use std::sync::Mutex;
 
     const TEST_CID: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
@@ -633,8 +900,11 @@ mod tests {
 
     fn dummy_config() -> CirclesConfig {
         CirclesConfig {
+            chain_id: 100,
             circles_rpc_url: "https://rpc.example.com".into(),
             chain_rpc_url: None,
+            replica_rpc_url: None,
+            archive_rpc_url: None,
             pathfinder_url: Some("https://pathfinder.example.com".into()),
             profile_service_url: Some("https://profiles.example.com".into()),
             referrals_service_url: None,
@@ -650,6 +920,7 @@ mod tests {
             invitation_farm_address: Address::repeat_byte(0x0a),
             referrals_module_address: Address::repeat_byte(0x0b),
             invitation_module_address: Address::repeat_byte(0x0c),
+            timeouts: Timeouts::default(),
         }
     }
 
@@ -692,6 +963,71 @@ mod tests {
         (avatar, runner, config)
     }
 
+    #[tokio::test]
+    async fn escrow_funding_encodes_hub_transfer_with_invitee_data() {
+        let (avatar, _runner, config) = test_avatar();
+        let invitee = Address::repeat_byte(0xcc);
+        let token = Address::repeat_byte(0xdd);
+
+        let txs = avatar
+            .plan_fund_escrow_invitations(
+                &[invitee, Address::repeat_byte(0xce)],
+                token,
+                U256::from(97u64),
+            )
+            .await
+            .expect("plan escrow funding");
+
+        assert_eq!(txs.len(), 2);
+        assert_eq!(txs[0].to, config.v2_hub_address);
+        let decoded =
+            HubV2::safeTransferFromCall::abi_decode(&txs[0].data).expect("decode transfer");
+        assert_eq!(decoded._from, avatar.address);
+        assert_eq!(decoded._to, config.invitation_escrow_address);
+        assert_eq!(decoded._id, U256::from_be_slice(token.as_slice()));
+        assert_eq!(decoded._value, U256::from(97u64));
+        assert_eq!(decoded._data, Bytes::from(invitee.abi_encode()));
+    }
+
+    #[tokio::test]
+    async fn escrow_funding_requires_invitees() {
+        let (avatar, _runner, _config) = test_avatar();
+
+        let err = avatar
+            .plan_fund_escrow_invitations(&[], Address::repeat_byte(0xdd), U256::from(1u64))
+            .await
+            .expect_err("empty invitees");
+
+        assert!(matches!(err, SdkError::OperationFailed(_)));
+    }
+
+    #[tokio::test]
+    async fn escrow_revocations_target_invitation_escrow() {
+        let (avatar, runner, config) = test_avatar();
+        let invitee = Address::repeat_byte(0xcc);
+
+        avatar
+            .revoke_escrow_invitation(invitee)
+            .await
+            .expect("revoke one");
+        avatar
+            .revoke_all_escrow_invitations()
+            .await
+            .expect("revoke all");
+
+        let sent = runner.sent.lock().expect("lock");
+        assert_eq!(sent[0][0].to, config.invitation_escrow_address);
+        assert_eq!(
+            sent[0][0].data,
+            Bytes::from(InvitationEscrow::revokeInvitationCall { invitee }.abi_encode())
+        );
+        assert_eq!(sent[1][0].to, config.invitation_escrow_address);
+        assert_eq!(
+            &sent[1][0].data[..4],
+            &InvitationEscrow::revokeAllInvitationsCall {}.abi_encode()[..4]
+        );
+    }
+
     #[tokio::test]
     async fn profile_write_helpers_encode_expected_calls() {
         let (avatar, runner, config) = test_avatar();
@@ -712,7 +1048,7 @@ mod tests {
         assert_eq!(
             &sent[0][0].data[..4],
             &circles_abis::NameRegistry::updateMetadataDigestCall {
-                _metadataDigest: cid_v0_to_digest(TEST_CID).expect("cid"),
+                _metadataDigest: cid_to_digest(TEST_CID).expect("cid"),
             }
             .abi_encode()[..4]
         );
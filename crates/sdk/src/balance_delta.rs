@@ -0,0 +1,260 @@
+//! Signed per-avatar balance changes derived from hub events.
+//!
+//! [`BalanceDeltas`] turns `TransferSingle`, `TransferBatch`, and `PersonalMint` events into
+//! [`BalanceDelta`]s, so apps can keep balances current without decoding ERC1155 payloads.
+
+use crate::SdkError;
+use crate::projector::{Projection, Projector, TokenTransfer, decode_payload};
+use alloy_primitives::{Address, B256, I256, U256};
+use circles_types::{CirclesEvent, CirclesEventPayload, CirclesEventType};
+use std::collections::HashSet;
+
+/// Change of one avatar's balance of one personal or group token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceDelta {
+    pub avatar: Address,
+    /// Avatar whose token moved (the ERC1155 id as an address).
+    pub token: Address,
+    /// Atto-circles gained (positive) or spent (negative).
+    pub delta: I256,
+    pub block: u64,
+}
+
+#[derive(Default)]
+struct Moves(Vec<TokenTransfer>);
+
+impl Projection for Moves {
+    fn on_transfer(&mut self, _event: &CirclesEvent, transfer: &TokenTransfer) {
+        if !transfer.wrapped {
+            self.0.push(transfer.clone());
+        }
+    }
+}
+
+/// Converts hub events into [`BalanceDelta`]s, optionally only for some avatars or tokens.
+///
+/// A personal mint emits both `TransferSingle` and `PersonalMint`; whichever arrives first
+/// yields the delta and the other is dropped, so subscribing to either or both gives the
+/// same result. ERC20 wrapper transfers are ignored because they do not move hub balances.
+pub struct BalanceDeltas {
+    projector: Projector<Moves>,
+    avatars: Option<HashSet<Address>>,
+    tokens: Option<HashSet<Address>>,
+    mint_block: u64,
+    /// Personal mints (block, tx index, human, amount) already reported in `mint_block`.
+    mints: HashSet<(u64, u32, Address, U256)>,
+}
+
+impl Default for BalanceDeltas {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BalanceDeltas {
+    pub fn new() -> Self {
+        Self {
+            projector: Projector::new(Moves::default()),
+            avatars: None,
+            tokens: None,
+            mint_block: 0,
+            mints: HashSet::new(),
+        }
+    }
+
+    /// Only report balance changes of these avatars.
+    pub fn with_avatars(mut self, avatars: impl IntoIterator<Item = Address>) -> Self {
+        self.avatars
+            .get_or_insert_with(HashSet::new)
+            .extend(avatars);
+        self
+    }
+
+    /// Only report changes of these tokens.
+    pub fn with_tokens(mut self, tokens: impl IntoIterator<Item = Address>) -> Self {
+        self.tokens.get_or_insert_with(HashSet::new).extend(tokens);
+        self
+    }
+
+    /// Deltas caused by one event, sender first; empty for unrelated events.
+    ///
+    /// Events must arrive in chain order for personal mints to be deduplicated.
+    pub fn apply(&mut self, event: &CirclesEvent) -> Result<Vec<BalanceDelta>, SdkError> {
+        let block = event.base.block_number;
+        if block != self.mint_block {
+            self.mint_block = block;
+            self.mints.clear();
+        }
+        let mut deltas = Vec::new();
+        if event.event_type == CirclesEventType::CrcV2PersonalMint {
+            if let CirclesEventPayload::CrcV2PersonalMint(mint) = decode_payload(event)?
+                && self.first_mint_report(event, mint.human, mint.amount)
+            {
+                self.push(
+                    &mut deltas,
+                    mint.human,
+                    mint.human,
+                    signed(mint.amount),
+                    block,
+                );
+            }
+            return Ok(deltas);
+        }
+
+        self.projector.apply(event)?;
+        for transfer in std::mem::take(&mut self.projector.projection_mut().0) {
+            let token = Address::from_word(B256::from(transfer.token_id));
+            let personal_mint = transfer.from.is_zero() && transfer.to == token;
+            if personal_mint && !self.first_mint_report(event, token, transfer.amount) {
+                continue;
+            }
+            let amount = signed(transfer.amount);
+            if !transfer.from.is_zero() {
+                self.push(&mut deltas, transfer.from, token, -amount, block);
+            }
+            if !transfer.to.is_zero() {
+                self.push(&mut deltas, transfer.to, token, amount, block);
+            }
+        }
+        Ok(deltas)
+    }
+
+    fn first_mint_report(&mut self, event: &CirclesEvent, human: Address, amount: U256) -> bool {
+        let key = (
+            event.base.block_number,
+            event.base.transaction_index,
+            human,
+            amount,
+        );
+        // The second report of the same mint matches and is dropped.
+        !self.mints.remove(&key) && self.mints.insert(key)
+    }
+
+    fn push(
+        &self,
+        deltas: &mut Vec<BalanceDelta>,
+        avatar: Address,
+        token: Address,
+        delta: I256,
+        block: u64,
+    ) {
+        let watched =
+            |set: &Option<HashSet<Address>>, item| set.as_ref().is_none_or(|s| s.contains(&item));
+        if delta.is_zero() || !watched(&self.avatars, avatar) || !watched(&self.tokens, token) {
+            return;
+        }
+        deltas.push(BalanceDelta {
+            avatar,
+            token,
+            delta,
+            block,
+        });
+    }
+
+    /// Turn an event subscription (or any event stream) into a stream of deltas.
+    #[cfg(feature = "ws")]
+    pub fn stream<S, E>(
+        mut self,
+        events: S,
+    ) -> impl futures::Stream<Item = Result<BalanceDelta, SdkError>>
+    where
+        S: futures::Stream<Item = Result<CirclesEvent, E>>,
+        E: Into<SdkError>,
+    {
+        use futures::StreamExt;
+        events.flat_map(move |event| {
+            let items: Vec<Result<BalanceDelta, SdkError>> = match event
+                .map_err(Into::into)
+                .and_then(|event| self.apply(&event))
+            {
+                Ok(deltas) => deltas.into_iter().map(Ok).collect(),
+                Err(err) => vec![Err(err)],
+            };
+            futures::stream::iter(items)
+        })
+    }
+}
+
+/// Circles supplies stay far below `I256::MAX`; saturate rather than wrap if one ever did not.
+fn signed(amount: U256) -> I256 {
+    I256::try_from(amount).unwrap_or(I256::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use circles_rpc_mock::fixtures::circles_event;
+    use serde_json::{Value, json};
+
+    fn event(event_type: CirclesEventType, tx: u32, data: Value) -> CirclesEvent {
+        let mut event = circles_event(event_type, data);
+        event.base.transaction_index = tx;
+        event
+    }
+
+    fn delta(avatar: Address, token: Address, delta: i64) -> BalanceDelta {
+        BalanceDelta {
+            avatar,
+            token,
+            delta: I256::try_from(delta).unwrap(),
+            block: 1,
+        }
+    }
+
+    #[test]
+    fn transfers_and_mints_become_signed_deltas() {
+        let alice = Address::repeat_byte(0x0a);
+        let bob = Address::repeat_byte(0x0b);
+        let token = |avatar: Address| U256::from_be_slice(avatar.as_slice()).to_string();
+        let mint = [
+            event(
+                CirclesEventType::CrcV2TransferSingle,
+                1,
+                json!({
+                    "operator": alice, "from": Address::ZERO, "to": alice,
+                    "id": token(alice), "value": 9
+                }),
+            ),
+            event(
+                CirclesEventType::CrcV2PersonalMint,
+                1,
+                json!({ "human": alice, "amount": 9, "startPeriod": 1, "endPeriod": 2 }),
+            ),
+        ];
+        let batch = event(
+            CirclesEventType::CrcV2TransferBatch,
+            2,
+            json!({
+                "operator": alice, "from": alice, "to": bob,
+                "ids": [token(alice), token(bob)], "values": [5, 3]
+            }),
+        );
+
+        let mut deltas = BalanceDeltas::new();
+        assert_eq!(
+            deltas.apply(&mint[0]).unwrap(),
+            vec![delta(alice, alice, 9)]
+        );
+        assert!(deltas.apply(&mint[1]).unwrap().is_empty());
+        assert_eq!(
+            deltas.apply(&batch).unwrap(),
+            vec![
+                delta(alice, alice, -5),
+                delta(bob, alice, 5),
+                delta(alice, bob, -3),
+                delta(bob, bob, 3),
+            ]
+        );
+
+        // Only the PersonalMint event seen still reports the mint once.
+        let mut mints_only = BalanceDeltas::new().with_tokens([alice]);
+        assert_eq!(
+            mints_only.apply(&mint[1]).unwrap(),
+            vec![delta(alice, alice, 9)]
+        );
+
+        let mut bob_only = BalanceDeltas::new().with_avatars([bob]).with_tokens([bob]);
+        assert!(bob_only.apply(&mint[0]).unwrap().is_empty());
+        assert_eq!(bob_only.apply(&batch).unwrap(), vec![delta(bob, bob, 3)]);
+    }
+}
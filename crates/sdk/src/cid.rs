@@ -0,0 +1,185 @@
+//! Conversions between IPFS CIDs and the `bytes32` digests the name registry stores.
+//!
+//! The registry keeps only the SHA-256 digest of a profile's dag-pb block, which is also
+//! what indexer rows expose as `cid_v0_digest`. A CIDv0 (`Qm…`) and a base32 CIDv1
+//! (`bafy…`) of the same block carry the same digest, so either can be written; turning a
+//! digest back into a CID gives the CIDv0 the profile service serves by default.
+
+use alloy_primitives::B256;
+use data_encoding::BASE32_NOPAD;
+use thiserror::Error;
+
+/// Multihash code of SHA-256.
+const SHA2_256: u8 = 0x12;
+/// Length byte of a SHA-256 multihash.
+const DIGEST_LEN: u8 = 0x20;
+/// Multicodec of dag-pb, the only codec a CIDv0 can express.
+const DAG_PB: u64 = 0x70;
+const CID_V1: u8 = 0x01;
+/// Multibase prefix of lowercase, unpadded base32.
+const BASE32_PREFIX: char = 'b';
+
+#[derive(Debug, Error)]
+pub enum CidError {
+    #[error("invalid base58 cid: {0}")]
+    InvalidCid(String),
+    #[error("cid digest must be 32 bytes, got {0} bytes")]
+    InvalidLength(usize),
+    #[error("unsupported multihash 0x{0:02x}: only sha2-256 digests fit in bytes32")]
+    UnsupportedMultihash(u8),
+    #[error("unsupported cid codec 0x{0:x}: profiles are dag-pb (0x70)")]
+    UnsupportedCodec(u64),
+    #[error("unsupported cid version {0}")]
+    UnsupportedVersion(u64),
+    #[error("unsupported multibase prefix {0:?}: expected base32 (`b`)")]
+    UnsupportedMultibase(char),
+    #[error("invalid hex digest: {0}")]
+    InvalidDigest(String),
+}
+
+/// Convert CIDv0 (base58btc) to bytes32 digest.
+pub fn cid_v0_to_digest(cid: &str) -> Result<B256, CidError> {
+    let data = bs58::decode(cid)
+        .into_vec()
+        .map_err(|e| CidError::InvalidCid(e.to_string()))?;
+    // CIDv0 = 0x12 0x20 + 32-byte multihash digest
+    if data.len() != 34 {
+        return Err(CidError::InvalidLength(data.len()));
+    }
+    multihash_digest(&data)
+}
+
+/// Convert a bytes32 digest back into its CIDv0.
+pub fn digest_to_cid_v0(digest: B256) -> String {
+    bs58::encode(multihash(digest)).into_string()
+}
+
+/// Convert a base32 CIDv1 of a dag-pb block to bytes32 digest.
+pub fn cid_v1_to_digest(cid: &str) -> Result<B256, CidError> {
+    let encoded = cid
+        .strip_prefix(BASE32_PREFIX)
+        .ok_or_else(|| CidError::UnsupportedMultibase(cid.chars().next().unwrap_or(' ')))?;
+    let data = BASE32_NOPAD
+        .decode(encoded.to_ascii_uppercase().as_bytes())
+        .map_err(|e| CidError::InvalidCid(e.to_string()))?;
+    let (version, rest) = read_varint(&data)?;
+    if version != u64::from(CID_V1) {
+        return Err(CidError::UnsupportedVersion(version));
+    }
+    let (codec, multihash) = read_varint(rest)?;
+    if codec != DAG_PB {
+        return Err(CidError::UnsupportedCodec(codec));
+    }
+    if multihash.len() != 34 {
+        return Err(CidError::InvalidLength(multihash.len()));
+    }
+    multihash_digest(multihash)
+}
+
+/// Convert a bytes32 digest into the base32 CIDv1 (`bafy…`) of the same dag-pb block.
+pub fn digest_to_cid_v1(digest: B256) -> String {
+    // Version and dag-pb codec both fit in a single varint byte.
+    let mut bytes = vec![CID_V1, DAG_PB as u8];
+    bytes.extend(multihash(digest));
+    format!(
+        "{BASE32_PREFIX}{}",
+        BASE32_NOPAD.encode(&bytes).to_ascii_lowercase()
+    )
+}
+
+/// Convert a CIDv0 or base32 CIDv1 to bytes32 digest.
+pub fn cid_to_digest(cid: &str) -> Result<B256, CidError> {
+    // Every sha2-256 CIDv0 starts with `Qm`; CIDv1 strings start with their multibase.
+    if cid.starts_with("Qm") {
+        cid_v0_to_digest(cid)
+    } else if cid.starts_with(BASE32_PREFIX) {
+        cid_v1_to_digest(cid)
+    } else {
+        Err(CidError::UnsupportedMultibase(
+            cid.chars().next().unwrap_or(' '),
+        ))
+    }
+}
+
+/// CIDv0 for a hex digest as returned by the indexer (`cid_v0_digest`), with or without `0x`.
+pub fn hex_digest_to_cid_v0(digest: &str) -> Result<String, CidError> {
+    let digest: B256 = digest
+        .parse()
+        .map_err(|_| CidError::InvalidDigest(digest.to_string()))?;
+    Ok(digest_to_cid_v0(digest))
+}
+
+/// Whether `cid` is a CIDv0 or dag-pb CIDv1 that can be stored in the name registry.
+pub fn is_valid_cid(cid: &str) -> bool {
+    cid_to_digest(cid).is_ok()
+}
+
+fn multihash(digest: B256) -> Vec<u8> {
+    let mut bytes = vec![SHA2_256, DIGEST_LEN];
+    bytes.extend_from_slice(digest.as_slice());
+    bytes
+}
+
+fn multihash_digest(multihash: &[u8]) -> Result<B256, CidError> {
+    if multihash[0] != SHA2_256 || multihash[1] != DIGEST_LEN {
+        return Err(CidError::UnsupportedMultihash(multihash[0]));
+    }
+    Ok(B256::from_slice(&multihash[2..]))
+}
+
+/// Split an unsigned LEB128 varint off the front of `bytes`.
+fn read_varint(bytes: &[u8]) -> Result<(u64, &[u8]), CidError> {
+    let mut value = 0u64;
+    for (index, byte) in bytes.iter().enumerate().take(9) {
+        value |= u64::from(byte & 0x7f) << (7 * index);
+        if byte & 0x80 == 0 {
+            return Ok((value, &bytes[index + 1..]));
+        }
+    }
+    Err(CidError::InvalidCid("truncated varint".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CID_V0: &str = "QmfDWxB9jtEGHLi6ToJKWyoXeRzu64WBSKUfnwCWKHLsFn";
+
+    #[test]
+    fn cid_roundtrip_length() {
+        let cid = "QmfDWxB9jtEGHLi6ToJKWyoXeRzu64WBSKUfnwCWKHLsFn";
+        let digest = cid_v0_to_digest(cid).unwrap();
+        assert_eq!(digest.len(), 32);
+    }
+
+    #[test]
+    fn digests_convert_back_to_both_cid_versions() {
+        let digest = cid_v0_to_digest(CID_V0).unwrap();
+        assert_eq!(digest_to_cid_v0(digest), CID_V0);
+        assert_eq!(hex_digest_to_cid_v0(&digest.to_string()).unwrap(), CID_V0);
+
+        let v1 = digest_to_cid_v1(digest);
+        assert!(v1.starts_with("bafybei"));
+        assert_eq!(cid_to_digest(&v1).unwrap(), digest);
+        assert_eq!(cid_to_digest(CID_V0).unwrap(), digest);
+    }
+
+    #[test]
+    fn cids_outside_the_registry_format_are_rejected() {
+        // Raw-codec CIDv1 of the same digest: a different block than the profile.
+        let digest = cid_v0_to_digest(CID_V0).unwrap();
+        let mut raw = vec![CID_V1, 0x55];
+        raw.extend(multihash(digest));
+        let raw = format!("b{}", BASE32_NOPAD.encode(&raw).to_ascii_lowercase());
+        assert!(matches!(
+            cid_to_digest(&raw),
+            Err(CidError::UnsupportedCodec(0x55))
+        ));
+        assert!(matches!(
+            cid_to_digest("zdj7WWeQ43G6JJvLWQWZpyHuAMq6uYWRjkBXFad11vE2LHhQ7"),
+            Err(CidError::UnsupportedMultibase('z'))
+        ));
+        assert!(!is_valid_cid("Qm"));
+        assert!(hex_digest_to_cid_v0("0x1234").is_err());
+    }
+}
@@ -0,0 +1,298 @@
+//! On-chain sanity check of the configured contract addresses.
+
+use crate::{Core, MulticallBatch, MulticallResults, SdkError};
+use alloy_primitives::Address;
+use alloy_provider::Provider;
+use alloy_sol_types::SolCall;
+use circles_abis::{
+    BaseGroupFactory, HubV2, InvitationEscrow, InvitationFarm, LiftERC20, NameRegistry,
+    ReferralsModule,
+};
+
+/// Outcome of checking one configured contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigCheckStatus {
+    /// Code is deployed and every probe answered as expected.
+    Ok,
+    /// The address is zero and the field is optional.
+    Skipped,
+    /// No code at the address (wrong network or never deployed).
+    NoCode,
+    /// Code exists but a probe reverted, failed to decode, or pointed at another deployment.
+    Mismatch(String),
+}
+
+/// Check result for one [`CirclesConfig`](circles_types::CirclesConfig) address field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigCheck {
+    pub field: &'static str,
+    pub address: Address,
+    pub status: ConfigCheckStatus,
+}
+
+/// Result of [`Sdk::verify_config`](crate::Sdk::verify_config).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigReport {
+    pub checks: Vec<ConfigCheck>,
+}
+
+impl ConfigReport {
+    /// Whether every required contract passed.
+    pub fn is_ok(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// Checks that found missing code or a mismatch.
+    pub fn failures(&self) -> impl Iterator<Item = &ConfigCheck> {
+        self.checks.iter().filter(|check| {
+            matches!(
+                check.status,
+                ConfigCheckStatus::NoCode | ConfigCheckStatus::Mismatch(_)
+            )
+        })
+    }
+}
+
+/// Reads one multicall result and describes what is wrong with it, if anything.
+type Probe = Box<dyn Fn(&MulticallResults) -> Option<String> + Send>;
+
+struct Target {
+    field: &'static str,
+    address: Address,
+    optional: bool,
+    probes: Vec<Probe>,
+}
+
+/// Probe that only requires `call` to succeed and decode.
+fn decodes<C: SolCall + 'static>(batch: &mut MulticallBatch, target: Address, call: C) -> Probe {
+    let handle = batch.add_allow_failure(target, call);
+    Box::new(move |results| {
+        results
+            .get(handle)
+            .err()
+            .map(|err| format!("{}: {err}", C::SIGNATURE))
+    })
+}
+
+/// Probe that requires `call` to return the `expected` companion contract.
+fn points<C>(batch: &mut MulticallBatch, target: Address, call: C, expected: Address) -> Probe
+where
+    C: SolCall<Return = Address> + 'static,
+{
+    let handle = batch.add_allow_failure(target, call);
+    Box::new(move |results| match results.get(handle) {
+        Ok(actual) if actual == expected => None,
+        Ok(actual) => Some(format!(
+            "{} returned {actual:#x}, expected {expected:#x}",
+            C::SIGNATURE
+        )),
+        Err(err) => Some(format!("{}: {err}", C::SIGNATURE)),
+    })
+}
+
+/// Check code presence and interface probes for every configured contract.
+pub(crate) async fn verify_config(core: &Core) -> Result<ConfigReport, SdkError> {
+    let config = &core.config;
+    let hub = config.v2_hub_address;
+    let registry = config.name_registry_address;
+    let invitation_module = config.invitation_module_address;
+    let mut batch = MulticallBatch::new();
+
+    let targets = vec![
+        Target {
+            field: "v1_hub_address",
+            address: config.v1_hub_address,
+            optional: true,
+            probes: Vec::new(),
+        },
+        Target {
+            field: "v2_hub_address",
+            address: hub,
+            optional: false,
+            probes: vec![decodes(
+                &mut batch,
+                hub,
+                HubV2::isHumanCall {
+                    _human: Address::ZERO,
+                },
+            )],
+        },
+        Target {
+            field: "name_registry_address",
+            address: registry,
+            optional: false,
+            probes: vec![points(&mut batch, registry, NameRegistry::hubCall {}, hub)],
+        },
+        Target {
+            field: "base_group_mint_policy",
+            address: config.base_group_mint_policy,
+            optional: false,
+            probes: Vec::new(),
+        },
+        Target {
+            field: "standard_treasury",
+            address: config.standard_treasury,
+            optional: false,
+            probes: Vec::new(),
+        },
+        Target {
+            field: "core_members_group_deployer",
+            address: config.core_members_group_deployer,
+            optional: false,
+            probes: Vec::new(),
+        },
+        Target {
+            field: "base_group_factory_address",
+            address: config.base_group_factory_address,
+            optional: false,
+            probes: vec![decodes(
+                &mut batch,
+                config.base_group_factory_address,
+                BaseGroupFactory::deployedByFactoryCall {
+                    group: Address::ZERO,
+                },
+            )],
+        },
+        Target {
+            field: "lift_erc20_address",
+            address: config.lift_erc20_address,
+            optional: false,
+            probes: vec![
+                points(
+                    &mut batch,
+                    config.lift_erc20_address,
+                    LiftERC20::hubCall {},
+                    hub,
+                ),
+                points(
+                    &mut batch,
+                    config.lift_erc20_address,
+                    LiftERC20::nameRegistryCall {},
+                    registry,
+                ),
+            ],
+        },
+        Target {
+            field: "invitation_escrow_address",
+            address: config.invitation_escrow_address,
+            optional: false,
+            probes: vec![decodes(
+                &mut batch,
+                config.invitation_escrow_address,
+                InvitationEscrow::getInvitersCall {
+                    invitee: Address::ZERO,
+                },
+            )],
+        },
+        Target {
+            field: "invitation_farm_address",
+            address: config.invitation_farm_address,
+            optional: true,
+            probes: vec![
+                points(
+                    &mut batch,
+                    config.invitation_farm_address,
+                    InvitationFarm::HUBCall {},
+                    hub,
+                ),
+                points(
+                    &mut batch,
+                    config.invitation_farm_address,
+                    InvitationFarm::invitationModuleCall {},
+                    invitation_module,
+                ),
+            ],
+        },
+        Target {
+            field: "referrals_module_address",
+            address: config.referrals_module_address,
+            optional: false,
+            probes: vec![
+                points(
+                    &mut batch,
+                    config.referrals_module_address,
+                    ReferralsModule::HUBCall {},
+                    hub,
+                ),
+                points(
+                    &mut batch,
+                    config.referrals_module_address,
+                    ReferralsModule::NAME_REGISTRYCall {},
+                    registry,
+                ),
+                points(
+                    &mut batch,
+                    config.referrals_module_address,
+                    ReferralsModule::INVITATION_MODULECall {},
+                    invitation_module,
+                ),
+            ],
+        },
+        Target {
+            field: "invitation_module_address",
+            address: invitation_module,
+            optional: false,
+            probes: Vec::new(),
+        },
+    ];
+
+    let results = core.multicall(batch).await?;
+    let provider = core.provider();
+    let mut checks = Vec::with_capacity(targets.len());
+    for target in targets {
+        let status = if target.address.is_zero() {
+            if target.optional {
+                ConfigCheckStatus::Skipped
+            } else {
+                ConfigCheckStatus::NoCode
+            }
+        } else {
+            let code = provider
+                .get_code_at(target.address)
+                .await
+                .map_err(|e| SdkError::Contract(e.to_string()))?;
+            if code.is_empty() {
+                ConfigCheckStatus::NoCode
+            } else {
+                probe_status(&target.probes, &results)
+            }
+        };
+        checks.push(ConfigCheck {
+            field: target.field,
+            address: target.address,
+            status,
+        });
+    }
+    Ok(ConfigReport { checks })
+}
+
+fn probe_status(probes: &[Probe], results: &MulticallResults) -> ConfigCheckStatus {
+    probes
+        .iter()
+        .find_map(|probe| probe(results))
+        .map_or(ConfigCheckStatus::Ok, ConfigCheckStatus::Mismatch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_lists_only_failures() {
+        let check = |field, status| ConfigCheck {
+            field,
+            address: Address::ZERO,
+            status,
+        };
+        let report = ConfigReport {
+            checks: vec![
+                check("v1_hub_address", ConfigCheckStatus::Skipped),
+                check("v2_hub_address", ConfigCheckStatus::Ok),
+                check("lift_erc20_address", ConfigCheckStatus::NoCode),
+            ],
+        };
+        assert!(!report.is_ok());
+        let failed: Vec<_> = report.failures().map(|c| c.field).collect();
+        assert_eq!(failed, vec!["lift_erc20_address"]);
+    }
+}
@@ -3,12 +3,131 @@
 //! `Core` owns the resolved Circles configuration and constructs lightweight Alloy
 //! contract handles on demand. It is shared by `Sdk` and the typed avatar wrappers.
 
+use crate::SdkError;
+use alloy_primitives::{Address, Bytes, U256, address};
 use alloy_provider::{Identity, ProviderBuilder, RootProvider};
+use alloy_sol_types::{SolCall, sol};
 use circles_abis::{
     BaseGroup, BaseGroupFactory, DemurrageCircles, HubV2, InflationaryCircles, InvitationEscrow,
-    InvitationFarm, LiftERC20, NameRegistry, ReferralsModule,
+    InvitationFarm, LiftERC20, NameRegistry, ReferralsModule, SubscriptionModule,
 };
 use circles_types::CirclesConfig;
+use std::marker::PhantomData;
+
+/// Canonical Multicall3 deployment address (identical on Gnosis Chain and most EVM chains).
+pub const MULTICALL3_ADDRESS: Address = address!("cA11bde05977b3631167028862bE2a173976CA11");
+
+sol! {
+    #[sol(rpc)]
+    interface IMulticall3 {
+        struct Call3 {
+            address target;
+            bool allowFailure;
+            bytes callData;
+        }
+
+        struct Call3Result {
+            bool success;
+            bytes returnData;
+        }
+
+        function aggregate3(Call3[] calldata calls) external payable returns (Call3Result[] memory returnData);
+    }
+}
+
+/// Typed handle to a call queued in a [`MulticallBatch`].
+#[derive(Debug)]
+pub struct MulticallHandle<C> {
+    index: usize,
+    _call: PhantomData<fn() -> C>,
+}
+
+impl<C> Clone for MulticallHandle<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for MulticallHandle<C> {}
+
+/// Read calls collected for a single Multicall3 `aggregate3` round-trip.
+#[derive(Debug, Clone, Default)]
+pub struct MulticallBatch {
+    calls: Vec<(Address, bool, Bytes)>,
+}
+
+impl MulticallBatch {
+    /// Create an empty batch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a call whose revert fails the whole batch.
+    pub fn add<C: SolCall>(&mut self, target: Address, call: C) -> MulticallHandle<C> {
+        self.push(target, false, call)
+    }
+
+    /// Queue a call that may revert without failing the rest of the batch.
+    pub fn add_allow_failure<C: SolCall>(
+        &mut self,
+        target: Address,
+        call: C,
+    ) -> MulticallHandle<C> {
+        self.push(target, true, call)
+    }
+
+    /// Number of queued calls.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Whether no calls are queued.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    fn push<C: SolCall>(
+        &mut self,
+        target: Address,
+        allow_failure: bool,
+        call: C,
+    ) -> MulticallHandle<C> {
+        self.calls
+            .push((target, allow_failure, Bytes::from(call.abi_encode())));
+        MulticallHandle {
+            index: self.calls.len() - 1,
+            _call: PhantomData,
+        }
+    }
+}
+
+/// Return data of an executed [`MulticallBatch`], decoded through its handles.
+#[derive(Debug, Clone, Default)]
+pub struct MulticallResults {
+    results: Vec<(bool, Bytes)>,
+}
+
+impl MulticallResults {
+    /// Decode the return value for `handle`.
+    ///
+    /// Calls queued with `add_allow_failure` that reverted yield `SdkError::Contract`.
+    pub fn get<C: SolCall>(&self, handle: MulticallHandle<C>) -> Result<C::Return, SdkError> {
+        let (success, data) = self.results.get(handle.index).ok_or_else(|| {
+            SdkError::OperationFailed(format!("multicall result {} missing", handle.index))
+        })?;
+        if !success {
+            return Err(SdkError::Contract(format!(
+                "multicall call {} reverted",
+                handle.index
+            )));
+        }
+        C::abi_decode_returns(data).map_err(|e| SdkError::Contract(e.to_string()))
+    }
+}
+
+pub(crate) fn avatar_token_id(avatar: Address) -> U256 {
+    U256::from_be_slice(avatar.as_slice())
+}
 
 /// Core contract bundle for the Circles SDK.
 #[derive(Clone)]
@@ -86,4 +205,153 @@ impl Core {
     pub fn referrals_module(&self) -> ReferralsModule::ReferralsModuleInstance<RootProvider> {
         ReferralsModule::new(self.config.referrals_module_address, self.provider())
     }
+
+    /// Subscription module contract instance at `address`.
+    pub fn subscription_module(
+        &self,
+        address: alloy_primitives::Address,
+    ) -> SubscriptionModule::SubscriptionModuleInstance<RootProvider> {
+        SubscriptionModule::new(address, self.provider())
+    }
+
+    /// Multicall3 contract instance at the canonical address.
+    pub fn multicall3(&self) -> IMulticall3::IMulticall3Instance<RootProvider> {
+        IMulticall3::new(MULTICALL3_ADDRESS, self.provider())
+    }
+
+    /// Execute a batch of read calls in one `eth_call` through Multicall3.
+    pub async fn multicall(&self, batch: MulticallBatch) -> Result<MulticallResults, SdkError> {
+        if batch.is_empty() {
+            return Ok(MulticallResults::default());
+        }
+        let calls = batch
+            .calls
+            .into_iter()
+            .map(|(target, allow_failure, call_data)| IMulticall3::Call3 {
+                target,
+                allowFailure: allow_failure,
+                callData: call_data,
+            })
+            .collect();
+        let results = self
+            .multicall3()
+            .aggregate3(calls)
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))?;
+        Ok(MulticallResults {
+            results: results
+                .into_iter()
+                .map(|result| (result.success, result.returnData))
+                .collect(),
+        })
+    }
+
+    /// Hub v2 balances of `account` for each avatar token, in one round-trip.
+    pub async fn hub_balances(
+        &self,
+        account: Address,
+        avatars: &[Address],
+    ) -> Result<Vec<U256>, SdkError> {
+        let hub = self.config.v2_hub_address;
+        let mut batch = MulticallBatch::new();
+        let handles: Vec<_> = avatars
+            .iter()
+            .map(|avatar| {
+                batch.add(
+                    hub,
+                    HubV2::balanceOfCall {
+                        _account: account,
+                        _id: avatar_token_id(*avatar),
+                    },
+                )
+            })
+            .collect();
+        let results = self.multicall(batch).await?;
+        handles.into_iter().map(|h| results.get(h)).collect()
+    }
+
+    /// Hub v2 `isApprovedForAll(owner, operator)` for each operator, in one round-trip.
+    pub async fn hub_approvals(
+        &self,
+        owner: Address,
+        operators: &[Address],
+    ) -> Result<Vec<bool>, SdkError> {
+        let hub = self.config.v2_hub_address;
+        let mut batch = MulticallBatch::new();
+        let handles: Vec<_> = operators
+            .iter()
+            .map(|operator| {
+                batch.add(
+                    hub,
+                    HubV2::isApprovedForAllCall {
+                        _account: owner,
+                        _operator: *operator,
+                    },
+                )
+            })
+            .collect();
+        let results = self.multicall(batch).await?;
+        handles.into_iter().map(|h| results.get(h)).collect()
+    }
+
+    /// Invitation escrow inviters for each invitee, in one round-trip.
+    pub async fn escrow_inviters(
+        &self,
+        invitees: &[Address],
+    ) -> Result<Vec<Vec<Address>>, SdkError> {
+        let escrow = self.config.invitation_escrow_address;
+        let mut batch = MulticallBatch::new();
+        let handles: Vec<_> = invitees
+            .iter()
+            .map(|invitee| {
+                batch.add(
+                    escrow,
+                    InvitationEscrow::getInvitersCall { invitee: *invitee },
+                )
+            })
+            .collect();
+        let results = self.multicall(batch).await?;
+        handles.into_iter().map(|h| results.get(h)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multicall_results_decode_through_typed_handles() {
+        let hub = Address::repeat_byte(0x02);
+        let mut batch = MulticallBatch::new();
+        let balance = batch.add(
+            hub,
+            HubV2::balanceOfCall {
+                _account: Address::repeat_byte(0x11),
+                _id: avatar_token_id(Address::repeat_byte(0x22)),
+            },
+        );
+        let approved = batch.add_allow_failure(
+            hub,
+            HubV2::isApprovedForAllCall {
+                _account: Address::repeat_byte(0x11),
+                _operator: Address::repeat_byte(0x33),
+            },
+        );
+        assert_eq!(batch.len(), 2);
+        assert!(!batch.calls[0].1);
+        assert!(batch.calls[1].1);
+
+        let results = MulticallResults {
+            results: vec![
+                (
+                    true,
+                    Bytes::from(HubV2::balanceOfCall::abi_encode_returns(&U256::from(7u64))),
+                ),
+                (false, Bytes::new()),
+            ],
+        };
+        assert_eq!(results.get(balance).expect("balance"), U256::from(7u64));
+        assert!(matches!(results.get(approved), Err(SdkError::Contract(_))));
+    }
 }
@@ -0,0 +1,272 @@
+//! Human-readable summaries of prepared calldata for audit logs and confirmation screens.
+//!
+//! Calldata is decoded against the bundled Circles ABIs. A call to one of the configured
+//! deployment addresses is decoded with that contract's ABI; calls elsewhere (groups,
+//! ERC20 wrappers, subscription modules) are matched by selector against the contracts
+//! that have no fixed address and always name the target. Any other target is marked as
+//! an unknown contract, so its calldata cannot pass for a call to the protocol.
+
+use crate::PreparedTransaction;
+use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
+use alloy_json_abi::{Function, JsonAbi};
+use alloy_primitives::{Address, hex};
+use circles_abis::JSON_ABIS;
+use circles_types::CirclesConfig;
+use once_cell::sync::Lazy;
+
+/// Bundled ABIs, parsed once, in [`JSON_ABIS`] order.
+static ABIS: Lazy<Vec<(&'static str, JsonAbi)>> = Lazy::new(|| {
+    JSON_ABIS
+        .iter()
+        .map(|(name, json)| (*name, serde_json::from_str(json).expect("bundled ABI")))
+        .collect()
+});
+
+/// Contracts deployed per avatar or group rather than at a config address.
+const UNBOUND_CONTRACTS: &[&str] = &[
+    "BaseGroup",
+    "DemurrageCircles",
+    "InflationaryCircles",
+    "StandardVault",
+    "SubscriptionModule",
+    "TokenV1",
+];
+
+/// Byte arguments longer than this are shortened to a prefix and their length.
+const MAX_BYTES_SHOWN: usize = 32;
+/// Arrays longer than this are shown as an item count.
+const MAX_ITEMS_SHOWN: usize = 4;
+
+impl PreparedTransaction {
+    /// One-line summary of the call, e.g. `HubV2.trust(trustReceiver=0x…, expiry=…)`,
+    /// with contract addresses resolved against the deployment in `config`
+    /// ([`Sdk::describe`](crate::Sdk::describe) passes the SDK's own).
+    ///
+    /// Calldata that matches no bundled ABI is shown as the raw selector and target.
+    pub fn describe(&self, config: &CirclesConfig) -> String {
+        let mut summary = match self.data.split_first_chunk::<4>() {
+            None if self.data.is_empty() => format!("transfer to {}", self.to),
+            None => format!(
+                "call {} with {} bytes of calldata",
+                self.to,
+                self.data.len()
+            ),
+            Some((selector, args)) => {
+                decode_call(config, self.to, *selector, args).unwrap_or_else(|| {
+                    format!(
+                        "call {} selector {} ({} bytes)",
+                        self.to,
+                        hex::encode_prefixed(selector),
+                        self.data.len()
+                    )
+                })
+            }
+        };
+        if let Some(value) = self.value.filter(|value| !value.is_zero()) {
+            summary.push_str(&format!(" with {value} wei"));
+        }
+        summary
+    }
+}
+
+fn decode_call(
+    config: &CirclesConfig,
+    to: Address,
+    selector: [u8; 4],
+    args: &[u8],
+) -> Option<String> {
+    let bound = bound_contract(config, to);
+    let candidates = match bound {
+        Some(name) => matching_functions(selector, |contract| contract == name),
+        None => matching_functions(selector, |contract| UNBOUND_CONTRACTS.contains(&contract)),
+    };
+    // Fall back to every ABI, e.g. for a deployment whose addresses differ from `config`;
+    // the target is then not known to be that contract.
+    let (candidates, known) = if candidates.is_empty() {
+        (matching_functions(selector, |_| true), false)
+    } else {
+        (candidates, true)
+    };
+    let (_, function) = candidates.first()?;
+    let values = function.abi_decode_input(args).ok()?;
+    // Wrappers share signatures (`unwrap`, ERC20 transfers); name every contract that fits.
+    let contracts = candidates
+        .iter()
+        .map(|(contract, _)| *contract)
+        .collect::<Vec<_>>()
+        .join("/");
+    let arguments = function
+        .inputs
+        .iter()
+        .zip(&values)
+        .map(|(param, value)| match param.name.trim_start_matches('_') {
+            "" => format_value(value),
+            name => format!("{name}={}", format_value(value)),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let call = format!("{}({arguments})", function.name);
+    Some(match (bound, known) {
+        (Some(_), _) => format!("{contracts}.{call}"),
+        (None, true) => format!("{contracts} {to}.{call}"),
+        (None, false) => format!("unknown contract {to}: {call} (selector of {contracts})"),
+    })
+}
+
+fn matching_functions(
+    selector: [u8; 4],
+    include: impl Fn(&str) -> bool,
+) -> Vec<(&'static str, &'static Function)> {
+    ABIS.iter()
+        .filter(|(contract, _)| include(contract))
+        .filter_map(|(contract, abi)| {
+            abi.functions()
+                .find(|function| function.selector() == selector)
+                .map(|function| (*contract, function))
+        })
+        .collect()
+}
+
+fn bound_contract(config: &CirclesConfig, to: Address) -> Option<&'static str> {
+    [
+        (config.v2_hub_address, "HubV2"),
+        (config.v1_hub_address, "HubV1"),
+        (config.name_registry_address, "NameRegistry"),
+        (config.standard_treasury, "StandardTreasury"),
+        (config.base_group_factory_address, "BaseGroupFactory"),
+        (config.lift_erc20_address, "LiftERC20"),
+        (config.invitation_escrow_address, "InvitationEscrow"),
+        (config.invitation_farm_address, "InvitationFarm"),
+        (config.referrals_module_address, "ReferralsModule"),
+    ]
+    .into_iter()
+    .find(|(address, _)| *address == to && !address.is_zero())
+    .map(|(_, name)| name)
+}
+
+fn format_value(value: &DynSolValue) -> String {
+    match value {
+        DynSolValue::Address(address) => address.to_string(),
+        DynSolValue::Bool(flag) => flag.to_string(),
+        DynSolValue::Uint(number, _) => number.to_string(),
+        DynSolValue::Int(number, _) => number.to_string(),
+        DynSolValue::FixedBytes(word, size) => hex::encode_prefixed(&word[..*size]),
+        DynSolValue::Bytes(bytes) if bytes.len() > MAX_BYTES_SHOWN => format!(
+            "{}…({} bytes)",
+            hex::encode_prefixed(&bytes[..8]),
+            bytes.len()
+        ),
+        DynSolValue::Bytes(bytes) => hex::encode_prefixed(bytes),
+        DynSolValue::String(text) => format!("{text:?}"),
+        DynSolValue::Array(items) | DynSolValue::FixedArray(items)
+            if items.len() > MAX_ITEMS_SHOWN =>
+        {
+            format!("[{} items]", items.len())
+        }
+        DynSolValue::Array(items) | DynSolValue::FixedArray(items) => {
+            format!("[{}]", join_values(items))
+        }
+        DynSolValue::Tuple(items) => format!("({})", join_values(items)),
+        other => format!("{other:?}"),
+    }
+}
+
+fn join_values(items: &[DynSolValue]) -> String {
+    items
+        .iter()
+        .map(format_value)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::call_to_tx;
+    use crate::config::GNOSIS_MAINNET;
+    use alloy_primitives::{Address, B256, Bytes, U256, address, aliases::U96};
+    use circles_abis::{DemurrageCircles, HubV2};
+
+    const HUB: Address = address!("c12C1E50ABB450d6205Ea2C3Fa861b3B834d13e8");
+
+    #[test]
+    fn hub_calls_are_decoded_with_parameter_names() {
+        let friend = Address::repeat_byte(0xab);
+        let tx = call_to_tx(
+            HUB,
+            HubV2::trustCall {
+                _trustReceiver: friend,
+                _expiry: U96::from(1_700_000_000u64),
+            },
+            None,
+        );
+        assert_eq!(
+            tx.describe(&GNOSIS_MAINNET),
+            format!("HubV2.trust(trustReceiver={friend}, expiry=1700000000)")
+        );
+    }
+
+    #[test]
+    fn wrapper_calls_name_every_matching_contract() {
+        let wrapper = Address::repeat_byte(0x11);
+        let tx = call_to_tx(
+            wrapper,
+            DemurrageCircles::unwrapCall {
+                _amount: U256::from(5),
+            },
+            Some(U256::from(7)),
+        );
+        assert_eq!(
+            tx.describe(&GNOSIS_MAINNET),
+            format!("DemurrageCircles/InflationaryCircles {wrapper}.unwrap(amount=5) with 7 wei")
+        );
+    }
+
+    #[test]
+    fn protocol_selectors_elsewhere_are_marked_unknown() {
+        let target = Address::repeat_byte(0x22);
+        let inviter = Address::repeat_byte(0xab);
+        let tx = call_to_tx(
+            target,
+            HubV2::registerHumanCall {
+                _inviter: inviter,
+                _metadataDigest: B256::ZERO,
+            },
+            None,
+        );
+        let call = format!(
+            "registerHuman(inviter={inviter}, metadataDigest={})",
+            B256::ZERO
+        );
+        assert_eq!(
+            tx.describe(&GNOSIS_MAINNET),
+            format!("unknown contract {target}: {call} (selector of HubV2)")
+        );
+
+        // The same call is a hub call for a deployment whose hub lives at `target`.
+        let mut config = GNOSIS_MAINNET.clone();
+        config.v2_hub_address = target;
+        assert_eq!(tx.describe(&config), format!("HubV2.{call}"));
+    }
+
+    #[test]
+    fn unknown_calldata_falls_back_to_selector() {
+        let tx = crate::PreparedTransaction {
+            to: HUB,
+            data: Bytes::from(vec![0xde, 0xad, 0xbe, 0xef, 0x00]),
+            value: None,
+        };
+        assert_eq!(
+            tx.describe(&GNOSIS_MAINNET),
+            format!("call {HUB} selector 0xdeadbeef (5 bytes)")
+        );
+        let plain = crate::PreparedTransaction {
+            to: HUB,
+            data: Bytes::new(),
+            value: Some(U256::from(1)),
+        };
+        assert_eq!(
+            plain.describe(&GNOSIS_MAINNET),
+            format!("transfer to {HUB} with 1 wei")
+        );
+    }
+}
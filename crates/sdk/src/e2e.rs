@@ -0,0 +1,270 @@
+//! Anvil-backed end-to-end harness (enable the `e2e` feature).
+//!
+//! [`ForkedGnosis`] spawns Anvil forking Gnosis Chain and hands out runners for funded
+//! dev accounts or impersonated on-chain avatars. The scenario helpers run the same
+//! `TransferBuilder` plans and registration flows the SDK uses against mainnet, so
+//! downstream crates can reuse them in their own test suites.
+//!
+//! Only transaction execution and contract reads go to the fork. Pathfinding and
+//! indexer reads still use `circles_rpc_url`, which follows the real chain, so fork
+//! near the head (the default) to keep both views consistent.
+
+use crate::{
+    ContractRunner, EoaContractRunner, HumanAvatar, PreparedTransaction, RegistrationResult,
+    RunnerError, Sdk, SdkError, SubmittedTx, call_to_tx, config,
+    runner::{call_error, prepared_to_request, tx_hash_to_bytes},
+};
+use alloy_node_bindings::{Anvil, AnvilInstance};
+use alloy_primitives::{Address, Bytes, U256, aliases::U96};
+use alloy_provider::{Identity, Provider, ProviderBuilder, RootProvider};
+use async_trait::async_trait;
+use circles_abis::HubV2;
+use circles_transfers::TransferBuilder;
+use circles_types::{AdvancedTransferOptions, CirclesConfig};
+use serde_json::{Value, json};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+
+/// Environment variable overriding the Gnosis Chain RPC used as fork source.
+pub const FORK_URL_ENV: &str = "CIRCLES_E2E_FORK_URL";
+/// Fork source used when [`FORK_URL_ENV`] is unset.
+pub const DEFAULT_FORK_URL: &str = "https://rpc.gnosischain.com";
+
+/// xDAI given to accounts that need gas (100 xDAI).
+const GAS_FUNDING: U256 = U256::from_limbs([0x6bc75e2d63100000, 0x5, 0, 0]);
+
+/// Whether an `anvil` binary is on `PATH`; tests should skip when it is not.
+pub fn anvil_available() -> bool {
+    Command::new("anvil")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok()
+}
+
+/// Options for [`ForkedGnosis::spawn`].
+#[derive(Debug, Clone, Default)]
+pub struct ForkOptions {
+    /// Fork source; defaults to [`FORK_URL_ENV`] or [`DEFAULT_FORK_URL`].
+    pub fork_url: Option<String>,
+    /// Block to fork at; defaults to the latest block.
+    pub fork_block: Option<u64>,
+    /// Base config; defaults to [`config::gnosis_mainnet`]. `chain_rpc_url` is replaced
+    /// with the Anvil endpoint.
+    pub config: Option<CirclesConfig>,
+}
+
+/// Running Anvil fork of Gnosis Chain. The node stops when this is dropped.
+pub struct ForkedGnosis {
+    anvil: AnvilInstance,
+    provider: RootProvider,
+    config: CirclesConfig,
+}
+
+impl ForkedGnosis {
+    /// Spawn Anvil forking Gnosis Chain.
+    pub fn spawn(options: ForkOptions) -> Result<Self, SdkError> {
+        let fork_url = options
+            .fork_url
+            .or_else(|| std::env::var(FORK_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_FORK_URL.to_string());
+        let mut anvil = Anvil::new().fork(fork_url);
+        if let Some(block) = options.fork_block {
+            anvil = anvil.fork_block_number(block);
+        }
+        let anvil = anvil
+            .try_spawn()
+            .map_err(|e| SdkError::OperationFailed(format!("anvil failed to start: {e}")))?;
+
+        let provider =
+            ProviderBuilder::<Identity, Identity>::default().connect_http(anvil.endpoint_url());
+        let mut config = options.config.unwrap_or_else(config::gnosis_mainnet);
+        config.chain_rpc_url = Some(anvil.endpoint());
+        Ok(Self {
+            anvil,
+            provider,
+            config,
+        })
+    }
+
+    /// HTTP endpoint of the fork.
+    pub fn endpoint(&self) -> String {
+        self.anvil.endpoint()
+    }
+
+    /// Circles config whose chain RPC points at the fork.
+    pub fn config(&self) -> &CirclesConfig {
+        &self.config
+    }
+
+    /// Read provider connected to the fork.
+    pub fn provider(&self) -> &RootProvider {
+        &self.provider
+    }
+
+    /// SDK wired to the fork, optionally with a runner for write paths.
+    pub fn sdk(&self, runner: Option<Arc<dyn ContractRunner>>) -> Result<Sdk, SdkError> {
+        Sdk::new(self.config.clone(), runner)
+    }
+
+    /// Set `account`'s xDAI balance via `anvil_setBalance`.
+    pub async fn fund(&self, account: Address, amount: U256) -> Result<(), SdkError> {
+        self.anvil_call("anvil_setBalance", json!([account, amount]))
+            .await
+    }
+
+    /// Runner signing for Anvil dev account `index` (funded with 10k xDAI by Anvil).
+    pub async fn dev_runner(&self, index: usize) -> Result<EoaContractRunner, SdkError> {
+        let key = self.anvil.keys().get(index).ok_or_else(|| {
+            SdkError::OperationFailed(format!("anvil has no dev account {index}"))
+        })?;
+        let private_key = format!("0x{}", hex::encode(key.to_bytes()));
+        Ok(EoaContractRunner::connect(&self.endpoint(), &private_key).await?)
+    }
+
+    /// Impersonate an existing account (e.g. a registered avatar) and fund it for gas.
+    pub async fn impersonate(&self, account: Address) -> Result<ImpersonatedRunner, SdkError> {
+        self.anvil_call("anvil_impersonateAccount", json!([account]))
+            .await?;
+        self.fund(account, GAS_FUNDING).await?;
+        Ok(ImpersonatedRunner {
+            provider: self.provider.clone(),
+            sender: account,
+        })
+    }
+
+    /// Plan an advanced transfer with `TransferBuilder` and execute it as `from`.
+    pub async fn execute_transfer(
+        &self,
+        from: Address,
+        to: Address,
+        amount: U256,
+        options: Option<AdvancedTransferOptions>,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        let plan = TransferBuilder::new(self.config.clone())?
+            .construct_advanced_transfer(from, to, amount, options)
+            .await?;
+        let txs = plan
+            .into_iter()
+            .map(|tx| PreparedTransaction {
+                to: tx.to,
+                data: tx.data,
+                value: Some(tx.value),
+            })
+            .collect();
+        Ok(self.impersonate(from).await?.send_transactions(txs).await?)
+    }
+
+    /// Register dev account `index` as a human invited by `inviter`.
+    ///
+    /// `inviter` is impersonated to trust the invitee first, then the invitee runs the
+    /// SDK registration flow with an already-pinned `profile_cid`.
+    pub async fn register_invited_human(
+        &self,
+        inviter: Address,
+        index: usize,
+        profile_cid: &str,
+    ) -> Result<RegistrationResult<HumanAvatar>, SdkError> {
+        let invitee = self.dev_runner(index).await?;
+        let trust = call_to_tx(
+            self.config.v2_hub_address,
+            HubV2::trustCall {
+                _trustReceiver: invitee.sender_address(),
+                _expiry: U96::MAX,
+            },
+            None,
+        );
+        let trusted = self
+            .impersonate(inviter)
+            .await?
+            .send_transactions(vec![trust])
+            .await?;
+        if !trusted.iter().all(|tx| tx.success) {
+            return Err(SdkError::OperationFailed(
+                "inviter trust transaction reverted".to_string(),
+            ));
+        }
+
+        let sdk = self.sdk(Some(Arc::new(invitee)))?;
+        sdk.register().as_human(inviter, profile_cid).await
+    }
+
+    async fn anvil_call(&self, method: &'static str, params: Value) -> Result<(), SdkError> {
+        self.provider
+            .raw_request::<_, Value>(method.into(), params)
+            .await
+            .map(|_| ())
+            .map_err(|e| SdkError::OperationFailed(format!("{method}: {e}")))
+    }
+}
+
+/// Runner sending unsigned transactions from an account impersonated on Anvil.
+#[derive(Clone, Debug)]
+pub struct ImpersonatedRunner {
+    provider: RootProvider,
+    sender: Address,
+}
+
+#[async_trait]
+impl ContractRunner for ImpersonatedRunner {
+    fn sender_address(&self) -> Address {
+        self.sender
+    }
+
+    async fn estimate_gas(&self, tx: PreparedTransaction) -> Result<u64, RunnerError> {
+        self.provider
+            .estimate_gas(prepared_to_request(Some(self.sender), tx))
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))
+    }
+
+    async fn call(&self, tx: PreparedTransaction) -> Result<Bytes, RunnerError> {
+        self.provider
+            .call(prepared_to_request(Some(self.sender), tx))
+            .await
+            .map_err(call_error)
+    }
+
+    /// Sends sequentially and stops at the first reverted transaction.
+    async fn send_transactions(
+        &self,
+        txs: Vec<PreparedTransaction>,
+    ) -> Result<Vec<SubmittedTx>, RunnerError> {
+        let mut submitted = Vec::with_capacity(txs.len());
+        for (index, tx) in txs.into_iter().enumerate() {
+            let receipt = self
+                .provider
+                .send_transaction(prepared_to_request(Some(self.sender), tx))
+                .await
+                .map_err(|err| RunnerError::Transport(err.to_string()))?
+                .get_receipt()
+                .await
+                .map_err(|err| RunnerError::Transport(err.to_string()))?;
+            let success = receipt.status();
+            submitted.push(SubmittedTx {
+                tx_hash: tx_hash_to_bytes(receipt.transaction_hash),
+                success,
+                index: Some(index),
+            });
+            if !success {
+                break;
+            }
+        }
+        Ok(submitted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GAS_FUNDING;
+    use alloy_primitives::U256;
+
+    #[test]
+    fn gas_funding_is_one_hundred_xdai() {
+        assert_eq!(
+            GAS_FUNDING,
+            U256::from(100u64) * U256::from(10u64).pow(U256::from(18u64))
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! Waiting for the Circles indexer to catch up with submitted transactions.
+
+use crate::runner::RunnerError;
+use crate::{Sdk, SdkError, SubmittedTx};
+use alloy_network::ReceiptResponse;
+use alloy_primitives::B256;
+use alloy_provider::Provider;
+use std::time::{Duration, Instant};
+
+/// Interval between receipt and indexer-height polls.
+const INDEX_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// What [`Sdk::await_indexed`] waits for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexTarget {
+    /// The block that included this transaction, once it is mined.
+    Tx(B256),
+    /// A block number.
+    Block(u64),
+}
+
+impl From<B256> for IndexTarget {
+    fn from(tx_hash: B256) -> Self {
+        Self::Tx(tx_hash)
+    }
+}
+
+impl From<u64> for IndexTarget {
+    fn from(block: u64) -> Self {
+        Self::Block(block)
+    }
+}
+
+/// Wait for `target` to be indexed; a `timeout` of `None` waits indefinitely.
+pub(crate) async fn await_indexed(
+    sdk: &Sdk,
+    target: IndexTarget,
+    timeout: Option<Duration>,
+) -> Result<u64, SdkError> {
+    let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+    let expired = || deadline.is_some_and(|deadline| Instant::now() >= deadline);
+    let block = match target {
+        IndexTarget::Block(block) => block,
+        IndexTarget::Tx(tx_hash) => loop {
+            if let Some(block) = mined_block(sdk, tx_hash).await? {
+                break block;
+            }
+            if expired() {
+                return Err(SdkError::IndexerTimeout {
+                    block: None,
+                    after: timeout.unwrap_or_default(),
+                });
+            }
+            tokio::time::sleep(INDEX_POLL_INTERVAL).await;
+        },
+    };
+
+    // Fail fast when the indexer is unreachable rather than polling until the deadline.
+    let health = sdk.rpc.health();
+    health.health().await?;
+    loop {
+        if health
+            .indexed_block_number()
+            .await?
+            .is_some_and(|indexed| indexed >= block)
+        {
+            return Ok(block);
+        }
+        if expired() {
+            return Err(SdkError::IndexerTimeout {
+                block: Some(block),
+                after: timeout.unwrap_or_default(),
+            });
+        }
+        tokio::time::sleep(INDEX_POLL_INTERVAL).await;
+    }
+}
+
+/// Wait until the last of `sent` is indexed, for at most the config's `timeouts.indexing`;
+/// runners that report no usable hash are skipped.
+pub(crate) async fn await_submitted(sdk: &Sdk, sent: &[SubmittedTx]) -> Result<(), SdkError> {
+    match sent.last().and_then(SubmittedTx::hash) {
+        Some(tx_hash) => await_indexed(sdk, tx_hash.into(), sdk.config.timeouts.indexing)
+            .await
+            .map(|_| ()),
+        None => Ok(()),
+    }
+}
+
+/// Wording for [`SdkError::IndexerTimeout`].
+pub(crate) fn describe_target(block: &Option<u64>) -> String {
+    match block {
+        Some(block) => format!("block {block}"),
+        None => "the transaction's block (not mined)".to_string(),
+    }
+}
+
+/// Inclusion block of `tx_hash`, asking the runner first and the chain RPC when the runner
+/// cannot look up receipts.
+async fn mined_block(sdk: &Sdk, tx_hash: B256) -> Result<Option<u64>, SdkError> {
+    if let Some(runner) = &sdk.runner {
+        match runner.transaction_receipt(tx_hash).await {
+            Ok(receipt) => return Ok(receipt.map(|receipt| receipt.block_number)),
+            Err(RunnerError::Unsupported(_)) => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+    let receipt = sdk
+        .core
+        .provider()
+        .get_transaction_receipt(tx_hash)
+        .await
+        .map_err(|e| SdkError::Contract(e.to_string()))?;
+    Ok(receipt.and_then(|receipt| receipt.block_number()))
+}
@@ -34,6 +34,10 @@
 //!
 //! - [`Sdk`] wires together RPC, profile lookups, pathfinding, transfers, and contract bindings.
 //! - [`Avatar`] gives you a typed wrapper after runtime avatar detection.
+//! - [`AvatarOps`] covers balances, trust, transfers, profile, and subscriptions on every avatar
+//!   kind and on [`Avatar`] itself, so generic code can take `impl AvatarOps`.
+//! - [`Avatar::address`], [`Avatar::info`], `as_human`/`as_organisation`/`as_group`, and
+//!   `TryFrom<Avatar>` for each wrapper avoid matching just to reach one variant.
 //! - [`ContractRunner`] is only required for write paths such as registrations, trust changes,
 //!   transfer submission, explicit ERC20/ERC1155 transfer execution, and ERC20 wrapper wrap/unwrap execution.
 //! - [`SafeContractRunner`] and [`EoaContractRunner`] are the built-in execution backends for
@@ -42,12 +46,38 @@
 //!   runner itself.
 //! - [`SafeExecutionBuilder`] is the browser/external-signature foundation for Safe-backed
 //!   flows: it prepares the canonical Safe payload/hash without requiring a local private key.
+//! - [`NonceManagedRunner`] wraps any runner to pin nonces locally, serialize rapid submissions,
+//!   and replace (speed up/cancel) pending transactions; [`RunnerConfig`] picks its fee
+//!   strategy, caps, and stuck-transaction retries.
+//! - [`Erc4337Runner`] submits through an ERC-4337 bundler as user operations built for a
+//!   [`SmartAccount`], optionally sponsored by a paymaster configured in [`BundlerConfig`].
+//! - [`RelayRunner`] signs ERC-2771 or Safe payloads and lets a relayer pay for gas, polling
+//!   the relayer's task ids until the on-chain hash is known.
+//! - [`SignerContractRunner`] drives EOA or 1/1 Safe execution from any alloy signer, such as a
+//!   Ledger, and signs Safe transactions as EIP-712 typed data so the device can display them;
+//!   the optional `ledger` feature adds `connect_ledger`/`connect_ledger_safe`.
+//! - [`Sdk::with_environment`] attaches further deployments (say `staging` on Chiado) to one
+//!   SDK and [`Sdk::environment`] picks one per call; every runner is checked against its
+//!   environment's `chain_id` before the first send ([`ChainCheckedRunner`]).
+//! - [`SubmittedTx::wait_for_receipt`] and [`TxMonitor`] track inclusion, confirmations, and
+//!   revert data for submitted hashes.
+//! - [`Sdk::await_indexed`] waits until RPC reads reflect a transaction or block; the
+//!   `register_*` flows use it before fetching the new avatar's info.
 //! - The optional `ws` feature enables WebSocket subscriptions with retry/backoff and HTTP catch-up helpers.
+//! - [`Sdk::resolve`] maps profile names, group symbols, and hex addresses to avatars,
+//!   reporting ambiguous names instead of guessing; the optional `ens` feature adds ENS
+//!   names via `Sdk::with_ens_rpc`.
+//! - The optional `mnemonic` feature encodes referral secrets as BIP-39 phrases
+//!   (`GeneratedReferral::mnemonic`).
 //!
 //! ## Recommended Entry Points
 //!
 //! - [`config::gnosis_mainnet`] for the shared mainnet configuration.
+//! - [`Sdk::verify_config`] to catch wrong-network or stale contract addresses before the
+//!   first write fails.
 //! - [`Sdk::avatar_info`] for a fast read-only probe.
+//! - [`HumanAvatar::snapshot`] and [`Sdk::load_snapshot`] to persist an avatar's state and
+//!   read it back offline.
 //! - [`Sdk::get_avatar`] when you want a typed avatar wrapper.
 //! - [`EoaContractRunner::connect`], [`EoaContractRunner::create`],
 //!   [`SafeContractRunner::connect`], and [`SafeContractRunner::create`] when you want built-in
@@ -60,6 +90,8 @@
 //!   [`BaseGroupAvatar::plan_transfer`] for pathfinding-based transaction planning.
 //! - [`HumanAvatar::plan_direct_transfer`], [`OrganisationAvatar::plan_direct_transfer`], and
 //!   [`BaseGroupAvatar::plan_direct_transfer`] for TS-style direct-send planning.
+//! - [`HumanAvatar::plan_v1_transfer`] for Hub v1 `transferThrough`; `plan_transfer` picks it
+//!   automatically for avatars that never migrated to v2.
 //! - [`HumanAvatar::plan_transfer_erc20`], [`HumanAvatar::transfer_erc20`],
 //!   [`HumanAvatar::plan_transfer_erc1155`], and [`HumanAvatar::transfer_erc1155`] for explicit
 //!   ERC20/ERC1155 transfer helpers when callers already know the token standard.
@@ -75,8 +107,43 @@
 //! - [`Sdk::invitations`] and [`Invitations::generate_invite`] for the dedicated TS-style
 //!   invitation service facade.
 //! - [`Sdk::data`] and [`Data::get_avatar`] for the dedicated TS-style basic-read namespace.
+//! - [`Sdk::trust_path`] to explain how (or whether) trust connects two avatars.
 //! - [`Sdk::data_profile_view`], [`Sdk::data_trust_network_summary`], and
 //!   [`Sdk::data_transaction_history_enriched`] for the newer consolidated RPC read surface.
+//! - [`Sdk::token_registry`] and [`TokenRegistry::resolve_many`] to label every token in a
+//!   balance list with owner, kind, symbol, and profile name.
+//! - [`Sdk::name_registry`] for profile digests and short names without hand-built
+//!   `call_to_tx` calls.
+//! - [`cid`] to turn indexer `cid_v0_digest` values back into fetchable CIDs and to accept
+//!   CIDv1 profile pointers.
+//! - [`Sdk::subscriptions`] and [`Subscriptions::plan_redeem`] for recurring payments:
+//!   create/cancel, list per subscriber, and redeem with a freshly routed flow matrix.
+//! - [`Sdk::accounting_report`] for per-token bookkeeping over a block range, including the
+//!   demurrage charged, as CSV or JSON; [`Sdk::balance_at_block`] rebuilds a single past
+//!   balance the same way and checks it against the hub when the node has the state.
+//! - [`Sdk::portfolio`] to aggregate balances and transaction history of several avatars and
+//!   stream combined [`PortfolioEvent`]s.
+//! - [`RedemptionWorker`] to redeem a set of subscriptions automatically as they fall due.
+//! - [`HumanAvatar::trust_export`] and [`HumanAvatar::trust_import`] to keep an avatar's
+//!   trust list in version control as canonical JSON or CSV ([`TrustList`]).
+//! - [`Sdk::trust_scheduler`] to list outgoing trust expiring soon and renew it in one batch,
+//!   once or periodically; `trust_add_with_duration` trusts for a number of days.
+//! - [`BalanceDeltas`] to turn hub transfer and mint events into signed per-avatar
+//!   [`BalanceDelta`]s, filtered by avatar and token; with `ws`, `BalanceDeltas::stream`
+//!   wraps an event subscription.
+//! - [`EventCursor`] for crash-resilient consumers: persist the cursor yielded with each event
+//!   and continue with `EventsMethods::resume_from` or, with `ws`,
+//!   `Sdk::subscribe_events_ws_from_cursor` without repeating events.
+//! - [`Projector`] and [`Projection`] to fold `CirclesEvent`s into balances, trust lists, and
+//!   group memberships without matching on every event type.
+//! - [`HumanAvatar::plan_resumable_transfer`] and [`TransferPlan::resume`] to run a
+//!   multi-transaction transfer step by step and resume it after a partial failure.
+//! - [`Sdk::assess_avatar`] for pre-trust heuristics: account age, invite chain depth,
+//!   mutual connections with the sender, and recent token velocity.
+//! - [`TrustGraph`] as a local trust-edge cache, built from relations or kept current as a
+//!   [`Projection`]; the `analytics` feature adds degree, mutual-trust, and PageRank metrics.
+//! - [`messages`] for EIP-712 signed invites, profile update intents, and group membership
+//!   attestations that services can verify without a transaction.
 //! - [`Sdk::tokens`] and [`Tokens::get_inflationary_wrapper`] for the dedicated TS-style
 //!   token-helper namespace.
 //! - [`Sdk::inflationary_wrapper`], [`Sdk::demurraged_wrapper`], and
@@ -93,6 +160,24 @@
 //!   invitation-farm facade mirroring the TS SDK service.
 //! - [`HumanAvatar::plan_generate_referrals`] and [`HumanAvatar::generate_referrals`] for
 //!   invitation-farm batch referral planning/execution.
+//! - [`PreparedTransaction::describe`] to show a prepared call as `HubV2.trust(...)` before
+//!   it is signed.
+//! - [`SdkError::kind`], [`SdkError::is_retryable`], and [`SdkError::is_user_error`] to
+//!   drive retries and error messages from an [`ErrorKind`] instead of error strings.
+//!
+//! ## Tracing
+//!
+//! The `tracing` feature (forwarded to `circles-rpc` and `circles-transfers`) wraps SDK
+//! operations in spans so one transfer can be followed from avatar call to JSON-RPC request.
+//! Span names identify the layer and fields identify the operation:
+//!
+//! - `circles.sdk` — avatar and [`Sdk`] operations; fields `method` and `avatar`.
+//! - `circles.transfer` — `TransferBuilder` planning; fields `method` and `avatar` (the sender).
+//! - `circles.rpc` — JSON-RPC requests and paged queries; fields `rpc_method`, plus `block`
+//!   or `table` where the request is block- or table-scoped.
+//!
+//! Spans nest, so `avatar.transfer(..)` yields `circles.sdk{method=transfer}` →
+//! `circles.transfer{method=construct_advanced_transfer}` → `circles.rpc{rpc_method=circlesV2_findPath}`.
 //!
 //! ## Validation
 //!
@@ -100,19 +185,44 @@
 //! - WS helpers: `cargo test -p circles-sdk --features ws`
 //! - Live checks (ignored by default): `RUN_LIVE=1 LIVE_AVATAR=0x... cargo test -p circles-sdk -- --ignored`
 
+mod accounting;
+#[cfg(feature = "analytics")]
+pub mod analytics;
+mod assessment;
 mod avatar;
-mod cid_v0_to_digest;
+mod balance_delta;
+pub mod cid;
 pub mod config;
+mod config_check;
 mod core;
+mod describe;
+#[cfg(feature = "e2e")]
+pub mod e2e;
+mod indexer;
+pub mod messages;
+mod projector;
+mod resolver;
 mod runner;
 mod services;
+mod snapshot;
+mod transfer_plan;
+mod trust_graph;
+mod trust_list;
+mod trust_path;
 #[cfg(feature = "ws")]
 pub mod ws;
+pub use balance_delta::{BalanceDelta, BalanceDeltas};
 pub use services::data::Data;
-pub use services::invitations::Invitations;
+pub use services::invitations::{Invitations, PasskeyClaim};
 pub use services::invite_farm::{
     GenerateInvitesResult, GenerateReferralsResult, GeneratedReferral, InviteFarm,
 };
+pub use services::names::{Names, ShortNameCandidate};
+pub use services::portfolio::{
+    Portfolio, PortfolioBalances, PortfolioEvent, PortfolioHolding, PortfolioToken,
+    PortfolioTransfer, TransferDirection,
+};
+pub use services::redemption_worker::{RedemptionOutcome, RedemptionReport, RedemptionWorker};
 pub use services::referrals::{
     AddKeysError, AddKeysResult, CreateSessionParams, DispenseErrorCode, DispenseResult,
     DistributionSession, DistributionSessionList, DistributionSessionListOptions, Distributions,
@@ -123,14 +233,36 @@ pub use services::referrals::{
     StoreBatchResult, UpdateSessionParams,
 };
 pub use services::registration;
-pub use services::registration::{Registration, RegistrationProfileInput};
+pub use services::registration::{
+    Registration, RegistrationProfileInput, predict_base_group_address,
+};
+pub use services::subscriptions::{RedeemPlan, Subscription, SubscriptionCategory, Subscriptions};
+pub use services::token_registry::{TokenKind, TokenMetadata, TokenRegistry};
 pub use services::tokens::Tokens;
+pub use services::trust_scheduler::{
+    ExpiringTrust, SECONDS_PER_DAY, TrustRenewal, TrustRenewalPlan, TrustScheduler,
+    trust_expiry_after_days,
+};
+pub use transfer_plan::{PlanStep, PlanStepKind, StepStatus, TransferPlan};
+pub use trust_graph::TrustGraph;
+pub use trust_list::{
+    TRUST_LIST_VERSION, TrustImportPlan, TrustImportPolicy, TrustList, TrustListEntry,
+};
+pub use trust_path::TrustHop;
 
+pub use accounting::{AccountingReport, HistoricalBalance, TokenAccount};
 #[cfg(feature = "ws")]
 use alloy_json_rpc::RpcSend;
 use alloy_primitives::Address;
+#[cfg(feature = "ledger")]
+pub use alloy_signer_ledger::{HDPath, LedgerSigner};
+pub use assessment::{AvatarAssessment, TokenVelocity, VELOCITY_WINDOW_SECS};
 pub use avatar::human::{ProxyInviter, ReferralCodePlan};
-pub use avatar::{BaseGroupAvatar, HumanAvatar, OrganisationAvatar};
+pub use avatar::{
+    AvatarOps, BaseGroupAvatar, ConditionCheck, ConditionOutcome, EscrowedInvitation,
+    GroupMintPolicy, GroupTreasury, HumanAvatar, JoinEligibility, MintPolicyKind,
+    MintPolicyViolation, OrganisationAvatar, RedemptionQuote, TreasuryCollateral, TrustDiff,
+};
 use circles_profiles::{Profile, Profiles};
 #[cfg(feature = "ws")]
 use circles_rpc::events::subscription::CirclesSubscription;
@@ -142,16 +274,31 @@ use circles_types::{
     CirclesConfig, EnrichedTransaction, EnrichedTransactionHistoryOptions, EscrowInvitation,
     GroupMembershipRow, GroupQueryParams, GroupRow, GroupTokenHolderRow,
     PagedAggregatedTrustRelationsResponse, PagedProfileSearchResponse, PagedResponse,
-    PagedValidInvitersResponse, ProfileView, SortOrder, TokenBalanceResponse, TokenHolderRow,
-    TransactionHistoryRow, TrustInvitation, TrustNetworkSummary, TrustRelation,
+    PagedValidInvitersResponse, PathfinderConfig, ProfileView, SortOrder, TokenBalanceResponse,
+    TokenHolderRow, TransactionHistoryRow, TrustInvitation, TrustNetworkSummary, TrustRelation,
 };
+pub use circles_types::{Environment, ErrorKind, EventCursor};
+pub use config_check::{ConfigCheck, ConfigCheckStatus, ConfigReport};
 use core::Core;
+pub use core::{MULTICALL3_ADDRESS, MulticallBatch, MulticallHandle, MulticallResults};
+pub use indexer::IndexTarget;
+pub use projector::{
+    AvatarRegistration, MembershipChange, Projection, Projector, TokenTransfer, TrustChange,
+};
+pub use resolver::{NameMatch, NameSource, Resolution};
 pub use runner::{
-    BatchRun, ContractRunner, EoaContractRunner, PreparedSafeExecution, PreparedTransaction,
-    RunnerError, SafeContractRunner, SafeExecutionBuilder, SubmittedTx, call_to_tx,
+    BatchRun, BundlerConfig, ChainCheckedRunner, ContractRunner, ENTRY_POINT_V07,
+    EoaContractRunner, Erc4337Runner, FeeCaps, FeeEstimate, FeePolicy, FeeStrategy,
+    NonceManagedRunner, PaymasterConfig, PendingNonceTx, PreparedSafeExecution,
+    PreparedTransaction, RelayConfig, RelayMode, RelayRunner, RelayTaskState, RelayTaskStatus,
+    RunnerConfig, RunnerError, SafeContractRunner, SafeExecutionBuilder, SignerContractRunner,
+    SimpleAccount, SmartAccount, StuckTxRetry, SubmittedTx, TxMonitor, TxMonitorEvent, TxOverrides,
+    TxReceiptInfo, UserOperation, call_to_tx,
 };
 #[cfg(feature = "ws")]
 use serde_json::to_value;
+pub use snapshot::{AvatarSnapshot, SNAPSHOT_VERSION};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use thiserror::Error;
 
@@ -159,10 +306,15 @@ use thiserror::Error;
 ///
 /// Registration helpers may return prepared txs without sending if no runner is provided.
 pub struct RegistrationResult<T> {
-    /// Best-effort typed avatar returned after registration succeeds.
+    /// Typed avatar, when the indexer already knew it on return; otherwise call
+    /// [`resolve_avatar`](Self::resolve_avatar) once it catches up.
     pub avatar: Option<T>,
     /// Submitted transactions returned by the runner.
     pub txs: Vec<SubmittedTx>,
+    /// Why the initial trust that follows a group registration was not sent. The registration
+    /// itself went through; send the trust again once the group is known.
+    pub trust_error: Option<SdkError>,
+    pub(crate) pending: Option<services::registration::PendingAvatar<T>>,
 }
 
 /// High-level SDK errors.
@@ -174,12 +326,14 @@ pub enum SdkError {
     Profiles(#[from] circles_profiles::ProfilesError),
     #[error("referrals error: {0}")]
     Referrals(#[from] services::referrals::ReferralsError),
+    #[error("pathfinder error: {0}")]
+    Pathfinder(#[from] circles_pathfinder::PathfinderError),
     #[error("transfers error: {0}")]
     Transfers(#[from] circles_transfers::TransferError),
     #[error("runner error: {0}")]
     Runner(#[from] RunnerError),
     #[error("cid error: {0}")]
-    Cid(#[from] cid_v0_to_digest::CidError),
+    Cid(#[from] cid::CidError),
     #[error("contract call error: {0}")]
     Contract(String),
     #[error("operation failed: {0}")]
@@ -192,14 +346,70 @@ pub enum SdkError {
     AvatarNotFound(Address),
     #[error("invalid registration input: {0}")]
     InvalidRegistration(String),
+    #[error("indexer did not reach {} within {after:?}", indexer::describe_target(.block))]
+    IndexerTimeout {
+        /// Target block, or `None` when the transaction was not mined in time.
+        block: Option<u64>,
+        after: std::time::Duration,
+    },
     #[error("websocket subscription failed after {attempts} attempts: {reason}")]
     WsSubscribeFailed { attempts: usize, reason: String },
+    #[error("no environment named {0:?}")]
+    UnknownEnvironment(String),
+    #[error("trust duration must be at least one day")]
+    ZeroTrustDuration,
+    #[error("group mint would fail: {0}")]
+    MintPolicy(#[from] MintPolicyViolation),
+}
+
+impl SdkError {
+    /// Coarse classification for retry and UX policies; wrapped client errors keep their
+    /// own kind.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Rpc(err) => err.kind(),
+            Self::Profiles(err) => err.kind(),
+            Self::Referrals(err) => err.kind(),
+            Self::Pathfinder(err) => err.kind(),
+            Self::Transfers(err) => err.kind(),
+            Self::Runner(err) => err.kind(),
+            Self::Cid(_) | Self::InvalidRegistration(_) | Self::ZeroTrustDuration => {
+                ErrorKind::InvalidInput
+            }
+            Self::AvatarNotFound(_) => ErrorKind::NotFound,
+            Self::MissingRunner | Self::MissingSender | Self::UnknownEnvironment(_) => {
+                ErrorKind::Configuration
+            }
+            Self::IndexerTimeout { .. } => ErrorKind::Timeout,
+            Self::WsSubscribeFailed { .. } => ErrorKind::Network,
+            Self::MintPolicy(MintPolicyViolation::InsufficientCollateral { .. }) => {
+                ErrorKind::InsufficientFunds
+            }
+            Self::MintPolicy(MintPolicyViolation::RejectedByPolicy { .. }) => ErrorKind::Rejected,
+            Self::MintPolicy(_) => ErrorKind::InvalidInput,
+            Self::Contract(_) | Self::OperationFailed(_) => ErrorKind::Other,
+        }
+    }
+
+    /// Whether repeating the operation later may succeed.
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+
+    /// Whether the user can fix the failure by changing what they asked for.
+    pub fn is_user_error(&self) -> bool {
+        self.kind().is_user_error()
+    }
 }
 
 /// Top-level SDK orchestrator.
 ///
 /// Construct this once per config/runner pair and reuse it across read and write flows.
+/// Further deployments can be attached with [`Sdk::with_environment`] and picked per call
+/// through [`Sdk::environment`].
 pub struct Sdk {
+    pub(crate) environment: String,
+    pub(crate) environments: BTreeMap<String, Sdk>,
     pub(crate) config: CirclesConfig,
     pub(crate) rpc: Arc<CirclesRpc>,
     pub(crate) profiles: Profiles,
@@ -207,24 +417,63 @@ pub struct Sdk {
     pub(crate) core: Arc<Core>,
     pub(crate) runner: Option<Arc<dyn ContractRunner>>,
     pub(crate) sender_address: Option<Address>,
+    pub(crate) token_registry: TokenRegistry,
+    #[cfg(feature = "ens")]
+    pub(crate) ens: Option<resolver::ens::EnsResolver>,
 }
 
 impl Sdk {
     /// Create a new SDK instance. Provide a runner for write operations; omit for read-only.
+    ///
+    /// RPC, profile, and referral requests are bounded by `config.timeouts`. Reads go to
+    /// `config.replica_rpc_url` and event backfills and table exports to
+    /// `config.archive_rpc_url` when set, otherwise to `config.circles_rpc_url`. The runner is
+    /// wrapped in a [`ChainCheckedRunner`], so sends fail with
+    /// [`RunnerError::ChainMismatch`] when it is not on `config.chain_id`. The environment
+    /// is named after the chain, see [`Environment::from_config`].
     pub fn new(
         config: CirclesConfig,
         runner: Option<Arc<dyn ContractRunner>>,
     ) -> Result<Self, SdkError> {
+        Self::from_environment(Environment::from_config(config), runner)
+    }
+
+    /// Create an SDK instance for a named environment.
+    pub fn from_environment(
+        environment: Environment,
+        runner: Option<Arc<dyn ContractRunner>>,
+    ) -> Result<Self, SdkError> {
+        let Environment { name, config } = environment;
+        let runner = runner.map(|runner| {
+            Arc::new(ChainCheckedRunner::new(runner, config.chain_id)) as Arc<dyn ContractRunner>
+        });
         let sender_address = runner.as_ref().map(|r| r.sender_address());
         let core = Arc::new(Core::new(config.clone()));
-        let rpc = Arc::new(CirclesRpc::try_from_http(&config.circles_rpc_url)?);
-        let profiles = Profiles::new(config.effective_profile_service_url())?;
+        let mut rpc = CirclesRpc::try_from_http_with_timeouts(
+            config.effective_replica_rpc_url(),
+            config.timeouts,
+        )?;
+        if let Some(archive) = config.archive_rpc_url.as_deref() {
+            rpc = rpc.with_archive(
+                CirclesRpc::try_from_http_with_timeouts(archive, config.timeouts)?.client,
+            );
+        }
+        let rpc = Arc::new(rpc);
+        let profiles =
+            Profiles::with_timeouts(config.effective_profile_service_url(), config.timeouts)?;
         let referrals = config
             .referrals_service_url
             .as_deref()
-            .map(|url| Referrals::new(url, core.clone()))
+            .map(|url| {
+                let client = runner::http_client(&config.timeouts)
+                    .map_err(services::referrals::ReferralsError::from)?;
+                Referrals::with_client(url, core.clone(), client)
+            })
             .transpose()?;
+        let token_registry = TokenRegistry::new(rpc.clone(), core.clone());
         Ok(Self {
+            environment: name,
+            environments: BTreeMap::new(),
             rpc,
             profiles,
             referrals,
@@ -232,9 +481,68 @@ impl Sdk {
             core,
             runner,
             sender_address,
+            token_registry,
+            #[cfg(feature = "ens")]
+            ens: None,
         })
     }
 
+    /// Bound every pathfinder request (direct, per avatar, and inside transfer planning)
+    /// with a timeout and a `max_transfers` default.
+    pub fn with_pathfinder_config(mut self, config: PathfinderConfig) -> Self {
+        self.rpc = Arc::new((*self.rpc).clone().with_pathfinder_config(config));
+        self.token_registry = TokenRegistry::new(self.rpc.clone(), self.core.clone());
+        self
+    }
+
+    /// Resolve `.eth` names in [`Sdk::resolve`] through an Ethereum RPC.
+    #[cfg(feature = "ens")]
+    pub fn with_ens_rpc(mut self, rpc_url: &str) -> Result<Self, SdkError> {
+        self.ens = Some(resolver::ens::EnsResolver::new(rpc_url)?);
+        Ok(self)
+    }
+
+    /// Attach another deployment, e.g. `staging` on Chiado next to `production`, with the
+    /// runner that should send there. Replaces an environment of the same name.
+    pub fn with_environment(
+        mut self,
+        environment: Environment,
+        runner: Option<Arc<dyn ContractRunner>>,
+    ) -> Result<Self, SdkError> {
+        if environment.name == self.environment {
+            return Err(SdkError::OperationFailed(format!(
+                "environment {:?} is this SDK's own environment",
+                environment.name
+            )));
+        }
+        let name = environment.name.clone();
+        let sdk = Self::from_environment(environment, runner)?;
+        self.environments.insert(name, sdk);
+        Ok(self)
+    }
+
+    /// The SDK for environment `name`: this one, or one attached with
+    /// [`Sdk::with_environment`].
+    pub fn environment(&self, name: &str) -> Result<&Sdk, SdkError> {
+        if name == self.environment {
+            return Ok(self);
+        }
+        self.environments
+            .get(name)
+            .ok_or_else(|| SdkError::UnknownEnvironment(name.to_string()))
+    }
+
+    /// Name of this SDK's own environment.
+    pub fn environment_name(&self) -> &str {
+        &self.environment
+    }
+
+    /// This environment's name followed by the attached ones, alphabetically.
+    pub fn environment_names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.environment.as_str())
+            .chain(self.environments.keys().map(String::as_str))
+    }
+
     /// Access the underlying RPC client.
     pub fn rpc(&self) -> &CirclesRpc {
         self.rpc.as_ref()
@@ -245,11 +553,22 @@ impl Sdk {
         &self.config
     }
 
+    /// One-line summary of `tx` for audit logs and confirmation screens, with contract
+    /// addresses resolved against this SDK's deployment.
+    pub fn describe(&self, tx: &PreparedTransaction) -> String {
+        tx.describe(&self.config)
+    }
+
     /// Access core contract bundle.
     pub fn core(&self) -> &Arc<Core> {
         &self.core
     }
 
+    /// Cached token metadata shared by this SDK instance.
+    pub fn token_registry(&self) -> &TokenRegistry {
+        &self.token_registry
+    }
+
     /// Access the profiles client.
     pub fn profiles(&self) -> &Profiles {
         &self.profiles
@@ -324,6 +643,38 @@ impl Sdk {
         )
     }
 
+    /// Name registry facade: profile digests, short names, and custom names.
+    pub fn name_registry(&self) -> Names {
+        Names::new(self.core.clone(), self.runner.clone())
+    }
+
+    /// Recurring-payment facade for the subscription module deployed at `module`.
+    pub fn subscriptions(&self, module: Address) -> Subscriptions {
+        Subscriptions::new(
+            self.core.clone(),
+            self.rpc.clone(),
+            self.runner.clone(),
+            module,
+        )
+    }
+
+    /// Trust renewal scheduler for outgoing trust given by `avatar` on the v2 hub.
+    ///
+    /// Base groups trust through their own contract; use [`BaseGroupAvatar::trust_scheduler`].
+    pub fn trust_scheduler(&self, avatar: Address) -> TrustScheduler {
+        TrustScheduler::new(
+            self.rpc.clone(),
+            self.runner.clone(),
+            avatar,
+            self.core.config.v2_hub_address,
+        )
+    }
+
+    /// Watch-only portfolio over `members`, e.g. an EOA, its Safe, and an organisation.
+    pub fn portfolio(&self, members: impl IntoIterator<Item = Address>) -> Portfolio {
+        Portfolio::new(self.rpc.clone(), members)
+    }
+
     /// Dedicated tokens facade mirroring the TS SDK service surface.
     pub fn tokens(&self) -> Tokens<'_> {
         Tokens::new(self)
@@ -373,6 +724,58 @@ impl Sdk {
             .await?)
     }
 
+    /// Shortest chain of trust connecting `from` to `to`, ignoring balances.
+    ///
+    /// Each [`TrustHop`] is an avatar trusting the previous one, so the chain shows how
+    /// `from`'s tokens could reach `to` given enough liquidity. Returns `None` when no chain
+    /// of at most `max_hops` hops exists. Every expanded avatar costs one trust-relations
+    /// query, so keep `max_hops` small (3-4) for well-connected avatars.
+    pub async fn trust_path(
+        &self,
+        from: Address,
+        to: Address,
+        max_hops: usize,
+    ) -> Result<Option<Vec<TrustHop>>, SdkError> {
+        trust_path::find_trust_path(&self.rpc, from, to, max_hops).await
+    }
+
+    /// Gather heuristics about `avatar` before deciding to trust it.
+    ///
+    /// Reports account age, the inviter chain up to its root, trust degree, recent transfer
+    /// velocity, and — when the SDK has a sender address — which of the avatar's trust
+    /// connections the sender already trusts. Costs one query per inviter in the chain.
+    pub async fn assess_avatar(&self, avatar: Address) -> Result<AvatarAssessment, SdkError> {
+        assessment::assess_avatar(&self.rpc, self.sender_address, avatar).await
+    }
+
+    /// Per-token accounting for `avatar` from `from_block` through `to_block`: opening and
+    /// closing balances, mints, transfers in/out, and demurrage, exportable as CSV or JSON.
+    ///
+    /// Replays the avatar's v2 history from its first transfer, so long-lived avatars
+    /// cost several history pages.
+    pub async fn accounting_report(
+        &self,
+        avatar: Address,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<AccountingReport, SdkError> {
+        accounting::accounting_report(self, avatar, from_block, to_block).await
+    }
+
+    /// `avatar`'s balance of `token` at `block`, rebuilt by replaying its v2 transfers with
+    /// day-indexed demurrage.
+    ///
+    /// When the chain RPC can serve state at `block`, the hub's `balanceOf` is attached as
+    /// [`HistoricalBalance::spot`] so [`HistoricalBalance::matches_spot`] can validate it.
+    pub async fn balance_at_block(
+        &self,
+        avatar: Address,
+        token: Address,
+        block: u64,
+    ) -> Result<HistoricalBalance, SdkError> {
+        accounting::balance_at_block(self, avatar, token, block).await
+    }
+
     /// Read token balances for an avatar directly from the RPC service.
     ///
     /// Set `as_time_circles` to request balances in time-Circles units and `use_v2`
@@ -608,6 +1011,15 @@ impl Sdk {
         self.data_token_holders(token, limit, cursor).await
     }
 
+    /// Resolve a hex address, profile name, group symbol, or (with the `ens` feature) ENS
+    /// name to avatar addresses.
+    ///
+    /// Names are matched exactly, ignoring ASCII case. Several avatars with the same name
+    /// come back as [`Resolution::Ambiguous`] rather than picking one.
+    pub async fn resolve(&self, name: &str) -> Result<Resolution, SdkError> {
+        resolver::resolve(self, name).await
+    }
+
     /// Search profiles by address prefix or text directly from the consolidated RPC endpoint.
     pub async fn search_profiles_by_address_or_name(
         &self,
@@ -657,7 +1069,39 @@ impl Sdk {
         self.rpc.group().get_group_holders(group, limit)
     }
 
+    /// Wait until Circles RPC reads reflect `target` (a transaction hash or block number).
+    ///
+    /// Transactions are first waited on until mined (through the runner's receipt lookup when
+    /// it has one, the chain RPC otherwise); then the indexer's latest processed block is
+    /// polled until it reaches the inclusion block. Returns that block, or
+    /// [`SdkError::IndexerTimeout`] once `timeout` has passed.
+    pub async fn await_indexed(
+        &self,
+        target: impl Into<IndexTarget>,
+        timeout: std::time::Duration,
+    ) -> Result<u64, SdkError> {
+        indexer::await_indexed(self, target.into(), Some(timeout)).await
+    }
+
+    /// Check that every configured contract has code and answers like the expected contract.
+    ///
+    /// Probes run in one multicall (e.g. HubV2 `isHuman`, and `hub()`/`nameRegistry()`
+    /// back-references on modules that must point at the configured hub), plus one
+    /// `eth_getCode` per address. Zero optional addresses are reported as skipped.
+    pub async fn verify_config(&self) -> Result<ConfigReport, SdkError> {
+        config_check::verify_config(&self.core).await
+    }
+
+    /// Restore an [`AvatarSnapshot`] from JSON produced by [`AvatarSnapshot::to_json`].
+    ///
+    /// Needs no network or SDK instance, so offline views and tests can hydrate avatar state
+    /// captured earlier with [`HumanAvatar::snapshot`].
+    pub fn load_snapshot(json: &str) -> Result<AvatarSnapshot, SdkError> {
+        AvatarSnapshot::from_json(json)
+    }
+
     /// Convenience accessor for avatar info (read-only).
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.sdk", skip_all, fields(method = "avatar_info", avatar = %avatar)))]
     pub async fn avatar_info(&self, avatar: Address) -> Result<AvatarInfo, SdkError> {
         Ok(self.rpc.avatar().get_avatar_info(avatar).await?)
     }
@@ -713,13 +1157,45 @@ impl Sdk {
         .await
     }
 
+    /// Resume an event consumer at a persisted `cursor`, backfilling missed events over
+    /// HTTP before continuing live; each event is paired with the cursor to persist next.
+    #[cfg(feature = "ws")]
+    pub async fn subscribe_events_ws_from_cursor(
+        &self,
+        ws_url: &str,
+        filter: serde_json::Value,
+        cursor: EventCursor,
+        catch_up_filter: Option<Vec<circles_types::Filter>>,
+    ) -> Result<circles_rpc::EventStream<(EventCursor, CirclesEvent)>, SdkError> {
+        ws::subscribe_from_cursor(
+            self.rpc.as_ref(),
+            ws_url,
+            filter,
+            None,
+            cursor,
+            catch_up_filter,
+            None,
+        )
+        .await
+    }
+
     /// Fetch avatar info and return the matching typed avatar wrapper.
     ///
     /// Unknown or personal avatar types are treated as [`Avatar::Human`] to match the
-    /// current SDK behavior.
+    /// current SDK behavior. Unregistered addresses fail with [`SdkError::AvatarNotFound`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.sdk", skip_all, fields(method = "get_avatar", avatar = %avatar)))]
     pub async fn get_avatar(&self, avatar: Address) -> Result<Avatar, SdkError> {
-        let info = self.rpc.avatar().get_avatar_info(avatar).await?;
-        Ok(match info.avatar_type {
+        self.find_avatar(avatar)
+            .await?
+            .ok_or(SdkError::AvatarNotFound(avatar))
+    }
+
+    /// Like [`get_avatar`](Self::get_avatar), but `None` when `avatar` is not registered.
+    pub async fn find_avatar(&self, avatar: Address) -> Result<Option<Avatar>, SdkError> {
+        let Some(info) = self.rpc.avatar().find_avatar_info(avatar).await? else {
+            return Ok(None);
+        };
+        Ok(Some(match info.avatar_type {
             AvatarType::CrcV2RegisterGroup => Avatar::Group(BaseGroupAvatar::new(
                 avatar,
                 info,
@@ -744,10 +1220,16 @@ impl Sdk {
                 self.rpc.clone(),
                 self.runner.clone(),
             )),
-        })
+        }))
+    }
+
+    /// Whether `avatar` is registered with Circles (v1 or v2).
+    pub async fn is_registered(&self, avatar: Address) -> Result<bool, SdkError> {
+        Ok(self.rpc.avatar().find_avatar_info(avatar).await?.is_some())
     }
 
     /// Register a human avatar (profile is pinned before submission). Requires a runner.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.sdk", skip_all, fields(method = "register_human", inviter = %inviter)))]
     pub async fn register_human(
         &self,
         inviter: Address,
@@ -757,6 +1239,7 @@ impl Sdk {
     }
 
     /// Register an organisation avatar. Requires a runner.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.sdk", skip_all, fields(method = "register_organisation", name = %name)))]
     pub async fn register_organisation(
         &self,
         name: &str,
@@ -765,7 +1248,29 @@ impl Sdk {
         registration::register_organisation(self, name, profile).await
     }
 
-    /// Register a base group via the factory. Returns submitted txs and best-effort avatar.
+    /// Register an organisation avatar that trusts `initial_trust` without expiry in the same
+    /// submission as the registration. Requires a runner.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.sdk", skip_all, fields(method = "register_organisation_with_trust", name = %name)))]
+    pub async fn register_organisation_with_trust(
+        &self,
+        name: &str,
+        profile: &Profile,
+        initial_trust: &[Address],
+    ) -> Result<RegistrationResult<OrganisationAvatar>, SdkError> {
+        registration::register_organisation_with_trust(self, name, profile, initial_trust).await
+    }
+
+    /// Address the next base group deployed by the factory will get, e.g. to pre-fund it or
+    /// share it before registering.
+    ///
+    /// Only holds if no other group is created first: the factory derives group addresses from
+    /// its own nonce and accepts no salt, so the address cannot be reserved.
+    pub async fn predict_next_group_address(&self) -> Result<Address, SdkError> {
+        registration::predict_next_group_address(self).await
+    }
+
+    /// Register a base group via the factory. Returns submitted txs and the new group avatar
+    /// once it is found on-chain.
     #[allow(clippy::too_many_arguments)]
     pub async fn register_group(
         &self,
@@ -789,9 +1294,43 @@ impl Sdk {
         )
         .await
     }
+
+    /// Register a base group like [`register_group`](Self::register_group), then trust
+    /// `initial_trust` as members, without expiry, in a `trustBatchWithConditions` call sent
+    /// once the group is located; the runner must be the group's owner.
+    ///
+    /// The group is registered even when that trust step cannot run or fails; the reason is
+    /// in [`RegistrationResult::trust_error`].
+    #[allow(clippy::too_many_arguments)]
+    pub async fn register_group_with_trust(
+        &self,
+        owner: Address,
+        service: Address,
+        fee_collection: Address,
+        initial_conditions: &[Address],
+        name: &str,
+        symbol: &str,
+        profile: &Profile,
+        initial_trust: &[Address],
+    ) -> Result<RegistrationResult<BaseGroupAvatar>, SdkError> {
+        registration::register_group_with_trust(
+            self,
+            owner,
+            service,
+            fee_collection,
+            initial_conditions,
+            name,
+            symbol,
+            profile,
+            initial_trust,
+        )
+        .await
+    }
 }
 
 /// Top-level avatar enum (human, organisation, group).
+///
+/// Implements [`AvatarOps`], so shared operations work without matching on the variant.
 pub enum Avatar {
     /// Human or personal avatar wrapper.
     Human(HumanAvatar),
@@ -801,6 +1340,101 @@ pub enum Avatar {
     Group(BaseGroupAvatar),
 }
 
+impl Avatar {
+    /// Avatar address, whatever the variant.
+    pub fn address(&self) -> Address {
+        match self {
+            Avatar::Human(avatar) => avatar.address,
+            Avatar::Organisation(avatar) => avatar.address,
+            Avatar::Group(avatar) => avatar.address,
+        }
+    }
+
+    /// RPC-derived avatar metadata, whatever the variant.
+    pub fn info(&self) -> &AvatarInfo {
+        match self {
+            Avatar::Human(avatar) => &avatar.info,
+            Avatar::Organisation(avatar) => &avatar.info,
+            Avatar::Group(avatar) => &avatar.info,
+        }
+    }
+
+    pub fn as_human(&self) -> Option<&HumanAvatar> {
+        match self {
+            Avatar::Human(avatar) => Some(avatar),
+            _ => None,
+        }
+    }
+
+    pub fn as_organisation(&self) -> Option<&OrganisationAvatar> {
+        match self {
+            Avatar::Organisation(avatar) => Some(avatar),
+            _ => None,
+        }
+    }
+
+    pub fn as_group(&self) -> Option<&BaseGroupAvatar> {
+        match self {
+            Avatar::Group(avatar) => Some(avatar),
+            _ => None,
+        }
+    }
+}
+
+impl From<HumanAvatar> for Avatar {
+    fn from(avatar: HumanAvatar) -> Self {
+        Avatar::Human(avatar)
+    }
+}
+
+impl From<OrganisationAvatar> for Avatar {
+    fn from(avatar: OrganisationAvatar) -> Self {
+        Avatar::Organisation(avatar)
+    }
+}
+
+impl From<BaseGroupAvatar> for Avatar {
+    fn from(avatar: BaseGroupAvatar) -> Self {
+        Avatar::Group(avatar)
+    }
+}
+
+/// Fails with the original [`Avatar`] when it is not a human.
+impl TryFrom<Avatar> for HumanAvatar {
+    type Error = Avatar;
+
+    fn try_from(avatar: Avatar) -> Result<Self, Avatar> {
+        match avatar {
+            Avatar::Human(avatar) => Ok(avatar),
+            other => Err(other),
+        }
+    }
+}
+
+/// Fails with the original [`Avatar`] when it is not an organisation.
+impl TryFrom<Avatar> for OrganisationAvatar {
+    type Error = Avatar;
+
+    fn try_from(avatar: Avatar) -> Result<Self, Avatar> {
+        match avatar {
+            Avatar::Organisation(avatar) => Ok(avatar),
+            other => Err(other),
+        }
+    }
+}
+
+/// Fails with the original [`Avatar`] when it is not a base group.
+impl TryFrom<Avatar> for BaseGroupAvatar {
+    type Error = Avatar;
+
+    fn try_from(avatar: Avatar) -> Result<Self, Avatar> {
+        match avatar {
+            Avatar::Group(avatar) => Ok(avatar),
+            other => Err(other),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Sdk;
@@ -0,0 +1,378 @@
+//! EIP-712 messages for off-chain Circles authentication.
+//!
+//! Services that only need to know "this avatar said so" (an invite handed out, a profile
+//! edit requested, a group vouching for a member) can check a typed-data signature instead
+//! of waiting for a transaction. Messages are bound to a deployment through
+//! [`circles_domain`], which uses the v2 hub as the verifying contract.
+//!
+//! EOAs are checked by recovering the signer. Safes and other contract avatars are checked
+//! through ERC-1271 `isValidSignature` when a [`ContractRunner`] is available; for a Safe
+//! that means the owners signed the Safe's own message wrapper of the hash.
+
+use crate::{ContractRunner, PreparedTransaction, RunnerError, SdkError, call_to_tx};
+use alloy_primitives::{Address, B256, Bytes, FixedBytes, Signature, keccak256};
+use alloy_signer::Signer;
+use alloy_sol_types::{Eip712Domain, SolCall, SolStruct, eip712_domain, sol};
+use serde::{Deserialize, Serialize};
+
+sol! {
+    /// An inviter vouching for an invitation secret it handed out.
+    ///
+    /// `secretHash` is the keccak256 of the secret, so the secret itself never appears in
+    /// the signed payload.
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct InviteSecret {
+        address inviter;
+        bytes32 secretHash;
+        uint64 issuedAt;
+        uint64 expiresAt;
+    }
+
+    /// An avatar asking a service to publish `cid` as its new profile.
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ProfileUpdate {
+        address avatar;
+        string cid;
+        uint64 nonce;
+        uint64 issuedAt;
+    }
+
+    /// A group attesting that `member` belongs to it until `expiresAt`.
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct GroupMembershipAttestation {
+        address group;
+        address member;
+        uint64 issuedAt;
+        uint64 expiresAt;
+    }
+
+    interface IERC1271 {
+        function isValidSignature(bytes32 hash, bytes signature) external view returns (bytes4);
+    }
+}
+
+/// Return value of `isValidSignature` for a valid signature.
+const ERC1271_MAGIC: FixedBytes<4> = FixedBytes([0x16, 0x26, 0xba, 0x7e]);
+
+/// EIP-712 domain for Circles messages on `chain_id`, bound to the deployment's v2 hub.
+pub fn circles_domain(chain_id: u64, v2_hub: Address) -> Eip712Domain {
+    eip712_domain! {
+        name: "Circles",
+        version: "2",
+        chain_id: chain_id,
+        verifying_contract: v2_hub,
+    }
+}
+
+/// A Circles message that names the avatar expected to sign it.
+pub trait CirclesMessage: SolStruct {
+    /// Avatar whose signature makes the message valid.
+    fn signer(&self) -> Address;
+}
+
+impl CirclesMessage for InviteSecret {
+    fn signer(&self) -> Address {
+        self.inviter
+    }
+}
+
+impl CirclesMessage for ProfileUpdate {
+    fn signer(&self) -> Address {
+        self.avatar
+    }
+}
+
+impl CirclesMessage for GroupMembershipAttestation {
+    fn signer(&self) -> Address {
+        self.group
+    }
+}
+
+impl InviteSecret {
+    /// Invite for the raw `secret`, valid between `issued_at` and `expires_at` (unix seconds).
+    pub fn new(inviter: Address, secret: &[u8], issued_at: u64, expires_at: u64) -> Self {
+        Self {
+            inviter,
+            secretHash: keccak256(secret),
+            issuedAt: issued_at,
+            expiresAt: expires_at,
+        }
+    }
+
+    /// Whether `secret` is the one this invite was issued for.
+    pub fn matches_secret(&self, secret: &[u8]) -> bool {
+        self.secretHash == keccak256(secret)
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.expiresAt
+    }
+}
+
+impl GroupMembershipAttestation {
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.expiresAt
+    }
+}
+
+/// A typed message together with its signature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedMessage<M> {
+    pub message: M,
+    /// 65-byte `r || s || v` signature for EOAs, or whatever the contract avatar accepts.
+    pub signature: Bytes,
+}
+
+impl<M: SolStruct + Send + Sync> SignedMessage<M> {
+    /// Sign `message` under `domain` with `signer`.
+    pub async fn sign<S: Signer + Send + Sync>(
+        message: M,
+        domain: &Eip712Domain,
+        signer: &S,
+    ) -> Result<Self, SdkError> {
+        let signature = signer
+            .sign_typed_data(&message, domain)
+            .await
+            .map_err(|err| SdkError::OperationFailed(format!("signing failed: {err}")))?;
+        Ok(Self {
+            message,
+            signature: Bytes::from(signature.as_bytes().to_vec()),
+        })
+    }
+
+    /// EIP-712 digest that was signed.
+    pub fn signing_hash(&self, domain: &Eip712Domain) -> B256 {
+        self.message.eip712_signing_hash(domain)
+    }
+
+    /// Address of the EOA that produced the signature.
+    pub fn recover(&self, domain: &Eip712Domain) -> Result<Address, SdkError> {
+        Signature::from_raw(&self.signature)
+            .and_then(|signature| {
+                signature.recover_address_from_prehash(&self.signing_hash(domain))
+            })
+            .map_err(|err| SdkError::OperationFailed(format!("invalid signature: {err}")))
+    }
+}
+
+impl<M: CirclesMessage + Send + Sync> SignedMessage<M> {
+    /// Whether the avatar named in the message signed it.
+    ///
+    /// A recovered EOA signer matching [`CirclesMessage::signer`] is enough. Otherwise, with
+    /// a `runner`, that avatar is asked via ERC-1271; without one, contract avatars always
+    /// fail. A revert or a malformed answer means "not valid", while a call that never got
+    /// an answer is returned as an error rather than mistaken for a bad signature.
+    pub async fn verify(
+        &self,
+        domain: &Eip712Domain,
+        runner: Option<&dyn ContractRunner>,
+    ) -> Result<bool, SdkError> {
+        let avatar = self.message.signer();
+        if self.recover(domain).ok() == Some(avatar) {
+            return Ok(true);
+        }
+        let Some(runner) = runner else {
+            return Ok(false);
+        };
+        let tx = erc1271_call(avatar, self.signing_hash(domain), self.signature.clone());
+        let output = match runner.call(tx).await {
+            Ok(output) => output,
+            // Reverts (EOAs, contracts without ERC-1271) mean "not valid".
+            Err(RunnerError::Rejected(_)) => return Ok(false),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(IERC1271::isValidSignatureCall::abi_decode_returns(&output)
+            .is_ok_and(|magic| magic == ERC1271_MAGIC))
+    }
+}
+
+fn erc1271_call(avatar: Address, hash: B256, signature: Bytes) -> PreparedTransaction {
+    call_to_tx(
+        avatar,
+        IERC1271::isValidSignatureCall { hash, signature },
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SubmittedTx;
+    use alloy_primitives::address;
+    use alloy_signer_local::PrivateKeySigner;
+    use async_trait::async_trait;
+
+    const HUB: Address = address!("c12C1E50ABB450d6205Ea2C3Fa861b3B834d13e8");
+
+    struct Erc1271Runner {
+        answer: FixedBytes<4>,
+    }
+
+    #[async_trait]
+    impl ContractRunner for Erc1271Runner {
+        fn sender_address(&self) -> Address {
+            Address::ZERO
+        }
+
+        async fn call(&self, tx: PreparedTransaction) -> Result<Bytes, RunnerError> {
+            IERC1271::isValidSignatureCall::abi_decode(&tx.data)
+                .map_err(|err| RunnerError::Rejected(err.to_string()))?;
+            Ok(Bytes::from(
+                IERC1271::isValidSignatureCall::abi_encode_returns(&self.answer),
+            ))
+        }
+
+        async fn send_transactions(
+            &self,
+            _txs: Vec<PreparedTransaction>,
+        ) -> Result<Vec<SubmittedTx>, RunnerError> {
+            unreachable!("messages never send transactions")
+        }
+    }
+
+    /// Fails every call with the error `fail` builds.
+    struct FailingRunner {
+        fail: fn(String) -> RunnerError,
+    }
+
+    #[async_trait]
+    impl ContractRunner for FailingRunner {
+        fn sender_address(&self) -> Address {
+            Address::ZERO
+        }
+
+        async fn call(&self, _tx: PreparedTransaction) -> Result<Bytes, RunnerError> {
+            Err((self.fail)("isValidSignature".into()))
+        }
+
+        async fn send_transactions(
+            &self,
+            _txs: Vec<PreparedTransaction>,
+        ) -> Result<Vec<SubmittedTx>, RunnerError> {
+            unreachable!("messages never send transactions")
+        }
+    }
+
+    #[tokio::test]
+    async fn eoa_signature_round_trips() {
+        let signer = PrivateKeySigner::random();
+        let domain = circles_domain(100, HUB);
+        let invite = InviteSecret::new(signer.address(), b"secret", 10, 20);
+
+        let signed = SignedMessage::sign(invite, &domain, &signer)
+            .await
+            .expect("sign");
+        assert_eq!(signed.recover(&domain).expect("recover"), signer.address());
+        assert!(signed.verify(&domain, None).await.expect("verify"));
+        assert!(signed.message.matches_secret(b"secret"));
+        assert!(signed.message.is_expired(20));
+
+        // Another chain is another domain, so the signature no longer matches.
+        let other = circles_domain(1, HUB);
+        assert!(!signed.verify(&other, None).await.expect("verify"));
+    }
+
+    #[tokio::test]
+    async fn contract_avatar_is_checked_with_erc1271() {
+        let owner = PrivateKeySigner::random();
+        let safe = address!("5afe00000000000000000000000000000000005a");
+        let domain = circles_domain(100, HUB);
+        let signed = SignedMessage::sign(
+            GroupMembershipAttestation {
+                group: safe,
+                member: owner.address(),
+                issuedAt: 1,
+                expiresAt: 2,
+            },
+            &domain,
+            &owner,
+        )
+        .await
+        .expect("sign");
+
+        assert!(!signed.verify(&domain, None).await.expect("verify"));
+        let valid = Erc1271Runner {
+            answer: ERC1271_MAGIC,
+        };
+        assert!(signed.verify(&domain, Some(&valid)).await.expect("verify"));
+        let invalid = Erc1271Runner {
+            answer: FixedBytes([0xff; 4]),
+        };
+        assert!(
+            !signed
+                .verify(&domain, Some(&invalid))
+                .await
+                .expect("verify")
+        );
+    }
+
+    #[tokio::test]
+    async fn signature_must_come_from_the_named_avatar() {
+        let signer = PrivateKeySigner::random();
+        let domain = circles_domain(100, HUB);
+        let someone_else = Address::repeat_byte(7);
+        let signed = SignedMessage::sign(
+            InviteSecret::new(someone_else, b"secret", 10, 20),
+            &domain,
+            &signer,
+        )
+        .await
+        .expect("sign");
+
+        assert_eq!(signed.recover(&domain).expect("recover"), signer.address());
+        assert!(!signed.verify(&domain, None).await.expect("verify"));
+    }
+
+    #[tokio::test]
+    async fn erc1271_reverts_reject_but_transport_failures_propagate() {
+        let owner = PrivateKeySigner::random();
+        let safe = address!("5afe00000000000000000000000000000000005a");
+        let domain = circles_domain(100, HUB);
+        let signed = SignedMessage::sign(
+            ProfileUpdate {
+                avatar: safe,
+                cid: "QmProfile".into(),
+                nonce: 1,
+                issuedAt: 2,
+            },
+            &domain,
+            &owner,
+        )
+        .await
+        .expect("sign");
+
+        let reverting = FailingRunner {
+            fail: RunnerError::Rejected,
+        };
+        assert!(
+            !signed
+                .verify(&domain, Some(&reverting))
+                .await
+                .expect("a revert is an invalid signature")
+        );
+        let unreachable = FailingRunner {
+            fail: RunnerError::Transport,
+        };
+        let err = signed
+            .verify(&domain, Some(&unreachable))
+            .await
+            .expect_err("transport failures are not a verdict");
+        assert!(matches!(err, SdkError::Runner(RunnerError::Transport(_))));
+    }
+
+    #[test]
+    fn signed_message_serializes_for_transport() {
+        let signed = SignedMessage {
+            message: ProfileUpdate {
+                avatar: Address::repeat_byte(1),
+                cid: "QmProfile".into(),
+                nonce: 3,
+                issuedAt: 4,
+            },
+            signature: Bytes::from(vec![0u8; 65]),
+        };
+        let json = serde_json::to_string(&signed).expect("serialize");
+        let back: SignedMessage<ProfileUpdate> = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(back, signed);
+    }
+}
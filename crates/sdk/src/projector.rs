@@ -0,0 +1,368 @@
+//! Fold [`CirclesEvent`]s into domain aggregates.
+//!
+//! [`Projector`] decodes each event into its typed [`CirclesEventPayload`] once and forwards
+//! typed changes to a [`Projection`], so an indexer only implements the callbacks it cares
+//! about.
+
+use crate::SdkError;
+use alloy_primitives::{Address, U256};
+use circles_types::{AvatarType, CirclesEvent, CirclesEventPayload, CirclesEventType};
+use std::collections::HashSet;
+
+/// Movement of one token between two accounts (`from`/`to` are zero on mint/burn).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenTransfer {
+    /// ERC1155 token id for hub transfers, the wrapper address (left-padded) for ERC20 ones.
+    pub token_id: U256,
+    pub from: Address,
+    pub to: Address,
+    pub amount: U256,
+    /// Whether the transfer came from an ERC20 wrapper rather than the hub.
+    pub wrapped: bool,
+}
+
+/// A `trust` call; an expiry at or before the event timestamp revokes trust.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustChange {
+    pub truster: Address,
+    pub trustee: Address,
+    pub expiry_time: u64,
+}
+
+impl TrustChange {
+    /// Whether this change removes trust as of `at`.
+    pub fn is_revocation(&self, at: u64) -> bool {
+        self.expiry_time <= at
+    }
+}
+
+/// A new avatar in the hub.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AvatarRegistration {
+    pub avatar: Address,
+    pub avatar_type: AvatarType,
+    /// Inviter for humans, when the hub recorded one.
+    pub inviter: Option<Address>,
+    /// Name for groups and organisations.
+    pub name: Option<String>,
+}
+
+/// Group trust towards a member, i.e. a membership grant or revocation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MembershipChange {
+    pub group: Address,
+    pub member: Address,
+    pub expiry_time: u64,
+}
+
+/// Callbacks invoked by a [`Projector`]; every method defaults to a no-op.
+pub trait Projection {
+    /// One call per token moved, including each entry of a batch transfer.
+    fn on_transfer(&mut self, _event: &CirclesEvent, _transfer: &TokenTransfer) {}
+
+    /// Every `CrcV2_Trust`, including group trust that is also reported as a membership.
+    fn on_trust(&mut self, _event: &CirclesEvent, _change: &TrustChange) {}
+
+    /// Trust issued by a known group.
+    fn on_membership(&mut self, _event: &CirclesEvent, _change: &MembershipChange) {}
+
+    fn on_avatar_registered(&mut self, _event: &CirclesEvent, _registration: &AvatarRegistration) {}
+
+    /// A human stopped minting personal tokens.
+    fn on_stopped(&mut self, _event: &CirclesEvent, _avatar: Address) {}
+
+    /// Events without a typed callback, e.g. profile updates or wrapper deposits.
+    fn on_other(&mut self, _event: &CirclesEvent) {}
+}
+
+/// Applies events in order to a [`Projection`].
+///
+/// Groups are learned from `CrcV2_RegisterGroup` events; when starting mid-stream, seed
+/// existing ones with [`Projector::with_known_groups`] so their trust maps to memberships.
+pub struct Projector<P> {
+    projection: P,
+    groups: HashSet<Address>,
+}
+
+impl<P: Projection> Projector<P> {
+    pub fn new(projection: P) -> Self {
+        Self {
+            projection,
+            groups: HashSet::new(),
+        }
+    }
+
+    /// Treat trust from these avatars as group membership.
+    pub fn with_known_groups(mut self, groups: impl IntoIterator<Item = Address>) -> Self {
+        self.groups.extend(groups);
+        self
+    }
+
+    pub fn projection(&self) -> &P {
+        &self.projection
+    }
+
+    pub fn projection_mut(&mut self) -> &mut P {
+        &mut self.projection
+    }
+
+    pub fn into_inner(self) -> P {
+        self.projection
+    }
+
+    /// Apply a sequence of events, stopping at the first malformed one.
+    pub fn apply_all<'a>(
+        &mut self,
+        events: impl IntoIterator<Item = &'a CirclesEvent>,
+    ) -> Result<(), SdkError> {
+        events.into_iter().try_for_each(|event| self.apply(event))
+    }
+
+    /// Decode one event and dispatch it.
+    ///
+    /// Fails when a known event lacks a field its callback needs; the projection is left
+    /// untouched in that case.
+    pub fn apply(&mut self, event: &CirclesEvent) -> Result<(), SdkError> {
+        let p = &mut self.projection;
+        let payload = match event.payload() {
+            Ok(payload) => payload,
+            Err(err) if has_callback(&event.event_type) => {
+                return Err(malformed(event, &err.to_string()));
+            }
+            // Nothing here reads the payload of other events.
+            Err(_) => {
+                p.on_other(event);
+                return Ok(());
+            }
+        };
+        match payload {
+            CirclesEventPayload::CrcV2TransferSingle(single) => {
+                let transfer = TokenTransfer {
+                    token_id: single.id,
+                    from: single.from,
+                    to: single.to,
+                    amount: single.value,
+                    wrapped: false,
+                };
+                p.on_transfer(event, &transfer);
+            }
+            CirclesEventPayload::CrcV2TransferBatch(batch) => {
+                if batch.ids.len() != batch.values.len() {
+                    return Err(malformed(event, "ids/values length mismatch"));
+                }
+                for (token_id, amount) in batch.ids.into_iter().zip(batch.values) {
+                    let transfer = TokenTransfer {
+                        token_id,
+                        from: batch.from,
+                        to: batch.to,
+                        amount,
+                        wrapped: false,
+                    };
+                    p.on_transfer(event, &transfer);
+                }
+            }
+            CirclesEventPayload::CrcV2Transfer(erc20) => {
+                let wrapper = erc20
+                    .token_address
+                    .ok_or_else(|| malformed(event, "missing `tokenAddress`"))?;
+                let transfer = TokenTransfer {
+                    token_id: U256::from_be_slice(wrapper.as_slice()),
+                    from: erc20.from,
+                    to: erc20.to,
+                    amount: erc20.value,
+                    wrapped: true,
+                };
+                p.on_transfer(event, &transfer);
+            }
+            CirclesEventPayload::CrcV2Trust(trust) => {
+                let change = TrustChange {
+                    truster: trust.truster,
+                    trustee: trust.trustee,
+                    expiry_time: trust.expiry_time.saturating_to(),
+                };
+                p.on_trust(event, &change);
+                if self.groups.contains(&change.truster) {
+                    p.on_membership(
+                        event,
+                        &MembershipChange {
+                            group: change.truster,
+                            member: change.trustee,
+                            expiry_time: change.expiry_time,
+                        },
+                    );
+                }
+            }
+            CirclesEventPayload::CrcV2RegisterHuman(human) => {
+                let registration = AvatarRegistration {
+                    avatar: human.avatar,
+                    avatar_type: AvatarType::CrcV2RegisterHuman,
+                    inviter: human.inviter.filter(|a| !a.is_zero()),
+                    name: None,
+                };
+                p.on_avatar_registered(event, &registration);
+            }
+            CirclesEventPayload::CrcV2RegisterGroup(group) => {
+                let registration = AvatarRegistration {
+                    avatar: group.group,
+                    avatar_type: AvatarType::CrcV2RegisterGroup,
+                    inviter: None,
+                    name: Some(group.name),
+                };
+                self.groups.insert(registration.avatar);
+                p.on_avatar_registered(event, &registration);
+            }
+            CirclesEventPayload::CrcV2RegisterOrganization(organization) => {
+                let registration = AvatarRegistration {
+                    avatar: organization.organization,
+                    avatar_type: AvatarType::CrcV2RegisterOrganization,
+                    inviter: None,
+                    name: Some(organization.name),
+                };
+                p.on_avatar_registered(event, &registration);
+            }
+            CirclesEventPayload::CrcV2Stopped(stopped) => p.on_stopped(event, stopped.avatar),
+            _ => p.on_other(event),
+        }
+        Ok(())
+    }
+}
+
+/// Event types [`Projector::apply`] decodes for a typed callback.
+fn has_callback(event_type: &CirclesEventType) -> bool {
+    matches!(
+        event_type,
+        CirclesEventType::CrcV2TransferSingle
+            | CirclesEventType::CrcV2TransferBatch
+            | CirclesEventType::CrcV2Transfer
+            | CirclesEventType::CrcV2Trust
+            | CirclesEventType::CrcV2RegisterHuman
+            | CirclesEventType::CrcV2RegisterGroup
+            | CirclesEventType::CrcV2RegisterOrganization
+            | CirclesEventType::CrcV2Stopped
+    )
+}
+
+/// Typed payload of `event`, with decode failures reported like other malformed events.
+pub(crate) fn decode_payload(event: &CirclesEvent) -> Result<CirclesEventPayload, SdkError> {
+    event
+        .payload()
+        .map_err(|err| malformed(event, &err.to_string()))
+}
+
+fn malformed(event: &CirclesEvent, reason: &str) -> SdkError {
+    SdkError::OperationFailed(format!(
+        "malformed {:?} event at block {} log {}: {reason}",
+        event.event_type, event.base.block_number, event.base.log_index
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use circles_rpc_mock::fixtures::circles_event as event;
+    use serde_json::json;
+
+    #[derive(Default)]
+    struct Recorder {
+        transfers: Vec<TokenTransfer>,
+        trusts: usize,
+        memberships: Vec<MembershipChange>,
+        registered: Vec<Address>,
+        other: usize,
+    }
+
+    impl Projection for Recorder {
+        fn on_transfer(&mut self, _event: &CirclesEvent, transfer: &TokenTransfer) {
+            self.transfers.push(transfer.clone());
+        }
+
+        fn on_trust(&mut self, _event: &CirclesEvent, _change: &TrustChange) {
+            self.trusts += 1;
+        }
+
+        fn on_membership(&mut self, _event: &CirclesEvent, change: &MembershipChange) {
+            self.memberships.push(change.clone());
+        }
+
+        fn on_avatar_registered(&mut self, _event: &CirclesEvent, reg: &AvatarRegistration) {
+            self.registered.push(reg.avatar);
+        }
+
+        fn on_other(&mut self, _event: &CirclesEvent) {
+            self.other += 1;
+        }
+    }
+
+    #[test]
+    fn dispatches_typed_changes_and_tracks_groups() {
+        let group = Address::repeat_byte(0x0a);
+        let alice = Address::repeat_byte(0x0b);
+        let bob = Address::repeat_byte(0x0c);
+        let events = vec![
+            event(
+                CirclesEventType::CrcV2RegisterGroup,
+                json!({
+                    "group": group, "mint": alice, "treasury": bob,
+                    "name": "G", "symbol": "G"
+                }),
+            ),
+            event(
+                CirclesEventType::CrcV2Trust,
+                json!({ "truster": group, "trustee": alice, "expiryTime": "1800000000" }),
+            ),
+            event(
+                CirclesEventType::CrcV2Trust,
+                json!({ "truster": alice, "trustee": bob, "expiryTime": 0 }),
+            ),
+            event(
+                CirclesEventType::CrcV2TransferBatch,
+                json!({
+                    "operator": alice, "from": alice, "to": bob,
+                    "ids": ["1", "0x02"], "values": [5, "7"]
+                }),
+            ),
+            event(CirclesEventType::CrcV2CidV0, json!({ "avatar": alice })),
+        ];
+
+        let mut projector = Projector::new(Recorder::default());
+        projector.apply_all(&events).unwrap();
+        let recorder = projector.into_inner();
+
+        assert_eq!(recorder.registered, vec![group]);
+        assert_eq!(recorder.trusts, 2);
+        assert_eq!(
+            recorder.memberships,
+            vec![MembershipChange {
+                group,
+                member: alice,
+                expiry_time: 1_800_000_000,
+            }]
+        );
+        let moved: Vec<_> = recorder
+            .transfers
+            .iter()
+            .map(|t| (t.token_id, t.amount))
+            .collect();
+        assert_eq!(
+            moved,
+            vec![
+                (U256::from(1), U256::from(5)),
+                (U256::from(2), U256::from(7))
+            ]
+        );
+        assert_eq!(recorder.other, 1);
+    }
+
+    #[test]
+    fn missing_field_is_an_error() {
+        let mut projector = Projector::new(Recorder::default());
+        let err = projector
+            .apply(&event(
+                CirclesEventType::CrcV2Trust,
+                json!({ "truster": Address::ZERO }),
+            ))
+            .unwrap_err();
+        assert!(err.to_string().contains("trustee"));
+        assert_eq!(projector.projection().trusts, 0);
+    }
+}
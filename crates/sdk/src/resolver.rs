@@ -0,0 +1,261 @@
+//! Human-readable name resolution for avatars.
+//!
+//! A name can be a hex address, a Circles profile name, a group symbol, or (with the
+//! `ens` feature and an ENS RPC configured) an ENS name. Profile names and symbols are not
+//! unique, so lookups return every exact match and leave the choice to the caller.
+
+use crate::{Sdk, SdkError};
+use alloy_primitives::Address;
+use circles_rpc::SearchOptions;
+use circles_types::GroupQueryParams;
+
+/// How many profile hits and groups are inspected for exact matches.
+const CANDIDATE_LIMIT: u32 = 25;
+
+/// Where a [`NameMatch`] came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NameSource {
+    /// The input was already a hex address.
+    Address,
+    /// Exact (case-insensitive) Circles profile name.
+    Profile,
+    /// Exact (case-insensitive) group token symbol.
+    GroupSymbol,
+    /// ENS `addr` record.
+    Ens,
+}
+
+/// One avatar a name resolved to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameMatch {
+    pub address: Address,
+    pub source: NameSource,
+    /// Profile name, group symbol, or ENS name as it was matched.
+    pub label: String,
+}
+
+/// Outcome of [`crate::Sdk::resolve`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    NotFound,
+    Unique(NameMatch),
+    /// Several avatars share the name; one entry per address, strongest source first.
+    Ambiguous(Vec<NameMatch>),
+}
+
+impl Resolution {
+    fn from_matches(mut matches: Vec<NameMatch>) -> Self {
+        // Keep the first (strongest) source per address.
+        let mut seen = Vec::new();
+        matches.retain(|m| {
+            let new = !seen.contains(&m.address);
+            seen.push(m.address);
+            new
+        });
+        match matches.len() {
+            0 => Self::NotFound,
+            1 => Self::Unique(matches.remove(0)),
+            _ => Self::Ambiguous(matches),
+        }
+    }
+
+    /// The address when the name resolved to exactly one avatar.
+    pub fn address(&self) -> Option<Address> {
+        match self {
+            Self::Unique(m) => Some(m.address),
+            _ => None,
+        }
+    }
+
+    /// Every candidate, in the order [`Resolution::Ambiguous`] lists them.
+    pub fn candidates(&self) -> &[NameMatch] {
+        match self {
+            Self::NotFound => &[],
+            Self::Unique(m) => std::slice::from_ref(m),
+            Self::Ambiguous(matches) => matches,
+        }
+    }
+}
+
+pub(crate) async fn resolve(sdk: &Sdk, name: &str) -> Result<Resolution, SdkError> {
+    let name = name.trim();
+    if let Ok(address) = name.parse::<Address>() {
+        return Ok(Resolution::Unique(NameMatch {
+            address,
+            source: NameSource::Address,
+            label: name.to_string(),
+        }));
+    }
+
+    let mut matches = Vec::new();
+    #[cfg(feature = "ens")]
+    if let Some(ens) = sdk.ens.as_ref().filter(|_| name.contains('.'))
+        && let Some(address) = ens.resolve(name).await?
+    {
+        matches.push(NameMatch {
+            address,
+            source: NameSource::Ens,
+            label: name.to_string(),
+        });
+    }
+
+    let options = SearchOptions {
+        limit: CANDIDATE_LIMIT,
+        ..Default::default()
+    };
+    let profiles = sdk
+        .rpc
+        .search()
+        .search_profiles_paged(name, &options)
+        .await?;
+    let groups = sdk
+        .rpc
+        .group()
+        .find_groups(
+            CANDIDATE_LIMIT,
+            Some(GroupQueryParams {
+                symbol_starts_with: Some(name.to_string()),
+                ..Default::default()
+            }),
+        )
+        .await?;
+    matches.extend(
+        profiles
+            .results
+            .into_iter()
+            .filter(|hit| hit.name.trim().eq_ignore_ascii_case(name))
+            .map(|hit| NameMatch {
+                address: hit.address,
+                source: NameSource::Profile,
+                label: hit.name,
+            }),
+    );
+    matches.extend(groups.into_iter().filter_map(|group| {
+        let symbol = group.symbol?;
+        symbol.eq_ignore_ascii_case(name).then_some(NameMatch {
+            address: group.group,
+            source: NameSource::GroupSymbol,
+            label: symbol,
+        })
+    }));
+    Ok(Resolution::from_matches(matches))
+}
+
+#[cfg(feature = "ens")]
+pub(crate) mod ens {
+    use crate::SdkError;
+    use alloy_primitives::{Address, B256, address, keccak256};
+    use alloy_provider::{DynProvider, Provider, ProviderBuilder};
+    use alloy_sol_types::sol;
+
+    /// ENS registry, deployed at the same address on mainnet and its testnets.
+    const ENS_REGISTRY: Address = address!("00000000000C2E074eC69A0dFb2997BA6C7d2e1e");
+
+    sol! {
+        #[sol(rpc)]
+        interface IEnsRegistry {
+            function resolver(bytes32 node) external view returns (address);
+        }
+
+        #[sol(rpc)]
+        interface IEnsAddrResolver {
+            function addr(bytes32 node) external view returns (address);
+        }
+    }
+
+    /// Forward ENS lookups against an Ethereum RPC (ENS does not live on Gnosis Chain).
+    #[derive(Clone)]
+    pub(crate) struct EnsResolver {
+        provider: DynProvider,
+    }
+
+    impl EnsResolver {
+        pub(crate) fn new(rpc_url: &str) -> Result<Self, SdkError> {
+            let url = rpc_url
+                .parse()
+                .map_err(|err| SdkError::OperationFailed(format!("invalid ENS rpc url: {err}")))?;
+            Ok(Self {
+                provider: ProviderBuilder::new().connect_http(url).erased(),
+            })
+        }
+
+        /// `None` when the name has no resolver or no `addr` record.
+        pub(crate) async fn resolve(&self, name: &str) -> Result<Option<Address>, SdkError> {
+            let node = namehash(name);
+            let resolver = IEnsRegistry::new(ENS_REGISTRY, &self.provider)
+                .resolver(node)
+                .call()
+                .await
+                .map_err(|err| SdkError::Contract(err.to_string()))?;
+            if resolver.is_zero() {
+                return Ok(None);
+            }
+            let address = IEnsAddrResolver::new(resolver, &self.provider)
+                .addr(node)
+                .call()
+                .await
+                .map_err(|err| SdkError::Contract(err.to_string()))?;
+            Ok((!address.is_zero()).then_some(address))
+        }
+    }
+
+    /// EIP-137 namehash. Labels are lowercased; full ENSIP-15 normalization is not applied.
+    pub(crate) fn namehash(name: &str) -> B256 {
+        name.to_lowercase()
+            .rsplit('.')
+            .filter(|label| !label.is_empty())
+            .fold(B256::ZERO, |node, label| {
+                keccak256([node.as_slice(), keccak256(label).as_slice()].concat())
+            })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::namehash;
+        use alloy_primitives::{B256, b256};
+
+        #[test]
+        fn namehash_matches_eip137_vectors() {
+            assert_eq!(namehash(""), B256::ZERO);
+            assert_eq!(
+                namehash("eth"),
+                b256!("93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae")
+            );
+            assert_eq!(
+                namehash("foo.eth"),
+                b256!("de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f")
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(byte: u8, source: NameSource) -> NameMatch {
+        NameMatch {
+            address: Address::repeat_byte(byte),
+            source,
+            label: "alice".into(),
+        }
+    }
+
+    #[test]
+    fn resolution_dedupes_by_address_keeping_first_source() {
+        let resolution = Resolution::from_matches(vec![
+            hit(1, NameSource::Profile),
+            hit(1, NameSource::GroupSymbol),
+        ]);
+        assert_eq!(resolution, Resolution::Unique(hit(1, NameSource::Profile)));
+        assert_eq!(resolution.address(), Some(Address::repeat_byte(1)));
+
+        let ambiguous = Resolution::from_matches(vec![
+            hit(1, NameSource::Profile),
+            hit(2, NameSource::GroupSymbol),
+        ]);
+        assert_eq!(ambiguous.address(), None);
+        assert_eq!(ambiguous.candidates().len(), 2);
+        assert!(Resolution::from_matches(Vec::new()).candidates().is_empty());
+    }
+}
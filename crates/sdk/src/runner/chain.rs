@@ -0,0 +1,205 @@
+//! Refuse to submit transactions when a runner is connected to the wrong chain.
+
+use super::{
+    ContractRunner, FeeEstimate, PreparedTransaction, RunnerError, SubmittedTx, TxOverrides,
+    TxReceiptInfo,
+};
+use alloy_primitives::{Address, B256, Bytes};
+use async_trait::async_trait;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+/// Runner wrapper that compares [`ContractRunner::chain_id`] with the chain a config
+/// targets before the first send, so a staging runner cannot sign production calls.
+///
+/// [`Sdk::new`](crate::Sdk::new) wraps every runner in one. The check runs once per
+/// wrapper; runners that cannot report a chain id are trusted as-is. Reads and
+/// estimates are passed through unchecked.
+pub struct ChainCheckedRunner {
+    inner: Arc<dyn ContractRunner>,
+    expected_chain_id: u64,
+    verified: OnceCell<()>,
+}
+
+impl ChainCheckedRunner {
+    /// Check sends through `inner` against `expected_chain_id`.
+    pub fn new(inner: Arc<dyn ContractRunner>, expected_chain_id: u64) -> Self {
+        Self {
+            inner,
+            expected_chain_id,
+            verified: OnceCell::new(),
+        }
+    }
+
+    /// Chain id sends are checked against.
+    pub fn expected_chain_id(&self) -> u64 {
+        self.expected_chain_id
+    }
+
+    /// The wrapped runner.
+    pub fn inner(&self) -> &Arc<dyn ContractRunner> {
+        &self.inner
+    }
+
+    /// Fail with [`RunnerError::ChainMismatch`] unless the runner is on the expected chain.
+    pub async fn verify_chain(&self) -> Result<(), RunnerError> {
+        self.verified
+            .get_or_try_init(|| async {
+                match self.inner.chain_id().await {
+                    Ok(actual) if actual != self.expected_chain_id => {
+                        Err(RunnerError::ChainMismatch {
+                            expected: self.expected_chain_id,
+                            actual,
+                        })
+                    }
+                    Ok(_) | Err(RunnerError::Unsupported(_)) => Ok(()),
+                    Err(err) => Err(err),
+                }
+            })
+            .await
+            .copied()
+    }
+}
+
+#[async_trait]
+impl ContractRunner for ChainCheckedRunner {
+    fn sender_address(&self) -> Address {
+        self.inner.sender_address()
+    }
+
+    fn address(&self) -> Option<Address> {
+        self.inner.address()
+    }
+
+    async fn estimate_gas(&self, tx: PreparedTransaction) -> Result<u64, RunnerError> {
+        self.inner.estimate_gas(tx).await
+    }
+
+    async fn call(&self, tx: PreparedTransaction) -> Result<Bytes, RunnerError> {
+        self.inner.call(tx).await
+    }
+
+    async fn resolve_name(&self, name: &str) -> Result<Option<Address>, RunnerError> {
+        self.inner.resolve_name(name).await
+    }
+
+    async fn pending_nonce(&self) -> Result<u64, RunnerError> {
+        self.inner.pending_nonce().await
+    }
+
+    async fn gas_price(&self) -> Result<u128, RunnerError> {
+        self.inner.gas_price().await
+    }
+
+    async fn estimate_fees(&self) -> Result<FeeEstimate, RunnerError> {
+        self.inner.estimate_fees().await
+    }
+
+    async fn block_number(&self) -> Result<u64, RunnerError> {
+        self.inner.block_number().await
+    }
+
+    async fn chain_id(&self) -> Result<u64, RunnerError> {
+        self.inner.chain_id().await
+    }
+
+    async fn transaction_receipt(
+        &self,
+        tx_hash: B256,
+    ) -> Result<Option<TxReceiptInfo>, RunnerError> {
+        self.inner.transaction_receipt(tx_hash).await
+    }
+
+    async fn send_transaction_with_overrides(
+        &self,
+        tx: PreparedTransaction,
+        overrides: TxOverrides,
+    ) -> Result<SubmittedTx, RunnerError> {
+        self.verify_chain().await?;
+        self.inner
+            .send_transaction_with_overrides(tx, overrides)
+            .await
+    }
+
+    async fn send_transactions(
+        &self,
+        txs: Vec<PreparedTransaction>,
+    ) -> Result<Vec<SubmittedTx>, RunnerError> {
+        self.verify_chain().await?;
+        self.inner.send_transactions(txs).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct ChainRunner {
+        chain_id: Option<u64>,
+        sent: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl ContractRunner for ChainRunner {
+        fn sender_address(&self) -> Address {
+            Address::repeat_byte(1)
+        }
+
+        async fn chain_id(&self) -> Result<u64, RunnerError> {
+            self.chain_id
+                .ok_or_else(|| RunnerError::Unsupported("no chain id".to_string()))
+        }
+
+        async fn send_transactions(
+            &self,
+            txs: Vec<PreparedTransaction>,
+        ) -> Result<Vec<SubmittedTx>, RunnerError> {
+            *self.sent.lock().unwrap() += txs.len();
+            Ok(Vec::new())
+        }
+    }
+
+    fn checked(chain_id: Option<u64>, expected: u64) -> (ChainCheckedRunner, Arc<ChainRunner>) {
+        let inner = Arc::new(ChainRunner {
+            chain_id,
+            sent: Mutex::new(0),
+        });
+        (ChainCheckedRunner::new(inner.clone(), expected), inner)
+    }
+
+    fn tx() -> PreparedTransaction {
+        PreparedTransaction {
+            to: Address::ZERO,
+            data: Bytes::new(),
+            value: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_are_refused_on_the_wrong_chain() {
+        let (runner, inner) = checked(Some(10200), 100);
+        let err = runner.send_transactions(vec![tx()]).await.unwrap_err();
+        assert!(matches!(
+            err,
+            RunnerError::ChainMismatch {
+                expected: 100,
+                actual: 10200
+            }
+        ));
+        let mut batch = runner.send_batch_transaction();
+        batch.add_transaction(tx());
+        assert!(batch.run().await.is_err());
+        assert_eq!(*inner.sent.lock().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn matching_or_unknown_chains_pass_through() {
+        let (runner, inner) = checked(Some(100), 100);
+        runner.send_transactions(vec![tx()]).await.expect("send");
+        let (blind, blind_inner) = checked(None, 100);
+        blind.send_transactions(vec![tx()]).await.expect("send");
+        assert_eq!(*inner.sent.lock().unwrap(), 1);
+        assert_eq!(*blind_inner.sent.lock().unwrap(), 1);
+    }
+}
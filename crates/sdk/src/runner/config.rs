@@ -0,0 +1,351 @@
+//! Fee selection for runners that control their own submissions
+//! ([`super::NonceManagedRunner`] and [`super::Erc4337Runner`]). The settings
+//! themselves live on [`RunnerConfig`], shared with `circles-types`.
+
+use super::{ContractRunner, FeeEstimate, RunnerError, TxOverrides};
+use async_trait::async_trait;
+use circles_types::Timeouts;
+pub use circles_types::{
+    BundlerConfig, ENTRY_POINT_V07, FeeCaps, FeeStrategy, PaymasterConfig, RelayConfig, RelayMode,
+    RunnerConfig, StuckTxRetry,
+};
+
+/// Fees a [`RunnerConfig`] picks for fresh and replacement submissions.
+#[async_trait]
+pub trait FeePolicy {
+    /// Fee overrides (nonce unset) for a fresh submission.
+    async fn initial_fees<R>(&self, runner: &R) -> Result<TxOverrides, RunnerError>
+    where
+        R: ContractRunner + ?Sized;
+
+    /// Fee overrides (nonce unset) for replacing a transaction sent with `previous`.
+    ///
+    /// Both EIP-1559 fees (or the gas price) beat `previous` by at least the replacement
+    /// bump and never undercut the current network suggestion. Fails if the caps leave no
+    /// room for a valid replacement.
+    async fn replacement_fees<R>(
+        &self,
+        runner: &R,
+        previous: TxOverrides,
+    ) -> Result<TxOverrides, RunnerError>
+    where
+        R: ContractRunner + ?Sized;
+}
+
+#[async_trait]
+impl FeePolicy for RunnerConfig {
+    async fn initial_fees<R>(&self, runner: &R) -> Result<TxOverrides, RunnerError>
+    where
+        R: ContractRunner + ?Sized,
+    {
+        let fees = match self.fee_strategy {
+            FeeStrategy::Provider => {
+                if self.fee_caps == FeeCaps::default() {
+                    return Ok(TxOverrides::default());
+                }
+                eip1559_overrides(runner.estimate_fees().await?)
+            }
+            FeeStrategy::Legacy { multiplier_percent } => {
+                let gas_price = runner.gas_price().await?;
+                TxOverrides {
+                    gas_price: Some(gas_price.saturating_mul(multiplier_percent) / 100),
+                    ..TxOverrides::default()
+                }
+            }
+            FeeStrategy::Eip1559 {
+                priority_fee_bump_percent,
+            } => {
+                let estimate = runner.estimate_fees().await?;
+                let priority_fee =
+                    bump_percent(estimate.max_priority_fee_per_gas, priority_fee_bump_percent);
+                let extra = priority_fee - estimate.max_priority_fee_per_gas;
+                eip1559_overrides(FeeEstimate {
+                    max_fee_per_gas: estimate.max_fee_per_gas.saturating_add(extra),
+                    max_priority_fee_per_gas: priority_fee,
+                })
+            }
+            FeeStrategy::Fixed {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            } => eip1559_overrides(FeeEstimate {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            }),
+        };
+        Ok(apply_caps(self, fees))
+    }
+
+    async fn replacement_fees<R>(
+        &self,
+        runner: &R,
+        previous: TxOverrides,
+    ) -> Result<TxOverrides, RunnerError>
+    where
+        R: ContractRunner + ?Sized,
+    {
+        let legacy =
+            previous.gas_price.is_some() || matches!(self.fee_strategy, FeeStrategy::Legacy { .. });
+        let fees = if legacy {
+            let current = runner.gas_price().await?;
+            TxOverrides {
+                gas_price: Some(bump(self, previous.gas_price.unwrap_or(current)).max(current)),
+                ..TxOverrides::default()
+            }
+        } else {
+            let current = runner.estimate_fees().await?;
+            let max_fee = bump(
+                self,
+                previous.max_fee_per_gas.unwrap_or(current.max_fee_per_gas),
+            )
+            .max(current.max_fee_per_gas);
+            let priority_fee = bump(
+                self,
+                previous
+                    .max_priority_fee_per_gas
+                    .unwrap_or(current.max_priority_fee_per_gas),
+            )
+            .max(current.max_priority_fee_per_gas);
+            eip1559_overrides(FeeEstimate {
+                max_fee_per_gas: max_fee,
+                max_priority_fee_per_gas: priority_fee,
+            })
+        };
+
+        // Nodes only accept a replacement when every fee it competes on (the gas price, or
+        // both EIP-1559 fees) beats the pending one by the bump.
+        let fees = apply_caps(self, fees);
+        let outbid = |new: Option<u128>, old: Option<u128>| match (new, old) {
+            (Some(new), Some(old)) => new >= bump(self, old),
+            _ => true,
+        };
+        if !outbid(fees.gas_price, previous.gas_price)
+            || !outbid(fees.max_fee_per_gas, previous.max_fee_per_gas)
+            || !outbid(
+                fees.max_priority_fee_per_gas,
+                previous.max_priority_fee_per_gas,
+            )
+        {
+            return Err(RunnerError::Rejected(
+                "replacement fee would exceed the configured fee cap".to_string(),
+            ));
+        }
+        Ok(fees)
+    }
+}
+
+fn bump(config: &RunnerConfig, value: u128) -> u128 {
+    bump_percent(value, config.replacement_bump_percent).max(value.saturating_add(1))
+}
+
+fn apply_caps(config: &RunnerConfig, mut fees: TxOverrides) -> TxOverrides {
+    let cap = |value: Option<u128>, cap: Option<u128>| match (value, cap) {
+        (Some(value), Some(cap)) => Some(value.min(cap)),
+        (value, _) => value,
+    };
+    fees.gas_price = cap(fees.gas_price, config.fee_caps.max_fee_per_gas);
+    fees.max_fee_per_gas = cap(fees.max_fee_per_gas, config.fee_caps.max_fee_per_gas);
+    fees.max_priority_fee_per_gas = cap(
+        cap(
+            fees.max_priority_fee_per_gas,
+            config.fee_caps.max_priority_fee_per_gas,
+        ),
+        fees.max_fee_per_gas,
+    );
+    fees
+}
+
+fn bump_percent(value: u128, percent: u128) -> u128 {
+    value
+        .saturating_mul(100u128.saturating_add(percent))
+        .div_ceil(100)
+}
+
+fn eip1559_overrides(fees: FeeEstimate) -> TxOverrides {
+    TxOverrides {
+        max_fee_per_gas: Some(fees.max_fee_per_gas),
+        max_priority_fee_per_gas: Some(fees.max_priority_fee_per_gas),
+        ..TxOverrides::default()
+    }
+}
+
+/// Reqwest client applying the connect and request limits of `timeouts`.
+pub(crate) fn http_client(timeouts: &Timeouts) -> Result<reqwest::Client, reqwest::Error> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(connect) = timeouts.connect {
+        builder = builder.connect_timeout(connect);
+    }
+    if let Some(request) = timeouts.request {
+        builder = builder.timeout(request);
+    }
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PreparedTransaction, SubmittedTx};
+    use alloy_primitives::Address;
+    use async_trait::async_trait;
+
+    struct FeeRunner;
+
+    #[async_trait]
+    impl ContractRunner for FeeRunner {
+        fn sender_address(&self) -> Address {
+            Address::ZERO
+        }
+
+        async fn gas_price(&self) -> Result<u128, RunnerError> {
+            Ok(2_000)
+        }
+
+        async fn estimate_fees(&self) -> Result<FeeEstimate, RunnerError> {
+            Ok(FeeEstimate {
+                max_fee_per_gas: 5_000,
+                max_priority_fee_per_gas: 10,
+            })
+        }
+
+        async fn send_transactions(
+            &self,
+            _txs: Vec<PreparedTransaction>,
+        ) -> Result<Vec<SubmittedTx>, RunnerError> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn provider_strategy_leaves_fees_unset() {
+        let fees = RunnerConfig::default()
+            .initial_fees(&FeeRunner)
+            .await
+            .expect("fees");
+
+        assert_eq!(fees, TxOverrides::default());
+    }
+
+    #[tokio::test]
+    async fn legacy_strategy_scales_gas_price() {
+        let fees = RunnerConfig::default()
+            .with_fee_strategy(FeeStrategy::Legacy {
+                multiplier_percent: 150,
+            })
+            .initial_fees(&FeeRunner)
+            .await
+            .expect("fees");
+
+        assert_eq!(fees.gas_price, Some(3_000));
+        assert_eq!(fees.max_fee_per_gas, None);
+    }
+
+    #[tokio::test]
+    async fn eip1559_strategy_bumps_priority_fee_and_headroom() {
+        let fees = RunnerConfig::default()
+            .with_fee_strategy(FeeStrategy::Eip1559 {
+                priority_fee_bump_percent: 50,
+            })
+            .initial_fees(&FeeRunner)
+            .await
+            .expect("fees");
+
+        assert_eq!(fees.max_priority_fee_per_gas, Some(15));
+        assert_eq!(fees.max_fee_per_gas, Some(5_005));
+    }
+
+    #[tokio::test]
+    async fn caps_apply_to_fixed_and_provider_strategies() {
+        let caps = FeeCaps {
+            max_fee_per_gas: Some(4_000),
+            max_priority_fee_per_gas: Some(5),
+        };
+
+        let fixed = RunnerConfig::default()
+            .with_fee_strategy(FeeStrategy::Fixed {
+                max_fee_per_gas: 9_000,
+                max_priority_fee_per_gas: 8,
+            })
+            .with_fee_caps(caps)
+            .initial_fees(&FeeRunner)
+            .await
+            .expect("fees");
+        assert_eq!(fixed.max_fee_per_gas, Some(4_000));
+        assert_eq!(fixed.max_priority_fee_per_gas, Some(5));
+
+        let provider = RunnerConfig::default()
+            .with_fee_caps(caps)
+            .initial_fees(&FeeRunner)
+            .await
+            .expect("fees");
+        assert_eq!(provider.max_fee_per_gas, Some(4_000));
+    }
+
+    #[tokio::test]
+    async fn replacement_fees_respect_bump_and_market() {
+        let config = RunnerConfig::default();
+        let previous = TxOverrides {
+            nonce: Some(1),
+            max_fee_per_gas: Some(1_000),
+            max_priority_fee_per_gas: Some(100),
+            gas_price: None,
+        };
+
+        let replaced = config
+            .replacement_fees(&FeeRunner, previous)
+            .await
+            .expect("replacement");
+        assert_eq!(replaced.max_fee_per_gas, Some(5_000));
+        assert_eq!(replaced.max_priority_fee_per_gas, Some(110));
+
+        let fresh = config
+            .replacement_fees(&FeeRunner, TxOverrides::default())
+            .await
+            .expect("replacement");
+        assert_eq!(fresh.max_fee_per_gas, Some(5_500));
+        assert_eq!(fresh.max_priority_fee_per_gas, Some(11));
+
+        // A priority fee the cap keeps at its previous level would be rejected as
+        // underpriced, even though the max fee clears the bump.
+        let priority_capped = RunnerConfig::default().with_fee_caps(FeeCaps {
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: Some(100),
+        });
+        assert!(matches!(
+            priority_capped.replacement_fees(&FeeRunner, previous).await,
+            Err(RunnerError::Rejected(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn legacy_replacement_bumps_gas_price() {
+        let replaced = RunnerConfig::default()
+            .replacement_fees(
+                &FeeRunner,
+                TxOverrides {
+                    gas_price: Some(3_000),
+                    ..TxOverrides::default()
+                },
+            )
+            .await
+            .expect("replacement");
+
+        assert_eq!(replaced.gas_price, Some(3_300));
+        assert_eq!(replaced.max_fee_per_gas, None);
+    }
+
+    #[tokio::test]
+    async fn replacement_rejected_when_cap_blocks_outbidding() {
+        let config = RunnerConfig::default().with_fee_caps(FeeCaps {
+            max_fee_per_gas: Some(5_000),
+            max_priority_fee_per_gas: None,
+        });
+        let previous = TxOverrides {
+            max_fee_per_gas: Some(5_000),
+            max_priority_fee_per_gas: Some(10),
+            ..TxOverrides::default()
+        };
+
+        let result = config.replacement_fees(&FeeRunner, previous).await;
+
+        assert!(matches!(result, Err(RunnerError::Rejected(_))));
+    }
+}
@@ -0,0 +1,795 @@
+//! ERC-4337 runner: a batch of prepared transactions becomes one v0.7 user operation.
+//!
+//! The smart account decides how calls are encoded and how the operation hash is
+//! signed ([`SmartAccount`]; [`SimpleAccount`] covers the eth-infinitism reference
+//! account). Gas limits come from the bundler (`eth_estimateUserOperationGas`), fees
+//! from the node under the [`RunnerConfig`] fee strategy and caps, and sponsorship from
+//! an optional ERC-7677 paymaster service.
+
+use super::{
+    AnyHttpProvider, BundlerConfig, ContractRunner, FeeEstimate, FeePolicy, PreparedTransaction,
+    RunnerConfig, RunnerError, SigningProvider, SubmittedTx, TxReceiptInfo, build_read_provider,
+    call_error, parse_private_key, parse_rpc_url, prepared_to_request, receipt, sign_prehash,
+    tx_hash_to_bytes,
+};
+use alloy_primitives::{Address, B256, Bytes, U256, aliases::U192, eip191_hash_message, keccak256};
+use alloy_provider::Provider;
+use alloy_provider::transport::TransportError;
+use alloy_signer_local::PrivateKeySigner;
+use alloy_sol_types::{SolCall, SolValue, sol};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+/// Interval between `eth_getUserOperationReceipt` polls.
+const USER_OP_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Signature of the right length and shape for gas estimation; never valid.
+const DUMMY_ECDSA_SIGNATURE: [u8; 65] = {
+    let mut sig = [0xff; 65];
+    sig[64] = 0x1c;
+    sig
+};
+
+sol! {
+    #[sol(rpc)]
+    interface IEntryPointNonces {
+        function getNonce(address sender, uint192 key) external view returns (uint256 nonce);
+    }
+
+    interface ISimpleAccount {
+        function execute(address dest, uint256 value, bytes calldata func) external;
+        function executeBatch(address[] calldata dest, uint256[] calldata value, bytes[] calldata func) external;
+    }
+}
+
+/// v0.7 user operation in the unpacked JSON-RPC form bundlers accept.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    pub sender: Address,
+    pub nonce: U256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub factory: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub factory_data: Option<Bytes>,
+    pub call_data: Bytes,
+    pub call_gas_limit: U256,
+    pub verification_gas_limit: U256,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paymaster: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paymaster_verification_gas_limit: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paymaster_post_op_gas_limit: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paymaster_data: Option<Bytes>,
+    pub signature: Bytes,
+}
+
+impl UserOperation {
+    /// `factory || factoryData`, empty for deployed accounts.
+    pub fn init_code(&self) -> Bytes {
+        match self.factory {
+            Some(factory) => {
+                let data = self
+                    .factory_data
+                    .as_ref()
+                    .map_or(&[][..], |data| data.as_ref());
+                [factory.as_slice(), data].concat().into()
+            }
+            None => Bytes::new(),
+        }
+    }
+
+    /// `paymaster || verificationGasLimit (16 bytes) || postOpGasLimit (16 bytes) || data`,
+    /// empty without a paymaster.
+    pub fn paymaster_and_data(&self) -> Bytes {
+        let Some(paymaster) = self.paymaster else {
+            return Bytes::new();
+        };
+        let gas = |limit: Option<U256>| low_u128(limit.unwrap_or_default()).to_be_bytes();
+        [
+            paymaster.as_slice(),
+            &gas(self.paymaster_verification_gas_limit),
+            &gas(self.paymaster_post_op_gas_limit),
+            self.paymaster_data
+                .as_ref()
+                .map_or(&[][..], |data| data.as_ref()),
+        ]
+        .concat()
+        .into()
+    }
+
+    /// Hash the EntryPoint hands to the account for validation (`getUserOpHash`).
+    ///
+    /// Covers every field except the signature, bound to `entry_point` and `chain_id`.
+    pub fn hash(&self, entry_point: Address, chain_id: u64) -> B256 {
+        let packed = (
+            self.sender,
+            self.nonce,
+            keccak256(self.init_code()),
+            keccak256(&self.call_data),
+            pack_u128_pair(self.verification_gas_limit, self.call_gas_limit),
+            self.pre_verification_gas,
+            pack_u128_pair(self.max_priority_fee_per_gas, self.max_fee_per_gas),
+            keccak256(self.paymaster_and_data()),
+        )
+            .abi_encode();
+        keccak256((keccak256(packed), entry_point, U256::from(chain_id)).abi_encode())
+    }
+}
+
+fn low_u128(value: U256) -> u128 {
+    value.saturating_to()
+}
+
+fn pack_u128_pair(high: U256, low: U256) -> B256 {
+    let mut word = [0u8; 32];
+    word[..16].copy_from_slice(&low_u128(high).to_be_bytes());
+    word[16..].copy_from_slice(&low_u128(low).to_be_bytes());
+    B256::from(word)
+}
+
+/// Smart account a user operation is built for.
+#[async_trait]
+pub trait SmartAccount: Send + Sync {
+    /// Account address (the operation's `sender`).
+    fn address(&self) -> Address;
+
+    /// Factory and factory calldata deploying the account; only used while it has no code.
+    fn factory(&self) -> Option<(Address, Bytes)> {
+        None
+    }
+
+    /// Calldata making the account execute `txs` in order.
+    fn encode_calls(&self, txs: &[PreparedTransaction]) -> Result<Bytes, RunnerError>;
+
+    /// Placeholder signature with the real one's length, used for gas estimation.
+    fn dummy_signature(&self) -> Bytes;
+
+    /// Sign the EntryPoint's user operation hash.
+    async fn sign_user_op_hash(&self, hash: B256) -> Result<Bytes, RunnerError>;
+}
+
+/// eth-infinitism `SimpleAccount` (v0.7) owned by a local key.
+///
+/// Single transactions go through `execute`, batches through `executeBatch`; the owner
+/// signs the operation hash with the EIP-191 prefix, as the account's validator expects.
+pub struct SimpleAccount {
+    address: Address,
+    owner: PrivateKeySigner,
+    factory: Option<(Address, Bytes)>,
+}
+
+impl SimpleAccount {
+    /// Account at `address` owned by `owner_private_key`.
+    pub fn new(address: Address, owner_private_key: &str) -> Result<Self, RunnerError> {
+        Ok(Self {
+            address,
+            owner: parse_private_key(owner_private_key)?,
+            factory: None,
+        })
+    }
+
+    /// Deploy the account through `factory` with the first operation if it has no code yet.
+    pub fn with_factory(mut self, factory: Address, factory_data: Bytes) -> Self {
+        self.factory = Some((factory, factory_data));
+        self
+    }
+
+    /// Address of the owning key.
+    pub fn owner(&self) -> Address {
+        self.owner.address()
+    }
+}
+
+#[async_trait]
+impl SmartAccount for SimpleAccount {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn factory(&self) -> Option<(Address, Bytes)> {
+        self.factory.clone()
+    }
+
+    fn encode_calls(&self, txs: &[PreparedTransaction]) -> Result<Bytes, RunnerError> {
+        let data = match txs {
+            [] => {
+                return Err(RunnerError::Rejected(
+                    "no transactions provided".to_string(),
+                ));
+            }
+            [tx] => ISimpleAccount::executeCall {
+                dest: tx.to,
+                value: tx.value.unwrap_or_default(),
+                func: tx.data.clone(),
+            }
+            .abi_encode(),
+            txs => ISimpleAccount::executeBatchCall {
+                dest: txs.iter().map(|tx| tx.to).collect(),
+                value: txs.iter().map(|tx| tx.value.unwrap_or_default()).collect(),
+                func: txs.iter().map(|tx| tx.data.clone()).collect(),
+            }
+            .abi_encode(),
+        };
+        Ok(data.into())
+    }
+
+    fn dummy_signature(&self) -> Bytes {
+        Bytes::from_static(&DUMMY_ECDSA_SIGNATURE)
+    }
+
+    async fn sign_user_op_hash(&self, hash: B256) -> Result<Bytes, RunnerError> {
+        sign_prehash(&self.owner, eip191_hash_message(hash))
+    }
+}
+
+/// Gas limits returned by `eth_estimateUserOperationGas`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GasEstimate {
+    pre_verification_gas: U256,
+    verification_gas_limit: U256,
+    call_gas_limit: U256,
+    #[serde(default)]
+    paymaster_verification_gas_limit: Option<U256>,
+    #[serde(default)]
+    paymaster_post_op_gas_limit: Option<U256>,
+}
+
+/// Paymaster fields from `pm_getPaymasterStubData` / `pm_getPaymasterData`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PaymasterFields {
+    paymaster: Option<Address>,
+    paymaster_data: Option<Bytes>,
+    #[serde(default)]
+    paymaster_verification_gas_limit: Option<U256>,
+    #[serde(default)]
+    paymaster_post_op_gas_limit: Option<U256>,
+    /// Stub data that is already final needs no second `pm_getPaymasterData` round.
+    #[serde(default)]
+    is_final: bool,
+}
+
+impl PaymasterFields {
+    fn apply(&self, op: &mut UserOperation) {
+        op.paymaster = self.paymaster;
+        op.paymaster_data = self.paymaster_data.clone();
+        if self.paymaster_verification_gas_limit.is_some() {
+            op.paymaster_verification_gas_limit = self.paymaster_verification_gas_limit;
+        }
+        if self.paymaster_post_op_gas_limit.is_some() {
+            op.paymaster_post_op_gas_limit = self.paymaster_post_op_gas_limit;
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UserOperationReceipt {
+    success: bool,
+    receipt: IncludedTransaction,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IncludedTransaction {
+    transaction_hash: B256,
+}
+
+/// Runner submitting prepared transactions as ERC-4337 user operations.
+///
+/// Each [`ContractRunner::send_transactions`] call becomes one user operation, so a
+/// batch executes atomically; the returned entry carries the bundle transaction hash
+/// once the bundler reports the operation as included.
+pub struct Erc4337Runner<A: SmartAccount = SimpleAccount> {
+    account: A,
+    provider: SigningProvider,
+    bundler: AnyHttpProvider,
+    paymaster: Option<(AnyHttpProvider, Option<Value>)>,
+    bundler_config: BundlerConfig,
+    config: RunnerConfig,
+    chain_id: u64,
+}
+
+impl<A: SmartAccount> Erc4337Runner<A> {
+    /// Connect `account` to the node at `rpc_url` and the bundler in `config.bundler`.
+    pub async fn connect(
+        rpc_url: &str,
+        account: A,
+        config: RunnerConfig,
+    ) -> Result<Self, RunnerError> {
+        let bundler_config = config.bundler.clone().ok_or_else(|| {
+            RunnerError::Rejected("RunnerConfig has no bundler configured".to_string())
+        })?;
+        let provider = build_read_provider(parse_rpc_url(rpc_url)?).erased();
+        let bundler = build_read_provider(parse_rpc_url(&bundler_config.bundler_url)?);
+        let paymaster = match &bundler_config.paymaster {
+            Some(paymaster) => Some((
+                build_read_provider(parse_rpc_url(&paymaster.url)?),
+                paymaster.context.clone(),
+            )),
+            None => None,
+        };
+        let chain_id = provider.get_chain_id().await.map_err(transport_error)?;
+        Ok(Self {
+            account,
+            provider,
+            bundler,
+            paymaster,
+            bundler_config,
+            config,
+            chain_id,
+        })
+    }
+
+    /// The smart account operations are built for.
+    pub fn account(&self) -> &A {
+        &self.account
+    }
+
+    /// EntryPoint the operations target.
+    pub fn entry_point(&self) -> Address {
+        self.bundler_config.entry_point
+    }
+
+    /// Build an unsigned user operation for `txs` with nonce, fees, gas limits and
+    /// paymaster data filled in. The signature is a placeholder.
+    pub async fn prepare_user_operation(
+        &self,
+        txs: Vec<PreparedTransaction>,
+    ) -> Result<UserOperation, RunnerError> {
+        if txs.is_empty() {
+            return Err(RunnerError::Rejected(
+                "no transactions provided".to_string(),
+            ));
+        }
+
+        let sender = self.account.address();
+        let nonce = IEntryPointNonces::new(self.entry_point(), &self.provider)
+            .getNonce(sender, U192::ZERO)
+            .call()
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))?;
+        let (factory, factory_data) = match self.account.factory() {
+            Some((factory, data)) if self.needs_deployment(sender).await? => {
+                (Some(factory), Some(data))
+            }
+            _ => (None, None),
+        };
+        let fees = self.user_operation_fees().await?;
+
+        let mut op = UserOperation {
+            sender,
+            nonce,
+            factory,
+            factory_data,
+            call_data: self.account.encode_calls(&txs)?,
+            max_fee_per_gas: U256::from(fees.max_fee_per_gas),
+            max_priority_fee_per_gas: U256::from(fees.max_priority_fee_per_gas),
+            signature: self.account.dummy_signature(),
+            ..UserOperation::default()
+        };
+
+        let stub = match &self.paymaster {
+            Some(_) => Some(
+                self.paymaster_request("pm_getPaymasterStubData", &op)
+                    .await?,
+            ),
+            None => None,
+        };
+        if let Some(stub) = &stub {
+            stub.apply(&mut op);
+        }
+
+        let estimate: GasEstimate = self
+            .bundler
+            .raw_request(
+                "eth_estimateUserOperationGas".into(),
+                (&op, self.entry_point()),
+            )
+            .await
+            .map_err(rpc_error)?;
+        op.pre_verification_gas = estimate.pre_verification_gas;
+        op.verification_gas_limit = estimate.verification_gas_limit;
+        op.call_gas_limit = estimate.call_gas_limit;
+        if op.paymaster.is_some() {
+            op.paymaster_verification_gas_limit = estimate
+                .paymaster_verification_gas_limit
+                .or(op.paymaster_verification_gas_limit);
+            op.paymaster_post_op_gas_limit = estimate
+                .paymaster_post_op_gas_limit
+                .or(op.paymaster_post_op_gas_limit);
+        }
+
+        if let Some(stub) = stub
+            && !stub.is_final
+        {
+            self.paymaster_request("pm_getPaymasterData", &op)
+                .await?
+                .apply(&mut op);
+        }
+        Ok(op)
+    }
+
+    /// Hash the account signs for `op` on this runner's chain and EntryPoint.
+    pub fn user_operation_hash(&self, op: &UserOperation) -> B256 {
+        op.hash(self.entry_point(), self.chain_id)
+    }
+
+    /// Submit a signed operation and return the bundler's user operation hash.
+    pub async fn send_user_operation(&self, op: &UserOperation) -> Result<B256, RunnerError> {
+        self.bundler
+            .raw_request("eth_sendUserOperation".into(), (op, self.entry_point()))
+            .await
+            .map_err(rpc_error)
+    }
+
+    /// Poll the bundler until `user_op_hash` is included, up to the configured timeout.
+    ///
+    /// Returns the bundle transaction hash and whether the operation itself succeeded.
+    pub async fn wait_for_user_operation(
+        &self,
+        user_op_hash: B256,
+    ) -> Result<(B256, bool), RunnerError> {
+        let timeout = self.bundler_config.receipt_timeout;
+        let deadline = Instant::now() + timeout;
+        loop {
+            let receipt: Option<UserOperationReceipt> = self
+                .bundler
+                .raw_request("eth_getUserOperationReceipt".into(), (user_op_hash,))
+                .await
+                .map_err(rpc_error)?;
+            if let Some(receipt) = receipt {
+                return Ok((receipt.receipt.transaction_hash, receipt.success));
+            }
+            if Instant::now() >= deadline {
+                return Err(RunnerError::Timeout(format!(
+                    "user operation {user_op_hash} not included within {timeout:?}"
+                )));
+            }
+            tokio::time::sleep(USER_OP_POLL_INTERVAL).await;
+        }
+    }
+
+    async fn needs_deployment(&self, sender: Address) -> Result<bool, RunnerError> {
+        let code = self
+            .provider
+            .get_code_at(sender)
+            .await
+            .map_err(transport_error)?;
+        Ok(code.is_empty())
+    }
+
+    /// Fees from the configured strategy; the provider strategy falls back to the node
+    /// estimate because user operations always carry explicit fees.
+    async fn user_operation_fees(&self) -> Result<FeeEstimate, RunnerError> {
+        let fees = self.config.initial_fees(self).await?;
+        Ok(
+            match (
+                fees.max_fee_per_gas,
+                fees.max_priority_fee_per_gas,
+                fees.gas_price,
+            ) {
+                (Some(max_fee_per_gas), Some(max_priority_fee_per_gas), _) => FeeEstimate {
+                    max_fee_per_gas,
+                    max_priority_fee_per_gas,
+                },
+                (_, _, Some(gas_price)) => FeeEstimate {
+                    max_fee_per_gas: gas_price,
+                    max_priority_fee_per_gas: gas_price,
+                },
+                _ => self.estimate_fees().await?,
+            },
+        )
+    }
+
+    async fn paymaster_request(
+        &self,
+        method: &'static str,
+        op: &UserOperation,
+    ) -> Result<PaymasterFields, RunnerError> {
+        let Some((paymaster, context)) = &self.paymaster else {
+            return Err(RunnerError::Unsupported(
+                "no paymaster configured".to_string(),
+            ));
+        };
+        paymaster
+            .raw_request(
+                method.into(),
+                (
+                    op,
+                    self.entry_point(),
+                    U256::from(self.chain_id),
+                    context.clone().unwrap_or(Value::Object(Default::default())),
+                ),
+            )
+            .await
+            .map_err(rpc_error)
+    }
+}
+
+#[async_trait]
+impl<A: SmartAccount> ContractRunner for Erc4337Runner<A> {
+    fn sender_address(&self) -> Address {
+        self.account.address()
+    }
+
+    async fn estimate_gas(&self, tx: PreparedTransaction) -> Result<u64, RunnerError> {
+        self.provider
+            .estimate_gas(prepared_to_request(self.address(), tx).into())
+            .await
+            .map_err(transport_error)
+    }
+
+    async fn call(&self, tx: PreparedTransaction) -> Result<Bytes, RunnerError> {
+        self.provider
+            .call(prepared_to_request(self.address(), tx).into())
+            .await
+            .map_err(call_error)
+    }
+
+    async fn gas_price(&self) -> Result<u128, RunnerError> {
+        self.provider.get_gas_price().await.map_err(transport_error)
+    }
+
+    async fn estimate_fees(&self) -> Result<FeeEstimate, RunnerError> {
+        let estimate = self
+            .provider
+            .estimate_eip1559_fees()
+            .await
+            .map_err(transport_error)?;
+        Ok(FeeEstimate {
+            max_fee_per_gas: estimate.max_fee_per_gas,
+            max_priority_fee_per_gas: estimate.max_priority_fee_per_gas,
+        })
+    }
+
+    async fn block_number(&self) -> Result<u64, RunnerError> {
+        receipt::fetch_block_number(&self.provider).await
+    }
+
+    async fn chain_id(&self) -> Result<u64, RunnerError> {
+        Ok(self.chain_id)
+    }
+
+    async fn transaction_receipt(
+        &self,
+        tx_hash: B256,
+    ) -> Result<Option<TxReceiptInfo>, RunnerError> {
+        receipt::fetch_receipt(&self.provider, tx_hash).await
+    }
+
+    async fn send_transactions(
+        &self,
+        txs: Vec<PreparedTransaction>,
+    ) -> Result<Vec<SubmittedTx>, RunnerError> {
+        let mut op = self.prepare_user_operation(txs).await?;
+        op.signature = self
+            .account
+            .sign_user_op_hash(self.user_operation_hash(&op))
+            .await?;
+        let user_op_hash = self.send_user_operation(&op).await?;
+        let (tx_hash, success) = self.wait_for_user_operation(user_op_hash).await?;
+        Ok(vec![SubmittedTx {
+            tx_hash: tx_hash_to_bytes(tx_hash),
+            success,
+            index: None,
+        }])
+    }
+}
+
+fn transport_error(err: TransportError) -> RunnerError {
+    RunnerError::Transport(err.to_string())
+}
+
+/// JSON-RPC errors from a bundler or paymaster are validation failures (e.g. `AA21`),
+/// everything else is a transport problem.
+fn rpc_error(err: TransportError) -> RunnerError {
+    if err.as_error_resp().is_some() {
+        RunnerError::Rejected(err.to_string())
+    } else {
+        RunnerError::Transport(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{BundlerConfig, FeeStrategy, PaymasterConfig};
+    use alloy_primitives::Signature;
+    use circles_rpc_mock::MockRpcServer;
+    use serde_json::json;
+
+    const OWNER_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    fn tx(byte: u8, value: Option<u64>) -> PreparedTransaction {
+        PreparedTransaction {
+            to: Address::repeat_byte(byte),
+            data: Bytes::from(vec![byte; 4]),
+            value: value.map(U256::from),
+        }
+    }
+
+    #[test]
+    fn packed_fields_follow_entry_point_layout() {
+        let op = UserOperation {
+            factory: Some(Address::repeat_byte(0xfa)),
+            factory_data: Some(Bytes::from_static(&[1, 2])),
+            paymaster: Some(Address::repeat_byte(0xaa)),
+            paymaster_verification_gas_limit: Some(U256::from(7u64)),
+            paymaster_post_op_gas_limit: Some(U256::from(9u64)),
+            paymaster_data: Some(Bytes::from_static(&[0xbe, 0xef])),
+            ..UserOperation::default()
+        };
+
+        let init_code = op.init_code();
+        assert_eq!(&init_code[..20], Address::repeat_byte(0xfa).as_slice());
+        assert_eq!(&init_code[20..], &[1, 2]);
+
+        let pm = op.paymaster_and_data();
+        assert_eq!(pm.len(), 20 + 16 + 16 + 2);
+        assert_eq!(&pm[..20], Address::repeat_byte(0xaa).as_slice());
+        assert_eq!(pm[35], 7);
+        assert_eq!(pm[51], 9);
+        assert_eq!(&pm[52..], &[0xbe, 0xef]);
+
+        let gas = pack_u128_pair(U256::from(1u64), U256::from(2u64));
+        assert_eq!(gas[15], 1);
+        assert_eq!(gas[31], 2);
+
+        assert!(UserOperation::default().paymaster_and_data().is_empty());
+        assert!(UserOperation::default().init_code().is_empty());
+    }
+
+    #[test]
+    fn hash_excludes_signature_and_binds_chain() {
+        let op = UserOperation {
+            sender: Address::repeat_byte(1),
+            nonce: U256::from(3u64),
+            call_data: Bytes::from_static(&[0xab]),
+            ..UserOperation::default()
+        };
+        let entry_point = crate::runner::ENTRY_POINT_V07;
+        let signed = UserOperation {
+            signature: Bytes::from_static(&[1; 65]),
+            ..op.clone()
+        };
+
+        assert_eq!(op.hash(entry_point, 100), signed.hash(entry_point, 100));
+        assert_ne!(op.hash(entry_point, 100), op.hash(entry_point, 10200));
+        assert_ne!(
+            op.hash(entry_point, 100),
+            op.hash(Address::repeat_byte(2), 100)
+        );
+    }
+
+    #[tokio::test]
+    async fn simple_account_encodes_batches_and_signs_prefixed_hash() {
+        let account = SimpleAccount::new(Address::repeat_byte(0x42), OWNER_KEY).expect("account");
+
+        let single = account.encode_calls(&[tx(1, Some(5))]).expect("single");
+        assert_eq!(&single[..4], ISimpleAccount::executeCall::SELECTOR);
+        let batch = account
+            .encode_calls(&[tx(1, None), tx(2, Some(5))])
+            .expect("batch");
+        let decoded = ISimpleAccount::executeBatchCall::abi_decode(&batch).expect("decode");
+        assert_eq!(
+            decoded.dest,
+            vec![Address::repeat_byte(1), Address::repeat_byte(2)]
+        );
+        assert_eq!(decoded.value, vec![U256::ZERO, U256::from(5u64)]);
+        assert!(account.encode_calls(&[]).is_err());
+
+        let hash = B256::repeat_byte(0x11);
+        let signature = account.sign_user_op_hash(hash).await.expect("sign");
+        assert_eq!(signature.len(), 65);
+        let recovered = Signature::from_raw(&signature)
+            .expect("signature")
+            .recover_address_from_prehash(&eip191_hash_message(hash))
+            .expect("recover");
+        assert_eq!(recovered, account.owner());
+    }
+
+    #[tokio::test]
+    async fn sponsored_batch_becomes_one_user_operation() {
+        let user_op_hash = B256::repeat_byte(0x0a);
+        let bundle_hash = B256::repeat_byte(0x0b);
+        let server = MockRpcServer::builder()
+            .respond("eth_chainId", json!("0x64"))
+            .respond("eth_call", json!(format!("0x{:064x}", 7)))
+            .respond(
+                "pm_getPaymasterStubData",
+                json!({
+                    "paymaster": Address::repeat_byte(0xaa),
+                    "paymasterData": "0x00",
+                    "paymasterPostOpGasLimit": "0x10",
+                }),
+            )
+            .respond(
+                "eth_estimateUserOperationGas",
+                json!({
+                    "preVerificationGas": "0x1",
+                    "verificationGasLimit": "0x2",
+                    "callGasLimit": "0x3",
+                    "paymasterVerificationGasLimit": "0x4",
+                }),
+            )
+            .respond(
+                "pm_getPaymasterData",
+                json!({
+                    "paymaster": Address::repeat_byte(0xaa),
+                    "paymasterData": "0xbeef",
+                }),
+            )
+            .respond("eth_sendUserOperation", json!(user_op_hash))
+            .respond(
+                "eth_getUserOperationReceipt",
+                json!({
+                    "success": true,
+                    "receipt": { "transactionHash": bundle_hash },
+                }),
+            )
+            .start()
+            .await
+            .expect("server");
+
+        let config = RunnerConfig::default()
+            .with_fee_strategy(FeeStrategy::Fixed {
+                max_fee_per_gas: 2_000,
+                max_priority_fee_per_gas: 1_000,
+            })
+            .with_bundler(
+                BundlerConfig::new(server.url()).with_paymaster(PaymasterConfig {
+                    url: server.url(),
+                    context: Some(json!({ "sponsorshipPolicyId": "circles" })),
+                }),
+            );
+        let account = SimpleAccount::new(Address::repeat_byte(0x42), OWNER_KEY).expect("account");
+        let runner = Erc4337Runner::connect(&server.url(), account, config)
+            .await
+            .expect("runner");
+
+        let submitted = runner
+            .send_transactions(vec![tx(1, None), tx(2, None)])
+            .await
+            .expect("submitted");
+        assert_eq!(submitted.len(), 1);
+        assert_eq!(submitted[0].hash(), Some(bundle_hash));
+        assert!(submitted[0].success);
+
+        let sent = &server.params_for("eth_sendUserOperation")[0];
+        let op: UserOperation = serde_json::from_value(sent[0].clone()).expect("user op");
+        assert_eq!(sent[1], json!(crate::runner::ENTRY_POINT_V07));
+        assert_eq!(op.nonce, U256::from(7u64));
+        assert_eq!(op.call_gas_limit, U256::from(3u64));
+        assert_eq!(op.max_fee_per_gas, U256::from(2_000u64));
+        assert_eq!(op.paymaster_data, Some(Bytes::from_static(&[0xbe, 0xef])));
+        assert_eq!(op.paymaster_verification_gas_limit, Some(U256::from(4u64)));
+        assert_eq!(op.paymaster_post_op_gas_limit, Some(U256::from(16u64)));
+        let recovered = Signature::from_raw(&op.signature)
+            .expect("signature")
+            .recover_address_from_prehash(&eip191_hash_message(runner.user_operation_hash(&op)))
+            .expect("recover");
+        assert_eq!(recovered, runner.account().owner());
+
+        let stub_params = &server.params_for("pm_getPaymasterStubData")[0];
+        assert_eq!(stub_params[2], json!("0x64"));
+        assert_eq!(stub_params[3]["sponsorshipPolicyId"], "circles");
+    }
+
+    #[tokio::test]
+    async fn connect_requires_bundler_config() {
+        let account = SimpleAccount::new(Address::repeat_byte(0x42), OWNER_KEY).expect("account");
+        let result =
+            Erc4337Runner::connect("http://127.0.0.1:1", account, RunnerConfig::default()).await;
+        assert!(matches!(result, Err(RunnerError::Rejected(_))));
+    }
+}
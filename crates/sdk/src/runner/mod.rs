@@ -7,11 +7,13 @@
 
 use alloy_network::{AnyNetwork, EthereumWallet};
 use alloy_primitives::{Address, B256, Bytes, U256, aliases::TxHash};
+use alloy_provider::transport::TransportError;
 use alloy_provider::{DynProvider, Identity, Provider, ProviderBuilder, RootProvider};
 use alloy_rpc_types::TransactionRequest;
 use alloy_signer_local::PrivateKeySigner;
 use alloy_sol_types::SolCall;
 use async_trait::async_trait;
+use circles_types::ErrorKind;
 use reqwest::Url;
 use safe_rs::{
     Call, CallBuilder, ChainConfig, Eoa, EoaBatchResult, Error as SafeRsError, ExecutionResult,
@@ -20,6 +22,26 @@ use safe_rs::{
 };
 use thiserror::Error;
 
+mod chain;
+mod config;
+mod erc4337;
+mod nonce;
+mod receipt;
+mod relay;
+mod signer;
+
+pub use chain::ChainCheckedRunner;
+pub(crate) use config::http_client;
+pub use config::{
+    BundlerConfig, ENTRY_POINT_V07, FeeCaps, FeePolicy, FeeStrategy, PaymasterConfig, RelayConfig,
+    RelayMode, RunnerConfig, StuckTxRetry,
+};
+pub use erc4337::{Erc4337Runner, SimpleAccount, SmartAccount, UserOperation};
+pub use nonce::{NonceManagedRunner, PendingNonceTx};
+pub use receipt::{TxMonitor, TxMonitorEvent, TxReceiptInfo};
+pub use relay::{RelayRunner, RelayTaskState, RelayTaskStatus};
+pub use signer::SignerContractRunner;
+
 // Read-only provider for the browser/external-signing builder (no local key).
 type AnyHttpProvider = RootProvider<AnyNetwork>;
 // Provider for runners that submit transactions. It carries a WalletFiller so
@@ -32,7 +54,7 @@ type EoaWallet = Wallet<Eoa<SigningProvider>>;
 
 /// Prepared transaction for a runner to submit. This is intentionally simple;
 /// we can swap to richer contract-specific types as we wire more flows.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct PreparedTransaction {
     /// Contract address to call.
     pub to: Address,
@@ -65,6 +87,30 @@ pub struct SubmittedTx {
     pub index: Option<usize>,
 }
 
+/// Explicit nonce and fee overrides for a single raw submission.
+///
+/// Unset fields are left to the backend's own fillers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TxOverrides {
+    /// Account nonce to submit with.
+    pub nonce: Option<u64>,
+    /// EIP-1559 fee cap in wei.
+    pub max_fee_per_gas: Option<u128>,
+    /// EIP-1559 priority fee in wei.
+    pub max_priority_fee_per_gas: Option<u128>,
+    /// Legacy gas price in wei; takes the place of the EIP-1559 fields.
+    pub gas_price: Option<u128>,
+}
+
+/// EIP-1559 fee suggestion reported by a runner backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeEstimate {
+    /// Suggested fee cap in wei.
+    pub max_fee_per_gas: u128,
+    /// Suggested priority fee in wei.
+    pub max_priority_fee_per_gas: u128,
+}
+
 /// Prepared Safe execution data for browser/external-signature workflows.
 ///
 /// This is the Rust analogue of the TypeScript Safe batch `getSafeTransaction()`
@@ -234,6 +280,65 @@ pub trait ContractRunner: Send + Sync {
         Box::new(BufferedBatchRun::new(self))
     }
 
+    /// Next account nonce including pending transactions, when the backend exposes it.
+    async fn pending_nonce(&self) -> Result<u64, RunnerError> {
+        Err(RunnerError::Unsupported(
+            "nonce lookup is not supported by this runner".to_string(),
+        ))
+    }
+
+    /// Current legacy gas price, when the backend exposes it.
+    async fn gas_price(&self) -> Result<u128, RunnerError> {
+        Err(RunnerError::Unsupported(
+            "gas price lookup is not supported by this runner".to_string(),
+        ))
+    }
+
+    /// Current EIP-1559 fee suggestion, when the backend exposes it.
+    async fn estimate_fees(&self) -> Result<FeeEstimate, RunnerError> {
+        Err(RunnerError::Unsupported(
+            "fee estimation is not supported by this runner".to_string(),
+        ))
+    }
+
+    /// Latest block number seen by the backend.
+    async fn block_number(&self) -> Result<u64, RunnerError> {
+        Err(RunnerError::Unsupported(
+            "block number lookup is not supported by this runner".to_string(),
+        ))
+    }
+
+    /// Chain id of the network this runner submits to.
+    async fn chain_id(&self) -> Result<u64, RunnerError> {
+        Err(RunnerError::Unsupported(
+            "chain id lookup is not supported by this runner".to_string(),
+        ))
+    }
+
+    /// Receipt for a mined transaction, or `None` while it is still pending.
+    async fn transaction_receipt(
+        &self,
+        _tx_hash: B256,
+    ) -> Result<Option<TxReceiptInfo>, RunnerError> {
+        Err(RunnerError::Unsupported(
+            "receipt lookup is not supported by this runner".to_string(),
+        ))
+    }
+
+    /// Submit a single transaction with explicit nonce/fee overrides.
+    ///
+    /// Used by [`NonceManagedRunner`] to pin nonces and to replace pending
+    /// transactions. The returned entry reflects broadcast, not inclusion.
+    async fn send_transaction_with_overrides(
+        &self,
+        _tx: PreparedTransaction,
+        _overrides: TxOverrides,
+    ) -> Result<SubmittedTx, RunnerError> {
+        Err(RunnerError::Unsupported(
+            "nonce/fee overrides are not supported by this runner".to_string(),
+        ))
+    }
+
     /// Submit one or more prepared transactions.
     async fn send_transactions(
         &self,
@@ -250,13 +355,43 @@ pub enum RunnerError {
     Transport(String),
     #[error("runner capability unsupported: {0}")]
     Unsupported(String),
+    #[error("runner timed out: {0}")]
+    Timeout(String),
+    #[error("runner is connected to chain {actual}, but the config targets chain {expected}")]
+    ChainMismatch { expected: u64, actual: u64 },
+}
+
+impl RunnerError {
+    /// Coarse classification for retry and UX policies.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Rejected(_) => ErrorKind::Rejected,
+            Self::Transport(_) => ErrorKind::Network,
+            Self::Unsupported(_) => ErrorKind::Configuration,
+            Self::Timeout(_) => ErrorKind::Timeout,
+            Self::ChainMismatch { .. } => ErrorKind::Configuration,
+        }
+    }
+}
+
+/// `eth_call` failures: a JSON-RPC error response is the node reporting a revert, anything
+/// else means the call never got an answer.
+pub(crate) fn call_error(err: TransportError) -> RunnerError {
+    if err.as_error_resp().is_some() {
+        RunnerError::Rejected(err.to_string())
+    } else {
+        RunnerError::Transport(err.to_string())
+    }
 }
 
-fn tx_hash_to_bytes(tx_hash: TxHash) -> Bytes {
+pub(crate) fn tx_hash_to_bytes(tx_hash: TxHash) -> Bytes {
     Bytes::copy_from_slice(tx_hash.as_slice())
 }
 
-fn prepared_to_request(from: Option<Address>, tx: PreparedTransaction) -> TransactionRequest {
+pub(crate) fn prepared_to_request(
+    from: Option<Address>,
+    tx: PreparedTransaction,
+) -> TransactionRequest {
     let mut request = TransactionRequest::default()
         .to(tx.to)
         .input(tx.data.into())
@@ -273,6 +408,22 @@ fn prepared_to_request(from: Option<Address>, tx: PreparedTransaction) -> Transa
     request
 }
 
+fn apply_overrides(mut request: TransactionRequest, overrides: TxOverrides) -> TransactionRequest {
+    if let Some(nonce) = overrides.nonce {
+        request = request.nonce(nonce);
+    }
+    if let Some(max_fee) = overrides.max_fee_per_gas {
+        request = request.max_fee_per_gas(max_fee);
+    }
+    if let Some(priority_fee) = overrides.max_priority_fee_per_gas {
+        request = request.max_priority_fee_per_gas(priority_fee);
+    }
+    if let Some(gas_price) = overrides.gas_price {
+        request = request.gas_price(gas_price);
+    }
+    request
+}
+
 fn submitted_from_execution_result(result: ExecutionResult) -> Vec<SubmittedTx> {
     vec![SubmittedTx {
         tx_hash: tx_hash_to_bytes(result.tx_hash),
@@ -314,6 +465,17 @@ fn parse_private_key(private_key: &str) -> Result<PrivateKeySigner, RunnerError>
         .map_err(|err| RunnerError::Rejected(format!("invalid private key: {err}")))
 }
 
+/// 65-byte `r || s || v` signature (`v` in `{27, 28}`) over an already hashed digest.
+fn sign_prehash(signer: &PrivateKeySigner, digest: B256) -> Result<Bytes, RunnerError> {
+    let (signature, recovery_id) = signer
+        .credential()
+        .sign_prehash_recoverable(digest.as_slice())
+        .map_err(|err| RunnerError::Rejected(format!("signing failed: {err}")))?;
+    let mut bytes = signature.to_bytes().to_vec();
+    bytes.push(27 + recovery_id.to_byte());
+    Ok(Bytes::from(bytes))
+}
+
 fn build_read_provider(rpc_url: Url) -> AnyHttpProvider {
     ProviderBuilder::<Identity, Identity, AnyNetwork>::default().connect_http(rpc_url)
 }
@@ -521,9 +683,27 @@ impl ContractRunner for SafeContractRunner {
         self.provider
             .call(prepared_to_request(self.address(), tx).into())
             .await
+            .map_err(call_error)
+    }
+
+    async fn block_number(&self) -> Result<u64, RunnerError> {
+        receipt::fetch_block_number(&self.provider).await
+    }
+
+    async fn chain_id(&self) -> Result<u64, RunnerError> {
+        self.provider
+            .get_chain_id()
+            .await
             .map_err(|err| RunnerError::Transport(err.to_string()))
     }
 
+    async fn transaction_receipt(
+        &self,
+        tx_hash: B256,
+    ) -> Result<Option<TxReceiptInfo>, RunnerError> {
+        receipt::fetch_receipt(&self.provider, tx_hash).await
+    }
+
     async fn send_transactions(
         &self,
         txs: Vec<PreparedTransaction>,
@@ -622,9 +802,72 @@ impl ContractRunner for EoaContractRunner {
         self.provider
             .call(prepared_to_request(self.address(), tx).into())
             .await
+            .map_err(call_error)
+    }
+
+    async fn pending_nonce(&self) -> Result<u64, RunnerError> {
+        self.provider
+            .get_transaction_count(self.wallet.address())
+            .pending()
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))
+    }
+
+    async fn gas_price(&self) -> Result<u128, RunnerError> {
+        self.provider
+            .get_gas_price()
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))
+    }
+
+    async fn estimate_fees(&self) -> Result<FeeEstimate, RunnerError> {
+        let estimate = self
+            .provider
+            .estimate_eip1559_fees()
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))?;
+        Ok(FeeEstimate {
+            max_fee_per_gas: estimate.max_fee_per_gas,
+            max_priority_fee_per_gas: estimate.max_priority_fee_per_gas,
+        })
+    }
+
+    async fn block_number(&self) -> Result<u64, RunnerError> {
+        receipt::fetch_block_number(&self.provider).await
+    }
+
+    async fn chain_id(&self) -> Result<u64, RunnerError> {
+        self.provider
+            .get_chain_id()
+            .await
             .map_err(|err| RunnerError::Transport(err.to_string()))
     }
 
+    async fn transaction_receipt(
+        &self,
+        tx_hash: B256,
+    ) -> Result<Option<TxReceiptInfo>, RunnerError> {
+        receipt::fetch_receipt(&self.provider, tx_hash).await
+    }
+
+    async fn send_transaction_with_overrides(
+        &self,
+        tx: PreparedTransaction,
+        overrides: TxOverrides,
+    ) -> Result<SubmittedTx, RunnerError> {
+        let request = apply_overrides(prepared_to_request(self.address(), tx), overrides);
+        let pending = self
+            .provider
+            .send_transaction(request.into())
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))?;
+        Ok(SubmittedTx {
+            tx_hash: tx_hash_to_bytes(*pending.tx_hash()),
+            success: true,
+            index: None,
+        })
+    }
+
     async fn send_transactions(
         &self,
         txs: Vec<PreparedTransaction>,
@@ -926,6 +1169,63 @@ mod tests {
         assert_eq!(request.input.data.as_ref(), Some(&tx.data));
     }
 
+    #[test]
+    fn apply_overrides_sets_only_provided_fields() {
+        let request = prepared_to_request(
+            Some(ANVIL_FIRST_ADDRESS),
+            PreparedTransaction {
+                to: ANVIL_SECOND_ADDRESS,
+                data: Bytes::new(),
+                value: None,
+            },
+        );
+
+        let untouched = apply_overrides(request.clone(), TxOverrides::default());
+        assert_eq!(untouched.nonce, None);
+        assert_eq!(untouched.max_fee_per_gas, None);
+
+        let pinned = apply_overrides(
+            request,
+            TxOverrides {
+                nonce: Some(7),
+                max_fee_per_gas: Some(2_000),
+                max_priority_fee_per_gas: Some(100),
+                gas_price: None,
+            },
+        );
+        assert_eq!(pinned.nonce, Some(7));
+        assert_eq!(pinned.max_fee_per_gas, Some(2_000));
+        assert_eq!(pinned.max_priority_fee_per_gas, Some(100));
+        assert_eq!(pinned.gas_price, None);
+    }
+
+    #[tokio::test]
+    async fn default_nonce_and_override_hooks_are_unsupported() {
+        let runner = RecordingRunner::default();
+
+        assert!(matches!(
+            runner.pending_nonce().await,
+            Err(RunnerError::Unsupported(_))
+        ));
+        assert!(matches!(
+            runner.estimate_fees().await,
+            Err(RunnerError::Unsupported(_))
+        ));
+        assert!(matches!(
+            runner
+                .send_transaction_with_overrides(
+                    PreparedTransaction {
+                        to: ANVIL_SECOND_ADDRESS,
+                        data: Bytes::new(),
+                        value: None,
+                    },
+                    TxOverrides::default(),
+                )
+                .await,
+            Err(RunnerError::Unsupported(_))
+        ));
+    }
+
     #[test]
     fn contract_runner_address_defaults_to_sender_address() {
         let runner = RecordingRunner::default();
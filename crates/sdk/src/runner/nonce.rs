@@ -0,0 +1,687 @@
+//! Nonce tracking and serialized submission on top of any [`ContractRunner`].
+
+use super::{
+    ContractRunner, FeeEstimate, FeePolicy, PreparedTransaction, RunnerConfig, RunnerError,
+    SubmittedTx, TxOverrides, TxReceiptInfo,
+};
+use alloy_primitives::{Address, B256, Bytes};
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::time::Instant;
+use tokio::sync::Mutex;
+
+/// Transaction broadcast through a [`NonceManagedRunner`] and still tracked as pending.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingNonceTx {
+    /// Nonce the transaction was submitted with.
+    pub nonce: u64,
+    /// Transaction currently occupying the nonce (the replacement after a speed-up/cancel).
+    pub tx: PreparedTransaction,
+    /// Hash of the latest broadcast for this nonce.
+    pub tx_hash: Bytes,
+    /// Overrides used for the latest broadcast.
+    pub overrides: TxOverrides,
+    /// When the latest broadcast for this nonce happened.
+    pub submitted_at: Instant,
+    /// Number of replacements sent for this nonce so far.
+    pub replacements: u32,
+}
+
+#[derive(Debug, Default)]
+struct NonceState {
+    next_nonce: Option<u64>,
+    pending: BTreeMap<u64, PendingNonceTx>,
+}
+
+/// Runner wrapper that assigns nonces locally and serializes submissions.
+///
+/// All sends for the wrapped sender, batches and single
+/// [`ContractRunner::send_transaction_with_overrides`] calls alike, go through one async
+/// lock and one nonce reservation, so rapid multi-tx plans never race each other for the
+/// same nonce. Nonces are seeded from
+/// [`ContractRunner::pending_nonce`] and resynced once when the backend reports
+/// "nonce too low". Fees and replacement bumps follow the wrapper's
+/// [`RunnerConfig`]. Runners without nonce support fall back to plain serialized
+/// [`ContractRunner::send_transactions`] calls.
+pub struct NonceManagedRunner<R> {
+    inner: R,
+    state: Mutex<NonceState>,
+    config: RunnerConfig,
+}
+
+impl<R: ContractRunner> NonceManagedRunner<R> {
+    /// Wrap a runner with local nonce management.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            state: Mutex::new(NonceState::default()),
+            config: RunnerConfig::default(),
+        }
+    }
+
+    /// Use the given fee/retry configuration.
+    pub fn with_config(mut self, config: RunnerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Override the minimum fee bump (in percent) applied to replacement transactions.
+    pub fn with_replacement_bump_percent(mut self, percent: u128) -> Self {
+        self.config.replacement_bump_percent = percent;
+        self
+    }
+
+    /// Active fee/retry configuration.
+    pub fn config(&self) -> &RunnerConfig {
+        &self.config
+    }
+
+    /// Borrow the wrapped runner.
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// Next nonce this wrapper will assign, if it has been seeded.
+    pub async fn next_nonce(&self) -> Option<u64> {
+        self.state.lock().await.next_nonce
+    }
+
+    /// Transactions broadcast by this wrapper that have not been pruned yet.
+    pub async fn pending(&self) -> Vec<PendingNonceTx> {
+        self.state.lock().await.pending.values().cloned().collect()
+    }
+
+    /// Drop tracked transactions with a nonce below `nonce` (e.g. once they are mined).
+    pub async fn prune_below(&self, nonce: u64) {
+        let mut state = self.state.lock().await;
+        state.pending = state.pending.split_off(&nonce);
+    }
+
+    /// Re-read the pending nonce from the backend and use it for the next submission.
+    pub async fn resync(&self) -> Result<u64, RunnerError> {
+        let mut state = self.state.lock().await;
+        let nonce = self.inner.pending_nonce().await?;
+        state.next_nonce = Some(nonce);
+        Ok(nonce)
+    }
+
+    /// Re-broadcast the pending transaction at `nonce` with bumped fees.
+    pub async fn speed_up(&self, nonce: u64) -> Result<SubmittedTx, RunnerError> {
+        let mut state = self.state.lock().await;
+        let tx = pending_entry(&state, nonce)?.tx.clone();
+        self.replace(&mut state, nonce, tx).await
+    }
+
+    /// Replace the pending transaction at `nonce` with an empty self-transfer.
+    pub async fn cancel(&self, nonce: u64) -> Result<SubmittedTx, RunnerError> {
+        let mut state = self.state.lock().await;
+        pending_entry(&state, nonce)?;
+        let tx = PreparedTransaction {
+            to: self.inner.sender_address(),
+            data: Bytes::new(),
+            value: None,
+        };
+        self.replace(&mut state, nonce, tx).await
+    }
+
+    /// Re-broadcast pending transactions that have been stuck longer than the
+    /// configured [`super::StuckTxRetry`] window, with bumped fees.
+    ///
+    /// Entries whose latest hash already has a receipt are pruned instead. Does
+    /// nothing when stuck retries are disabled.
+    pub async fn retry_stuck(&self) -> Result<Vec<SubmittedTx>, RunnerError> {
+        let Some(policy) = self.config.stuck_retry else {
+            return Ok(Vec::new());
+        };
+
+        let mut state = self.state.lock().await;
+        let stuck = state
+            .pending
+            .values()
+            .filter(|entry| {
+                entry.submitted_at.elapsed() >= policy.stuck_after
+                    && entry.replacements < policy.max_attempts
+            })
+            .map(|entry| (entry.nonce, entry.tx_hash.clone(), entry.tx.clone()))
+            .collect::<Vec<_>>();
+
+        let mut resubmitted = Vec::new();
+        for (nonce, tx_hash, tx) in stuck {
+            if let Ok(tx_hash) = B256::try_from(tx_hash.as_ref()) {
+                match self.inner.transaction_receipt(tx_hash).await {
+                    Ok(Some(_)) => {
+                        state.pending.remove(&nonce);
+                        continue;
+                    }
+                    Ok(None) | Err(RunnerError::Unsupported(_)) => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            resubmitted.push(self.replace(&mut state, nonce, tx).await?);
+        }
+        Ok(resubmitted)
+    }
+
+    async fn replace(
+        &self,
+        state: &mut NonceState,
+        nonce: u64,
+        tx: PreparedTransaction,
+    ) -> Result<SubmittedTx, RunnerError> {
+        let previous = pending_entry(state, nonce)?;
+        let replacements = previous.replacements + 1;
+        let fees = self
+            .config
+            .replacement_fees(&self.inner, previous.overrides)
+            .await?;
+        let overrides = TxOverrides {
+            nonce: Some(nonce),
+            ..fees
+        };
+
+        let submitted = self
+            .inner
+            .send_transaction_with_overrides(tx.clone(), overrides)
+            .await?;
+        state.pending.insert(
+            nonce,
+            PendingNonceTx {
+                nonce,
+                tx,
+                tx_hash: submitted.tx_hash.clone(),
+                overrides,
+                submitted_at: Instant::now(),
+                replacements,
+            },
+        );
+        Ok(submitted)
+    }
+
+    /// Seed the local nonce from the backend if it is not known yet. `Ok(false)` means
+    /// the wrapped runner does not expose nonces.
+    async fn seed(&self, state: &mut NonceState) -> Result<bool, RunnerError> {
+        if state.next_nonce.is_none() {
+            match self.inner.pending_nonce().await {
+                Ok(nonce) => state.next_nonce = Some(nonce),
+                Err(RunnerError::Unsupported(_)) => return Ok(false),
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(true)
+    }
+
+    async fn send_managed(
+        &self,
+        state: &mut NonceState,
+        txs: Vec<PreparedTransaction>,
+    ) -> Result<Vec<SubmittedTx>, RunnerError> {
+        let mut submitted = Vec::with_capacity(txs.len());
+        for (index, tx) in txs.into_iter().enumerate() {
+            let mut entry = self
+                .send_reserved(state, tx, TxOverrides::default())
+                .await?;
+            entry.index = Some(index);
+            submitted.push(entry);
+        }
+        Ok(submitted)
+    }
+
+    /// Send `tx` at the next reserved nonce and track it as pending.
+    ///
+    /// Fees set in `requested` are used as given; otherwise the config picks them. A nonce
+    /// set in `requested` is honoured, and the reservation moves past it.
+    async fn send_reserved(
+        &self,
+        state: &mut NonceState,
+        tx: PreparedTransaction,
+        requested: TxOverrides,
+    ) -> Result<SubmittedTx, RunnerError> {
+        let pinned = requested.nonce.is_some();
+        let nonce = match requested.nonce.or(state.next_nonce) {
+            Some(nonce) => nonce,
+            None => self.inner.pending_nonce().await?,
+        };
+        let fees = if has_fees(&requested) {
+            requested
+        } else {
+            match self.config.initial_fees(&self.inner).await {
+                Ok(fees) => fees,
+                Err(err) => {
+                    state.next_nonce = None;
+                    return Err(err);
+                }
+            }
+        };
+        let mut overrides = TxOverrides {
+            nonce: Some(nonce),
+            ..fees
+        };
+
+        let result = match self
+            .inner
+            .send_transaction_with_overrides(tx.clone(), overrides)
+            .await
+        {
+            Err(err) if !pinned && is_nonce_too_low(&err) => {
+                overrides.nonce = Some(self.inner.pending_nonce().await?);
+                self.inner
+                    .send_transaction_with_overrides(tx.clone(), overrides)
+                    .await
+            }
+            other => other,
+        };
+
+        let entry = match result {
+            Ok(entry) => entry,
+            Err(err) => {
+                // The backend's view is now authoritative; reseed on the next send.
+                state.next_nonce = None;
+                return Err(err);
+            }
+        };
+
+        let nonce = overrides.nonce.unwrap_or(nonce);
+        state.next_nonce = Some(state.next_nonce.unwrap_or(0).max(nonce + 1));
+        state.pending.insert(
+            nonce,
+            PendingNonceTx {
+                nonce,
+                tx,
+                tx_hash: entry.tx_hash.clone(),
+                overrides,
+                submitted_at: Instant::now(),
+                replacements: 0,
+            },
+        );
+        Ok(entry)
+    }
+}
+
+#[async_trait]
+impl<R: ContractRunner> ContractRunner for NonceManagedRunner<R> {
+    fn sender_address(&self) -> Address {
+        self.inner.sender_address()
+    }
+
+    async fn estimate_gas(&self, tx: PreparedTransaction) -> Result<u64, RunnerError> {
+        self.inner.estimate_gas(tx).await
+    }
+
+    async fn call(&self, tx: PreparedTransaction) -> Result<Bytes, RunnerError> {
+        self.inner.call(tx).await
+    }
+
+    async fn resolve_name(&self, name: &str) -> Result<Option<Address>, RunnerError> {
+        self.inner.resolve_name(name).await
+    }
+
+    async fn pending_nonce(&self) -> Result<u64, RunnerError> {
+        let state = self.state.lock().await;
+        match state.next_nonce {
+            Some(nonce) => Ok(nonce),
+            None => self.inner.pending_nonce().await,
+        }
+    }
+
+    async fn gas_price(&self) -> Result<u128, RunnerError> {
+        self.inner.gas_price().await
+    }
+
+    async fn estimate_fees(&self) -> Result<FeeEstimate, RunnerError> {
+        self.inner.estimate_fees().await
+    }
+
+    async fn block_number(&self) -> Result<u64, RunnerError> {
+        self.inner.block_number().await
+    }
+
+    async fn chain_id(&self) -> Result<u64, RunnerError> {
+        self.inner.chain_id().await
+    }
+
+    async fn transaction_receipt(
+        &self,
+        tx_hash: B256,
+    ) -> Result<Option<TxReceiptInfo>, RunnerError> {
+        self.inner.transaction_receipt(tx_hash).await
+    }
+
+    async fn send_transaction_with_overrides(
+        &self,
+        tx: PreparedTransaction,
+        overrides: TxOverrides,
+    ) -> Result<SubmittedTx, RunnerError> {
+        let mut state = self.state.lock().await;
+        if !self.seed(&mut state).await? {
+            return self
+                .inner
+                .send_transaction_with_overrides(tx, overrides)
+                .await;
+        }
+        self.send_reserved(&mut state, tx, overrides).await
+    }
+
+    async fn send_transactions(
+        &self,
+        txs: Vec<PreparedTransaction>,
+    ) -> Result<Vec<SubmittedTx>, RunnerError> {
+        if txs.is_empty() {
+            return Err(RunnerError::Rejected(
+                "no transactions provided".to_string(),
+            ));
+        }
+
+        let mut state = self.state.lock().await;
+        if !self.seed(&mut state).await? {
+            return self.inner.send_transactions(txs).await;
+        }
+        self.send_managed(&mut state, txs).await
+    }
+}
+
+fn pending_entry(state: &NonceState, nonce: u64) -> Result<&PendingNonceTx, RunnerError> {
+    state.pending.get(&nonce).ok_or_else(|| {
+        RunnerError::Rejected(format!("no pending transaction tracked for nonce {nonce}"))
+    })
+}
+
+fn has_fees(overrides: &TxOverrides) -> bool {
+    overrides.gas_price.is_some()
+        || overrides.max_fee_per_gas.is_some()
+        || overrides.max_priority_fee_per_gas.is_some()
+}
+
+fn is_nonce_too_low(err: &RunnerError) -> bool {
+    let message = err.to_string().to_ascii_lowercase();
+    message.contains("nonce too low") || message.contains("nonce is too low")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{FeeStrategy, StuckTxRetry};
+    use alloy_primitives::U256;
+    use std::sync::Mutex as StdMutex;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    struct NonceRunner {
+        chain_nonce: StdMutex<u64>,
+        sent: StdMutex<Vec<(PreparedTransaction, TxOverrides)>>,
+        reject_next_as_stale: AtomicBool,
+    }
+
+    impl NonceRunner {
+        fn at(nonce: u64) -> Self {
+            Self {
+                chain_nonce: StdMutex::new(nonce),
+                sent: StdMutex::new(Vec::new()),
+                reject_next_as_stale: AtomicBool::new(false),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ContractRunner for NonceRunner {
+        fn sender_address(&self) -> Address {
+            Address::repeat_byte(0x55)
+        }
+
+        async fn pending_nonce(&self) -> Result<u64, RunnerError> {
+            Ok(*self.chain_nonce.lock().unwrap())
+        }
+
+        async fn estimate_fees(&self) -> Result<FeeEstimate, RunnerError> {
+            Ok(FeeEstimate {
+                max_fee_per_gas: 1_000,
+                max_priority_fee_per_gas: 100,
+            })
+        }
+
+        async fn send_transaction_with_overrides(
+            &self,
+            tx: PreparedTransaction,
+            overrides: TxOverrides,
+        ) -> Result<SubmittedTx, RunnerError> {
+            if self.reject_next_as_stale.swap(false, Ordering::SeqCst) {
+                return Err(RunnerError::Transport(
+                    "server returned an error response: nonce too low".to_string(),
+                ));
+            }
+            let mut sent = self.sent.lock().unwrap();
+            sent.push((tx, overrides));
+            Ok(SubmittedTx {
+                tx_hash: Bytes::copy_from_slice(&[sent.len() as u8; 32]),
+                success: true,
+                index: None,
+            })
+        }
+
+        async fn send_transactions(
+            &self,
+            _txs: Vec<PreparedTransaction>,
+        ) -> Result<Vec<SubmittedTx>, RunnerError> {
+            unreachable!("managed sends go through send_transaction_with_overrides")
+        }
+    }
+
+    struct PlainRunner {
+        batches: StdMutex<usize>,
+    }
+
+    #[async_trait]
+    impl ContractRunner for PlainRunner {
+        fn sender_address(&self) -> Address {
+            Address::repeat_byte(0x66)
+        }
+
+        async fn send_transactions(
+            &self,
+            txs: Vec<PreparedTransaction>,
+        ) -> Result<Vec<SubmittedTx>, RunnerError> {
+            *self.batches.lock().unwrap() += 1;
+            Ok(txs
+                .iter()
+                .map(|_| SubmittedTx {
+                    tx_hash: Bytes::new(),
+                    success: true,
+                    index: None,
+                })
+                .collect())
+        }
+    }
+
+    fn tx(byte: u8) -> PreparedTransaction {
+        PreparedTransaction {
+            to: Address::repeat_byte(byte),
+            data: Bytes::from(vec![byte]),
+            value: Some(U256::from(byte)),
+        }
+    }
+
+    #[tokio::test]
+    async fn assigns_sequential_nonces_across_batches() {
+        let runner = NonceManagedRunner::new(NonceRunner::at(5));
+
+        let first = runner
+            .send_transactions(vec![tx(0x11), tx(0x22)])
+            .await
+            .expect("first batch");
+        runner
+            .send_transactions(vec![tx(0x33)])
+            .await
+            .expect("second batch");
+
+        let sent = runner.inner().sent.lock().unwrap().clone();
+        let nonces = sent.iter().map(|(_, o)| o.nonce).collect::<Vec<_>>();
+        assert_eq!(nonces, vec![Some(5), Some(6), Some(7)]);
+        assert_eq!(first[1].index, Some(1));
+        assert_eq!(runner.next_nonce().await, Some(8));
+        assert_eq!(runner.pending().await.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn recovers_from_nonce_too_low() {
+        let runner = NonceManagedRunner::new(NonceRunner::at(2));
+        runner
+            .send_transactions(vec![tx(0x11)])
+            .await
+            .expect("seed");
+
+        // Another client consumed nonces 3..=9 behind our back.
+        *runner.inner().chain_nonce.lock().unwrap() = 10;
+        runner
+            .inner()
+            .reject_next_as_stale
+            .store(true, Ordering::SeqCst);
+        runner
+            .send_transactions(vec![tx(0x22)])
+            .await
+            .expect("resynced send");
+
+        let sent = runner.inner().sent.lock().unwrap().clone();
+        assert_eq!(sent[1].1.nonce, Some(10));
+        assert_eq!(runner.next_nonce().await, Some(11));
+    }
+
+    #[tokio::test]
+    async fn speed_up_and_cancel_reuse_nonce_with_bumped_fees() {
+        let runner = NonceManagedRunner::new(NonceRunner::at(0));
+        runner
+            .send_transactions(vec![tx(0x11)])
+            .await
+            .expect("send");
+
+        runner.speed_up(0).await.expect("speed up");
+        runner.cancel(0).await.expect("cancel");
+
+        let sent = runner.inner().sent.lock().unwrap().clone();
+        let (sped_up, first_bump) = &sent[1];
+        assert_eq!(sped_up, &tx(0x11));
+        assert_eq!(first_bump.nonce, Some(0));
+        assert_eq!(first_bump.max_fee_per_gas, Some(1_100));
+        assert_eq!(first_bump.max_priority_fee_per_gas, Some(110));
+
+        let (cancel, second_bump) = &sent[2];
+        assert_eq!(cancel.to, Address::repeat_byte(0x55));
+        assert!(cancel.data.is_empty());
+        assert_eq!(second_bump.nonce, Some(0));
+        assert_eq!(second_bump.max_fee_per_gas, Some(1_210));
+
+        let pending = runner.pending().await;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].tx, *cancel);
+    }
+
+    #[tokio::test]
+    async fn retry_stuck_replaces_until_attempts_are_exhausted() {
+        let runner = NonceManagedRunner::new(NonceRunner::at(4)).with_config(
+            RunnerConfig::default().with_stuck_retry(StuckTxRetry {
+                stuck_after: Duration::ZERO,
+                max_attempts: 1,
+            }),
+        );
+        runner
+            .send_transactions(vec![tx(0x11)])
+            .await
+            .expect("send");
+
+        let first = runner.retry_stuck().await.expect("first retry");
+        let second = runner.retry_stuck().await.expect("second retry");
+
+        assert_eq!(first.len(), 1);
+        assert!(second.is_empty());
+        let pending = runner.pending().await;
+        assert_eq!(pending[0].replacements, 1);
+        assert_eq!(pending[0].overrides.max_fee_per_gas, Some(1_100));
+    }
+
+    #[tokio::test]
+    async fn fee_strategy_applies_to_fresh_submissions() {
+        let runner = NonceManagedRunner::new(NonceRunner::at(0)).with_config(
+            RunnerConfig::default().with_fee_strategy(FeeStrategy::Fixed {
+                max_fee_per_gas: 3_000,
+                max_priority_fee_per_gas: 30,
+            }),
+        );
+        runner
+            .send_transactions(vec![tx(0x11)])
+            .await
+            .expect("send");
+
+        let sent = runner.inner().sent.lock().unwrap().clone();
+        assert_eq!(sent[0].1.nonce, Some(0));
+        assert_eq!(sent[0].1.max_fee_per_gas, Some(3_000));
+        assert_eq!(sent[0].1.max_priority_fee_per_gas, Some(30));
+    }
+
+    #[tokio::test]
+    async fn single_sends_with_overrides_reserve_nonces_too() {
+        let runner = NonceManagedRunner::new(NonceRunner::at(3));
+        runner
+            .send_transactions(vec![tx(0x11)])
+            .await
+            .expect("batch");
+        let fees = TxOverrides {
+            max_fee_per_gas: Some(2_000),
+            max_priority_fee_per_gas: Some(200),
+            ..TxOverrides::default()
+        };
+        runner
+            .send_transaction_with_overrides(tx(0x22), fees)
+            .await
+            .expect("single send");
+        runner
+            .send_transactions(vec![tx(0x33)])
+            .await
+            .expect("next batch");
+
+        let sent = runner.inner().sent.lock().unwrap().clone();
+        let nonces = sent.iter().map(|(_, o)| o.nonce).collect::<Vec<_>>();
+        assert_eq!(nonces, vec![Some(3), Some(4), Some(5)]);
+        assert_eq!(sent[1].1.max_fee_per_gas, Some(2_000));
+        assert_eq!(sent[1].1.max_priority_fee_per_gas, Some(200));
+        assert_eq!(runner.pending().await.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn replacing_unknown_nonce_is_rejected() {
+        let runner = NonceManagedRunner::new(NonceRunner::at(0));
+
+        assert!(matches!(
+            runner.speed_up(3).await,
+            Err(RunnerError::Rejected(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn prune_below_drops_mined_entries() {
+        let runner = NonceManagedRunner::new(NonceRunner::at(0));
+        runner
+            .send_transactions(vec![tx(0x11), tx(0x22), tx(0x33)])
+            .await
+            .expect("send");
+
+        runner.prune_below(2).await;
+
+        let pending = runner.pending().await;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].nonce, 2);
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_batch_send_without_nonce_support() {
+        let runner = NonceManagedRunner::new(PlainRunner {
+            batches: StdMutex::new(0),
+        });
+
+        let submitted = runner
+            .send_transactions(vec![tx(0x11), tx(0x22)])
+            .await
+            .expect("fallback send");
+
+        assert_eq!(submitted.len(), 2);
+        assert_eq!(*runner.inner().batches.lock().unwrap(), 1);
+        assert_eq!(runner.next_nonce().await, None);
+    }
+}
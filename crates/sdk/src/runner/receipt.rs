@@ -0,0 +1,600 @@
+//! Receipt waiting and confirmation tracking for submitted transactions.
+//!
+//! Everything here goes through the [`ContractRunner`] receipt/block hooks, so the
+//! same helpers work for EOA, Safe, and custom runners. [`TxMonitor::poll`] is a
+//! single tick: drive it from a timer ([`TxMonitor::spawn`]) or, with the `ws` feature,
+//! from a chain `newHeads` subscription (`TxMonitor::spawn_ws`).
+
+use super::{ContractRunner, RunnerError, SigningProvider, SubmittedTx};
+use alloy_network::ReceiptResponse;
+use alloy_primitives::{B256, Bytes};
+use alloy_provider::Provider;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Interval used by [`SubmittedTx::wait_for_receipt`] between receipt polls.
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Mined receipt summary for a submitted transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxReceiptInfo {
+    /// Transaction hash.
+    pub tx_hash: B256,
+    /// Block the transaction was included in.
+    pub block_number: u64,
+    /// Receipt status (`false` when the transaction reverted).
+    pub success: bool,
+    /// Gas consumed by the transaction.
+    pub gas_used: u64,
+    /// Revert data recovered by replaying a failed transaction, when available.
+    pub revert_data: Option<Bytes>,
+}
+
+impl TxReceiptInfo {
+    /// Number of confirmations at the given chain head (the inclusion block counts as one).
+    pub fn confirmations_at(&self, head: u64) -> u64 {
+        head.saturating_add(1).saturating_sub(self.block_number)
+    }
+}
+
+impl SubmittedTx {
+    /// Transaction hash as a fixed 32-byte value, if the runner reported a full hash.
+    pub fn hash(&self) -> Option<B256> {
+        B256::try_from(self.tx_hash.as_ref()).ok()
+    }
+
+    /// Poll the runner until this transaction has `confirmations` confirmations.
+    ///
+    /// Reverted transactions resolve like successful ones; inspect
+    /// [`TxReceiptInfo::success`] and [`TxReceiptInfo::revert_data`].
+    pub async fn wait_for_receipt<R>(
+        &self,
+        runner: &R,
+        confirmations: u64,
+        timeout: Duration,
+    ) -> Result<TxReceiptInfo, RunnerError>
+    where
+        R: ContractRunner + ?Sized,
+    {
+        let tx_hash = self.hash().ok_or_else(|| {
+            RunnerError::Rejected(format!("invalid transaction hash {}", self.tx_hash))
+        })?;
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if let Some(receipt) = runner.transaction_receipt(tx_hash).await? {
+                let head = runner.block_number().await?;
+                if receipt.confirmations_at(head) >= confirmations.max(1) {
+                    return Ok(receipt);
+                }
+            }
+            if Instant::now() >= deadline {
+                return Err(RunnerError::Timeout(format!(
+                    "transaction {tx_hash} not confirmed within {timeout:?}"
+                )));
+            }
+            tokio::time::sleep(RECEIPT_POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// Event emitted by a [`TxMonitor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxMonitorEvent {
+    /// The transaction was first seen in a block.
+    Included {
+        tx_hash: B256,
+        receipt: TxReceiptInfo,
+    },
+    /// The transaction reached the monitor's confirmation target.
+    Confirmed {
+        tx_hash: B256,
+        receipt: TxReceiptInfo,
+        confirmations: u64,
+    },
+    /// The transaction was not confirmed before the monitor timeout.
+    TimedOut { tx_hash: B256 },
+}
+
+struct Watched {
+    tx_hash: B256,
+    since: Instant,
+    included: Option<TxReceiptInfo>,
+}
+
+/// Watches submitted transaction hashes and reports inclusion/confirmation events.
+pub struct TxMonitor {
+    runner: Arc<dyn ContractRunner>,
+    confirmations: u64,
+    poll_interval: Duration,
+    timeout: Option<Duration>,
+    watched: Vec<Watched>,
+}
+
+impl TxMonitor {
+    /// Create a monitor that reports transactions once they have `confirmations` confirmations.
+    pub fn new(runner: Arc<dyn ContractRunner>, confirmations: u64) -> Self {
+        Self {
+            runner,
+            confirmations: confirmations.max(1),
+            poll_interval: Duration::from_secs(2),
+            timeout: None,
+            watched: Vec::new(),
+        }
+    }
+
+    /// Set the interval used by [`TxMonitor::spawn`] between polls.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Give up on transactions that are not confirmed within `timeout` of being watched.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Start watching a submitted transaction. Entries without a 32-byte hash are ignored.
+    pub fn watch(&mut self, tx: &SubmittedTx) {
+        if let Some(tx_hash) = tx.hash() {
+            self.watch_hash(tx_hash);
+        }
+    }
+
+    /// Start watching a transaction hash.
+    pub fn watch_hash(&mut self, tx_hash: B256) {
+        if self.watched.iter().any(|w| w.tx_hash == tx_hash) {
+            return;
+        }
+        self.watched.push(Watched {
+            tx_hash,
+            since: Instant::now(),
+            included: None,
+        });
+    }
+
+    /// Number of transactions still being watched.
+    pub fn pending(&self) -> usize {
+        self.watched.len()
+    }
+
+    /// Check every watched transaction once and return the resulting events.
+    ///
+    /// Confirmed and timed-out transactions stop being watched.
+    pub async fn poll(&mut self) -> Result<Vec<TxMonitorEvent>, RunnerError> {
+        if self.watched.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let head = self.runner.block_number().await?;
+        let mut receipts = Vec::with_capacity(self.watched.len());
+        for watched in &self.watched {
+            receipts.push(self.runner.transaction_receipt(watched.tx_hash).await?);
+        }
+
+        let mut events = Vec::new();
+        let mut remaining = Vec::with_capacity(self.watched.len());
+        for (mut watched, receipt) in std::mem::take(&mut self.watched).into_iter().zip(receipts) {
+            if let Some(receipt) = receipt {
+                if watched.included.as_ref() != Some(&receipt) {
+                    events.push(TxMonitorEvent::Included {
+                        tx_hash: watched.tx_hash,
+                        receipt: receipt.clone(),
+                    });
+                    watched.included = Some(receipt.clone());
+                }
+                let confirmations = receipt.confirmations_at(head);
+                if confirmations >= self.confirmations {
+                    events.push(TxMonitorEvent::Confirmed {
+                        tx_hash: watched.tx_hash,
+                        receipt,
+                        confirmations,
+                    });
+                    continue;
+                }
+            }
+
+            if self
+                .timeout
+                .is_some_and(|timeout| watched.since.elapsed() >= timeout)
+            {
+                events.push(TxMonitorEvent::TimedOut {
+                    tx_hash: watched.tx_hash,
+                });
+                continue;
+            }
+            remaining.push(watched);
+        }
+
+        self.watched = remaining;
+        Ok(events)
+    }
+
+    /// Poll in a background task until every watched transaction resolves.
+    ///
+    /// Poll errors are retried on the next tick. The channel closes once nothing
+    /// is left to watch.
+    pub fn spawn(mut self) -> mpsc::UnboundedReceiver<TxMonitorEvent> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while self.pending() > 0 {
+                if let Ok(events) = self.poll().await {
+                    for event in events {
+                        if tx.send(event).is_err() {
+                            return;
+                        }
+                    }
+                }
+                if self.pending() > 0 {
+                    tokio::time::sleep(self.poll_interval).await;
+                }
+            }
+        });
+        rx
+    }
+}
+
+#[cfg(feature = "ws")]
+impl TxMonitor {
+    /// Poll on every `newHeads` notification from the chain WebSocket at `ws_url` until
+    /// every watched transaction resolves.
+    ///
+    /// See [`TxMonitor::spawn_with_heads`] for how the heads drive polling.
+    pub async fn spawn_ws(
+        self,
+        ws_url: &str,
+    ) -> Result<mpsc::UnboundedReceiver<TxMonitorEvent>, RunnerError> {
+        let url = ws_url
+            .parse()
+            .map_err(|err| RunnerError::Transport(format!("invalid ws url {ws_url}: {err}")))?;
+        let client = circles_rpc::RpcClient::ws(url)
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))?;
+        let heads = client
+            .subscribe::<_, Value>(("newHeads",))
+            .map_err(|err| RunnerError::Transport(err.to_string()))?
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))?
+            .into_stream();
+        // The stream only lives as long as the connection, so the task keeps the client.
+        let heads = futures::StreamExt::map(heads, move |head| {
+            let _connection = &client;
+            head
+        });
+        Ok(self.spawn_with_heads(heads))
+    }
+
+    /// Poll once per item of `heads` in a background task until every watched transaction
+    /// resolves.
+    ///
+    /// When no head arrives within the poll interval the monitor polls anyway, and once
+    /// `heads` ends it keeps polling on the interval, so timeouts still fire on a stalled
+    /// subscription. Poll errors are retried on the next head or tick.
+    pub fn spawn_with_heads<S>(mut self, heads: S) -> mpsc::UnboundedReceiver<TxMonitorEvent>
+    where
+        S: futures::Stream + Send + Unpin + 'static,
+    {
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut heads = Some(heads);
+            while self.pending() > 0 {
+                if let Ok(events) = self.poll().await {
+                    for event in events {
+                        if tx.send(event).is_err() {
+                            return;
+                        }
+                    }
+                }
+                if self.pending() == 0 {
+                    break;
+                }
+                match heads.as_mut() {
+                    Some(stream) => {
+                        let next = futures::StreamExt::next(stream);
+                        if let Ok(None) = tokio::time::timeout(self.poll_interval, next).await {
+                            heads = None;
+                        }
+                    }
+                    None => tokio::time::sleep(self.poll_interval).await,
+                }
+            }
+        });
+        rx
+    }
+}
+
+pub(super) async fn fetch_block_number(provider: &SigningProvider) -> Result<u64, RunnerError> {
+    provider
+        .get_block_number()
+        .await
+        .map_err(|err| RunnerError::Transport(err.to_string()))
+}
+
+pub(super) async fn fetch_receipt(
+    provider: &SigningProvider,
+    tx_hash: B256,
+) -> Result<Option<TxReceiptInfo>, RunnerError> {
+    let Some(receipt) = provider
+        .get_transaction_receipt(tx_hash)
+        .await
+        .map_err(|err| RunnerError::Transport(err.to_string()))?
+    else {
+        return Ok(None);
+    };
+    let Some(block_number) = receipt.block_number() else {
+        return Ok(None);
+    };
+
+    let success = receipt.status();
+    let revert_data = if success {
+        None
+    } else {
+        replay_revert_data(provider, tx_hash, block_number).await
+    };
+
+    Ok(Some(TxReceiptInfo {
+        tx_hash,
+        block_number,
+        success,
+        gas_used: receipt.gas_used(),
+        revert_data,
+    }))
+}
+
+// Receipts carry no revert reason, so re-run the call against the parent block
+// and pull the data out of the error response. Best effort only.
+async fn replay_revert_data(
+    provider: &SigningProvider,
+    tx_hash: B256,
+    block_number: u64,
+) -> Option<Bytes> {
+    let tx: Value = provider
+        .raw_request("eth_getTransactionByHash".into(), (tx_hash,))
+        .await
+        .ok()?;
+    let call = json!({
+        "from": tx.get("from")?,
+        "to": tx.get("to")?,
+        "data": tx.get("input")?,
+        "value": tx.get("value").cloned().unwrap_or(Value::Null),
+    });
+    let block = format!("{:#x}", block_number.saturating_sub(1));
+
+    match provider
+        .raw_request::<_, Bytes>("eth_call".into(), (call, block))
+        .await
+    {
+        Ok(_) => None,
+        Err(err) => err
+            .as_error_resp()
+            .and_then(|payload| payload.as_revert_data()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PreparedTransaction;
+    use alloy_primitives::Address;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    struct ChainRunner {
+        head: Mutex<u64>,
+        receipts: Mutex<Vec<TxReceiptInfo>>,
+    }
+
+    impl ChainRunner {
+        fn new(head: u64) -> Self {
+            Self {
+                head: Mutex::new(head),
+                receipts: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn mine(&self, tx_hash: B256, success: bool) {
+            let head = *self.head.lock().unwrap();
+            self.receipts.lock().unwrap().push(TxReceiptInfo {
+                tx_hash,
+                block_number: head,
+                success,
+                gas_used: 21_000,
+                revert_data: (!success).then(|| Bytes::from_static(&[0x08, 0xc3, 0x79, 0xa0])),
+            });
+        }
+
+        fn advance(&self, blocks: u64) {
+            *self.head.lock().unwrap() += blocks;
+        }
+    }
+
+    #[async_trait]
+    impl ContractRunner for ChainRunner {
+        fn sender_address(&self) -> Address {
+            Address::repeat_byte(0x55)
+        }
+
+        async fn block_number(&self) -> Result<u64, RunnerError> {
+            Ok(*self.head.lock().unwrap())
+        }
+
+        async fn transaction_receipt(
+            &self,
+            tx_hash: B256,
+        ) -> Result<Option<TxReceiptInfo>, RunnerError> {
+            Ok(self
+                .receipts
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|receipt| receipt.tx_hash == tx_hash)
+                .cloned())
+        }
+
+        async fn send_transactions(
+            &self,
+            _txs: Vec<PreparedTransaction>,
+        ) -> Result<Vec<SubmittedTx>, RunnerError> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn submitted(byte: u8) -> SubmittedTx {
+        SubmittedTx {
+            tx_hash: Bytes::copy_from_slice(&[byte; 32]),
+            success: true,
+            index: None,
+        }
+    }
+
+    #[test]
+    fn confirmations_count_inclusion_block() {
+        let receipt = TxReceiptInfo {
+            tx_hash: B256::ZERO,
+            block_number: 10,
+            success: true,
+            gas_used: 0,
+            revert_data: None,
+        };
+
+        assert_eq!(receipt.confirmations_at(9), 0);
+        assert_eq!(receipt.confirmations_at(10), 1);
+        assert_eq!(receipt.confirmations_at(12), 3);
+    }
+
+    #[test]
+    fn hash_requires_full_32_bytes() {
+        assert_eq!(submitted(0x11).hash(), Some(B256::repeat_byte(0x11)));
+        assert_eq!(
+            SubmittedTx {
+                tx_hash: Bytes::from_static(&[0x11]),
+                success: true,
+                index: None,
+            }
+            .hash(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn wait_for_receipt_returns_reverted_receipt_with_data() {
+        let runner = ChainRunner::new(100);
+        runner.mine(B256::repeat_byte(0x11), false);
+
+        let receipt = submitted(0x11)
+            .wait_for_receipt(&runner, 1, Duration::from_secs(1))
+            .await
+            .expect("receipt");
+
+        assert!(!receipt.success);
+        assert_eq!(receipt.block_number, 100);
+        assert_eq!(
+            receipt.revert_data,
+            Some(Bytes::from_static(&[0x08, 0xc3, 0x79, 0xa0]))
+        );
+    }
+
+    #[tokio::test]
+    async fn wait_for_receipt_times_out_when_not_mined() {
+        let runner = ChainRunner::new(100);
+
+        let result = submitted(0x22)
+            .wait_for_receipt(&runner, 1, Duration::ZERO)
+            .await;
+
+        assert!(matches!(result, Err(RunnerError::Timeout(_))));
+    }
+
+    #[tokio::test]
+    async fn monitor_emits_inclusion_then_confirmation() {
+        let runner = Arc::new(ChainRunner::new(50));
+        let mut monitor = TxMonitor::new(runner.clone(), 3);
+        monitor.watch(&submitted(0x11));
+
+        assert!(monitor.poll().await.expect("first poll").is_empty());
+
+        runner.mine(B256::repeat_byte(0x11), true);
+        let events = monitor.poll().await.expect("included");
+        assert!(matches!(
+            events.as_slice(),
+            [TxMonitorEvent::Included { .. }]
+        ));
+
+        runner.advance(2);
+        let events = monitor.poll().await.expect("confirmed");
+        assert!(matches!(
+            events.as_slice(),
+            [TxMonitorEvent::Confirmed {
+                confirmations: 3,
+                ..
+            }]
+        ));
+        assert_eq!(monitor.pending(), 0);
+    }
+
+    #[tokio::test]
+    async fn monitor_reports_timeouts() {
+        let runner = Arc::new(ChainRunner::new(1));
+        let mut monitor = TxMonitor::new(runner, 1).with_timeout(Duration::ZERO);
+        monitor.watch(&submitted(0x33));
+
+        let events = monitor.poll().await.expect("poll");
+
+        assert_eq!(
+            events,
+            vec![TxMonitorEvent::TimedOut {
+                tx_hash: B256::repeat_byte(0x33)
+            }]
+        );
+        assert_eq!(monitor.pending(), 0);
+    }
+
+    #[tokio::test]
+    async fn spawned_monitor_closes_after_confirmation() {
+        let runner = Arc::new(ChainRunner::new(7));
+        runner.mine(B256::repeat_byte(0x44), true);
+        let mut monitor = TxMonitor::new(runner, 1).with_poll_interval(Duration::from_millis(10));
+        monitor.watch(&submitted(0x44));
+
+        let mut events = monitor.spawn();
+        let mut seen = Vec::new();
+        while let Some(event) = events.recv().await {
+            seen.push(event);
+        }
+
+        assert_eq!(seen.len(), 2);
+        assert!(matches!(seen[1], TxMonitorEvent::Confirmed { .. }));
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn head_driven_monitor_polls_on_each_new_head() {
+        let runner = Arc::new(ChainRunner::new(7));
+        runner.mine(B256::repeat_byte(0x55), true);
+        // A long interval, so only heads can advance the monitor within the test.
+        let mut monitor =
+            TxMonitor::new(runner.clone(), 2).with_poll_interval(Duration::from_secs(60));
+        monitor.watch(&submitted(0x55));
+
+        let (heads, head_rx) = futures::channel::mpsc::unbounded::<()>();
+        let mut events = monitor.spawn_with_heads(head_rx);
+        assert!(matches!(
+            events.recv().await,
+            Some(TxMonitorEvent::Included { .. })
+        ));
+
+        runner.advance(1);
+        heads.unbounded_send(()).unwrap();
+        assert!(matches!(
+            events.recv().await,
+            Some(TxMonitorEvent::Confirmed {
+                confirmations: 2,
+                ..
+            })
+        ));
+        assert!(events.recv().await.is_none());
+    }
+}
@@ -0,0 +1,538 @@
+//! Gasless submission through a relayer service (Gelato relay API).
+//!
+//! The runner signs a typed payload locally and hands it to the relayer, which pays for
+//! gas and answers with a task id. Task ids are polled until the relayer reports the
+//! on-chain transaction, so sponsored invitation and registration flows still end with
+//! a regular [`SubmittedTx`].
+
+use super::{
+    ContractRunner, PreparedTransaction, RelayConfig, RelayMode, RunnerConfig, RunnerError,
+    SigningProvider, SubmittedTx, TxReceiptInfo, build_read_provider, call_error, http_client,
+    parse_private_key, parse_rpc_url, prepare_safe_execution, prepared_to_request, receipt,
+    sign_prehash, tx_hash_to_bytes,
+};
+use alloy_primitives::{Address, B256, Bytes, U256};
+use alloy_provider::Provider;
+use alloy_signer_local::PrivateKeySigner;
+use alloy_sol_types::{SolStruct, eip712_domain, sol};
+use async_trait::async_trait;
+use circles_utils::time::unix_now;
+use reqwest::{Client, Url};
+use safe_rs::ISafe;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::time::Instant;
+
+/// EIP-712 domain name of Gelato's ERC-2771 sponsored-call relay contract.
+const ERC2771_DOMAIN_NAME: &str = "GelatoRelay1BalanceERC2771";
+
+sol! {
+    struct SponsoredCallERC2771 {
+        uint256 chainId;
+        address target;
+        bytes data;
+        address user;
+        uint256 userNonce;
+        uint256 userDeadline;
+    }
+
+    #[sol(rpc)]
+    interface IRelayNonces {
+        function userNonce(address account) external view returns (uint256);
+    }
+}
+
+/// Lifecycle state of a relay task, as reported by `tasks/status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum RelayTaskState {
+    CheckPending,
+    ExecPending,
+    WaitingForConfirmation,
+    ExecSuccess,
+    ExecReverted,
+    Cancelled,
+    #[serde(other)]
+    Unknown,
+}
+
+impl RelayTaskState {
+    /// Whether the relayer will not change this task any more.
+    pub fn is_final(self) -> bool {
+        matches!(
+            self,
+            Self::ExecSuccess | Self::ExecReverted | Self::Cancelled
+        )
+    }
+}
+
+/// Status of a relay task.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayTaskStatus {
+    pub task_id: String,
+    pub task_state: RelayTaskState,
+    /// Hash of the relayer's transaction, once it has sent one.
+    #[serde(default)]
+    pub transaction_hash: Option<B256>,
+    /// Relayer's explanation for cancellations and reverts.
+    #[serde(default)]
+    pub last_check_message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TaskStatusResponse {
+    task: RelayTaskStatus,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TaskCreated {
+    task_id: String,
+}
+
+/// Runner whose transactions are paid for by a relayer.
+///
+/// In [`RelayMode::Erc2771`] each prepared transaction is signed and relayed on its own
+/// (sequential, not atomic); in [`RelayMode::Safe`] a batch becomes one Safe
+/// `execTransaction`. Native value cannot be relayed in ERC-2771 mode.
+pub struct RelayRunner {
+    signer: PrivateKeySigner,
+    provider: SigningProvider,
+    client: Client,
+    base_url: Url,
+    relay: RelayConfig,
+    chain_id: u64,
+}
+
+impl RelayRunner {
+    /// Connect a signer to the node at `rpc_url` and the relayer in `config.relay`.
+    pub async fn connect(
+        rpc_url: &str,
+        private_key: &str,
+        config: RunnerConfig,
+    ) -> Result<Self, RunnerError> {
+        let relay = config.relay.ok_or_else(|| {
+            RunnerError::Rejected("RunnerConfig has no relay configured".to_string())
+        })?;
+        let base_url = relay_base_url(&relay.relay_url)?;
+        let client =
+            http_client(&config.timeouts).map_err(|err| RunnerError::Transport(err.to_string()))?;
+        let provider = build_read_provider(parse_rpc_url(rpc_url)?).erased();
+        let chain_id = provider
+            .get_chain_id()
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))?;
+        Ok(Self {
+            signer: parse_private_key(private_key)?,
+            provider,
+            client,
+            base_url,
+            relay,
+            chain_id,
+        })
+    }
+
+    /// Address of the signing key.
+    pub fn signer_address(&self) -> Address {
+        self.signer.address()
+    }
+
+    /// Sign `txs` and hand them to the relayer, returning one task id per relay call.
+    pub async fn submit(&self, txs: Vec<PreparedTransaction>) -> Result<Vec<String>, RunnerError> {
+        if txs.is_empty() {
+            return Err(RunnerError::Rejected(
+                "no transactions provided".to_string(),
+            ));
+        }
+        match self.relay.mode {
+            RelayMode::Erc2771 { forwarder } => {
+                // The forwarder bumps `userNonce` per executed call, so a batch signs
+                // consecutive nonces from the current one instead of re-reading it.
+                let user_nonce = IRelayNonces::new(forwarder, &self.provider)
+                    .userNonce(self.signer.address())
+                    .call()
+                    .await
+                    .map_err(|err| RunnerError::Transport(err.to_string()))?;
+                let deadline = unix_now().saturating_add(self.relay.signature_validity.as_secs());
+                let calls = erc2771_calls(
+                    self.chain_id,
+                    self.signer.address(),
+                    user_nonce,
+                    deadline,
+                    txs,
+                )?;
+                let mut tasks = Vec::with_capacity(calls.len());
+                for call in calls {
+                    let signature = sign_erc2771(&self.signer, &call, forwarder)?;
+                    tasks.push(
+                        self.create_task(
+                            "relays/v2/sponsored-call-erc2771",
+                            erc2771_body(&call, &signature, &self.relay.api_key),
+                        )
+                        .await?,
+                    );
+                }
+                Ok(tasks)
+            }
+            RelayMode::Safe { safe } => Ok(vec![self.submit_safe(safe, txs).await?]),
+        }
+    }
+
+    /// Current status of a relay task.
+    pub async fn task_status(&self, task_id: &str) -> Result<RelayTaskStatus, RunnerError> {
+        let url = endpoint(&self.base_url, &format!("tasks/status/{task_id}"))?;
+        let response: TaskStatusResponse = self.request(self.client.get(url)).await?;
+        Ok(response.task)
+    }
+
+    /// Poll `task_id` until it is final; returns the transaction hash and whether it succeeded.
+    ///
+    /// Cancelled tasks (and reverts without a transaction) fail with
+    /// [`RunnerError::Rejected`] carrying the relayer's message.
+    pub async fn wait_for_task(&self, task_id: &str) -> Result<(B256, bool), RunnerError> {
+        let timeout = self.relay.task_timeout;
+        let deadline = Instant::now() + timeout;
+        loop {
+            let status = self.task_status(task_id).await?;
+            if status.task_state.is_final() {
+                return match (status.task_state, status.transaction_hash) {
+                    (RelayTaskState::ExecSuccess, Some(hash)) => Ok((hash, true)),
+                    (RelayTaskState::ExecReverted, Some(hash)) => Ok((hash, false)),
+                    (state, _) => Err(RunnerError::Rejected(format!(
+                        "relay task {task_id} ended as {state:?}: {}",
+                        status.last_check_message.unwrap_or_default()
+                    ))),
+                };
+            }
+            if Instant::now() >= deadline {
+                return Err(RunnerError::Timeout(format!(
+                    "relay task {task_id} not final within {timeout:?}"
+                )));
+            }
+            tokio::time::sleep(self.relay.poll_interval).await;
+        }
+    }
+
+    async fn submit_safe(
+        &self,
+        safe: Address,
+        txs: Vec<PreparedTransaction>,
+    ) -> Result<String, RunnerError> {
+        let nonce = ISafe::new(safe, &self.provider)
+            .nonce()
+            .call()
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))?;
+        let prepared = prepare_safe_execution(safe, self.chain_id, nonce, txs)?;
+        let signature = sign_prehash(&self.signer, prepared.safe_tx_hash)?;
+        let exec = prepared.to_exec_transaction(signature);
+        self.create_task(
+            "relays/v2/sponsored-call",
+            sponsored_call_body(self.chain_id, &exec, &self.relay.api_key),
+        )
+        .await
+    }
+
+    async fn create_task(&self, path: &str, body: Value) -> Result<String, RunnerError> {
+        let url = endpoint(&self.base_url, path)?;
+        let created: TaskCreated = self.request(self.client.post(url).json(&body)).await?;
+        Ok(created.task_id)
+    }
+
+    async fn request<T: serde::de::DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T, RunnerError> {
+        let response = request
+            .send()
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))?;
+        if status.is_client_error() {
+            return Err(RunnerError::Rejected(format!(
+                "relayer answered {status}: {body}"
+            )));
+        }
+        if !status.is_success() {
+            return Err(RunnerError::Transport(format!(
+                "relayer answered {status}: {body}"
+            )));
+        }
+        serde_json::from_str(&body).map_err(|err| {
+            RunnerError::Transport(format!("unexpected relayer response {body}: {err}"))
+        })
+    }
+}
+
+#[async_trait]
+impl ContractRunner for RelayRunner {
+    /// The Safe in [`RelayMode::Safe`], otherwise the signer.
+    fn sender_address(&self) -> Address {
+        match self.relay.mode {
+            RelayMode::Safe { safe } => safe,
+            RelayMode::Erc2771 { .. } => self.signer.address(),
+        }
+    }
+
+    async fn estimate_gas(&self, tx: PreparedTransaction) -> Result<u64, RunnerError> {
+        self.provider
+            .estimate_gas(prepared_to_request(self.address(), tx).into())
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))
+    }
+
+    async fn call(&self, tx: PreparedTransaction) -> Result<Bytes, RunnerError> {
+        self.provider
+            .call(prepared_to_request(self.address(), tx).into())
+            .await
+            .map_err(call_error)
+    }
+
+    async fn block_number(&self) -> Result<u64, RunnerError> {
+        receipt::fetch_block_number(&self.provider).await
+    }
+
+    async fn chain_id(&self) -> Result<u64, RunnerError> {
+        Ok(self.chain_id)
+    }
+
+    async fn transaction_receipt(
+        &self,
+        tx_hash: B256,
+    ) -> Result<Option<TxReceiptInfo>, RunnerError> {
+        receipt::fetch_receipt(&self.provider, tx_hash).await
+    }
+
+    async fn send_transactions(
+        &self,
+        txs: Vec<PreparedTransaction>,
+    ) -> Result<Vec<SubmittedTx>, RunnerError> {
+        let sequential = matches!(self.relay.mode, RelayMode::Erc2771 { .. });
+        let tasks = self.submit(txs).await?;
+        let mut submitted = Vec::with_capacity(tasks.len());
+        for (index, task_id) in tasks.iter().enumerate() {
+            let (tx_hash, success) = self.wait_for_task(task_id).await?;
+            submitted.push(SubmittedTx {
+                tx_hash: tx_hash_to_bytes(tx_hash),
+                success,
+                index: sequential.then_some(index),
+            });
+        }
+        Ok(submitted)
+    }
+}
+
+fn relay_base_url(relay_url: &str) -> Result<Url, RunnerError> {
+    let mut base = relay_url.trim_end_matches('/').to_string();
+    base.push('/');
+    parse_rpc_url(&base)
+}
+
+fn endpoint(base_url: &Url, path: &str) -> Result<Url, RunnerError> {
+    base_url
+        .join(path)
+        .map_err(|err| RunnerError::Rejected(format!("invalid relay url: {err}")))
+}
+
+/// One `SponsoredCallERC2771` per transaction, numbered from `user_nonce` on.
+fn erc2771_calls(
+    chain_id: u64,
+    user: Address,
+    user_nonce: U256,
+    deadline: u64,
+    txs: Vec<PreparedTransaction>,
+) -> Result<Vec<SponsoredCallERC2771>, RunnerError> {
+    txs.into_iter()
+        .enumerate()
+        .map(|(index, tx)| {
+            if tx.value.is_some_and(|value| !value.is_zero()) {
+                return Err(RunnerError::Unsupported(
+                    "ERC-2771 relay calls cannot carry native value".to_string(),
+                ));
+            }
+            Ok(SponsoredCallERC2771 {
+                chainId: U256::from(chain_id),
+                target: tx.to,
+                data: tx.data,
+                user,
+                userNonce: user_nonce + U256::from(index),
+                userDeadline: U256::from(deadline),
+            })
+        })
+        .collect()
+}
+
+fn sign_erc2771(
+    signer: &PrivateKeySigner,
+    call: &SponsoredCallERC2771,
+    forwarder: Address,
+) -> Result<Bytes, RunnerError> {
+    let domain = eip712_domain! {
+        name: ERC2771_DOMAIN_NAME,
+        version: "1",
+        chain_id: call.chainId.saturating_to(),
+        verifying_contract: forwarder,
+    };
+    sign_prehash(signer, call.eip712_signing_hash(&domain))
+}
+
+fn erc2771_body(call: &SponsoredCallERC2771, signature: &Bytes, api_key: &str) -> Value {
+    json!({
+        "chainId": call.chainId.to_string(),
+        "target": call.target,
+        "data": call.data,
+        "user": call.user,
+        "userNonce": call.userNonce.to_string(),
+        "userDeadline": call.userDeadline.saturating_to::<u64>(),
+        "userSignature": signature,
+        "sponsorApiKey": api_key,
+    })
+}
+
+fn sponsored_call_body(chain_id: u64, tx: &PreparedTransaction, api_key: &str) -> Value {
+    json!({
+        "chainId": chain_id.to_string(),
+        "target": tx.to,
+        "data": tx.data,
+        "sponsorApiKey": api_key,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Signature;
+    use alloy_sol_types::SolCall;
+
+    const KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[test]
+    fn erc2771_signature_recovers_user_and_body_matches_api() {
+        let signer = parse_private_key(KEY).expect("key");
+        let forwarder = Address::repeat_byte(0xf0);
+        let call = SponsoredCallERC2771 {
+            chainId: U256::from(100u64),
+            target: Address::repeat_byte(0x11),
+            data: Bytes::from_static(&[0xde, 0xad]),
+            user: signer.address(),
+            userNonce: U256::from(4u64),
+            userDeadline: U256::from(1_700_000_000u64),
+        };
+
+        let signature = sign_erc2771(&signer, &call, forwarder).expect("sign");
+        let domain = eip712_domain! {
+            name: ERC2771_DOMAIN_NAME,
+            version: "1",
+            chain_id: 100,
+            verifying_contract: forwarder,
+        };
+        let recovered = Signature::from_raw(&signature)
+            .expect("signature")
+            .recover_address_from_prehash(&call.eip712_signing_hash(&domain))
+            .expect("recover");
+        assert_eq!(recovered, signer.address());
+
+        let body = erc2771_body(&call, &signature, "key");
+        assert_eq!(body["chainId"], "100");
+        assert_eq!(body["data"], "0xdead");
+        assert_eq!(body["userNonce"], "4");
+        assert_eq!(body["userDeadline"], 1_700_000_000u64);
+        assert_eq!(body["sponsorApiKey"], "key");
+    }
+
+    #[test]
+    fn erc2771_batch_signs_consecutive_nonces() {
+        let user = Address::repeat_byte(0x22);
+        let tx = |byte| PreparedTransaction {
+            to: Address::repeat_byte(byte),
+            data: Bytes::from_static(&[0x01]),
+            value: None,
+        };
+        let calls = erc2771_calls(
+            100,
+            user,
+            U256::from(7u64),
+            1_700_000_000,
+            vec![tx(0x11), tx(0x12), tx(0x13)],
+        )
+        .expect("calls");
+        let nonces: Vec<U256> = calls.iter().map(|call| call.userNonce).collect();
+        assert_eq!(nonces, [7u64, 8, 9].map(U256::from));
+        assert_eq!(calls[2].target, Address::repeat_byte(0x13));
+
+        let with_value = PreparedTransaction {
+            value: Some(U256::from(1u64)),
+            ..tx(0x11)
+        };
+        assert!(matches!(
+            erc2771_calls(100, user, U256::ZERO, 0, vec![with_value]),
+            Err(RunnerError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn safe_mode_relays_signed_exec_transaction() {
+        let signer = parse_private_key(KEY).expect("key");
+        let safe = Address::repeat_byte(0x5a);
+        let prepared = prepare_safe_execution(
+            safe,
+            100,
+            U256::from(2u64),
+            vec![PreparedTransaction {
+                to: Address::repeat_byte(0x11),
+                data: Bytes::from_static(&[0x01]),
+                value: None,
+            }],
+        )
+        .expect("prepare");
+        let signature = sign_prehash(&signer, prepared.safe_tx_hash).expect("sign");
+        let exec = prepared.to_exec_transaction(signature.clone());
+
+        let body = sponsored_call_body(100, &exec, "key");
+        assert_eq!(body["target"], json!(safe));
+        let decoded = ISafe::execTransactionCall::abi_decode(&exec.data).expect("decode");
+        assert_eq!(decoded.signatures, signature);
+        let recovered = Signature::from_raw(&decoded.signatures)
+            .expect("signature")
+            .recover_address_from_prehash(&prepared.safe_tx_hash)
+            .expect("recover");
+        assert_eq!(recovered, signer.address());
+    }
+
+    #[test]
+    fn task_status_parses_relayer_states() {
+        let status: TaskStatusResponse = serde_json::from_value(json!({
+            "task": {
+                "chainId": 100,
+                "taskId": "0xabc",
+                "taskState": "ExecSuccess",
+                "transactionHash": B256::repeat_byte(0x0f),
+            }
+        }))
+        .expect("status");
+        assert_eq!(status.task.task_state, RelayTaskState::ExecSuccess);
+        assert!(status.task.task_state.is_final());
+        assert_eq!(status.task.transaction_hash, Some(B256::repeat_byte(0x0f)));
+
+        let pending: RelayTaskStatus = serde_json::from_value(json!({
+            "taskId": "0xabc",
+            "taskState": "NotFound",
+        }))
+        .expect("status");
+        assert_eq!(pending.task_state, RelayTaskState::Unknown);
+        assert!(!pending.task_state.is_final());
+    }
+
+    #[test]
+    fn relay_endpoints_keep_base_path() {
+        let base = relay_base_url("https://relay.example/api").expect("url");
+        assert_eq!(
+            endpoint(&base, "tasks/status/0x1").expect("url").as_str(),
+            "https://relay.example/api/tasks/status/0x1"
+        );
+    }
+}
@@ -0,0 +1,389 @@
+//! Runner backed by any alloy signer, for keys the SDK never sees.
+//!
+//! Hardware wallets (`alloy-signer-ledger`, `alloy-signer-trezor`) and remote signers
+//! implement alloy's [`Signer`] and [`TxSigner`] traits, so they can drive EOA sends
+//! and owner signatures for 1/1 Safes without exporting a private key. Safe
+//! transactions are signed as EIP-712 typed data rather than a bare hash, which lets a
+//! Ledger show the Safe call fields on its screen before the owner confirms. The
+//! `ledger` feature adds constructors that open the device directly.
+
+use super::{
+    ContractRunner, PreparedSafeExecution, PreparedTransaction, RunnerError, SigningProvider,
+    SubmittedTx, TxOverrides, TxReceiptInfo, apply_overrides, call_error, parse_rpc_url,
+    prepare_safe_execution, prepared_to_request, receipt, tx_hash_to_bytes,
+};
+use alloy_network::{AnyNetwork, EthereumWallet, TxSigner};
+use alloy_primitives::{Address, B256, Bytes, Signature, U256};
+use alloy_provider::{Identity, Provider, ProviderBuilder};
+use alloy_signer::Signer;
+use alloy_sol_types::{eip712_domain, sol};
+use async_trait::async_trait;
+use safe_rs::ISafe;
+use std::sync::Arc;
+
+sol! {
+    struct SafeTx {
+        address to;
+        uint256 value;
+        bytes data;
+        uint8 operation;
+        uint256 safeTxGas;
+        uint256 baseGas;
+        uint256 gasPrice;
+        address gasToken;
+        address refundReceiver;
+        uint256 nonce;
+    }
+}
+
+/// Contract runner that signs with an external alloy signer.
+///
+/// Without a Safe, prepared transactions are sent one by one from the signer's
+/// address (not atomic). With [`SignerContractRunner::connect_safe`] a batch becomes
+/// one `execTransaction` on a Safe the signer solely owns.
+///
+/// ```rust,ignore
+/// use circles_sdk::{HDPath, SignerContractRunner};
+///
+/// let runner =
+///     SignerContractRunner::connect_ledger_safe(rpc_url, HDPath::LedgerLive(0), 100, treasury)
+///         .await?;
+/// ```
+pub struct SignerContractRunner<S> {
+    signer: Arc<S>,
+    signer_address: Address,
+    provider: SigningProvider,
+    safe: Option<Address>,
+}
+
+impl<S> SignerContractRunner<S>
+where
+    S: Signer + TxSigner<Signature> + Send + Sync + 'static,
+{
+    /// Send transactions directly from the signer's own address.
+    pub fn connect(rpc_url: &str, signer: S) -> Result<Self, RunnerError> {
+        let rpc_url = parse_rpc_url(rpc_url)?;
+        // Shared with the wallet, so device signers that cannot be cloned still work.
+        let signer = Arc::new(signer);
+        // Same fillers as the private-key runners; only the wallet differs.
+        let provider = ProviderBuilder::<Identity, Identity, AnyNetwork>::default()
+            .with_recommended_fillers()
+            .wallet(EthereumWallet::from(signer.clone()))
+            .connect_http(rpc_url)
+            .erased();
+        Ok(Self {
+            signer_address: Signer::address(&*signer),
+            signer,
+            provider,
+            safe: None,
+        })
+    }
+
+    /// Execute through `safe`, which must have the signer as its only owner.
+    pub async fn connect_safe(
+        rpc_url: &str,
+        signer: S,
+        safe: Address,
+    ) -> Result<Self, RunnerError> {
+        let mut runner = Self::connect(rpc_url, signer)?;
+        let contract = ISafe::new(safe, &runner.provider);
+        let threshold = contract
+            .getThreshold()
+            .call()
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))?;
+        let is_owner = contract
+            .isOwner(runner.signer_address)
+            .call()
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))?;
+        if threshold != U256::from(1) || !is_owner {
+            return Err(RunnerError::Rejected(format!(
+                "{safe:#x} is not a 1/1 Safe owned by {:#x}",
+                runner.signer_address
+            )));
+        }
+        runner.safe = Some(safe);
+        Ok(runner)
+    }
+
+    /// Address of the external signer.
+    pub fn signer_address(&self) -> Address {
+        self.signer_address
+    }
+
+    /// Safe this runner executes through, if any.
+    pub fn safe_address(&self) -> Option<Address> {
+        self.safe
+    }
+
+    /// Ask the signer to approve `prepared` as EIP-712 typed data.
+    ///
+    /// The result is the owner signature in the `r || s || v` layout Safe expects.
+    pub async fn sign_safe_execution(
+        &self,
+        prepared: &PreparedSafeExecution,
+    ) -> Result<Bytes, RunnerError> {
+        let (payload, domain) = safe_typed_data(prepared);
+        let signature = self
+            .signer
+            .sign_typed_data(&payload, &domain)
+            .await
+            .map_err(|err| RunnerError::Rejected(err.to_string()))?;
+        Ok(Bytes::from(signature.as_bytes().to_vec()))
+    }
+
+    async fn send_one(&self, tx: PreparedTransaction) -> Result<B256, RunnerError> {
+        let pending = self
+            .provider
+            .send_transaction(prepared_to_request(Some(self.signer_address), tx).into())
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))?;
+        Ok(*pending.tx_hash())
+    }
+}
+
+#[cfg(feature = "ledger")]
+impl SignerContractRunner<alloy_signer_ledger::LedgerSigner> {
+    /// Send from the Ledger account at `path`, signing for `chain_id`.
+    ///
+    /// The Ethereum app must be open on an unlocked device.
+    pub async fn connect_ledger(
+        rpc_url: &str,
+        path: alloy_signer_ledger::HDPath,
+        chain_id: u64,
+    ) -> Result<Self, RunnerError> {
+        Self::connect(rpc_url, open_ledger(path, chain_id).await?)
+    }
+
+    /// Execute through `safe`, a 1/1 Safe owned by the Ledger account at `path`.
+    ///
+    /// Each batch is shown on the device as a `SafeTx` for the owner to confirm.
+    pub async fn connect_ledger_safe(
+        rpc_url: &str,
+        path: alloy_signer_ledger::HDPath,
+        chain_id: u64,
+        safe: Address,
+    ) -> Result<Self, RunnerError> {
+        Self::connect_safe(rpc_url, open_ledger(path, chain_id).await?, safe).await
+    }
+}
+
+#[cfg(feature = "ledger")]
+async fn open_ledger(
+    path: alloy_signer_ledger::HDPath,
+    chain_id: u64,
+) -> Result<alloy_signer_ledger::LedgerSigner, RunnerError> {
+    alloy_signer_ledger::LedgerSigner::new(path, Some(chain_id))
+        .await
+        .map_err(|err| RunnerError::Transport(format!("ledger: {err}")))
+}
+
+#[async_trait]
+impl<S> ContractRunner for SignerContractRunner<S>
+where
+    S: Signer + TxSigner<Signature> + Send + Sync + 'static,
+{
+    /// The Safe when one is configured, otherwise the signer.
+    fn sender_address(&self) -> Address {
+        self.safe.unwrap_or(self.signer_address)
+    }
+
+    async fn estimate_gas(&self, tx: PreparedTransaction) -> Result<u64, RunnerError> {
+        self.provider
+            .estimate_gas(prepared_to_request(self.address(), tx).into())
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))
+    }
+
+    async fn call(&self, tx: PreparedTransaction) -> Result<Bytes, RunnerError> {
+        self.provider
+            .call(prepared_to_request(self.address(), tx).into())
+            .await
+            .map_err(call_error)
+    }
+
+    async fn pending_nonce(&self) -> Result<u64, RunnerError> {
+        self.provider
+            .get_transaction_count(self.signer_address)
+            .pending()
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))
+    }
+
+    async fn block_number(&self) -> Result<u64, RunnerError> {
+        receipt::fetch_block_number(&self.provider).await
+    }
+
+    async fn chain_id(&self) -> Result<u64, RunnerError> {
+        self.provider
+            .get_chain_id()
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))
+    }
+
+    async fn transaction_receipt(
+        &self,
+        tx_hash: B256,
+    ) -> Result<Option<TxReceiptInfo>, RunnerError> {
+        receipt::fetch_receipt(&self.provider, tx_hash).await
+    }
+
+    async fn send_transaction_with_overrides(
+        &self,
+        tx: PreparedTransaction,
+        overrides: TxOverrides,
+    ) -> Result<SubmittedTx, RunnerError> {
+        if self.safe.is_some() {
+            return Err(RunnerError::Rejected(
+                "raw overrides are not supported for Safe execution".to_string(),
+            ));
+        }
+        let request = apply_overrides(
+            prepared_to_request(Some(self.signer_address), tx),
+            overrides,
+        );
+        let pending = self
+            .provider
+            .send_transaction(request.into())
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))?;
+        Ok(SubmittedTx {
+            tx_hash: tx_hash_to_bytes(*pending.tx_hash()),
+            success: true,
+            index: None,
+        })
+    }
+
+    async fn send_transactions(
+        &self,
+        txs: Vec<PreparedTransaction>,
+    ) -> Result<Vec<SubmittedTx>, RunnerError> {
+        if txs.is_empty() {
+            return Err(RunnerError::Rejected(
+                "no transactions provided".to_string(),
+            ));
+        }
+
+        let Some(safe) = self.safe else {
+            let mut submitted = Vec::with_capacity(txs.len());
+            for (index, tx) in txs.into_iter().enumerate() {
+                let tx_hash = self.send_one(tx).await?;
+                submitted.push(SubmittedTx {
+                    tx_hash: tx_hash_to_bytes(tx_hash),
+                    success: true,
+                    index: Some(index),
+                });
+            }
+            return Ok(submitted);
+        };
+
+        let chain_id = self
+            .provider
+            .get_chain_id()
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))?;
+        let nonce = ISafe::new(safe, &self.provider)
+            .nonce()
+            .call()
+            .await
+            .map_err(|err| RunnerError::Transport(err.to_string()))?;
+        let prepared = prepare_safe_execution(safe, chain_id, nonce, txs)?;
+        let signature = self.sign_safe_execution(&prepared).await?;
+        let tx_hash = self
+            .send_one(prepared.to_exec_transaction(signature))
+            .await?;
+        Ok(vec![SubmittedTx {
+            tx_hash: tx_hash_to_bytes(tx_hash),
+            success: true,
+            index: None,
+        }])
+    }
+}
+
+fn safe_typed_data(prepared: &PreparedSafeExecution) -> (SafeTx, alloy_sol_types::Eip712Domain) {
+    let tx = &prepared.safe_tx;
+    let payload = SafeTx {
+        to: tx.to,
+        value: tx.value,
+        data: tx.data.clone(),
+        operation: tx.operation.as_u8(),
+        safeTxGas: tx.safe_tx_gas,
+        baseGas: tx.base_gas,
+        gasPrice: tx.gas_price,
+        gasToken: tx.gas_token,
+        refundReceiver: tx.refund_receiver,
+        nonce: tx.nonce,
+    };
+    let domain = eip712_domain! {
+        chain_id: prepared.chain_id,
+        verifying_contract: prepared.safe_address,
+    };
+    (payload, domain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+    use alloy_signer_local::PrivateKeySigner;
+    use alloy_sol_types::SolStruct;
+
+    const SAFE: Address = address!("5afe00000000000000000000000000000000005a");
+
+    fn prepared() -> PreparedSafeExecution {
+        prepare_safe_execution(
+            SAFE,
+            100,
+            U256::from(7),
+            vec![
+                PreparedTransaction {
+                    to: Address::repeat_byte(0x11),
+                    data: Bytes::from(vec![0xaa, 0xbb]),
+                    value: None,
+                },
+                PreparedTransaction {
+                    to: Address::repeat_byte(0x22),
+                    data: Bytes::new(),
+                    value: Some(U256::from(5)),
+                },
+            ],
+        )
+        .expect("prepare")
+    }
+
+    #[test]
+    fn safe_typed_data_hashes_to_safe_tx_hash() {
+        let prepared = prepared();
+        let (payload, domain) = safe_typed_data(&prepared);
+        assert_eq!(payload.eip712_signing_hash(&domain), prepared.safe_tx_hash);
+    }
+
+    #[tokio::test]
+    async fn typed_data_signature_recovers_owner() {
+        let signer = PrivateKeySigner::random();
+        let runner =
+            SignerContractRunner::connect("http://127.0.0.1:8545", signer.clone()).expect("runner");
+        assert_eq!(runner.sender_address(), signer.address());
+        assert_eq!(runner.safe_address(), None);
+
+        let prepared = prepared();
+        let signature = runner
+            .sign_safe_execution(&prepared)
+            .await
+            .expect("signature");
+        assert_eq!(signature.len(), 65);
+        assert!(matches!(signature[64], 27 | 28));
+        let recovered = Signature::from_raw(&signature)
+            .expect("parse")
+            .recover_address_from_prehash(&prepared.safe_tx_hash)
+            .expect("recover");
+        assert_eq!(recovered, signer.address());
+    }
+
+    #[test]
+    fn signer_runner_rejects_invalid_rpc_url() {
+        let result = SignerContractRunner::connect("not a url", PrivateKeySigner::random());
+        assert!(matches!(result, Err(RunnerError::Rejected(_))));
+    }
+}
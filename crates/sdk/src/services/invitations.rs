@@ -1,11 +1,10 @@
 use crate::avatar::human::{HumanAvatar, ProxyInviter, ReferralCodePlan};
 use crate::services::invite_farm::GeneratedReferral;
 use crate::services::referrals::{
-    ReferralPreviewList, ReferralPublicListOptions, Referrals, generate_private_key,
-    private_key_to_address,
+    ReferralPreviewList, ReferralPublicListOptions, Referrals, private_key_to_address, sign_digest,
 };
-use crate::{ContractRunner, Core, PreparedTransaction, SdkError};
-use alloy_primitives::{Address, Bytes};
+use crate::{ContractRunner, Core, PreparedTransaction, SdkError, SubmittedTx, call_to_tx};
+use alloy_primitives::{Address, Bytes, U256};
 use alloy_sol_types::{SolCall, SolValue, sol};
 use circles_abis::ReferralsModule;
 use circles_profiles::Profiles;
@@ -20,6 +19,17 @@ sol! {
     }
 }
 
+/// Passkey that takes over a referral account in [`Invitations::redeem_with_secret`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PasskeyClaim {
+    /// Public key x coordinate.
+    pub x: U256,
+    /// Public key y coordinate.
+    pub y: U256,
+    /// WebAuthn verifier contract for the key.
+    pub verifier: Address,
+}
+
 /// Dedicated invitations facade mirroring the TypeScript SDK service surface.
 #[derive(Clone)]
 pub struct Invitations {
@@ -184,13 +194,63 @@ impl Invitations {
 
     /// Generate referral secrets plus their derived signer addresses.
     pub fn generate_secrets(&self, count: usize) -> Result<Vec<GeneratedReferral>, SdkError> {
-        let mut generated = Vec::with_capacity(count);
-        for _ in 0..count {
-            let secret = generate_private_key();
-            let signer = private_key_to_address(&secret)?;
-            generated.push(GeneratedReferral { secret, signer });
+        (0..count).map(|_| GeneratedReferral::generate()).collect()
+    }
+
+    /// Plan claiming the referral account of `secret` for a passkey owner.
+    ///
+    /// The secret signs the module's `getPasskeyHash(x, y, verifier)` digest, which
+    /// `ReferralsModule.claimAccount` recovers back to the referral signer. Fails early if
+    /// the signer has no account or it was already claimed.
+    pub async fn plan_redeem_with_secret(
+        &self,
+        secret: &str,
+        passkey: &PasskeyClaim,
+    ) -> Result<PreparedTransaction, SdkError> {
+        let signer = private_key_to_address(secret)?;
+        let module = self.core.referrals_module();
+        let ReferralsModule::accountsReturn { account, claimed } = module
+            .accounts(signer)
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))?;
+        if account == Address::ZERO {
+            return Err(SdkError::OperationFailed(format!(
+                "no referral account for signer {signer:#x}"
+            )));
+        }
+        if claimed {
+            return Err(SdkError::OperationFailed(format!(
+                "referral account {account:#x} was already claimed"
+            )));
         }
-        Ok(generated)
+
+        let digest = module
+            .getPasskeyHash(passkey.x, passkey.y, passkey.verifier)
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))?;
+        Ok(call_to_tx(
+            self.core.config.referrals_module_address,
+            ReferralsModule::claimAccount_0Call {
+                x: passkey.x,
+                y: passkey.y,
+                verifier: passkey.verifier,
+                signature: sign_digest(secret, digest)?,
+            },
+            None,
+        ))
+    }
+
+    /// Claim the referral account of `secret` for a passkey owner using the runner.
+    pub async fn redeem_with_secret(
+        &self,
+        secret: &str,
+        passkey: &PasskeyClaim,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        let runner = self.runner.as_ref().ok_or(SdkError::MissingRunner)?;
+        let tx = self.plan_redeem_with_secret(secret, passkey).await?;
+        Ok(runner.send_transactions(vec![tx]).await?)
     }
 
     async fn human_avatar(&self, inviter: Address) -> Result<HumanAvatar, SdkError> {
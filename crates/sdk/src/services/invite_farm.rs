@@ -2,6 +2,8 @@ use crate::services::referrals::{
     ReferralPreviewList, ReferralPublicListOptions, Referrals, generate_private_key,
     private_key_to_address,
 };
+#[cfg(feature = "mnemonic")]
+use crate::services::referrals::{mnemonic_to_private_key, private_key_to_mnemonic};
 use crate::{Core, PreparedTransaction, SdkError, call_to_tx};
 use alloy_primitives::{Address, Bytes, U256};
 use alloy_sol_types::{SolCall, SolValue, sol};
@@ -15,6 +17,32 @@ pub struct GeneratedReferral {
     pub signer: Address,
 }
 
+impl GeneratedReferral {
+    /// Fresh random secp256k1 secret with the address its public key hashes to.
+    pub fn generate() -> Result<Self, SdkError> {
+        Self::from_secret(generate_private_key())
+    }
+
+    /// Rebuild a referral from a secret shared as hex.
+    pub fn from_secret(secret: impl Into<String>) -> Result<Self, SdkError> {
+        let secret = secret.into();
+        let signer = private_key_to_address(&secret)?;
+        Ok(Self { secret, signer })
+    }
+
+    /// The secret as a 24-word BIP-39 phrase, easier to hand over than hex.
+    #[cfg(feature = "mnemonic")]
+    pub fn mnemonic(&self) -> Result<String, SdkError> {
+        Ok(private_key_to_mnemonic(&self.secret)?)
+    }
+
+    /// Rebuild a referral from a phrase produced by [`GeneratedReferral::mnemonic`].
+    #[cfg(feature = "mnemonic")]
+    pub fn from_mnemonic(phrase: &str) -> Result<Self, SdkError> {
+        Self::from_secret(mnemonic_to_private_key(phrase)?)
+    }
+}
+
 /// Result of planning TS-style batch referral generation.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GenerateReferralsResult {
@@ -213,15 +241,7 @@ fn invitation_fee_amount() -> U256 {
 }
 
 fn generate_referral_secrets(count: usize) -> Result<Vec<GeneratedReferral>, SdkError> {
-    let mut referrals = Vec::with_capacity(count);
-
-    for _ in 0..count {
-        let secret = generate_private_key();
-        let signer = private_key_to_address(&secret)?;
-        referrals.push(GeneratedReferral { secret, signer });
-    }
-
-    Ok(referrals)
+    (0..count).map(|_| GeneratedReferral::generate()).collect()
 }
 
 fn build_claim_tx(invitation_farm: Address, count: u64) -> PreparedTransaction {
@@ -345,7 +365,10 @@ fn build_invite_transfer_tx(
 
 #[cfg(test)]
 mod tests {
-    use super::{InviteFarm, build_claim_tx, encode_invitees_data, generate_referral_secrets};
+    use super::{
+        GeneratedReferral, InviteFarm, build_claim_tx, encode_invitees_data,
+        generate_referral_secrets,
+    };
     use crate::{Core, SdkError, config};
     use alloy_primitives::{Address, U256, address};
     use alloy_sol_types::{SolCall, SolValue};
@@ -406,6 +429,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn generated_referral_round_trips_secret() {
+        let referral = GeneratedReferral::generate().expect("generated referral");
+        let rebuilt = GeneratedReferral::from_secret(referral.secret.clone()).expect("rebuilt");
+        assert_eq!(rebuilt, referral);
+        assert!(GeneratedReferral::from_secret("0x00").is_err());
+    }
+
     #[test]
     fn list_referrals_requires_referrals_backend() {
         let runtime = tokio::runtime::Runtime::new().expect("runtime");
@@ -4,9 +4,21 @@ pub mod data;
 pub mod invitations;
 /// Dedicated invitation-farm facade mirroring the TS SDK service.
 pub mod invite_farm;
+/// Metadata digests, short names, and custom names in the name registry.
+pub mod names;
+/// Watch-only aggregation of balances and history across several avatars.
+pub mod portfolio;
+/// Background redemption of due subscriptions.
+pub mod redemption_worker;
 /// Optional referrals backend client and related response types.
 pub mod referrals;
 /// Registration helpers (human/org/base group).
 pub mod registration;
+/// Recurring payments through the subscription module.
+pub mod subscriptions;
+/// Cached token metadata (owner, kind, symbol, name) for labelling balances.
+pub mod token_registry;
 /// Dedicated tokens facade mirroring the TS SDK service.
 pub mod tokens;
+/// Renewal of outgoing trust before it expires.
+pub mod trust_scheduler;
@@ -0,0 +1,243 @@
+use crate::cid::{cid_to_digest, digest_to_cid_v0};
+use crate::{ContractRunner, Core, PreparedTransaction, SdkError, SubmittedTx, call_to_tx};
+use alloy_primitives::aliases::U72;
+use alloy_primitives::{Address, B256, U256};
+use circles_abis::NameRegistry;
+use std::sync::Arc;
+
+/// A free short name found by [`Names::find_short_name`] and the nonce that produces it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShortNameCandidate {
+    pub short_name: u128,
+    pub nonce: u64,
+}
+
+/// Profile pointers and names stored in the name registry.
+///
+/// Metadata digests and short names are written by the avatar itself, so the runner must
+/// send as the avatar. Custom names and symbols can only be set by the hub while
+/// registering an organisation or group; this facade validates and reads them.
+#[derive(Clone)]
+pub struct Names {
+    core: Arc<Core>,
+    runner: Option<Arc<dyn ContractRunner>>,
+}
+
+impl Names {
+    pub fn new(core: Arc<Core>, runner: Option<Arc<dyn ContractRunner>>) -> Self {
+        Self { core, runner }
+    }
+
+    /// Name registry address this facade talks to.
+    pub fn registry_address(&self) -> Address {
+        self.core.config.name_registry_address
+    }
+
+    /// Profile digest of `avatar`, or `None` when no profile was published.
+    pub async fn metadata_digest(&self, avatar: Address) -> Result<Option<B256>, SdkError> {
+        let digest = self
+            .core
+            .name_registry()
+            .getMetadataDigest(avatar)
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))?;
+        Ok((!digest.is_zero()).then_some(digest))
+    }
+
+    /// CIDv0 of `avatar`'s profile, or `None` when no profile was published.
+    pub async fn profile_cid(&self, avatar: Address) -> Result<Option<String>, SdkError> {
+        Ok(self.metadata_digest(avatar).await?.map(digest_to_cid_v0))
+    }
+
+    /// Plan pointing the sender's profile at a CIDv0 or dag-pb CIDv1.
+    pub fn plan_update_metadata(&self, cid: &str) -> Result<PreparedTransaction, SdkError> {
+        Ok(self.plan_update_metadata_digest(cid_to_digest(cid)?))
+    }
+
+    /// Plan pointing the sender's profile at a raw 32-byte digest.
+    pub fn plan_update_metadata_digest(&self, digest: B256) -> PreparedTransaction {
+        call_to_tx(
+            self.registry_address(),
+            NameRegistry::updateMetadataDigestCall {
+                _metadataDigest: digest,
+            },
+            None,
+        )
+    }
+
+    /// Point the runner's avatar profile at a CIDv0 or dag-pb CIDv1.
+    pub async fn update_metadata(&self, cid: &str) -> Result<Vec<SubmittedTx>, SdkError> {
+        let tx = self.plan_update_metadata(cid)?;
+        self.send(vec![tx]).await
+    }
+
+    /// Display name: the custom name if one was registered, else the short-name default.
+    pub async fn name(&self, avatar: Address) -> Result<String, SdkError> {
+        self.core
+            .name_registry()
+            .name(avatar)
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))
+    }
+
+    /// Token symbol: the custom symbol if one was registered, else the registry default.
+    pub async fn symbol(&self, avatar: Address) -> Result<String, SdkError> {
+        self.core
+            .name_registry()
+            .symbol(avatar)
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))
+    }
+
+    /// Custom name registered for `avatar` by the hub, if any.
+    pub async fn custom_name(&self, avatar: Address) -> Result<Option<String>, SdkError> {
+        let name = self
+            .core
+            .name_registry()
+            .customNames(avatar)
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))?;
+        Ok((!name.is_empty()).then_some(name))
+    }
+
+    /// Custom symbol registered for `avatar` by the hub, if any.
+    pub async fn custom_symbol(&self, avatar: Address) -> Result<Option<String>, SdkError> {
+        let symbol = self
+            .core
+            .name_registry()
+            .customSymbols(avatar)
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))?;
+        Ok((!symbol.is_empty()).then_some(symbol))
+    }
+
+    /// Whether the hub would accept `name` when registering an organisation or group.
+    pub async fn is_valid_name(&self, name: &str) -> Result<bool, SdkError> {
+        self.core
+            .name_registry()
+            .isValidName(name.to_string())
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))
+    }
+
+    /// Whether the hub would accept `symbol` when registering a group.
+    pub async fn is_valid_symbol(&self, symbol: &str) -> Result<bool, SdkError> {
+        self.core
+            .name_registry()
+            .isValidSymbol(symbol.to_string())
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))
+    }
+
+    /// Short name registered by `avatar`, or `None` if it has none.
+    pub async fn short_name(&self, avatar: Address) -> Result<Option<u128>, SdkError> {
+        let short_name = self
+            .core
+            .name_registry()
+            .shortNames(avatar)
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))?;
+        Ok((!short_name.is_zero()).then(|| short_name.to::<u128>()))
+    }
+
+    /// Avatar owning `short_name`, or `None` if it is unclaimed.
+    pub async fn resolve_short_name(&self, short_name: u128) -> Result<Option<Address>, SdkError> {
+        // Values wider than 72 bits can never have been registered.
+        let Ok(short_name) = U72::try_from(short_name) else {
+            return Ok(None);
+        };
+        let avatar = self
+            .core
+            .name_registry()
+            .shortNameToAvatar(short_name)
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))?;
+        Ok((avatar != Address::ZERO).then_some(avatar))
+    }
+
+    /// Short name `avatar` would get by registering with `nonce`.
+    pub async fn short_name_with_nonce(
+        &self,
+        avatar: Address,
+        nonce: u64,
+    ) -> Result<u128, SdkError> {
+        let short_name = self
+            .core
+            .name_registry()
+            .calculateShortNameWithNonce(avatar, U256::from(nonce))
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))?;
+        Ok(short_name.to::<u128>())
+    }
+
+    /// First unclaimed short name for `avatar`, to pass to [`register_short_name`](Self::register_short_name).
+    pub async fn find_short_name(&self, avatar: Address) -> Result<ShortNameCandidate, SdkError> {
+        let found = self
+            .core
+            .name_registry()
+            .searchShortName(avatar)
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))?;
+        Ok(ShortNameCandidate {
+            short_name: found.shortName_.to::<u128>(),
+            nonce: found.nonce_.saturating_to(),
+        })
+    }
+
+    /// Plan claiming the sender's short name derived from `nonce`.
+    pub fn plan_register_short_name(&self, nonce: u64) -> PreparedTransaction {
+        call_to_tx(
+            self.registry_address(),
+            NameRegistry::registerShortNameWithNonceCall {
+                _nonce: U256::from(nonce),
+            },
+            None,
+        )
+    }
+
+    /// Claim the runner avatar's short name derived from `nonce`.
+    pub async fn register_short_name(&self, nonce: u64) -> Result<Vec<SubmittedTx>, SdkError> {
+        self.send(vec![self.plan_register_short_name(nonce)]).await
+    }
+
+    async fn send(&self, txs: Vec<PreparedTransaction>) -> Result<Vec<SubmittedTx>, SdkError> {
+        let runner = self.runner.as_ref().ok_or(SdkError::MissingRunner)?;
+        Ok(runner.send_transactions(txs).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config;
+    use alloy_sol_types::SolCall;
+
+    #[test]
+    fn plans_target_the_registry_with_encoded_calls() {
+        let sdk = crate::Sdk::new(config::gnosis_mainnet(), None).expect("sdk");
+        let names = sdk.name_registry();
+        let cid = "QmfDWxB9jtEGHLi6ToJKWyoXeRzu64WBSKUfnwCWKHLsFn";
+
+        let update = names.plan_update_metadata(cid).expect("plan");
+        assert_eq!(update.to, sdk.config().name_registry_address);
+        let decoded = NameRegistry::updateMetadataDigestCall::abi_decode(&update.data).unwrap();
+        assert_eq!(decoded._metadataDigest, cid_to_digest(cid).unwrap());
+        assert!(names.plan_update_metadata("not-a-cid").is_err());
+
+        let register = names.plan_register_short_name(42);
+        let decoded =
+            NameRegistry::registerShortNameWithNonceCall::abi_decode(&register.data).unwrap();
+        assert_eq!(decoded._nonce, U256::from(42));
+    }
+}
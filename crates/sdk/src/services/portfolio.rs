@@ -0,0 +1,466 @@
+use crate::SdkError;
+use alloy_primitives::{Address, U256};
+use circles_rpc::CirclesRpc;
+use circles_types::{Balance, TokenBalanceResponse, TransactionHistoryRow};
+use circles_utils::converter::atto_static_circles_to_atto_circles;
+use circles_utils::time::unix_now;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(30);
+const DEFAULT_HISTORY_LIMIT: u32 = 50;
+
+/// One member's holding of one token, in demurraged atto-circles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortfolioHolding {
+    pub holder: Address,
+    pub token_address: Address,
+    pub amount: U256,
+    pub is_wrapped: bool,
+    pub is_inflationary: bool,
+}
+
+/// Everything the portfolio holds of one avatar's Circles, across members and wrappers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortfolioToken {
+    pub token_owner: Address,
+    /// Sum of `holdings`, with static (inflationary) wrappers converted to demurraged units.
+    pub total: U256,
+    pub holdings: Vec<PortfolioHolding>,
+}
+
+/// Aggregated balances of all portfolio members.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortfolioBalances {
+    /// One entry per token owner, ordered by address.
+    pub tokens: Vec<PortfolioToken>,
+    pub total: U256,
+    /// Unix time used for static → demurraged conversions.
+    pub as_of: u64,
+}
+
+impl PortfolioBalances {
+    /// Aggregate `(holder, rows)` pairs as returned by `circlesV2_getTokenBalances`.
+    pub fn aggregate(
+        balances: impl IntoIterator<Item = (Address, Vec<TokenBalanceResponse>)>,
+        as_of: u64,
+    ) -> Self {
+        let mut tokens: BTreeMap<Address, PortfolioToken> = BTreeMap::new();
+        for (holder, rows) in balances {
+            for row in rows {
+                let Some(amount) = demurraged_amount(&row, as_of) else {
+                    continue;
+                };
+                let token = tokens
+                    .entry(row.token_owner)
+                    .or_insert_with(|| PortfolioToken {
+                        token_owner: row.token_owner,
+                        total: U256::ZERO,
+                        holdings: Vec::new(),
+                    });
+                token.total = token.total.saturating_add(amount);
+                token.holdings.push(PortfolioHolding {
+                    holder,
+                    token_address: row.token_address,
+                    amount,
+                    is_wrapped: row.is_wrapped,
+                    is_inflationary: row.is_inflationary,
+                });
+            }
+        }
+        let tokens: Vec<_> = tokens.into_values().collect();
+        let total = tokens
+            .iter()
+            .fold(U256::ZERO, |sum, token| sum.saturating_add(token.total));
+        Self {
+            tokens,
+            total,
+            as_of,
+        }
+    }
+
+    /// Aggregated amount of `token_owner`'s Circles, zero when none are held.
+    pub fn token_total(&self, token_owner: Address) -> U256 {
+        self.tokens
+            .iter()
+            .find(|token| token.token_owner == token_owner)
+            .map_or(U256::ZERO, |token| token.total)
+    }
+}
+
+/// How a transfer relates to the portfolio as a whole.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferDirection {
+    Incoming,
+    Outgoing,
+    /// Between two portfolio members; it does not change the combined balance.
+    Internal,
+}
+
+/// A history row seen from the portfolio's point of view.
+#[derive(Debug, Clone)]
+pub struct PortfolioTransfer {
+    pub direction: TransferDirection,
+    pub row: TransactionHistoryRow,
+}
+
+/// Change reported by [`Portfolio::poll`].
+#[derive(Debug, Clone)]
+pub enum PortfolioEvent {
+    /// The combined amount of `token_owner`'s Circles changed.
+    TokenChanged {
+        token_owner: Address,
+        previous: U256,
+        current: U256,
+    },
+    /// The combined portfolio total changed.
+    TotalChanged { previous: U256, current: U256 },
+    /// A transfer newer than anything seen before touched a member.
+    Transfer(Box<PortfolioTransfer>),
+}
+
+/// Watch-only view over several avatars (for example an EOA, its Safe, and an organisation).
+///
+/// Balances are summed per token owner with wrappers folded in, histories are merged
+/// with member-to-member transfers reported once as [`TransferDirection::Internal`].
+/// [`Portfolio::poll`] diffs against the previous poll and reports combined changes.
+pub struct Portfolio {
+    rpc: Arc<CirclesRpc>,
+    members: Vec<Address>,
+    poll_interval: Duration,
+    history_limit: u32,
+    last_balances: Option<PortfolioBalances>,
+    /// `(block, transaction index, log index)` of the newest transfer reported.
+    last_seen: Option<(u64, u32, u32)>,
+}
+
+impl Portfolio {
+    pub fn new(rpc: Arc<CirclesRpc>, members: impl IntoIterator<Item = Address>) -> Self {
+        let mut portfolio = Self {
+            rpc,
+            members: Vec::new(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            history_limit: DEFAULT_HISTORY_LIMIT,
+            last_balances: None,
+            last_seen: None,
+        };
+        for member in members {
+            portfolio.watch(member);
+        }
+        portfolio
+    }
+
+    /// Interval between polls in [`Portfolio::spawn`] (default 30 seconds).
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Transfers fetched per member for [`Portfolio::history`] and each poll (default 50).
+    pub fn with_history_limit(mut self, history_limit: u32) -> Self {
+        self.history_limit = history_limit;
+        self
+    }
+
+    /// Add `member`; the next poll reports its balances as changes.
+    pub fn watch(&mut self, member: Address) {
+        if !self.members.contains(&member) {
+            self.members.push(member);
+        }
+    }
+
+    /// Remove `member`; the next poll reports its balances leaving the portfolio.
+    pub fn unwatch(&mut self, member: Address) {
+        self.members.retain(|watched| *watched != member);
+    }
+
+    pub fn members(&self) -> &[Address] {
+        &self.members
+    }
+
+    /// Current aggregated v2 balances of every member.
+    pub async fn balances(&self) -> Result<PortfolioBalances, SdkError> {
+        let mut balances = Vec::with_capacity(self.members.len());
+        for &member in &self.members {
+            let rows = self
+                .rpc
+                .token()
+                .get_token_balances(member, false, true)
+                .await?;
+            balances.push((member, rows));
+        }
+        Ok(PortfolioBalances::aggregate(balances, unix_now()))
+    }
+
+    /// The most recent transfers of all members, newest first.
+    pub async fn history(&self) -> Result<Vec<PortfolioTransfer>, SdkError> {
+        let mut pages = Vec::with_capacity(self.members.len());
+        for &member in &self.members {
+            let page = self
+                .rpc
+                .transaction()
+                .get_transaction_history_page(member, Some(self.history_limit), None, None, None)
+                .await?;
+            pages.push(page.results);
+        }
+        Ok(merge_histories(&self.members, pages))
+    }
+
+    /// Fetch balances and history once and report what changed since the previous poll.
+    ///
+    /// The first poll only records a baseline and returns no events.
+    pub async fn poll(&mut self) -> Result<Vec<PortfolioEvent>, SdkError> {
+        let balances = self.balances().await?;
+        let history = self.history().await?;
+        let newest = history.first().map(|t| position(&t.row));
+
+        let mut events = Vec::new();
+        if let Some(previous) = &self.last_balances {
+            events.extend(diff_balances(previous, &balances));
+            events.extend(
+                history
+                    .into_iter()
+                    .rev()
+                    .filter(|t| self.last_seen.is_none_or(|seen| position(&t.row) > seen))
+                    .map(|t| PortfolioEvent::Transfer(Box::new(t))),
+            );
+        }
+        self.last_balances = Some(balances);
+        self.last_seen = newest.max(self.last_seen);
+        Ok(events)
+    }
+
+    /// Poll in a background task, streaming every event.
+    ///
+    /// Poll errors are retried on the next tick. The task stops once the receiver is
+    /// dropped.
+    pub fn spawn(mut self) -> mpsc::UnboundedReceiver<PortfolioEvent> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            loop {
+                if let Ok(events) = self.poll().await {
+                    for event in events {
+                        if tx.send(event).is_err() {
+                            return;
+                        }
+                    }
+                }
+                if tx.is_closed() {
+                    return;
+                }
+                tokio::time::sleep(self.poll_interval).await;
+            }
+        });
+        rx
+    }
+}
+
+/// Demurraged atto-circles of a balance row; rows with only a time-circles float are skipped.
+fn demurraged_amount(row: &TokenBalanceResponse, as_of: u64) -> Option<U256> {
+    if let Some(amount) = row.atto_circles {
+        return Some(amount);
+    }
+    if row.is_wrapped
+        && row.is_inflationary
+        && let Some(amount) = row.static_atto_circles
+    {
+        return Some(atto_static_circles_to_atto_circles(amount, Some(as_of)));
+    }
+    match row.balance {
+        Balance::Raw(amount) if !(row.is_wrapped && row.is_inflationary) => Some(amount),
+        _ => None,
+    }
+}
+
+fn position(row: &TransactionHistoryRow) -> (u64, u32, u32) {
+    (row.block_number, row.transaction_index, row.log_index)
+}
+
+fn merge_histories(
+    members: &[Address],
+    pages: Vec<Vec<TransactionHistoryRow>>,
+) -> Vec<PortfolioTransfer> {
+    let mut seen = HashSet::new();
+    let mut merged: Vec<_> = pages
+        .into_iter()
+        .flatten()
+        .filter(|row| seen.insert((row.transaction_hash, row.log_index)))
+        .map(|row| {
+            let direction = match (members.contains(&row.from), members.contains(&row.to)) {
+                (true, true) => TransferDirection::Internal,
+                (true, false) => TransferDirection::Outgoing,
+                _ => TransferDirection::Incoming,
+            };
+            PortfolioTransfer { direction, row }
+        })
+        .collect();
+    merged.sort_by_key(|t| std::cmp::Reverse(position(&t.row)));
+    merged
+}
+
+fn diff_balances(previous: &PortfolioBalances, current: &PortfolioBalances) -> Vec<PortfolioEvent> {
+    let owners: std::collections::BTreeSet<Address> = previous
+        .tokens
+        .iter()
+        .chain(&current.tokens)
+        .map(|token| token.token_owner)
+        .collect();
+    let mut events: Vec<_> = owners
+        .into_iter()
+        .filter_map(|token_owner| {
+            let (before, after) = (
+                previous.token_total(token_owner),
+                current.token_total(token_owner),
+            );
+            (before != after).then_some(PortfolioEvent::TokenChanged {
+                token_owner,
+                previous: before,
+                current: after,
+            })
+        })
+        .collect();
+    if previous.total != current.total {
+        events.push(PortfolioEvent::TotalChanged {
+            previous: previous.total,
+            current: current.total,
+        });
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::TxHash;
+
+    const EOA: Address = Address::repeat_byte(0x01);
+    const SAFE: Address = Address::repeat_byte(0x02);
+    const ALICE: Address = Address::repeat_byte(0xaa);
+
+    fn row(
+        token_owner: Address,
+        amount: u64,
+        wrapped: bool,
+        inflationary: bool,
+    ) -> TokenBalanceResponse {
+        TokenBalanceResponse {
+            token_address: if wrapped {
+                Address::repeat_byte(0xee)
+            } else {
+                token_owner
+            },
+            token_id: token_owner,
+            balance: Balance::Raw(U256::from(amount)),
+            static_atto_circles: inflationary.then(|| U256::from(amount)),
+            static_circles: None,
+            token_type: None,
+            version: Some(2),
+            atto_circles: (!inflationary).then(|| U256::from(amount)),
+            circles: None,
+            atto_crc: None,
+            crc: None,
+            is_erc20: wrapped,
+            is_erc1155: !wrapped,
+            is_wrapped: wrapped,
+            is_inflationary: inflationary,
+            is_group: false,
+            token_owner,
+        }
+    }
+
+    fn transfer(from: Address, to: Address, block: u64, log_index: u32) -> TransactionHistoryRow {
+        TransactionHistoryRow {
+            block_number: block,
+            timestamp: block,
+            transaction_index: 0,
+            log_index,
+            transaction_hash: TxHash::repeat_byte(block as u8),
+            version: 2,
+            operator: None,
+            from,
+            to,
+            id: None,
+            token_address: ALICE,
+            value: "1".into(),
+            circles: None,
+            atto_circles: None,
+            static_circles: None,
+            static_atto_circles: None,
+            crc: None,
+            atto_crc: None,
+        }
+    }
+
+    #[test]
+    fn aggregate_folds_members_and_wrappers_per_token_owner() {
+        let balances = PortfolioBalances::aggregate(
+            [
+                (
+                    EOA,
+                    vec![row(ALICE, 10, false, false), row(EOA, 5, false, false)],
+                ),
+                (SAFE, vec![row(ALICE, 7, true, false)]),
+            ],
+            0,
+        );
+        assert_eq!(balances.tokens.len(), 2);
+        assert_eq!(balances.token_total(ALICE), U256::from(17));
+        assert_eq!(balances.token_total(EOA), U256::from(5));
+        assert_eq!(balances.total, U256::from(22));
+        assert_eq!(balances.token_total(SAFE), U256::ZERO);
+    }
+
+    #[test]
+    fn static_wrappers_are_converted_to_demurraged_units() {
+        let as_of = 1_800_000_000;
+        let balances =
+            PortfolioBalances::aggregate([(SAFE, vec![row(ALICE, 1_000_000, true, true)])], as_of);
+        assert_eq!(
+            balances.token_total(ALICE),
+            atto_static_circles_to_atto_circles(U256::from(1_000_000), Some(as_of))
+        );
+        assert!(balances.total < U256::from(1_000_000));
+    }
+
+    #[test]
+    fn merged_history_dedupes_internal_transfers() {
+        let internal = transfer(EOA, SAFE, 3, 0);
+        let merged = merge_histories(
+            &[EOA, SAFE],
+            vec![
+                vec![internal.clone(), transfer(EOA, ALICE, 1, 0)],
+                vec![internal, transfer(ALICE, SAFE, 2, 0)],
+            ],
+        );
+        let summary: Vec<_> = merged
+            .iter()
+            .map(|t| (t.row.block_number, t.direction))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (3, TransferDirection::Internal),
+                (2, TransferDirection::Incoming),
+                (1, TransferDirection::Outgoing),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_reports_token_and_total_changes() {
+        let before = PortfolioBalances::aggregate([(EOA, vec![row(ALICE, 10, false, false)])], 0);
+        let after = PortfolioBalances::aggregate([(EOA, vec![row(EOA, 4, false, false)])], 0);
+        let events = diff_balances(&before, &after);
+        assert!(matches!(
+            events.as_slice(),
+            [
+                PortfolioEvent::TokenChanged { token_owner: a, current: c1, .. },
+                PortfolioEvent::TokenChanged { token_owner: b, previous: p2, .. },
+                PortfolioEvent::TotalChanged { .. },
+            ] if *a == EOA && *c1 == U256::from(4) && *b == ALICE && *p2 == U256::from(10)
+        ));
+        assert!(diff_balances(&after, &after).is_empty());
+    }
+}
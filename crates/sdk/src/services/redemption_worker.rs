@@ -0,0 +1,199 @@
+use crate::services::subscriptions::Subscriptions;
+use crate::{SdkError, SubmittedTx};
+use alloy_primitives::{B256, U256};
+use circles_utils::time::unix_now;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// What happened to one subscription during a [`RedemptionWorker`] tick.
+#[derive(Debug)]
+pub enum RedemptionOutcome {
+    /// A `redeem` transaction was submitted.
+    Redeemed {
+        periods: u64,
+        amount: U256,
+        txs: Vec<SubmittedTx>,
+    },
+    /// No period has elapsed yet.
+    NotDue { next_redeem_at: u64 },
+    /// Reading, routing, or submitting failed; the subscription is retried next tick.
+    Failed(SdkError),
+}
+
+/// Per-subscription result of a [`RedemptionWorker`] tick.
+#[derive(Debug)]
+pub struct RedemptionReport {
+    pub id: B256,
+    pub outcome: RedemptionOutcome,
+}
+
+/// Redeems a fixed set of subscriptions whenever they fall due.
+///
+/// Each tick re-reads every subscription, builds a redeem plan with a fresh path for the
+/// due ones, and submits it through the [`Subscriptions`] runner. Drive it manually with
+/// [`RedemptionWorker::tick`] or in the background with [`RedemptionWorker::spawn`].
+pub struct RedemptionWorker {
+    subscriptions: Subscriptions,
+    ids: Vec<B256>,
+    poll_interval: Duration,
+}
+
+impl RedemptionWorker {
+    pub fn new(subscriptions: Subscriptions, ids: impl IntoIterator<Item = B256>) -> Self {
+        let mut worker = Self {
+            subscriptions,
+            ids: Vec::new(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        };
+        for id in ids {
+            worker.watch(id);
+        }
+        worker
+    }
+
+    /// Upper bound between ticks (default one minute).
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Start redeeming `id` from the next tick on.
+    pub fn watch(&mut self, id: B256) {
+        if !self.ids.contains(&id) {
+            self.ids.push(id);
+        }
+    }
+
+    /// Stop redeeming `id`.
+    pub fn unwatch(&mut self, id: B256) {
+        self.ids.retain(|watched| *watched != id);
+    }
+
+    /// Subscription ids currently handled.
+    pub fn ids(&self) -> &[B256] {
+        &self.ids
+    }
+
+    /// Check every subscription once and redeem the due ones, in watch order.
+    pub async fn tick(&self) -> Vec<RedemptionReport> {
+        let mut reports = Vec::with_capacity(self.ids.len());
+        for &id in &self.ids {
+            reports.push(RedemptionReport {
+                id,
+                outcome: self.redeem_if_due(id).await,
+            });
+        }
+        reports
+    }
+
+    async fn redeem_if_due(&self, id: B256) -> RedemptionOutcome {
+        let subscription = match self.subscriptions.get(id).await {
+            Ok(subscription) => subscription,
+            Err(err) => return RedemptionOutcome::Failed(err),
+        };
+        let now = unix_now();
+        if subscription.redeemable_periods(now) == 0 {
+            return RedemptionOutcome::NotDue {
+                next_redeem_at: subscription.next_redeem_at(),
+            };
+        }
+
+        let result = async {
+            let plan = self.subscriptions.plan_redeem_at(subscription, now).await?;
+            let txs = self.subscriptions.send(vec![plan.tx]).await?;
+            Ok::<_, SdkError>(RedemptionOutcome::Redeemed {
+                periods: plan.periods,
+                amount: plan.amount,
+                txs,
+            })
+        };
+        result.await.unwrap_or_else(RedemptionOutcome::Failed)
+    }
+
+    /// Tick in a background task, streaming every report.
+    ///
+    /// Sleeps until the earliest pending `next_redeem_at`, capped by the poll interval.
+    /// The task stops as soon as the receiver is dropped; with no watched ids it never
+    /// starts, so the receiver yields `None` right away.
+    pub fn spawn(self) -> mpsc::UnboundedReceiver<RedemptionReport> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        if self.ids.is_empty() {
+            return rx;
+        }
+        tokio::spawn(async move {
+            loop {
+                let reports = self.tick().await;
+                let wait = next_wake(&reports, unix_now(), self.poll_interval);
+                for report in reports {
+                    if tx.send(report).is_err() {
+                        return;
+                    }
+                }
+                // Wake early only to notice that the receiver went away.
+                if tokio::time::timeout(wait, tx.closed()).await.is_ok() {
+                    return;
+                }
+            }
+        });
+        rx
+    }
+}
+
+/// Time until the earliest not-yet-due subscription, capped by `poll_interval`.
+fn next_wake(reports: &[RedemptionReport], now: u64, poll_interval: Duration) -> Duration {
+    reports
+        .iter()
+        .filter_map(|report| match report.outcome {
+            RedemptionOutcome::NotDue { next_redeem_at } => {
+                Some(Duration::from_secs(next_redeem_at.saturating_sub(now)))
+            }
+            _ => None,
+        })
+        .min()
+        .map_or(poll_interval, |due| due.min(poll_interval))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Address;
+
+    fn not_due(next_redeem_at: u64) -> RedemptionReport {
+        RedemptionReport {
+            id: B256::ZERO,
+            outcome: RedemptionOutcome::NotDue { next_redeem_at },
+        }
+    }
+
+    #[test]
+    fn next_wake_targets_earliest_due_subscription() {
+        let interval = Duration::from_secs(60);
+        assert_eq!(next_wake(&[], 1_000, interval), interval);
+        assert_eq!(
+            next_wake(&[not_due(1_500), not_due(1_020)], 1_000, interval),
+            Duration::from_secs(20)
+        );
+        assert_eq!(next_wake(&[not_due(5_000)], 1_000, interval), interval);
+        assert_eq!(
+            next_wake(
+                &[RedemptionReport {
+                    id: B256::ZERO,
+                    outcome: RedemptionOutcome::Failed(SdkError::MissingRunner),
+                }],
+                1_000,
+                interval
+            ),
+            interval
+        );
+    }
+
+    #[tokio::test]
+    async fn spawn_without_ids_closes_the_stream() {
+        let sdk = crate::Sdk::new(crate::config::gnosis_mainnet(), None).expect("sdk");
+        let worker = RedemptionWorker::new(sdk.subscriptions(Address::ZERO), []);
+        let mut reports = worker.spawn();
+        assert!(reports.recv().await.is_none());
+    }
+}
@@ -1,7 +1,12 @@
 use crate::core::Core;
-use alloy_primitives::{Address, keccak256};
+use alloy_primitives::{Address, B256, Bytes, keccak256};
 use circles_abis::ReferralsModule;
-use k256::{SecretKey, elliptic_curve::rand_core::OsRng, elliptic_curve::sec1::ToEncodedPoint};
+use circles_profiles::reqwest_error_kind;
+use circles_types::ErrorKind;
+use k256::{
+    SecretKey, ecdsa::SigningKey, elliptic_curve::rand_core::OsRng,
+    elliptic_curve::sec1::ToEncodedPoint,
+};
 use reqwest::{Client, StatusCode, Url};
 use serde::{Deserialize, Serialize};
 use std::future::Future;
@@ -51,6 +56,47 @@ pub enum ReferralsError {
     Contract(String),
 }
 
+impl ReferralsError {
+    /// Coarse classification for retry and UX policies.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::InvalidUrl { .. } | Self::CannotBeABase { .. } | Self::AuthRequired => {
+                ErrorKind::Configuration
+            }
+            Self::InvalidPrivateKey(_) => ErrorKind::InvalidInput,
+            Self::Http(err) => reqwest_error_kind(err),
+            Self::RetrieveFailed {
+                http_status: Some(status),
+                ..
+            } => ErrorKind::from_http_status(status.as_u16()),
+            Self::SessionFailed { code, .. } => match code {
+                SessionErrorCode::ValidationError | SessionErrorCode::Conflict => {
+                    ErrorKind::InvalidInput
+                }
+                SessionErrorCode::NotFound => ErrorKind::NotFound,
+                SessionErrorCode::ServerError => ErrorKind::Network,
+            },
+            Self::DispenseFailed {
+                code, http_status, ..
+            } => match code {
+                DispenseErrorCode::SessionNotFound => ErrorKind::NotFound,
+                DispenseErrorCode::RateLimited => ErrorKind::RateLimited,
+                DispenseErrorCode::PoolEmpty
+                | DispenseErrorCode::SessionExpired
+                | DispenseErrorCode::QuotaExhausted
+                | DispenseErrorCode::SessionPaused => ErrorKind::Rejected,
+                DispenseErrorCode::Unknown => ErrorKind::from_http_status(http_status.as_u16()),
+            },
+            Self::DecodeFailed { .. } => ErrorKind::Protocol,
+            Self::StoreFailed(_)
+            | Self::StoreBatchFailed(_)
+            | Self::RetrieveFailed { .. }
+            | Self::ListFailed(_)
+            | Self::Contract(_) => ErrorKind::Other,
+        }
+    }
+}
+
 /// Referral status lifecycle exposed by the referrals backend.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -727,23 +773,62 @@ impl Distributions {
     }
 }
 
+/// Fresh referral secret: a random secp256k1 private key as `0x`-prefixed hex.
 pub fn generate_private_key() -> String {
     let secret = SecretKey::random(&mut OsRng);
     format!("0x{}", hex::encode(secret.to_bytes()))
 }
 
+/// Ethereum address of a referral secret (keccak of the uncompressed public key).
 pub fn private_key_to_address(private_key: &str) -> Result<Address, ReferralsError> {
-    let clean_key = private_key.strip_prefix("0x").unwrap_or(private_key);
-    let key_bytes =
-        hex::decode(clean_key).map_err(|err| ReferralsError::InvalidPrivateKey(err.to_string()))?;
-    let secret = SecretKey::from_slice(&key_bytes)
-        .map_err(|err| ReferralsError::InvalidPrivateKey(err.to_string()))?;
+    let secret = parse_private_key(private_key)?;
     let public_key = secret.public_key();
     let encoded = public_key.to_encoded_point(false);
     let hash = keccak256(&encoded.as_bytes()[1..]);
     Ok(Address::from_slice(&hash[12..]))
 }
 
+/// Sign a 32-byte digest (no EIP-191 prefix) with a referral secret.
+///
+/// Returns the 65-byte `r || s || v` signature with `v` in `{27, 28}`, as `ecrecover`
+/// expects.
+pub fn sign_digest(private_key: &str, digest: B256) -> Result<Bytes, ReferralsError> {
+    let signing_key = SigningKey::from(parse_private_key(private_key)?);
+    let (signature, recovery_id) = signing_key
+        .sign_prehash_recoverable(digest.as_slice())
+        .map_err(|err| ReferralsError::InvalidPrivateKey(err.to_string()))?;
+    let mut bytes = signature.to_bytes().to_vec();
+    bytes.push(27 + recovery_id.to_byte());
+    Ok(Bytes::from(bytes))
+}
+
+/// Encode a referral secret as a 24-word BIP-39 phrase (the key is the entropy).
+#[cfg(feature = "mnemonic")]
+pub fn private_key_to_mnemonic(private_key: &str) -> Result<String, ReferralsError> {
+    let secret = parse_private_key(private_key)?;
+    bip39::Mnemonic::from_entropy(&secret.to_bytes())
+        .map(|mnemonic| mnemonic.to_string())
+        .map_err(|err| ReferralsError::InvalidPrivateKey(err.to_string()))
+}
+
+/// Decode a phrase produced by [`private_key_to_mnemonic`] back into the secret.
+#[cfg(feature = "mnemonic")]
+pub fn mnemonic_to_private_key(phrase: &str) -> Result<String, ReferralsError> {
+    let mnemonic = bip39::Mnemonic::parse(phrase)
+        .map_err(|err| ReferralsError::InvalidPrivateKey(err.to_string()))?;
+    let private_key = format!("0x{}", hex::encode(mnemonic.to_entropy()));
+    parse_private_key(&private_key)?;
+    Ok(private_key)
+}
+
+fn parse_private_key(private_key: &str) -> Result<SecretKey, ReferralsError> {
+    let clean_key = private_key.strip_prefix("0x").unwrap_or(private_key);
+    let key_bytes =
+        hex::decode(clean_key).map_err(|err| ReferralsError::InvalidPrivateKey(err.to_string()))?;
+    SecretKey::from_slice(&key_bytes)
+        .map_err(|err| ReferralsError::InvalidPrivateKey(err.to_string()))
+}
+
 #[derive(Debug, Serialize)]
 struct StoreBatchRequest<'a> {
     invitations: &'a [ReferralStoreInput],
@@ -1037,12 +1122,12 @@ mod tests {
     use super::{
         DispenseErrorCode, DistributionSessionListOptions, ReferralInfo, ReferralListMineOptions,
         ReferralPublicListOptions, Referrals, ReferralsError, SessionErrorCode,
-        SessionKeyListOptions, api_reason, dispense_error_code, normalize_base_url,
-        private_key_to_address, referral_not_found_info, session_collection_url,
-        session_error_code, session_keys_url,
+        SessionKeyListOptions, api_reason, dispense_error_code, generate_private_key,
+        normalize_base_url, private_key_to_address, referral_not_found_info,
+        session_collection_url, session_error_code, session_keys_url, sign_digest,
     };
     use crate::{config, core::Core};
-    use alloy_primitives::{Address, address};
+    use alloy_primitives::{Address, address, keccak256};
     use reqwest::Client;
     use reqwest::StatusCode;
     use std::sync::Arc;
@@ -1078,6 +1163,38 @@ mod tests {
         assert_eq!(signer, address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266"));
     }
 
+    #[test]
+    fn sign_digest_recovers_to_secret_address() {
+        use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+        let secret = generate_private_key();
+        let digest = keccak256(b"passkey");
+        let signature = sign_digest(&secret, digest).unwrap();
+        assert_eq!(signature.len(), 65);
+        assert!(matches!(signature[64], 27 | 28));
+
+        let recovered = VerifyingKey::recover_from_prehash(
+            digest.as_slice(),
+            &Signature::from_slice(&signature[..64]).unwrap(),
+            RecoveryId::from_byte(signature[64] - 27).unwrap(),
+        )
+        .unwrap();
+        let encoded = recovered.to_encoded_point(false);
+        let recovered_address = Address::from_slice(&keccak256(&encoded.as_bytes()[1..])[12..]);
+        assert_eq!(recovered_address, private_key_to_address(&secret).unwrap());
+    }
+
+    #[cfg(feature = "mnemonic")]
+    #[test]
+    fn mnemonic_round_trips_secret() {
+        use super::{mnemonic_to_private_key, private_key_to_mnemonic};
+
+        let secret = generate_private_key();
+        let phrase = private_key_to_mnemonic(&secret).unwrap();
+        assert_eq!(phrase.split_whitespace().count(), 24);
+        assert_eq!(mnemonic_to_private_key(&phrase).unwrap(), secret);
+    }
+
     #[test]
     fn referral_not_found_info_sets_ts_style_error() {
         let signer = Address::repeat_byte(0x11);
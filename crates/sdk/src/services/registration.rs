@@ -1,10 +1,16 @@
 use crate::avatar::{BaseGroupAvatar, HumanAvatar, OrganisationAvatar};
-use crate::cid_v0_to_digest::cid_v0_to_digest;
-use crate::{RegistrationResult, Sdk, SdkError, call_to_tx};
-use alloy_primitives::{Address, U256};
-use circles_abis::{BaseGroupFactory, HubV2};
-use circles_profiles::Profile;
+use crate::cid::cid_to_digest;
+use crate::core::MulticallBatch;
+use crate::indexer::await_submitted;
+use crate::{ContractRunner, Core, RegistrationResult, Sdk, SdkError, SubmittedTx, call_to_tx};
+use alloy_primitives::{Address, B256, U256, aliases::U96};
+use alloy_provider::Provider;
+use circles_abis::{BaseGroup, BaseGroupFactory, HubV2};
+use circles_profiles::{Profile, Profiles};
+use circles_rpc::CirclesRpc;
 use circles_types::AvatarInfo;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// TS-style registration profile input: either a full profile to pin or an existing CID.
 #[derive(Debug, Clone, Copy)]
@@ -59,7 +65,7 @@ impl<'a> Registration<'a> {
         P: Into<RegistrationProfileInput<'p>>,
     {
         let (name, cid) = resolve_organization_name_and_cid(self.sdk, profile.into()).await?;
-        submit_organisation_registration(self.sdk, name, cid).await
+        submit_organisation_registration(self.sdk, name, cid, &[]).await
     }
 
     /// Register a base group using either a profile to pin or an existing profile CID.
@@ -86,6 +92,7 @@ impl<'a> Registration<'a> {
             name,
             symbol,
             profile.into(),
+            &[],
         )
         .await
     }
@@ -136,13 +143,25 @@ async fn submit_human_registration(
     let runner = sdk.runner.clone().ok_or(SdkError::MissingRunner)?;
     let sender = sdk.sender_address.ok_or(SdkError::MissingSender)?;
 
-    let inviters = sdk
+    // Escrowed inviters and the inviter's own balance are fetched in one multicall.
+    let mut batch = MulticallBatch::new();
+    let inviters_call = batch.add_allow_failure(
+        sdk.config.invitation_escrow_address,
+        circles_abis::InvitationEscrow::getInvitersCall { invitee: sender },
+    );
+    let balance_call = batch.add_allow_failure(
+        sdk.config.v2_hub_address,
+        HubV2::balanceOfCall {
+            _account: inviter,
+            _id: U256::from_be_slice(inviter.as_slice()),
+        },
+    );
+    let results = sdk
         .core
-        .invitation_escrow()
-        .getInviters(sender)
-        .call()
+        .multicall(batch)
         .await
-        .unwrap_or_default();
+        .map_err(|e| SdkError::InvalidRegistration(e.to_string()))?;
+    let inviters = results.get(inviters_call).unwrap_or_default();
 
     let mut txs = Vec::new();
     if let Some(first_inviter) = inviters.first() {
@@ -155,19 +174,8 @@ async fn submit_human_registration(
             None,
         ));
     } else {
-        let token_id = sdk
-            .core
-            .hub_v2()
-            .toTokenId(inviter)
-            .call()
-            .await
-            .map_err(|e| SdkError::InvalidRegistration(e.to_string()))?;
-        let balance = sdk
-            .core
-            .hub_v2()
-            .balanceOf(inviter, token_id)
-            .call()
-            .await
+        let balance = results
+            .get(balance_call)
             .map_err(|e| SdkError::InvalidRegistration(e.to_string()))?;
         let min_required = U256::from(96u128) * U256::from(10).pow(U256::from(18));
         if balance < min_required {
@@ -177,32 +185,23 @@ async fn submit_human_registration(
         }
     }
 
-    let digest = cid_v0_to_digest(cid)?;
+    let digest = cid_to_digest(cid)?;
     let call = HubV2::registerHumanCall {
         _inviter: inviter,
         _metadataDigest: digest,
     };
     txs.push(call_to_tx(sdk.config.v2_hub_address, call, None));
     let sent = runner.send_transactions(txs).await?;
-    let info = sdk.rpc.avatar().get_avatar_info(sender).await?;
-    let avatar = HumanAvatar::new(
-        sender,
-        info,
-        sdk.core.clone(),
-        sdk.profiles.clone(),
-        sdk.rpc.clone(),
-        sdk.runner.clone(),
-    );
-    Ok(RegistrationResult {
-        avatar: Some(avatar),
-        txs: sent,
-    })
+    let indexed = await_submitted(sdk, &sent).await.is_ok();
+    let pending = PendingAvatar::new(sdk, sender, HumanAvatar::new);
+    Ok(registration_result(sent, Some(pending), indexed).await)
 }
 
 async fn submit_organisation_registration(
     sdk: &Sdk,
     name: String,
     cid: String,
+    initial_trust: &[Address],
 ) -> Result<RegistrationResult<OrganisationAvatar>, SdkError> {
     if name.is_empty() {
         return Err(SdkError::InvalidRegistration(
@@ -212,26 +211,24 @@ async fn submit_organisation_registration(
 
     let runner = sdk.runner.clone().ok_or(SdkError::MissingRunner)?;
     let sender = sdk.sender_address.ok_or(SdkError::MissingSender)?;
-    let digest = cid_v0_to_digest(&cid)?;
+    let digest = cid_to_digest(&cid)?;
     let call = HubV2::registerOrganizationCall {
         _name: name,
         _metadataDigest: digest,
     };
-    let txs = vec![call_to_tx(sdk.config.v2_hub_address, call, None)];
+    // Trust goes out in the same submission, right after the registration it depends on.
+    let mut txs = vec![call_to_tx(sdk.config.v2_hub_address, call, None)];
+    txs.extend(initial_trust.iter().map(|avatar| {
+        let call = HubV2::trustCall {
+            _trustReceiver: *avatar,
+            _expiry: U96::MAX,
+        };
+        call_to_tx(sdk.config.v2_hub_address, call, None)
+    }));
     let sent = runner.send_transactions(txs).await?;
-    let info = sdk.rpc.avatar().get_avatar_info(sender).await?;
-    let avatar = OrganisationAvatar::new(
-        sender,
-        info,
-        sdk.core.clone(),
-        sdk.profiles.clone(),
-        sdk.rpc.clone(),
-        sdk.runner.clone(),
-    );
-    Ok(RegistrationResult {
-        avatar: Some(avatar),
-        txs: sent,
-    })
+    let indexed = await_submitted(sdk, &sent).await.is_ok();
+    let pending = PendingAvatar::new(sdk, sender, OrganisationAvatar::new);
+    Ok(registration_result(sent, Some(pending), indexed).await)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -244,6 +241,7 @@ async fn submit_group_registration(
     name: &str,
     symbol: &str,
     cid: &str,
+    initial_trust: &[Address],
 ) -> Result<RegistrationResult<BaseGroupAvatar>, SdkError> {
     if name.is_empty() || name.len() > 19 {
         return Err(SdkError::InvalidRegistration(
@@ -257,7 +255,7 @@ async fn submit_group_registration(
     }
 
     let runner = sdk.runner.clone().ok_or(SdkError::MissingRunner)?;
-    let digest = cid_v0_to_digest(cid)?;
+    let digest = cid_to_digest(cid)?;
     let call = BaseGroupFactory::createBaseGroupCall {
         _owner: owner,
         _service: service,
@@ -272,38 +270,237 @@ async fn submit_group_registration(
         call,
         None,
     )];
-    let predicted = sdk
-        .core
-        .base_group_factory()
-        .createBaseGroup(
-            owner,
-            service,
-            fee_collection,
-            initial_conditions.to_vec(),
-            name.to_string(),
-            symbol.to_string(),
-            digest,
-        )
-        .call()
-        .await
-        .ok();
+    let mut sent = runner.send_transactions(txs).await?;
+    // Waits for inclusion, which reading the group from the receipt needs as well.
+    let indexed = await_submitted(sdk, &sent).await.is_ok();
+    let mut pending = sent
+        .last()
+        .and_then(SubmittedTx::hash)
+        .map(|tx_hash| PendingAvatar::created_by(sdk, tx_hash, owner, BaseGroupAvatar::new));
+    // Best-effort: while the creation is not mined (or its receipt cannot be read yet) the
+    // address stays unknown and `resolve_avatar` locates it later.
+    if indexed && let Some(pending) = pending.as_mut() {
+        let _ = pending.locate().await;
+    }
 
-    let sent = runner.send_transactions(txs).await?;
-    let avatar = if let Some(predicted) = predicted {
-        let info: AvatarInfo = sdk.rpc.avatar().get_avatar_info(predicted.group).await?;
-        Some(BaseGroupAvatar::new(
-            predicted.group,
-            info,
+    // Group trust targets the group contract, so it can only follow once the group is found.
+    // The group is registered either way, so a trust step that cannot run or fails is
+    // reported next to the result rather than instead of it.
+    let mut trust_error = None;
+    if !initial_trust.is_empty() {
+        match pending.as_ref().and_then(|pending| pending.address) {
+            Some(group) => {
+                let call = BaseGroup::trustBatchWithConditionsCall {
+                    _members: initial_trust.to_vec(),
+                    _expiry: U96::MAX,
+                };
+                match runner
+                    .send_transactions(vec![call_to_tx(group, call, None)])
+                    .await
+                {
+                    Ok(trust_sent) => sent.extend(trust_sent),
+                    Err(err) => trust_error = Some(err.into()),
+                }
+            }
+            None => {
+                trust_error = Some(SdkError::OperationFailed(
+                    "group address is not known yet; initial trust was not sent".to_string(),
+                ));
+            }
+        }
+    }
+    let mut result = registration_result(sent, pending, indexed).await;
+    result.trust_error = trust_error;
+    Ok(result)
+}
+
+/// Registered address plus what is needed to build its typed avatar once indexed.
+pub(crate) struct PendingAvatar<T> {
+    address: Option<Address>,
+    /// `createBaseGroup` transaction and group owner, to read the address from the receipt
+    /// while it is still unknown.
+    created_by: Option<(B256, Address)>,
+    core: Arc<Core>,
+    rpc: Arc<CirclesRpc>,
+    build: Box<dyn Fn(Address, AvatarInfo) -> T + Send + Sync>,
+}
+
+type AvatarConstructor<T> = fn(
+    Address,
+    AvatarInfo,
+    Arc<Core>,
+    Profiles,
+    Arc<CirclesRpc>,
+    Option<Arc<dyn ContractRunner>>,
+) -> T;
+
+impl<T: 'static> PendingAvatar<T> {
+    fn new(sdk: &Sdk, address: Address, new: AvatarConstructor<T>) -> Self {
+        let (core, profiles, rpc, runner) = (
             sdk.core.clone(),
             sdk.profiles.clone(),
             sdk.rpc.clone(),
             sdk.runner.clone(),
-        ))
-    } else {
-        None
+        );
+        Self {
+            address: Some(address),
+            created_by: None,
+            core: sdk.core.clone(),
+            rpc: sdk.rpc.clone(),
+            build: Box::new(move |address, info| {
+                new(
+                    address,
+                    info,
+                    core.clone(),
+                    profiles.clone(),
+                    rpc.clone(),
+                    runner.clone(),
+                )
+            }),
+        }
+    }
+
+    /// Avatar of the group that `tx_hash` creates for `owner`, located once it is mined.
+    fn created_by(sdk: &Sdk, tx_hash: B256, owner: Address, new: AvatarConstructor<T>) -> Self {
+        Self {
+            address: None,
+            created_by: Some((tx_hash, owner)),
+            ..Self::new(sdk, Address::ZERO, new)
+        }
+    }
+}
+
+impl<T> PendingAvatar<T> {
+    /// The registered address, reading it from the creation receipt when not yet known.
+    async fn locate(&mut self) -> Result<Option<Address>, SdkError> {
+        if self.address.is_none()
+            && let Some((tx_hash, owner)) = self.created_by
+        {
+            self.address = created_group(&self.core, owner, tx_hash).await?;
+        }
+        Ok(self.address)
+    }
+}
+
+impl<T> RegistrationResult<T> {
+    /// Address that was registered, when known (a new group's address is only known once
+    /// its creation is mined; [`resolve_avatar`](Self::resolve_avatar) looks it up).
+    pub fn registered_address(&self) -> Option<Address> {
+        self.pending.as_ref().and_then(|pending| pending.address)
+    }
+
+    /// The typed avatar, looking it up again if the indexer had not caught up on return.
+    ///
+    /// Tries `circles_getAvatarInfo` up to `attempts` times, sleeping `delay` in between, and
+    /// keeps the result in [`avatar`](Self::avatar). A group whose creation was not mined on
+    /// return is located from its receipt first. Fails with [`SdkError::AvatarNotFound`]
+    /// when the avatar is still unknown after the last attempt.
+    pub async fn resolve_avatar(&mut self, attempts: u32, delay: Duration) -> Result<&T, SdkError> {
+        if self.avatar.is_none() {
+            let pending = self.pending.as_mut().ok_or_else(|| {
+                SdkError::OperationFailed("registered avatar address is unknown".to_string())
+            })?;
+            let mut attempt = 1;
+            let (address, info) = loop {
+                if let Some(address) = pending.locate().await?
+                    && let Some(info) = pending.rpc.avatar().find_avatar_info(address).await?
+                {
+                    break (address, info);
+                }
+                if attempt >= attempts {
+                    return Err(match (pending.address, pending.created_by) {
+                        (Some(address), _) => SdkError::AvatarNotFound(address),
+                        (None, Some((tx_hash, _))) => SdkError::OperationFailed(format!(
+                            "group creation {tx_hash} is not mined yet"
+                        )),
+                        (None, None) => SdkError::OperationFailed(
+                            "registered avatar address is unknown".to_string(),
+                        ),
+                    });
+                }
+                attempt += 1;
+                tokio::time::sleep(delay).await;
+            };
+            let avatar = (pending.build)(address, info);
+            self.avatar = Some(avatar);
+        }
+        Ok(self.avatar.as_ref().expect("avatar was resolved above"))
+    }
+}
+
+/// Result for submitted registration txs, with the avatar looked up once if `indexed`.
+///
+/// Everything after submission is best-effort: the transactions are already out, so an
+/// indexer that lags or errors leaves `avatar` unset for
+/// [`RegistrationResult::resolve_avatar`] instead of failing the registration.
+async fn registration_result<T>(
+    sent: Vec<SubmittedTx>,
+    pending: Option<PendingAvatar<T>>,
+    indexed: bool,
+) -> RegistrationResult<T> {
+    let mut result = RegistrationResult {
+        avatar: None,
+        txs: sent,
+        trust_error: None,
+        pending,
     };
+    if indexed {
+        let _ = result.resolve_avatar(1, Duration::ZERO).await;
+    }
+    result
+}
+
+/// Address of the contract `factory` deploys with `CREATE` at `factory_nonce`.
+///
+/// Computed offline; pair it with the factory's nonce from just before `createBaseGroup`
+/// to know the group address without simulating the call. `createBaseGroup` takes no salt,
+/// so the nonce is the only input: caller-chosen or vanity group addresses are not possible
+/// with this factory.
+pub fn predict_base_group_address(factory: Address, factory_nonce: u64) -> Address {
+    factory.create(factory_nonce)
+}
+
+/// Address the next `createBaseGroup` call deploys to, from the factory's current nonce.
+pub(crate) async fn predict_next_group_address(sdk: &Sdk) -> Result<Address, SdkError> {
+    let factory = sdk.config.base_group_factory_address;
+    let nonce = sdk
+        .core
+        .provider()
+        .get_transaction_count(factory)
+        .await
+        .map_err(|e| SdkError::Contract(e.to_string()))?;
+    Ok(predict_base_group_address(factory, nonce))
+}
 
-    Ok(RegistrationResult { avatar, txs: sent })
+/// Group deployed for `owner` by the `createBaseGroup` transaction `tx_hash`, read from the
+/// factory's `BaseGroupCreated` log; `None` while the transaction is not mined.
+async fn created_group(
+    core: &Core,
+    owner: Address,
+    tx_hash: B256,
+) -> Result<Option<Address>, SdkError> {
+    let Some(receipt) = core
+        .provider()
+        .get_transaction_receipt(tx_hash)
+        .await
+        .map_err(|e| SdkError::Contract(e.to_string()))?
+    else {
+        return Ok(None);
+    };
+    let factory = core.config.base_group_factory_address;
+    receipt
+        .logs()
+        .iter()
+        .filter(|log| log.address() == factory)
+        .filter_map(|log| log.log_decode::<BaseGroupFactory::BaseGroupCreated>().ok())
+        .map(|log| log.inner.data)
+        .find(|created| created.owner == owner)
+        .map(|created| Some(created.group))
+        .ok_or_else(|| {
+            SdkError::OperationFailed(format!(
+                "transaction {tx_hash} did not create a base group for {owner}"
+            ))
+        })
 }
 
 async fn register_human_with_profile_input(
@@ -325,6 +522,7 @@ async fn register_group_with_profile_input(
     name: &str,
     symbol: &str,
     profile: RegistrationProfileInput<'_>,
+    initial_trust: &[Address],
 ) -> Result<RegistrationResult<BaseGroupAvatar>, SdkError> {
     let cid = resolve_profile_cid(sdk, profile).await?;
     submit_group_registration(
@@ -336,6 +534,7 @@ async fn register_group_with_profile_input(
         name,
         symbol,
         &cid,
+        initial_trust,
     )
     .await
 }
@@ -354,9 +553,19 @@ pub async fn register_organisation(
     sdk: &Sdk,
     name: &str,
     profile: &Profile,
+) -> Result<RegistrationResult<OrganisationAvatar>, SdkError> {
+    register_organisation_with_trust(sdk, name, profile, &[]).await
+}
+
+/// Register an organisation avatar that trusts `initial_trust` in the same submission.
+pub async fn register_organisation_with_trust(
+    sdk: &Sdk,
+    name: &str,
+    profile: &Profile,
+    initial_trust: &[Address],
 ) -> Result<RegistrationResult<OrganisationAvatar>, SdkError> {
     let cid = resolve_profile_cid(sdk, profile.into()).await?;
-    submit_organisation_registration(sdk, name.to_string(), cid).await
+    submit_organisation_registration(sdk, name.to_string(), cid, initial_trust).await
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -370,6 +579,37 @@ pub async fn register_group(
     name: &str,
     symbol: &str,
     profile: &Profile,
+) -> Result<RegistrationResult<BaseGroupAvatar>, SdkError> {
+    register_group_with_trust(
+        sdk,
+        owner,
+        service,
+        fee_collection,
+        initial_conditions,
+        name,
+        symbol,
+        profile,
+        &[],
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+/// Register a base group via the factory (requires runner), then trust `initial_trust` as
+/// members.
+///
+/// A trust step that fails after the group was created is reported in
+/// [`RegistrationResult::trust_error`].
+pub async fn register_group_with_trust(
+    sdk: &Sdk,
+    owner: Address,
+    service: Address,
+    fee_collection: Address,
+    initial_conditions: &[Address],
+    name: &str,
+    symbol: &str,
+    profile: &Profile,
+    initial_trust: &[Address],
 ) -> Result<RegistrationResult<BaseGroupAvatar>, SdkError> {
     register_group_with_profile_input(
         sdk,
@@ -380,16 +620,59 @@ pub async fn register_group(
         name,
         symbol,
         profile.into(),
+        initial_trust,
     )
     .await
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{RegistrationProfileInput, register_human};
-    use crate::{SdkError, config};
-    use alloy_primitives::address;
+    use super::{
+        PendingAvatar, RegistrationProfileInput, created_group, predict_base_group_address,
+        register_human, submit_group_registration,
+    };
+    use crate::avatar::{BaseGroupAvatar, HumanAvatar};
+    use crate::{
+        ContractRunner, PreparedTransaction, RegistrationResult, RunnerError, SdkError,
+        SubmittedTx, config,
+    };
+    use alloy_primitives::{Address, Bytes, address, b256};
+    use alloy_sol_types::SolEvent;
+    use circles_abis::BaseGroupFactory::BaseGroupCreated;
     use circles_profiles::Profile;
+    use circles_rpc_mock::{MockRpcServer, fixtures};
+    use circles_types::AvatarType;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// Records each submission and reports no transaction hash for it.
+    #[derive(Default)]
+    struct HashlessRunner {
+        sent: Mutex<Vec<Vec<PreparedTransaction>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ContractRunner for HashlessRunner {
+        fn sender_address(&self) -> Address {
+            address!("1000000000000000000000000000000000000001")
+        }
+
+        async fn send_transactions(
+            &self,
+            txs: Vec<PreparedTransaction>,
+        ) -> Result<Vec<SubmittedTx>, RunnerError> {
+            self.sent.lock().expect("lock").push(txs.clone());
+            Ok(txs
+                .iter()
+                .map(|_| SubmittedTx {
+                    tx_hash: Bytes::new(),
+                    success: true,
+                    index: None,
+                })
+                .collect())
+        }
+    }
 
     fn test_profile(name: &str) -> Profile {
         Profile {
@@ -416,6 +699,207 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn predicted_group_address_follows_create_derivation() {
+        let factory = address!("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0");
+        assert_eq!(
+            predict_base_group_address(factory, 0),
+            address!("cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d")
+        );
+        assert_eq!(
+            predict_base_group_address(factory, 1),
+            address!("343c43a37d37dff08ae8c4a11544c718abb4fcf8")
+        );
+    }
+
+    fn topic(address: Address) -> String {
+        format!("{}", address.into_word())
+    }
+
+    /// Receipt of a `createBaseGroup` call whose factory log reports `group` for `owner`.
+    fn group_created_receipt(
+        factory: Address,
+        group: Address,
+        owner: Address,
+    ) -> serde_json::Value {
+        let tx_hash = "0x1111111111111111111111111111111111111111111111111111111111111111";
+        let block_hash = "0x2222222222222222222222222222222222222222222222222222222222222222";
+        json!({
+            "transactionHash": tx_hash,
+            "transactionIndex": "0x0",
+            "blockHash": block_hash,
+            "blockNumber": "0x10",
+            "from": owner,
+            "to": factory,
+            "contractAddress": null,
+            "gasUsed": "0x5208",
+            "cumulativeGasUsed": "0x5208",
+            "effectiveGasPrice": "0x1",
+            "status": "0x1",
+            "type": "0x2",
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "logs": [{
+                "address": factory,
+                "topics": [
+                    format!("{}", <BaseGroupCreated as SolEvent>::SIGNATURE_HASH),
+                    topic(group),
+                    topic(owner),
+                    topic(Address::ZERO),
+                ],
+                "data": format!("0x{}", "00".repeat(32)),
+                "blockHash": block_hash,
+                "blockNumber": "0x10",
+                "transactionHash": tx_hash,
+                "transactionIndex": "0x0",
+                "logIndex": "0x0",
+                "removed": false
+            }]
+        })
+    }
+
+    #[tokio::test]
+    async fn created_group_is_read_from_the_factory_log() {
+        let owner = address!("1000000000000000000000000000000000000001");
+        let group = address!("2000000000000000000000000000000000000002");
+        let mut cfg = config::gnosis_mainnet();
+        let receipt = group_created_receipt(cfg.base_group_factory_address, group, owner);
+        let server = MockRpcServer::builder()
+            .respond("eth_getTransactionReceipt", serde_json::Value::Null)
+            .respond("eth_getTransactionReceipt", receipt)
+            .start()
+            .await
+            .expect("mock server");
+        cfg.chain_rpc_url = Some(server.url());
+        let core = crate::Core::new(cfg);
+        let tx_hash = b256!("1111111111111111111111111111111111111111111111111111111111111111");
+
+        assert_eq!(
+            created_group(&core, owner, tx_hash).await.expect("pending"),
+            None
+        );
+        assert_eq!(
+            created_group(&core, owner, tx_hash).await.expect("mined"),
+            Some(group)
+        );
+        let other = address!("3000000000000000000000000000000000000003");
+        assert!(matches!(
+            created_group(&core, other, tx_hash).await,
+            Err(SdkError::OperationFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn resolve_avatar_retries_until_the_indexer_knows_the_avatar() {
+        let alice = address!("1000000000000000000000000000000000000001");
+        let server = MockRpcServer::builder()
+            .respond("circles_getAvatarInfo", serde_json::Value::Null)
+            .respond(
+                "circles_getAvatarInfo",
+                fixtures::avatar_info(alice, AvatarType::CrcV2RegisterHuman),
+            )
+            .start()
+            .await
+            .expect("mock server");
+        let mut cfg = config::gnosis_mainnet();
+        cfg.circles_rpc_url = server.url();
+        let sdk = crate::Sdk::new(cfg, None).expect("sdk");
+
+        let mut result = RegistrationResult {
+            avatar: None,
+            txs: Vec::new(),
+            trust_error: None,
+            pending: Some(PendingAvatar::new(&sdk, alice, HumanAvatar::new)),
+        };
+        assert_eq!(result.registered_address(), Some(alice));
+        assert!(matches!(
+            result.resolve_avatar(1, Duration::ZERO).await,
+            Err(SdkError::AvatarNotFound(addr)) if addr == alice
+        ));
+
+        let avatar = result
+            .resolve_avatar(3, Duration::from_millis(10))
+            .await
+            .expect("resolved");
+        assert_eq!(avatar.address, alice);
+        assert!(result.avatar.is_some());
+        assert_eq!(server.params_for("circles_getAvatarInfo").len(), 2);
+    }
+
+    #[tokio::test]
+    async fn pending_group_is_located_once_its_creation_is_mined() {
+        let owner = address!("1000000000000000000000000000000000000001");
+        let group = address!("2000000000000000000000000000000000000002");
+        let mut cfg = config::gnosis_mainnet();
+        let receipt = group_created_receipt(cfg.base_group_factory_address, group, owner);
+        let server = MockRpcServer::builder()
+            .respond("eth_getTransactionReceipt", serde_json::Value::Null)
+            .respond("eth_getTransactionReceipt", receipt)
+            .respond(
+                "circles_getAvatarInfo",
+                fixtures::avatar_info(group, AvatarType::CrcV2RegisterGroup),
+            )
+            .start()
+            .await
+            .expect("mock server");
+        cfg.circles_rpc_url = server.url();
+        cfg.chain_rpc_url = Some(server.url());
+        let sdk = crate::Sdk::new(cfg, None).expect("sdk");
+        let tx_hash = b256!("1111111111111111111111111111111111111111111111111111111111111111");
+
+        let mut result = RegistrationResult {
+            avatar: None,
+            txs: Vec::new(),
+            trust_error: None,
+            pending: Some(PendingAvatar::created_by(
+                &sdk,
+                tx_hash,
+                owner,
+                BaseGroupAvatar::new,
+            )),
+        };
+        assert_eq!(result.registered_address(), None);
+        assert!(matches!(
+            result.resolve_avatar(1, Duration::ZERO).await,
+            Err(SdkError::OperationFailed(_))
+        ));
+
+        let avatar = result
+            .resolve_avatar(2, Duration::from_millis(10))
+            .await
+            .expect("resolved");
+        assert_eq!(avatar.address, group);
+        assert_eq!(result.registered_address(), Some(group));
+    }
+
+    #[tokio::test]
+    async fn group_trust_that_cannot_follow_is_reported_with_the_registration() {
+        let runner = Arc::new(HashlessRunner::default());
+        let sdk = crate::Sdk::new(config::gnosis_mainnet(), Some(runner.clone())).expect("sdk");
+        let owner = runner.sender_address();
+        let member = address!("4000000000000000000000000000000000000004");
+
+        let result = submit_group_registration(
+            &sdk,
+            owner,
+            owner,
+            owner,
+            &[],
+            "Group",
+            "GRP",
+            "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+            &[member],
+        )
+        .await
+        .expect("registration was sent");
+
+        assert_eq!(result.txs.len(), 1);
+        assert_eq!(runner.sent.lock().expect("lock").len(), 1);
+        assert!(matches!(
+            result.trust_error,
+            Some(SdkError::OperationFailed(_))
+        ));
+    }
+
     #[tokio::test]
     async fn register_human_requires_runner_before_network_profile_work() {
         let sdk = crate::Sdk::new(config::gnosis_mainnet(), None).expect("sdk");
@@ -0,0 +1,380 @@
+use crate::{
+    ContractRunner, Core, MulticallBatch, PreparedTransaction, SdkError, SubmittedTx, call_to_tx,
+};
+use alloy_primitives::aliases::U192;
+use alloy_primitives::{Address, B256, Bytes, U256};
+use circles_abis::SubscriptionModule;
+use circles_pathfinder::{
+    FindPathParams, create_flow_matrix, encode_redeem_flow_matrix, find_path_with_params_via_rpc,
+};
+use circles_rpc::CirclesRpc;
+use circles_utils::time::unix_now;
+use std::sync::Arc;
+
+/// How the subscription module pulls payments for a subscription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubscriptionCategory {
+    /// Paid through a trust path; redeeming needs a flow matrix.
+    Trusted,
+    /// Paid directly in the subscriber's personal Circles.
+    Untrusted,
+    /// Paid by minting group tokens to the recipient.
+    Group,
+}
+
+impl SubscriptionCategory {
+    fn from_u8(value: u8) -> Result<Self, SdkError> {
+        match value {
+            0 => Ok(Self::Trusted),
+            1 => Ok(Self::Untrusted),
+            2 => Ok(Self::Group),
+            other => Err(SdkError::OperationFailed(format!(
+                "unknown subscription category {other}"
+            ))),
+        }
+    }
+
+    fn as_u8(self) -> u8 {
+        match self {
+            Self::Trusted => 0,
+            Self::Untrusted => 1,
+            Self::Group => 2,
+        }
+    }
+}
+
+/// A recurring payment as stored by the subscription module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subscription {
+    pub id: B256,
+    pub subscriber: Address,
+    pub recipient: Address,
+    /// Amount due per period (atto-Circles).
+    pub amount: U256,
+    /// Unix seconds of the last redemption (or creation).
+    pub last_redeemed: u64,
+    /// Period length in seconds.
+    pub frequency: u64,
+    pub category: SubscriptionCategory,
+}
+
+impl Subscription {
+    fn from_contract(id: B256, raw: SubscriptionModule::Subscription) -> Result<Self, SdkError> {
+        Ok(Self {
+            id,
+            subscriber: raw.subscriber,
+            recipient: raw.recipient,
+            amount: raw.amount,
+            last_redeemed: raw.lastRedeemed.saturating_to(),
+            frequency: raw.frequency.saturating_to(),
+            category: SubscriptionCategory::from_u8(raw.category)?,
+        })
+    }
+
+    /// Earliest unix time at which at least one period can be redeemed.
+    pub fn next_redeem_at(&self) -> u64 {
+        self.last_redeemed.saturating_add(self.frequency)
+    }
+
+    /// Whole periods elapsed since the last redemption at `now`.
+    pub fn redeemable_periods(&self, now: u64) -> u64 {
+        if self.frequency == 0 {
+            return 0;
+        }
+        now.saturating_sub(self.last_redeemed) / self.frequency
+    }
+
+    /// Amount a redemption at `now` would transfer.
+    pub fn redeemable_amount(&self, now: u64) -> U256 {
+        self.amount
+            .saturating_mul(U256::from(self.redeemable_periods(now)))
+    }
+}
+
+/// A prepared `redeem` call together with what it will pay out.
+#[derive(Debug, Clone)]
+pub struct RedeemPlan {
+    pub subscription: Subscription,
+    pub periods: u64,
+    pub amount: U256,
+    /// `redeem` payload: the encoded flow matrix for trusted subscriptions, empty otherwise.
+    pub data: Bytes,
+    pub tx: PreparedTransaction,
+}
+
+/// Recurring payments through a deployed subscription module.
+///
+/// The module is a Safe module: subscribing and unsubscribing are sent by the subscriber's
+/// Safe, redemption by anyone (usually the recipient or a bot).
+#[derive(Clone)]
+pub struct Subscriptions {
+    core: Arc<Core>,
+    rpc: Arc<CirclesRpc>,
+    runner: Option<Arc<dyn ContractRunner>>,
+    module: Address,
+}
+
+impl Subscriptions {
+    pub fn new(
+        core: Arc<Core>,
+        rpc: Arc<CirclesRpc>,
+        runner: Option<Arc<dyn ContractRunner>>,
+        module: Address,
+    ) -> Self {
+        Self {
+            core,
+            rpc,
+            runner,
+            module,
+        }
+    }
+
+    /// Subscription module address this facade talks to.
+    pub fn module_address(&self) -> Address {
+        self.module
+    }
+
+    /// Plan a new subscription paying `amount` to `recipient` every `frequency` seconds.
+    pub fn plan_subscribe(
+        &self,
+        recipient: Address,
+        amount: U256,
+        frequency: u64,
+        category: SubscriptionCategory,
+    ) -> Result<PreparedTransaction, SdkError> {
+        if frequency == 0 {
+            return Err(SdkError::OperationFailed(
+                "subscription frequency must be non-zero".to_string(),
+            ));
+        }
+        if recipient == Address::ZERO || amount.is_zero() {
+            return Err(SdkError::OperationFailed(
+                "subscription needs a recipient and a non-zero amount".to_string(),
+            ));
+        }
+        Ok(call_to_tx(
+            self.module,
+            SubscriptionModule::subscribeCall {
+                recipient,
+                amount,
+                frequency: U256::from(frequency),
+                category: category.as_u8(),
+            },
+            None,
+        ))
+    }
+
+    /// Create a subscription from the runner's Safe.
+    pub async fn subscribe(
+        &self,
+        recipient: Address,
+        amount: U256,
+        frequency: u64,
+        category: SubscriptionCategory,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        let tx = self.plan_subscribe(recipient, amount, frequency, category)?;
+        self.send(vec![tx]).await
+    }
+
+    /// Plan cancelling a subscription; only its subscriber may send this.
+    pub fn plan_unsubscribe(&self, id: B256) -> PreparedTransaction {
+        call_to_tx(
+            self.module,
+            SubscriptionModule::unsubscribeCall { id },
+            None,
+        )
+    }
+
+    /// Cancel a subscription from the runner's Safe.
+    pub async fn unsubscribe(&self, id: B256) -> Result<Vec<SubmittedTx>, SdkError> {
+        self.send(vec![self.plan_unsubscribe(id)]).await
+    }
+
+    /// Read one subscription.
+    pub async fn get(&self, id: B256) -> Result<Subscription, SdkError> {
+        let raw = self
+            .core
+            .subscription_module(self.module)
+            .getSubscription(id)
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))?;
+        if raw.subscriber == Address::ZERO {
+            return Err(SdkError::OperationFailed(format!(
+                "unknown subscription {id}"
+            )));
+        }
+        Subscription::from_contract(id, raw)
+    }
+
+    /// Every active subscription of `subscriber`, read in one multicall.
+    pub async fn list(&self, subscriber: Address) -> Result<Vec<Subscription>, SdkError> {
+        let ids = self
+            .core
+            .subscription_module(self.module)
+            .getSubscriptionIds(subscriber)
+            .call()
+            .await
+            .map_err(|e| SdkError::Contract(e.to_string()))?;
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut batch = MulticallBatch::new();
+        let handles: Vec<_> = ids
+            .iter()
+            .map(|id| {
+                batch.add(
+                    self.module,
+                    SubscriptionModule::getSubscriptionCall { id: *id },
+                )
+            })
+            .collect();
+        let results = self.core.multicall(batch).await?;
+        ids.into_iter()
+            .zip(handles)
+            .map(|(id, handle)| Subscription::from_contract(id, results.get(handle)?))
+            .collect()
+    }
+
+    /// Earliest unix time at which `id` can be redeemed.
+    pub async fn next_redeem_at(&self, id: B256) -> Result<u64, SdkError> {
+        Ok(self.get(id).await?.next_redeem_at())
+    }
+
+    /// Plan redeeming every elapsed period of `id` as of now.
+    pub async fn plan_redeem(&self, id: B256) -> Result<RedeemPlan, SdkError> {
+        let subscription = self.get(id).await?;
+        self.plan_redeem_at(subscription, unix_now()).await
+    }
+
+    /// Plan redeeming `subscription` as of `now`.
+    ///
+    /// Trusted subscriptions get a fresh path from the subscriber to the recipient for
+    /// `amount * periods`, encoded with [`encode_redeem_flow_matrix`]. Fails when no period
+    /// has elapsed or the network cannot route the full amount.
+    pub async fn plan_redeem_at(
+        &self,
+        subscription: Subscription,
+        now: u64,
+    ) -> Result<RedeemPlan, SdkError> {
+        let periods = subscription.redeemable_periods(now);
+        if periods == 0 {
+            return Err(SdkError::OperationFailed(format!(
+                "subscription {} is not redeemable before {}",
+                subscription.id,
+                subscription.next_redeem_at()
+            )));
+        }
+        let amount = subscription.redeemable_amount(now);
+
+        let data = match subscription.category {
+            SubscriptionCategory::Trusted => {
+                let target_flow =
+                    U192::checked_from_limbs_slice(amount.as_limbs()).ok_or_else(|| {
+                        SdkError::OperationFailed("redeem amount exceeds uint192".to_string())
+                    })?;
+                let transfers = find_path_with_params_via_rpc(
+                    &self.rpc,
+                    FindPathParams {
+                        from: subscription.subscriber,
+                        to: subscription.recipient,
+                        target_flow: amount,
+                        use_wrapped_balances: Some(true),
+                        from_tokens: None,
+                        to_tokens: None,
+                        exclude_from_tokens: None,
+                        exclude_to_tokens: None,
+                        simulated_balances: None,
+                        simulated_trusts: None,
+                        max_transfers: None,
+                    },
+                )
+                .await?;
+                let matrix = create_flow_matrix(
+                    subscription.subscriber,
+                    subscription.recipient,
+                    target_flow,
+                    &transfers,
+                )?;
+                Bytes::from(encode_redeem_flow_matrix(matrix))
+            }
+            SubscriptionCategory::Untrusted | SubscriptionCategory::Group => Bytes::new(),
+        };
+
+        let tx = call_to_tx(
+            self.module,
+            SubscriptionModule::redeemCall {
+                id: subscription.id,
+                data: data.clone(),
+            },
+            None,
+        );
+        Ok(RedeemPlan {
+            subscription,
+            periods,
+            amount,
+            data,
+            tx,
+        })
+    }
+
+    /// Redeem every elapsed period of `id` using the runner.
+    pub async fn redeem(&self, id: B256) -> Result<Vec<SubmittedTx>, SdkError> {
+        let plan = self.plan_redeem(id).await?;
+        self.send(vec![plan.tx]).await
+    }
+
+    pub(crate) async fn send(
+        &self,
+        txs: Vec<PreparedTransaction>,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        let runner = self.runner.as_ref().ok_or(SdkError::MissingRunner)?;
+        Ok(runner.send_transactions(txs).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monthly(last_redeemed: u64) -> Subscription {
+        Subscription {
+            id: B256::ZERO,
+            subscriber: Address::repeat_byte(1),
+            recipient: Address::repeat_byte(2),
+            amount: U256::from(10u64),
+            last_redeemed,
+            frequency: 30 * 86_400,
+            category: SubscriptionCategory::Trusted,
+        }
+    }
+
+    #[test]
+    fn redeemable_periods_count_whole_elapsed_periods() {
+        let sub = monthly(1_000);
+        assert_eq!(sub.next_redeem_at(), 1_000 + 30 * 86_400);
+        assert_eq!(sub.redeemable_periods(500), 0);
+        assert_eq!(sub.redeemable_periods(sub.next_redeem_at() - 1), 0);
+        assert_eq!(sub.redeemable_periods(sub.next_redeem_at()), 1);
+        assert_eq!(
+            sub.redeemable_amount(1_000 + 3 * 30 * 86_400 + 5),
+            U256::from(30u64)
+        );
+    }
+
+    #[test]
+    fn category_round_trips_through_contract_encoding() {
+        for category in [
+            SubscriptionCategory::Trusted,
+            SubscriptionCategory::Untrusted,
+            SubscriptionCategory::Group,
+        ] {
+            assert_eq!(
+                SubscriptionCategory::from_u8(category.as_u8()).unwrap(),
+                category
+            );
+        }
+        assert!(SubscriptionCategory::from_u8(7).is_err());
+    }
+}
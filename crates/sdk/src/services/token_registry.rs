@@ -0,0 +1,249 @@
+use crate::{Core, MulticallBatch, SdkError};
+use alloy_primitives::{Address, B256, U256};
+use circles_abis::{DemurrageCircles, NameRegistry};
+use circles_rpc::CirclesRpc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+/// What a token is, derived from its indexed `token_type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// Personal v2 token of a human avatar.
+    Personal,
+    /// v2 group token.
+    Group,
+    /// ERC20 wrapper holding demurraged balances.
+    DemurragedWrapper,
+    /// ERC20 wrapper holding static (inflationary) balances.
+    InflationaryWrapper,
+    /// v1 personal token.
+    V1Personal,
+    /// Anything else the indexer reports.
+    Other,
+}
+
+impl TokenKind {
+    fn from_token_type(token_type: &str) -> Self {
+        match token_type {
+            "CrcV2_RegisterHuman" => Self::Personal,
+            "CrcV2_RegisterGroup" => Self::Group,
+            "CrcV2_ERC20WrapperDeployed_Demurraged" => Self::DemurragedWrapper,
+            "CrcV2_ERC20WrapperDeployed_Inflationary" => Self::InflationaryWrapper,
+            "CrcV1_Signup" => Self::V1Personal,
+            _ => Self::Other,
+        }
+    }
+
+    /// Whether the token is an ERC20 wrapper around an ERC1155 Circles token.
+    pub fn is_wrapper(self) -> bool {
+        matches!(self, Self::DemurragedWrapper | Self::InflationaryWrapper)
+    }
+}
+
+/// Display metadata for one token, as resolved by [`TokenRegistry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenMetadata {
+    pub token: Address,
+    /// Avatar whose Circles the token represents (the wrapped avatar for wrappers).
+    pub owner: Address,
+    pub kind: TokenKind,
+    /// Raw `token_type` reported by the indexer.
+    pub token_type: String,
+    /// ERC20 symbol for wrappers, name-registry symbol otherwise.
+    pub symbol: Option<String>,
+    /// Owner's profile name, or the registered group name.
+    pub name: Option<String>,
+}
+
+/// Lazily built, cached token → metadata map for labelling balance lists.
+///
+/// Lookups batch `circles_getTokenInfoBatch`, avatar/profile batch queries, and one
+/// multicall for symbols; resolved entries stay cached for the registry's lifetime.
+/// Missing profiles and failed symbol calls leave `name`/`symbol` unset per token; an
+/// entry whose profile or symbol lookup failed is returned but not cached, so the next
+/// lookup retries it. Clones share the cache.
+#[derive(Clone)]
+pub struct TokenRegistry {
+    rpc: Arc<CirclesRpc>,
+    core: Arc<Core>,
+    cache: Arc<RwLock<HashMap<Address, TokenMetadata>>>,
+}
+
+impl TokenRegistry {
+    pub fn new(rpc: Arc<CirclesRpc>, core: Arc<Core>) -> Self {
+        Self {
+            rpc,
+            core,
+            cache: Arc::default(),
+        }
+    }
+
+    /// Metadata for one token address.
+    pub async fn resolve(&self, token: Address) -> Result<TokenMetadata, SdkError> {
+        self.resolve_many(&[token])
+            .await?
+            .remove(&token)
+            .ok_or_else(|| SdkError::OperationFailed(format!("unknown token {token:#x}")))
+    }
+
+    /// Metadata for a v2 ERC1155 token id (the owner address as an integer).
+    pub async fn resolve_id(&self, token_id: U256) -> Result<TokenMetadata, SdkError> {
+        self.resolve(Address::from_word(B256::from(token_id))).await
+    }
+
+    /// Metadata for every token the indexer knows; unknown tokens are omitted.
+    pub async fn resolve_many(
+        &self,
+        tokens: &[Address],
+    ) -> Result<HashMap<Address, TokenMetadata>, SdkError> {
+        let mut resolved = HashMap::with_capacity(tokens.len());
+        let mut missing = Vec::new();
+        {
+            let cache = self.cache.read().expect("token registry lock");
+            for token in tokens.iter().copied().collect::<HashSet<_>>() {
+                match cache.get(&token) {
+                    Some(meta) => {
+                        resolved.insert(token, meta.clone());
+                    }
+                    None => missing.push(token),
+                }
+            }
+        }
+        if missing.is_empty() {
+            return Ok(resolved);
+        }
+
+        let fetched = self.fetch(missing).await?;
+        let mut cache = self.cache.write().expect("token registry lock");
+        for (meta, complete) in fetched {
+            if complete {
+                cache.insert(meta.token, meta.clone());
+            }
+            resolved.insert(meta.token, meta);
+        }
+        Ok(resolved)
+    }
+
+    /// Cached metadata without touching the network.
+    pub fn cached(&self, token: Address) -> Option<TokenMetadata> {
+        self.cache
+            .read()
+            .expect("token registry lock")
+            .get(&token)
+            .cloned()
+    }
+
+    /// Drop every cached entry, e.g. after profile updates.
+    pub fn clear(&self) {
+        self.cache.write().expect("token registry lock").clear();
+    }
+
+    /// Metadata for `tokens`, each flagged with whether every lookup behind it succeeded.
+    async fn fetch(&self, tokens: Vec<Address>) -> Result<Vec<(TokenMetadata, bool)>, SdkError> {
+        let infos = self.rpc.token_info().get_token_info_batch(tokens).await?;
+        if infos.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut seen = HashSet::new();
+        let owners: Vec<Address> = infos
+            .iter()
+            .map(|info| info.token_owner)
+            .filter(|owner| seen.insert(*owner))
+            .collect();
+
+        let group_names: HashMap<Address, String> = self
+            .rpc
+            .avatar()
+            .get_avatar_info_batch(owners.clone())
+            .await?
+            .into_iter()
+            .filter_map(|info| info.name.map(|name| (info.avatar, name)))
+            .collect();
+        // Profiles are cosmetic: a failing or partial profile batch must not hide tokens.
+        let profile_names: Option<HashMap<Address, String>> = match self
+            .rpc
+            .avatar()
+            .get_profile_by_address_batch(owners.clone())
+            .await
+        {
+            Ok(profiles) if profiles.len() == owners.len() => Some(
+                owners
+                    .iter()
+                    .copied()
+                    .zip(profiles)
+                    .filter_map(|(owner, profile)| Some((owner, profile?.name)))
+                    .filter(|(_, name)| !name.is_empty())
+                    .collect(),
+            ),
+            _ => None,
+        };
+
+        let mut batch = MulticallBatch::new();
+        let symbol_calls: Vec<_> = infos
+            .iter()
+            .map(|info| {
+                if TokenKind::from_token_type(&info.token_type).is_wrapper() {
+                    SymbolCall::Wrapper(
+                        batch.add_allow_failure(info.token, DemurrageCircles::symbolCall {}),
+                    )
+                } else {
+                    SymbolCall::Registry(batch.add_allow_failure(
+                        self.core.config.name_registry_address,
+                        NameRegistry::symbolCall {
+                            _avatar: info.token_owner,
+                        },
+                    ))
+                }
+            })
+            .collect();
+        // Symbols are cosmetic too: a failed multicall leaves every symbol unset.
+        let symbols = self.core.multicall(batch).await.ok();
+
+        Ok(infos
+            .into_iter()
+            .zip(symbol_calls)
+            .map(|(info, call)| {
+                let symbol = symbols.as_ref().and_then(|symbols| match call {
+                    SymbolCall::Wrapper(handle) => symbols.get(handle).ok(),
+                    SymbolCall::Registry(handle) => symbols.get(handle).ok(),
+                });
+                let complete = symbol.is_some() && profile_names.is_some();
+                let name = profile_names
+                    .as_ref()
+                    .and_then(|names| names.get(&info.token_owner))
+                    .or_else(|| group_names.get(&info.token_owner))
+                    .cloned();
+                let meta = TokenMetadata {
+                    token: info.token,
+                    owner: info.token_owner,
+                    kind: TokenKind::from_token_type(&info.token_type),
+                    token_type: info.token_type,
+                    symbol: symbol.filter(|symbol| !symbol.is_empty()),
+                    name,
+                };
+                (meta, complete)
+            })
+            .collect())
+    }
+}
+
+enum SymbolCall {
+    Wrapper(crate::MulticallHandle<DemurrageCircles::symbolCall>),
+    Registry(crate::MulticallHandle<NameRegistry::symbolCall>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TokenKind;
+
+    #[test]
+    fn token_kind_maps_indexer_types() {
+        assert_eq!(
+            TokenKind::from_token_type("CrcV2_RegisterHuman"),
+            TokenKind::Personal
+        );
+        assert!(TokenKind::from_token_type("CrcV2_ERC20WrapperDeployed_Inflationary").is_wrapper());
+        assert!(!TokenKind::from_token_type("CrcV2_RegisterGroup").is_wrapper());
+        assert_eq!(TokenKind::from_token_type("unknown"), TokenKind::Other);
+    }
+}
@@ -0,0 +1,272 @@
+use crate::trust_list::TrustList;
+use crate::{ContractRunner, PreparedTransaction, SdkError, SubmittedTx, call_to_tx};
+use alloy_primitives::{Address, aliases::U96};
+use circles_abis::HubV2;
+use circles_rpc::CirclesRpc;
+use circles_types::TrustRelation;
+use circles_utils::time::unix_now;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Seconds in one day of trust duration.
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+const DEFAULT_WINDOW_DAYS: u64 = 7;
+const DEFAULT_RENEWAL_DAYS: u64 = 365;
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Expiry timestamp for trust that should last `days` days from `now` (unix seconds).
+///
+/// The hub treats any expiry at or before the current block as a removal, so zero days
+/// is rejected instead of silently untrusting.
+pub fn trust_expiry_after_days(now: u64, days: u64) -> Result<u128, SdkError> {
+    if days == 0 {
+        return Err(SdkError::ZeroTrustDuration);
+    }
+    // Cannot overflow: u64::MAX days in seconds stays far below the u96 expiry range.
+    Ok(u128::from(now) + u128::from(days) * u128::from(SECONDS_PER_DAY))
+}
+
+/// An outgoing trust relation that lapses within the scheduler's window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpiringTrust {
+    pub trustee: Address,
+    /// Current expiry in unix seconds.
+    pub expiry_time: u64,
+}
+
+/// Renewal batch built by [`TrustScheduler::plan_renewals`].
+#[derive(Debug, Clone)]
+pub struct TrustRenewalPlan {
+    /// Relations to renew, soonest expiry first.
+    pub expiring: Vec<ExpiringTrust>,
+    /// Expiry every renewed relation will have.
+    pub new_expiry: u128,
+    /// One `trust` call per entry in `expiring`.
+    pub txs: Vec<PreparedTransaction>,
+}
+
+/// Result of one [`TrustScheduler::renew`] run.
+#[derive(Debug)]
+pub struct TrustRenewal {
+    /// Relations that were renewed, soonest expiry first.
+    pub renewed: Vec<ExpiringTrust>,
+    /// Expiry every renewed relation now has.
+    pub new_expiry: u128,
+    /// Submitted transactions; empty when nothing was due.
+    pub txs: Vec<SubmittedTx>,
+}
+
+/// Keeps an avatar's outgoing trust alive by re-trusting relations shortly before they
+/// expire.
+///
+/// Relations expiring within the window (default 7 days) are renewed for the renewal
+/// duration (default 365 days) with one batch of `trust` calls. Relations that already
+/// expired are left alone, as are ones trusted indefinitely. Call
+/// [`TrustScheduler::renew`] from a cron job or run it in the background with
+/// [`TrustScheduler::spawn`].
+pub struct TrustScheduler {
+    rpc: Arc<CirclesRpc>,
+    runner: Option<Arc<dyn ContractRunner>>,
+    avatar: Address,
+    trust_contract: Address,
+    window_days: u64,
+    renewal_days: u64,
+    poll_interval: Duration,
+}
+
+impl TrustScheduler {
+    /// Renew trust given by `avatar` through `trust_contract` (the v2 hub for humans and
+    /// organisations, the group contract for base groups).
+    pub fn new(
+        rpc: Arc<CirclesRpc>,
+        runner: Option<Arc<dyn ContractRunner>>,
+        avatar: Address,
+        trust_contract: Address,
+    ) -> Self {
+        Self {
+            rpc,
+            runner,
+            avatar,
+            trust_contract,
+            window_days: DEFAULT_WINDOW_DAYS,
+            renewal_days: DEFAULT_RENEWAL_DAYS,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Renew relations expiring within `days` days.
+    pub fn with_window_days(mut self, days: u64) -> Self {
+        self.window_days = days;
+        self
+    }
+
+    /// Extend renewed relations to `days` days from the run.
+    pub fn with_renewal_days(mut self, days: u64) -> Self {
+        self.renewal_days = days;
+        self
+    }
+
+    /// Time between background runs (default one hour).
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Outgoing trust relations expiring within the window, soonest first.
+    pub async fn expiring(&self) -> Result<Vec<ExpiringTrust>, SdkError> {
+        let relations = self.rpc.trust().get_trust_relations(self.avatar).await?;
+        Ok(expiring_within(
+            self.avatar,
+            &relations,
+            unix_now(),
+            self.window_days,
+        ))
+    }
+
+    /// Build the renewal batch without sending it.
+    pub async fn plan_renewals(&self) -> Result<TrustRenewalPlan, SdkError> {
+        let new_expiry = trust_expiry_after_days(unix_now(), self.renewal_days)?;
+        let expiring = self.expiring().await?;
+        let txs = expiring
+            .iter()
+            .map(|trust| {
+                let call = HubV2::trustCall {
+                    _trustReceiver: trust.trustee,
+                    _expiry: U96::from(new_expiry),
+                };
+                call_to_tx(self.trust_contract, call, None)
+            })
+            .collect();
+        Ok(TrustRenewalPlan {
+            expiring,
+            new_expiry,
+            txs,
+        })
+    }
+
+    /// Renew every relation expiring within the window (requires runner).
+    pub async fn renew(&self) -> Result<TrustRenewal, SdkError> {
+        let runner = self.runner.clone().ok_or(SdkError::MissingRunner)?;
+        let plan = self.plan_renewals().await?;
+        let txs = if plan.txs.is_empty() {
+            Vec::new()
+        } else {
+            runner.send_transactions(plan.txs).await?
+        };
+        Ok(TrustRenewal {
+            renewed: plan.expiring,
+            new_expiry: plan.new_expiry,
+            txs,
+        })
+    }
+
+    /// Run [`renew`](Self::renew) every poll interval in a background task, streaming
+    /// each result. The task stops once the receiver is dropped.
+    pub fn spawn(self) -> mpsc::UnboundedReceiver<Result<TrustRenewal, SdkError>> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            loop {
+                if tx.send(self.renew().await).is_err() {
+                    return;
+                }
+                tokio::time::sleep(self.poll_interval).await;
+            }
+        });
+        rx
+    }
+}
+
+/// Live outgoing relations of `avatar` that expire within `window_days` of `now`.
+fn expiring_within(
+    avatar: Address,
+    relations: &[TrustRelation],
+    now: u64,
+    window_days: u64,
+) -> Vec<ExpiringTrust> {
+    let horizon = now.saturating_add(window_days.saturating_mul(SECONDS_PER_DAY));
+    let mut expiring: Vec<ExpiringTrust> = TrustList::from_relations(avatar, relations, now)
+        .entries
+        .into_iter()
+        .filter(|entry| entry.expiry <= horizon)
+        .map(|entry| ExpiringTrust {
+            trustee: entry.trustee,
+            expiry_time: entry.expiry,
+        })
+        .collect();
+    expiring.sort_by_key(|trust| (trust.expiry_time, trust.trustee));
+    expiring
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::TxHash;
+
+    fn relation(truster: u8, trustee: u8, expiry_time: u64) -> TrustRelation {
+        relation_at(1, truster, trustee, expiry_time)
+    }
+
+    fn relation_at(block_number: u64, truster: u8, trustee: u8, expiry_time: u64) -> TrustRelation {
+        TrustRelation {
+            block_number,
+            timestamp: 0,
+            transaction_index: 0,
+            log_index: 0,
+            transaction_hash: TxHash::ZERO,
+            truster: Address::repeat_byte(truster),
+            trustee: Address::repeat_byte(trustee),
+            expiry_time,
+        }
+    }
+
+    #[test]
+    fn trust_durations_convert_to_expiry_timestamps() {
+        assert_eq!(
+            trust_expiry_after_days(1_700_000_000, 30).unwrap(),
+            1_700_000_000 + 30 * 86_400
+        );
+        assert!(trust_expiry_after_days(u64::MAX, u64::MAX).unwrap() <= U96::MAX.to::<u128>());
+        assert!(matches!(
+            trust_expiry_after_days(1_700_000_000, 0),
+            Err(SdkError::ZeroTrustDuration)
+        ));
+    }
+
+    #[test]
+    fn only_live_outgoing_relations_inside_the_window_expire() {
+        let now = 1_000_000;
+        let day = SECONDS_PER_DAY;
+        let relations = [
+            relation(0xaa, 0x01, now + 3 * day),
+            // The most recent row for a trustee wins, whichever expiry is larger.
+            relation_at(2, 0xaa, 0x02, now + day),
+            relation_at(1, 0xaa, 0x02, now + 30 * day),
+            relation_at(3, 0xaa, 0x06, now + 30 * day),
+            relation_at(2, 0xaa, 0x06, now + day),
+            relation(0xaa, 0x03, now - 1),
+            relation(0xaa, 0x04, now + day),
+            relation(0xbb, 0xaa, now + day),
+            relation(0xaa, 0x05, u64::MAX),
+        ];
+        let expiring = expiring_within(Address::repeat_byte(0xaa), &relations, now, 7);
+        assert_eq!(
+            expiring,
+            vec![
+                ExpiringTrust {
+                    trustee: Address::repeat_byte(0x02),
+                    expiry_time: now + day,
+                },
+                ExpiringTrust {
+                    trustee: Address::repeat_byte(0x04),
+                    expiry_time: now + day,
+                },
+                ExpiringTrust {
+                    trustee: Address::repeat_byte(0x01),
+                    expiry_time: now + 3 * day,
+                },
+            ]
+        );
+    }
+}
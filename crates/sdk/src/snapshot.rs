@@ -0,0 +1,153 @@
+//! Serializable, read-only avatar state for offline views and deterministic tests.
+
+use crate::SdkError;
+use alloy_primitives::Address;
+use circles_types::{
+    AggregatedTrustRelation, AvatarInfo, Profile, TokenBalanceResponse, TrustRelation,
+    TrustRelationType,
+};
+use serde::{Deserialize, Serialize};
+
+/// Format version written by [`AvatarSnapshot::to_json`].
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Point-in-time copy of an avatar's info, v2 balances, trust relations, and profile.
+///
+/// Produced by [`HumanAvatar::snapshot`](crate::HumanAvatar::snapshot) and restored with
+/// [`Sdk::load_snapshot`](crate::Sdk::load_snapshot). Every accessor answers from the
+/// captured data only; nothing here touches the network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvatarSnapshot {
+    pub version: u32,
+    /// Unix seconds at capture; trust expiry is evaluated against this, not the wall clock.
+    pub taken_at: u64,
+    pub info: AvatarInfo,
+    /// Per-token v2 balances in atto-circles.
+    pub balances: Vec<TokenBalanceResponse>,
+    pub trust_relations: Vec<TrustRelation>,
+    pub profile: Option<Profile>,
+}
+
+impl AvatarSnapshot {
+    /// Avatar the snapshot was taken of.
+    pub fn address(&self) -> Address {
+        self.info.avatar
+    }
+
+    /// Balance row for `token_id`, if the avatar held it.
+    pub fn balance_of(&self, token_id: Address) -> Option<&TokenBalanceResponse> {
+        self.balances.iter().find(|row| row.token_id == token_id)
+    }
+
+    /// Trust relations merged per counterpart as of `taken_at`.
+    pub fn aggregated_trust_relations(&self) -> Vec<AggregatedTrustRelation> {
+        AggregatedTrustRelation::aggregate(self.address(), &self.trust_relations, self.taken_at)
+    }
+
+    /// Whether the avatar trusted `other` when the snapshot was taken.
+    pub fn is_trusting(&self, other: Address) -> bool {
+        self.relation_with(other).is_some_and(|relation| {
+            matches!(
+                relation,
+                TrustRelationType::Trusts | TrustRelationType::MutuallyTrusts
+            )
+        })
+    }
+
+    /// Whether `other` trusted the avatar when the snapshot was taken.
+    pub fn is_trusted_by(&self, other: Address) -> bool {
+        self.relation_with(other).is_some_and(|relation| {
+            matches!(
+                relation,
+                TrustRelationType::TrustedBy | TrustRelationType::MutuallyTrusts
+            )
+        })
+    }
+
+    fn relation_with(&self, other: Address) -> Option<TrustRelationType> {
+        self.aggregated_trust_relations()
+            .into_iter()
+            .find(|relation| relation.object_avatar == other)
+            .map(|relation| relation.relation)
+    }
+
+    /// Serialize to JSON.
+    pub fn to_json(&self) -> Result<String, SdkError> {
+        serde_json::to_string(self)
+            .map_err(|e| SdkError::OperationFailed(format!("snapshot encode failed: {e}")))
+    }
+
+    /// Parse a snapshot, rejecting versions this SDK does not understand.
+    pub fn from_json(json: &str) -> Result<Self, SdkError> {
+        let snapshot: Self = serde_json::from_str(json)
+            .map_err(|e| SdkError::OperationFailed(format!("invalid snapshot: {e}")))?;
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(SdkError::OperationFailed(format!(
+                "unsupported snapshot version {} (expected {SNAPSHOT_VERSION})",
+                snapshot.version
+            )));
+        }
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{TxHash, address};
+    use circles_types::AvatarType;
+
+    fn relation(truster: Address, trustee: Address, expiry_time: u64) -> TrustRelation {
+        TrustRelation {
+            block_number: 1,
+            timestamp: 1,
+            transaction_index: 0,
+            log_index: 0,
+            transaction_hash: TxHash::ZERO,
+            truster,
+            trustee,
+            expiry_time,
+        }
+    }
+
+    #[test]
+    fn round_trips_and_answers_trust_offline() {
+        let me = address!("1000000000000000000000000000000000000001");
+        let friend = address!("2000000000000000000000000000000000000002");
+        let expired = address!("3000000000000000000000000000000000000003");
+        let info: AvatarInfo = serde_json::from_value(serde_json::json!({
+            "version": 2,
+            "type": "CrcV2_RegisterHuman",
+            "avatar": me,
+            "tokenId": null,
+            "hasV1": false,
+            "isHuman": true,
+        }))
+        .unwrap();
+        assert_eq!(info.avatar_type, AvatarType::CrcV2RegisterHuman);
+
+        let snapshot = AvatarSnapshot {
+            version: SNAPSHOT_VERSION,
+            taken_at: 1_000,
+            info,
+            balances: Vec::new(),
+            trust_relations: vec![
+                relation(me, friend, u64::MAX),
+                relation(friend, me, u64::MAX),
+                relation(me, expired, 500),
+            ],
+            profile: None,
+        };
+
+        let restored = AvatarSnapshot::from_json(&snapshot.to_json().unwrap()).unwrap();
+        assert_eq!(restored.address(), me);
+        assert!(restored.is_trusting(friend));
+        assert!(restored.is_trusted_by(friend));
+        assert!(!restored.is_trusting(expired));
+
+        let mut future = snapshot;
+        future.version = SNAPSHOT_VERSION + 1;
+        assert!(AvatarSnapshot::from_json(&future.to_json().unwrap()).is_err());
+    }
+}
@@ -0,0 +1,441 @@
+//! Multi-transaction transfer plans that can be resumed after a partial failure.
+
+use crate::{ContractRunner, Core, PreparedTransaction, SdkError, SubmittedTx};
+use alloy_primitives::{Address, B256, Bytes, U256, keccak256};
+use alloy_sol_types::SolCall;
+use circles_abis::{DemurrageCircles, HubV2};
+use serde::{Deserialize, Serialize};
+
+/// What a [`PlanStep`] does, decoded from its calldata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum PlanStepKind {
+    /// Hub `setApprovalForAll(operator, approved)`.
+    Approval {
+        operator: Address,
+        approved: bool,
+    },
+    /// ERC20 wrapper `unwrap(amount)` (demurraged or inflationary).
+    Unwrap {
+        wrapper: Address,
+        amount: U256,
+    },
+    /// Hub `wrap(avatar, amount, type)`, e.g. re-wrapping leftovers.
+    Wrap {
+        avatar: Address,
+        amount: U256,
+    },
+    /// Hub `operateFlowMatrix`.
+    FlowMatrix,
+    Other,
+}
+
+impl PlanStepKind {
+    fn classify(tx: &PreparedTransaction) -> Self {
+        if let Ok(call) = HubV2::setApprovalForAllCall::abi_decode(&tx.data) {
+            return Self::Approval {
+                operator: call._operator,
+                approved: call._approved,
+            };
+        }
+        // Demurraged and inflationary wrappers share the `unwrap(uint256)` selector.
+        if let Ok(call) = DemurrageCircles::unwrapCall::abi_decode(&tx.data) {
+            return Self::Unwrap {
+                wrapper: tx.to,
+                amount: call._amount,
+            };
+        }
+        if let Ok(call) = HubV2::wrapCall::abi_decode(&tx.data) {
+            return Self::Wrap {
+                avatar: call._avatar,
+                amount: call._amount,
+            };
+        }
+        if tx.data.starts_with(&HubV2::operateFlowMatrixCall::SELECTOR) {
+            return Self::FlowMatrix;
+        }
+        Self::Other
+    }
+
+    /// Whether chain state alone shows if the step took effect, so an unconfirmed
+    /// submission can be retried without asking the caller.
+    fn is_idempotent(self) -> bool {
+        matches!(self, Self::Approval { .. } | Self::Unwrap { .. })
+    }
+}
+
+/// Progress of one [`PlanStep`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum StepStatus {
+    Pending,
+    /// Executed by this plan.
+    Done {
+        tx_hash: Bytes,
+    },
+    /// Found already in effect on-chain, so it was not sent.
+    AlreadyApplied,
+    /// Submission failed or the transaction reverted.
+    Failed {
+        tx_hash: Option<Bytes>,
+        reason: String,
+    },
+}
+
+impl StepStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Done { .. } | Self::AlreadyApplied)
+    }
+
+    fn tx_hash(&self) -> Option<&Bytes> {
+        match self {
+            Self::Done { tx_hash } => Some(tx_hash),
+            Self::Failed { tx_hash, .. } => tx_hash.as_ref(),
+            Self::Pending | Self::AlreadyApplied => None,
+        }
+    }
+}
+
+/// One transaction of a [`TransferPlan`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanStep {
+    pub kind: PlanStepKind,
+    pub tx: PreparedTransaction,
+    pub status: StepStatus,
+}
+
+/// Ordered transfer transactions with per-step status.
+///
+/// Built from a planned transfer (approval, unwraps, `operateFlowMatrix`, re-wraps).
+/// [`TransferPlan::resume`] sends the steps one at a time and records each outcome, so a
+/// plan that failed half-way can be resumed without replaying steps that already landed.
+/// Plans are serializable; persist one between attempts to resume across restarts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferPlan {
+    /// Idempotency key: hash of the avatar and every step's target, value, and calldata.
+    pub key: B256,
+    pub avatar: Address,
+    pub steps: Vec<PlanStep>,
+}
+
+impl TransferPlan {
+    pub fn new(avatar: Address, txs: Vec<PreparedTransaction>) -> Self {
+        let mut preimage = avatar.to_vec();
+        for tx in &txs {
+            preimage.extend_from_slice(tx.to.as_slice());
+            preimage.extend_from_slice(&tx.value.unwrap_or_default().to_be_bytes::<32>());
+            preimage.extend_from_slice(keccak256(&tx.data).as_slice());
+        }
+        let steps = txs
+            .into_iter()
+            .map(|tx| PlanStep {
+                kind: PlanStepKind::classify(&tx),
+                tx,
+                status: StepStatus::Pending,
+            })
+            .collect();
+        Self {
+            key: keccak256(preimage),
+            avatar,
+            steps,
+        }
+    }
+
+    /// Whether every step is done or already applied.
+    pub fn is_complete(&self) -> bool {
+        self.steps.iter().all(|step| step.status.is_finished())
+    }
+
+    /// Steps that still need to run, in order.
+    pub fn remaining(&self) -> impl Iterator<Item = &PlanStep> {
+        self.steps.iter().filter(|step| !step.status.is_finished())
+    }
+
+    /// Record the outcome of a failed step after checking the chain by hand: `executed`
+    /// marks it applied, otherwise it is reset so [`resume`](Self::resume) sends it again.
+    pub fn confirm_step(&mut self, index: usize, executed: bool) -> Result<(), SdkError> {
+        let step = self
+            .steps
+            .get_mut(index)
+            .ok_or_else(|| SdkError::OperationFailed(format!("plan has no step {index}")))?;
+        if !matches!(step.status, StepStatus::Failed { .. }) {
+            return Err(SdkError::OperationFailed(format!(
+                "plan step {index} has not failed"
+            )));
+        }
+        step.status = if executed {
+            StepStatus::AlreadyApplied
+        } else {
+            StepStatus::Pending
+        };
+        Ok(())
+    }
+
+    /// Run every unfinished step in order, one transaction at a time.
+    ///
+    /// Before sending, each step is checked: a recorded transaction with a successful
+    /// receipt counts as done, an approval already in place on-chain is skipped, and an
+    /// unwrap that was attempted before is skipped once the wrapper balance no longer
+    /// covers it. Stops at the first failure, leaving that step `Failed` so the next call
+    /// retries from there. A step whose recorded transaction is still pending also stops
+    /// the run. Returns the transactions sent by this call.
+    ///
+    /// A failed flow-matrix, wrap, or other step whose outcome cannot be looked up (no
+    /// recorded hash, or a runner without receipt lookup) may already be mined, and
+    /// sending it again could pay twice. Such steps stop the run with an error until the
+    /// caller checks the chain and calls [`confirm_step`](Self::confirm_step).
+    pub async fn resume(
+        &mut self,
+        core: &Core,
+        runner: &dyn ContractRunner,
+    ) -> Result<Vec<SubmittedTx>, SdkError> {
+        if runner.sender_address() != self.avatar {
+            return Err(SdkError::OperationFailed(format!(
+                "plan belongs to {:#x}, runner sends as {:#x}",
+                self.avatar,
+                runner.sender_address()
+            )));
+        }
+
+        let avatar = self.avatar;
+        let mut sent = Vec::new();
+        for index in 0..self.steps.len() {
+            let step = &mut self.steps[index];
+            if step.status.is_finished() {
+                continue;
+            }
+            if step.status != StepStatus::Pending {
+                match recorded_outcome(runner, step.status.tx_hash()).await? {
+                    Recorded::Mined { success: true } => {
+                        let tx_hash = step.status.tx_hash().cloned().unwrap_or_default();
+                        step.status = StepStatus::Done { tx_hash };
+                        continue;
+                    }
+                    Recorded::Mined { success: false } => {}
+                    Recorded::Pending => {
+                        return Err(SdkError::OperationFailed(format!(
+                            "plan step {index} is still pending"
+                        )));
+                    }
+                    Recorded::Unknown if !step.kind.is_idempotent() => {
+                        return Err(SdkError::OperationFailed(format!(
+                            "plan step {index} may already be on-chain; check it and call \
+                             confirm_step({index}, ..) before resuming"
+                        )));
+                    }
+                    Recorded::Unknown => {}
+                }
+            }
+            if already_applied(core, avatar, step).await? {
+                step.status = StepStatus::AlreadyApplied;
+                continue;
+            }
+
+            match runner.send_transactions(vec![step.tx.clone()]).await {
+                Ok(mut submitted) if !submitted.is_empty() => {
+                    let tx = submitted.remove(0);
+                    if tx.success {
+                        step.status = StepStatus::Done {
+                            tx_hash: tx.tx_hash.clone(),
+                        };
+                        sent.push(tx);
+                    } else {
+                        step.status = StepStatus::Failed {
+                            tx_hash: Some(tx.tx_hash.clone()),
+                            reason: "transaction reverted".to_string(),
+                        };
+                        return Err(SdkError::OperationFailed(format!(
+                            "plan step {index} reverted"
+                        )));
+                    }
+                }
+                Ok(_) => {
+                    step.status = StepStatus::Failed {
+                        tx_hash: None,
+                        reason: "runner returned no transaction".to_string(),
+                    };
+                    return Err(SdkError::OperationFailed(format!(
+                        "plan step {index} was not submitted"
+                    )));
+                }
+                Err(e) => {
+                    step.status = StepStatus::Failed {
+                        tx_hash: None,
+                        reason: e.to_string(),
+                    };
+                    return Err(e.into());
+                }
+            }
+        }
+        Ok(sent)
+    }
+}
+
+/// What is known about a failed step's earlier submission.
+enum Recorded {
+    Mined {
+        success: bool,
+    },
+    Pending,
+    /// No hash was recorded or the runner cannot look receipts up.
+    Unknown,
+}
+
+async fn recorded_outcome(
+    runner: &dyn ContractRunner,
+    hash: Option<&Bytes>,
+) -> Result<Recorded, SdkError> {
+    let Some(hash) = hash.and_then(|hash| B256::try_from(hash.as_ref()).ok()) else {
+        return Ok(Recorded::Unknown);
+    };
+    match runner.transaction_receipt(hash).await {
+        Ok(Some(receipt)) => Ok(Recorded::Mined {
+            success: receipt.success,
+        }),
+        Ok(None) => Ok(Recorded::Pending),
+        Err(crate::RunnerError::Unsupported(_)) => Ok(Recorded::Unknown),
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn already_applied(core: &Core, avatar: Address, step: &PlanStep) -> Result<bool, SdkError> {
+    match step.kind {
+        PlanStepKind::Approval { operator, approved } => {
+            let current = core.hub_approvals(avatar, &[operator]).await?;
+            Ok(current.first() == Some(&approved))
+        }
+        // A fresh plan with a short balance is stale rather than applied; only trust the
+        // balance for unwraps this plan already tried.
+        PlanStepKind::Unwrap { wrapper, amount } if step.status != StepStatus::Pending => {
+            let balance = DemurrageCircles::new(wrapper, core.provider())
+                .balanceOf(avatar)
+                .call()
+                .await
+                .map_err(|e| SdkError::Contract(e.to_string()))?;
+            Ok(balance < amount)
+        }
+        _ => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RunnerError, call_to_tx, config};
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    /// Runner without receipt lookup that counts what it is asked to send.
+    struct BlindRunner {
+        avatar: Address,
+        sent: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl ContractRunner for BlindRunner {
+        fn sender_address(&self) -> Address {
+            self.avatar
+        }
+
+        async fn send_transactions(
+            &self,
+            txs: Vec<PreparedTransaction>,
+        ) -> Result<Vec<SubmittedTx>, RunnerError> {
+            *self.sent.lock().unwrap() += txs.len();
+            Ok(Vec::new())
+        }
+    }
+
+    #[test]
+    fn classifies_steps_and_keys_by_content() {
+        let hub = Address::repeat_byte(0x01);
+        let avatar = Address::repeat_byte(0x02);
+        let wrapper = Address::repeat_byte(0x03);
+        let txs = vec![
+            call_to_tx(
+                hub,
+                HubV2::setApprovalForAllCall {
+                    _operator: avatar,
+                    _approved: true,
+                },
+                None,
+            ),
+            call_to_tx(
+                wrapper,
+                DemurrageCircles::unwrapCall {
+                    _amount: U256::from(5u64),
+                },
+                None,
+            ),
+            call_to_tx(
+                hub,
+                HubV2::wrapCall {
+                    _avatar: avatar,
+                    _amount: U256::from(2u64),
+                    _type: 1,
+                },
+                None,
+            ),
+        ];
+
+        let plan = TransferPlan::new(avatar, txs.clone());
+        let kinds: Vec<_> = plan.steps.iter().map(|step| step.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                PlanStepKind::Approval {
+                    operator: avatar,
+                    approved: true
+                },
+                PlanStepKind::Unwrap {
+                    wrapper,
+                    amount: U256::from(5u64)
+                },
+                PlanStepKind::Wrap {
+                    avatar,
+                    amount: U256::from(2u64)
+                },
+            ]
+        );
+        assert_eq!(plan.remaining().count(), 3);
+        assert!(!plan.is_complete());
+
+        assert_eq!(TransferPlan::new(avatar, txs.clone()).key, plan.key);
+        assert_ne!(TransferPlan::new(avatar, txs[..2].to_vec()).key, plan.key);
+
+        let json = serde_json::to_string(&plan).unwrap();
+        let restored: TransferPlan = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, plan);
+    }
+
+    #[tokio::test]
+    async fn unverifiable_flow_step_waits_for_confirmation() {
+        let avatar = Address::repeat_byte(0x02);
+        let flow = PreparedTransaction {
+            to: Address::repeat_byte(0x01),
+            data: HubV2::operateFlowMatrixCall::SELECTOR.to_vec().into(),
+            value: None,
+        };
+        let mut plan = TransferPlan::new(avatar, vec![flow]);
+        assert_eq!(plan.steps[0].kind, PlanStepKind::FlowMatrix);
+        plan.steps[0].status = StepStatus::Failed {
+            tx_hash: None,
+            reason: "connection reset".to_string(),
+        };
+
+        let core = Core::new(config::gnosis_mainnet());
+        let runner = BlindRunner {
+            avatar,
+            sent: Mutex::new(0),
+        };
+        assert!(plan.resume(&core, &runner).await.is_err());
+        assert_eq!(*runner.sent.lock().unwrap(), 0);
+
+        plan.confirm_step(0, false).expect("confirm");
+        assert_eq!(plan.steps[0].status, StepStatus::Pending);
+        assert!(plan.confirm_step(0, true).is_err());
+        assert!(plan.confirm_step(1, true).is_err());
+    }
+}
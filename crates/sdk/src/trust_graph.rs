@@ -0,0 +1,201 @@
+//! In-memory trust graph kept up to date from relations or events.
+
+use crate::projector::{MembershipChange, Projection, TrustChange};
+use alloy_primitives::Address;
+use circles_types::{CirclesEvent, NetworkSnapshot, TrustRelation};
+use circles_utils::time::unix_now;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Directed trust edges (`truster -> trustee`) with their expiry.
+///
+/// Build it from [`TrustRelation`] rows or a [`NetworkSnapshot`], then keep it current by
+/// feeding it to a [`Projector`](crate::Projector): it implements [`Projection`] and applies
+/// every `CrcV2_Trust` event, including group trust in members. Expired and revoked edges
+/// are not stored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustGraph {
+    /// truster -> trustee -> expiry_time
+    outgoing: BTreeMap<Address, BTreeMap<Address, u64>>,
+    /// trustee -> trusters
+    incoming: BTreeMap<Address, BTreeSet<Address>>,
+}
+
+impl TrustGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Graph of every relation still valid at `now`.
+    pub fn from_relations(relations: &[TrustRelation], now: u64) -> Self {
+        let mut graph = Self::new();
+        for rel in relations.iter().filter(|rel| rel.expiry_time > now) {
+            graph.insert(rel.truster, rel.trustee, rel.expiry_time);
+        }
+        graph
+    }
+
+    /// Graph of a snapshot's relations as of its own timestamp.
+    pub fn from_snapshot(snapshot: &NetworkSnapshot) -> Self {
+        Self::from_relations(&snapshot.trust_relations, snapshot.timestamp)
+    }
+
+    /// Add or update `truster -> trustee`.
+    pub fn insert(&mut self, truster: Address, trustee: Address, expiry_time: u64) {
+        if truster == trustee {
+            return;
+        }
+        self.outgoing
+            .entry(truster)
+            .or_default()
+            .insert(trustee, expiry_time);
+        self.incoming.entry(trustee).or_default().insert(truster);
+    }
+
+    /// Drop `truster -> trustee`, if present.
+    pub fn remove(&mut self, truster: Address, trustee: Address) {
+        if let Some(out) = self.outgoing.get_mut(&truster) {
+            out.remove(&trustee);
+            if out.is_empty() {
+                self.outgoing.remove(&truster);
+            }
+        }
+        if let Some(inc) = self.incoming.get_mut(&trustee) {
+            inc.remove(&truster);
+            if inc.is_empty() {
+                self.incoming.remove(&trustee);
+            }
+        }
+    }
+
+    /// Drop every edge that expires at or before `now`.
+    pub fn prune_expired(&mut self, now: u64) {
+        let expired: Vec<_> = self
+            .edges()
+            .filter(|(_, _, expiry)| *expiry <= now)
+            .map(|(truster, trustee, _)| (truster, trustee))
+            .collect();
+        for (truster, trustee) in expired {
+            self.remove(truster, trustee);
+        }
+    }
+
+    /// Whether `truster` currently trusts `trustee`.
+    pub fn is_trusting(&self, truster: Address, trustee: Address) -> bool {
+        self.outgoing
+            .get(&truster)
+            .is_some_and(|out| out.contains_key(&trustee))
+    }
+
+    /// Avatars `avatar` trusts.
+    pub fn trusts(&self, avatar: Address) -> impl Iterator<Item = Address> + '_ {
+        self.outgoing
+            .get(&avatar)
+            .into_iter()
+            .flat_map(|out| out.keys().copied())
+    }
+
+    /// Avatars trusting `avatar`.
+    pub fn trusted_by(&self, avatar: Address) -> impl Iterator<Item = Address> + '_ {
+        self.incoming
+            .get(&avatar)
+            .into_iter()
+            .flat_map(|inc| inc.iter().copied())
+    }
+
+    /// Every avatar with at least one edge, in address order.
+    pub fn avatars(&self) -> BTreeSet<Address> {
+        self.outgoing
+            .keys()
+            .chain(self.incoming.keys())
+            .copied()
+            .collect()
+    }
+
+    /// All edges as `(truster, trustee, expiry_time)`.
+    pub fn edges(&self) -> impl Iterator<Item = (Address, Address, u64)> + '_ {
+        self.outgoing.iter().flat_map(|(truster, out)| {
+            out.iter()
+                .map(move |(trustee, expiry)| (*truster, *trustee, *expiry))
+        })
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.outgoing.values().map(BTreeMap::len).sum()
+    }
+}
+
+/// When `event` happened; events without a timestamp (e.g. live ones) are treated as
+/// happening now, so an expiry in the past still reads as a revocation.
+fn event_time(event: &CirclesEvent) -> u64 {
+    event.base.timestamp.unwrap_or_else(unix_now)
+}
+
+impl Projection for TrustGraph {
+    fn on_trust(&mut self, event: &CirclesEvent, change: &TrustChange) {
+        if change.is_revocation(event_time(event)) {
+            self.remove(change.truster, change.trustee);
+        } else {
+            self.insert(change.truster, change.trustee, change.expiry_time);
+        }
+    }
+
+    fn on_membership(&mut self, event: &CirclesEvent, change: &MembershipChange) {
+        if change.expiry_time <= event_time(event) {
+            self.remove(change.group, change.member);
+        } else {
+            self.insert(change.group, change.member, change.expiry_time);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Projector;
+    use circles_rpc_mock::fixtures::circles_event;
+    use circles_types::CirclesEventType;
+    use serde_json::json;
+
+    fn trust_event(truster: Address, trustee: Address, expiry: u64) -> CirclesEvent {
+        circles_event(
+            CirclesEventType::CrcV2Trust,
+            json!({
+                "truster": truster,
+                "trustee": trustee,
+                "expiryTime": expiry.to_string(),
+            }),
+        )
+    }
+
+    #[test]
+    fn trust_events_add_and_revoke_edges() {
+        let a = Address::repeat_byte(1);
+        let b = Address::repeat_byte(2);
+        let mut projector = Projector::new(TrustGraph::new());
+        projector
+            .apply_all(&[trust_event(a, b, u64::MAX), trust_event(b, a, u64::MAX)])
+            .unwrap();
+        assert!(projector.projection().is_trusting(a, b));
+        assert_eq!(projector.projection().edge_count(), 2);
+
+        projector.apply(&trust_event(a, b, 0)).unwrap();
+        let graph = projector.into_inner();
+        assert!(!graph.is_trusting(a, b));
+        assert_eq!(graph.trusted_by(a).collect::<Vec<_>>(), vec![b]);
+        assert_eq!(graph.trusts(a).count(), 0);
+    }
+
+    #[test]
+    fn revocations_without_a_timestamp_still_remove_edges() {
+        let a = Address::repeat_byte(1);
+        let b = Address::repeat_byte(2);
+        let mut projector = Projector::new(TrustGraph::new());
+        projector.apply(&trust_event(a, b, u64::MAX)).unwrap();
+
+        // Live events carry no timestamp; the revocation expiry (the block time) is in the past.
+        let mut revoke = trust_event(a, b, 1_000);
+        revoke.base.timestamp = None;
+        projector.apply(&revoke).unwrap();
+        assert!(!projector.projection().is_trusting(a, b));
+    }
+}
@@ -0,0 +1,292 @@
+//! Canonical trust-list files, so an avatar's outgoing trust can be reviewed and kept in
+//! version control.
+
+use crate::{PreparedTransaction, SdkError, call_to_tx};
+use alloy_primitives::{Address, aliases::U96};
+use circles_abis::HubV2;
+use circles_types::TrustRelation;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Format version written by [`TrustList::to_json`].
+pub const TRUST_LIST_VERSION: u32 = 1;
+
+const CSV_HEADER: &str = "trustee,expiry";
+
+/// One trusted avatar and when the trust lapses (unix seconds).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustListEntry {
+    pub trustee: Address,
+    pub expiry: u64,
+}
+
+/// An avatar's outgoing trust, one entry per trustee, ordered by trustee address.
+///
+/// Both encodings are canonical: the same trust state always produces the same bytes,
+/// so exports diff cleanly between commits.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustList {
+    pub version: u32,
+    pub avatar: Address,
+    pub entries: Vec<TrustListEntry>,
+}
+
+impl TrustList {
+    /// Build a list from `entries`, keeping the last entry per trustee.
+    pub fn new(avatar: Address, entries: impl IntoIterator<Item = TrustListEntry>) -> Self {
+        let entries: BTreeMap<Address, u64> = entries
+            .into_iter()
+            .filter(|entry| entry.trustee != avatar)
+            .map(|entry| (entry.trustee, entry.expiry))
+            .collect();
+        Self {
+            version: TRUST_LIST_VERSION,
+            avatar,
+            entries: entries
+                .into_iter()
+                .map(|(trustee, expiry)| TrustListEntry { trustee, expiry })
+                .collect(),
+        }
+    }
+
+    /// Outgoing trust of `avatar` that is still live at `now`.
+    ///
+    /// Rows are applied in chain order, so a later `trust` call for the same trustee
+    /// replaces an earlier one even when it shortens the expiry.
+    pub fn from_relations(avatar: Address, relations: &[TrustRelation], now: u64) -> Self {
+        let mut outgoing: Vec<&TrustRelation> = relations
+            .iter()
+            .filter(|rel| rel.truster == avatar)
+            .collect();
+        outgoing.sort_by_key(|rel| (rel.block_number, rel.transaction_index, rel.log_index));
+        let mut list = Self::new(
+            avatar,
+            outgoing.into_iter().map(|rel| TrustListEntry {
+                trustee: rel.trustee,
+                expiry: rel.expiry_time,
+            }),
+        );
+        list.entries.retain(|entry| entry.expiry > now);
+        list
+    }
+
+    /// Expiry of the trust given to `trustee`, if listed.
+    pub fn expiry_of(&self, trustee: Address) -> Option<u64> {
+        self.entries
+            .binary_search_by_key(&trustee, |entry| entry.trustee)
+            .ok()
+            .map(|index| self.entries[index].expiry)
+    }
+
+    /// Pretty-printed JSON including the avatar and format version.
+    pub fn to_json(&self) -> Result<String, SdkError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| SdkError::OperationFailed(format!("trust list encode failed: {e}")))
+    }
+
+    /// Parse a JSON list, rejecting versions this SDK does not understand.
+    pub fn from_json(json: &str) -> Result<Self, SdkError> {
+        let list: Self = serde_json::from_str(json)
+            .map_err(|e| SdkError::OperationFailed(format!("invalid trust list: {e}")))?;
+        if list.version != TRUST_LIST_VERSION {
+            return Err(SdkError::OperationFailed(format!(
+                "unsupported trust list version {} (expected {TRUST_LIST_VERSION})",
+                list.version
+            )));
+        }
+        Ok(Self::new(list.avatar, list.entries))
+    }
+
+    /// `trustee,expiry` rows with lowercase addresses; the avatar is not part of the CSV.
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("{CSV_HEADER}\n");
+        for entry in &self.entries {
+            let _ = writeln!(csv, "{:#x},{}", entry.trustee, entry.expiry);
+        }
+        csv
+    }
+
+    /// Parse `trustee,expiry` rows for `avatar`. The header and blank lines are optional.
+    pub fn from_csv(avatar: Address, csv: &str) -> Result<Self, SdkError> {
+        let mut entries = Vec::new();
+        for (index, line) in csv.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || (index == 0 && line == CSV_HEADER) {
+                continue;
+            }
+            let invalid = |reason: &str| {
+                SdkError::OperationFailed(format!(
+                    "invalid trust list line {}: {reason}",
+                    index + 1
+                ))
+            };
+            let (trustee, expiry) = line
+                .split_once(',')
+                .ok_or_else(|| invalid("expected `trustee,expiry`"))?;
+            entries.push(TrustListEntry {
+                trustee: trustee
+                    .trim()
+                    .parse()
+                    .map_err(|_| invalid("bad trustee address"))?,
+                expiry: expiry.trim().parse().map_err(|_| invalid("bad expiry"))?,
+            });
+        }
+        Ok(Self::new(avatar, entries))
+    }
+}
+
+/// How [`trust_import`](crate::HumanAvatar::trust_import) treats trust missing from the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrustImportPolicy {
+    /// Trust new entries and update changed expiries; leave unlisted trust alone.
+    Merge,
+    /// Also untrust every avatar the file does not list.
+    Replace,
+}
+
+/// Changes needed to bring current trust in line with a [`TrustList`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustImportPlan {
+    /// Listed but not currently trusted.
+    pub add: Vec<TrustListEntry>,
+    /// Trusted with a different expiry than listed.
+    pub update: Vec<TrustListEntry>,
+    /// Trusted but unlisted; only filled under [`TrustImportPolicy::Replace`].
+    pub remove: Vec<Address>,
+}
+
+impl TrustImportPlan {
+    /// Diff `current` trust against `target`. Target entries that expired by `now` are
+    /// treated as unlisted, since trusting them would be a removal on-chain.
+    pub fn compute(
+        current: &TrustList,
+        target: &TrustList,
+        policy: TrustImportPolicy,
+        now: u64,
+    ) -> Self {
+        let mut plan = Self::default();
+        for entry in target.entries.iter().filter(|entry| entry.expiry > now) {
+            match current.expiry_of(entry.trustee) {
+                None => plan.add.push(*entry),
+                Some(expiry) if expiry != entry.expiry => plan.update.push(*entry),
+                Some(_) => {}
+            }
+        }
+        if policy == TrustImportPolicy::Replace {
+            plan.remove = current
+                .entries
+                .iter()
+                .map(|entry| entry.trustee)
+                .filter(|trustee| {
+                    target
+                        .expiry_of(*trustee)
+                        .is_none_or(|expiry| expiry <= now)
+                })
+                .collect();
+        }
+        plan
+    }
+
+    /// Whether current trust already matches the list.
+    pub fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.update.is_empty() && self.remove.is_empty()
+    }
+
+    /// `trust(address,uint96)` calls on `trust_contract`: adds, then updates, then removals.
+    pub fn txs(&self, trust_contract: Address) -> Vec<PreparedTransaction> {
+        let set = self
+            .add
+            .iter()
+            .chain(&self.update)
+            .map(|entry| (entry.trustee, u128::from(entry.expiry)));
+        let removes = self.remove.iter().map(|trustee| (*trustee, 0));
+        set.chain(removes)
+            .map(|(trustee, expiry)| {
+                let call = HubV2::trustCall {
+                    _trustReceiver: trustee,
+                    _expiry: U96::from(expiry),
+                };
+                call_to_tx(trust_contract, call, None)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::TxHash;
+
+    fn entry(byte: u8, expiry: u64) -> TrustListEntry {
+        TrustListEntry {
+            trustee: Address::repeat_byte(byte),
+            expiry,
+        }
+    }
+
+    #[test]
+    fn exports_are_canonical_and_round_trip() {
+        let me = Address::repeat_byte(0xaa);
+        let relation = |block_number: u64, trustee: u8, expiry_time: u64| TrustRelation {
+            block_number,
+            timestamp: 0,
+            transaction_index: 0,
+            log_index: 0,
+            transaction_hash: TxHash::ZERO,
+            truster: me,
+            trustee: Address::repeat_byte(trustee),
+            expiry_time,
+        };
+        let list = TrustList::from_relations(
+            me,
+            &[
+                relation(2, 0x02, 5_000),
+                relation(1, 0x02, 9_000),
+                relation(1, 0x01, 7_000),
+                relation(1, 0x03, 50),
+            ],
+            100,
+        );
+        assert_eq!(list.entries, vec![entry(0x01, 7_000), entry(0x02, 5_000)]);
+
+        let csv = list.to_csv();
+        assert_eq!(
+            csv,
+            format!(
+                "trustee,expiry\n{:#x},7000\n{:#x},5000\n",
+                Address::repeat_byte(0x01),
+                Address::repeat_byte(0x02)
+            )
+        );
+        assert_eq!(TrustList::from_csv(me, &csv).unwrap(), list);
+        assert_eq!(
+            TrustList::from_json(&list.to_json().unwrap()).unwrap(),
+            list
+        );
+        assert!(TrustList::from_csv(me, "trustee,expiry\nnot-an-address,1\n").is_err());
+    }
+
+    #[test]
+    fn import_plan_follows_policy() {
+        let me = Address::repeat_byte(0xaa);
+        let current = TrustList::new(me, [entry(0x01, 1_000), entry(0x02, 1_000)]);
+        let target = TrustList::new(
+            me,
+            [entry(0x02, 2_000), entry(0x03, 1_000), entry(0x04, 10)],
+        );
+
+        let merge = TrustImportPlan::compute(&current, &target, TrustImportPolicy::Merge, 100);
+        assert_eq!(merge.add, vec![entry(0x03, 1_000)]);
+        assert_eq!(merge.update, vec![entry(0x02, 2_000)]);
+        assert!(merge.remove.is_empty());
+
+        let replace = TrustImportPlan::compute(&current, &target, TrustImportPolicy::Replace, 100);
+        assert_eq!(replace.remove, vec![Address::repeat_byte(0x01)]);
+        assert_eq!(replace.txs(me).len(), 3);
+        assert!(
+            TrustImportPlan::compute(&current, &current, TrustImportPolicy::Replace, 100)
+                .is_empty()
+        );
+    }
+}
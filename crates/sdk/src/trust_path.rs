@@ -0,0 +1,81 @@
+//! Trust-chain search between two avatars, independent of balances.
+
+use crate::SdkError;
+use alloy_primitives::Address;
+use circles_rpc::CirclesRpc;
+use circles_types::TrustRelationType;
+use std::collections::{HashMap, VecDeque};
+
+/// One hop of a trust chain: `to` trusts `from`, so `from`'s tokens are accepted by `to`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrustHop {
+    pub from: Address,
+    pub to: Address,
+    /// When the relation behind this hop expires (unix seconds); for mutual trust, the
+    /// earlier of both directions.
+    pub expiry_time: u64,
+}
+
+/// Breadth-first search over aggregated trust relations, expanding from `from` towards
+/// avatars that trust the current one. Returns the shortest chain of at most `max_hops`
+/// hops, or `None` when `to` is not reachable within that bound.
+pub(crate) async fn find_trust_path(
+    rpc: &CirclesRpc,
+    from: Address,
+    to: Address,
+    max_hops: usize,
+) -> Result<Option<Vec<TrustHop>>, SdkError> {
+    if from == to {
+        return Ok(Some(Vec::new()));
+    }
+
+    // Visited avatar -> hop that first reached it.
+    let mut reached: HashMap<Address, TrustHop> = HashMap::new();
+    let mut queue = VecDeque::from([(from, 0usize)]);
+    while let Some((current, depth)) = queue.pop_front() {
+        if depth >= max_hops {
+            continue;
+        }
+        let relations = rpc
+            .trust()
+            .compute_aggregated_trust_relations(current)
+            .await?;
+        let trusted_by = relations.into_iter().filter(|rel| {
+            matches!(
+                rel.relation,
+                TrustRelationType::TrustedBy | TrustRelationType::MutuallyTrusts
+            )
+        });
+        for rel in trusted_by {
+            let next = rel.object_avatar;
+            if next == from || reached.contains_key(&next) {
+                continue;
+            }
+            reached.insert(
+                next,
+                TrustHop {
+                    from: current,
+                    to: next,
+                    expiry_time: rel.expiry_time,
+                },
+            );
+            if next == to {
+                return Ok(Some(unwind(&reached, from, to)));
+            }
+            queue.push_back((next, depth + 1));
+        }
+    }
+    Ok(None)
+}
+
+fn unwind(reached: &HashMap<Address, TrustHop>, from: Address, to: Address) -> Vec<TrustHop> {
+    let mut hops = Vec::new();
+    let mut cursor = to;
+    while cursor != from {
+        let hop = reached[&cursor];
+        hops.push(hop);
+        cursor = hop.from;
+    }
+    hops.reverse();
+    hops
+}
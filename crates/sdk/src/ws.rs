@@ -1,16 +1,67 @@
 //! WebSocket helpers for SDK event subscriptions.
 //!
 //! These functions sit on top of `circles-rpc` and provide the light retry/catch-up
-//! behavior used by the SDK public API.
+//! behavior used by the SDK public API. [`WsHub`] shares one connection across
+//! subscribers instead of opening a socket per subscription.
 
 use crate::SdkError;
-use circles_rpc::{CirclesRpc, events::subscription::CirclesSubscription};
-use circles_types::{CirclesEvent, Filter};
-use futures::StreamExt;
+use circles_rpc::{CirclesRpc, EventStream, events::subscription::CirclesSubscription};
+use circles_types::{CirclesEvent, EventCursor, Filter};
+use futures::{Stream, StreamExt};
 use serde_json::Value;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::mpsc;
 use tokio::time::sleep;
 use tracing::warn;
 
+/// Counter of WebSocket retries, labelled by `stage` (`connect`/`subscribe`); emitted
+/// with the `metrics` feature.
+pub const WS_RETRIES_TOTAL: &str = "circles_ws_retries_total";
+/// Counter of upstream subscriptions closed under a [`WsHub`]; emitted with the
+/// `metrics` feature.
+pub const WS_HUB_DISCONNECTS_TOTAL: &str = "circles_ws_hub_disconnects_total";
+
+fn record_retry(stage: &'static str) {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!(WS_RETRIES_TOTAL, "stage" => stage).increment(1);
+    #[cfg(not(feature = "metrics"))]
+    let _ = stage;
+}
+
+fn retry_delay(attempt: usize) -> Duration {
+    let delay_ms = (1u64 << attempt.min(16)).saturating_mul(1000).min(10_000);
+    Duration::from_millis(delay_ms)
+}
+
+async fn connect_with_retries(
+    ws_url: &str,
+    max_attempts: Option<usize>,
+) -> Result<CirclesRpc, SdkError> {
+    let cap = max_attempts.unwrap_or(5);
+    let mut attempt = 0usize;
+    loop {
+        attempt += 1;
+        match CirclesRpc::try_from_ws(ws_url).await {
+            Ok(ws) => return Ok(ws),
+            Err(_err) if attempt < cap => {
+                record_retry("connect");
+                sleep(retry_delay(attempt)).await;
+            }
+            Err(err) => {
+                return Err(SdkError::WsSubscribeFailed {
+                    attempts: attempt,
+                    reason: err.to_string(),
+                });
+            }
+        }
+    }
+}
+
 /// Subscribe with retry/backoff on websocket disconnects.
 pub async fn subscribe_with_retries(
     ws_url: &str,
@@ -24,8 +75,8 @@ pub async fn subscribe_with_retries(
         let ws = match CirclesRpc::try_from_ws(ws_url).await {
             Ok(ws) => ws,
             Err(_err) if attempt < cap => {
-                let delay_ms = (1u64 << attempt).saturating_mul(1000).min(10_000);
-                sleep(std::time::Duration::from_millis(delay_ms)).await;
+                record_retry("connect");
+                sleep(retry_delay(attempt)).await;
                 continue;
             }
             Err(err) => {
@@ -39,8 +90,8 @@ pub async fn subscribe_with_retries(
         match sub_res {
             Ok(sub) => return Ok(sub),
             Err(_err) if attempt < cap => {
-                let delay_ms = (1u64 << attempt).saturating_mul(1000).min(10_000);
-                sleep(std::time::Duration::from_millis(delay_ms)).await;
+                record_retry("subscribe");
+                sleep(retry_delay(attempt)).await;
             }
             Err(err) => {
                 return Err(SdkError::WsSubscribeFailed {
@@ -76,6 +127,35 @@ pub async fn subscribe_with_catchup(
     Ok((catch_up_events, sub))
 }
 
+/// Resume a consumer at `cursor`: fetch missed events over HTTP, then continue live.
+///
+/// The live subscription is opened before the backfill so nothing falls in between;
+/// events seen by both are yielded once. Each event comes with the cursor to persist.
+pub async fn subscribe_from_cursor(
+    rpc: &CirclesRpc,
+    ws_url: &str,
+    filter: Value,
+    max_attempts: Option<usize>,
+    cursor: EventCursor,
+    catch_up_filter: Option<Vec<Filter>>,
+    address: Option<circles_types::Address>,
+) -> Result<EventStream<(EventCursor, CirclesEvent)>, SdkError> {
+    let live = subscribe_with_retries(ws_url, filter, max_attempts).await?;
+    let mut missed = rpc
+        .events()
+        .circles_events(address, cursor.block_number(), None, catch_up_filter)
+        .await?;
+    missed.sort_by_key(|event| {
+        (
+            event.base.block_number,
+            event.base.transaction_index,
+            event.base.log_index,
+        )
+    });
+    let events = futures::stream::iter(missed.into_iter().map(Ok)).chain(live);
+    Ok(EventStream::new(events).resume_from(cursor))
+}
+
 /// Spawn a background handler for a live event subscription.
 ///
 /// Stream errors are logged at `warn` and do not stop the task from receiving
@@ -99,3 +179,284 @@ where
         }
     })
 }
+
+type Subscribers = Arc<Mutex<Vec<mpsc::UnboundedSender<CirclesEvent>>>>;
+
+struct SharedFilter {
+    id: u64,
+    refs: usize,
+    subscribers: Subscribers,
+    task: tokio::task::JoinHandle<()>,
+}
+
+struct WsHubInner {
+    ws_url: String,
+    max_attempts: Option<usize>,
+    connection: tokio::sync::Mutex<Option<Arc<CirclesRpc>>>,
+    filters: Mutex<HashMap<String, SharedFilter>>,
+    next_id: AtomicU64,
+}
+
+impl WsHubInner {
+    fn release(&self, key: &str, id: u64) {
+        let mut filters = self.filters.lock().expect("lock");
+        if let Some(shared) = filters.get_mut(key).filter(|shared| shared.id == id) {
+            shared.refs -= 1;
+            if shared.refs == 0 {
+                // Aborting drops the upstream subscription, which unsubscribes.
+                shared.task.abort();
+                filters.remove(key);
+            }
+        }
+    }
+
+    fn attach(&self, key: &str) -> Option<(u64, mpsc::UnboundedReceiver<CirclesEvent>)> {
+        let mut filters = self.filters.lock().expect("lock");
+        let shared = filters.get_mut(key)?;
+        let (tx, rx) = mpsc::unbounded_channel();
+        shared.subscribers.lock().expect("lock").push(tx);
+        shared.refs += 1;
+        Some((shared.id, rx))
+    }
+}
+
+/// Multiplexes event subscriptions over a single WebSocket connection.
+///
+/// Subscribers asking for the same filter share one upstream `eth_subscribe`; each gets
+/// its own channel. The upstream subscription is dropped when the last subscriber goes
+/// away, and the connection is re-established lazily after it closes.
+#[derive(Clone)]
+pub struct WsHub {
+    inner: Arc<WsHubInner>,
+}
+
+impl WsHub {
+    /// Create a hub for `ws_url`; the connection is opened on first subscribe.
+    pub fn new(ws_url: impl Into<String>) -> Self {
+        Self::with_max_attempts(ws_url, None)
+    }
+
+    /// Create a hub with a custom connect/subscribe retry cap (default 5).
+    pub fn with_max_attempts(ws_url: impl Into<String>, max_attempts: Option<usize>) -> Self {
+        Self {
+            inner: Arc::new(WsHubInner {
+                ws_url: ws_url.into(),
+                max_attempts,
+                connection: tokio::sync::Mutex::new(None),
+                filters: Mutex::new(HashMap::new()),
+                next_id: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Number of distinct upstream subscriptions currently open.
+    pub fn active_filters(&self) -> usize {
+        self.inner.filters.lock().expect("lock").len()
+    }
+
+    /// Subscribe to events matching `filter`, sharing the upstream subscription with
+    /// any existing subscriber using the same filter.
+    pub async fn subscribe(&self, filter: Value) -> Result<WsHubSubscription, SdkError> {
+        let key = filter.to_string();
+        if let Some((id, rx)) = self.inner.attach(&key) {
+            return Ok(self.subscription(key, id, rx));
+        }
+
+        let upstream = self.open_upstream(filter).await?;
+        {
+            let mut filters = self.inner.filters.lock().expect("lock");
+            if !filters.contains_key(&key) {
+                let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+                let subscribers: Subscribers = Arc::default();
+                let task = tokio::spawn(forward_events(
+                    Arc::downgrade(&self.inner),
+                    key.clone(),
+                    id,
+                    upstream,
+                    subscribers.clone(),
+                ));
+                filters.insert(
+                    key.clone(),
+                    SharedFilter {
+                        id,
+                        refs: 0,
+                        subscribers,
+                        task,
+                    },
+                );
+            }
+            // Otherwise another caller won the race; `upstream` is dropped and unsubscribed.
+        }
+        let (id, rx) = self
+            .inner
+            .attach(&key)
+            .expect("shared filter was just registered");
+        Ok(self.subscription(key, id, rx))
+    }
+
+    async fn open_upstream(
+        &self,
+        filter: Value,
+    ) -> Result<CirclesSubscription<CirclesEvent>, SdkError> {
+        let mut connection = self.inner.connection.lock().await;
+        let rpc = match connection.as_ref() {
+            Some(rpc) => rpc.clone(),
+            None => {
+                let rpc = Arc::new(
+                    connect_with_retries(&self.inner.ws_url, self.inner.max_attempts).await?,
+                );
+                *connection = Some(rpc.clone());
+                rpc
+            }
+        };
+        match rpc.events().subscribe_parsed_events(filter).await {
+            Ok(sub) => Ok(sub),
+            Err(err) => {
+                // Force a reconnect next time in case the socket is gone.
+                *connection = None;
+                Err(SdkError::WsSubscribeFailed {
+                    attempts: 1,
+                    reason: err.to_string(),
+                })
+            }
+        }
+    }
+
+    fn subscription(
+        &self,
+        key: String,
+        id: u64,
+        rx: mpsc::UnboundedReceiver<CirclesEvent>,
+    ) -> WsHubSubscription {
+        WsHubSubscription {
+            rx,
+            key,
+            id,
+            hub: Arc::downgrade(&self.inner),
+        }
+    }
+}
+
+async fn forward_events(
+    hub: std::sync::Weak<WsHubInner>,
+    key: String,
+    id: u64,
+    mut upstream: CirclesSubscription<CirclesEvent>,
+    subscribers: Subscribers,
+) {
+    while let Some(item) = upstream.next().await {
+        match item {
+            Ok(evt) => subscribers
+                .lock()
+                .expect("lock")
+                .retain(|tx| tx.send(evt.clone()).is_ok()),
+            Err(err) => warn!(error = %err, "ws hub event stream error"),
+        }
+    }
+    // Upstream closed: end every subscriber stream and let the next subscribe reconnect.
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!(WS_HUB_DISCONNECTS_TOTAL).increment(1);
+    subscribers.lock().expect("lock").clear();
+    if let Some(hub) = hub.upgrade() {
+        {
+            let mut filters = hub.filters.lock().expect("lock");
+            if filters.get(&key).is_some_and(|shared| shared.id == id) {
+                filters.remove(&key);
+            }
+        }
+        *hub.connection.lock().await = None;
+    }
+}
+
+/// Per-subscriber event stream handed out by [`WsHub::subscribe`].
+pub struct WsHubSubscription {
+    rx: mpsc::UnboundedReceiver<CirclesEvent>,
+    key: String,
+    id: u64,
+    hub: std::sync::Weak<WsHubInner>,
+}
+
+impl Stream for WsHubSubscription {
+    type Item = CirclesEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl Drop for WsHubSubscription {
+    fn drop(&mut self) {
+        if let Some(hub) = self.hub.upgrade() {
+            hub.release(&self.key, self.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn register(hub: &WsHub, key: &str) -> u64 {
+        let id = hub.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        hub.inner.filters.lock().expect("lock").insert(
+            key.to_string(),
+            SharedFilter {
+                id,
+                refs: 0,
+                subscribers: Arc::default(),
+                task: tokio::spawn(std::future::pending()),
+            },
+        );
+        id
+    }
+
+    #[tokio::test]
+    async fn hub_reference_counts_shared_filters() {
+        let hub = WsHub::new("ws://localhost:0");
+        let id = register(&hub, "filter");
+
+        let (_, rx_a) = hub.inner.attach("filter").expect("attach a");
+        let (_, rx_b) = hub.inner.attach("filter").expect("attach b");
+        let sub_a = hub.subscription("filter".into(), id, rx_a);
+        let sub_b = hub.subscription("filter".into(), id, rx_b);
+        assert_eq!(hub.active_filters(), 1);
+
+        drop(sub_a);
+        assert_eq!(hub.active_filters(), 1);
+        drop(sub_b);
+        assert_eq!(hub.active_filters(), 0);
+    }
+
+    #[tokio::test]
+    async fn hub_fans_events_out_to_each_subscriber() {
+        let hub = WsHub::new("ws://localhost:0");
+        let id = register(&hub, "filter");
+        let (_, rx_a) = hub.inner.attach("filter").expect("attach a");
+        let (_, rx_b) = hub.inner.attach("filter").expect("attach b");
+        let mut sub_a = hub.subscription("filter".into(), id, rx_a);
+        let mut sub_b = hub.subscription("filter".into(), id, rx_b);
+
+        let event = CirclesEvent {
+            base: circles_types::CirclesBaseEvent {
+                block_number: 1,
+                timestamp: None,
+                transaction_index: 0,
+                log_index: 0,
+                transaction_hash: None,
+            },
+            event_type: circles_types::CirclesEventType::CrcV2Trust,
+            data: HashMap::new(),
+        };
+        let subscribers = hub.inner.filters.lock().expect("lock")["filter"]
+            .subscribers
+            .clone();
+        subscribers
+            .lock()
+            .expect("lock")
+            .iter()
+            .for_each(|tx| tx.send(event.clone()).expect("send"));
+
+        assert!(sub_a.next().await.is_some());
+        assert!(sub_b.next().await.is_some());
+    }
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "e2e")]
+
+mod common;
+
+use alloy_primitives::U256;
+use alloy_provider::Provider;
+use circles_sdk::e2e::{ForkOptions, ForkedGnosis, anvil_available};
+
+fn fork() -> Option<ForkedGnosis> {
+    if !anvil_available() {
+        eprintln!("skipping e2e test: `anvil` is not installed");
+        return None;
+    }
+    let Some(cfg) = common::maybe_live_config() else {
+        eprintln!("skipping e2e test: set RUN_LIVE=1");
+        return None;
+    };
+    let fork = ForkedGnosis::spawn(ForkOptions {
+        config: Some(cfg),
+        ..Default::default()
+    })
+    .expect("spawn forked anvil");
+    Some(fork)
+}
+
+#[tokio::test]
+#[ignore]
+async fn e2e_impersonated_avatar_is_funded_for_gas() -> Result<(), Box<dyn std::error::Error>> {
+    let Some(fork) = fork() else {
+        return Ok(());
+    };
+    let Some(avatar) = common::maybe_live_avatar() else {
+        eprintln!("skipping e2e test: set LIVE_AVATAR=0x...");
+        return Ok(());
+    };
+
+    fork.impersonate(avatar).await?;
+    let balance = fork.provider().get_balance(avatar).await?;
+    assert!(balance > U256::ZERO);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn e2e_self_transfer_executes_on_fork() -> Result<(), Box<dyn std::error::Error>> {
+    let Some(fork) = fork() else {
+        return Ok(());
+    };
+    let Some(avatar) = common::maybe_live_avatar() else {
+        eprintln!("skipping e2e test: set LIVE_AVATAR=0x...");
+        return Ok(());
+    };
+
+    // 1 CRC self-transfer: exercises unwrap/operateFlowMatrix/rewrap without moving value.
+    let amount = U256::from(10u64).pow(U256::from(18u64));
+    let submitted = fork.execute_transfer(avatar, avatar, amount, None).await?;
+    assert!(!submitted.is_empty());
+    assert!(submitted.iter().all(|tx| tx.success));
+    Ok(())
+}
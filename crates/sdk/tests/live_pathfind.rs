@@ -30,6 +30,7 @@ async fn live_max_flow_self() -> Result<(), Box<dyn std::error::Error>> {
         simulated_trusts: None,
         max_transfers: None,
         tx_data: None,
+        validate_path: None,
     };
 
     if let circles_sdk::Avatar::Human(h) = avatar {
@@ -0,0 +1,648 @@
+use alloy_primitives::{Address, U256, address};
+use circles_rpc_mock::{MockRpcServer, fixtures};
+use circles_sdk::{Avatar, ErrorKind, NameMatch, NameSource, Resolution, Sdk, config};
+use circles_types::AvatarType;
+use std::time::Duration;
+
+const ALICE: Address = address!("1000000000000000000000000000000000000001");
+const BOB: Address = address!("2000000000000000000000000000000000000002");
+
+#[tokio::test]
+async fn avatar_find_path_against_mock_rpc() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockRpcServer::builder()
+        .respond(
+            "circles_getAvatarInfo",
+            fixtures::avatar_info(ALICE, AvatarType::CrcV2RegisterHuman),
+        )
+        .respond(
+            "circlesV2_findPath",
+            fixtures::path_result(&[(ALICE, BOB, ALICE, U256::from(42u64))]),
+        )
+        .start()
+        .await?;
+
+    let mut cfg = config::gnosis_mainnet();
+    cfg.circles_rpc_url = server.url();
+    let sdk = Sdk::new(cfg, None)?;
+
+    let Avatar::Human(alice) = sdk.get_avatar(ALICE).await? else {
+        panic!("expected a human avatar");
+    };
+    let path = alice.find_path(BOB, U256::from(42u64), None).await?;
+    assert_eq!(path.max_flow, U256::from(42u64));
+    assert_eq!(path.transfers.len(), 1);
+
+    let sent = server.params_for("circlesV2_findPath");
+    assert_eq!(sent.len(), 1);
+    let source: Address = sent[0][0]["Source"].as_str().expect("source").parse()?;
+    assert_eq!(source, ALICE);
+    assert_eq!(sent[0][0]["UseWrappedBalances"], true);
+    Ok(())
+}
+
+fn trust_row(truster: Address, trustee: Address) -> serde_json::Value {
+    serde_json::json!({
+        "block_number": 1,
+        "timestamp": 1,
+        "transaction_index": 0,
+        "log_index": 0,
+        "transaction_hash": format!("0x{}", "00".repeat(32)),
+        "truster": format!("{truster:#x}"),
+        "trustee": format!("{trustee:#x}"),
+        "expiry_time": u64::MAX
+    })
+}
+
+#[tokio::test]
+async fn trust_path_follows_incoming_trust() -> Result<(), Box<dyn std::error::Error>> {
+    const CAROL: Address = address!("3000000000000000000000000000000000000003");
+    // BOB trusts ALICE, CAROL trusts BOB; ALICE trusting CAROL is a dead end.
+    let server = MockRpcServer::builder()
+        .respond_to(
+            "circles_getTrustRelations",
+            serde_json::json!([format!("{ALICE:#x}")]),
+            serde_json::json!([trust_row(BOB, ALICE), trust_row(ALICE, CAROL)]),
+        )
+        .respond_to(
+            "circles_getTrustRelations",
+            serde_json::json!([format!("{BOB:#x}")]),
+            serde_json::json!([trust_row(BOB, ALICE), trust_row(CAROL, BOB)]),
+        )
+        .start()
+        .await?;
+
+    let mut cfg = config::gnosis_mainnet();
+    cfg.circles_rpc_url = server.url();
+    let sdk = Sdk::new(cfg, None)?;
+
+    let path = sdk.trust_path(ALICE, CAROL, 3).await?.expect("path");
+    let hops: Vec<_> = path.iter().map(|hop| (hop.from, hop.to)).collect();
+    assert_eq!(hops, vec![(ALICE, BOB), (BOB, CAROL)]);
+
+    assert!(sdk.trust_path(ALICE, CAROL, 1).await?.is_none());
+    Ok(())
+}
+
+fn origin(address: Address, inviter: Option<Address>, timestamp: u64) -> serde_json::Value {
+    serde_json::json!({
+        "address": address,
+        "invitationType": "v2_standard",
+        "inviter": inviter,
+        "proxyInviter": null,
+        "escrowAmount": null,
+        "blockNumber": 1,
+        "timestamp": timestamp,
+        "transactionHash": format!("0x{}", "00".repeat(32)),
+        "version": 2
+    })
+}
+
+#[tokio::test]
+async fn assess_avatar_reports_age_invite_chain_and_velocity()
+-> Result<(), Box<dyn std::error::Error>> {
+    const CAROL: Address = address!("3000000000000000000000000000000000000003");
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs();
+    let transfer = |from: Address, to: Address, timestamp: u64| {
+        serde_json::json!({
+            "blockNumber": 1,
+            "timestamp": timestamp,
+            "transactionIndex": 0,
+            "logIndex": 0,
+            "transactionHash": format!("0x{}", "00".repeat(32)),
+            "version": 2,
+            "from": from,
+            "to": to,
+            "tokenAddress": from,
+            "value": "1000000000000000000",
+            "circles": 1.0
+        })
+    };
+    // ALICE was invited by BOB, who was invited by CAROL, who registered uninvited.
+    let server = MockRpcServer::builder()
+        .respond(
+            "circles_getAvatarInfo",
+            fixtures::avatar_info(ALICE, AvatarType::CrcV2RegisterHuman),
+        )
+        .respond_to(
+            "circles_getInvitationOrigin",
+            serde_json::json!([format!("{ALICE:#x}")]),
+            origin(ALICE, Some(BOB), now - 86_400),
+        )
+        .respond_to(
+            "circles_getInvitationOrigin",
+            serde_json::json!([format!("{BOB:#x}")]),
+            origin(BOB, Some(CAROL), now - 2 * 86_400),
+        )
+        .respond_to(
+            "circles_getInvitationOrigin",
+            serde_json::json!([format!("{CAROL:#x}")]),
+            origin(CAROL, None, now - 3 * 86_400),
+        )
+        .respond(
+            "circles_getTrustRelations",
+            serde_json::json!([
+                trust_row(BOB, ALICE),
+                trust_row(ALICE, BOB),
+                trust_row(CAROL, ALICE)
+            ]),
+        )
+        .respond(
+            "circles_getTransactionHistory",
+            serde_json::json!({
+                "results": [
+                    transfer(BOB, ALICE, now - 60),
+                    transfer(ALICE, CAROL, now - 120),
+                    transfer(CAROL, ALICE, now - 400 * 86_400),
+                ],
+                "hasMore": false,
+                "nextCursor": null
+            }),
+        )
+        .start()
+        .await?;
+
+    let mut cfg = config::gnosis_mainnet();
+    cfg.circles_rpc_url = server.url();
+    let sdk = Sdk::new(cfg, None)?;
+
+    let report = sdk.assess_avatar(ALICE).await?;
+    assert_eq!(report.invite_chain, vec![BOB, CAROL]);
+    assert_eq!(report.invite_depth(), 2);
+    assert!(!report.invite_chain_truncated);
+    assert!(report.account_age_secs.is_some_and(|age| age >= 86_400));
+    assert_eq!((report.trusted_by_count, report.trusts_count), (2, 1));
+    assert!(report.mutual_connections.is_none());
+    assert_eq!(report.velocity.transfers_in, 1);
+    assert_eq!(report.velocity.transfers_out, 1);
+    assert_eq!(report.velocity.counterparties, 2);
+    assert!(!report.velocity.truncated);
+    Ok(())
+}
+
+#[tokio::test]
+async fn unregistered_address_is_not_found_rather_than_a_decode_error()
+-> Result<(), Box<dyn std::error::Error>> {
+    let server = MockRpcServer::builder()
+        .respond("circles_getAvatarInfo", serde_json::Value::Null)
+        .respond("circles_getAvatarInfo", serde_json::Value::Null)
+        .respond("circles_getAvatarInfo", serde_json::Value::Null)
+        .start()
+        .await?;
+    let mut cfg = config::gnosis_mainnet();
+    cfg.circles_rpc_url = server.url();
+    let sdk = Sdk::new(cfg, None)?;
+
+    assert!(!sdk.is_registered(BOB).await?);
+    assert!(sdk.find_avatar(BOB).await?.is_none());
+    assert!(matches!(
+        sdk.get_avatar(BOB).await,
+        Err(circles_sdk::SdkError::AvatarNotFound(addr)) if addr == BOB
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn await_indexed_polls_until_the_indexer_reaches_the_block()
+-> Result<(), Box<dyn std::error::Error>> {
+    let indexed = |block: u64| fixtures::query_result(&["blockNumber"], vec![vec![block.into()]]);
+    let server = MockRpcServer::builder()
+        .respond("circles_health", serde_json::json!({ "status": "ok" }))
+        .respond("circles_query", indexed(99))
+        .respond("circles_query", indexed(100))
+        .start()
+        .await?;
+    let mut cfg = config::gnosis_mainnet();
+    cfg.circles_rpc_url = server.url();
+    let sdk = Sdk::new(cfg, None)?;
+
+    let block = sdk.await_indexed(100u64, Duration::from_secs(5)).await?;
+    assert_eq!(block, 100);
+    let queries = server.params_for("circles_query");
+    assert_eq!(queries.len(), 2);
+    assert_eq!(queries[0][0]["Namespace"], "System");
+    assert_eq!(queries[0][0]["Table"], "Block");
+
+    assert!(matches!(
+        sdk.await_indexed(101u64, Duration::ZERO).await,
+        Err(circles_sdk::SdkError::IndexerTimeout {
+            block: Some(101),
+            ..
+        })
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn resolve_reports_every_exact_name_match() -> Result<(), Box<dyn std::error::Error>> {
+    const GROUP: Address = address!("a00000000000000000000000000000000000000a");
+    let server = MockRpcServer::builder()
+        .respond(
+            "circles_searchProfiles",
+            serde_json::json!([
+                { "address": ALICE, "name": "Berlin" },
+                { "address": BOB, "name": "Berlin Bakery" },
+            ]),
+        )
+        .respond(
+            "circles_query",
+            fixtures::query_result(
+                &["group", "type", "owner", "symbol"],
+                vec![vec![
+                    serde_json::json!(GROUP),
+                    serde_json::json!("Standard"),
+                    serde_json::json!(ALICE),
+                    serde_json::json!("BERLIN"),
+                ]],
+            ),
+        )
+        .respond(
+            "circles_searchProfiles",
+            serde_json::json!([{ "address": BOB, "name": "Berlin Bakery" }]),
+        )
+        .respond("circles_query", fixtures::query_result(&["group"], vec![]))
+        .start()
+        .await?;
+    let mut cfg = config::gnosis_mainnet();
+    cfg.circles_rpc_url = server.url();
+    let sdk = Sdk::new(cfg, None)?;
+
+    let Resolution::Ambiguous(candidates) = sdk.resolve("berlin").await? else {
+        panic!("expected an ambiguous name");
+    };
+    let found: Vec<_> = candidates.iter().map(|m| (m.address, m.source)).collect();
+    assert_eq!(
+        found,
+        [
+            (ALICE, NameSource::Profile),
+            (GROUP, NameSource::GroupSymbol)
+        ]
+    );
+
+    let bakery = sdk.resolve(" Berlin Bakery ").await?;
+    assert_eq!(bakery.address(), Some(BOB));
+    assert!(matches!(
+        sdk.resolve(&format!("{BOB:#x}")).await?,
+        Resolution::Unique(NameMatch {
+            source: NameSource::Address,
+            ..
+        })
+    ));
+    // The address short-circuits before any lookup.
+    assert_eq!(server.params_for("circles_searchProfiles").len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn config_timeouts_bound_sdk_requests() -> Result<(), Box<dyn std::error::Error>> {
+    // Accepts the connection but never answers.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let mut cfg = config::gnosis_mainnet();
+    cfg.circles_rpc_url = format!("http://{}/", listener.local_addr()?);
+    cfg.timeouts.request = Some(Duration::from_millis(50));
+    cfg.timeouts.pathfinder = Some(Duration::from_secs(2));
+    let sdk = Sdk::new(cfg, None)?;
+
+    assert_eq!(
+        sdk.rpc().pathfinder_config().timeout,
+        Some(Duration::from_secs(2))
+    );
+    let err = sdk.rpc().health().health().await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            circles_rpc::CirclesRpcError::RequestTimeout { ref method, .. } if method == "circles_health"
+        ),
+        "{err:?}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn sdk_errors_classify_by_kind() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockRpcServer::builder()
+        .respond("circles_getAvatarInfo", serde_json::Value::Null)
+        .respond_error("circles_getAvatarInfo", -32602, "invalid address")
+        .respond_error("circles_getAvatarInfo", -32005, "limit exceeded")
+        .start()
+        .await?;
+    let mut cfg = config::gnosis_mainnet();
+    cfg.circles_rpc_url = server.url();
+    let sdk = Sdk::new(cfg, None)?;
+
+    let not_found = sdk.get_avatar(BOB).await.err().expect("unregistered");
+    assert_eq!(not_found.kind(), ErrorKind::NotFound);
+    assert!(not_found.is_user_error());
+
+    let invalid = sdk.get_avatar(BOB).await.err().expect("invalid params");
+    assert_eq!(invalid.kind(), ErrorKind::InvalidInput);
+    assert!(!invalid.is_retryable());
+
+    let limited = sdk.get_avatar(BOB).await.err().expect("rate limited");
+    assert_eq!(limited.kind(), ErrorKind::RateLimited);
+    assert!(limited.is_retryable());
+    assert!(!limited.is_user_error());
+    Ok(())
+}
+
+struct GnosisRunner;
+
+#[async_trait::async_trait]
+impl circles_sdk::ContractRunner for GnosisRunner {
+    fn sender_address(&self) -> Address {
+        BOB
+    }
+
+    async fn chain_id(&self) -> Result<u64, circles_sdk::RunnerError> {
+        Ok(100)
+    }
+
+    async fn send_transactions(
+        &self,
+        _txs: Vec<circles_sdk::PreparedTransaction>,
+    ) -> Result<Vec<circles_sdk::SubmittedTx>, circles_sdk::RunnerError> {
+        Ok(Vec::new())
+    }
+}
+
+#[tokio::test]
+async fn environments_route_calls_and_check_runner_chains() -> Result<(), Box<dyn std::error::Error>>
+{
+    let production = MockRpcServer::builder().start().await?;
+    let staging = MockRpcServer::builder()
+        .respond("circles_getAvatarInfo", serde_json::Value::Null)
+        .start()
+        .await?;
+    let mut production_cfg = config::gnosis_mainnet();
+    production_cfg.circles_rpc_url = production.url();
+    let mut staging_cfg = config::gnosis_mainnet();
+    staging_cfg.chain_id = circles_types::CHIADO_CHAIN_ID;
+    staging_cfg.circles_rpc_url = staging.url();
+
+    let runner: std::sync::Arc<dyn circles_sdk::ContractRunner> = std::sync::Arc::new(GnosisRunner);
+    let sdk = Sdk::new(production_cfg, Some(runner.clone()))?.with_environment(
+        circles_sdk::Environment::from_config(staging_cfg),
+        Some(runner),
+    )?;
+    assert_eq!(
+        sdk.environment_names().collect::<Vec<_>>(),
+        ["production", "staging"]
+    );
+    assert!(matches!(
+        sdk.environment("preview"),
+        Err(circles_sdk::SdkError::UnknownEnvironment(name)) if name == "preview"
+    ));
+
+    let staging_sdk = sdk.environment("staging")?;
+    assert!(!staging_sdk.is_registered(BOB).await?);
+    assert_eq!(staging.params_for("circles_getAvatarInfo").len(), 1);
+    assert!(production.params_for("circles_getAvatarInfo").is_empty());
+
+    let tx = circles_sdk::PreparedTransaction {
+        to: BOB,
+        data: Default::default(),
+        value: None,
+    };
+    let runner_of = |sdk: &Sdk| sdk.runner().cloned().expect("runner");
+    runner_of(sdk.environment("production")?)
+        .send_transactions(vec![tx.clone()])
+        .await?;
+    let err = runner_of(staging_sdk)
+        .send_transactions(vec![tx])
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        circles_sdk::RunnerError::ChainMismatch {
+            expected: 10200,
+            actual: 100
+        }
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn archive_url_serves_backfills_from_config() -> Result<(), Box<dyn std::error::Error>> {
+    let primary = MockRpcServer::builder()
+        .respond("circles_getAvatarInfo", serde_json::Value::Null)
+        .start()
+        .await?;
+    let archive = MockRpcServer::builder()
+        .respond("circles_events", serde_json::json!([]))
+        .start()
+        .await?;
+    let mut cfg = config::gnosis_mainnet();
+    cfg.circles_rpc_url = primary.url();
+    cfg.archive_rpc_url = Some(archive.url());
+    let sdk = Sdk::new(cfg, None)?;
+
+    assert!(!sdk.is_registered(BOB).await?);
+    let events: Vec<_> =
+        futures::StreamExt::collect(sdk.rpc().events().iter_events(None, 0, 9, None)).await;
+    assert!(events.is_empty());
+    assert_eq!(archive.params_for("circles_events").len(), 1);
+    assert_eq!(primary.params_for("circles_getAvatarInfo").len(), 1);
+    assert!(archive.params_for("circles_getAvatarInfo").is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn trust_scheduler_renews_only_trust_expiring_soon() -> Result<(), Box<dyn std::error::Error>>
+{
+    const CAROL: Address = address!("3000000000000000000000000000000000000003");
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs();
+    let expiring = |trustee: Address, expiry_time: u64| {
+        let mut row = trust_row(ALICE, trustee);
+        row["expiry_time"] = expiry_time.into();
+        row
+    };
+    let server = MockRpcServer::builder()
+        .respond(
+            "circles_getTrustRelations",
+            serde_json::json!([
+                expiring(BOB, now + 2 * 86_400),
+                expiring(CAROL, now + 60 * 86_400),
+                trust_row(CAROL, ALICE)
+            ]),
+        )
+        .start()
+        .await?;
+    let mut cfg = config::gnosis_mainnet();
+    cfg.circles_rpc_url = server.url();
+    let hub = cfg.v2_hub_address;
+    let sdk = Sdk::new(cfg, None)?;
+
+    let plan = sdk
+        .trust_scheduler(ALICE)
+        .with_renewal_days(30)
+        .plan_renewals()
+        .await?;
+    assert_eq!(
+        plan.expiring.iter().map(|t| t.trustee).collect::<Vec<_>>(),
+        [BOB]
+    );
+    assert!(plan.new_expiry >= u128::from(now + 30 * 86_400));
+    assert_eq!(plan.txs.len(), 1);
+    assert_eq!(plan.txs[0].to, hub);
+    assert!(sdk.describe(&plan.txs[0]).starts_with(&format!(
+        "HubV2.trust(trustReceiver={BOB}, expiry={}",
+        plan.new_expiry
+    )));
+
+    let err = sdk.trust_scheduler(ALICE).renew().await.unwrap_err();
+    assert!(matches!(err, circles_sdk::SdkError::MissingRunner));
+    Ok(())
+}
+
+#[tokio::test]
+async fn trust_list_export_reimports_as_a_no_op() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockRpcServer::builder()
+        .respond(
+            "circles_getAvatarInfo",
+            fixtures::avatar_info(ALICE, AvatarType::CrcV2RegisterHuman),
+        )
+        .respond(
+            "circles_getTrustRelations",
+            serde_json::json!([trust_row(ALICE, BOB), trust_row(BOB, ALICE)]),
+        )
+        .start()
+        .await?;
+    let mut cfg = config::gnosis_mainnet();
+    cfg.circles_rpc_url = server.url();
+    let sdk = Sdk::new(cfg, None)?;
+    let Avatar::Human(alice) = sdk.get_avatar(ALICE).await? else {
+        panic!("expected a human avatar");
+    };
+
+    let exported = alice.trust_export().await?;
+    assert_eq!(
+        exported.to_csv(),
+        format!("trustee,expiry\n{BOB:#x},{}\n", u64::MAX)
+    );
+    let list = circles_sdk::TrustList::from_json(&exported.to_json()?)?;
+    let plan = alice
+        .plan_trust_import(&list, circles_sdk::TrustImportPolicy::Replace)
+        .await?;
+    assert!(plan.is_empty());
+    // Nothing to change, so no runner is needed.
+    assert!(
+        alice
+            .trust_import(&list, circles_sdk::TrustImportPolicy::Replace)
+            .await?
+            .is_empty()
+    );
+
+    let empty = circles_sdk::TrustList::from_csv(ALICE, "trustee,expiry\n")?;
+    let plan = alice
+        .plan_trust_import(&empty, circles_sdk::TrustImportPolicy::Replace)
+        .await?;
+    assert_eq!(plan.remove, [BOB]);
+    let foreign = circles_sdk::TrustList::new(BOB, []);
+    assert!(
+        alice
+            .plan_trust_import(&foreign, circles_sdk::TrustImportPolicy::Merge)
+            .await
+            .is_err()
+    );
+    Ok(())
+}
+
+fn token_info(token: Address, owner: Address, token_type: &str) -> serde_json::Value {
+    serde_json::json!({
+        "block_number": 1,
+        "timestamp": 1,
+        "transaction_index": 0,
+        "log_index": 0,
+        "transaction_hash": format!("0x{}", "00".repeat(32)),
+        "version": 2,
+        "type": null,
+        "token_type": token_type,
+        "token": token,
+        "token_owner": owner
+    })
+}
+
+#[tokio::test]
+async fn token_registry_keeps_tokens_without_profiles_or_symbols()
+-> Result<(), Box<dyn std::error::Error>> {
+    let server = MockRpcServer::builder()
+        .respond(
+            "circles_getTokenInfoBatch",
+            serde_json::json!([
+                token_info(ALICE, ALICE, "CrcV2_RegisterHuman"),
+                token_info(BOB, BOB, "CrcV2_RegisterHuman"),
+            ]),
+        )
+        .respond("circles_getAvatarInfoBatch", serde_json::json!([]))
+        // BOB never published a profile; no `eth_call` fixture, so the symbol multicall fails.
+        .respond(
+            "circles_getProfileByAddressBatch",
+            serde_json::json!([{ "name": "Alice" }, null]),
+        )
+        .start()
+        .await?;
+    let mut cfg = config::gnosis_mainnet();
+    cfg.circles_rpc_url = server.url();
+    let sdk = Sdk::new(cfg, None)?;
+
+    let resolved = sdk.token_registry().resolve_many(&[ALICE, BOB]).await?;
+    assert_eq!(resolved.len(), 2);
+    assert_eq!(resolved[&ALICE].name.as_deref(), Some("Alice"));
+    assert_eq!(resolved[&BOB].name, None);
+    assert!(resolved.values().all(|meta| meta.symbol.is_none()));
+
+    // The failed symbol lookups are not cached, so the next call asks again.
+    assert_eq!(sdk.token_registry().cached(ALICE), None);
+    sdk.token_registry().resolve_many(&[ALICE, BOB]).await?;
+    assert_eq!(server.params_for("circles_getTokenInfoBatch").len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn v1_only_transfers_convert_to_crc_and_read_the_chain_rpc()
+-> Result<(), Box<dyn std::error::Error>> {
+    use alloy_sol_types::SolCall;
+    use circles_utils::converter::atto_circles_to_atto_crc;
+
+    let mut info = fixtures::avatar_info(ALICE, AvatarType::CrcV1Signup);
+    info["version"] = 1.into();
+    info["hasV1"] = true.into();
+    info["isHuman"] = true.into();
+    let circles = MockRpcServer::builder()
+        .respond("circles_getAvatarInfo", info)
+        .start()
+        .await?;
+    let chain = MockRpcServer::builder()
+        .respond(
+            "eth_call",
+            serde_json::json!(alloy_primitives::hex::encode_prefixed(
+                U256::MAX.to_be_bytes::<32>()
+            )),
+        )
+        .start()
+        .await?;
+    let mut cfg = config::gnosis_mainnet();
+    cfg.circles_rpc_url = circles.url();
+    cfg.chain_rpc_url = Some(chain.url());
+    let sdk = Sdk::new(cfg, None)?;
+
+    let Avatar::Human(alice) = sdk.get_avatar(ALICE).await? else {
+        panic!("expected a human avatar");
+    };
+    let amount = U256::from(10u64).pow(U256::from(18u64));
+    let before = circles_utils::time::unix_now();
+    let txs = alice.plan_transfer(BOB, amount, None).await?;
+    let after = circles_utils::time::unix_now();
+
+    assert_eq!(txs.len(), 1);
+    let call = circles_abis::HubV1::transferThroughCall::abi_decode(&txs[0].data)?;
+    let expected = [before, after].map(|at| atto_circles_to_atto_crc(amount, at));
+    assert!(expected.contains(&call.wads[0]), "{:?}", call.wads);
+    assert_ne!(call.wads[0], amount);
+    assert_eq!(chain.params_for("eth_call").len(), 1);
+    assert!(circles.params_for("eth_call").is_empty());
+    Ok(())
+}
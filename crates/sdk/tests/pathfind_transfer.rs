@@ -14,6 +14,7 @@ fn advanced_options_to_find_path_params() {
         simulated_trusts: None,
         max_transfers: Some(5),
         tx_data: None,
+        validate_path: None,
     };
     let params = opts.to_find_path_params(
         address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
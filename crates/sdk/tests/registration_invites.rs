@@ -1,10 +1,13 @@
 use alloy_primitives::{Address, TxHash};
-use circles_types::{AvatarInfo, AvatarType, CirclesConfig};
+use circles_types::{AvatarInfo, AvatarType, CirclesConfig, Timeouts};
 
 fn dummy_config() -> CirclesConfig {
     CirclesConfig {
+        chain_id: 100,
         circles_rpc_url: "https://rpc.example.com".into(),
         chain_rpc_url: None,
+        replica_rpc_url: None,
+        archive_rpc_url: None,
         pathfinder_url: None,
         profile_service_url: Some("https://profiles.example.com".into()),
         referrals_service_url: None,
@@ -20,6 +23,7 @@ fn dummy_config() -> CirclesConfig {
         invitation_farm_address: Address::ZERO,
         referrals_module_address: Address::ZERO,
         invitation_module_address: Address::ZERO,
+        timeouts: Timeouts::default(),
     }
 }
 
@@ -1,23 +1,27 @@
 use crate::error::{TransferError, TransfersErrorSource};
-use alloy_primitives::{aliases::U96, Address, Bytes, U256};
+use crate::metrics;
+use alloy_primitives::{
+    aliases::{U192, U96},
+    Address, Bytes, I256, U256,
+};
 use alloy_sol_types::SolCall;
-use circles_abis::{BaseGroup, DemurrageCircles, HubV2, InflationaryCircles, LiftERC20};
+use circles_abis::{BaseGroup, DemurrageCircles, HubV1, HubV2, InflationaryCircles, LiftERC20};
 use circles_pathfinder::{
-    create_flow_matrix, expected_unwrapped_totals, replace_wrapped_tokens,
-    token_info_map_from_path, wrapped_totals_from_path,
+    assert_no_netted_flow_mismatch, compute_netted_flow, create_flow_matrix,
+    expected_unwrapped_totals, replace_wrapped_tokens, shrink_path_values,
+    token_info_map_from_path, u256_to_u192, wrapped_totals_from_path,
 };
 use circles_rpc::CirclesRpc;
 use circles_types::{
     AdvancedTransferOptions, AggregatedTrustRelation, Balance, CirclesConfig, FindPathParams,
-    PathfindingTransferStep, SimulatedTrust, TokenBalanceResponse, TokenInfo, TransferStep,
-    TrustRelationType,
-};
-use circles_utils::converter::{
-    atto_circles_to_atto_static_circles, atto_static_circles_to_atto_circles,
+    PathfinderConfig, PathfindingTransferStep, SimulatedTrust, TokenBalanceResponse, TokenInfo,
+    TransferStep, TrustRelationType,
 };
+use circles_utils::converter::{atto_static_circles_to_atto_circles, static_circles_covering};
+use circles_utils::time::unix_now;
 use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::Instant;
 
 /// Simple transfer transaction representation.
 #[derive(Debug, Clone)]
@@ -27,6 +31,44 @@ pub struct TransferTx {
     pub value: U256,
 }
 
+/// When [`TransferBuilder`] includes the hub operator approval.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ApprovalMode {
+    /// Approve only if `isApprovedForAll(from, operator)` is not already set. Falls back to
+    /// approving when the check cannot be made.
+    #[default]
+    Once,
+    /// Include the approval in every transfer without checking on-chain state.
+    PerTransfer,
+}
+
+/// Hub v2 operator approval handling for `operateFlowMatrix`.
+///
+/// The hub requires every stream source to have approved `msg.sender` of
+/// `operateFlowMatrix`, so the operator must be the account that submits that call.
+/// By default this is the sender itself, which is why a Safe approves its own address.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ApprovalPolicy {
+    /// Account sending `operateFlowMatrix`; `None` means the transfer's `from`.
+    ///
+    /// Set this when a relayer or module submits the flow matrix on the sender's behalf.
+    /// The approval transactions are still sent by `from`; the caller must route the
+    /// `operateFlowMatrix` transaction through the submitter.
+    pub submitter: Option<Address>,
+    pub mode: ApprovalMode,
+    /// Revoke the approval again after `operateFlowMatrix`. Only applies when the
+    /// transfer granted it, so approvals that already existed are left alone.
+    pub revoke_after: bool,
+}
+
+impl ApprovalPolicy {
+    /// Operator approved for a transfer sent from `from`: the account that submits
+    /// `operateFlowMatrix`.
+    pub fn operator_for(&self, from: Address) -> Address {
+        self.submitter.unwrap_or(from)
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 struct ReplenishBalances {
     unwrapped_balance: U256,
@@ -44,6 +86,69 @@ impl ReplenishBalances {
     }
 }
 
+/// How [`TransferBuilder`] fits the requested amount to the pathfinder's six-decimal
+/// granularity (multiples of 1e12 wei).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RoundingPolicy {
+    /// Send the amount truncated to six decimals; the dust stays with the sender.
+    #[default]
+    Truncate,
+    /// Round up to the next six-decimal unit so the recipient receives at least the
+    /// requested amount. The overpaid dust goes to the recipient and is not refunded
+    /// (only the recipient could send it back); [`TransferAmount::dust`] reports it.
+    RoundUpOverpay,
+    /// Fail with [`TransferError::DustAmount`] unless the amount has no dust.
+    ErrorOnDust,
+}
+
+impl RoundingPolicy {
+    /// Amount to route for `requested` under this policy.
+    pub fn apply(self, requested: U256) -> Result<TransferAmount, TransferError> {
+        let truncated = truncate_to_six_decimals(requested);
+        let sent = match self {
+            RoundingPolicy::Truncate => truncated,
+            RoundingPolicy::RoundUpOverpay => round_up_to_six_decimals(requested),
+            RoundingPolicy::ErrorOnDust if truncated != requested => {
+                return Err(TransferError::dust_amount(requested, requested - truncated));
+            }
+            RoundingPolicy::ErrorOnDust => requested,
+        };
+        Ok(TransferAmount::new(requested, sent))
+    }
+}
+
+/// Requested versus actually-sent amount of a planned transfer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferAmount {
+    pub requested: U256,
+    pub sent: U256,
+    /// Difference between the two: left with the sender when truncating, overpaid to the
+    /// recipient when rounding up.
+    pub dust: U256,
+}
+
+impl TransferAmount {
+    fn new(requested: U256, sent: U256) -> Self {
+        let dust = if sent >= requested {
+            sent - requested
+        } else {
+            requested - sent
+        };
+        Self {
+            requested,
+            sent,
+            dust,
+        }
+    }
+}
+
+/// Transactions of an advanced transfer together with the amount they move.
+#[derive(Debug, Clone)]
+pub struct AdvancedTransferPlan {
+    pub txs: Vec<TransferTx>,
+    pub amount: TransferAmount,
+}
+
 /// High-level builder for Circles transfers.
 ///
 /// Mirrors the TS `TransferBuilder`: finds a path, handles wrappers, and
@@ -52,27 +157,29 @@ impl ReplenishBalances {
 pub struct TransferBuilder {
     config: CirclesConfig,
     rpc: CirclesRpc,
-    /// If false, will skip the approval check and always include approval.
-    check_approval: bool,
+    approval: ApprovalPolicy,
+    rounding: RoundingPolicy,
+    /// Check path netting and shrink consistency before building the flow matrix.
+    validate_path: bool,
 }
 
 impl TransferBuilder {
     /// Create a new builder from a Circles config.
     ///
-    /// Uses the config's `circles_rpc_url` for pathfinding + balances; does not
-    /// submit transactions (pair with a runner in the SDK to send).
+    /// Uses the config's read replica (or `circles_rpc_url`) for pathfinding + balances,
+    /// bounded by its `timeouts`; does not submit transactions (pair with a runner in the
+    /// SDK to send).
     pub fn new(config: CirclesConfig) -> Result<Self, TransferError> {
-        let rpc = CirclesRpc::try_from(config.circles_rpc_url.as_str()).map_err(|e| {
-            TransferError::generic(
-                e.to_string(),
-                None::<String>,
-                TransfersErrorSource::Transfers,
-            )
-        })?;
+        let rpc = CirclesRpc::try_from_http_with_timeouts(
+            config.effective_replica_rpc_url(),
+            config.timeouts,
+        )?;
         Ok(Self {
             config,
             rpc,
-            check_approval: true,
+            approval: ApprovalPolicy::default(),
+            rounding: RoundingPolicy::default(),
+            validate_path: false,
         })
     }
 
@@ -85,7 +192,54 @@ impl TransferBuilder {
     /// If `false`, the safety approval (`setApprovalForAll`) is always included
     /// without checking existing approval status.
     pub fn with_approval_check(mut self, check: bool) -> Self {
-        self.check_approval = check;
+        self.approval.mode = if check {
+            ApprovalMode::Once
+        } else {
+            ApprovalMode::PerTransfer
+        };
+        self
+    }
+
+    /// Bound pathfinder requests made by this builder with a timeout and `max_transfers`
+    /// default.
+    pub fn with_pathfinder_config(mut self, config: PathfinderConfig) -> Self {
+        self.rpc = self.rpc.with_pathfinder_config(config);
+        self
+    }
+
+    /// Replace the operator approval policy (default: sender as operator, approve once).
+    pub fn with_approval_policy(mut self, policy: ApprovalPolicy) -> Self {
+        self.approval = policy;
+        self
+    }
+
+    pub fn approval_policy(&self) -> &ApprovalPolicy {
+        &self.approval
+    }
+
+    /// No longer has an effect: demurraged wrappers are unwrapped only by the amount the
+    /// path spends, so the rest of the balance never leaves the wrapper and there is
+    /// nothing to re-wrap.
+    #[deprecated(note = "demurraged leftovers stay wrapped; there is nothing to re-wrap")]
+    pub fn with_demurraged_rewrap(self, _rewrap: bool) -> Self {
+        self
+    }
+
+    /// Choose how advanced transfers handle amounts below six-decimal precision
+    /// (default: [`RoundingPolicy::Truncate`]).
+    pub fn with_rounding_policy(mut self, policy: RoundingPolicy) -> Self {
+        self.rounding = policy;
+        self
+    }
+
+    /// Check each path before building its flow matrix (default: false): the source must
+    /// net negative, intermediates must balance, the receiver must net exactly the
+    /// path's `max_flow`, and shrinking the path at full retention must leave every edge
+    /// unchanged. Failures surface as [`TransferError::PathValidation`] instead of a
+    /// reverted `operateFlowMatrix`. `AdvancedTransferOptions::validate_path` overrides
+    /// this per transfer.
+    pub fn with_path_validation(mut self, validate: bool) -> Self {
+        self.validate_path = validate;
         self
     }
 
@@ -116,6 +270,43 @@ impl TransferBuilder {
         options: Option<AdvancedTransferOptions>,
         aggregate: bool,
     ) -> Result<Vec<TransferTx>, TransferError> {
+        self.construct_advanced_transfer_plan(from, to, amount, options, aggregate)
+            .await
+            .map(|plan| plan.txs)
+    }
+
+    /// Like [`construct_advanced_transfer_with_aggregate`](Self::construct_advanced_transfer_with_aggregate),
+    /// but also reports the amount actually sent and the dust left over by the builder's
+    /// [`RoundingPolicy`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.transfer", skip_all, fields(method = "construct_advanced_transfer", avatar = %from, to = %to, aggregate = aggregate)))]
+    pub async fn construct_advanced_transfer_plan(
+        &self,
+        from: Address,
+        to: Address,
+        amount: U256,
+        options: Option<AdvancedTransferOptions>,
+        aggregate: bool,
+    ) -> Result<AdvancedTransferPlan, TransferError> {
+        let started = Instant::now();
+        let result = self
+            .plan_advanced_transfer(from, to, amount, options, aggregate)
+            .await;
+        metrics::record_plan(
+            "advanced",
+            result.as_ref().map(|plan| plan.txs.as_slice()),
+            started.elapsed(),
+        );
+        result
+    }
+
+    async fn plan_advanced_transfer(
+        &self,
+        from: Address,
+        to: Address,
+        amount: U256,
+        options: Option<AdvancedTransferOptions>,
+        aggregate: bool,
+    ) -> Result<AdvancedTransferPlan, TransferError> {
         // Self-transfer fast-path for unwrap: if from == to and from/to tokens are provided and distinct.
         if from == to {
             if let Some(ref opts) = options {
@@ -129,7 +320,10 @@ impl TransferBuilder {
                             // Attempt unwrap via wrapper contracts
                             if let Some(tx) = self.self_unwrap(from_token, to_token, amount).await?
                             {
-                                return Ok(vec![tx]);
+                                return Ok(AdvancedTransferPlan {
+                                    txs: vec![tx],
+                                    amount: TransferAmount::new(amount, amount),
+                                });
                             }
                         }
                     }
@@ -147,9 +341,11 @@ impl TransferBuilder {
             simulated_trusts: None,
             max_transfers: None,
             tx_data: None,
+            validate_path: None,
         });
 
-        let target_flow = truncate_to_six_decimals(amount);
+        let transfer_amount = self.rounding.apply(amount)?;
+        let target_flow = transfer_amount.sent;
 
         // Pathfinding
         let opts_for_path = opts.clone();
@@ -167,13 +363,12 @@ impl TransferBuilder {
             max_transfers: opts_for_path.max_transfers,
         };
 
-        let path = self.rpc.pathfinder().find_path(params).await.map_err(|e| {
-            TransferError::generic(
-                e.to_string(),
-                None::<String>,
-                TransfersErrorSource::Pathfinding,
-            )
-        })?;
+        let path = self
+            .rpc
+            .pathfinder()
+            .find_path(params)
+            .await
+            .map_err(TransferError::PathfindingFailed)?;
 
         if path.transfers.is_empty() {
             return Err(TransferError::no_path_found(from, to, None));
@@ -192,13 +387,7 @@ impl TransferBuilder {
         // Token info + wrapper bookkeeping
         let token_info_map = token_info_map_from_path(from, &self.rpc, &path)
             .await
-            .map_err(|e| {
-                TransferError::generic(
-                    e.to_string(),
-                    None::<String>,
-                    TransfersErrorSource::Pathfinding,
-                )
-            })?;
+            .map_err(TransferError::TokenInfoLookupFailed)?;
         let wrapped_totals = wrapped_totals_from_path(&path, &token_info_map);
         let has_wrapped = !wrapped_totals.is_empty();
 
@@ -211,7 +400,7 @@ impl TransferBuilder {
             HashMap::new()
         };
 
-        self.assemble_transactions_inner(
+        let txs = self.assemble_transactions_inner(
             from,
             to,
             path,
@@ -220,18 +409,87 @@ impl TransferBuilder {
             balance_map,
             opts,
             true,
-        )
+        )?;
+        Ok(AdvancedTransferPlan {
+            txs,
+            amount: transfer_amount,
+        })
+    }
+
+    /// Construct a Hub v1 `transferThrough` for avatars that have not migrated to v2.
+    ///
+    /// `amount` is in v1 (inflationary) atto-CRC, not demurraged atto-Circles; convert
+    /// v2 amounts with [`atto_circles_to_atto_crc`](circles_utils::converter::atto_circles_to_atto_crc).
+    /// Sends `from`'s own v1 token in a single hop, so `to` must trust `from` on v1. The
+    /// hub's `checkSendLimit` is read first and a short limit fails as insufficient balance.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.transfer", skip_all, fields(method = "construct_v1_transfer", avatar = %from, to = %to)))]
+    pub async fn construct_v1_transfer(
+        &self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<Vec<TransferTx>, TransferError> {
+        let started = Instant::now();
+        let result = self.plan_v1_transfer(from, to, amount).await;
+        metrics::record_plan("v1", result.as_deref(), started.elapsed());
+        result
+    }
+
+    async fn plan_v1_transfer(
+        &self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<Vec<TransferTx>, TransferError> {
+        if self.config.v1_hub_address == Address::ZERO {
+            return Err(TransferError::generic(
+                "v1 hub address is not configured",
+                None::<String>,
+                TransfersErrorSource::Validation,
+            ));
+        }
+        let url = self.config.effective_chain_rpc_url().parse().map_err(|e| {
+            TransferError::generic(
+                format!("invalid rpc url: {e}"),
+                None::<String>,
+                TransfersErrorSource::Transfers,
+            )
+        })?;
+        let provider = alloy_provider::ProviderBuilder::new().connect_http(url);
+        let limit = HubV1::new(self.config.v1_hub_address, provider)
+            .checkSendLimit(from, from, to)
+            .call()
+            .await
+            .map_err(|e| TransferError::contract("checkSendLimit", e))?;
+        if limit < amount {
+            return Err(TransferError::insufficient_balance(amount, limit, from, to));
+        }
+        Ok(vec![self.v1_transfer_tx(from, to, amount)])
     }
 
     /// Construct the TS-style replenish flow: use existing unwrapped balance
     /// first, then unwrap local wrappers, and only pathfind the remaining
     /// deficit when necessary.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.transfer", skip_all, fields(method = "construct_replenish", avatar = %from, token = %token_id)))]
     pub async fn construct_replenish(
         &self,
         from: Address,
         token_id: Address,
         amount: U256,
         receiver: Option<Address>,
+    ) -> Result<Vec<TransferTx>, TransferError> {
+        let started = Instant::now();
+        let result = self.plan_replenish(from, token_id, amount, receiver).await;
+        metrics::record_plan("replenish", result.as_deref(), started.elapsed());
+        result
+    }
+
+    async fn plan_replenish(
+        &self,
+        from: Address,
+        token_id: Address,
+        amount: U256,
+        receiver: Option<Address>,
     ) -> Result<Vec<TransferTx>, TransferError> {
         let receiver = receiver.unwrap_or(from);
         let balances = self.fetch_replenish_balances(from, token_id).await?;
@@ -285,16 +543,7 @@ impl TransferBuilder {
                 max_transfers: None,
             })
             .await
-            .map_err(|e| {
-                replenish_pathfinding_error(
-                    e.to_string(),
-                    amount,
-                    &balances,
-                    total_available,
-                    from,
-                    token_id,
-                )
-            })?;
+            .map_err(TransferError::PathfindingFailed)?;
 
         if path.transfers.is_empty() {
             return Err(TransferError::no_path_found(
@@ -320,13 +569,7 @@ impl TransferBuilder {
 
         let token_info_map = token_info_map_from_path(from, &self.rpc, &path)
             .await
-            .map_err(|e| {
-                TransferError::generic(
-                    e.to_string(),
-                    None::<String>,
-                    TransfersErrorSource::Pathfinding,
-                )
-            })?;
+            .map_err(TransferError::TokenInfoLookupFailed)?;
         let wrapped_totals = wrapped_totals_from_path(&path, &token_info_map);
         let balance_map = if wrapped_totals.is_empty() {
             HashMap::new()
@@ -343,6 +586,7 @@ impl TransferBuilder {
             simulated_trusts,
             max_transfers: None,
             tx_data: None,
+            validate_path: None,
         };
 
         let mut replenish_txs = self.assemble_transactions_inner(
@@ -365,11 +609,24 @@ impl TransferBuilder {
     }
 
     /// Construct the TS-style automatic group-token redeem flow.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "circles.transfer", skip_all, fields(method = "construct_group_token_redeem", avatar = %from, group = %group)))]
     pub async fn construct_group_token_redeem(
         &self,
         from: Address,
         group: Address,
         amount: U256,
+    ) -> Result<Vec<TransferTx>, TransferError> {
+        let started = Instant::now();
+        let result = self.plan_group_token_redeem(from, group, amount).await;
+        metrics::record_plan("group_redeem", result.as_deref(), started.elapsed());
+        result
+    }
+
+    async fn plan_group_token_redeem(
+        &self,
+        from: Address,
+        group: Address,
+        amount: U256,
     ) -> Result<Vec<TransferTx>, TransferError> {
         if amount.is_zero() {
             return Err(TransferError::generic(
@@ -384,13 +641,7 @@ impl TransferBuilder {
             .token_info()
             .get_token_info(group)
             .await
-            .map_err(|e| {
-                TransferError::generic(
-                    e.to_string(),
-                    None::<String>,
-                    TransfersErrorSource::Transfers,
-                )
-            })?;
+            .map_err(|e| TransferError::TokenInfoLookupFailed(e.into()))?;
 
         if group_info.token_type != "CrcV2_RegisterGroup" {
             return Err(TransferError::generic(
@@ -408,14 +659,7 @@ impl TransferBuilder {
             .rpc
             .token()
             .get_token_balances(treasury, false, true)
-            .await
-            .map_err(|e| {
-                TransferError::generic(
-                    e.to_string(),
-                    None::<String>,
-                    TransfersErrorSource::Transfers,
-                )
-            })?;
+            .await?;
         let treasury_tokens = treasury_balances
             .into_iter()
             .filter(|balance| balance.is_erc1155)
@@ -426,14 +670,7 @@ impl TransferBuilder {
             .rpc
             .trust()
             .get_aggregated_trust_relations(from)
-            .await
-            .map_err(|e| {
-                TransferError::generic(
-                    e.to_string(),
-                    None::<String>,
-                    TransfersErrorSource::Transfers,
-                )
-            })?;
+            .await?;
         let expected_to_tokens =
             filter_redeemable_collateral_tokens(&trust_relationships, &treasury_tokens);
 
@@ -498,6 +735,7 @@ impl TransferBuilder {
                     simulated_trusts: None,
                     max_transfers: None,
                     tx_data: None,
+                    validate_path: None,
                 }),
             )
             .await?;
@@ -534,6 +772,7 @@ impl TransferBuilder {
         for (wrapper, (amount_dem, _owner)) in &unwrapped_map {
             if let Some(info) = token_info_map.get(wrapper) {
                 if info.token_type == "CrcV2_ERC20WrapperDeployed_Demurraged" {
+                    // Unwrap exactly what the path spends; the rest stays wrapped.
                     let call = DemurrageCircles::unwrapCall {
                         _amount: *amount_dem,
                     };
@@ -544,8 +783,9 @@ impl TransferBuilder {
                     });
                 } else if info.token_type == "CrcV2_ERC20WrapperDeployed_Inflationary" {
                     // Unwrap only the amount used in the path, converted with
-                    // current-time semantics to match the TS TransferBuilder.
-                    let static_amt = atto_circles_to_atto_static_circles(*amount_dem, None);
+                    // current-time semantics like the TS TransferBuilder but rounded
+                    // up so the unwrapped tokens never fall short of the path value.
+                    let static_amt = static_circles_covering(*amount_dem, None);
                     let call = InflationaryCircles::unwrapCall {
                         _amount: static_amt,
                     };
@@ -559,17 +799,7 @@ impl TransferBuilder {
                     if let Some(current_static) = balance_map.get(wrapper) {
                         let leftover = current_static.saturating_sub(static_amt);
                         if leftover > U256::ZERO {
-                            let owner = info.token_owner;
-                            let wrap_call = HubV2::wrapCall {
-                                _avatar: owner,
-                                _amount: leftover,
-                                _type: 1u8, // Inflationary
-                            };
-                            rewraps.push(TransferTx {
-                                to: self.config.v2_hub_address,
-                                data: Bytes::from(wrap_call.abi_encode()),
-                                value: U256::ZERO,
-                            });
+                            rewraps.push(self.rewrap_tx(info.token_owner, leftover, 1));
                         }
                     }
                 }
@@ -580,31 +810,28 @@ impl TransferBuilder {
         let unwrapped_addr_map: HashMap<Address, (U256, Address)> = unwrapped_map.clone();
         let path_unwrapped = replace_wrapped_tokens(&path, &unwrapped_addr_map);
 
+        if opts.validate_path.unwrap_or(self.validate_path) {
+            validate_path_netting(&path_unwrapped, from, to)?;
+            validate_shrink_consistency(&path_unwrapped, to)?;
+        }
+
         // Build TransferStep list for flow matrix
         let transfers = path_unwrapped
             .transfers
             .iter()
             .filter_map(|t| {
                 let token_owner = Address::from_str(&t.token_owner).ok()?;
-                Some(TransferStep {
+                Some(to_u192(t.value).map(|value| TransferStep {
                     from_address: t.from,
                     to_address: t.to,
                     token_owner,
-                    value: u256_to_u192_local(t.value),
-                })
+                    value,
+                }))
             })
-            .collect::<Vec<_>>();
+            .collect::<Result<Vec<_>, _>>()?;
 
-        let mut flow_matrix =
-            create_flow_matrix(from, to, u256_to_u192_local(path.max_flow), &transfers).map_err(
-                |e| {
-                    TransferError::generic(
-                        e.to_string(),
-                        None::<String>,
-                        TransfersErrorSource::FlowMatrix,
-                    )
-                },
-            )?;
+        let mut flow_matrix = create_flow_matrix(from, to, to_u192(path.max_flow)?, &transfers)
+            .map_err(TransferError::FlowMatrix)?;
 
         if let Some(tx_data) = opts.tx_data {
             if let Some(first) = flow_matrix.streams.get_mut(0) {
@@ -632,22 +859,15 @@ impl TransferBuilder {
         };
 
         let mut txs = Vec::new();
+        let operator = self.approval.operator_for(from);
         // In test/fixture contexts we skip the approval check and always include it to avoid async DNS.
-        let needs_approval = if check_approval && self.check_approval {
-            needs_approval_blocking(|| self.needs_approval(from)).unwrap_or(true)
+        let needs_approval = if check_approval && self.approval.mode == ApprovalMode::Once {
+            needs_approval_blocking(|| self.needs_approval(from, operator))?
         } else {
             true
         };
         if needs_approval {
-            let approve_call = HubV2::setApprovalForAllCall {
-                _operator: from,
-                _approved: true,
-            };
-            txs.push(TransferTx {
-                to: self.config.v2_hub_address,
-                data: Bytes::from(approve_call.abi_encode()),
-                value: U256::ZERO,
-            });
+            txs.push(self.approval_tx(operator, true));
         }
         txs.extend(unwraps);
         txs.push(TransferTx {
@@ -655,6 +875,9 @@ impl TransferBuilder {
             data: Bytes::from(op_call.abi_encode()),
             value: U256::ZERO,
         });
+        if needs_approval && self.approval.revoke_after {
+            txs.push(self.approval_tx(operator, false));
+        }
 
         txs.extend(rewraps);
 
@@ -694,14 +917,7 @@ impl TransferBuilder {
             .rpc
             .token()
             .get_token_balances(avatar, false, true)
-            .await
-            .map_err(|e| {
-                TransferError::generic(
-                    e.to_string(),
-                    None::<String>,
-                    TransfersErrorSource::Transfers,
-                )
-            })?;
+            .await?;
 
         let mut map = HashMap::new();
         for b in balances {
@@ -721,14 +937,7 @@ impl TransferBuilder {
             .rpc
             .token()
             .get_token_balances(avatar, false, true)
-            .await
-            .map_err(|e| {
-                TransferError::generic(
-                    e.to_string(),
-                    None::<String>,
-                    TransfersErrorSource::Transfers,
-                )
-            })?;
+            .await?;
 
         let relevant_balances = balances
             .into_iter()
@@ -749,13 +958,7 @@ impl TransferBuilder {
             .token_info()
             .get_token_info_batch(token_ids)
             .await
-            .map_err(|e| {
-                TransferError::generic(
-                    e.to_string(),
-                    None::<String>,
-                    TransfersErrorSource::Transfers,
-                )
-            })?
+            .map_err(|e| TransferError::TokenInfoLookupFailed(e.into()))?
             .into_iter()
             .map(|info| (info.token, info))
             .collect::<HashMap<_, _>>();
@@ -778,14 +981,10 @@ fn round_up_to_six_decimals(amount: U256) -> U256 {
     }
 }
 
-fn u256_to_u192_local(value: U256) -> alloy_primitives::aliases::U192 {
-    use alloy_primitives::aliases::U192;
-    let limbs = value.into_limbs();
-    if limbs[3] != 0 {
-        U192::MAX
-    } else {
-        U192::from_limbs([limbs[0], limbs[1], limbs[2]])
-    }
+/// Flow amounts are `uint192` on-chain; refuse amounts that would not fit instead of
+/// clamping them.
+fn to_u192(value: U256) -> Result<U192, TransferError> {
+    u256_to_u192(value).map_err(|_| TransferError::amount_overflow(value))
 }
 
 fn validate_wrapped_balance_usage(
@@ -798,6 +997,63 @@ fn validate_wrapped_balance_usage(
     Ok(())
 }
 
+/// Netting check behind [`TransferBuilder::with_path_validation`].
+///
+/// Beyond the per-vertex rules of `assert_no_netted_flow_mismatch`, the receiver's net
+/// inflow must equal `max_flow`, the value the flow matrix is built for. Self-loops such as
+/// the aggregate self-transfer net to zero, so they do not affect either check.
+fn validate_path_netting(
+    path: &circles_types::PathfindingResult,
+    from: Address,
+    to: Address,
+) -> Result<(), TransferError> {
+    assert_no_netted_flow_mismatch(path, Some(from), Some(to))
+        .map_err(|e| TransferError::path_validation(e.to_string()))?;
+    if from == to {
+        return Ok(());
+    }
+    let received = compute_netted_flow(path)
+        .get(&to)
+        .copied()
+        .unwrap_or(I256::ZERO);
+    if received != I256::from_raw(path.max_flow) {
+        return Err(TransferError::path_validation(format!(
+            "receiver {to:#x} nets {received}, but the path's max flow is {}",
+            path.max_flow
+        )));
+    }
+    Ok(())
+}
+
+/// Shrink-path check behind [`TransferBuilder::with_path_validation`].
+///
+/// `shrink_path_values` pushes flow through the path in flow order, so at full retention it
+/// reproduces every edge of a consistent path. An edge that comes back with a different
+/// value means the flow cannot be forwarded in order, e.g. it loops through a cycle.
+fn validate_shrink_consistency(
+    path: &circles_types::PathfindingResult,
+    to: Address,
+) -> Result<(), TransferError> {
+    let full = U256::from(1_000_000_000_000u64);
+    let shrunk = shrink_path_values(path, to, full);
+    let mut shrunk_edges = shrunk.transfers.iter();
+    for edge in path.transfers.iter().filter(|edge| !edge.value.is_zero()) {
+        let matches = shrunk_edges.next().is_some_and(|s| {
+            s.from == edge.from
+                && s.to == edge.to
+                && s.token_owner == edge.token_owner
+                && s.value == edge.value
+        });
+        if !matches {
+            return Err(TransferError::path_validation(format!(
+                "edge {:#x} -> {:#x} ({}) does not survive shrinking unchanged",
+                edge.from, edge.to, edge.token_owner
+            )));
+        }
+    }
+    Ok(())
+}
+
 fn maybe_add_aggregate_transfer(
     mut path: circles_types::PathfindingResult,
     to: Address,
@@ -839,7 +1095,7 @@ fn create_replenish_unwraps(balances: &ReplenishBalances, deficit: U256) -> Vec<
 
     if remaining_to_unwrap > U256::ZERO && balances.wrapped_inflationary_balance > U256::ZERO {
         if let Some(wrapper) = balances.wrapped_inflationary_address {
-            let static_to_unwrap = atto_circles_to_atto_static_circles(remaining_to_unwrap, None);
+            let static_to_unwrap = static_circles_covering(remaining_to_unwrap, None);
             let actual_unwrap = static_to_unwrap.min(balances.wrapped_inflationary_balance);
             let call = InflationaryCircles::unwrapCall {
                 _amount: actual_unwrap,
@@ -904,34 +1160,8 @@ fn classify_replenish_balances(
     Ok(replenishment)
 }
 
-fn replenish_pathfinding_error(
-    message: String,
-    amount: U256,
-    balances: &ReplenishBalances,
-    total_available: U256,
-    from: Address,
-    token_id: Address,
-) -> TransferError {
-    let deficit = amount.saturating_sub(balances.unwrapped_balance);
-    let unreachable =
-        deficit.saturating_sub(total_available.saturating_sub(balances.unwrapped_balance));
-
-    TransferError::generic(
-        format!(
-            "Insufficient tokens to replenish from {from:#x} for token {token_id:#x}. Target: {amount} wei, current unwrapped: {} wei, need: {deficit} wei, available: {total_available} wei. Cannot acquire the remaining {unreachable} wei. RPC/pathfinder error: {message}",
-            balances.unwrapped_balance,
-        ),
-        Some("REPLENISH_INSUFFICIENT_TOKENS"),
-        TransfersErrorSource::Validation,
-    )
-}
-
 fn replenish_trust_expiry() -> U96 {
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
-    let expiry = now + (365 * 24 * 60 * 60);
+    let expiry = unix_now() + (365 * 24 * 60 * 60);
     U96::from(expiry)
 }
 
@@ -958,16 +1188,32 @@ fn filter_redeemable_collateral_tokens(
 }
 
 impl TransferBuilder {
-    async fn needs_approval(&self, operator: Address) -> Option<bool> {
+    /// Whether `owner` still has to approve `operator` on the v2 hub before a flow matrix
+    /// can move its tokens.
+    pub async fn needs_approval(
+        &self,
+        owner: Address,
+        operator: Address,
+    ) -> Result<bool, TransferError> {
         let Ok(url) = self.config.circles_rpc_url.parse() else {
-            return None;
+            return Err(TransferError::generic(
+                "invalid circles rpc url",
+                None::<String>,
+                TransfersErrorSource::Transfers,
+            ));
         };
         let provider = alloy_provider::ProviderBuilder::new().connect_http(url);
         let hub = HubV2::new(self.config.v2_hub_address, provider);
-        match hub.isApprovedForAll(operator, operator).call().await {
-            Ok(resp) => Some(!resp),
-            Err(_) => None,
-        }
+        let approved = hub
+            .isApprovedForAll(owner, operator)
+            .call()
+            .await
+            .map_err(|source| TransferError::ApprovalCheckFailed {
+                owner,
+                operator,
+                source: Box::new(source),
+            })?;
+        Ok(!approved)
     }
 
     async fn self_unwrap(
@@ -986,25 +1232,13 @@ impl TransferBuilder {
             .erc20Circles(0u8, to_token)
             .call()
             .await
-            .map_err(|e| {
-                TransferError::generic(
-                    e.to_string(),
-                    None::<String>,
-                    TransfersErrorSource::Transfers,
-                )
-            })?
+            .map_err(|e| TransferError::contract("erc20Circles", e))?
             .0;
         let inf = lift
             .erc20Circles(1u8, to_token)
             .call()
             .await
-            .map_err(|e| {
-                TransferError::generic(
-                    e.to_string(),
-                    None::<String>,
-                    TransfersErrorSource::Transfers,
-                )
-            })?
+            .map_err(|e| TransferError::contract("erc20Circles", e))?
             .0;
 
         let dem_addr = Address::from(dem);
@@ -1018,7 +1252,7 @@ impl TransferBuilder {
                 value: U256::ZERO,
             }));
         } else if from_token == inf_addr {
-            let static_amt = atto_circles_to_atto_static_circles(amount, None);
+            let static_amt = static_circles_covering(amount, None);
             let infl_call = InflationaryCircles::unwrapCall {
                 _amount: static_amt,
             };
@@ -1041,13 +1275,11 @@ impl TransferBuilder {
         })?;
         let provider = alloy_provider::ProviderBuilder::new().connect_http(url);
         let base_group = BaseGroup::new(group, provider);
-        base_group.BASE_TREASURY().call().await.map_err(|e| {
-            TransferError::generic(
-                e.to_string(),
-                Some("GROUP_TOKEN_REDEEM_TREASURY_LOOKUP_FAILED"),
-                TransfersErrorSource::Transfers,
-            )
-        })
+        base_group
+            .BASE_TREASURY()
+            .call()
+            .await
+            .map_err(|e| TransferError::contract("BASE_TREASURY", e))
     }
 
     async fn is_trusted(&self, truster: Address, trustee: Address) -> Result<bool, TransferError> {
@@ -1060,13 +1292,10 @@ impl TransferBuilder {
         };
         let provider = alloy_provider::ProviderBuilder::new().connect_http(url);
         let hub = HubV2::new(self.config.v2_hub_address, provider);
-        hub.isTrusted(truster, trustee).call().await.map_err(|e| {
-            TransferError::generic(
-                e.to_string(),
-                None::<String>,
-                TransfersErrorSource::Transfers,
-            )
-        })
+        hub.isTrusted(truster, trustee)
+            .call()
+            .await
+            .map_err(|e| TransferError::contract("isTrusted", e))
     }
 
     async fn safe_transfer_tx(
@@ -1085,13 +1314,11 @@ impl TransferBuilder {
         };
         let provider = alloy_provider::ProviderBuilder::new().connect_http(url);
         let hub = HubV2::new(self.config.v2_hub_address, provider);
-        let erc1155_id = hub.toTokenId(token_id).call().await.map_err(|e| {
-            TransferError::generic(
-                e.to_string(),
-                None::<String>,
-                TransfersErrorSource::Transfers,
-            )
-        })?;
+        let erc1155_id = hub
+            .toTokenId(token_id)
+            .call()
+            .await
+            .map_err(|e| TransferError::contract("toTokenId", e))?;
         let call = HubV2::safeTransferFromCall {
             _from: from,
             _to: to,
@@ -1106,6 +1333,46 @@ impl TransferBuilder {
         })
     }
 
+    /// Hub `wrap` call; `wrap_type` is 0 for demurraged and 1 for inflationary wrappers.
+    fn rewrap_tx(&self, owner: Address, amount: U256, wrap_type: u8) -> TransferTx {
+        let call = HubV2::wrapCall {
+            _avatar: owner,
+            _amount: amount,
+            _type: wrap_type,
+        };
+        TransferTx {
+            to: self.config.v2_hub_address,
+            data: Bytes::from(call.abi_encode()),
+            value: U256::ZERO,
+        }
+    }
+
+    fn v1_transfer_tx(&self, from: Address, to: Address, amount: U256) -> TransferTx {
+        let call = HubV1::transferThroughCall {
+            tokenOwners: vec![from],
+            srcs: vec![from],
+            dests: vec![to],
+            wads: vec![amount],
+        };
+        TransferTx {
+            to: self.config.v1_hub_address,
+            data: Bytes::from(call.abi_encode()),
+            value: U256::ZERO,
+        }
+    }
+
+    fn approval_tx(&self, operator: Address, approved: bool) -> TransferTx {
+        let call = HubV2::setApprovalForAllCall {
+            _operator: operator,
+            _approved: approved,
+        };
+        TransferTx {
+            to: self.config.v2_hub_address,
+            data: Bytes::from(call.abi_encode()),
+            value: U256::ZERO,
+        }
+    }
+
     fn trust_tx(&self, trust_receiver: Address, expiry: U96) -> TransferTx {
         let call = HubV2::trustCall {
             _trustReceiver: trust_receiver,
@@ -1119,10 +1386,10 @@ impl TransferBuilder {
     }
 }
 
-fn needs_approval_blocking<F, Fut>(f: F) -> Option<bool>
+fn needs_approval_blocking<F, Fut>(f: F) -> Result<bool, TransferError>
 where
     F: FnOnce() -> Fut,
-    Fut: std::future::Future<Output = Option<bool>>,
+    Fut: std::future::Future<Output = Result<bool, TransferError>>,
 {
     futures::executor::block_on(f())
 }
@@ -1131,10 +1398,11 @@ where
 mod tests {
     use super::{
         classify_replenish_balances, create_replenish_unwraps, maybe_add_aggregate_transfer,
-        round_up_to_six_decimals, validate_wrapped_balance_usage, ReplenishBalances,
+        round_up_to_six_decimals, to_u192, validate_path_netting, validate_shrink_consistency,
+        validate_wrapped_balance_usage, ReplenishBalances, RoundingPolicy,
     };
     use crate::TransferError;
-    use alloy_primitives::{address, fixed_bytes, Address, TxHash, U256};
+    use alloy_primitives::{address, aliases::U192, fixed_bytes, Address, TxHash, U256};
     use circles_types::{
         AdvancedTransferOptions, Balance, PathfindingResult, PathfindingTransferStep,
         TokenBalanceResponse, TokenInfo,
@@ -1155,6 +1423,39 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn rounding_policy_reports_sent_amount_and_dust() {
+        let unit = U256::from(1_000_000_000_000u64);
+        let requested = unit * U256::from(3u64) + U256::from(250u64);
+
+        let truncated = RoundingPolicy::Truncate.apply(requested).unwrap();
+        assert_eq!(truncated.sent, unit * U256::from(3u64));
+        assert_eq!(truncated.dust, U256::from(250u64));
+
+        let rounded = RoundingPolicy::RoundUpOverpay.apply(requested).unwrap();
+        assert_eq!(rounded.sent, unit * U256::from(4u64));
+        assert_eq!(rounded.dust, unit - U256::from(250u64));
+
+        assert!(matches!(
+            RoundingPolicy::ErrorOnDust.apply(requested),
+            Err(TransferError::DustAmount { ref dust, .. }) if dust == "250"
+        ));
+        let exact = RoundingPolicy::ErrorOnDust.apply(unit).unwrap();
+        assert_eq!((exact.sent, exact.dust), (unit, U256::ZERO));
+    }
+
+    #[test]
+    fn flow_amounts_above_u192_are_rejected_not_clamped() {
+        let max = U256::from(U192::MAX);
+        assert_eq!(to_u192(max).unwrap(), U192::MAX);
+
+        let over = max + U256::from(1u64);
+        match to_u192(over) {
+            Err(TransferError::AmountOverflow { value }) => assert_eq!(value, over.to_string()),
+            other => panic!("expected AmountOverflow, got {other:?}"),
+        }
+    }
+
     #[test]
     fn aggregate_transfer_is_appended_for_single_to_token() {
         let source = address!("0x1000000000000000000000000000000000000001");
@@ -1168,6 +1469,7 @@ mod tests {
                 token_owner: format!("{source:#x}"),
                 value: U256::from(5u64),
             }],
+            block_number: None,
         };
         let opts = AdvancedTransferOptions {
             use_wrapped_balances: Some(true),
@@ -1179,6 +1481,7 @@ mod tests {
             simulated_trusts: None,
             max_transfers: None,
             tx_data: None,
+            validate_path: None,
         };
 
         let aggregated = maybe_add_aggregate_transfer(path, sink, &opts, true);
@@ -1189,6 +1492,18 @@ mod tests {
         assert_eq!(appended.to, sink);
         assert_eq!(appended.token_owner, format!("{aggregate_token:#x}"));
         assert_eq!(appended.value, U256::from(5u64));
+
+        // The aggregate self-transfer nets to zero, so validation still sees 5 at the sink.
+        assert!(validate_path_netting(&aggregated, source, sink).is_ok());
+        assert!(validate_shrink_consistency(&aggregated, sink).is_ok());
+        let overstated = PathfindingResult {
+            max_flow: U256::from(6u64),
+            ..aggregated
+        };
+        assert!(matches!(
+            validate_path_netting(&overstated, source, sink),
+            Err(TransferError::PathValidation { .. })
+        ));
     }
 
     #[test]
@@ -1203,6 +1518,7 @@ mod tests {
                 token_owner: format!("{source:#x}"),
                 value: U256::from(5u64),
             }],
+            block_number: None,
         };
         let opts = AdvancedTransferOptions {
             use_wrapped_balances: Some(true),
@@ -1217,6 +1533,7 @@ mod tests {
             simulated_trusts: None,
             max_transfers: None,
             tx_data: None,
+            validate_path: None,
         };
 
         let unchanged = maybe_add_aggregate_transfer(path, sink, &opts, true);
@@ -1,4 +1,7 @@
 use alloy_primitives::Address;
+use circles_pathfinder::PathfinderError;
+use circles_rpc::CirclesRpcError;
+use circles_types::ErrorKind;
 use thiserror::Error;
 
 /// Transfers package error source categories.
@@ -52,12 +55,86 @@ pub enum TransferError {
         terminal_sum: String,
         expected: String,
     },
+    /// Amount has precision below six decimals and the rounding policy forbids dropping it.
+    #[error("Amount {requested} wei has {dust} wei of dust below six-decimal precision")]
+    DustAmount { requested: String, dust: String },
+    /// Amount does not fit the hub's `uint192` flow amounts.
+    #[error("Amount {value} exceeds the uint192 range of flow matrix amounts")]
+    AmountOverflow { value: String },
+    /// Path failed the netting checks enabled by `TransferBuilder::with_path_validation`.
+    #[error("Transfer path failed validation: {reason}")]
+    PathValidation { reason: String },
     /// Transfer path is empty.
     #[error("Transfer path is empty for route from {from:#x} to {to:#x}")]
     EmptyPath { from: Address, to: Address },
+    /// Circles RPC request failed while fetching balances or trust relations.
+    #[error(transparent)]
+    Rpc(#[from] CirclesRpcError),
+    /// The pathfinder RPC could not compute a route.
+    #[error("Pathfinding failed: {0}")]
+    PathfindingFailed(#[source] CirclesRpcError),
+    /// Token metadata for the path (or the redeemed group) could not be fetched.
+    #[error("Token info lookup failed: {0}")]
+    TokenInfoLookupFailed(#[source] PathfinderError),
+    /// The hub v2 `isApprovedForAll` check for the flow matrix operator failed.
+    #[error("Operator approval check for {owner:#x} -> {operator:#x} failed: {source}")]
+    ApprovalCheckFailed {
+        owner: Address,
+        operator: Address,
+        #[source]
+        source: Box<alloy_contract::Error>,
+    },
+    /// The path could not be turned into a flow matrix.
+    #[error("Flow matrix construction failed: {0}")]
+    FlowMatrix(#[source] PathfinderError),
+    /// An on-chain read the transfer depends on (e.g. `checkSendLimit`, `toTokenId`) failed.
+    #[error("Contract call {call} failed: {source}")]
+    Contract {
+        call: &'static str,
+        #[source]
+        source: Box<alloy_contract::Error>,
+    },
 }
 
 impl TransferError {
+    /// Coarse classification for retry and UX policies.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            TransferError::Generic { code, category, .. } => {
+                generic_kind(code.as_deref(), *category)
+            }
+            TransferError::NoPathFound { .. }
+            | TransferError::InsufficientBalance { .. }
+            | TransferError::EmptyPath { .. } => ErrorKind::InsufficientFunds,
+            TransferError::WrappedTokensRequired
+            | TransferError::UnregisteredAvatars { .. }
+            | TransferError::DustAmount { .. }
+            | TransferError::AmountOverflow { .. } => ErrorKind::InvalidInput,
+            TransferError::FlowMatrixMismatch { .. } | TransferError::PathValidation { .. } => {
+                ErrorKind::Protocol
+            }
+            TransferError::Rpc(err) | TransferError::PathfindingFailed(err) => err.kind(),
+            TransferError::TokenInfoLookupFailed(err) | TransferError::FlowMatrix(err) => {
+                err.kind()
+            }
+            TransferError::ApprovalCheckFailed { source, .. }
+            | TransferError::Contract { source, .. } => match source.as_ref() {
+                alloy_contract::Error::TransportError(_) => ErrorKind::Network,
+                _ => ErrorKind::Protocol,
+            },
+        }
+    }
+
+    /// Whether repeating the transfer later may succeed.
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+
+    /// Whether the caller can fix the failure by changing the transfer request.
+    pub fn is_user_error(&self) -> bool {
+        self.kind().is_user_error()
+    }
+
     pub fn no_path_found(from: Address, to: Address, reason: Option<String>) -> Self {
         TransferError::NoPathFound {
             from,
@@ -99,10 +176,36 @@ impl TransferError {
         }
     }
 
+    pub fn dust_amount(requested: alloy_primitives::U256, dust: alloy_primitives::U256) -> Self {
+        TransferError::DustAmount {
+            requested: requested.to_string(),
+            dust: dust.to_string(),
+        }
+    }
+
+    pub fn amount_overflow(value: alloy_primitives::U256) -> Self {
+        TransferError::AmountOverflow {
+            value: value.to_string(),
+        }
+    }
+
+    pub fn path_validation(reason: impl Into<String>) -> Self {
+        TransferError::PathValidation {
+            reason: reason.into(),
+        }
+    }
+
     pub fn empty_path(from: Address, to: Address) -> Self {
         TransferError::EmptyPath { from, to }
     }
 
+    pub fn contract(call: &'static str, source: alloy_contract::Error) -> Self {
+        TransferError::Contract {
+            call,
+            source: Box::new(source),
+        }
+    }
+
     pub fn generic(
         message: impl Into<String>,
         code: Option<impl Into<String>>,
@@ -115,3 +218,23 @@ impl TransferError {
         }
     }
 }
+
+fn generic_kind(code: Option<&str>, category: TransfersErrorSource) -> ErrorKind {
+    match code {
+        Some(
+            "GROUP_TOKEN_REDEEM_INVALID_AMOUNT"
+            | "GROUP_TOKEN_REDEEM_EXCEEDS_MAX_FLOW"
+            | "GROUP_TOKEN_REDEEM_UNSUPPORTED_TYPE",
+        ) => ErrorKind::InvalidInput,
+        Some("REPLENISH_INSUFFICIENT_PATH_FLOW" | "GROUP_TOKEN_REDEEM_NO_TRUSTED_COLLATERAL") => {
+            ErrorKind::InsufficientFunds
+        }
+        Some("GROUP_TOKEN_REDEEM_INVALID_RPC_URL") => ErrorKind::Configuration,
+        Some("UNEXPECTED_TIME_CIRCLES_BALANCE") => ErrorKind::Protocol,
+        _ => match category {
+            TransfersErrorSource::Validation => ErrorKind::InvalidInput,
+            TransfersErrorSource::FlowMatrix => ErrorKind::Protocol,
+            TransfersErrorSource::Transfers | TransfersErrorSource::Pathfinding => ErrorKind::Other,
+        },
+    }
+}
@@ -7,6 +7,10 @@
 
 mod builder;
 mod error;
+pub mod metrics;
 
-pub use builder::{TransferBuilder, TransferTx};
+pub use builder::{
+    AdvancedTransferPlan, ApprovalMode, ApprovalPolicy, RoundingPolicy, TransferAmount,
+    TransferBuilder, TransferTx,
+};
 pub use error::{TransferError, TransfersErrorSource};
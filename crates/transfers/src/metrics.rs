@@ -0,0 +1,36 @@
+//! Transfer planning metrics for the `metrics` feature.
+//!
+//! Emitted through the `metrics` facade; install a recorder in the host service to
+//! export them. Without the feature the recorder is a no-op.
+
+use crate::builder::TransferTx;
+use crate::error::TransferError;
+use std::time::Duration;
+
+/// Counter of transfer plans, labelled by `kind` and `outcome` (`ok`/`error`).
+pub const TRANSFER_PLANS_TOTAL: &str = "circles_transfer_plans_total";
+/// Histogram of planning latency in seconds (pathfinding included), labelled by `kind`.
+pub const TRANSFER_PLAN_DURATION_SECONDS: &str = "circles_transfer_plan_duration_seconds";
+/// Histogram of transactions per successful plan, labelled by `kind`.
+pub const TRANSFER_PLAN_TRANSACTIONS: &str = "circles_transfer_plan_transactions";
+
+pub(crate) fn record_plan(
+    kind: &'static str,
+    result: Result<&[TransferTx], &TransferError>,
+    elapsed: Duration,
+) {
+    #[cfg(feature = "metrics")]
+    {
+        let outcome = if result.is_ok() { "ok" } else { "error" };
+        ::metrics::counter!(TRANSFER_PLANS_TOTAL, "kind" => kind, "outcome" => outcome)
+            .increment(1);
+        ::metrics::histogram!(TRANSFER_PLAN_DURATION_SECONDS, "kind" => kind)
+            .record(elapsed.as_secs_f64());
+        if let Ok(txs) = result {
+            ::metrics::histogram!(TRANSFER_PLAN_TRANSACTIONS, "kind" => kind)
+                .record(txs.len() as f64);
+        }
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = (kind, result, elapsed);
+}
@@ -2,7 +2,7 @@ use alloy_primitives::{address, Address, Bytes, U256};
 use alloy_sol_types::SolCall;
 use circles_abis::{BaseGroup, HubV2};
 use circles_transfers::{TransferBuilder, TransferError};
-use circles_types::CirclesConfig;
+use circles_types::{CirclesConfig, Timeouts};
 use serde_json::{json, Value};
 use std::io::{BufRead, BufReader, Read, Write};
 use std::net::{TcpListener, TcpStream};
@@ -15,8 +15,11 @@ use std::time::Duration;
 
 fn demo_config(rpc_url: &str) -> CirclesConfig {
     CirclesConfig {
+        chain_id: 100,
         circles_rpc_url: rpc_url.into(),
         chain_rpc_url: None,
+        replica_rpc_url: None,
+        archive_rpc_url: None,
         pathfinder_url: None,
         profile_service_url: None,
         referrals_service_url: None,
@@ -32,6 +35,7 @@ fn demo_config(rpc_url: &str) -> CirclesConfig {
         invitation_farm_address: Address::ZERO,
         referrals_module_address: Address::ZERO,
         invitation_module_address: Address::ZERO,
+        timeouts: Timeouts::default(),
     }
 }
 
@@ -761,3 +765,47 @@ async fn construct_group_token_redeem_fails_without_trusted_collateral() {
         0
     );
 }
+
+#[tokio::test]
+async fn construct_v1_transfer_checks_send_limit_before_transfer_through() {
+    use circles_abis::HubV1;
+
+    let from = address!("0xde374ece6fa50e781e81aac78e811b33d16912c7");
+    let to = address!("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    let v1_hub = address!("0x29b9a7fbb8995b2423a71cc17cf9810798f6c543");
+    let send_limit_selector = selector_hex(HubV1::checkSendLimitCall {
+        tokenOwner: Address::ZERO,
+        src: Address::ZERO,
+        dest: Address::ZERO,
+    });
+    let server = MockRpcServer::spawn(move |request| match request["method"].as_str().unwrap() {
+        "eth_call" => {
+            assert!(eth_call_data(request).starts_with(&send_limit_selector));
+            json_rpc_success(&request["id"], u256_result(U256::from(150u64)))
+        }
+        other => panic!("unexpected method {other}"),
+    });
+    let config = CirclesConfig {
+        v1_hub_address: v1_hub,
+        ..demo_config(server.url())
+    };
+    let builder = TransferBuilder::new(config).expect("builder");
+
+    let txs = builder
+        .construct_v1_transfer(from, to, U256::from(100u64))
+        .await
+        .expect("within send limit");
+    assert_eq!(txs.len(), 1);
+    assert_eq!(txs[0].to, v1_hub);
+    let call = HubV1::transferThroughCall::abi_decode(&txs[0].data).unwrap();
+    assert_eq!(call.tokenOwners, vec![from]);
+    assert_eq!(call.srcs, vec![from]);
+    assert_eq!(call.dests, vec![to]);
+    assert_eq!(call.wads, vec![U256::from(100u64)]);
+
+    let err = builder
+        .construct_v1_transfer(from, to, U256::from(200u64))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, TransferError::InsufficientBalance { .. }));
+}
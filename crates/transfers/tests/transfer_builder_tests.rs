@@ -1,15 +1,21 @@
 use alloy_primitives::address;
 use alloy_primitives::U256;
-use circles_transfers::TransferBuilder;
+use circles_rpc::CirclesRpcError;
+use circles_transfers::{TransferBuilder, TransferError};
 use circles_types::{
-    Address, CirclesConfig, PathfindingResult, PathfindingTransferStep, TokenInfo,
+    Address, CirclesConfig, ErrorKind, PathfindingResult, PathfindingTransferStep, Timeouts,
+    TokenInfo,
 };
 use std::collections::HashMap;
+use std::error::Error;
 
 fn demo_config() -> CirclesConfig {
     CirclesConfig {
+        chain_id: 100,
         circles_rpc_url: "http://localhost:8545".into(), // unused in these unit tests
         chain_rpc_url: None,
+        replica_rpc_url: None,
+        archive_rpc_url: None,
         pathfinder_url: None,
         profile_service_url: None,
         referrals_service_url: None,
@@ -25,6 +31,7 @@ fn demo_config() -> CirclesConfig {
         invitation_farm_address: Address::ZERO,
         referrals_module_address: Address::ZERO,
         invitation_module_address: Address::ZERO,
+        timeouts: Timeouts::default(),
     }
 }
 
@@ -67,6 +74,7 @@ fn assemble_orders_approval_unwrap_operate_rewrap() {
             token_owner: format!("{wrapper:#x}"),
             value: U256::from(1_000_000u64),
         }],
+        block_number: None,
     };
     // Wrapped totals: wrapper carries the full amount, type inflationary.
     let mut wrapped = HashMap::new();
@@ -102,6 +110,7 @@ fn assemble_orders_approval_unwrap_operate_rewrap() {
                 simulated_trusts: None,
                 max_transfers: None,
                 tx_data: None,
+                validate_path: None,
             },
             false, // skip approval check in tests
         )
@@ -147,6 +156,7 @@ fn demurraged_only_has_no_rewrap() {
             token_owner: format!("{wrapper:#x}"),
             value: U256::from(1_000_000u64),
         }],
+        block_number: None,
     };
     let mut wrapped = HashMap::new();
     wrapped.insert(
@@ -176,6 +186,7 @@ fn demurraged_only_has_no_rewrap() {
                 simulated_trusts: None,
                 max_transfers: None,
                 tx_data: None,
+                validate_path: None,
             },
             false,
         )
@@ -244,6 +255,7 @@ fn mixed_wrappers_include_rewrap_for_inflationary() {
                 value: U256::from(1_000_000u64),
             },
         ],
+        block_number: None,
     };
 
     let mut wrapped = HashMap::new();
@@ -283,6 +295,7 @@ fn mixed_wrappers_include_rewrap_for_inflationary() {
                 simulated_trusts: None,
                 max_transfers: None,
                 tx_data: None,
+                validate_path: None,
             },
             false,
         )
@@ -334,6 +347,7 @@ fn inflationary_no_leftover_skips_rewrap() {
             token_owner: format!("{inf_wrapper:#x}"),
             value: U256::from(1_000_000u64),
         }],
+        block_number: None,
     };
 
     let mut wrapped = HashMap::new();
@@ -370,6 +384,7 @@ fn inflationary_no_leftover_skips_rewrap() {
                 simulated_trusts: None,
                 max_transfers: None,
                 tx_data: None,
+                validate_path: None,
             },
             false,
         )
@@ -388,3 +403,338 @@ fn inflationary_no_leftover_skips_rewrap() {
 
 // Integration-style tests that exercise actual RPC/pathfinding are out of scope
 // for unit tests; they require a live Circles RPC. Add mocks when available.
+
+fn assemble_single_hop(
+    builder: &TransferBuilder,
+) -> Result<Vec<circles_transfers::TransferTx>, TransferError> {
+    let from = address!("0xde374ece6fa50e781e81aac78e811b33d16912c7");
+    let to = address!("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    let path = PathfindingResult {
+        max_flow: U256::from(1_000u64),
+        transfers: vec![PathfindingTransferStep {
+            from,
+            to,
+            token_owner: format!("{from:#x}"),
+            value: U256::from(1_000u64),
+        }],
+        block_number: None,
+    };
+    builder.assemble_transactions(
+        from,
+        to,
+        path,
+        HashMap::new(),
+        HashMap::new(),
+        HashMap::new(),
+        circles_types::AdvancedTransferOptions {
+            use_wrapped_balances: Some(true),
+            from_tokens: None,
+            to_tokens: None,
+            exclude_from_tokens: None,
+            exclude_to_tokens: None,
+            simulated_balances: None,
+            simulated_trusts: None,
+            max_transfers: None,
+            tx_data: None,
+            validate_path: None,
+        },
+        true, // PerTransfer skips the on-chain check
+    )
+}
+
+#[test]
+fn approval_policy_approves_the_submitter_and_revokes_after() {
+    use alloy_sol_types::SolCall;
+    use circles_abis::HubV2;
+    use circles_transfers::{ApprovalMode, ApprovalPolicy};
+
+    let relayer = address!("0xcccccccccccccccccccccccccccccccccccccccc");
+    let builder = TransferBuilder::new(demo_config())
+        .unwrap()
+        .with_approval_policy(ApprovalPolicy {
+            submitter: Some(relayer),
+            mode: ApprovalMode::PerTransfer,
+            revoke_after: true,
+        });
+
+    let txs = assemble_single_hop(&builder).unwrap();
+
+    // Expect: approve the relayer, operate, revoke the relayer.
+    assert_eq!(txs.len(), 3);
+    let approve = HubV2::setApprovalForAllCall::abi_decode(&txs[0].data).unwrap();
+    assert_eq!((approve._operator, approve._approved), (relayer, true));
+    assert!(txs[1]
+        .data
+        .starts_with(&HubV2::operateFlowMatrixCall::SELECTOR));
+    let revoke = HubV2::setApprovalForAllCall::abi_decode(&txs[2].data).unwrap();
+    assert_eq!((revoke._operator, revoke._approved), (relayer, false));
+}
+
+#[test]
+fn demurraged_wrappers_unwrap_only_what_the_path_spends() {
+    use alloy_sol_types::SolCall;
+    use circles_abis::DemurrageCircles;
+
+    #[allow(deprecated)]
+    let builder = TransferBuilder::new(demo_config())
+        .unwrap()
+        .with_demurraged_rewrap(true);
+    let from = address!("0xde374ece6fa50e781e81aac78e811b33d16912c7");
+    let to = address!("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    let wrapper = address!("0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+    let mut token_info_map = HashMap::new();
+    token_info_map.insert(
+        wrapper,
+        TokenInfo {
+            block_number: 0,
+            timestamp: 0,
+            transaction_index: 0,
+            log_index: 0,
+            transaction_hash: alloy_primitives::TxHash::ZERO,
+            version: 2,
+            info_type: None,
+            token_type: "CrcV2_ERC20WrapperDeployed_Demurraged".into(),
+            token: wrapper,
+            token_owner: to,
+        },
+    );
+    let path = PathfindingResult {
+        max_flow: U256::from(1_000_000u64),
+        transfers: vec![PathfindingTransferStep {
+            from,
+            to,
+            token_owner: format!("{wrapper:#x}"),
+            value: U256::from(1_000_000u64),
+        }],
+        block_number: None,
+    };
+    let mut wrapped = HashMap::new();
+    wrapped.insert(
+        wrapper,
+        (
+            U256::from(1_000_000u64),
+            "CrcV2_ERC20WrapperDeployed_Demurraged".into(),
+        ),
+    );
+    // The wrapper holds more than the path spends; the rest must stay wrapped.
+    let mut balances = HashMap::new();
+    balances.insert(wrapper, U256::from(3_000_000u64));
+
+    let txs = builder
+        .assemble_transactions(
+            from,
+            to,
+            path,
+            token_info_map,
+            wrapped,
+            balances,
+            circles_types::AdvancedTransferOptions {
+                use_wrapped_balances: Some(true),
+                from_tokens: None,
+                to_tokens: None,
+                exclude_from_tokens: None,
+                exclude_to_tokens: None,
+                simulated_balances: None,
+                simulated_trusts: None,
+                max_transfers: None,
+                tx_data: None,
+                validate_path: None,
+            },
+            false,
+        )
+        .unwrap();
+
+    // Expect: approval, unwrap of the spent amount, operate. No re-wrap.
+    assert_eq!(txs.len(), 3);
+    let unwrap = DemurrageCircles::unwrapCall::abi_decode(&txs[1].data).unwrap();
+    assert_eq!(txs[1].to, wrapper);
+    assert_eq!(unwrap._amount, U256::from(1_000_000u64));
+    assert_eq!(txs[2].to, builder.config().v2_hub_address);
+}
+
+#[test]
+fn path_validation_rejects_unbalanced_intermediate() {
+    let from = address!("0xde374ece6fa50e781e81aac78e811b33d16912c7");
+    let middle = address!("0xcccccccccccccccccccccccccccccccccccccccc");
+    let to = address!("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    // `middle` keeps 2 of the 5 it receives; the terminal sum still matches max_flow, so
+    // the flow matrix alone does not catch it.
+    let path = PathfindingResult {
+        max_flow: U256::from(3u64),
+        transfers: vec![
+            PathfindingTransferStep {
+                from,
+                to: middle,
+                token_owner: format!("{from:#x}"),
+                value: U256::from(5u64),
+            },
+            PathfindingTransferStep {
+                from: middle,
+                to,
+                token_owner: format!("{middle:#x}"),
+                value: U256::from(3u64),
+            },
+        ],
+        block_number: None,
+    };
+    let opts = circles_types::AdvancedTransferOptions {
+        use_wrapped_balances: Some(true),
+        from_tokens: None,
+        to_tokens: None,
+        exclude_from_tokens: None,
+        exclude_to_tokens: None,
+        simulated_balances: None,
+        simulated_trusts: None,
+        max_transfers: None,
+        tx_data: None,
+        validate_path: None,
+    };
+    let assemble = |builder: &TransferBuilder| {
+        builder.assemble_transactions(
+            from,
+            to,
+            path.clone(),
+            HashMap::new(),
+            HashMap::new(),
+            HashMap::new(),
+            opts.clone(),
+            false,
+        )
+    };
+
+    let lenient = TransferBuilder::new(demo_config()).unwrap();
+    assert!(assemble(&lenient).is_ok());
+
+    let strict = TransferBuilder::new(demo_config())
+        .unwrap()
+        .with_path_validation(true);
+    match assemble(&strict) {
+        Err(TransferError::PathValidation { reason }) => {
+            assert!(reason.contains(&format!("{middle:#x}")), "{reason}");
+        }
+        other => panic!("expected PathValidation, got {other:?}"),
+    }
+}
+
+#[test]
+fn path_validation_rejects_cycles_that_net_correctly() {
+    let from = address!("0xde374ece6fa50e781e81aac78e811b33d16912c7");
+    let a = address!("0xcccccccccccccccccccccccccccccccccccccccc");
+    let b = address!("0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+    let to = address!("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    // Every vertex nets correctly, but 2 of the flow loops back from `b` to `a`, so the path
+    // cannot be forwarded in order and shrinking it changes the edges.
+    let edge = |from: Address, to: Address, value: u64| PathfindingTransferStep {
+        from,
+        to,
+        token_owner: format!("{from:#x}"),
+        value: U256::from(value),
+    };
+    let path = PathfindingResult {
+        max_flow: U256::from(5u64),
+        transfers: vec![
+            edge(from, a, 5),
+            edge(a, b, 7),
+            edge(b, a, 2),
+            edge(b, to, 5),
+        ],
+        block_number: None,
+    };
+    let opts = |validate_path: Option<bool>| circles_types::AdvancedTransferOptions {
+        use_wrapped_balances: Some(true),
+        from_tokens: None,
+        to_tokens: None,
+        exclude_from_tokens: None,
+        exclude_to_tokens: None,
+        simulated_balances: None,
+        simulated_trusts: None,
+        max_transfers: None,
+        tx_data: None,
+        validate_path,
+    };
+    let assemble = |builder: &TransferBuilder, validate_path: Option<bool>| {
+        builder.assemble_transactions(
+            from,
+            to,
+            path.clone(),
+            HashMap::new(),
+            HashMap::new(),
+            HashMap::new(),
+            opts(validate_path),
+            false,
+        )
+    };
+
+    let lenient = TransferBuilder::new(demo_config()).unwrap();
+    assert!(assemble(&lenient, None).is_ok());
+    // The per-transfer option overrides the builder in both directions.
+    assert!(matches!(
+        assemble(&lenient, Some(true)),
+        Err(TransferError::PathValidation { .. })
+    ));
+    let strict = TransferBuilder::new(demo_config())
+        .unwrap()
+        .with_path_validation(true);
+    assert!(matches!(
+        assemble(&strict, None),
+        Err(TransferError::PathValidation { .. })
+    ));
+    assert!(assemble(&strict, Some(false)).is_ok());
+}
+
+#[tokio::test]
+async fn unreachable_rpc_keeps_typed_sources() {
+    let mut cfg = demo_config();
+    // Nothing listens on port 1, so every RPC call fails to connect.
+    cfg.circles_rpc_url = "http://127.0.0.1:1".into();
+    let builder = TransferBuilder::new(cfg).unwrap();
+    let from = address!("0xde374ece6fa50e781e81aac78e811b33d16912c7");
+    let to = address!("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    let err = builder
+        .construct_advanced_transfer(from, to, U256::from(1_000_000_000_000_000_000u128), None)
+        .await
+        .expect_err("no rpc server");
+
+    match &err {
+        TransferError::PathfindingFailed(source) => {
+            assert!(
+                matches!(source, CirclesRpcError::Transport(_)),
+                "{source:?}"
+            );
+        }
+        other => panic!("expected PathfindingFailed, got {other:?}"),
+    }
+    assert!(err.source().is_some());
+    assert_eq!(err.kind(), ErrorKind::Network);
+    assert!(err.is_retryable());
+    assert!(!err.is_user_error());
+
+    let err = builder
+        .needs_approval(from, from)
+        .await
+        .expect_err("no rpc server");
+    assert!(
+        matches!(err, TransferError::ApprovalCheckFailed { owner, .. } if owner == from),
+        "{err:?}"
+    );
+    assert!(err.is_retryable());
+    let mut cfg = demo_config();
+    cfg.chain_rpc_url = Some("http://127.0.0.1:1".into());
+    cfg.v1_hub_address = address!("0x29b9a7fbb8995b2423a71cc17cf9810798f6c543");
+    let err = TransferBuilder::new(cfg)
+        .unwrap()
+        .construct_v1_transfer(from, to, U256::from(1u64))
+        .await
+        .expect_err("no chain rpc");
+    assert!(
+        matches!(
+            err,
+            TransferError::Contract {
+                call: "checkSendLimit",
+                ..
+            }
+        ),
+        "{err:?}"
+    );
+    assert_eq!(err.kind(), ErrorKind::Network);
+}
@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+use std::fmt;
+
+/// Failure to produce credentials for an outgoing request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthError(pub String);
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "auth failed: {}", self.0)
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+/// Source of HTTP headers attached to every request against an authenticated
+/// Circles RPC or profile service deployment.
+///
+/// Called once per request, so implementations can refresh or sign tokens lazily.
+#[async_trait]
+pub trait AuthProvider: Send + Sync {
+    /// Header name/value pairs for the next request.
+    async fn headers(&self) -> Result<Vec<(String, String)>, AuthError>;
+}
+
+/// Static `Authorization: Bearer <token>` credentials.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BearerToken(String);
+
+impl BearerToken {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+}
+
+impl fmt::Debug for BearerToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BearerToken(..)")
+    }
+}
+
+#[async_trait]
+impl AuthProvider for BearerToken {
+    async fn headers(&self) -> Result<Vec<(String, String)>, AuthError> {
+        Ok(vec![(
+            "Authorization".to_string(),
+            format!("Bearer {}", self.0),
+        )])
+    }
+}
+
+/// Fixed set of headers, e.g. an `x-api-key`.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct StaticHeaders(Vec<(String, String)>);
+
+impl StaticHeaders {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.0.push((name.into(), value.into()));
+        self
+    }
+}
+
+impl fmt::Debug for StaticHeaders {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Header values are usually secrets; only show names.
+        f.debug_list()
+            .entries(self.0.iter().map(|(name, _)| name))
+            .finish()
+    }
+}
+
+#[async_trait]
+impl AuthProvider for StaticHeaders {
+    async fn headers(&self) -> Result<Vec<(String, String)>, AuthError> {
+        Ok(self.0.clone())
+    }
+}
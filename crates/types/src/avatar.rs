@@ -102,6 +102,24 @@ pub struct GroupProfile {
     pub symbol: String,
 }
 
+/// One profile search hit: the avatar plus the profile fields a result list shows.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResultProfile {
+    pub address: Address,
+    #[serde(default)]
+    pub avatar_type: Option<AvatarType>,
+    /// Empty when the avatar has no profile.
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub preview_image_url: Option<String>,
+    #[serde(default)]
+    pub image_url: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::Profile;
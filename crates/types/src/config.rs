@@ -1,15 +1,66 @@
-use alloy_primitives::Address;
+use crate::Timeouts;
+use alloy_primitives::{address, Address};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// Gnosis Chain id.
+pub const GNOSIS_CHAIN_ID: u64 = 100;
+/// Chiado testnet chain id.
+pub const CHIADO_CHAIN_ID: u64 = 10200;
+
+/// Reasons a [`CirclesConfig`] could not be loaded or failed validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// The source document or an override could not be parsed.
+    Parse(String),
+    /// A URL field is not an absolute http(s) URL.
+    InvalidUrl { field: &'static str, value: String },
+    /// A required contract address is zero.
+    MissingAddress(&'static str),
+    /// No preset exists for the requested chain id.
+    UnknownChain(u64),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(message) => write!(f, "invalid config: {message}"),
+            Self::InvalidUrl { field, value } => {
+                write!(f, "{field} is not an http(s) url: {value:?}")
+            }
+            Self::MissingAddress(field) => write!(f, "{field} must be a non-zero address"),
+            Self::UnknownChain(chain_id) => write!(f, "no circles preset for chain {chain_id}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
 
 /// Circles protocol configuration for a specific chain.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CirclesConfig {
+    /// Chain the deployment lives on; runners are checked against it before sending.
+    #[serde(default = "default_chain_id")]
+    pub chain_id: u64,
     /// RPC URL for Circles-specific endpoints
     pub circles_rpc_url: String,
     /// Optional RPC URL for direct chain calls such as `eth_call`.
     ///
     /// Falls back to `circles_rpc_url` when omitted.
     pub chain_rpc_url: Option<String>,
+    /// Optional read replica for latency-sensitive `circles_*` reads (balances, trust,
+    /// avatar lookups, pathfinding).
+    ///
+    /// Falls back to `circles_rpc_url` when omitted.
+    #[serde(default)]
+    pub replica_rpc_url: Option<String>,
+    /// Optional archive endpoint for heavy historical queries: `circles_events` backfills
+    /// and paged table exports.
+    ///
+    /// Falls back to `circles_rpc_url` when omitted.
+    #[serde(default)]
+    pub archive_rpc_url: Option<String>,
     /// Deprecated pathfinder URL.
     ///
     /// Newer deployments serve pathfinder data from the main Circles RPC host.
@@ -44,9 +95,239 @@ pub struct CirclesConfig {
     pub referrals_module_address: Address,
     /// Invitation Module contract address
     pub invitation_module_address: Address,
+    /// Connect, request, subscription-idle, and pathfinder time limits.
+    #[serde(default)]
+    pub timeouts: Timeouts,
 }
 
 impl CirclesConfig {
+    /// Circles deployment on Gnosis Chain.
+    pub fn gnosis_mainnet() -> Self {
+        Self {
+            chain_id: GNOSIS_CHAIN_ID,
+            circles_rpc_url: "https://rpc.aboutcircles.com/".to_string(),
+            chain_rpc_url: None,
+            replica_rpc_url: None,
+            archive_rpc_url: None,
+            pathfinder_url: None,
+            profile_service_url: None,
+            referrals_service_url: Some("https://referrals.aboutcircles.com".to_string()),
+            v1_hub_address: address!("29b9a7fbb8995b2423a71cc17cf9810798f6c543"),
+            v2_hub_address: address!("c12c1e50abb450d6205ea2c3fa861b3b834d13e8"),
+            name_registry_address: address!("a27566fd89162cc3d40cb59c87aaaa49b85f3474"),
+            base_group_mint_policy: address!("cca27c26cf7bac2a9928f42201d48220f0e3a549"),
+            standard_treasury: address!("08f90ab73a515308f03a718257ff9887ed330c6e"),
+            core_members_group_deployer: address!("feca40eb02fb1f4f5f795fc7a03c1a27819b1ded"),
+            base_group_factory_address: address!("d0b5bd9962197beac4cba24244ec3587f19bd06d"),
+            lift_erc20_address: address!("5f99a795dd2743c36d63511f0d4bc667e6d3cdb5"),
+            invitation_escrow_address: address!("8f8b74fa13eaaff4176d061a0f98ad5c8e19c903"),
+            invitation_farm_address: Address::ZERO,
+            referrals_module_address: address!("12105a9b291af2abb0591001155a75949b062ce5"),
+            invitation_module_address: address!("00738aca013b7b2e6cfe1690f0021c3182fa40b5"),
+            timeouts: Timeouts::default(),
+        }
+    }
+
+    /// Chiado testnet: chain id and public chain RPC.
+    ///
+    /// No Circles contract addresses ship for Chiado, so this preset does not pass
+    /// [`CirclesConfig::validate`] until the Circles RPC URL and addresses of your
+    /// testnet deployment are layered on top (see [`CirclesConfig::from_json_str`] and
+    /// [`CirclesConfig::from_env`]).
+    pub fn chiado() -> Self {
+        Self {
+            chain_id: CHIADO_CHAIN_ID,
+            circles_rpc_url: String::new(),
+            chain_rpc_url: Some("https://rpc.chiadochain.net/".to_string()),
+            replica_rpc_url: None,
+            archive_rpc_url: None,
+            pathfinder_url: None,
+            profile_service_url: None,
+            referrals_service_url: None,
+            v1_hub_address: Address::ZERO,
+            v2_hub_address: Address::ZERO,
+            name_registry_address: Address::ZERO,
+            base_group_mint_policy: Address::ZERO,
+            standard_treasury: Address::ZERO,
+            core_members_group_deployer: Address::ZERO,
+            base_group_factory_address: Address::ZERO,
+            lift_erc20_address: Address::ZERO,
+            invitation_escrow_address: Address::ZERO,
+            invitation_farm_address: Address::ZERO,
+            referrals_module_address: Address::ZERO,
+            invitation_module_address: Address::ZERO,
+            timeouts: Timeouts::default(),
+        }
+    }
+
+    /// Preset for a chain id ([`GNOSIS_CHAIN_ID`] or [`CHIADO_CHAIN_ID`]).
+    pub fn for_chain(chain_id: u64) -> Result<Self, ConfigError> {
+        match chain_id {
+            GNOSIS_CHAIN_ID => Ok(Self::gnosis_mainnet()),
+            CHIADO_CHAIN_ID => Ok(Self::chiado()),
+            other => Err(ConfigError::UnknownChain(other)),
+        }
+    }
+
+    /// Check that every URL is an absolute http(s) URL and every required contract
+    /// address is set. `v1_hub_address` and `invitation_farm_address` may be zero.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        check_url("circles_rpc_url", Some(&self.circles_rpc_url))?;
+        check_url("chain_rpc_url", self.chain_rpc_url.as_deref())?;
+        check_url("replica_rpc_url", self.replica_rpc_url.as_deref())?;
+        check_url("archive_rpc_url", self.archive_rpc_url.as_deref())?;
+        check_url("pathfinder_url", self.pathfinder_url.as_deref())?;
+        check_url("profile_service_url", self.profile_service_url.as_deref())?;
+        check_url(
+            "referrals_service_url",
+            self.referrals_service_url.as_deref(),
+        )?;
+
+        let required = [
+            ("v2_hub_address", self.v2_hub_address),
+            ("name_registry_address", self.name_registry_address),
+            ("base_group_mint_policy", self.base_group_mint_policy),
+            ("standard_treasury", self.standard_treasury),
+            (
+                "core_members_group_deployer",
+                self.core_members_group_deployer,
+            ),
+            (
+                "base_group_factory_address",
+                self.base_group_factory_address,
+            ),
+            ("lift_erc20_address", self.lift_erc20_address),
+            ("invitation_escrow_address", self.invitation_escrow_address),
+            ("referrals_module_address", self.referrals_module_address),
+            ("invitation_module_address", self.invitation_module_address),
+        ];
+        match required.iter().find(|(_, address)| address.is_zero()) {
+            Some((field, _)) => Err(ConfigError::MissingAddress(field)),
+            None => Ok(()),
+        }
+    }
+
+    /// Load and validate a config from JSON.
+    ///
+    /// A document with every required field (URLs may be omitted) is read as-is and must
+    /// name its `chain_id`, so configs for Chiado or a local chain load without a preset.
+    /// A partial document overrides the preset of its `chain_id`. `timeouts` is a table
+    /// of [`Timeouts`] in milliseconds.
+    pub fn from_json_str(json: &str) -> Result<Self, ConfigError> {
+        let value: Value =
+            serde_json::from_str(json).map_err(|e| ConfigError::Parse(e.to_string()))?;
+        Self::from_value(value)
+    }
+
+    /// Load and validate a config from TOML, with the same layering as
+    /// [`CirclesConfig::from_json_str`]. Requires the `toml` feature.
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(document: &str) -> Result<Self, ConfigError> {
+        let value: toml::Value =
+            toml::from_str(document).map_err(|e| ConfigError::Parse(e.to_string()))?;
+        Self::from_value(
+            serde_json::to_value(value).map_err(|e| ConfigError::Parse(e.to_string()))?,
+        )
+    }
+
+    /// Load and validate a config from `CIRCLES_*` environment variables.
+    ///
+    /// Variables are read like a [`CirclesConfig::from_json_str`] document, except that a
+    /// partial set without `CIRCLES_CHAIN_ID` layers over the Gnosis Chain preset. Each
+    /// field is set by its upper-cased name with a `CIRCLES_` prefix, e.g.
+    /// `CIRCLES_CHAIN_RPC_URL`, `CIRCLES_V2_HUB_ADDRESS`. `CIRCLES_RPC_URL` and
+    /// `CIRCLES_PROFILE_URL` set `circles_rpc_url` and `profile_service_url`.
+    /// Other `CIRCLES_*` variables are ignored.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Self::from_env_vars(std::env::vars())
+    }
+
+    /// [`CirclesConfig::from_env`] over an explicit set of variables.
+    pub fn from_env_vars<I, K, V>(vars: I) -> Result<Self, ConfigError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        let mut fields = Map::new();
+        for (key, value) in vars {
+            let Some(name) = key.as_ref().strip_prefix("CIRCLES_") else {
+                continue;
+            };
+            let value = value.into();
+            if name == "CHAIN_ID" {
+                let chain_id: u64 = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| ConfigError::Parse(format!("CIRCLES_CHAIN_ID: {value:?}")))?;
+                fields.insert("chain_id".to_string(), chain_id.into());
+                continue;
+            }
+            let field = match name.to_ascii_lowercase() {
+                field if field == "rpc_url" => "circles_rpc_url".to_string(),
+                field if field == "profile_url" => "profile_service_url".to_string(),
+                field => field,
+            };
+            if FIELDS.contains(&field.as_str()) {
+                fields.insert(field, Value::String(value));
+            }
+        }
+        Self::from_fields(fields, Some(GNOSIS_CHAIN_ID))
+    }
+
+    fn from_value(value: Value) -> Result<Self, ConfigError> {
+        let Value::Object(fields) = value else {
+            return Err(ConfigError::Parse(
+                "expected a table of config fields".to_string(),
+            ));
+        };
+        Self::from_fields(fields, None)
+    }
+
+    /// Read `fields` as a whole config when every required field is present, otherwise
+    /// layer them over the preset for their `chain_id` (or `default_chain`).
+    fn from_fields(
+        mut fields: Map<String, Value>,
+        default_chain: Option<u64>,
+    ) -> Result<Self, ConfigError> {
+        if let Some(key) = fields.keys().find(|key| !FIELDS.contains(&key.as_str())) {
+            return Err(ConfigError::Parse(format!("unknown field {key}")));
+        }
+        let chain_id = match fields.get("chain_id") {
+            Some(chain_id) => Some(
+                chain_id
+                    .as_u64()
+                    .ok_or_else(|| ConfigError::Parse(format!("chain_id: {chain_id}")))?,
+            ),
+            None => None,
+        };
+        let complete = REQUIRED_FIELDS
+            .iter()
+            .all(|field| fields.contains_key(*field));
+        let merged = if complete {
+            if chain_id.is_none() {
+                return Err(ConfigError::Parse(
+                    "chain_id is required for a complete config".to_string(),
+                ));
+            }
+            fields
+        } else {
+            let chain_id = chain_id.or(default_chain).ok_or_else(|| {
+                ConfigError::Parse("a partial config needs a chain_id to pick its preset".into())
+            })?;
+            let mut merged = match serde_json::to_value(Self::for_chain(chain_id)?) {
+                Ok(Value::Object(preset)) => preset,
+                _ => unreachable!("config serializes to an object"),
+            };
+            merged.append(&mut fields);
+            merged
+        };
+        let config: Self = serde_json::from_value(Value::Object(merged))
+            .map_err(|e| ConfigError::Parse(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
     /// Chain RPC URL, falling back to `circles_rpc_url`.
     pub fn effective_chain_rpc_url(&self) -> &str {
         self.chain_rpc_url
@@ -54,6 +335,20 @@ impl CirclesConfig {
             .unwrap_or(self.circles_rpc_url.as_str())
     }
 
+    /// Read replica URL, falling back to `circles_rpc_url`.
+    pub fn effective_replica_rpc_url(&self) -> &str {
+        self.replica_rpc_url
+            .as_deref()
+            .unwrap_or(self.circles_rpc_url.as_str())
+    }
+
+    /// Archive URL, falling back to `circles_rpc_url`.
+    pub fn effective_archive_rpc_url(&self) -> &str {
+        self.archive_rpc_url
+            .as_deref()
+            .unwrap_or(self.circles_rpc_url.as_str())
+    }
+
     /// Profile service URL, falling back to `<circles_rpc_url>/profiles/`.
     pub fn effective_profile_service_url(&self) -> String {
         match self.profile_service_url.as_deref() {
@@ -63,6 +358,106 @@ impl CirclesConfig {
     }
 }
 
+/// A [`CirclesConfig`] under a name, such as `production` on Gnosis Chain or `staging`
+/// on Chiado, so one application can address several deployments side by side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Environment {
+    pub name: String,
+    pub config: CirclesConfig,
+}
+
+impl Environment {
+    /// Name given to a Gnosis Chain config by [`Environment::from_config`].
+    pub const PRODUCTION: &'static str = "production";
+    /// Name given to a Chiado config by [`Environment::from_config`].
+    pub const STAGING: &'static str = "staging";
+
+    pub fn new(name: impl Into<String>, config: CirclesConfig) -> Self {
+        Self {
+            name: name.into(),
+            config,
+        }
+    }
+
+    /// The Gnosis Chain deployment as `production`.
+    pub fn production() -> Self {
+        Self::new(Self::PRODUCTION, CirclesConfig::gnosis_mainnet())
+    }
+
+    /// Name `config` after its chain: `production`, `staging`, or `chain-<id>`.
+    pub fn from_config(config: CirclesConfig) -> Self {
+        let name = match config.chain_id {
+            GNOSIS_CHAIN_ID => Self::PRODUCTION.to_string(),
+            CHIADO_CHAIN_ID => Self::STAGING.to_string(),
+            other => format!("chain-{other}"),
+        };
+        Self::new(name, config)
+    }
+}
+
+fn default_chain_id() -> u64 {
+    GNOSIS_CHAIN_ID
+}
+
+/// Every top-level key of a config document.
+const FIELDS: [&str; 21] = [
+    "chain_id",
+    "circles_rpc_url",
+    "chain_rpc_url",
+    "replica_rpc_url",
+    "archive_rpc_url",
+    "pathfinder_url",
+    "profile_service_url",
+    "referrals_service_url",
+    "v1_hub_address",
+    "v2_hub_address",
+    "name_registry_address",
+    "base_group_mint_policy",
+    "standard_treasury",
+    "core_members_group_deployer",
+    "base_group_factory_address",
+    "lift_erc20_address",
+    "invitation_escrow_address",
+    "invitation_farm_address",
+    "referrals_module_address",
+    "invitation_module_address",
+    "timeouts",
+];
+
+/// Fields without a default; a document with all of them is read without a preset.
+const REQUIRED_FIELDS: [&str; 13] = [
+    "circles_rpc_url",
+    "v1_hub_address",
+    "v2_hub_address",
+    "name_registry_address",
+    "base_group_mint_policy",
+    "standard_treasury",
+    "core_members_group_deployer",
+    "base_group_factory_address",
+    "lift_erc20_address",
+    "invitation_escrow_address",
+    "invitation_farm_address",
+    "referrals_module_address",
+    "invitation_module_address",
+];
+
+fn check_url(field: &'static str, value: Option<&str>) -> Result<(), ConfigError> {
+    let Some(value) = value else {
+        return Ok(());
+    };
+    let host = value
+        .strip_prefix("https://")
+        .or_else(|| value.strip_prefix("http://"))
+        .map(|rest| rest.split(['/', '?', '#']).next().unwrap_or_default());
+    match host {
+        Some(host) if !host.is_empty() && !host.contains(char::is_whitespace) => Ok(()),
+        _ => Err(ConfigError::InvalidUrl {
+            field,
+            value: value.to_string(),
+        }),
+    }
+}
+
 fn ensure_trailing_slash(url: &str) -> String {
     if url.ends_with('/') {
         url.to_owned()
@@ -77,8 +472,11 @@ mod tests {
 
     fn demo_config() -> CirclesConfig {
         CirclesConfig {
+            chain_id: GNOSIS_CHAIN_ID,
             circles_rpc_url: "https://rpc.example.com".into(),
             chain_rpc_url: None,
+            replica_rpc_url: None,
+            archive_rpc_url: None,
             pathfinder_url: None,
             profile_service_url: None,
             referrals_service_url: None,
@@ -94,6 +492,7 @@ mod tests {
             invitation_farm_address: Address::ZERO,
             referrals_module_address: Address::ZERO,
             invitation_module_address: Address::ZERO,
+            timeouts: Timeouts::default(),
         }
     }
 
@@ -114,6 +513,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn replica_and_archive_urls_fall_back_to_circles_rpc_url() {
+        let mut config = demo_config();
+        assert_eq!(
+            config.effective_replica_rpc_url(),
+            "https://rpc.example.com"
+        );
+        assert_eq!(
+            config.effective_archive_rpc_url(),
+            "https://rpc.example.com"
+        );
+
+        config.archive_rpc_url = Some("https://archive.example.com".into());
+        assert_eq!(
+            config.effective_replica_rpc_url(),
+            "https://rpc.example.com"
+        );
+        assert_eq!(
+            config.effective_archive_rpc_url(),
+            "https://archive.example.com"
+        );
+
+        let from_env =
+            CirclesConfig::from_env_vars([("CIRCLES_REPLICA_RPC_URL", "https://replica.example/")])
+                .expect("config");
+        assert_eq!(
+            from_env.effective_replica_rpc_url(),
+            "https://replica.example/"
+        );
+    }
+
     #[test]
     fn effective_profile_service_url_defaults_to_profiles_path() {
         let config = demo_config();
@@ -133,4 +563,154 @@ mod tests {
             "https://profiles.example.com/api/"
         );
     }
+
+    #[test]
+    fn presets_validate_where_deployed() {
+        assert!(CirclesConfig::gnosis_mainnet().validate().is_ok());
+        assert!(matches!(
+            CirclesConfig::chiado().validate(),
+            Err(ConfigError::InvalidUrl {
+                field: "circles_rpc_url",
+                ..
+            })
+        ));
+        assert_eq!(
+            CirclesConfig::for_chain(CHIADO_CHAIN_ID).unwrap().chain_id,
+            CHIADO_CHAIN_ID
+        );
+        assert_eq!(
+            CirclesConfig::for_chain(1).unwrap_err(),
+            ConfigError::UnknownChain(1)
+        );
+    }
+
+    #[test]
+    fn json_overrides_preset_fields() {
+        let config = CirclesConfig::from_json_str(
+            r#"{ "chain_id": 100, "circles_rpc_url": "https://staging.example.com/" }"#,
+        )
+        .expect("config");
+        assert_eq!(config.circles_rpc_url, "https://staging.example.com/");
+        assert_eq!(
+            config.v2_hub_address,
+            CirclesConfig::gnosis_mainnet().v2_hub_address
+        );
+
+        let err = CirclesConfig::from_json_str(r#"{ "chain_id": 100, "v2_hub_adress": "0x01" }"#)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ConfigError::Parse("unknown field v2_hub_adress".into())
+        );
+        assert!(matches!(
+            CirclesConfig::from_json_str(r#"{ "circles_rpc_url": "https://x.example" }"#),
+            Err(ConfigError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn chain_id_follows_preset_and_names_environment() {
+        let layered = CirclesConfig::from_json_str(r#"{ "chain_id": 100 }"#).expect("config");
+        assert_eq!(layered.chain_id, GNOSIS_CHAIN_ID);
+
+        let mut config = demo_config();
+        config.chain_id = CHIADO_CHAIN_ID;
+        assert_eq!(Environment::from_config(config.clone()).name, "staging");
+        config.chain_id = 31337;
+        assert_eq!(Environment::from_config(config).name, "chain-31337");
+        assert_eq!(Environment::production().config.chain_id, GNOSIS_CHAIN_ID);
+
+        // Full documents written before `chain_id` existed still load as Gnosis Chain.
+        let mut value = serde_json::to_value(CirclesConfig::gnosis_mainnet()).unwrap();
+        value.as_object_mut().unwrap().remove("chain_id");
+        let legacy: CirclesConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(legacy.chain_id, GNOSIS_CHAIN_ID);
+    }
+
+    #[test]
+    fn complete_documents_keep_their_chain_id() {
+        let mut config = CirclesConfig::gnosis_mainnet();
+        config.chain_id = CHIADO_CHAIN_ID;
+        config.circles_rpc_url = "https://chiado.example.com/".into();
+        config.v2_hub_address = Address::repeat_byte(2);
+
+        let json = serde_json::to_string(&config).unwrap();
+        let loaded = CirclesConfig::from_json_str(&json).expect("config");
+        assert_eq!(loaded.chain_id, CHIADO_CHAIN_ID);
+        assert_eq!(loaded.circles_rpc_url, "https://chiado.example.com/");
+        assert_eq!(loaded.v2_hub_address, Address::repeat_byte(2));
+        assert_eq!(serde_json::to_string(&loaded).unwrap(), json);
+
+        let mut value = serde_json::to_value(&config).unwrap();
+        value.as_object_mut().unwrap().remove("chain_id");
+        assert_eq!(
+            CirclesConfig::from_json_str(&value.to_string()).unwrap_err(),
+            ConfigError::Parse("chain_id is required for a complete config".into())
+        );
+    }
+
+    #[test]
+    fn json_overrides_timeouts() {
+        let config = CirclesConfig::from_json_str(
+            r#"{ "chain_id": 100, "timeouts": { "request_ms": 5000, "connect_ms": null } }"#,
+        )
+        .expect("config");
+        assert_eq!(
+            config.timeouts.request,
+            Some(std::time::Duration::from_secs(5))
+        );
+        assert_eq!(config.timeouts.connect, None);
+        assert_eq!(config.timeouts.pathfinder, Timeouts::default().pathfinder);
+    }
+
+    #[test]
+    fn full_json_document_is_validated() {
+        let mut config = demo_config();
+        config.v2_hub_address = Address::repeat_byte(1);
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            CirclesConfig::from_json_str(&json).unwrap_err(),
+            ConfigError::MissingAddress("name_registry_address")
+        );
+    }
+
+    #[test]
+    fn env_vars_layer_over_chain_preset() {
+        let config = CirclesConfig::from_env_vars([
+            ("CIRCLES_RPC_URL", "https://rpc.example.com/"),
+            (
+                "CIRCLES_INVITATION_FARM_ADDRESS",
+                "0x0101010101010101010101010101010101010101",
+            ),
+            ("CIRCLES_PROFILE_URL", "https://profiles.example.com/"),
+            ("CIRCLES_RPC_WS_URL", "wss://rpc.example.com/ws"),
+            ("PATH", "/usr/bin"),
+        ])
+        .expect("config");
+        assert_eq!(config.circles_rpc_url, "https://rpc.example.com/");
+        assert_eq!(
+            config.profile_service_url.as_deref(),
+            Some("https://profiles.example.com/")
+        );
+        assert_eq!(config.invitation_farm_address, Address::repeat_byte(1));
+
+        assert_eq!(
+            CirclesConfig::from_env_vars([("CIRCLES_CHAIN_RPC_URL", "rpc.example.com")])
+                .unwrap_err(),
+            ConfigError::InvalidUrl {
+                field: "chain_rpc_url",
+                value: "rpc.example.com".into()
+            }
+        );
+        assert!(matches!(
+            CirclesConfig::from_env_vars([("CIRCLES_V2_HUB_ADDRESS", "0xnothex")]),
+            Err(ConfigError::Parse(_))
+        ));
+        assert_eq!(
+            CirclesConfig::from_env_vars([("CIRCLES_DEPLOY_TAG", "v2")])
+                .expect("config")
+                .v2_hub_address,
+            CirclesConfig::gnosis_mainnet().v2_hub_address
+        );
+    }
 }
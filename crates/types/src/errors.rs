@@ -10,3 +10,78 @@ pub struct DecodedContractError {
     pub raw_data: String,
     pub formatted_message: String,
 }
+
+/// Coarse classification of a Circles error, shared by the RPC, pathfinder, transfer,
+/// and SDK error types so applications can drive retries and user-facing messages
+/// without matching on error strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// The connection failed or the server answered with a 5xx status.
+    Network,
+    /// A configured time limit elapsed before the operation finished.
+    Timeout,
+    /// The server asked the client to slow down (HTTP 429 or a JSON-RPC rate-limit error).
+    RateLimited,
+    /// The request was malformed: a bad amount, an unsupported token, invalid parameters.
+    InvalidInput,
+    /// The sender cannot cover the amount, or no trust path carries enough flow.
+    InsufficientFunds,
+    /// The avatar, token, or record does not exist.
+    NotFound,
+    /// A runner, signer, or contract refused the operation.
+    Rejected,
+    /// The client is misconfigured: a bad URL, missing credentials, no runner attached.
+    Configuration,
+    /// The server answered with something the client could not interpret, or a
+    /// computed result broke a protocol invariant.
+    Protocol,
+    /// Anything the source error does not classify further.
+    Other,
+}
+
+impl ErrorKind {
+    /// Whether repeating the same request later may succeed.
+    pub const fn is_retryable(self) -> bool {
+        matches!(self, Self::Network | Self::Timeout | Self::RateLimited)
+    }
+
+    /// Whether the end user can fix the failure by changing what they asked for.
+    pub const fn is_user_error(self) -> bool {
+        matches!(
+            self,
+            Self::InvalidInput | Self::InsufficientFunds | Self::NotFound | Self::Rejected
+        )
+    }
+
+    /// Classify a non-success HTTP status code.
+    pub const fn from_http_status(status: u16) -> Self {
+        match status {
+            408 => Self::Timeout,
+            429 => Self::RateLimited,
+            401 | 403 => Self::Configuration,
+            404 => Self::NotFound,
+            400..=499 => Self::InvalidInput,
+            500..=599 => Self::Network,
+            _ => Self::Protocol,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ErrorKind;
+
+    #[test]
+    fn http_statuses_map_to_kinds() {
+        assert_eq!(ErrorKind::from_http_status(429), ErrorKind::RateLimited);
+        assert_eq!(ErrorKind::from_http_status(503), ErrorKind::Network);
+        assert_eq!(ErrorKind::from_http_status(404), ErrorKind::NotFound);
+        assert_eq!(ErrorKind::from_http_status(401), ErrorKind::Configuration);
+        assert_eq!(ErrorKind::from_http_status(422), ErrorKind::InvalidInput);
+        assert!(ErrorKind::from_http_status(502).is_retryable());
+        assert!(!ErrorKind::from_http_status(400).is_retryable());
+        assert!(ErrorKind::InsufficientFunds.is_user_error());
+        assert!(!ErrorKind::Timeout.is_user_error());
+    }
+}
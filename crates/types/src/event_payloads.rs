@@ -0,0 +1,434 @@
+//! Typed payloads for every [`CirclesEventType`].
+//!
+//! [`CirclesEvent::data`] keeps the raw key/value map from the RPC. The structs here mirror
+//! the on-chain event parameters (camelCase keys, numbers as JSON numbers or decimal/hex
+//! strings) and are obtained with `TryFrom<CirclesEvent>`, either per event type or through
+//! [`CirclesEventPayload`].
+
+use crate::events::{CirclesEvent, CirclesEventType};
+use alloy_primitives::{Address, B256, U256};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Failure to convert a [`CirclesEvent`] into a typed payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventPayloadError {
+    /// The event is of a different type than the requested payload.
+    WrongType {
+        expected: CirclesEventType,
+        actual: CirclesEventType,
+    },
+    /// A field is missing or has the wrong shape.
+    Decode {
+        event_type: CirclesEventType,
+        message: String,
+    },
+}
+
+impl fmt::Display for EventPayloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongType { expected, actual } => {
+                write!(f, "expected {expected:?} event, got {actual:?}")
+            }
+            Self::Decode {
+                event_type,
+                message,
+            } => write!(f, "invalid {event_type:?} payload: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for EventPayloadError {}
+
+/// Small integers (`uint8`/`uint16`) arrive as numbers or strings like the `uint256` ones.
+fn small_uint<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryFrom<U256>,
+{
+    let value = U256::deserialize(deserializer)?;
+    T::try_from(value).map_err(|_| serde::de::Error::custom(format!("{value} out of range")))
+}
+
+// HubV2 ---------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalForAllEvent {
+    pub account: Address,
+    pub operator: Address,
+    pub approved: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscountCostEvent {
+    pub account: Address,
+    /// Token id for hub events; absent when emitted by an ERC20 wrapper.
+    #[serde(default)]
+    pub id: Option<U256>,
+    pub discount_cost: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlowEdgesScopeLastEndedEvent {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlowEdgesScopeSingleStartedEvent {
+    pub flow_edge_id: U256,
+    #[serde(deserialize_with = "small_uint")]
+    pub stream_id: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupMintEvent {
+    pub sender: Address,
+    pub receiver: Address,
+    pub group: Address,
+    pub collateral: Vec<U256>,
+    pub amounts: Vec<U256>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonalMintEvent {
+    pub human: Address,
+    pub amount: U256,
+    pub start_period: U256,
+    pub end_period: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterGroupEvent {
+    pub group: Address,
+    pub mint: Address,
+    pub treasury: Address,
+    pub name: String,
+    pub symbol: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterHumanEvent {
+    pub avatar: Address,
+    /// Zero or absent for self-registrations and older indexer rows.
+    #[serde(default)]
+    pub inviter: Option<Address>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterOrganizationEvent {
+    pub organization: Address,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetAdvancedUsageFlagEvent {
+    pub avatar: Address,
+    pub flag: B256,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoppedEvent {
+    pub avatar: Address,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamCompletedEvent {
+    pub operator: Address,
+    pub from: Address,
+    pub to: Address,
+    pub ids: Vec<U256>,
+    pub amounts: Vec<U256>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferBatchEvent {
+    pub operator: Address,
+    pub from: Address,
+    pub to: Address,
+    pub ids: Vec<U256>,
+    pub values: Vec<U256>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferSingleEvent {
+    pub operator: Address,
+    pub from: Address,
+    pub to: Address,
+    pub id: U256,
+    pub value: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustEvent {
+    pub truster: Address,
+    pub trustee: Address,
+    /// Unix seconds; at or before the block timestamp means trust was removed.
+    pub expiry_time: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UriEvent {
+    pub value: String,
+    pub id: U256,
+}
+
+// ERC20 wrappers ------------------------------------------------------------
+//
+// `tokenAddress` is the emitting wrapper when the indexer includes it.
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalEvent {
+    #[serde(default)]
+    pub token_address: Option<Address>,
+    pub owner: Address,
+    pub spender: Address,
+    pub value: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositDemurragedEvent {
+    #[serde(default)]
+    pub token_address: Option<Address>,
+    pub account: Address,
+    pub amount: U256,
+    pub inflationary_amount: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositInflationaryEvent {
+    #[serde(default)]
+    pub token_address: Option<Address>,
+    pub account: Address,
+    pub amount: U256,
+    pub demurraged_amount: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Eip712DomainChangedEvent {
+    #[serde(default)]
+    pub token_address: Option<Address>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Erc20TransferEvent {
+    #[serde(default)]
+    pub token_address: Option<Address>,
+    pub from: Address,
+    pub to: Address,
+    pub value: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawDemurragedEvent {
+    #[serde(default)]
+    pub token_address: Option<Address>,
+    pub account: Address,
+    pub amount: U256,
+    pub inflationary_amount: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawInflationaryEvent {
+    #[serde(default)]
+    pub token_address: Option<Address>,
+    pub account: Address,
+    pub amount: U256,
+    pub demurraged_amount: U256,
+}
+
+// Name registry -------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CidV0Event {
+    pub avatar: Address,
+    pub cid_v0_digest: B256,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterShortNameEvent {
+    pub avatar: Address,
+    pub short_name: U256,
+    pub nonce: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateMetadataDigestEvent {
+    pub avatar: Address,
+    pub metadata_digest: B256,
+}
+
+// Base group treasury -------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupRedeemCollateralBurnEvent {
+    pub group: Address,
+    #[serde(alias = "burnIds")]
+    pub ids: Vec<U256>,
+    #[serde(alias = "burnValues")]
+    pub values: Vec<U256>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupRedeemCollateralReturnEvent {
+    pub group: Address,
+    pub to: Address,
+    #[serde(alias = "redemptionIds")]
+    pub ids: Vec<U256>,
+    #[serde(alias = "redemptionValues")]
+    pub values: Vec<U256>,
+}
+
+// Invitations ---------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InviteHumanEvent {
+    pub inviter: Address,
+    pub invitee: Address,
+}
+
+fn decode<T: DeserializeOwned>(event: &CirclesEvent) -> Result<T, EventPayloadError> {
+    let object = event
+        .data
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    serde_json::from_value(serde_json::Value::Object(object)).map_err(|err| {
+        EventPayloadError::Decode {
+            event_type: event.event_type.clone(),
+            message: err.to_string(),
+        }
+    })
+}
+
+macro_rules! event_payloads {
+    ($($variant:ident => $payload:ident),* $(,)?) => {
+        /// Typed payload of a [`CirclesEvent`], one variant per [`CirclesEventType`].
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum CirclesEventPayload {
+            $($variant($payload),)*
+            /// `Crc_UnknownEvent`: the raw values are kept as-is.
+            Unknown(HashMap<String, serde_json::Value>),
+        }
+
+        impl CirclesEventPayload {
+            /// Event type this payload was decoded from.
+            pub fn event_type(&self) -> CirclesEventType {
+                match self {
+                    $(Self::$variant(_) => CirclesEventType::$variant,)*
+                    Self::Unknown(_) => CirclesEventType::CrcUnknownEvent,
+                }
+            }
+        }
+
+        impl TryFrom<&CirclesEvent> for CirclesEventPayload {
+            type Error = EventPayloadError;
+
+            fn try_from(event: &CirclesEvent) -> Result<Self, Self::Error> {
+                match event.event_type {
+                    $(CirclesEventType::$variant => decode(event).map(Self::$variant),)*
+                    CirclesEventType::CrcUnknownEvent => Ok(Self::Unknown(event.data.clone())),
+                }
+            }
+        }
+
+        $(
+            impl TryFrom<&CirclesEvent> for $payload {
+                type Error = EventPayloadError;
+
+                fn try_from(event: &CirclesEvent) -> Result<Self, Self::Error> {
+                    if event.event_type != CirclesEventType::$variant {
+                        return Err(EventPayloadError::WrongType {
+                            expected: CirclesEventType::$variant,
+                            actual: event.event_type.clone(),
+                        });
+                    }
+                    decode(event)
+                }
+            }
+
+            impl TryFrom<CirclesEvent> for $payload {
+                type Error = EventPayloadError;
+
+                fn try_from(event: CirclesEvent) -> Result<Self, Self::Error> {
+                    Self::try_from(&event)
+                }
+            }
+        )*
+    };
+}
+
+event_payloads! {
+    CrcV2ApprovalForAll => ApprovalForAllEvent,
+    CrcV2DiscountCost => DiscountCostEvent,
+    CrcV2FlowEdgesScopeLastEnded => FlowEdgesScopeLastEndedEvent,
+    CrcV2FlowEdgesScopeSingleStarted => FlowEdgesScopeSingleStartedEvent,
+    CrcV2GroupMint => GroupMintEvent,
+    CrcV2PersonalMint => PersonalMintEvent,
+    CrcV2RegisterGroup => RegisterGroupEvent,
+    CrcV2RegisterHuman => RegisterHumanEvent,
+    CrcV2RegisterOrganization => RegisterOrganizationEvent,
+    CrcV2SetAdvancedUsageFlag => SetAdvancedUsageFlagEvent,
+    CrcV2Stopped => StoppedEvent,
+    CrcV2StreamCompleted => StreamCompletedEvent,
+    CrcV2TransferBatch => TransferBatchEvent,
+    CrcV2TransferSingle => TransferSingleEvent,
+    CrcV2Trust => TrustEvent,
+    CrcV2URI => UriEvent,
+    CrcV2Approval => ApprovalEvent,
+    CrcV2DepositDemurraged => DepositDemurragedEvent,
+    CrcV2DepositInflationary => DepositInflationaryEvent,
+    CrcV2EIP712DomainChanged => Eip712DomainChangedEvent,
+    CrcV2Transfer => Erc20TransferEvent,
+    CrcV2WithdrawDemurraged => WithdrawDemurragedEvent,
+    CrcV2WithdrawInflationary => WithdrawInflationaryEvent,
+    CrcV2CidV0 => CidV0Event,
+    CrcV2RegisterShortName => RegisterShortNameEvent,
+    CrcV2UpdateMetadataDigest => UpdateMetadataDigestEvent,
+    CrcV2GroupRedeemCollateralBurn => GroupRedeemCollateralBurnEvent,
+    CrcV2GroupRedeemCollateralReturn => GroupRedeemCollateralReturnEvent,
+    CrcV2InviteHuman => InviteHumanEvent,
+}
+
+impl TryFrom<CirclesEvent> for CirclesEventPayload {
+    type Error = EventPayloadError;
+
+    fn try_from(event: CirclesEvent) -> Result<Self, Self::Error> {
+        Self::try_from(&event)
+    }
+}
+
+impl CirclesEvent {
+    /// Decode [`Self::data`] into the typed payload for [`Self::event_type`].
+    pub fn payload(&self) -> Result<CirclesEventPayload, EventPayloadError> {
+        CirclesEventPayload::try_from(self)
+    }
+}
@@ -1,6 +1,8 @@
 use alloy_primitives::TxHash;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 
 /// Base event information shared by all Circles events.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -101,9 +103,160 @@ pub struct CirclesEvent {
     pub data: HashMap<String, serde_json::Value>,
 }
 
+impl CirclesEvent {
+    /// Cursor to persist once this event is handled; resuming from it skips the event.
+    pub fn cursor(&self) -> EventCursor {
+        EventCursor::after(self)
+    }
+}
+
+/// Opaque resume position in the event log, ordered by block, transaction and log index.
+///
+/// A cursor points at the next event to deliver, so resuming from one never repeats an
+/// event that was already handled. It round-trips through its string form (and serde),
+/// which is what consumers should persist.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(into = "String", try_from = "String")]
+pub struct EventCursor {
+    block_number: u64,
+    transaction_index: u32,
+    log_index: u32,
+}
+
+impl EventCursor {
+    /// Start with the first event of `block_number`.
+    pub fn at_block(block_number: u64) -> Self {
+        Self {
+            block_number,
+            transaction_index: 0,
+            log_index: 0,
+        }
+    }
+
+    /// Position right after `event`.
+    pub fn after(event: &CirclesEvent) -> Self {
+        Self {
+            block_number: event.base.block_number,
+            transaction_index: event.base.transaction_index,
+            log_index: event.base.log_index.saturating_add(1),
+        }
+    }
+
+    /// First block that may still hold undelivered events.
+    pub fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    /// Whether `event` comes at or after this cursor, i.e. has not been delivered yet.
+    pub fn is_pending(&self, event: &CirclesEvent) -> bool {
+        let position = (
+            event.base.block_number,
+            event.base.transaction_index,
+            event.base.log_index,
+        );
+        position >= (self.block_number, self.transaction_index, self.log_index)
+    }
+}
+
+impl fmt::Display for EventCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}",
+            self.block_number, self.transaction_index, self.log_index
+        )
+    }
+}
+
+/// A string that is not a cursor written by [`EventCursor`]'s `Display`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidEventCursor(pub String);
+
+impl fmt::Display for InvalidEventCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid event cursor {:?}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidEventCursor {}
+
+impl FromStr for EventCursor {
+    type Err = InvalidEventCursor;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidEventCursor(s.to_string());
+        let mut parts = s.split(':');
+        let mut next = || parts.next().ok_or_else(invalid);
+        let cursor = Self {
+            block_number: next()?.parse().map_err(|_| invalid())?,
+            transaction_index: next()?.parse().map_err(|_| invalid())?,
+            log_index: next()?.parse().map_err(|_| invalid())?,
+        };
+        match parts.next() {
+            None => Ok(cursor),
+            Some(_) => Err(invalid()),
+        }
+    }
+}
+
+impl From<EventCursor> for String {
+    fn from(cursor: EventCursor) -> Self {
+        cursor.to_string()
+    }
+}
+
+impl TryFrom<String> for EventCursor {
+    type Error = InvalidEventCursor;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
 /// Raw RPC subscription event (`eth_subscribe("circles")`) before parsing.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RpcSubscriptionEvent {
     pub event: String,
     pub values: HashMap<String, serde_json::Value>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(block_number: u64, transaction_index: u32, log_index: u32) -> CirclesEvent {
+        CirclesEvent {
+            base: CirclesBaseEvent {
+                block_number,
+                timestamp: None,
+                transaction_index,
+                log_index,
+                transaction_hash: None,
+            },
+            event_type: CirclesEventType::CrcUnknownEvent,
+            data: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn cursor_skips_handled_events_and_round_trips() {
+        let handled = event(10, 2, 7);
+        let cursor = handled.cursor();
+        assert!(!cursor.is_pending(&handled));
+        assert!(cursor.is_pending(&event(10, 2, 8)));
+        assert!(cursor.is_pending(&event(10, 3, 0)));
+        assert!(!cursor.is_pending(&event(9, 9, 9)));
+        assert!(EventCursor::at_block(10).is_pending(&event(10, 0, 0)));
+
+        let persisted = serde_json::to_string(&cursor).unwrap();
+        assert_eq!(persisted, "\"10:2:8\"");
+        assert_eq!(
+            serde_json::from_str::<EventCursor>(&persisted).unwrap(),
+            cursor
+        );
+        assert!("10:2".parse::<EventCursor>().is_err());
+        assert!("10:2:8:1".parse::<EventCursor>().is_err());
+    }
+}
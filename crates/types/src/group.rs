@@ -127,6 +127,20 @@ pub struct GroupTokenHolderRow {
     pub fraction_ownership: f64,
 }
 
+/// A group member's holdings of the group token.
+///
+/// Members without a `GroupTokenHoldersBalance` row hold nothing and get zero balances.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupMemberBalance {
+    pub group: Address,
+    pub member: Address,
+    pub expiry_time: u64,
+    pub total_balance: U256,
+    pub demurraged_total_balance: U256,
+    pub fraction_ownership: f64,
+}
+
 /// Group query parameters
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -138,6 +152,9 @@ pub struct GroupQueryParams {
     pub owner_in: Option<Vec<Address>>,
     pub mint_handler_equals: Option<Address>,
     pub treasury_equals: Option<Address>,
+    pub mint_policy_equals: Option<Address>,
+    /// Only groups this avatar is a member of (resolved from its memberships client-side).
+    pub has_member: Option<Address>,
 }
 
 #[cfg(test)]
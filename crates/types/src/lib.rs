@@ -85,6 +85,7 @@
 //! - [`AvatarInfo`] - Complete avatar information and metadata
 //! - [`Profile`] - User profile with name, description, images
 //! - [`GroupProfile`] - Group profile extending Profile with symbol
+//! - [`SearchResultProfile`] - Profile search hit with avatar type and preview image
 //! - [`AvatarType`] - Registration event types (Human, Group, Organization)
 //!
 //! ### Trust & Social Graph
@@ -101,6 +102,7 @@
 //! ### Group Management
 //! - [`GroupRow`] - Group registration and metadata
 //! - [`GroupMembershipRow`] - Group membership records
+//! - [`GroupMemberBalance`] - Member holdings of a group token
 //! - [`GroupQueryParams`] - Parameters for group queries
 //!
 //! ### Pathfinding & Transfers
@@ -115,12 +117,18 @@
 //! - [`CirclesEvent`] - Universal event structure
 //! - [`CirclesEventType`] - All supported event types (25+ variants)
 //! - [`CirclesBaseEvent`] - Common event metadata
+//! - [`CirclesEventPayload`] - Typed payload per event type (e.g. [`TrustEvent`],
+//!   [`TransferSingleEvent`]) via `TryFrom<CirclesEvent>`
 //!
 //! ### RPC & Communication
 //! - [`JsonRpcRequest`], [`JsonRpcResponse`] - Standard JSON-RPC types
 //! - [`CirclesQueryResponse`] - Response format for queries
 //! - [`TokenBalanceResponse`] - Token balance from RPC calls
 //!
+//! ### Authentication
+//! - [`AuthProvider`] - Per-request header source for authenticated deployments
+//! - [`BearerToken`], [`StaticHeaders`] - Static credentials
+//!
 //! ### Query System
 //! - [`QueryParams`] - Parameters for `circles_query` RPC method
 //! - [`FilterPredicate`], [`Conjunction`] - Query filtering DSL
@@ -130,15 +138,22 @@
 //! ### Contract Execution
 //! - [`ContractRunner`] - Async trait for contract interactions
 //! - [`BatchRun`] - Trait for batched transaction execution
-//! - [`RunnerConfig`] - Configuration for contract runners
+//! - [`RunnerConfig`] - Configuration for contract runners, including fee strategy,
+//!   stuck-transaction retries, and bundler/relayer endpoints
 //!
 //! ### Protocol Configuration
-//! - [`CirclesConfig`] - Complete protocol configuration
+//! - [`CirclesConfig`] - Complete protocol configuration, with chain presets and
+//!   JSON/TOML/env loaders
+//! - [`ConfigError`] - Config loading and validation failures
+//! - [`Environment`] - A config under a name, e.g. `production` or `staging`
+//! - [`Timeouts`] - Connect, request, subscription-idle, and pathfinder limits
 //! - [`EscrowedAmountAndDays`] - Contract-specific response types
 //! - [`DecodedContractError`] - Contract error information
+//! - [`ErrorKind`] - Retryable / user-error classification shared by the client errors
 //!
 //! ### Network State
 //! - [`NetworkSnapshot`] - Complete network state at a block
+//! - [`SnapshotDiff`] - Trust and balance changes between two snapshots
 //! - [`EventRow`] - Base structure for event pagination
 //! - [`Cursor`] - Pagination cursor for efficient queries
 
@@ -153,33 +168,55 @@ pub use alloy_primitives::Bytes;
 pub use alloy_primitives::{aliases::U192, U256};
 
 // Alloy RPC types
-pub use alloy_provider::transport::TransportResult;
 pub use alloy_rpc_types::TransactionRequest;
+pub use alloy_transport::TransportResult;
 
 // =============================================================================
 // Internal modules with explicit re-exports
 // =============================================================================
 
+mod auth;
+pub use auth::{AuthError, AuthProvider, BearerToken, StaticHeaders};
+
 mod avatar;
-pub use avatar::{AvatarInfo, AvatarType, GeoLocation, GroupProfile, Profile};
+pub use avatar::{AvatarInfo, AvatarType, GeoLocation, GroupProfile, Profile, SearchResultProfile};
 
 mod config;
-pub use config::CirclesConfig;
+pub use config::{CirclesConfig, ConfigError, Environment, CHIADO_CHAIN_ID, GNOSIS_CHAIN_ID};
+mod timeouts;
+pub use timeouts::Timeouts;
 
 mod contracts;
 pub use contracts::EscrowedAmountAndDays;
 
 mod errors;
-pub use errors::DecodedContractError;
+pub use errors::{DecodedContractError, ErrorKind};
 
 mod events;
-pub use events::{CirclesBaseEvent, CirclesEvent, CirclesEventType, RpcSubscriptionEvent};
+pub use events::{
+    CirclesBaseEvent, CirclesEvent, CirclesEventType, EventCursor, InvalidEventCursor,
+    RpcSubscriptionEvent,
+};
+
+mod event_payloads;
+pub use event_payloads::{
+    ApprovalEvent, ApprovalForAllEvent, CidV0Event, CirclesEventPayload, DepositDemurragedEvent,
+    DepositInflationaryEvent, DiscountCostEvent, Eip712DomainChangedEvent, Erc20TransferEvent,
+    EventPayloadError, FlowEdgesScopeLastEndedEvent, FlowEdgesScopeSingleStartedEvent,
+    GroupMintEvent, GroupRedeemCollateralBurnEvent, GroupRedeemCollateralReturnEvent,
+    InviteHumanEvent, PersonalMintEvent, RegisterGroupEvent, RegisterHumanEvent,
+    RegisterOrganizationEvent, RegisterShortNameEvent, SetAdvancedUsageFlagEvent, StoppedEvent,
+    StreamCompletedEvent, TransferBatchEvent, TransferSingleEvent, TrustEvent,
+    UpdateMetadataDigestEvent, UriEvent, WithdrawDemurragedEvent, WithdrawInflationaryEvent,
+};
 
 mod group;
-pub use group::{GroupMembershipRow, GroupQueryParams, GroupRow, GroupTokenHolderRow};
+pub use group::{
+    GroupMemberBalance, GroupMembershipRow, GroupQueryParams, GroupRow, GroupTokenHolderRow,
+};
 
 mod network;
-pub use network::{EventType, NetworkSnapshot};
+pub use network::{BalanceChange, EventType, NetworkSnapshot, SnapshotDiff, TrustChange};
 
 mod trust;
 pub use trust::{AggregatedTrustRelation, TrustRelation, TrustRelationType};
@@ -194,7 +231,10 @@ mod client;
 pub use client::{AvatarRow, CirclesQuery, GroupType, TokenBalanceRow, TrustRelationRow};
 
 mod runner;
-pub use runner::{BatchRun, ContractRunner, RunnerConfig};
+pub use runner::{
+    BatchRun, BundlerConfig, ContractRunner, FeeCaps, FeeStrategy, PaymasterConfig, RelayConfig,
+    RelayMode, RunnerConfig, StuckTxRetry, ENTRY_POINT_V07,
+};
 
 mod rpc;
 pub use rpc::{
@@ -203,7 +243,7 @@ pub use rpc::{
     InvitationsFromResponse, InvitedAccountInfo, InviterInfo, JsonRpcError, JsonRpcRequest,
     JsonRpcResponse, PagedAggregatedTrustRelationsResponse, PagedProfileSearchResponse,
     PagedResponse, PagedValidInvitersResponse, ParticipantInfo, ProfileView, QueryResponse,
-    SafeQueryResponse, TokenBalanceResponse, TransactionHistoryRow, TrustInvitation,
+    SafeQueryResponse, TokenBalanceResponse, TotalBalance, TransactionHistoryRow, TrustInvitation,
     TrustNetworkSummary, TrustRelationCounts, TrustRelationInfo, TrustStats,
 };
 
@@ -222,6 +262,7 @@ pub use pathfinding::{
     FlowEdge,
     FlowEdgeStruct,
     FlowMatrix,
+    PathfinderConfig,
     PathfindingFlowMatrix,
     PathfindingResult,
     PathfindingTransferStep,
@@ -1,5 +1,7 @@
 use crate::{TokenBalance, TrustRelation};
+use alloy_primitives::{Address, U256};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Event types for network events
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -34,3 +36,237 @@ pub struct NetworkSnapshot {
     pub block_number: u64,
     pub timestamp: u64,
 }
+
+/// A single trust edge change between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrustChange {
+    /// Edge present only in the newer snapshot.
+    Added {
+        truster: Address,
+        trustee: Address,
+        expiry_time: u64,
+    },
+    /// Edge present only in the older snapshot.
+    Removed { truster: Address, trustee: Address },
+    /// Edge present in both snapshots with a different expiry.
+    ExpiryChanged {
+        truster: Address,
+        trustee: Address,
+        from: u64,
+        to: u64,
+    },
+}
+
+/// Balance change for one holder/token pair between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceChange {
+    pub account: Option<Address>,
+    pub token_address: Address,
+    pub token_id: U256,
+    /// Balance in atto-circles in the older snapshot (zero when absent).
+    pub before: U256,
+    /// Balance in atto-circles in the newer snapshot (zero when absent).
+    pub after: U256,
+}
+
+impl BalanceChange {
+    /// Whether the balance grew between the two snapshots.
+    pub fn is_increase(&self) -> bool {
+        self.after > self.before
+    }
+}
+
+/// Trust and balance changes between two [`NetworkSnapshot`]s.
+///
+/// Changes are ordered by their keys, so diffs of the same snapshots are stable.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    pub from_block: u64,
+    pub to_block: u64,
+    pub trust_changes: Vec<TrustChange>,
+    pub balance_changes: Vec<BalanceChange>,
+}
+
+type BalanceKey = (Option<Address>, Address, U256);
+
+impl SnapshotDiff {
+    /// Compute the changes needed to go from `old` to `new`.
+    pub fn between(old: &NetworkSnapshot, new: &NetworkSnapshot) -> Self {
+        Self {
+            from_block: old.block_number,
+            to_block: new.block_number,
+            trust_changes: diff_trust(&old.trust_relations, &new.trust_relations),
+            balance_changes: diff_balances(&old.balances, &new.balances),
+        }
+    }
+
+    /// Whether the snapshots are equivalent in trust and balances.
+    pub fn is_empty(&self) -> bool {
+        self.trust_changes.is_empty() && self.balance_changes.is_empty()
+    }
+}
+
+fn trust_index(relations: &[TrustRelation]) -> BTreeMap<(Address, Address), u64> {
+    relations
+        .iter()
+        .map(|rel| ((rel.truster, rel.trustee), rel.expiry_time))
+        .collect()
+}
+
+fn diff_trust(old: &[TrustRelation], new: &[TrustRelation]) -> Vec<TrustChange> {
+    let old = trust_index(old);
+    let new = trust_index(new);
+    let mut changes = Vec::new();
+    for (&(truster, trustee), &expiry_time) in &new {
+        match old.get(&(truster, trustee)) {
+            None => changes.push(TrustChange::Added {
+                truster,
+                trustee,
+                expiry_time,
+            }),
+            Some(&from) if from != expiry_time => changes.push(TrustChange::ExpiryChanged {
+                truster,
+                trustee,
+                from,
+                to: expiry_time,
+            }),
+            Some(_) => {}
+        }
+    }
+    for &(truster, trustee) in old.keys() {
+        if !new.contains_key(&(truster, trustee)) {
+            changes.push(TrustChange::Removed { truster, trustee });
+        }
+    }
+    changes
+}
+
+fn balance_index(balances: &[TokenBalance]) -> BTreeMap<BalanceKey, U256> {
+    let mut index = BTreeMap::new();
+    for balance in balances {
+        *index
+            .entry((balance.account, balance.token_address, balance.token_id))
+            .or_insert(U256::ZERO) += balance.atto_circles;
+    }
+    index
+}
+
+fn diff_balances(old: &[TokenBalance], new: &[TokenBalance]) -> Vec<BalanceChange> {
+    let old = balance_index(old);
+    let new = balance_index(new);
+    let mut keys: Vec<&BalanceKey> = old.keys().chain(new.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter()
+        .filter_map(|key| {
+            let before = old.get(key).copied().unwrap_or_default();
+            let after = new.get(key).copied().unwrap_or_default();
+            (before != after).then_some(BalanceChange {
+                account: key.0,
+                token_address: key.1,
+                token_id: key.2,
+                before,
+                after,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::TxHash;
+
+    fn trust(truster: u8, trustee: u8, expiry_time: u64) -> TrustRelation {
+        TrustRelation {
+            block_number: 0,
+            timestamp: 0,
+            transaction_index: 0,
+            log_index: 0,
+            transaction_hash: TxHash::ZERO,
+            truster: Address::repeat_byte(truster),
+            trustee: Address::repeat_byte(trustee),
+            expiry_time,
+        }
+    }
+
+    fn balance(account: u8, token: u8, atto: u64) -> TokenBalance {
+        TokenBalance {
+            account: Some(Address::repeat_byte(account)),
+            token_address: Address::repeat_byte(token),
+            token_id: U256::from(token),
+            token_owner: Address::repeat_byte(token),
+            token_type: "CrcV2_RegisterHuman".into(),
+            version: 2,
+            atto_circles: U256::from(atto),
+            circles: 0.0,
+            static_atto_circles: U256::ZERO,
+            static_circles: 0.0,
+            atto_crc: U256::ZERO,
+            crc: 0.0,
+            is_erc20: false,
+            is_erc1155: true,
+            is_wrapped: false,
+            is_inflationary: false,
+            is_group: false,
+        }
+    }
+
+    fn snapshot(
+        block_number: u64,
+        trust_relations: Vec<TrustRelation>,
+        balances: Vec<TokenBalance>,
+    ) -> NetworkSnapshot {
+        NetworkSnapshot {
+            trust_relations,
+            balances,
+            block_number,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn snapshot_diff_reports_trust_changes() {
+        let old = snapshot(1, vec![trust(1, 2, 10), trust(1, 3, 10)], vec![]);
+        let new = snapshot(2, vec![trust(1, 2, 20), trust(2, 3, 30)], vec![]);
+
+        let diff = SnapshotDiff::between(&old, &new);
+        assert_eq!((diff.from_block, diff.to_block), (1, 2));
+        assert_eq!(
+            diff.trust_changes,
+            vec![
+                TrustChange::ExpiryChanged {
+                    truster: Address::repeat_byte(1),
+                    trustee: Address::repeat_byte(2),
+                    from: 10,
+                    to: 20,
+                },
+                TrustChange::Added {
+                    truster: Address::repeat_byte(2),
+                    trustee: Address::repeat_byte(3),
+                    expiry_time: 30,
+                },
+                TrustChange::Removed {
+                    truster: Address::repeat_byte(1),
+                    trustee: Address::repeat_byte(3),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn snapshot_diff_reports_balance_changes() {
+        let old = snapshot(1, vec![], vec![balance(1, 9, 100), balance(2, 9, 50)]);
+        let new = snapshot(2, vec![], vec![balance(1, 9, 100), balance(3, 9, 5)]);
+
+        let diff = SnapshotDiff::between(&old, &new);
+        assert_eq!(diff.balance_changes.len(), 2);
+        assert_eq!(
+            diff.balance_changes[0].account,
+            Some(Address::repeat_byte(2))
+        );
+        assert_eq!(diff.balance_changes[0].after, U256::ZERO);
+        assert!(diff.balance_changes[1].is_increase());
+        assert!(SnapshotDiff::between(&new, &new).is_empty());
+    }
+}
@@ -1,13 +1,20 @@
 use alloy_primitives::{aliases::U192, Address, Bytes, U256};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// Simulated balance for path finding.
+///
+/// Replaces the holder's indexed balance of `token` for one pathfinder request.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SimulatedBalance {
     pub holder: Address,
     pub token: Address,
     pub amount: U256,
+    #[serde(alias = "is_wrapped")]
     pub is_wrapped: bool,
+    /// `amount` is in static (inflationary) units.
+    #[serde(alias = "is_static")]
     pub is_static: bool,
 }
 
@@ -62,6 +69,10 @@ pub struct PathfindingTransferStep {
 pub struct PathfindingResult {
     pub max_flow: U256,
     pub transfers: Vec<PathfindingTransferStep>,
+    /// Indexer block the path was computed against, when the backend reports it or the
+    /// caller fetched it. Lets cached paths be aged in blocks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
 }
 
 /// Flow edge structure for `operateFlowMatrix`.
@@ -115,6 +126,9 @@ pub struct AdvancedTransferOptions {
 
     /// Custom data to attach to the transfer (optional)
     pub tx_data: Option<Bytes>,
+
+    /// Validate the found path before building transactions (optional; `None` keeps the builder's setting)
+    pub validate_path: Option<bool>,
 }
 
 impl AdvancedTransferOptions {
@@ -141,10 +155,33 @@ impl AdvancedTransferOptions {
     }
 }
 
+/// Limits applied to every pathfinder request made through a client.
+///
+/// Set on `CirclesRpc` (and forwarded by the transfer builder and SDK) so a slow
+/// `circlesV2_findPath` response fails with a timeout instead of hanging the caller.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PathfinderConfig {
+    /// Abort a pathfinder request after this long. `None` waits indefinitely.
+    pub timeout: Option<Duration>,
+    /// `max_transfers` used when a request leaves it unset.
+    pub default_max_transfers: Option<u32>,
+}
+
+impl PathfinderConfig {
+    /// Fill `params.max_transfers` from the default when the caller left it unset.
+    pub fn apply_defaults(&self, params: &mut FindPathParams) {
+        if params.max_transfers.is_none() {
+            params.max_transfers = self.default_max_transfers;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{FindPathParams, SimulatedTrust};
-    use alloy_primitives::{Address, U256};
+    use super::{
+        FindPathParams, FlowEdge, FlowMatrix, PathfindingFlowMatrix, SimulatedTrust, Stream,
+    };
+    use alloy_primitives::{aliases::U192, Address, U256};
     use serde_json::json;
 
     #[test]
@@ -176,6 +213,50 @@ mod tests {
             }])
         );
     }
+
+    #[test]
+    fn flow_matrix_round_trips_through_pathfinding_shape() {
+        let matrix = FlowMatrix {
+            flow_vertices: vec![Address::repeat_byte(0x11), Address::repeat_byte(0xab)],
+            flow_edges: vec![FlowEdge {
+                stream_sink_id: 1,
+                amount: U192::from(1000u64),
+            }],
+            streams: vec![Stream {
+                source_coordinate: 0,
+                flow_edge_ids: vec![0],
+                data: vec![0xca, 0xfe],
+            }],
+            packed_coordinates: vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x01],
+            source_coordinate: 0,
+        };
+
+        let shaped = PathfindingFlowMatrix::from(matrix.clone());
+        assert_eq!(
+            shaped.flow_vertices[1],
+            format!("{:#x}", Address::repeat_byte(0xab))
+        );
+        assert_eq!(shaped.packed_coordinates, "0x000000000001");
+        assert_eq!(shaped.streams[0].data.as_ref(), &[0xca, 0xfe]);
+
+        let back = FlowMatrix::try_from(shaped).expect("valid matrix");
+        assert_eq!(back.flow_vertices, matrix.flow_vertices);
+        assert_eq!(back.packed_coordinates, matrix.packed_coordinates);
+        assert_eq!(back.streams[0].data, matrix.streams[0].data);
+        assert_eq!(back.source_coordinate, 0);
+    }
+
+    #[test]
+    fn invalid_pathfinding_vertices_are_rejected() {
+        let shaped = PathfindingFlowMatrix {
+            flow_vertices: vec!["not-an-address".to_string()],
+            flow_edges: vec![],
+            streams: vec![],
+            packed_coordinates: "0x".to_string(),
+            source_coordinate: 0,
+        };
+        assert!(FlowMatrix::try_from(shaped).is_err());
+    }
 }
 
 // ============================================================================
@@ -219,3 +300,81 @@ pub struct FlowMatrix {
     pub packed_coordinates: Vec<u8>,
     pub source_coordinate: u16,
 }
+
+// ============================================================================
+// Conversions between the flow representations
+// ============================================================================
+
+impl From<FlowEdge> for FlowEdgeStruct {
+    fn from(edge: FlowEdge) -> Self {
+        Self {
+            stream_sink_id: edge.stream_sink_id,
+            amount: edge.amount,
+        }
+    }
+}
+
+impl From<FlowEdgeStruct> for FlowEdge {
+    fn from(edge: FlowEdgeStruct) -> Self {
+        Self {
+            stream_sink_id: edge.stream_sink_id,
+            amount: edge.amount,
+        }
+    }
+}
+
+impl From<Stream> for StreamStruct {
+    fn from(stream: Stream) -> Self {
+        Self {
+            source_coordinate: stream.source_coordinate,
+            flow_edge_ids: stream.flow_edge_ids,
+            data: stream.data.into(),
+        }
+    }
+}
+
+impl From<StreamStruct> for Stream {
+    fn from(stream: StreamStruct) -> Self {
+        Self {
+            source_coordinate: stream.source_coordinate,
+            flow_edge_ids: stream.flow_edge_ids,
+            data: stream.data.into(),
+        }
+    }
+}
+
+impl From<FlowMatrix> for PathfindingFlowMatrix {
+    /// Vertices become lowercase `0x` strings and coordinates a `0x` hex string.
+    fn from(matrix: FlowMatrix) -> Self {
+        Self {
+            flow_vertices: matrix
+                .flow_vertices
+                .iter()
+                .map(|vertex| format!("{vertex:#x}"))
+                .collect(),
+            flow_edges: matrix.flow_edges.into_iter().map(Into::into).collect(),
+            streams: matrix.streams.into_iter().map(Into::into).collect(),
+            packed_coordinates: alloy_primitives::hex::encode_prefixed(&matrix.packed_coordinates),
+            source_coordinate: matrix.source_coordinate,
+        }
+    }
+}
+
+impl TryFrom<PathfindingFlowMatrix> for FlowMatrix {
+    type Error = alloy_primitives::hex::FromHexError;
+
+    /// Fails when a vertex is not an address or the coordinates are not valid hex.
+    fn try_from(matrix: PathfindingFlowMatrix) -> Result<Self, Self::Error> {
+        Ok(Self {
+            flow_vertices: matrix
+                .flow_vertices
+                .iter()
+                .map(|vertex| vertex.parse())
+                .collect::<Result<_, _>>()?,
+            flow_edges: matrix.flow_edges.into_iter().map(Into::into).collect(),
+            streams: matrix.streams.into_iter().map(Into::into).collect(),
+            packed_coordinates: alloy_primitives::hex::decode(&matrix.packed_coordinates)?,
+            source_coordinate: matrix.source_coordinate,
+        })
+    }
+}
@@ -342,6 +342,27 @@ pub enum Balance {
     TimeCircles(f64),
 }
 
+/// An avatar's aggregate balance on both hubs, from `circles_getTotalBalance` (v1) and
+/// `circlesV2_getTotalBalance` (v2).
+///
+/// Amounts are 18-decimal atto units: atto-time-circles when `as_time_circles` is set,
+/// otherwise demurraged atto-circles (v2) or atto-CRC of the v1 hub.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TotalBalance {
+    pub v1: U256,
+    pub v2: U256,
+    pub as_time_circles: bool,
+}
+
+impl TotalBalance {
+    /// `v1 + v2`, only when both are in time circles; the hubs' raw units differ.
+    pub fn total_time_circles(&self) -> Option<U256> {
+        self.as_time_circles
+            .then(|| self.v1.saturating_add(self.v2))
+    }
+}
+
 /// Token balance response from circles_getTokenBalances
 #[derive(Debug, Clone, Serialize)]
 pub struct TokenBalanceResponse {
@@ -1,9 +1,11 @@
-use alloy_primitives::Address;
+use alloy_primitives::{address, Address};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
 
 // Re-export from our base types
-use crate::TransactionRequest;
+use crate::{Timeouts, TransactionRequest};
 
 /// Batch transaction runner trait
 /// Allows multiple transactions to be batched and executed atomically
@@ -68,8 +70,13 @@ pub trait ContractRunner {
     fn send_batch_transaction(&self) -> Option<Self::BatchRunner>;
 }
 
-/// Configuration for contract runners
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Configuration for contract runners.
+///
+/// Besides the endpoint description, it carries the submission settings of runners
+/// that control their own transactions: fee strategy and caps, stuck-transaction
+/// retries, and the bundler or relayer that account-abstraction and sponsored
+/// runners submit through.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RunnerConfig {
     /// RPC endpoint URL
     pub rpc_url: String,
@@ -79,4 +86,297 @@ pub struct RunnerConfig {
     pub default_gas_limit: Option<u64>,
     /// Default gas price
     pub default_gas_price: Option<u64>,
+    /// Limits for the runner's RPC and relayer requests
+    #[serde(default)]
+    pub timeouts: Timeouts,
+    /// Fee strategy for fresh submissions.
+    #[serde(default)]
+    pub fee_strategy: FeeStrategy,
+    /// Hard caps applied after the strategy and after replacement bumps.
+    #[serde(default)]
+    pub fee_caps: FeeCaps,
+    /// Minimum fee increase (in percent) for replacement transactions.
+    #[serde(default = "default_replacement_bump_percent")]
+    pub replacement_bump_percent: u128,
+    /// Retry policy for stuck transactions; `None` disables automatic retries.
+    #[serde(default)]
+    pub stuck_retry: Option<StuckTxRetry>,
+    /// Bundler and paymaster endpoints for account-abstraction runners.
+    #[serde(default)]
+    pub bundler: Option<BundlerConfig>,
+    /// Relayer for sponsored meta-transactions.
+    #[serde(default)]
+    pub relay: Option<RelayConfig>,
+}
+
+/// Most nodes reject replacements that bump fees by less than 10%.
+const DEFAULT_REPLACEMENT_BUMP_PERCENT: u128 = 10;
+
+fn default_replacement_bump_percent() -> u128 {
+    DEFAULT_REPLACEMENT_BUMP_PERCENT
+}
+
+impl Default for RunnerConfig {
+    /// No endpoint, provider-chosen fees, no automatic retries.
+    fn default() -> Self {
+        Self {
+            rpc_url: String::new(),
+            chain_id: 0,
+            default_gas_limit: None,
+            default_gas_price: None,
+            timeouts: Timeouts::default(),
+            fee_strategy: FeeStrategy::Provider,
+            fee_caps: FeeCaps::default(),
+            replacement_bump_percent: DEFAULT_REPLACEMENT_BUMP_PERCENT,
+            stuck_retry: None,
+            bundler: None,
+            relay: None,
+        }
+    }
+}
+
+impl RunnerConfig {
+    /// Set the fee strategy.
+    pub fn with_fee_strategy(mut self, fee_strategy: FeeStrategy) -> Self {
+        self.fee_strategy = fee_strategy;
+        self
+    }
+
+    /// Set the fee caps.
+    pub fn with_fee_caps(mut self, fee_caps: FeeCaps) -> Self {
+        self.fee_caps = fee_caps;
+        self
+    }
+
+    /// Set the minimum replacement bump in percent.
+    pub fn with_replacement_bump_percent(mut self, percent: u128) -> Self {
+        self.replacement_bump_percent = percent;
+        self
+    }
+
+    /// Enable automatic re-broadcast of stuck transactions.
+    pub fn with_stuck_retry(mut self, stuck_retry: StuckTxRetry) -> Self {
+        self.stuck_retry = Some(stuck_retry);
+        self
+    }
+
+    /// Submit through an ERC-4337 bundler.
+    pub fn with_bundler(mut self, bundler: BundlerConfig) -> Self {
+        self.bundler = Some(bundler);
+        self
+    }
+
+    /// Submit through a sponsoring relayer.
+    pub fn with_relay(mut self, relay: RelayConfig) -> Self {
+        self.relay = Some(relay);
+        self
+    }
+
+    /// Replace the connect and request limits.
+    pub fn with_timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+}
+
+/// Canonical ERC-4337 v0.7 EntryPoint deployment.
+pub const ENTRY_POINT_V07: Address = address!("0000000071727De22E5E9d8BAf0edAc6f37da032");
+
+/// How fees are chosen for a fresh submission.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FeeStrategy {
+    /// Leave fees to the backend's fillers.
+    #[default]
+    Provider,
+    /// Legacy `gasPrice` transactions at `multiplier_percent` of the node's gas price.
+    Legacy { multiplier_percent: u128 },
+    /// EIP-1559 fees from the node estimate, with the priority fee bumped by
+    /// `priority_fee_bump_percent` to get ahead of the queue.
+    Eip1559 { priority_fee_bump_percent: u128 },
+    /// Fixed EIP-1559 fees, regardless of network conditions.
+    Fixed {
+        max_fee_per_gas: u128,
+        max_priority_fee_per_gas: u128,
+    },
+}
+
+/// Upper bounds applied to every fee the runner chooses, including replacements.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FeeCaps {
+    /// Cap for `maxFeePerGas` (and for `gasPrice` under [`FeeStrategy::Legacy`]).
+    pub max_fee_per_gas: Option<u128>,
+    /// Cap for `maxPriorityFeePerGas`.
+    pub max_priority_fee_per_gas: Option<u128>,
+}
+
+/// When and how often pending transactions are re-broadcast with higher fees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StuckTxRetry {
+    /// Age after which an unmined transaction counts as stuck.
+    #[serde(rename = "stuck_after_ms", with = "crate::timeouts::millis")]
+    pub stuck_after: Duration,
+    /// Maximum number of replacements per nonce.
+    pub max_attempts: u32,
+}
+
+impl Default for StuckTxRetry {
+    fn default() -> Self {
+        Self {
+            stuck_after: Duration::from_secs(60),
+            max_attempts: 3,
+        }
+    }
+}
+
+/// ERC-7677 paymaster service that sponsors user operations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaymasterConfig {
+    /// Paymaster service JSON-RPC endpoint (`pm_getPaymasterStubData` / `pm_getPaymasterData`).
+    pub url: String,
+    /// Provider-specific context passed through unchanged, e.g. a sponsorship policy id.
+    pub context: Option<serde_json::Value>,
+}
+
+/// Where an ERC-4337 runner sends user operations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundlerConfig {
+    /// Bundler JSON-RPC endpoint (`eth_sendUserOperation` and friends).
+    pub bundler_url: String,
+    /// EntryPoint the bundler serves; must be a v0.7 deployment.
+    pub entry_point: Address,
+    /// Paymaster sponsoring gas; without one the account pays from its own deposit.
+    pub paymaster: Option<PaymasterConfig>,
+    /// How long to wait for a submitted operation to be included.
+    #[serde(rename = "receipt_timeout_ms", with = "crate::timeouts::millis")]
+    pub receipt_timeout: Duration,
+}
+
+impl BundlerConfig {
+    /// Bundler at `bundler_url` serving the canonical v0.7 EntryPoint, without a paymaster.
+    pub fn new(bundler_url: impl Into<String>) -> Self {
+        Self {
+            bundler_url: bundler_url.into(),
+            entry_point: ENTRY_POINT_V07,
+            paymaster: None,
+            receipt_timeout: Duration::from_secs(120),
+        }
+    }
+
+    /// Sponsor operations through the given paymaster service.
+    pub fn with_paymaster(mut self, paymaster: PaymasterConfig) -> Self {
+        self.paymaster = Some(paymaster);
+        self
+    }
+}
+
+/// What a relay runner signs and asks the relayer to execute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RelayMode {
+    /// `sponsoredCallERC2771`: the signer is the `user` of an EIP-712 `SponsoredCallERC2771`
+    /// verified by `forwarder`. Targets must accept ERC-2771 calls from that forwarder.
+    Erc2771 { forwarder: Address },
+    /// `sponsoredCall` of `execTransaction` on a single-owner `safe`, authorised by the
+    /// owner's signature over the Safe transaction hash. Batches go through MultiSend.
+    Safe { safe: Address },
+}
+
+/// Relayer service sponsoring the gas of a relay runner (Gelato relay API).
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelayConfig {
+    /// Relay API base URL, e.g. `https://api.gelato.digital`.
+    pub relay_url: String,
+    /// Sponsor key sent as `sponsorApiKey`.
+    pub api_key: String,
+    /// Payload the runner signs.
+    pub mode: RelayMode,
+    /// How long an ERC-2771 signature stays valid (`userDeadline`).
+    #[serde(rename = "signature_validity_ms", with = "crate::timeouts::millis")]
+    pub signature_validity: Duration,
+    /// Interval between task status polls.
+    #[serde(rename = "poll_interval_ms", with = "crate::timeouts::millis")]
+    pub poll_interval: Duration,
+    /// How long to wait for a task to reach a final state.
+    #[serde(rename = "task_timeout_ms", with = "crate::timeouts::millis")]
+    pub task_timeout: Duration,
+}
+
+impl RelayConfig {
+    /// Relayer at `relay_url` sponsoring with `api_key`.
+    pub fn new(relay_url: impl Into<String>, api_key: impl Into<String>, mode: RelayMode) -> Self {
+        Self {
+            relay_url: relay_url.into(),
+            api_key: api_key.into(),
+            mode,
+            signature_validity: Duration::from_secs(600),
+            poll_interval: Duration::from_secs(2),
+            task_timeout: Duration::from_secs(180),
+        }
+    }
+}
+
+impl fmt::Debug for RelayConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The sponsor key pays for gas; keep it out of logs.
+        f.debug_struct("RelayConfig")
+            .field("relay_url", &self.relay_url)
+            .field("api_key", &"..")
+            .field("mode", &self.mode)
+            .field("signature_validity", &self.signature_validity)
+            .field("poll_interval", &self.poll_interval)
+            .field("task_timeout", &self.task_timeout)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn endpoint_only_documents_keep_submission_defaults() {
+        let config: RunnerConfig = serde_json::from_value(json!({
+            "rpc_url": "https://rpc.aboutcircles.com/",
+            "chain_id": 100,
+            "default_gas_limit": null,
+            "default_gas_price": null
+        }))
+        .unwrap();
+        assert_eq!(config.fee_strategy, FeeStrategy::Provider);
+        assert_eq!(config.replacement_bump_percent, 10);
+        assert_eq!(config.stuck_retry, None);
+
+        let config = config
+            .with_stuck_retry(StuckTxRetry::default())
+            .with_relay(RelayConfig::new(
+                "https://api.gelato.digital",
+                "key",
+                RelayMode::Safe {
+                    safe: Address::repeat_byte(1),
+                },
+            ));
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(value["stuck_retry"]["stuck_after_ms"], json!(60_000));
+        assert_eq!(
+            serde_json::from_value::<RunnerConfig>(value).unwrap(),
+            config
+        );
+    }
+
+    #[test]
+    fn relay_config_debug_hides_api_key() {
+        let config = RelayConfig::new(
+            "https://api.gelato.digital",
+            "sponsor-secret",
+            RelayMode::Erc2771 {
+                forwarder: Address::repeat_byte(1),
+            },
+        );
+        let debug = format!("{config:?}");
+        assert!(debug.contains("api.gelato.digital"));
+        assert!(!debug.contains("sponsor-secret"));
+    }
 }
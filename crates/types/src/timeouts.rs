@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Network time limits shared by the Circles clients.
+///
+/// Carried by `CirclesConfig` (and the SDK's runner config) and honored by the RPC
+/// client, the profile service client, and the transfer builder. `None` waits
+/// indefinitely. Serialized as milliseconds (`connect_ms`, `request_ms`, ...); keys
+/// missing from a config document keep their defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Timeouts {
+    /// Establishing a TCP/TLS connection.
+    #[serde(rename = "connect_ms", with = "optional_millis")]
+    pub connect: Option<Duration>,
+    /// A single HTTP request, from sending to the last byte of the response.
+    #[serde(rename = "request_ms", with = "optional_millis")]
+    pub request: Option<Duration>,
+    /// Silence on a WebSocket subscription (no events and no heartbeats) before the
+    /// stream ends with a timeout error.
+    #[serde(rename = "ws_idle_ms", with = "optional_millis")]
+    pub ws_idle: Option<Duration>,
+    /// A `circlesV2_findPath` request, which routinely outlasts ordinary calls.
+    #[serde(rename = "pathfinder_ms", with = "optional_millis")]
+    pub pathfinder: Option<Duration>,
+    /// Waiting for the indexer to catch up with a submitted transaction, e.g. after
+    /// registering an avatar.
+    #[serde(rename = "indexing_ms", with = "optional_millis")]
+    pub indexing: Option<Duration>,
+}
+
+impl Timeouts {
+    /// No limits at all; the behavior of clients built without a config.
+    pub const fn none() -> Self {
+        Self {
+            connect: None,
+            request: None,
+            ws_idle: None,
+            pathfinder: None,
+            indexing: None,
+        }
+    }
+}
+
+impl Default for Timeouts {
+    /// 10s to connect, 30s per request, 60s per pathfinder query and for indexing.
+    /// Subscriptions may stay quiet indefinitely, since not every endpoint sends heartbeats.
+    fn default() -> Self {
+        Self {
+            connect: Some(Duration::from_secs(10)),
+            request: Some(Duration::from_secs(30)),
+            ws_idle: None,
+            pathfinder: Some(Duration::from_secs(60)),
+            indexing: Some(Duration::from_secs(60)),
+        }
+    }
+}
+
+mod optional_millis {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(duration) => serializer.serialize_some(&(duration.as_millis() as u64)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Duration>, D::Error> {
+        Ok(Option::<u64>::deserialize(deserializer)?.map(Duration::from_millis))
+    }
+}
+
+/// Serde for a required [`Duration`] as whole milliseconds, matching [`Timeouts`].
+pub(crate) mod millis {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(value: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(value.as_millis() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        Ok(Duration::from_millis(u64::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Timeouts;
+    use serde_json::json;
+    use std::time::Duration;
+
+    #[test]
+    fn serializes_as_milliseconds_and_fills_missing_keys() {
+        let timeouts = Timeouts::default();
+        let value = serde_json::to_value(timeouts).unwrap();
+        assert_eq!(value["request_ms"], json!(30_000));
+        assert_eq!(value["ws_idle_ms"], json!(null));
+        assert_eq!(value["indexing_ms"], json!(60_000));
+        assert_eq!(serde_json::from_value::<Timeouts>(value).unwrap(), timeouts);
+
+        let partial: Timeouts =
+            serde_json::from_value(json!({ "request_ms": 1500, "pathfinder_ms": null })).unwrap();
+        assert_eq!(partial.request, Some(Duration::from_millis(1500)));
+        assert_eq!(partial.pathfinder, None);
+        assert_eq!(partial.connect, Timeouts::default().connect);
+    }
+}
@@ -4,6 +4,9 @@ use serde::{Deserialize, Serialize};
 /// Token balance information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenBalance {
+    /// Holder of the balance, when the endpoint reports it (network snapshots do).
+    #[serde(default, alias = "holder")]
+    pub account: Option<Address>,
     pub token_address: Address,
     pub token_id: U256,
     pub token_owner: Address,
@@ -1,5 +1,6 @@
 use alloy_primitives::{Address, TxHash};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Trust relation information
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,4 +33,131 @@ pub struct AggregatedTrustRelation {
     pub relation: TrustRelationType,
     pub object_avatar: Address,
     pub timestamp: u64,
+    /// Earliest expiry among the merged directions, i.e. when this relation next changes.
+    #[serde(default)]
+    pub expiry_time: u64,
+}
+
+impl AggregatedTrustRelation {
+    /// Merge raw trust rows touching `avatar` into one relation per counterpart.
+    ///
+    /// Rows expired at `now` (unix seconds) and self-trust rows are dropped. A counterpart
+    /// with live rows in both directions is `MutuallyTrusts`; otherwise the direction decides
+    /// between `Trusts` and `TrustedBy`. `timestamp` is the latest row timestamp, and the
+    /// result is ordered by counterpart address.
+    pub fn aggregate(avatar: Address, relations: &[TrustRelation], now: u64) -> Vec<Self> {
+        #[derive(Default)]
+        struct Bucket {
+            outgoing: bool,
+            incoming: bool,
+            timestamp: u64,
+            expiry_time: u64,
+        }
+
+        let mut buckets: BTreeMap<Address, Bucket> = BTreeMap::new();
+        for rel in relations.iter().filter(|rel| rel.expiry_time > now) {
+            let (counterpart, outgoing) = if rel.truster == avatar {
+                (rel.trustee, true)
+            } else if rel.trustee == avatar {
+                (rel.truster, false)
+            } else {
+                continue;
+            };
+            if counterpart == avatar {
+                continue;
+            }
+            let bucket = buckets.entry(counterpart).or_insert_with(|| Bucket {
+                expiry_time: u64::MAX,
+                ..Bucket::default()
+            });
+            if outgoing {
+                bucket.outgoing = true;
+            } else {
+                bucket.incoming = true;
+            }
+            bucket.timestamp = bucket.timestamp.max(rel.timestamp);
+            bucket.expiry_time = bucket.expiry_time.min(rel.expiry_time);
+        }
+
+        buckets
+            .into_iter()
+            .map(|(counterpart, bucket)| Self {
+                subject_avatar: avatar,
+                relation: match (bucket.outgoing, bucket.incoming) {
+                    (true, true) => TrustRelationType::MutuallyTrusts,
+                    (true, false) => TrustRelationType::Trusts,
+                    _ => TrustRelationType::TrustedBy,
+                },
+                object_avatar: counterpart,
+                timestamp: bucket.timestamp,
+                expiry_time: bucket.expiry_time,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(truster: Address, trustee: Address, timestamp: u64, expiry_time: u64) -> TrustRelation {
+        TrustRelation {
+            block_number: 0,
+            timestamp,
+            transaction_index: 0,
+            log_index: 0,
+            transaction_hash: TxHash::ZERO,
+            truster,
+            trustee,
+            expiry_time,
+        }
+    }
+
+    #[test]
+    fn aggregate_merges_directions_and_drops_expired() {
+        let me = Address::repeat_byte(0x01);
+        let mutual = Address::repeat_byte(0x02);
+        let outgoing = Address::repeat_byte(0x03);
+        let incoming = Address::repeat_byte(0x04);
+        let expired = Address::repeat_byte(0x05);
+        let now = 1_000;
+
+        let rows = vec![
+            row(me, mutual, 10, 5_000),
+            row(mutual, me, 20, 2_000),
+            row(me, outgoing, 30, u64::MAX),
+            row(incoming, me, 40, u64::MAX),
+            row(me, expired, 50, now),
+            row(me, me, 60, u64::MAX),
+        ];
+        let aggregated = AggregatedTrustRelation::aggregate(me, &rows, now);
+
+        let summary: Vec<_> = aggregated
+            .iter()
+            .map(|rel| (rel.object_avatar, rel.relation.clone(), rel.timestamp))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (mutual, TrustRelationType::MutuallyTrusts, 20),
+                (outgoing, TrustRelationType::Trusts, 30),
+                (incoming, TrustRelationType::TrustedBy, 40),
+            ]
+        );
+        assert_eq!(aggregated[0].expiry_time, 2_000);
+        assert!(aggregated.iter().all(|rel| rel.subject_avatar == me));
+    }
+
+    #[test]
+    fn aggregated_relation_without_expiry_still_decodes() {
+        let rel: AggregatedTrustRelation = serde_json::from_value(serde_json::json!({
+            "subject_avatar": "0x0000000000000000000000000000000000000001",
+            "relation": "trustedBy",
+            "object_avatar": "0x0000000000000000000000000000000000000002",
+            "timestamp": 7
+        }))
+        .unwrap();
+        assert_eq!(rel.relation, TrustRelationType::TrustedBy);
+        assert_eq!(rel.expiry_time, 0);
+    }
 }
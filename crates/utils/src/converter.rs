@@ -1,3 +1,4 @@
+use crate::time::unix_now;
 use alloy_primitives::U256;
 use num_bigint::BigUint;
 use num_traits::{ToPrimitive, Zero};
@@ -77,6 +78,61 @@ fn v1_inflate_factor(period_idx: u64) -> BigUint {
     (BigUint::from(V1_ACCURACY) * num) / den
 }
 
+/// How a conversion rounds its final division.
+///
+/// The 1e36 day factors are shared by every mode; only the last step differs, so `Floor`
+/// reproduces the TS converter bit for bit while `Ceil` never undershoots.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Rounding {
+    /// Round towards zero (TS converter and on-chain behaviour).
+    #[default]
+    Floor,
+    /// Round away from zero.
+    Ceil,
+    /// Round to nearest, ties away from zero.
+    HalfUp,
+}
+
+fn div_rounded(numerator: BigUint, denominator: &BigUint, rounding: Rounding) -> BigUint {
+    let quotient = &numerator / denominator;
+    let remainder = numerator % denominator;
+    let round_up = match rounding {
+        Rounding::Floor => false,
+        Rounding::Ceil => !remainder.is_zero(),
+        Rounding::HalfUp => remainder * 2u32 >= *denominator,
+    };
+    if round_up { quotient + 1u32 } else { quotient }
+}
+
+/// Scale of the fixed-point factors in [`DayFactors`] (1e36).
+pub fn factor_scale() -> U256 {
+    big_to_u256(one_36())
+}
+
+/// Fixed-point conversion factors for one Circles day, scaled by [`factor_scale`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayFactors {
+    pub day: u64,
+    /// `GAMMA^day`: static → demurraged.
+    pub gamma: U256,
+    /// `BETA^day`: demurraged → static.
+    pub beta: U256,
+}
+
+/// The factors the converters use on Circles day `day`.
+pub fn day_factors(day: u64) -> DayFactors {
+    DayFactors {
+        day,
+        gamma: big_to_u256(pow36(&gamma_36(), day)),
+        beta: big_to_u256(pow36(&beta_36(), day)),
+    }
+}
+
+/// [`day_factors`] for every day in `days`, e.g. to cache a range of upcoming days.
+pub fn day_factor_table(days: std::ops::Range<u64>) -> Vec<DayFactors> {
+    days.map(day_factors).collect()
+}
+
 /// Demurraged atto-circles → static atto-circles for a given timestamp.
 ///
 /// If `now_unix_seconds` is `None`, uses the current time. Negative day indices
@@ -85,14 +141,22 @@ pub fn atto_circles_to_atto_static_circles(
     demurraged: U256,
     now_unix_seconds: Option<u64>,
 ) -> U256 {
-    let day = day_from_timestamp(now_unix_seconds.unwrap_or_else(now_ts));
+    atto_circles_to_atto_static_circles_rounded(demurraged, now_unix_seconds, Rounding::Floor)
+}
+
+/// [`atto_circles_to_atto_static_circles`] with an explicit [`Rounding`].
+pub fn atto_circles_to_atto_static_circles_rounded(
+    demurraged: U256,
+    now_unix_seconds: Option<u64>,
+    rounding: Rounding,
+) -> U256 {
+    let day = day_from_timestamp(now_unix_seconds.unwrap_or_else(unix_now));
     if day < 0 {
         return demurraged;
     }
     let factor = pow36(&beta_36(), day as u64);
     let dem = u256_to_big(demurraged);
-    let static_val = (dem * factor) / one_36();
-    big_to_u256(static_val)
+    big_to_u256(div_rounded(dem * factor, &one_36(), rounding))
 }
 
 /// Static atto-circles → demurraged atto-circles for a given timestamp.
@@ -103,14 +167,40 @@ pub fn atto_static_circles_to_atto_circles(
     static_circles: U256,
     now_unix_seconds: Option<u64>,
 ) -> U256 {
-    let day = day_from_timestamp(now_unix_seconds.unwrap_or_else(now_ts));
+    atto_static_circles_to_atto_circles_rounded(static_circles, now_unix_seconds, Rounding::Floor)
+}
+
+/// [`atto_static_circles_to_atto_circles`] with an explicit [`Rounding`].
+pub fn atto_static_circles_to_atto_circles_rounded(
+    static_circles: U256,
+    now_unix_seconds: Option<u64>,
+    rounding: Rounding,
+) -> U256 {
+    let day = day_from_timestamp(now_unix_seconds.unwrap_or_else(unix_now));
     if day < 0 {
         return static_circles;
     }
     let factor = pow36(&gamma_36(), day as u64);
     let infl = u256_to_big(static_circles);
-    let dem = (infl * factor) / one_36();
-    big_to_u256(dem)
+    big_to_u256(div_rounded(infl * factor, &one_36(), rounding))
+}
+
+/// Smallest static amount that converts back to at least `demurraged` atto-circles.
+///
+/// Use this when unwrapping inflationary wrappers to cover a path value: converting
+/// demurraged → static → demurraged with floor rounding can lose a few wei, whereas
+/// `atto_static_circles_to_atto_circles(static_circles_covering(x, t), t) >= x` always holds.
+pub fn static_circles_covering(demurraged: U256, now_unix_seconds: Option<u64>) -> U256 {
+    let day = day_from_timestamp(now_unix_seconds.unwrap_or_else(unix_now));
+    if day < 0 {
+        return demurraged;
+    }
+    let factor = pow36(&gamma_36(), day as u64);
+    big_to_u256(div_rounded(
+        u256_to_big(demurraged) * one_36(),
+        &factor,
+        Rounding::Ceil,
+    ))
 }
 
 /// Demurraged atto-circles → UI circles as `f64`.
@@ -129,29 +219,101 @@ pub fn atto_circles_to_circles(atto: U256) -> f64 {
 
 /// Demurraged atto-circles → inflationary CRC amount at the provided timestamp.
 pub fn atto_circles_to_atto_crc(demurraged: U256, block_timestamp_utc: u64) -> U256 {
-    let seconds_since_epoch = block_timestamp_utc.saturating_sub(INFLATION_DAY_ZERO_UNIX);
+    let numerator = u256_to_big(demurraged)
+        * BigUint::from(3u64)
+        * BigUint::from(V1_ACCURACY)
+        * BigUint::from(PERIOD_SEC);
+    big_to_u256(numerator / v1_rate(block_timestamp_utc))
+}
+
+/// v1 inflation factor interpolated linearly within the current period, scaled by
+/// `V1_ACCURACY * PERIOD_SEC`.
+fn v1_rate(timestamp: u64) -> BigUint {
+    let seconds_since_epoch = timestamp.saturating_sub(INFLATION_DAY_ZERO_UNIX);
     let period_idx = seconds_since_epoch / PERIOD_SEC;
-    let seconds_into_period = seconds_since_epoch % PERIOD_SEC;
+    let seconds_into = BigUint::from(seconds_since_epoch % PERIOD_SEC);
 
     let factor_cur = v1_inflate_factor(period_idx);
     let factor_next = v1_inflate_factor(period_idx + 1);
+    (&factor_cur * (BigUint::from(PERIOD_SEC) - &seconds_into)) + (&factor_next * &seconds_into)
+}
 
-    let period = BigUint::from(PERIOD_SEC);
-    let seconds_into = BigUint::from(seconds_into_period);
-    let r_p = (&factor_cur * (BigUint::from(PERIOD_SEC) - &seconds_into))
-        + (&factor_next * &seconds_into);
+/// Inflationary (v1) atto-CRC → atto time-circles at `timestamp`.
+///
+/// Time circles count hours of basic income, which is exactly what a demurraged v2
+/// circle is, so this is the inverse of [`atto_circles_to_atto_crc`].
+pub fn crc_to_time_circles(atto_crc: U256, timestamp: u64) -> U256 {
+    let denominator = BigUint::from(3u64) * BigUint::from(V1_ACCURACY) * BigUint::from(PERIOD_SEC);
+    big_to_u256((u256_to_big(atto_crc) * v1_rate(timestamp)) / denominator)
+}
 
-    let numerator =
-        u256_to_big(demurraged) * BigUint::from(3u64) * BigUint::from(V1_ACCURACY) * &period;
-    big_to_u256(numerator / r_p)
+/// Atto time-circles → inflationary (v1) atto-CRC at `timestamp`.
+pub fn time_circles_to_crc(atto_time_circles: U256, timestamp: u64) -> U256 {
+    atto_circles_to_atto_crc(atto_time_circles, timestamp)
 }
 
-fn now_ts() -> u64 {
-    use std::time::{SystemTime, UNIX_EPOCH};
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs()
+/// Demurraged balance held at `from_ts`, valued at `to_ts`.
+///
+/// Demurrage is applied per whole Circles day between the two timestamps; projecting
+/// backwards (`to_ts < from_ts`) undoes it.
+pub fn project_balance(balance: U256, from_ts: u64, to_ts: u64) -> U256 {
+    let days = day_from_timestamp(to_ts) - day_from_timestamp(from_ts);
+    let factor = if days >= 0 {
+        pow36(&gamma_36(), days as u64)
+    } else {
+        pow36(&beta_36(), days.unsigned_abs())
+    };
+    big_to_u256((u256_to_big(balance) * factor) / one_36())
+}
+
+/// Circles issued to a human per day (one per hour).
+pub const DAILY_ISSUANCE_CIRCLES: u64 = 24;
+/// Longest stretch of unclaimed issuance the hub lets a human mint at once (14 days).
+pub const MAX_MINT_WINDOW_SECS: u64 = 14 * SECONDS_PER_DAY;
+const SECONDS_PER_HOUR: u64 = 3_600;
+const ATTO_PER_CIRCLE: u64 = 1_000_000_000_000_000_000;
+
+/// Demurraged value at `at_ts` of the full personal issuance of Circles day `day`.
+///
+/// Days after `at_ts` are returned undiscounted.
+pub fn daily_issuance(day: i64, at_ts: u64) -> U256 {
+    issuance_value(DAILY_ISSUANCE_CIRCLES, day, day_from_timestamp(at_ts))
+}
+
+/// Estimated personal issuance mintable at `now_ts` when the last mint was at
+/// `last_mint_ts`.
+///
+/// Counts every full hour since the last mint (at most [`MAX_MINT_WINDOW_SECS`]) as one
+/// circle issued on that hour's day, then demurrages it to `now_ts`. The hub rounds hour
+/// boundaries slightly differently, so use `calculateIssuance` on-chain for the exact
+/// amount a mint transaction will produce.
+pub fn mintable_amount(last_mint_ts: u64, now_ts: u64) -> U256 {
+    let start = last_mint_ts.max(now_ts.saturating_sub(MAX_MINT_WINDOW_SECS));
+    let hours = now_ts.saturating_sub(start) / SECONDS_PER_HOUR;
+    let today = day_from_timestamp(now_ts);
+
+    let mut total = U256::ZERO;
+    let mut hour = 0;
+    while hour < hours {
+        let day = day_from_timestamp(start + hour * SECONDS_PER_HOUR);
+        let mut count = 0;
+        while hour < hours && day_from_timestamp(start + hour * SECONDS_PER_HOUR) == day {
+            count += 1;
+            hour += 1;
+        }
+        total += issuance_value(count, day, today);
+    }
+    total
+}
+
+fn issuance_value(circles: u64, issued_day: i64, at_day: i64) -> U256 {
+    let amount = U256::from(circles) * U256::from(ATTO_PER_CIRCLE);
+    let elapsed = at_day - issued_day;
+    if elapsed <= 0 {
+        return amount;
+    }
+    let factor = pow36(&gamma_36(), elapsed as u64);
+    big_to_u256((u256_to_big(amount) * factor) / one_36())
 }
 
 #[cfg(test)]
@@ -163,6 +325,116 @@ mod tests {
         assert_eq!(day_from_timestamp(INFLATION_DAY_ZERO_UNIX), 0);
     }
 
+    const ONE_CIRCLE: u64 = ATTO_PER_CIRCLE;
+    const DAY_1000: u64 = INFLATION_DAY_ZERO_UNIX + 1_000 * SECONDS_PER_DAY;
+
+    #[test]
+    fn project_balance_applies_seven_percent_yearly_demurrage() {
+        let balance = U256::from(100u64) * U256::from(ONE_CIRCLE);
+        let year_later = DAY_1000 + 365 * SECONDS_PER_DAY + SECONDS_PER_DAY / 4;
+        let projected = project_balance(balance, DAY_1000, year_later);
+        let circles = atto_circles_to_circles(projected);
+        assert!((circles - 93.0).abs() < 0.01, "got {circles}");
+
+        assert_eq!(
+            project_balance(balance, DAY_1000, DAY_1000 + 3_600),
+            balance
+        );
+        let back = project_balance(projected, year_later, DAY_1000);
+        assert!(balance - back < U256::from(1_000u64));
+    }
+
+    #[test]
+    fn daily_issuance_is_24_circles_on_the_day() {
+        let today = day_from_timestamp(DAY_1000);
+        assert_eq!(
+            daily_issuance(today, DAY_1000),
+            U256::from(24u64) * U256::from(ONE_CIRCLE)
+        );
+        assert!(daily_issuance(today - 1, DAY_1000) < daily_issuance(today, DAY_1000));
+    }
+
+    #[test]
+    fn mintable_amount_counts_full_hours_within_window() {
+        let now = DAY_1000 + 12 * 3_600;
+        let one_circle = U256::from(ONE_CIRCLE);
+
+        assert_eq!(mintable_amount(now - 1_800, now), U256::ZERO);
+        assert_eq!(
+            mintable_amount(now - 5 * 3_600, now),
+            one_circle * U256::from(5u64)
+        );
+
+        // 12 hours of today are undiscounted; the 12 from yesterday lose a day of demurrage.
+        let day = mintable_amount(now - SECONDS_PER_DAY, now);
+        assert!(day < one_circle * U256::from(24u64));
+        assert!(day > one_circle * U256::from(23u64));
+
+        let capped = mintable_amount(now - 14 * SECONDS_PER_DAY, now);
+        assert_eq!(mintable_amount(now - 30 * SECONDS_PER_DAY, now), capped);
+        assert_eq!(mintable_amount(0, now), capped);
+    }
+
+    #[test]
+    fn rounding_modes_bracket_floor() {
+        let amount = U256::from(123_456_789_012_345_678_901u128);
+        let floor =
+            atto_circles_to_atto_static_circles_rounded(amount, Some(DAY_1000), Rounding::Floor);
+        let ceil =
+            atto_circles_to_atto_static_circles_rounded(amount, Some(DAY_1000), Rounding::Ceil);
+        let half =
+            atto_circles_to_atto_static_circles_rounded(amount, Some(DAY_1000), Rounding::HalfUp);
+        assert_eq!(
+            floor,
+            atto_circles_to_atto_static_circles(amount, Some(DAY_1000))
+        );
+        assert_eq!(ceil, floor + U256::from(1u64));
+        assert!(half == floor || half == ceil);
+    }
+
+    #[test]
+    fn static_circles_covering_never_undershoots() {
+        for day in [1u64, 365, 1_000, 2_000] {
+            let ts = INFLATION_DAY_ZERO_UNIX + day * SECONDS_PER_DAY;
+            for needed in [
+                1u128,
+                999,
+                1_000_000_000_000_000_007,
+                96_000_000_000_000_000_000,
+            ] {
+                let needed = U256::from(needed);
+                let covering = static_circles_covering(needed, Some(ts));
+                assert!(atto_static_circles_to_atto_circles(covering, Some(ts)) >= needed);
+                if covering > U256::ZERO {
+                    let less = covering - U256::from(1u64);
+                    assert!(atto_static_circles_to_atto_circles(less, Some(ts)) < needed);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn day_factor_table_matches_converters() {
+        let table = day_factor_table(999..1_002);
+        assert_eq!(table.len(), 3);
+        assert_eq!(table[1], day_factors(1_000));
+        let amount = U256::from(ONE_CIRCLE);
+        assert_eq!(
+            amount * table[1].gamma / factor_scale(),
+            atto_static_circles_to_atto_circles(amount, Some(DAY_1000))
+        );
+        assert!(table[0].gamma > table[2].gamma && table[0].beta < table[2].beta);
+    }
+
+    #[test]
+    fn time_circles_round_trip_through_crc() {
+        let amount = U256::from(42u64) * U256::from(ONE_CIRCLE);
+        let crc = time_circles_to_crc(amount, DAY_1000);
+        assert_eq!(crc, atto_circles_to_atto_crc(amount, DAY_1000));
+        let back = crc_to_time_circles(crc, DAY_1000);
+        assert!(amount.abs_diff(back) < U256::from(1_000u64));
+    }
+
     #[derive(Debug, serde::Deserialize)]
     struct ConverterFixture {
         cases: Vec<ConverterCase>,
@@ -0,0 +1,263 @@
+//! Exact decimal formatting and parsing of atto-denominated Circles amounts.
+//!
+//! All math is integer-based on `U256`, so formatting never shows float artifacts and
+//! `parse_crc(&format_crc(x, 18)) == x` for every amount.
+
+use alloy_primitives::U256;
+use thiserror::Error;
+
+/// Decimal places of an atto amount (1 CRC = 1e18 atto).
+pub const CRC_DECIMALS: u8 = 18;
+
+/// Digit grouping and decimal mark used when formatting or parsing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    /// `1234567.89`, no grouping.
+    #[default]
+    Plain,
+    /// `1,234,567.89`
+    En,
+    /// `1.234.567,89`
+    De,
+    /// `1 234 567,89` (narrow no-break space, U+202F)
+    Fr,
+    /// `1'234'567.89`
+    Ch,
+}
+
+impl Locale {
+    /// Thousands separator, if the locale groups digits.
+    pub fn group_separator(self) -> Option<char> {
+        match self {
+            Locale::Plain => None,
+            Locale::En => Some(','),
+            Locale::De => Some('.'),
+            Locale::Fr => Some('\u{202f}'),
+            Locale::Ch => Some('\''),
+        }
+    }
+
+    pub fn decimal_mark(self) -> char {
+        match self {
+            Locale::De | Locale::Fr => ',',
+            Locale::Plain | Locale::En | Locale::Ch => '.',
+        }
+    }
+}
+
+/// How [`format_amount`] renders an atto amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayOptions {
+    /// Fraction digits to show (capped at 18). Extra digits are truncated, never rounded
+    /// up, so a displayed balance is never more than what is held.
+    pub decimals: u8,
+    pub locale: Locale,
+    /// Drop trailing zeros of the fraction (and the decimal mark if nothing is left).
+    pub trim_trailing_zeros: bool,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            decimals: 2,
+            locale: Locale::Plain,
+            trim_trailing_zeros: false,
+        }
+    }
+}
+
+/// Why a string could not be parsed as an amount.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseAmountError {
+    #[error("empty amount")]
+    Empty,
+    #[error("invalid character {0:?} in amount")]
+    InvalidCharacter(char),
+    #[error("amount has more than {CRC_DECIMALS} decimal places")]
+    TooManyDecimals,
+    #[error("amount does not fit in U256")]
+    Overflow,
+}
+
+/// Format atto-circles with `decimals` fraction digits and no grouping, e.g. `"1.50"`.
+pub fn format_crc(atto: U256, decimals: u8) -> String {
+    format_amount(
+        atto,
+        &DisplayOptions {
+            decimals,
+            ..DisplayOptions::default()
+        },
+    )
+}
+
+/// Format atto-circles with the grouping and decimal mark of `locale`.
+pub fn format_crc_localized(atto: U256, decimals: u8, locale: Locale) -> String {
+    format_amount(
+        atto,
+        &DisplayOptions {
+            decimals,
+            locale,
+            trim_trailing_zeros: false,
+        },
+    )
+}
+
+/// Format atto time circles with a `TC` suffix, e.g. `"24.00 TC"`.
+///
+/// Demurraged v2 circles already count time circles; convert v1 CRC first with
+/// [`crc_to_time_circles`](crate::converter::crc_to_time_circles).
+pub fn format_time_circles(atto_time_circles: U256, options: &DisplayOptions) -> String {
+    format!("{} TC", format_amount(atto_time_circles, options))
+}
+
+/// Format an atto amount according to `options`.
+pub fn format_amount(atto: U256, options: &DisplayOptions) -> String {
+    let unit = U256::from(10u64).pow(U256::from(CRC_DECIMALS));
+    let whole = group_digits(&(atto / unit).to_string(), options.locale.group_separator());
+
+    let fraction = format!("{:0>18}", (atto % unit).to_string());
+    let mut fraction = &fraction[..usize::from(options.decimals.min(CRC_DECIMALS))];
+    if options.trim_trailing_zeros {
+        fraction = fraction.trim_end_matches('0');
+    }
+    if fraction.is_empty() {
+        whole
+    } else {
+        format!("{whole}{}{fraction}", options.locale.decimal_mark())
+    }
+}
+
+/// Parse a decimal CRC string such as `"1.5"` into atto-circles.
+pub fn parse_crc(input: &str) -> Result<U256, ParseAmountError> {
+    parse_crc_localized(input, Locale::Plain)
+}
+
+/// Parse a CRC string written with `locale`'s grouping and decimal mark, e.g.
+/// `"1.234,5"` for [`Locale::De`]. Surrounding whitespace is ignored.
+pub fn parse_crc_localized(input: &str, locale: Locale) -> Result<U256, ParseAmountError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(ParseAmountError::Empty);
+    }
+    let group = locale.group_separator();
+    let mut whole = String::new();
+    let mut fraction = String::new();
+    let mut in_fraction = false;
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            if in_fraction {
+                fraction.push(c);
+            } else {
+                whole.push(c);
+            }
+        } else if c == locale.decimal_mark() && !in_fraction {
+            in_fraction = true;
+        } else if Some(c) == group && !in_fraction {
+            continue;
+        } else {
+            return Err(ParseAmountError::InvalidCharacter(c));
+        }
+    }
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(ParseAmountError::Empty);
+    }
+    if fraction.len() > usize::from(CRC_DECIMALS) {
+        return Err(ParseAmountError::TooManyDecimals);
+    }
+
+    let digits = format!("{whole}{fraction:0<18}");
+    U256::from_str_radix(&digits, 10).map_err(|_| ParseAmountError::Overflow)
+}
+
+fn group_digits(digits: &str, separator: Option<char>) -> String {
+    let Some(separator) = separator else {
+        return digits.to_string();
+    };
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(separator);
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crc(whole: u64, atto: u64) -> U256 {
+        U256::from(whole) * U256::from(1_000_000_000_000_000_000u64) + U256::from(atto)
+    }
+
+    #[test]
+    fn formats_exactly_and_truncates() {
+        assert_eq!(format_crc(crc(1, 500_000_000_000_000_000), 2), "1.50");
+        assert_eq!(format_crc(crc(0, 999_999_999_999_999_999), 2), "0.99");
+        assert_eq!(format_crc(crc(7, 0), 0), "7");
+        assert_eq!(format_crc(U256::from(1u64), 18), "0.000000000000000001");
+        assert_eq!(format_crc(U256::from(1u64), 30), "0.000000000000000001");
+    }
+
+    #[test]
+    fn groups_digits_per_locale() {
+        let amount = crc(1_234_567, 890_000_000_000_000_000);
+        assert_eq!(format_crc_localized(amount, 2, Locale::En), "1,234,567.89");
+        assert_eq!(format_crc_localized(amount, 2, Locale::De), "1.234.567,89");
+        assert_eq!(
+            format_crc_localized(amount, 2, Locale::Fr),
+            "1\u{202f}234\u{202f}567,89"
+        );
+        assert_eq!(format_crc_localized(amount, 2, Locale::Ch), "1'234'567.89");
+        assert_eq!(format_crc_localized(crc(999, 0), 0, Locale::En), "999");
+
+        let trimmed = DisplayOptions {
+            decimals: 6,
+            locale: Locale::En,
+            trim_trailing_zeros: true,
+        };
+        assert_eq!(format_amount(amount, &trimmed), "1,234,567.89");
+        assert_eq!(format_time_circles(crc(24, 0), &trimmed), "24 TC");
+    }
+
+    #[test]
+    fn parses_plain_and_localized_amounts() {
+        assert_eq!(parse_crc("1.5"), Ok(crc(1, 500_000_000_000_000_000)));
+        assert_eq!(parse_crc(" .25 "), Ok(crc(0, 250_000_000_000_000_000)));
+        assert_eq!(parse_crc("3."), Ok(crc(3, 0)));
+        assert_eq!(
+            parse_crc_localized("1.234,5", Locale::De),
+            Ok(crc(1_234, 500_000_000_000_000_000))
+        );
+        assert_eq!(parse_crc_localized("1,234", Locale::En), Ok(crc(1_234, 0)));
+
+        assert_eq!(parse_crc(""), Err(ParseAmountError::Empty));
+        assert_eq!(parse_crc("."), Err(ParseAmountError::Empty));
+        assert_eq!(
+            parse_crc("-1"),
+            Err(ParseAmountError::InvalidCharacter('-'))
+        );
+        assert_eq!(
+            parse_crc("1.2.3"),
+            Err(ParseAmountError::InvalidCharacter('.'))
+        );
+        assert_eq!(
+            parse_crc("0.0000000000000000001"),
+            Err(ParseAmountError::TooManyDecimals)
+        );
+        assert_eq!(parse_crc(&"9".repeat(80)), Err(ParseAmountError::Overflow));
+    }
+
+    #[test]
+    fn round_trips_full_precision() {
+        for amount in [
+            U256::ZERO,
+            U256::from(1u64),
+            crc(42, 123_456_789),
+            U256::MAX,
+        ] {
+            assert_eq!(parse_crc(&format_crc(amount, 18)), Ok(amount));
+        }
+    }
+}
@@ -1 +1,3 @@
 pub mod converter;
+pub mod display;
+pub mod time;
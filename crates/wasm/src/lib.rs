@@ -0,0 +1,262 @@
+//! WebAssembly bindings for Circles flow-matrix construction.
+//!
+//! Exposes the exact Rust implementations of `create_flow_matrix`, `pack_coordinates`,
+//! and `PathData::from_transfers` so web frontends do not need to re-implement matrix
+//! logic in TypeScript.
+//!
+//! The functions take and return JSON strings with camelCase keys and decimal amount
+//! strings, which keeps the JS side free of BigInt/Uint8Array plumbing. Build with the
+//! `wasm` feature (e.g. `wasm-pack build crates/wasm -- --features wasm`) to emit the
+//! `wasm-bindgen` exports; without it the crate is a plain Rust library, which is how
+//! the conversions are unit-tested.
+
+use alloy_primitives::aliases::U192;
+use alloy_primitives::{Address, Bytes};
+use circles_pathfinder::{FlowMatrix, PathData, PathfinderError};
+use circles_types::TransferStep;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Errors surfaced to JS callers as thrown `Error`s.
+#[derive(Debug, thiserror::Error)]
+pub enum BindingError {
+    /// Malformed JSON or unparsable address/amount.
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+    /// Flow matrix construction failed.
+    #[error(transparent)]
+    Pathfinder(#[from] PathfinderError),
+}
+
+#[cfg(feature = "wasm")]
+impl From<BindingError> for JsValue {
+    fn from(err: BindingError) -> Self {
+        JsError::new(&err.to_string()).into()
+    }
+}
+
+/// Transfer step as accepted from JS (`fromAddress`/`from`, `toAddress`/`to`, `tokenOwner`, `value`).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransferStepInput {
+    #[serde(alias = "from")]
+    from_address: Address,
+    #[serde(alias = "to")]
+    to_address: Address,
+    token_owner: Address,
+    value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct FlowEdgeOutput {
+    stream_sink_id: u16,
+    amount: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct StreamOutput {
+    source_coordinate: u16,
+    flow_edge_ids: Vec<u16>,
+    data: Bytes,
+}
+
+/// JSON shape returned for both flow matrices and `PathData`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct PathDataOutput {
+    flow_vertices: Vec<Address>,
+    flow_edges: Vec<FlowEdgeOutput>,
+    streams: Vec<StreamOutput>,
+    packed_coordinates: Bytes,
+    source_coordinate: String,
+}
+
+impl PathDataOutput {
+    fn new(
+        flow_vertices: Vec<Address>,
+        flow_edges: &[circles_pathfinder::FlowEdge],
+        streams: &[circles_pathfinder::Stream],
+        packed_coordinates: Vec<u8>,
+        source_coordinate: String,
+    ) -> Self {
+        Self {
+            flow_vertices,
+            flow_edges: flow_edges
+                .iter()
+                .map(|edge| FlowEdgeOutput {
+                    stream_sink_id: edge.streamSinkId,
+                    amount: edge.amount.to_string(),
+                })
+                .collect(),
+            streams: streams
+                .iter()
+                .map(|stream| StreamOutput {
+                    source_coordinate: stream.sourceCoordinate,
+                    flow_edge_ids: stream.flowEdgeIds.clone(),
+                    data: stream.data.clone(),
+                })
+                .collect(),
+            packed_coordinates: Bytes::from(packed_coordinates),
+            source_coordinate,
+        }
+    }
+}
+
+impl From<FlowMatrix> for PathDataOutput {
+    fn from(matrix: FlowMatrix) -> Self {
+        Self::new(
+            matrix.flow_vertices,
+            &matrix.flow_edges,
+            &matrix.streams,
+            matrix.packed_coordinates,
+            matrix.source_coordinate.to_string(),
+        )
+    }
+}
+
+impl From<PathData> for PathDataOutput {
+    fn from(data: PathData) -> Self {
+        Self::new(
+            data.flow_vertices,
+            &data.flow_edges,
+            &data.streams,
+            data.packed_coordinates,
+            data.source_coordinate.to_string(),
+        )
+    }
+}
+
+fn parse_address(value: &str, field: &str) -> Result<Address, BindingError> {
+    value
+        .parse()
+        .map_err(|e| BindingError::InvalidInput(format!("{field}: {e}")))
+}
+
+fn parse_amount(value: &str, field: &str) -> Result<U192, BindingError> {
+    value
+        .parse()
+        .map_err(|e| BindingError::InvalidInput(format!("{field}: {e}")))
+}
+
+fn parse_transfers(transfers_json: &str) -> Result<Vec<TransferStep>, BindingError> {
+    let inputs: Vec<TransferStepInput> = serde_json::from_str(transfers_json)
+        .map_err(|e| BindingError::InvalidInput(format!("transfers: {e}")))?;
+    inputs
+        .into_iter()
+        .map(|step| {
+            Ok(TransferStep {
+                from_address: step.from_address,
+                to_address: step.to_address,
+                token_owner: step.token_owner,
+                value: parse_amount(&step.value, "transfers.value")?,
+            })
+        })
+        .collect()
+}
+
+fn to_json(output: PathDataOutput) -> Result<String, BindingError> {
+    serde_json::to_string(&output).map_err(|e| BindingError::InvalidInput(e.to_string()))
+}
+
+/// Build the ABI-ready flow matrix for `transfers` (JSON array of transfer steps).
+///
+/// `value` is a decimal or `0x` hex amount in atto-circles. Returns the matrix as JSON.
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name = createFlowMatrix))]
+pub fn create_flow_matrix(
+    sender: &str,
+    receiver: &str,
+    value: &str,
+    transfers_json: &str,
+) -> Result<String, BindingError> {
+    let matrix = circles_pathfinder::create_flow_matrix(
+        parse_address(sender, "sender")?,
+        parse_address(receiver, "receiver")?,
+        parse_amount(value, "value")?,
+        &parse_transfers(transfers_json)?,
+    )?;
+    to_json(matrix.into())
+}
+
+/// Build `PathData` for `transfers`, capping the flow at what actually reaches `to`.
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name = pathDataFromTransfers))]
+pub fn path_data_from_transfers(
+    from: &str,
+    to: &str,
+    target_flow: &str,
+    transfers_json: &str,
+) -> Result<String, BindingError> {
+    let data = PathData::from_transfers(
+        &parse_transfers(transfers_json)?,
+        parse_address(from, "from")?,
+        parse_address(to, "to")?,
+        parse_amount(target_flow, "targetFlow")?,
+    )?;
+    to_json(data.into())
+}
+
+/// Pack `u16` coordinates into big-endian bytes (a `Uint8Array` in JS).
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name = packCoordinates))]
+pub fn pack_coordinates(coords: Vec<u16>) -> Vec<u8> {
+    circles_pathfinder::pack_coordinates(&coords)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SENDER: &str = "0x1000000000000000000000000000000000000001";
+    const RECEIVER: &str = "0x2000000000000000000000000000000000000002";
+
+    fn single_hop() -> String {
+        format!(
+            r#"[{{"from":"{SENDER}","to":"{RECEIVER}","tokenOwner":"{SENDER}","value":"1000"}}]"#
+        )
+    }
+
+    #[test]
+    fn pack_coordinates_matches_pathfinder() {
+        assert_eq!(
+            pack_coordinates(vec![0x1234, 0x5678]),
+            vec![0x12, 0x34, 0x56, 0x78]
+        );
+    }
+
+    #[test]
+    fn create_flow_matrix_matches_native_output() {
+        let json = create_flow_matrix(SENDER, RECEIVER, "1000", &single_hop()).expect("matrix");
+        let output: PathDataOutput = serde_json::from_str(&json).expect("decode");
+
+        let native = circles_pathfinder::create_flow_matrix(
+            SENDER.parse().unwrap(),
+            RECEIVER.parse().unwrap(),
+            U192::from(1000u64),
+            &parse_transfers(&single_hop()).unwrap(),
+        )
+        .expect("native");
+        assert_eq!(output, PathDataOutput::from(native));
+        assert_eq!(output.flow_edges[0].amount, "1000");
+    }
+
+    #[test]
+    fn path_data_caps_flow_at_available_amount() {
+        let json =
+            path_data_from_transfers(SENDER, RECEIVER, "5000", &single_hop()).expect("path data");
+        let output: PathDataOutput = serde_json::from_str(&json).expect("decode");
+        assert_eq!(output.flow_edges[0].amount, "1000");
+    }
+
+    #[test]
+    fn invalid_input_is_reported() {
+        assert!(matches!(
+            create_flow_matrix("nope", RECEIVER, "1", "[]"),
+            Err(BindingError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            create_flow_matrix(SENDER, RECEIVER, "1", "{"),
+            Err(BindingError::InvalidInput(_))
+        ));
+    }
+}